@@ -0,0 +1,222 @@
+use crate::addon::Addon;
+use crate::catalog::{Catalog, CatalogAddon, Source};
+use crate::config::Flavor;
+use crate::game::ClientBuild;
+
+use chrono::{DateTime, Duration, Utc};
+
+/// Amount of expansions the game has to have moved on since the interface of an
+/// addon, before it's considered abandoned.
+const EXPANSIONS_BEHIND: u32 = 2;
+
+/// Amount of alternatives suggested for an abandoned addon.
+const NUM_ALTERNATIVES: usize = 3;
+
+/// Returns true if `addon` looks abandoned, which is the case if its latest release
+/// is older than `months` and it's made for an interface at least two expansions
+/// behind `client_build`. A `months` of 0 turns this off.
+pub fn is_abandoned(addon: &Addon, client_build: Option<&ClientBuild>, months: u32) -> bool {
+    is_abandoned_at(addon, client_build, months, Utc::now())
+}
+
+fn is_abandoned_at(
+    addon: &Addon,
+    client_build: Option<&ClientBuild>,
+    months: u32,
+    now: DateTime<Utc>,
+) -> bool {
+    if months == 0 {
+        return false;
+    }
+
+    let (build, interface) = match (client_build, addon.interface()) {
+        (Some(build), Some(interface)) => (build, interface),
+        _ => return false,
+    };
+
+    let expansions_behind = (build.interface / 10000).saturating_sub(interface / 10000);
+
+    match latest_release(addon) {
+        Some(released) => {
+            expansions_behind >= EXPANSIONS_BEHIND && released < now - months_ago(months)
+        }
+        None => false,
+    }
+}
+
+/// Returns when the newest release of `addon` in any channel was released.
+fn latest_release(addon: &Addon) -> Option<DateTime<Utc>> {
+    addon
+        .remote_packages()
+        .values()
+        .filter_map(|p| p.date_time)
+        .max()
+}
+
+fn months_ago(months: u32) -> Duration {
+    Duration::days(i64::from(months) * 30)
+}
+
+/// Returns popular addons of the catalog which could replace `addon` for `flavor`,
+/// the most downloaded first.
+///
+/// They share a category with `addon`, had a release within `months` and aren't
+/// in `installed` yet. Empty if `addon` isn't in the catalog.
+pub fn alternatives<'a>(
+    addon: &Addon,
+    installed: &[Addon],
+    catalog: &'a Catalog,
+    flavor: Flavor,
+    months: u32,
+) -> Vec<&'a CatalogAddon> {
+    let categories = match catalog.addons.iter().find(|c| is_catalog_entry(addon, c)) {
+        Some(entry) => &entry.categories,
+        None => return vec![],
+    };
+
+    let released_after = Utc::now() - months_ago(months.max(1));
+
+    let mut alternatives = catalog
+        .addons
+        .iter()
+        .filter(|c| c.flavors.contains(&flavor.catalog_flavor()))
+        .filter(|c| {
+            c.categories
+                .iter()
+                .any(|category| categories.contains(category))
+        })
+        .filter(|c| c.date_released.map_or(false, |d| d > released_after))
+        .filter(|c| !installed.iter().any(|a| is_catalog_entry(a, c)))
+        .collect::<Vec<_>>();
+
+    alternatives.sort_by(|a, b| b.number_of_downloads.cmp(&a.number_of_downloads));
+    alternatives.truncate(NUM_ALTERNATIVES);
+
+    alternatives
+}
+
+/// Returns true if `addon` was installed from any of the sources of `catalog_addon`.
+fn is_catalog_entry(addon: &Addon, catalog_addon: &CatalogAddon) -> bool {
+    catalog_addon.entries().any(|entry| match entry.source {
+        Source::Curse => addon.curse_id() == Some(entry.id),
+        Source::Tukui => addon.tukui_id() == Some(&entry.id.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::addon::{AddonFolder, ReleaseChannel, RemotePackage};
+    use std::path::PathBuf;
+
+    fn addon(id: &str, curse_id: u32, interface: u32, released: DateTime<Utc>) -> Addon {
+        let mut folder = AddonFolder::new(
+            id.to_string(),
+            id.to_string(),
+            PathBuf::from(id),
+            None,
+            None,
+            None,
+            Default::default(),
+            vec![],
+        );
+        folder.interface = Some(interface);
+
+        let mut addon = Addon::empty(id);
+        addon.folders = vec![folder];
+        addon.repository_identifiers.curse = Some(curse_id);
+        addon.repository_metadata.remote_packages.insert(
+            ReleaseChannel::Stable,
+            RemotePackage {
+                version: "1.0.0".to_string(),
+                download_url: String::new(),
+                file_id: None,
+                date_time: Some(released),
+                fingerprint: None,
+                dependencies: vec![],
+            },
+        );
+        addon
+    }
+
+    fn catalog_addon(
+        id: u32,
+        category: &str,
+        downloads: u64,
+        released: DateTime<Utc>,
+    ) -> CatalogAddon {
+        CatalogAddon {
+            id,
+            website_url: String::new(),
+            date_released: Some(released),
+            name: id.to_string(),
+            categories: vec![category.to_string()],
+            summary: String::new(),
+            number_of_downloads: downloads,
+            source: Source::Curse,
+            flavors: vec![Flavor::Retail],
+            duplicates: vec![],
+        }
+    }
+
+    #[test]
+    fn test_is_abandoned() {
+        let now = Utc::now();
+        let client = ClientBuild {
+            version: "9.0.5.38134".to_string(),
+            interface: 90005,
+        };
+        let two_years_ago = now - Duration::days(730);
+
+        let abandoned = addon("Old", 1, 70300, two_years_ago);
+        assert!(is_abandoned_at(&abandoned, Some(&client), 18, now));
+        assert!(!is_abandoned_at(&abandoned, Some(&client), 0, now));
+        assert!(!is_abandoned_at(&abandoned, Some(&client), 36, now));
+        assert!(!is_abandoned_at(&abandoned, None, 18, now));
+
+        // Only one expansion behind.
+        let lagging = addon("Lagging", 2, 80300, two_years_ago);
+        assert!(!is_abandoned_at(&lagging, Some(&client), 18, now));
+
+        // Still released for, even if the interface wasn't bumped.
+        let maintained = addon("Maintained", 3, 70300, now - Duration::days(30));
+        assert!(!is_abandoned_at(&maintained, Some(&client), 18, now));
+    }
+
+    #[test]
+    fn test_alternatives() {
+        let now = Utc::now();
+        let old = addon("Old", 1, 70300, now - Duration::days(730));
+        let installed = addon("Installed", 2, 90005, now);
+
+        let catalog = Catalog {
+            addons: vec![
+                catalog_addon(1, "Boss Encounters", 10, now - Duration::days(730)),
+                catalog_addon(2, "Boss Encounters", 5000, now),
+                catalog_addon(3, "Boss Encounters", 100, now),
+                catalog_addon(4, "Boss Encounters", 1000, now),
+                catalog_addon(5, "Boss Encounters", 9000, now - Duration::days(900)),
+                catalog_addon(6, "Bags & Inventory", 90000, now),
+            ],
+        };
+
+        let ids = |alternatives: Vec<&CatalogAddon>| {
+            alternatives.iter().map(|c| c.id).collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            ids(alternatives(
+                &old,
+                &[old.clone(), installed.clone()],
+                &catalog,
+                Flavor::Retail,
+                18
+            )),
+            vec![4, 3]
+        );
+        assert!(alternatives(&old, &[], &catalog, Flavor::ClassicEra, 18).is_empty());
+
+        let unknown = addon("Unknown", 99, 70300, now);
+        assert!(alternatives(&unknown, &[], &catalog, Flavor::Retail, 18).is_empty());
+    }
+}
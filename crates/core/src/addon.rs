@@ -1,4 +1,11 @@
-use crate::{config::Flavor, curse_api, tukui_api, utility::strip_non_digits};
+use crate::{
+    config::Flavor,
+    curse_api,
+    fs::installed::InstallDates,
+    tukui_api,
+    utility::strip_non_digits,
+    version::{compare_versions, version_order},
+};
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
@@ -11,23 +18,34 @@ pub enum AddonVersionKey {
     Remote,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct RemotePackage {
     pub version: String,
     pub download_url: String,
     pub file_id: Option<i64>,
     pub date_time: Option<DateTime<Utc>>,
+    /// Fingerprint of the release file, if the repository exposes one.
+    pub fingerprint: Option<u32>,
+    /// Curse ids of the addons this release requires.
+    pub dependencies: Vec<u32>,
 }
 
 impl PartialOrd for RemotePackage {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.version.cmp(&other.version))
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for RemotePackage {
+    /// Packages are ordered by version, and by the rest of their fields so only
+    /// equal packages are ordered the same.
     fn cmp(&self, other: &Self) -> Ordering {
-        self.version.cmp(&other.version)
+        version_order(&self.version, &other.version)
+            .then_with(|| self.file_id.cmp(&other.file_id))
+            .then_with(|| self.date_time.cmp(&other.date_time))
+            .then_with(|| self.download_url.cmp(&other.download_url))
+            .then_with(|| self.fingerprint.cmp(&other.fingerprint))
+            .then_with(|| self.dependencies.cmp(&other.dependencies))
     }
 }
 
@@ -66,9 +84,12 @@ impl std::fmt::Display for ReleaseChannel {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum AddonState {
     Ignored,
+    Pinned,
+    /// A folder of the addon is a symlink, so Ajour doesn't touch it.
+    Symlinked,
     Unknown,
     Ajour(Option<String>),
     Downloading,
@@ -81,7 +102,18 @@ pub enum AddonState {
     Updatable,
 }
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq)]
+/// Details about an addon which are only fetched from its repository once the
+/// details of the addon are shown.
+pub struct AddonDetails {
+    pub summary: Option<String>,
+    pub authors: Vec<String>,
+    pub download_count: Option<u64>,
+    /// Urls of the screenshots of the addon.
+    pub screenshots: Vec<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 /// Struct which stores identifiers for the different repositories.
 pub struct RepositoryIdentifiers {
     pub wowi: Option<String>,
@@ -89,16 +121,27 @@ pub struct RepositoryIdentifiers {
     pub curse: Option<u32>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Repository {
     WowI,
     Tukui,
     Curse,
 }
 
+impl std::fmt::Display for Repository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Repository::WowI => "WowInterface",
+            Repository::Tukui => "Tukui",
+            Repository::Curse => "Curse",
+        };
+        write!(f, "{}", s)
+    }
+}
+
 /// Struct that stores the metadata parsed from an Addon folder's
 /// `.toc` file
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AddonFolder {
     /// ID is always the folder name
     pub id: String,
@@ -110,6 +153,13 @@ pub struct AddonFolder {
     pub repository_identifiers: RepositoryIdentifiers,
     pub dependencies: Vec<String>,
     pub fingerprint: Option<u32>,
+    /// True if the folder is a symlink or junction, eg. to a git checkout.
+    pub is_symlink: bool,
+    /// Size of the folder on disk in bytes.
+    pub size: u64,
+    /// Version of the game the folder was made for, as `## Interface` in its `.toc`.
+    #[serde(default)]
+    pub interface: Option<u32>,
 }
 
 impl PartialEq for AddonFolder {
@@ -154,12 +204,15 @@ impl AddonFolder {
             repository_identifiers,
             dependencies,
             fingerprint: None,
+            is_symlink: false,
+            size: 0,
+            interface: None,
         }
     }
 }
 
 /// Metadata from one of the repository APIs
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct RepositoryMetadata {
     // If these fields are not set, we will try to get the value
     // from the primary `AddonFolder` of the `Addon`
@@ -208,6 +261,7 @@ pub struct Addon {
     /// The `Repository` that this addon is linked against.
     pub active_repository: Option<Repository>,
     pub(crate) repository_metadata: RepositoryMetadata,
+    pub install_dates: InstallDates,
 
     // States for GUI
     #[cfg(feature = "gui")]
@@ -219,19 +273,37 @@ pub struct Addon {
     #[cfg(feature = "gui")]
     pub full_changelog_btn_state: iced_native::button::State,
     #[cfg(feature = "gui")]
+    pub changelog_btn_state: iced_native::button::State,
+    #[cfg(feature = "gui")]
+    pub screenshots_btn_state: iced_native::button::State,
+    #[cfg(feature = "gui")]
     pub update_btn_state: iced_native::button::State,
     #[cfg(feature = "gui")]
+    pub pause_download_btn_state: iced_native::button::State,
+    #[cfg(feature = "gui")]
     pub force_btn_state: iced_native::button::State,
     #[cfg(feature = "gui")]
+    pub rollback_btn_state: iced_native::button::State,
+    #[cfg(feature = "gui")]
     pub delete_btn_state: iced_native::button::State,
     #[cfg(feature = "gui")]
+    pub cancel_delete_btn_state: iced_native::button::State,
+    #[cfg(feature = "gui")]
     pub ignore_btn_state: iced_native::button::State,
     #[cfg(feature = "gui")]
     pub unignore_btn_state: iced_native::button::State,
     #[cfg(feature = "gui")]
+    pub pin_btn_state: iced_native::button::State,
+    #[cfg(feature = "gui")]
     pub website_btn_state: iced_native::button::State,
     #[cfg(feature = "gui")]
+    pub change_source_btn_state: iced_native::button::State,
+    #[cfg(feature = "gui")]
+    pub pick_preferred_source_state: iced_native::pick_list::State<Repository>,
+    #[cfg(feature = "gui")]
     pub pick_release_channel_state: iced_native::pick_list::State<ReleaseChannel>,
+    #[cfg(feature = "gui")]
+    pub pick_release_channel_row_state: iced_native::pick_list::State<ReleaseChannel>,
 }
 
 impl Addon {
@@ -244,6 +316,7 @@ impl Addon {
             state: AddonState::Ajour(None),
             repository_identifiers: Default::default(),
             repository_metadata: Default::default(),
+            install_dates: Default::default(),
 
             #[cfg(feature = "gui")]
             details_btn_state: Default::default(),
@@ -254,19 +327,37 @@ impl Addon {
             #[cfg(feature = "gui")]
             full_changelog_btn_state: Default::default(),
             #[cfg(feature = "gui")]
+            changelog_btn_state: Default::default(),
+            #[cfg(feature = "gui")]
+            screenshots_btn_state: Default::default(),
+            #[cfg(feature = "gui")]
             update_btn_state: Default::default(),
             #[cfg(feature = "gui")]
+            pause_download_btn_state: Default::default(),
+            #[cfg(feature = "gui")]
             force_btn_state: Default::default(),
             #[cfg(feature = "gui")]
+            rollback_btn_state: Default::default(),
+            #[cfg(feature = "gui")]
             delete_btn_state: Default::default(),
             #[cfg(feature = "gui")]
+            cancel_delete_btn_state: Default::default(),
+            #[cfg(feature = "gui")]
             ignore_btn_state: Default::default(),
             #[cfg(feature = "gui")]
             unignore_btn_state: Default::default(),
             #[cfg(feature = "gui")]
+            pin_btn_state: Default::default(),
+            #[cfg(feature = "gui")]
             website_btn_state: Default::default(),
             #[cfg(feature = "gui")]
+            change_source_btn_state: Default::default(),
+            #[cfg(feature = "gui")]
+            pick_preferred_source_state: Default::default(),
+            #[cfg(feature = "gui")]
             pick_release_channel_state: Default::default(),
+            #[cfg(feature = "gui")]
+            pick_release_channel_row_state: Default::default(),
         }
     }
 
@@ -297,6 +388,8 @@ impl Addon {
                 download_url,
                 date_time,
                 file_id: None,
+                fingerprint: None,
+                dependencies: vec![],
             };
 
             // Since Tukui does not support release channels, our default is 'stable'.
@@ -363,6 +456,13 @@ impl Addon {
                     download_url,
                     date_time,
                     file_id: Some(file.id),
+                    fingerprint: file.package_fingerprint,
+                    dependencies: file
+                        .dependencies
+                        .iter()
+                        .filter(|d| d.is_required())
+                        .map(|d| d.addon_id)
+                        .collect(),
                 };
 
                 let file_folders: Vec<AddonFolder> = addon_folders
@@ -466,6 +566,13 @@ impl Addon {
                     download_url,
                     date_time,
                     file_id: Some(file.id),
+                    fingerprint: file.package_fingerprint,
+                    dependencies: file
+                        .dependencies
+                        .iter()
+                        .filter(|d| d.is_required())
+                        .map(|d| d.addon_id)
+                        .collect(),
                 };
 
                 let file_folders: Vec<AddonFolder> = addon_folders
@@ -548,6 +655,14 @@ impl Addon {
         }
     }
 
+    /// Returns the interface version the addon was made for, eg. 90005 for 9.0.5.
+    pub fn interface(&self) -> Option<u32> {
+        self.folders
+            .iter()
+            .find(|f| f.id == self.primary_folder_id)
+            .and_then(|f| f.interface)
+    }
+
     /// Sets the version of the addon
     pub fn set_version(&mut self, version: String) {
         self.repository_metadata.version = Some(version);
@@ -575,6 +690,11 @@ impl Addon {
         meta_author.map_or(folder_author, Option::Some)
     }
 
+    /// Returns the size of the folders of the addon on disk in bytes.
+    pub fn size(&self) -> u64 {
+        self.folders.iter().map(|f| f.size).sum()
+    }
+
     /// Returns the game version of the addon.
     pub fn game_version(&self) -> Option<&str> {
         self.repository_metadata.game_version.as_deref()
@@ -695,10 +815,17 @@ impl Addon {
         remote_package.file_id > self.repository_metadata.file_id
     }
 
-    /// We strip both version for non digits, and then
-    /// checks if `remote_version` is a sub_slice of `local_version`.
+    /// Compares the versions by their scheme, eg. semver or a date.
+    ///
+    /// Versions which can't be compared, eg. when one is a placeholder like
+    /// `@project-version@`, are stripped of non digits, and then we check if
+    /// `remote_version` is a sub_slice of `local_version`.
     fn is_updatable_by_version_comparison(&self, remote_package: &RemotePackage) -> bool {
         if let Some(version) = self.version() {
+            if let Some(ordering) = compare_versions(version, &remote_package.version) {
+                return ordering == Ordering::Less;
+            }
+
             let srv = strip_non_digits(&remote_package.version);
             let slv = strip_non_digits(&version);
 
@@ -732,7 +859,12 @@ impl Addon {
             }
 
             if let (Some(base), Some(other)) = (base, other) {
-                other.file_id > base.file_id
+                // File ids are incremental, so a higher id is a newer file. Not all
+                // repositories provide one, so fall back to the release date.
+                match (base.file_id, other.file_id) {
+                    (Some(base_id), Some(other_id)) => other_id > base_id,
+                    _ => other.date_time > base.date_time,
+                }
             } else {
                 false
             }
@@ -769,6 +901,98 @@ impl Addon {
             }
         }
     }
+
+    /// Returns true if one of the folders of this addon has the given id.
+    /// Folder names are compared case insensitively, like the game does.
+    pub fn provides_folder(&self, folder_id: &str) -> bool {
+        self.folders
+            .iter()
+            .any(|f| f.id.eq_ignore_ascii_case(folder_id))
+    }
+
+    /// Returns the folders of this addon which none of the other `addons` provide,
+    /// so deleting them doesn't break the others, eg. a library bundled by several addons.
+    pub fn owned_folders(&self, addons: &[Addon]) -> Vec<&AddonFolder> {
+        self.folders
+            .iter()
+            .filter(|f| self.other_providers(&f.id, addons).next().is_none())
+            .collect()
+    }
+
+    /// Returns the folders of this addon which other `addons` provide as well, along
+    /// with those addons. This happens when several addons ship the same library as
+    /// a folder of its own, eg. `LibStub`.
+    pub fn shared_folders<'a>(
+        &'a self,
+        addons: &'a [Addon],
+    ) -> Vec<(&'a AddonFolder, Vec<&'a Addon>)> {
+        self.folders
+            .iter()
+            .map(|f| (f, self.other_providers(&f.id, addons).collect::<Vec<_>>()))
+            .filter(|(_, providers)| !providers.is_empty())
+            .collect()
+    }
+
+    /// Returns the `addons` other than this one which provide the folder.
+    fn other_providers<'a>(
+        &'a self,
+        folder_id: &'a str,
+        addons: &'a [Addon],
+    ) -> impl Iterator<Item = &'a Addon> {
+        addons
+            .iter()
+            .filter(move |a| a.primary_folder_id != self.primary_folder_id)
+            .filter(move |a| a.provides_folder(folder_id))
+    }
+
+    /// Returns the Curse ids of the addons required by the relevant release
+    /// which aren't part of `addons`.
+    pub fn missing_dependencies(&self, addons: &[Addon]) -> Vec<u32> {
+        let dependencies = match self.relevant_release_package() {
+            Some(package) => &package.dependencies,
+            None => return vec![],
+        };
+
+        dependencies
+            .iter()
+            .filter(|id| !addons.iter().any(|a| a.curse_id() == Some(**id)))
+            .copied()
+            .collect()
+    }
+
+    /// Returns the folders required in the `.toc` files of this addon which
+    /// aren't provided by the addon itself or any of `addons`.
+    ///
+    /// Dependencies on the addons shipped with the game are left out.
+    pub fn missing_folder_dependencies(&self, addons: &[Addon]) -> Vec<String> {
+        let mut missing: Vec<String> = self
+            .folders
+            .iter()
+            .flat_map(|f| f.dependencies.iter())
+            .filter(|d| !d.starts_with("Blizzard_"))
+            .filter(|d| !self.provides_folder(d) && !addons.iter().any(|a| a.provides_folder(d)))
+            .cloned()
+            .collect();
+
+        missing.sort();
+        missing.dedup();
+        missing
+    }
+
+    /// Returns the addons in `addons` which require one of the folders of this addon
+    /// in their `.toc` files.
+    pub fn dependents<'a>(&self, addons: &'a [Addon]) -> Vec<&'a Addon> {
+        addons
+            .iter()
+            .filter(|a| a.primary_folder_id != self.primary_folder_id)
+            .filter(|a| {
+                a.folders
+                    .iter()
+                    .flat_map(|f| f.dependencies.iter())
+                    .any(|d| self.provides_folder(d))
+            })
+            .collect()
+    }
 }
 
 impl PartialEq for Addon {
@@ -797,3 +1021,163 @@ impl Ord for Addon {
     }
 }
 impl Eq for Addon {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(version: &str, file_id: i64) -> RemotePackage {
+        RemotePackage {
+            version: version.to_string(),
+            download_url: String::new(),
+            file_id: Some(file_id),
+            date_time: None,
+            fingerprint: None,
+            dependencies: vec![],
+        }
+    }
+
+    fn addon_with_packages(packages: Vec<(ReleaseChannel, RemotePackage)>) -> Addon {
+        let mut addon = Addon::empty("Addon");
+        addon.repository_metadata.remote_packages = packages.into_iter().collect();
+        addon
+    }
+
+    #[test]
+    fn test_relevant_release_package() {
+        let mut addon = addon_with_packages(vec![
+            (ReleaseChannel::Stable, package("1.0.0", 1)),
+            (ReleaseChannel::Beta, package("1.1.0-beta", 2)),
+            (ReleaseChannel::Alpha, package("1.1.0-alpha", 3)),
+        ]);
+
+        addon.release_channel = ReleaseChannel::Stable;
+        assert_eq!(addon.relevant_release_package().unwrap().version, "1.0.0");
+
+        addon.release_channel = ReleaseChannel::Beta;
+        assert_eq!(
+            addon.relevant_release_package().unwrap().version,
+            "1.1.0-beta"
+        );
+
+        addon.release_channel = ReleaseChannel::Alpha;
+        assert_eq!(
+            addon.relevant_release_package().unwrap().version,
+            "1.1.0-alpha"
+        );
+    }
+
+    fn addon_with_folders(folders: Vec<(&str, Vec<&str>)>) -> Addon {
+        let mut addon = Addon::empty(folders[0].0);
+        addon.folders = folders
+            .into_iter()
+            .map(|(id, dependencies)| {
+                AddonFolder::new(
+                    id.to_string(),
+                    id.to_string(),
+                    PathBuf::from(id),
+                    None,
+                    None,
+                    None,
+                    Default::default(),
+                    dependencies.into_iter().map(str::to_string).collect(),
+                )
+            })
+            .collect();
+        addon
+    }
+
+    #[test]
+    fn test_dependencies() {
+        let library = addon_with_folders(vec![("Library", vec![])]);
+        let addon = addon_with_folders(vec![
+            ("Addon", vec!["library", "Blizzard_TalentUI"]),
+            ("Addon_Options", vec!["Addon", "Missing"]),
+        ]);
+        let addons = vec![library.clone(), addon.clone()];
+
+        assert_eq!(addon.missing_folder_dependencies(&addons), vec!["Missing"]);
+        assert_eq!(
+            library
+                .dependents(&addons)
+                .iter()
+                .map(|a| a.primary_folder_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Addon"]
+        );
+        assert!(addon.dependents(&addons).is_empty());
+    }
+
+    #[test]
+    fn test_owned_folders() {
+        let addon = addon_with_folders(vec![("Addon", vec![]), ("LibShared", vec![])]);
+        let other = addon_with_folders(vec![("Other", vec![]), ("libshared", vec![])]);
+        let addons = vec![addon.clone(), other];
+
+        assert_eq!(
+            addon
+                .owned_folders(&addons)
+                .iter()
+                .map(|f| f.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Addon"]
+        );
+        assert_eq!(addon.owned_folders(&[addon.clone()]).len(), 2);
+
+        let shared = addon.shared_folders(&addons);
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].0.id, "LibShared");
+        assert_eq!(shared[0].1[0].primary_folder_id, "Other");
+    }
+
+    #[test]
+    fn test_relevant_release_package_prefers_newer_stable() {
+        let mut addon = addon_with_packages(vec![
+            (ReleaseChannel::Stable, package("1.2.0", 4)),
+            (ReleaseChannel::Beta, package("1.1.0-beta", 2)),
+        ]);
+
+        // A stable release newer than the beta is picked on the beta channel,
+        // and a missing alpha release falls back to the newest package available.
+        addon.release_channel = ReleaseChannel::Beta;
+        assert_eq!(addon.relevant_release_package().unwrap().version, "1.2.0");
+
+        addon.release_channel = ReleaseChannel::Alpha;
+        assert_eq!(addon.relevant_release_package().unwrap().version, "1.2.0");
+    }
+
+    #[test]
+    fn test_sort_packages_of_mixed_schemes() {
+        let mut packages = vec![
+            package("2021.03.15", 1),
+            package("10.0", 2),
+            package("r800", 3),
+            package("@project-version@", 4),
+            package("9.0.5", 5),
+            package("1.2.0", 6),
+            package("1.2", 7),
+        ];
+        packages.sort();
+
+        let versions = packages
+            .iter()
+            .map(|p| p.version.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            versions,
+            vec![
+                "1.2",
+                "1.2.0",
+                "9.0.5",
+                "10.0",
+                "r800",
+                "2021.03.15",
+                "@project-version@"
+            ]
+        );
+
+        // Packages which aren't equal are never ordered the same.
+        assert_ne!(package("1.2", 1).cmp(&package("1.2.0", 1)), Ordering::Equal);
+        assert_ne!(package("1.2", 1).cmp(&package("1.2", 2)), Ordering::Equal);
+    }
+}
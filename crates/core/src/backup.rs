@@ -1,10 +1,91 @@
+use crate::config::{Backups, Config, Flavor};
 use crate::error::ClientError;
 use crate::fs::backup::{Backup, ZipBackup};
 use crate::Result;
 
 use chrono::{Local, NaiveDateTime};
 use std::convert::TryFrom;
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Folders of a flavor which can be backed up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BackupContent {
+    AddOns,
+    Wtf,
+    Screenshots,
+}
+
+impl BackupContent {
+    pub const ALL: [BackupContent; 3] = [
+        BackupContent::AddOns,
+        BackupContent::Wtf,
+        BackupContent::Screenshots,
+    ];
+
+    /// Folders which are backed up unless others are chosen.
+    pub const DEFAULT: [BackupContent; 2] = [BackupContent::AddOns, BackupContent::Wtf];
+
+    /// Directory of the folder for `flavor`, `None` if the path isn't set in the config.
+    fn directory(self, config: &Config, flavor: &Flavor) -> Option<PathBuf> {
+        match self {
+            BackupContent::AddOns => config.get_addon_directory_for_flavor(flavor),
+            BackupContent::Wtf => config.get_wtf_directory_for_flavor(flavor),
+            BackupContent::Screenshots => config
+                .wow
+                .directory
+                .as_ref()
+                .map(|dir| dir.join(flavor.folder_name()).join("Screenshots")),
+        }
+    }
+
+    /// Splits the path of an archive entry below the flavor folder into the
+    /// folder it belongs to and its path inside that folder.
+    fn split_entry<'a, 'b>(components: &'a [&'b str]) -> Option<(Self, &'a [&'b str])> {
+        match components {
+            [interface, addons, rest @ ..]
+                if interface.eq_ignore_ascii_case("interface")
+                    && addons.eq_ignore_ascii_case("addons") =>
+            {
+                Some((BackupContent::AddOns, rest))
+            }
+            [wtf, rest @ ..] if wtf.eq_ignore_ascii_case("wtf") => Some((BackupContent::Wtf, rest)),
+            [screenshots, rest @ ..] if screenshots.eq_ignore_ascii_case("screenshots") => {
+                Some((BackupContent::Screenshots, rest))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BackupContent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            BackupContent::AddOns => "addons",
+            BackupContent::Wtf => "wtf",
+            BackupContent::Screenshots => "screenshots",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for BackupContent {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        BackupContent::ALL
+            .iter()
+            .copied()
+            .find(|content| content.to_string() == s.trim().to_lowercase())
+            .ok_or_else(|| {
+                format!(
+                    "'{}' can't be backed up, expected addons, wtf or screenshots",
+                    s
+                )
+            })
+    }
+}
 
 /// Creates a .zip archive from the list of source folders and
 /// saves it to the dest folder.
@@ -31,6 +112,13 @@ pub async fn backup_folders(
 /// Finds the latest archive in the supplied backup folder and returns
 /// the datetime it was saved
 pub async fn latest_backup(backup_dir: PathBuf) -> Option<NaiveDateTime> {
+    list_backups(&backup_dir)
+        .first()
+        .map(|archive| archive.as_of)
+}
+
+/// Returns all archives in the supplied backup folder, newest first.
+pub fn list_backups(backup_dir: &Path) -> Vec<Archive> {
     let pattern = format!("{}/ajour_backup_[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9]_[0-9][0-9]-[0-9][0-9]-[0-9][0-9].zip", &backup_dir.display());
 
     let mut backups = vec![];
@@ -38,14 +126,196 @@ pub async fn latest_backup(backup_dir: PathBuf) -> Option<NaiveDateTime> {
     for entry in glob::glob(&pattern).unwrap() {
         if let Ok(path) = entry {
             if let Ok(archive) = Archive::try_from(path) {
-                backups.push(archive.as_of);
+                backups.push(archive);
+            }
+        }
+    }
+
+    backups.sort_by(|a, b| b.as_of.cmp(&a.as_of));
+    backups
+}
+
+/// Returns `true` if a scheduled backup should be made, based on when the latest
+/// backup was made.
+pub fn is_backup_due(backups: &Backups, last_backup: Option<NaiveDateTime>) -> bool {
+    match backups.schedule.interval() {
+        Some(interval) => match last_backup {
+            Some(last_backup) => Local::now().naive_local() - last_backup >= interval,
+            None => true,
+        },
+        None => false,
+    }
+}
+
+/// Removes the oldest archives in the supplied backup folder until the retention
+/// policy is met. The latest archive is always kept. Returns the removed archives.
+pub fn prune_backups(backup_dir: &Path, backups: &Backups) -> Result<Vec<PathBuf>> {
+    let mut archives = list_backups(backup_dir);
+    let mut removed = vec![];
+
+    let keep_count = backups.keep_count.unwrap_or(usize::MAX).max(1);
+    if archives.len() > keep_count {
+        for archive in archives.split_off(keep_count) {
+            std::fs::remove_file(&archive.path)?;
+            removed.push(archive.path);
+        }
+    }
+
+    if let Some(max_size_mb) = backups.max_size_mb {
+        let max_size = max_size_mb * 1024 * 1024;
+        let mut total_size: u64 = archives
+            .iter()
+            .filter_map(|a| a.path.metadata().ok())
+            .map(|m| m.len())
+            .sum();
+
+        while total_size > max_size && archives.len() > 1 {
+            let archive = archives.pop().unwrap();
+            let size = archive.path.metadata().map(|m| m.len()).unwrap_or_default();
+
+            std::fs::remove_file(&archive.path)?;
+            removed.push(archive.path);
+
+            total_size -= size.min(total_size);
+        }
+    }
+
+    for path in removed.iter() {
+        log::debug!("removed backup {:?} due to retention policy", path);
+    }
+
+    Ok(removed)
+}
+
+/// Returns the folders of `include`, eg. `Interface/AddOns` and `WTF`, of each flavor
+/// that exist on disk. Entries in the archive will be relative to the WoW directory.
+pub fn flavor_backup_folders(
+    config: &Config,
+    flavors: &[Flavor],
+    include: &[BackupContent],
+) -> Vec<BackupFolder> {
+    let mut src_folders = vec![];
+
+    let wow_dir = match config.wow.directory.as_ref() {
+        Some(dir) => dir,
+        None => return src_folders,
+    };
+
+    for flavor in flavors {
+        for content in include {
+            if let Some(dir) = content.directory(config, flavor) {
+                if dir.exists() {
+                    src_folders.push(BackupFolder::new(dir, wow_dir));
+                }
             }
         }
     }
 
-    // Apparently NaiveDateTime sorts in Desc order by default, no need to reverse
-    backups.sort();
-    backups.pop()
+    src_folders
+}
+
+/// Restores the folders of `include`, eg. `Interface/AddOns` and `WTF`, of `flavor`
+/// from an archive.
+///
+/// Before anything is replaced, a backup of the current folders is saved next to the
+/// archive so the restore can be undone. Only folders present in the archive are
+/// replaced. Returns when the safety backup was made, if there was anything to backup.
+pub async fn restore_backup(
+    archive_path: PathBuf,
+    flavor: Flavor,
+    include: Vec<BackupContent>,
+    config: Config,
+) -> Result<Option<NaiveDateTime>> {
+    let mut dirs = vec![];
+    for content in include.iter() {
+        let dir = content
+            .directory(&config, &flavor)
+            .ok_or(ClientError::NoWowDirectory)?;
+
+        dirs.push((*content, dir));
+    }
+
+    let mut zip_file = std::fs::File::open(&archive_path)?;
+    let mut archive = zip::ZipArchive::new(&mut zip_file)?;
+
+    // Entries are relative to the WoW directory, eg. `_retail_/WTF/Config.wtf`. Map
+    // the entries of this flavor to where they are restored to.
+    let folder_name = flavor.folder_name();
+    let mut entries = vec![];
+    let mut restored = vec![];
+
+    for i in 0..archive.len() {
+        let file = archive.by_index(i)?;
+
+        // Archives made on Windows may use `\` as separator.
+        let components = file
+            .name()
+            .split(|c| c == '/' || c == '\\')
+            .filter(|c| !c.is_empty() && *c != ".")
+            .collect::<Vec<_>>();
+
+        if components.iter().any(|c| *c == ".." || c.contains(':')) {
+            continue;
+        }
+
+        let (content, rest) = match components.as_slice() {
+            [flavor_folder, rest @ ..] if *flavor_folder == folder_name => {
+                match BackupContent::split_entry(rest) {
+                    Some(entry) => entry,
+                    None => continue,
+                }
+            }
+            _ => continue,
+        };
+
+        let path = match dirs.iter().find(|(c, _)| *c == content) {
+            Some((_, dir)) => dir.join(rest.iter().collect::<PathBuf>()),
+            None => continue,
+        };
+
+        if !restored.contains(&content) {
+            restored.push(content);
+        }
+
+        entries.push((i, path));
+    }
+
+    if entries.is_empty() {
+        return Err(ClientError::Custom(format!(
+            "Backup doesn't contain any {} folders",
+            flavor
+        )));
+    }
+
+    let src_folders = flavor_backup_folders(&config, &[flavor], &restored);
+    let snapshot = match archive_path.parent() {
+        Some(dest) if !src_folders.is_empty() => {
+            Some(backup_folders(src_folders, dest.to_path_buf()).await?)
+        }
+        _ => None,
+    };
+
+    for (content, dir) in dirs.iter() {
+        if restored.contains(content) && dir.exists() {
+            std::fs::remove_dir_all(dir)?;
+        }
+    }
+
+    for (i, path) in entries {
+        let mut file = archive.by_index(i)?;
+
+        if file.is_dir() {
+            std::fs::create_dir_all(&path)?;
+        } else {
+            if let Some(p) = path.parent() {
+                std::fs::create_dir_all(&p)?;
+            }
+            let mut outfile = std::fs::File::create(&path)?;
+            std::io::copy(&mut file, &mut outfile)?;
+        }
+    }
+
+    Ok(snapshot)
 }
 
 /// Specifies a folder that we want backed up. `prefix` will get stripped out of
@@ -66,7 +336,9 @@ impl BackupFolder {
 
 /// Metadata for our archive saved on the filesystem. Converted from a `PathBuf` with
 /// the correct naming convention
-struct Archive {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Archive {
+    pub path: PathBuf,
     pub as_of: NaiveDateTime,
 }
 
@@ -83,8 +355,59 @@ impl TryFrom<PathBuf> for Archive {
         );
 
         let as_of = NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H-%M-%S")
-            .map_err(|_| ClientError::Custom("Invalid archive file format".to_string()))?;
+            .map_err(|_| ClientError::ParseError("Invalid archive file format".to_string()))?;
+
+        Ok(Archive { path, as_of })
+    }
+}
+
+impl fmt::Display for Archive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_of.format("%Y-%m-%d %H:%M:%S"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BackupSchedule;
+
+    #[test]
+    fn test_is_backup_due() {
+        let mut backups = Backups::default();
+        let now = Local::now().naive_local();
+        let two_days_ago = now - chrono::Duration::days(2);
+
+        // Manual backups are never due.
+        assert!(!is_backup_due(&backups, None));
+        assert!(!is_backup_due(&backups, Some(two_days_ago)));
+
+        backups.schedule = BackupSchedule::Daily;
+        assert!(is_backup_due(&backups, None));
+        assert!(is_backup_due(&backups, Some(two_days_ago)));
+        assert!(!is_backup_due(&backups, Some(now)));
+
+        backups.schedule = BackupSchedule::Weekly;
+        assert!(!is_backup_due(&backups, Some(two_days_ago)));
+    }
+
+    #[test]
+    fn test_split_entry() {
+        assert_eq!(
+            BackupContent::split_entry(&["Interface", "AddOns", "Details", "Details.toc"]),
+            Some((BackupContent::AddOns, &["Details", "Details.toc"][..]))
+        );
+        assert_eq!(
+            BackupContent::split_entry(&["WTF", "Config.wtf"]),
+            Some((BackupContent::Wtf, &["Config.wtf"][..]))
+        );
+        assert_eq!(
+            BackupContent::split_entry(&["Screenshots", "WoWScrnShot.jpg"]),
+            Some((BackupContent::Screenshots, &["WoWScrnShot.jpg"][..]))
+        );
+        assert_eq!(BackupContent::split_entry(&["Interface", "Icons"]), None);
 
-        Ok(Archive { as_of })
+        assert_eq!("WTF".parse(), Ok(BackupContent::Wtf));
+        assert!("logs".parse::<BackupContent>().is_err());
     }
 }
@@ -49,7 +49,7 @@ fn main() {
     };
 
     task::block_on(async move {
-        let addons = read_addon_directory(collection, &path, Flavor::Classic)
+        let addons = read_addon_directory(collection, &path, Flavor::Classic, Default::default())
             .await
             .unwrap();
 
@@ -1,15 +1,21 @@
 use crate::config::Flavor;
-use crate::error::ClientError;
-use crate::network::request_async;
+use crate::network::{request_async, status_error};
 use crate::Result;
 use chrono::prelude::*;
 
 use isahc::{config::RedirectPolicy, prelude::*};
 use serde::Deserialize;
+use std::collections::HashMap;
 
-const CATALOG_URL: &str =
+const CURSE_CATALOG_URL: &str =
     "https://raw.githubusercontent.com/casperstorm/ajour-catalog/master/curse.json";
+const TUKUI_CATALOG_URL: &str =
+    "https://raw.githubusercontent.com/casperstorm/ajour-catalog/master/tukui.json";
 
+/// Fetches the catalogs of all sources, merged into one.
+///
+/// A catalog which can't be fetched is left out, so the others can still be
+/// browsed. Only fails if none of them could be fetched.
 pub async fn get_catalog() -> Result<Catalog> {
     let client = HttpClient::builder()
         .redirect_policy(RedirectPolicy::Follow)
@@ -17,16 +23,34 @@ pub async fn get_catalog() -> Result<Catalog> {
         .build()
         .unwrap();
 
-    let mut resp = request_async(&client, CATALOG_URL, vec![], Some(30)).await?;
+    let mut addons = vec![];
+    let mut error = None;
+    for url in [CURSE_CATALOG_URL, TUKUI_CATALOG_URL].iter() {
+        match fetch_catalog(&client, url).await {
+            Ok(catalog) => addons.extend(catalog.addons),
+            Err(e) => {
+                log::error!("failed to fetch catalog {}: {}", url, e);
+                error = Some(e);
+            }
+        }
+    }
+
+    match error {
+        Some(e) if addons.is_empty() => Err(e),
+        _ => Ok(Catalog {
+            addons: merge_duplicates(addons),
+        }),
+    }
+}
+
+async fn fetch_catalog(client: &HttpClient, url: &str) -> Result<Catalog> {
+    let mut resp = request_async(client, url, vec![], Some(30)).await?;
 
     if resp.status().is_success() {
         let catalog = resp.json()?;
         Ok(catalog)
     } else {
-        Err(ClientError::Custom(format!(
-            "Couldn't fetch catalog: {}",
-            resp.text()?
-        )))
+        Err(status_error("GitHub", "fetch the catalog", &mut resp))
     }
 }
 
@@ -67,6 +91,87 @@ pub struct CatalogAddon {
     pub number_of_downloads: u64,
     pub source: Source,
     pub flavors: Vec<Flavor>,
+    /// The same addon on other sources, merged into this entry.
+    #[serde(skip)]
+    pub duplicates: Vec<CatalogAddon>,
+}
+
+impl CatalogAddon {
+    /// Returns this entry followed by its duplicates.
+    pub fn entries(&self) -> impl Iterator<Item = &CatalogAddon> {
+        std::iter::once(self).chain(self.duplicates.iter())
+    }
+
+    /// Returns the sources the addon is on.
+    pub fn sources(&self) -> Vec<Source> {
+        self.entries().map(|e| e.source).collect()
+    }
+
+    /// Returns the addon as it is on `source`, with the other sources as its
+    /// duplicates.
+    pub fn with_source(&self, source: Source) -> Option<CatalogAddon> {
+        let mut entries: Vec<_> = self
+            .entries()
+            .map(|e| CatalogAddon {
+                duplicates: vec![],
+                ..e.clone()
+            })
+            .collect();
+
+        let position = entries.iter().position(|e| e.source == source)?;
+        let mut addon = entries.remove(position);
+        addon.duplicates = entries;
+
+        Some(addon)
+    }
+
+    /// Returns `true` if `other` is likely the same addon on another source.
+    ///
+    /// The catalogs don't link entries across sources, so entries with the same
+    /// name, ignoring case and punctuation, and a flavor in common are assumed to be.
+    fn is_duplicate(&self, other: &CatalogAddon) -> bool {
+        let name = normalized_name(&self.name);
+
+        !name.is_empty()
+            && name == normalized_name(&other.name)
+            && self.entries().all(|e| e.source != other.source)
+            && self.flavors.iter().any(|f| other.flavors.contains(f))
+    }
+}
+
+fn normalized_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Merges the entries of an addon on several sources into the most downloaded one.
+fn merge_duplicates(mut addons: Vec<CatalogAddon>) -> Vec<CatalogAddon> {
+    addons.sort_by(|a, b| b.number_of_downloads.cmp(&a.number_of_downloads));
+
+    let mut merged: Vec<CatalogAddon> = vec![];
+    // Indices into `merged` by normalized name, so not every entry is compared.
+    let mut by_name: HashMap<String, Vec<usize>> = HashMap::new();
+
+    for addon in addons {
+        let indices = by_name.entry(normalized_name(&addon.name)).or_default();
+
+        let duplicate_of = indices
+            .iter()
+            .copied()
+            .find(|i| merged[*i].is_duplicate(&addon));
+
+        match duplicate_of {
+            Some(i) => merged[i].duplicates.push(addon),
+            None => {
+                indices.push(merged.len());
+                merged.push(addon);
+            }
+        }
+    }
+
+    merged
 }
 
 mod date_parser {
@@ -102,6 +207,44 @@ mod date_parser {
 mod tests {
     use super::*;
 
+    fn catalog_addon(id: u32, name: &str, source: Source, downloads: u64) -> CatalogAddon {
+        CatalogAddon {
+            id,
+            website_url: String::new(),
+            date_released: None,
+            name: name.to_string(),
+            categories: vec![],
+            summary: String::new(),
+            number_of_downloads: downloads,
+            source,
+            flavors: vec![Flavor::Retail],
+            duplicates: vec![],
+        }
+    }
+
+    #[test]
+    fn test_merge_duplicates() {
+        let addons = vec![
+            catalog_addon(1, "ElvUI", Source::Tukui, 100),
+            catalog_addon(2, "Elv UI", Source::Curse, 500),
+            catalog_addon(3, "ElvUI", Source::Curse, 10),
+            catalog_addon(4, "Details!", Source::Curse, 50),
+        ];
+
+        let merged = merge_duplicates(addons);
+        assert_eq!(merged.len(), 3);
+
+        // The second Curse entry can't be a duplicate of the merged one.
+        let elvui = &merged[0];
+        assert_eq!(elvui.id, 2);
+        assert_eq!(elvui.sources(), vec![Source::Curse, Source::Tukui]);
+
+        let tukui = elvui.with_source(Source::Tukui).unwrap();
+        assert_eq!(tukui.id, 1);
+        assert_eq!(tukui.sources(), vec![Source::Tukui, Source::Curse]);
+        assert!(tukui.duplicates[0].duplicates.is_empty());
+    }
+
     #[test]
     fn test_catalog_download() {
         async_std::task::block_on(async {
@@ -1,6 +1,7 @@
 use super::Flavor;
-use crate::addon::ReleaseChannel;
+use crate::addon::{Addon, ReleaseChannel, Repository};
 use de::de_ignored;
+use glob::{MatchOptions, Pattern};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -10,19 +11,149 @@ pub struct Addons {
     #[serde(default, deserialize_with = "de_ignored")]
     pub ignored: HashMap<Flavor, Vec<String>>,
 
+    /// Glob patterns, eg. `MyDevAddon*`. Addons with a folder matching any of them
+    /// are ignored for all flavors.
+    #[serde(default)]
+    pub ignore_patterns: Vec<String>,
+
     #[serde(default)]
     pub release_channels: HashMap<Flavor, HashMap<String, ReleaseChannel>>,
+
+    /// Remote versions the user has rolled back from, which shouldn't be
+    /// offered as an update again.
+    #[serde(default)]
+    pub rolled_back: HashMap<Flavor, HashMap<String, String>>,
+
+    /// Addons kept on their installed version, which aren't updated by "Update All".
+    #[serde(default)]
+    pub pinned: HashMap<Flavor, Vec<String>>,
+
+    /// Order repositories are tried in for addons which are on several of them.
+    /// Tukui is tried before Curse when empty.
+    #[serde(default)]
+    pub source_priority: Vec<Repository>,
+
+    /// Repository tried first for an addon, instead of going by `source_priority`.
+    #[serde(default)]
+    pub preferred_sources: HashMap<Flavor, HashMap<String, Repository>>,
+
+    /// Months without a release after which an addon made for an interface two
+    /// or more expansions behind the game is flagged as abandoned. 0 turns it off.
+    #[serde(default)]
+    pub abandoned_after_months: Option<u32>,
 }
 
 impl Default for Addons {
     fn default() -> Self {
         Addons {
             ignored: HashMap::new(),
+            ignore_patterns: vec![],
             release_channels: HashMap::new(),
+            rolled_back: HashMap::new(),
+            pinned: HashMap::new(),
+            source_priority: vec![],
+            preferred_sources: HashMap::new(),
+            abandoned_after_months: None,
         }
     }
 }
 
+impl Addons {
+    /// Months an addon is flagged as abandoned after, if not set.
+    pub const DEFAULT_ABANDONED_AFTER_MONTHS: u32 = 18;
+
+    /// Returns the months without a release after which an addon is flagged as
+    /// abandoned, if it's made for an old interface.
+    pub fn abandoned_after_months(&self) -> u32 {
+        self.abandoned_after_months
+            .unwrap_or(Self::DEFAULT_ABANDONED_AFTER_MONTHS)
+    }
+
+    /// Returns `true` if the user has rolled back the addon from the given remote version.
+    pub fn is_rolled_back_from(&self, flavor: &Flavor, addon_id: &str, version: &str) -> bool {
+        self.rolled_back
+            .get(flavor)
+            .and_then(|rolled_back| rolled_back.get(addon_id))
+            .map(|v| v == version)
+            .unwrap_or(false)
+    }
+
+    /// Returns `true` if a folder of the addon matches one of the ignore patterns.
+    /// Folder names are matched case insensitively.
+    pub fn is_ignored_by_pattern(&self, addon: &Addon) -> bool {
+        let options = MatchOptions {
+            case_sensitive: false,
+            ..Default::default()
+        };
+
+        let patterns = self
+            .ignore_patterns
+            .iter()
+            .filter_map(|pattern| match Pattern::new(pattern) {
+                Ok(pattern) => Some(pattern),
+                Err(e) => {
+                    log::warn!("invalid ignore pattern {}: {}", pattern, e);
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut folder_ids = addon
+            .folders
+            .iter()
+            .map(|f| f.id.as_str())
+            .chain(std::iter::once(addon.primary_folder_id.as_str()));
+
+        folder_ids.any(|id| patterns.iter().any(|p| p.matches_with(id, options)))
+    }
+
+    /// Returns `true` if the user has pinned the addon to its installed version.
+    pub fn is_pinned(&self, flavor: &Flavor, addon_id: &str) -> bool {
+        self.pinned
+            .get(flavor)
+            .map(|pinned| pinned.iter().any(|id| id == addon_id))
+            .unwrap_or(false)
+    }
+
+    /// Returns the order repositories are tried in for the addons of `flavor`.
+    pub fn source_priority(&self, flavor: &Flavor) -> SourcePriority {
+        SourcePriority {
+            order: self.source_priority.clone(),
+            preferred: self
+                .preferred_sources
+                .get(flavor)
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Order repositories are tried in for the addons of a flavor, while parsing them.
+#[derive(Debug, Clone, Default)]
+pub struct SourcePriority {
+    order: Vec<Repository>,
+    preferred: HashMap<String, Repository>,
+}
+
+impl SourcePriority {
+    /// Returns the repository tried first for the addon with the given folders.
+    ///
+    /// Only Curse and Tukui can identify an addon from its folders, so any other
+    /// repository in the order is skipped.
+    pub fn preferred<'a>(&self, folder_ids: impl IntoIterator<Item = &'a str>) -> Repository {
+        folder_ids
+            .into_iter()
+            .find_map(|id| self.preferred.get(id).copied())
+            .or_else(|| {
+                self.order
+                    .iter()
+                    .copied()
+                    .find(|r| matches!(r, Repository::Curse | Repository::Tukui))
+            })
+            .unwrap_or(Repository::Tukui)
+    }
+}
+
 mod de {
     use crate::config::Flavor;
     use serde::{
@@ -73,3 +204,49 @@ mod de {
         deserializer.deserialize_any(DeIgnored)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_ignored_by_pattern() {
+        let mut addons = Addons::default();
+        addons.ignore_patterns = vec!["MyDevAddon*".to_string(), "[".to_string()];
+
+        assert!(addons.is_ignored_by_pattern(&Addon::empty("MyDevAddon")));
+        assert!(addons.is_ignored_by_pattern(&Addon::empty("mydevaddon_options")));
+        assert!(!addons.is_ignored_by_pattern(&Addon::empty("Details")));
+    }
+
+    #[test]
+    fn test_source_priority() {
+        let mut addons = Addons::default();
+        assert_eq!(
+            addons
+                .source_priority(&Flavor::Retail)
+                .preferred(vec!["ElvUI"]),
+            Repository::Tukui
+        );
+
+        addons.source_priority = vec![Repository::WowI, Repository::Curse, Repository::Tukui];
+        addons
+            .preferred_sources
+            .entry(Flavor::Retail)
+            .or_default()
+            .insert("ElvUI".to_string(), Repository::Tukui);
+
+        let priority = addons.source_priority(&Flavor::Retail);
+        assert_eq!(priority.preferred(vec!["Details"]), Repository::Curse);
+        assert_eq!(
+            priority.preferred(vec!["ElvUI_Options", "ElvUI"]),
+            Repository::Tukui
+        );
+        assert_eq!(
+            addons
+                .source_priority(&Flavor::Classic)
+                .preferred(vec!["ElvUI"]),
+            Repository::Curse
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+/// Struct for settings related to backups.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Backups {
+    #[serde(default)]
+    pub schedule: BackupSchedule,
+
+    /// Creates a backup before updating all addons.
+    #[serde(default)]
+    pub before_update_all: bool,
+
+    /// Maximum amount of backups to keep.
+    #[serde(default)]
+    pub keep_count: Option<usize>,
+
+    /// Maximum combined size of the backups to keep, in megabytes.
+    #[serde(default)]
+    pub max_size_mb: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Hash)]
+pub enum BackupSchedule {
+    Manual,
+    Daily,
+    Weekly,
+}
+
+impl BackupSchedule {
+    pub const ALL: [BackupSchedule; 3] = [
+        BackupSchedule::Manual,
+        BackupSchedule::Daily,
+        BackupSchedule::Weekly,
+    ];
+
+    /// Returns the time between scheduled backups, or `None` if backups are
+    /// only made manually.
+    pub fn interval(self) -> Option<chrono::Duration> {
+        match self {
+            BackupSchedule::Manual => None,
+            BackupSchedule::Daily => Some(chrono::Duration::days(1)),
+            BackupSchedule::Weekly => Some(chrono::Duration::weeks(1)),
+        }
+    }
+}
+
+impl Default for BackupSchedule {
+    fn default() -> BackupSchedule {
+        BackupSchedule::Manual
+    }
+}
+
+impl std::fmt::Display for BackupSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            BackupSchedule::Manual => "Manual",
+            BackupSchedule::Daily => "Daily",
+            BackupSchedule::Weekly => "Weekly",
+        };
+        write!(f, "{}", s)
+    }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// Commands run with the system shell on update events, eg. to back up WTF or
+/// post to a chat. Details of the event are passed as JSON on stdin, and as
+/// `AJOUR_*` environment variables.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Hooks {
+    /// Run once "Update All" found updates, before any addon is updated. The
+    /// command line waits for it to exit.
+    #[serde(default)]
+    pub update_all_started: Option<String>,
+
+    /// Run for every addon which was updated.
+    #[serde(default)]
+    pub addon_updated: Option<String>,
+
+    /// Run once every addon of "Update All" was updated or failed.
+    #[serde(default)]
+    pub update_all_finished: Option<String>,
+}
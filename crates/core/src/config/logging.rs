@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Struct for settings related to the log.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Logging {
+    /// Least severe level which is logged, everything if not set.
+    #[serde(default)]
+    pub level: Option<LogLevel>,
+
+    /// Amount of logs of earlier runs to keep, besides the current one.
+    #[serde(default)]
+    pub keep_count: Option<usize>,
+
+    /// Size of the log in megabytes after which it's rotated.
+    #[serde(default)]
+    pub max_size_mb: Option<u64>,
+}
+
+impl Logging {
+    pub const DEFAULT_KEEP_COUNT: usize = 3;
+    pub const DEFAULT_MAX_SIZE_MB: u64 = 5;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 6] = [
+        LogLevel::Off,
+        LogLevel::Error,
+        LogLevel::Warn,
+        LogLevel::Info,
+        LogLevel::Debug,
+        LogLevel::Trace,
+    ];
+
+    pub fn level_filter(self) -> log::LevelFilter {
+        match self {
+            LogLevel::Off => log::LevelFilter::Off,
+            LogLevel::Error => log::LevelFilter::Error,
+            LogLevel::Warn => log::LevelFilter::Warn,
+            LogLevel::Info => log::LevelFilter::Info,
+            LogLevel::Debug => log::LevelFilter::Debug,
+            LogLevel::Trace => log::LevelFilter::Trace,
+        }
+    }
+}
+
+impl Default for LogLevel {
+    fn default() -> LogLevel {
+        LogLevel::Trace
+    }
+}
+
+impl std::fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            LogLevel::Off => "off",
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LogLevel::ALL
+            .iter()
+            .copied()
+            .find(|level| level.to_string() == s.to_lowercase())
+            .ok_or_else(|| {
+                format!(
+                    "'{}' is not a valid log level (off, error, warn, info, debug, trace)",
+                    s
+                )
+            })
+    }
+}
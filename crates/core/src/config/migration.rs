@@ -0,0 +1,177 @@
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+
+/// Version of the config schema written by this release of Ajour.
+pub const CONFIG_VERSION: u32 = 1;
+
+/// Steps migrating the config from the version of their index to the next one.
+/// A step is added, and `CONFIG_VERSION` raised, whenever a change to the config
+/// would otherwise drop or misread settings of the previous version.
+const MIGRATIONS: [fn(&mut Mapping); CONFIG_VERSION as usize] = [migrate_flavor_settings];
+
+/// Version of the schema of the config. Configs from before it was versioned are
+/// version 0.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(transparent)]
+pub struct ConfigVersion(pub u32);
+
+impl Default for ConfigVersion {
+    fn default() -> Self {
+        ConfigVersion(CONFIG_VERSION)
+    }
+}
+
+/// Returns the version of the schema of `config`.
+pub(crate) fn version_of(config: &Value) -> u32 {
+    config
+        .get("version")
+        .and_then(Value::as_u64)
+        .map_or(0, |version| version as u32)
+}
+
+/// Migrates `config` to `CONFIG_VERSION`, one version at a time. Returns `true`
+/// if it was migrated.
+pub(crate) fn migrate(config: &mut Value) -> bool {
+    let version = version_of(config);
+
+    let mapping = match config {
+        Value::Mapping(mapping) => mapping,
+        _ => return false,
+    };
+
+    if version >= CONFIG_VERSION {
+        return false;
+    }
+
+    for migration in MIGRATIONS.iter().skip(version as usize) {
+        migration(mapping);
+    }
+
+    mapping.insert("version".into(), Value::Number(CONFIG_VERSION.into()));
+
+    true
+}
+
+/// Version 0 to 1: Ignored addons were a single list for all flavors, and
+/// flavors were written in lowercase, eg. `wow_retail`.
+fn migrate_flavor_settings(config: &mut Mapping) {
+    if let Some(Value::Mapping(wow)) = config.get_mut(&"wow".into()) {
+        if let Some(flavor) = wow.get_mut(&"flavor".into()) {
+            rename_flavor(flavor);
+        }
+    }
+
+    if let Some(Value::Sequence(installations)) = config.get_mut(&"installations".into()) {
+        for installation in installations.iter_mut() {
+            if let Some(flavor) = installation.get_mut("flavor") {
+                rename_flavor(flavor);
+            }
+        }
+    }
+
+    if let Some(Value::Mapping(addons)) = config.get_mut(&"addons".into()) {
+        let ignored_key = Value::from("ignored");
+
+        if let Some(Value::Sequence(ignored)) = addons.get(&ignored_key).cloned() {
+            let mut per_flavor = Mapping::new();
+            per_flavor.insert("Retail".into(), Value::Sequence(ignored.clone()));
+            per_flavor.insert("Classic".into(), Value::Sequence(ignored));
+
+            addons.insert(ignored_key, Value::Mapping(per_flavor));
+        }
+
+        for key in [
+            "ignored",
+            "release_channels",
+            "rolled_back",
+            "pinned",
+            "preferred_sources",
+        ]
+        .iter()
+        {
+            if let Some(Value::Mapping(per_flavor)) = addons.get_mut(&(*key).into()) {
+                *per_flavor = per_flavor
+                    .iter()
+                    .map(|(flavor, value)| {
+                        let mut flavor = flavor.clone();
+                        rename_flavor(&mut flavor);
+
+                        (flavor, value.clone())
+                    })
+                    .collect();
+            }
+        }
+    }
+}
+
+fn rename_flavor(flavor: &mut Value) {
+    let renamed = match flavor.as_str() {
+        Some("retail") | Some("wow_retail") => "Retail",
+        Some("classic") | Some("wow_classic") => "Classic",
+        Some("classic_era") => "ClassicEra",
+        _ => return,
+    };
+
+    *flavor = renamed.into();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Config, Flavor};
+
+    /// Configs as they were written by each version of the schema.
+    const FIXTURES: [&str; CONFIG_VERSION as usize + 1] = [
+        include_str!("fixtures/v0.yml"),
+        include_str!("fixtures/v1.yml"),
+    ];
+
+    fn load(fixture: &str) -> (Config, bool) {
+        let mut value: Value = serde_yaml::from_str(fixture).unwrap();
+        let migrated = migrate(&mut value);
+
+        (serde_yaml::from_value(value).unwrap(), migrated)
+    }
+
+    #[test]
+    fn test_migrate_fixtures() {
+        for (version, fixture) in FIXTURES.iter().enumerate() {
+            let value: Value = serde_yaml::from_str(fixture).unwrap();
+            assert_eq!(version_of(&value), version as u32);
+
+            let (config, migrated) = load(fixture);
+            assert_eq!(migrated, version < CONFIG_VERSION as usize);
+            assert_eq!(config.version, ConfigVersion(CONFIG_VERSION));
+
+            // Settings every version had survive the migration.
+            assert_eq!(
+                config.wow.directory.unwrap().to_str(),
+                Some("/games/World of Warcraft")
+            );
+            assert_eq!(config.wow.flavor, Flavor::Classic);
+            assert_eq!(config.theme.as_deref(), Some("Dark"));
+            assert_eq!(
+                config.addons.ignored.get(&Flavor::Retail),
+                Some(&vec!["MyDevAddon".to_string()])
+            );
+            assert_eq!(
+                config.addons.ignored.get(&Flavor::Classic),
+                Some(&vec!["MyDevAddon".to_string()])
+            );
+
+            // Saving and loading the migrated config changes nothing.
+            let saved = serde_yaml::to_string(&config).unwrap();
+            let (reloaded, migrated) = load(&saved);
+            assert!(!migrated);
+            assert_eq!(reloaded, config);
+        }
+    }
+
+    #[test]
+    fn test_newer_version_is_left_alone() {
+        let mut value: Value = serde_yaml::from_str("version: 99\ntheme: Dark").unwrap();
+
+        assert!(!migrate(&mut value));
+        assert_eq!(version_of(&value), 99);
+    }
+}
@@ -1,19 +1,50 @@
 use glob::MatchOptions;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 mod addons;
+mod backups;
+mod cloud_sync;
+mod hooks;
+mod logging;
+mod migration;
+mod notifications;
+mod options;
+mod profile;
+mod tokens;
+mod updates;
+mod wago;
+mod webhook;
 mod wow;
 
+use crate::error::ClientError;
 use crate::fs::PersistentData;
 use crate::Result;
 
-pub use crate::config::addons::Addons;
-pub use crate::config::wow::{Flavor, Wow};
+pub use crate::config::addons::{Addons, SourcePriority};
+pub use crate::config::backups::{BackupSchedule, Backups};
+pub use crate::config::cloud_sync::{CloudSync, SyncRemote};
+pub use crate::config::hooks::Hooks;
+pub use crate::config::logging::{LogLevel, Logging};
+pub use crate::config::migration::{ConfigVersion, CONFIG_VERSION};
+pub use crate::config::notifications::{CheckInterval, Notifications};
+pub use crate::config::options::TextOption;
+pub use crate::config::profile::Profile;
+pub use crate::config::tokens::{Token, Tokens};
+pub use crate::config::updates::{ScheduledUpdates, UpdateSchedule};
+pub use crate::config::wago::Wago;
+pub use crate::config::webhook::{Webhook, WebhookEvent, WebhookFormat};
+pub use crate::config::wow::{Flavor, Installation, Wow};
+pub use crate::localization::Language;
 
 /// Config struct.
 #[derive(Deserialize, Serialize, Debug, PartialEq, Default, Clone)]
 pub struct Config {
+    /// Version of the schema the config was written with.
+    #[serde(default)]
+    pub version: ConfigVersion,
+
     #[serde(default)]
     pub wow: Wow,
 
@@ -29,10 +60,284 @@ pub struct Config {
 
     pub scale: Option<f64>,
 
+    /// Font size of text, in the GUI.
+    pub font_size: Option<u16>,
+
+    /// How tightly the rows of My Addons are packed.
+    #[serde(default)]
+    pub row_density: RowDensity,
+
     pub backup_directory: Option<PathBuf>,
+
+    #[serde(default)]
+    pub backups: Backups,
+
+    #[serde(default)]
+    pub notifications: Notifications,
+
+    #[serde(default)]
+    pub scheduled_updates: ScheduledUpdates,
+
+    #[serde(default)]
+    pub wago: Wago,
+
+    /// "Update All" updates the addons of every flavor, not only the selected one.
+    #[serde(default)]
+    pub update_all_flavors: bool,
+
+    /// Installations of World of Warcraft to switch between, including the one in
+    /// use. Only set once a second installation is added.
+    #[serde(default)]
+    pub installations: Vec<Installation>,
+
+    /// Name of the profile in use. Not set until a second profile is added.
+    #[serde(default)]
+    pub profile: Option<String>,
+
+    /// Profiles to switch between, including the one in use.
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+
+    /// Proxy for all network requests, eg. `http://proxy:3128` or `socks5h://proxy:1080`.
+    #[serde(default)]
+    pub proxy: Option<String>,
+
+    /// Amount of times a network request is retried after a transient failure.
+    #[serde(default)]
+    pub max_retries: Option<u32>,
+
+    #[serde(default)]
+    pub tokens: Tokens,
+
+    /// No requests are made, addons are shown as they were when last refreshed.
+    #[serde(default)]
+    pub offline: bool,
+
+    /// Amount of threads addon folders are parsed with, one per CPU if not set.
+    #[serde(default)]
+    pub parse_threads: Option<usize>,
+
+    /// Updates and deletions held back while the game is running run once it exits.
+    #[serde(default)]
+    pub run_after_game_exits: bool,
+
+    /// Language of the GUI, the one of the system if not set.
+    #[serde(default)]
+    pub language: Option<Language>,
+
+    /// Ajour is registered to open `ajour://` install links.
+    #[serde(default)]
+    pub open_links: bool,
+
+    /// An icon of Ajour is shown in the system tray, with quick actions.
+    #[serde(default)]
+    pub tray_icon: bool,
+
+    /// Closing the window hides Ajour to the tray instead of quitting, on Windows.
+    #[serde(default)]
+    pub close_to_tray: bool,
+
+    #[serde(default)]
+    pub logging: Logging,
+
+    /// Releases of Ajour which are offered as updates.
+    #[serde(default)]
+    pub self_update_channel: SelfUpdateChannel,
+
+    /// Release of Ajour the user chose to skip, which isn't offered as an update.
+    #[serde(default)]
+    pub skipped_self_update: Option<String>,
+
+    #[serde(default)]
+    pub cloud_sync: CloudSync,
+
+    #[serde(default)]
+    pub hooks: Hooks,
+
+    #[serde(default)]
+    pub webhook: Webhook,
+
+    /// Settings overridden by the environment, which aren't saved.
+    #[serde(skip)]
+    env_overrides: EnvOverrides,
+}
+
+/// Values of the settings overridden by the environment, as they are in the
+/// config file and as they were overridden with.
+#[derive(Debug, PartialEq, Default, Clone)]
+struct EnvOverrides {
+    wow_directory: Option<(Option<PathBuf>, PathBuf)>,
+    offline: Option<(bool, bool)>,
 }
 
 impl Config {
+    /// Returns the directories of the installations of World of Warcraft, with
+    /// the one in use first.
+    pub fn installation_directories(&self) -> Vec<PathBuf> {
+        let mut directories = self.wow.directory.iter().cloned().collect::<Vec<_>>();
+
+        for installation in self.installations.iter() {
+            if !directories.contains(&installation.directory) {
+                directories.push(installation.directory.clone());
+            }
+        }
+
+        directories
+    }
+
+    /// Switches to the installation in `directory`, adding it if it's new.
+    ///
+    /// The addon settings of the installation in use are stored with it, and
+    /// replaced by the ones of the new installation.
+    pub fn switch_installation(&mut self, directory: PathBuf) {
+        self.store_installation();
+
+        let installation = match self.installations.iter().find(|i| i.directory == directory) {
+            Some(installation) => installation.clone(),
+            None => {
+                let installation = Installation {
+                    directory,
+                    flavor: self.wow.flavor,
+                    addons: Default::default(),
+                };
+                self.installations.push(installation.clone());
+                installation
+            }
+        };
+
+        self.wow.directory = Some(installation.directory);
+        self.wow.flavor = installation.flavor;
+        self.addons = installation.addons;
+    }
+
+    /// Changes the directory of the installation in use, keeping its addon
+    /// settings.
+    pub fn set_wow_directory(&mut self, directory: PathBuf) {
+        if let Some(current) = self.saved_wow_directory() {
+            self.installations.retain(|i| i.directory != current);
+        }
+
+        self.wow.directory = Some(directory);
+
+        if !self.installations.is_empty() {
+            self.store_installation();
+        }
+    }
+
+    /// Stores the addon settings of the installation in use in `installations`.
+    fn store_installation(&mut self) {
+        let directory = match self.saved_wow_directory() {
+            Some(directory) => directory,
+            None => return,
+        };
+
+        let installation = Installation {
+            directory,
+            flavor: self.wow.flavor,
+            addons: self.addons.clone(),
+        };
+
+        match self
+            .installations
+            .iter_mut()
+            .find(|i| i.directory == installation.directory)
+        {
+            Some(existing) => *existing = installation,
+            None => self.installations.push(installation),
+        }
+    }
+
+    /// Returns the names of the profiles, with the one in use first.
+    pub fn profile_names(&self) -> Vec<String> {
+        let mut names = self.profile.iter().cloned().collect::<Vec<_>>();
+
+        for profile in self.profiles.iter() {
+            if !names.contains(&profile.name) {
+                names.push(profile.name.clone());
+            }
+        }
+
+        names
+    }
+
+    /// Adds a profile named `name` holding the settings in use, and switches to
+    /// it. The settings in use until now are stored as the "Default" profile if
+    /// there were no profiles yet.
+    pub fn add_profile(&mut self, name: String) -> Result<()> {
+        let name = name.trim().to_string();
+
+        if name.is_empty() || self.profile_names().contains(&name) {
+            return Err(ClientError::Custom(format!(
+                "a profile named '{}' can't be added",
+                name
+            )));
+        }
+
+        if self.profile.is_none() {
+            self.profile = Some(DEFAULT_PROFILE.to_string());
+        }
+
+        self.store_profile();
+        self.profile = Some(name);
+        self.store_profile();
+
+        Ok(())
+    }
+
+    /// Switches to the profile named `name`.
+    ///
+    /// The settings of the profile in use are stored with it, and replaced by the
+    /// ones of the new profile.
+    pub fn switch_profile(&mut self, name: &str) -> Result<()> {
+        let profile = self
+            .profiles
+            .iter()
+            .find(|p| p.name == name)
+            .cloned()
+            .ok_or_else(|| ClientError::Custom(format!("no profile named '{}'", name)))?;
+
+        self.store_profile();
+
+        self.profile = Some(profile.name);
+        self.wow = profile.wow;
+        self.addons = profile.addons;
+        self.installations = profile.installations;
+
+        Ok(())
+    }
+
+    /// Stores the settings of the profile in use in `profiles`.
+    fn store_profile(&mut self) {
+        let name = match &self.profile {
+            Some(name) => name.clone(),
+            None => return,
+        };
+
+        let mut wow = self.wow.clone();
+        wow.directory = self.saved_wow_directory();
+
+        let profile = Profile {
+            name,
+            wow,
+            addons: self.addons.clone(),
+            installations: self.installations.clone(),
+        };
+
+        match self.profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => self.profiles.push(profile),
+        }
+    }
+
+    /// Returns the flavors managed by Ajour, which are all but the ignored ones.
+    pub fn managed_flavors(&self) -> Vec<Flavor> {
+        Flavor::ALL
+            .iter()
+            .copied()
+            .filter(|f| !self.wow.ignored_flavors.contains(f))
+            .collect()
+    }
+
     /// Returns a `Option<PathBuf>` to the directory containing the addons.
     /// This will return `None` if no `wow_directory` is set in the config.
     pub fn get_addon_directory_for_flavor(&self, flavor: &Flavor) -> Option<PathBuf> {
@@ -115,12 +420,123 @@ impl Config {
             None => None,
         }
     }
+
+    /// Overrides settings with `AJOUR_WOW_DIR` and `AJOUR_OFFLINE`, so Ajour can
+    /// be set up without writing a config first.
+    ///
+    /// The overrides only last while Ajour runs, `save` keeps the values of the
+    /// config file unless they were changed since.
+    fn apply_env_overrides(&mut self, env: impl Fn(&str) -> Option<String>) {
+        if let Some(directory) = env("AJOUR_WOW_DIR").filter(|d| !d.is_empty()) {
+            let directory = PathBuf::from(directory);
+            let saved = match self.env_overrides.wow_directory.take() {
+                Some((saved, _)) => saved,
+                None => self.wow.directory.clone(),
+            };
+
+            self.wow.directory = Some(directory.clone());
+            self.env_overrides.wow_directory = Some((saved, directory));
+        }
+
+        if let Some(offline) = env("AJOUR_OFFLINE") {
+            let overridden = match offline.to_lowercase().as_str() {
+                "1" | "true" | "yes" | "on" => true,
+                "0" | "false" | "no" | "off" | "" => false,
+                _ => {
+                    log::warn!("ignoring AJOUR_OFFLINE, '{}' isn't a boolean", offline);
+                    return;
+                }
+            };
+            let saved = match self.env_overrides.offline.take() {
+                Some((saved, _)) => saved,
+                None => self.offline,
+            };
+
+            self.offline = overridden;
+            self.env_overrides.offline = Some((saved, overridden));
+        }
+    }
+
+    /// Returns the config as it's saved, with the settings still overridden by
+    /// the environment set back to their values in the config file.
+    fn without_env_overrides(&self) -> Config {
+        let mut config = self.clone();
+        config.wow.directory = self.saved_wow_directory();
+
+        if let Some((saved, overridden)) = self.env_overrides.offline {
+            if config.offline == overridden {
+                config.offline = saved;
+            }
+        }
+
+        config
+    }
+
+    /// Returns the World of Warcraft directory as it's saved, which is the one
+    /// in use unless it's overridden by `AJOUR_WOW_DIR`.
+    fn saved_wow_directory(&self) -> Option<PathBuf> {
+        match &self.env_overrides.wow_directory {
+            Some((saved, overridden)) if self.wow.directory.as_ref() == Some(overridden) => {
+                saved.clone()
+            }
+            _ => self.wow.directory.clone(),
+        }
+    }
 }
 
 impl PersistentData for Config {
     fn relative_path() -> PathBuf {
         PathBuf::from("ajour.yml")
     }
+
+    /// Loads the config, migrating it to the current schema first. The config
+    /// from before the migration is kept as `ajour.yml.v<version>.bak`.
+    fn load() -> Result<Self> {
+        let path = Self::path()?;
+
+        if !path.exists() {
+            return Err(ClientError::LoadFileDoesntExist(path));
+        }
+
+        let mut value: serde_yaml::Value = serde_yaml::from_reader(std::fs::File::open(&path)?)?;
+        let version = migration::version_of(&value);
+
+        if version > CONFIG_VERSION {
+            log::warn!(
+                "config is of version {}, newer than {}, settings it added are lost once saved",
+                version,
+                CONFIG_VERSION
+            );
+        }
+
+        if !migration::migrate(&mut value) {
+            return Ok(serde_yaml::from_value(value)?);
+        }
+
+        let backup = path.with_extension(format!("yml.v{}.bak", version));
+        std::fs::copy(&path, &backup)?;
+
+        let config: Config = serde_yaml::from_value(value)?;
+        config.save()?;
+
+        log::info!(
+            "migrated config from version {} to {}, the previous one is kept at {:?}",
+            version,
+            CONFIG_VERSION,
+            backup
+        );
+
+        Ok(config)
+    }
+
+    /// Saves the config, leaving out the overrides of the environment.
+    fn save(&self) -> Result<()> {
+        let contents = serde_yaml::to_string(&self.without_env_overrides())?;
+
+        std::fs::write(Self::path()?, contents)?;
+
+        Ok(())
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
@@ -156,11 +572,279 @@ impl Default for ColumnConfig {
     }
 }
 
+/// Releases of Ajour which are offered as updates. Betas also offer stable
+/// releases, and nightlies both, whichever is newest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize, Hash)]
+pub enum SelfUpdateChannel {
+    Stable,
+    Beta,
+    Nightly,
+}
+
+impl SelfUpdateChannel {
+    pub const ALL: [SelfUpdateChannel; 3] = [
+        SelfUpdateChannel::Stable,
+        SelfUpdateChannel::Beta,
+        SelfUpdateChannel::Nightly,
+    ];
+}
+
+impl Default for SelfUpdateChannel {
+    fn default() -> SelfUpdateChannel {
+        SelfUpdateChannel::Stable
+    }
+}
+
+impl std::fmt::Display for SelfUpdateChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            SelfUpdateChannel::Stable => "Stable",
+            SelfUpdateChannel::Beta => "Beta",
+            SelfUpdateChannel::Nightly => "Nightly",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Density of the rows in My Addons. Compact rows are shorter and don't
+/// inline secondary details, so more addons fit on screen at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Hash)]
+pub enum RowDensity {
+    Normal,
+    Compact,
+}
+
+impl RowDensity {
+    pub const ALL: [RowDensity; 2] = [RowDensity::Normal, RowDensity::Compact];
+}
+
+impl Default for RowDensity {
+    fn default() -> RowDensity {
+        RowDensity::Normal
+    }
+}
+
+impl std::fmt::Display for RowDensity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            RowDensity::Normal => "Normal",
+            RowDensity::Compact => "Compact",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Name of the profile the settings in use become once a second profile is added.
+pub const DEFAULT_PROFILE: &str = "Default";
+
+lazy_static::lazy_static! {
+    /// Profile chosen on the command line, switched to on the first load.
+    static ref CHOSEN_PROFILE: Mutex<Option<String>> = Mutex::new(None);
+}
+
+/// Sets the profile which is switched to once the config is loaded, eg. from
+/// `--profile`.
+pub fn set_profile(name: Option<String>) {
+    *CHOSEN_PROFILE.lock().unwrap() = name;
+}
+
 /// Returns a Config.
 ///
 /// This functions handles the initialization of a Config.
 pub async fn load_config() -> Result<Config> {
     log::debug!("loading config");
 
-    Ok(Config::load_or_default()?)
+    let mut config = Config::load_or_default()?;
+
+    let chosen_profile = CHOSEN_PROFILE.lock().unwrap().take();
+    if let Some(name) = chosen_profile {
+        if config.profile.as_ref() != Some(&name) {
+            config.switch_profile(&name)?;
+            config.save()?;
+        }
+    }
+
+    config.apply_env_overrides(|name| std::env::var(name).ok());
+
+    // An invalid proxy shouldn't keep Ajour from starting, requests are made without it.
+    if let Err(e) = crate::network::set_proxy(config.proxy.as_deref()) {
+        log::error!("{}", e);
+    }
+
+    crate::network::set_max_retries(
+        config
+            .max_retries
+            .unwrap_or(crate::network::DEFAULT_MAX_RETRIES),
+    );
+
+    crate::network::set_tokens(config.tokens.clone());
+    crate::network::set_offline(config.offline);
+    crate::parse::set_parse_threads(config.parse_threads.unwrap_or_default());
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_overrides() {
+        let mut config = Config::default();
+        config.wow.directory = Some(PathBuf::from("/games/World of Warcraft"));
+
+        config.apply_env_overrides(|name| match name {
+            "AJOUR_WOW_DIR" => Some("/srv/wow".to_string()),
+            "AJOUR_OFFLINE" => Some("True".to_string()),
+            _ => None,
+        });
+        assert_eq!(config.wow.directory, Some(PathBuf::from("/srv/wow")));
+        assert!(config.offline);
+
+        config.apply_env_overrides(|name| match name {
+            "AJOUR_OFFLINE" => Some("maybe".to_string()),
+            _ => None,
+        });
+        assert_eq!(config.wow.directory, Some(PathBuf::from("/srv/wow")));
+        assert!(config.offline);
+    }
+
+    #[test]
+    fn test_env_overrides_arent_saved() {
+        let mut config = Config::default();
+        config.wow.directory = Some(PathBuf::from("/games/World of Warcraft"));
+        let saved = serde_yaml::to_string(&config).unwrap();
+
+        config.apply_env_overrides(|name| match name {
+            "AJOUR_WOW_DIR" => Some("/srv/wow".to_string()),
+            "AJOUR_OFFLINE" => Some("1".to_string()),
+            _ => None,
+        });
+        assert_eq!(
+            serde_yaml::to_string(&config.without_env_overrides()).unwrap(),
+            saved
+        );
+
+        // Settings changed after the override are saved.
+        config.wow.directory = Some(PathBuf::from("/games/wow"));
+        assert_eq!(
+            config.without_env_overrides().wow.directory,
+            Some(PathBuf::from("/games/wow"))
+        );
+    }
+
+    #[test]
+    fn test_env_overrides_arent_stored_in_profiles() {
+        let live = PathBuf::from("/games/World of Warcraft");
+        let other = PathBuf::from("/other/World of Warcraft");
+
+        let mut config = Config::default();
+        config.wow.directory = Some(live.clone());
+
+        config.apply_env_overrides(|name| match name {
+            "AJOUR_WOW_DIR" => Some("/srv/wow".to_string()),
+            _ => None,
+        });
+
+        config.add_profile("Laptop".to_string()).unwrap();
+        config.switch_installation(other);
+
+        let saved = config.without_env_overrides();
+        assert!(saved
+            .profiles
+            .iter()
+            .all(|p| p.wow.directory == Some(live.clone())));
+        assert!(saved.installations.iter().any(|i| i.directory == live));
+        assert!(saved
+            .installations
+            .iter()
+            .all(|i| i.directory != PathBuf::from("/srv/wow")));
+    }
+
+    #[test]
+    fn test_profiles() {
+        let mut config = Config::default();
+        config.wow.directory = Some(PathBuf::from("/games/World of Warcraft"));
+        config.addons.ignore_patterns.push("Raid*".to_string());
+
+        config.add_profile("Laptop".to_string()).unwrap();
+        assert_eq!(config.profile_names(), vec!["Laptop", DEFAULT_PROFILE]);
+        assert!(config.add_profile(" Laptop ".to_string()).is_err());
+
+        config.wow.flavor = Flavor::ClassicEra;
+        config.addons.ignore_patterns.clear();
+
+        config.switch_profile(DEFAULT_PROFILE).unwrap();
+        assert_eq!(config.profile.as_deref(), Some(DEFAULT_PROFILE));
+        assert_eq!(config.wow.flavor, Flavor::Retail);
+        assert_eq!(config.addons.ignore_patterns, vec!["Raid*".to_string()]);
+
+        config.switch_profile("Laptop").unwrap();
+        assert_eq!(config.wow.flavor, Flavor::ClassicEra);
+        assert!(config.addons.ignore_patterns.is_empty());
+
+        assert!(config.switch_profile("Raid PC").is_err());
+        assert_eq!(config.profile.as_deref(), Some("Laptop"));
+    }
+
+    #[test]
+    fn test_switch_installation() {
+        let live = PathBuf::from("/games/World of Warcraft");
+        let other = PathBuf::from("/other/World of Warcraft");
+
+        let mut config = Config::default();
+        config.wow.directory = Some(live.clone());
+        config.addons.ignore_patterns.push("Live*".to_string());
+
+        config.switch_installation(other.clone());
+        assert_eq!(config.wow.directory, Some(other.clone()));
+        assert!(config.addons.ignore_patterns.is_empty());
+        assert_eq!(
+            config.installation_directories(),
+            vec![other.clone(), live.clone()]
+        );
+
+        config.wow.flavor = Flavor::Classic;
+        config.switch_installation(live.clone());
+        assert_eq!(config.wow.flavor, Flavor::Retail);
+        assert_eq!(config.addons.ignore_patterns, vec!["Live*".to_string()]);
+
+        config.switch_installation(other);
+        assert_eq!(config.wow.flavor, Flavor::Classic);
+    }
+
+    #[test]
+    fn test_managed_flavors() {
+        let mut config = Config::default();
+        assert_eq!(config.managed_flavors(), Flavor::ALL.to_vec());
+
+        config.wow.ignored_flavors = vec![Flavor::RetailPTR, Flavor::ClassicEra];
+        let managed = config.managed_flavors();
+        assert_eq!(managed.len(), Flavor::ALL.len() - 2);
+        assert!(managed.contains(&Flavor::Retail));
+        assert!(!managed.contains(&Flavor::ClassicEra));
+    }
+
+    #[test]
+    fn test_row_density() {
+        let config: Config = serde_yaml::from_str("theme: Dark").unwrap();
+        assert_eq!(config.row_density, RowDensity::Normal);
+
+        let config: Config = serde_yaml::from_str("row_density: Compact").unwrap();
+        assert_eq!(config.row_density, RowDensity::Compact);
+    }
+
+    #[test]
+    fn test_logging() {
+        let config: Config = serde_yaml::from_str("theme: Dark").unwrap();
+        assert_eq!(config.logging, Logging::default());
+
+        let config: Config =
+            serde_yaml::from_str("logging:\n  level: warn\n  keep_count: 5").unwrap();
+        assert_eq!(config.logging.level, Some(LogLevel::Warn));
+        assert_eq!(config.logging.keep_count, Some(5));
+
+        assert_eq!("Debug".parse::<LogLevel>(), Ok(LogLevel::Debug));
+        assert!("verbose".parse::<LogLevel>().is_err());
+    }
 }
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+/// Struct for settings related to update notifications.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Notifications {
+    /// Checks for updates in the background and notifies about new versions.
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub interval: CheckInterval,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Hash)]
+pub enum CheckInterval {
+    Hourly,
+    SixHours,
+    TwelveHours,
+    Daily,
+}
+
+impl CheckInterval {
+    pub const ALL: [CheckInterval; 4] = [
+        CheckInterval::Hourly,
+        CheckInterval::SixHours,
+        CheckInterval::TwelveHours,
+        CheckInterval::Daily,
+    ];
+
+    /// Returns the time between checks for updates.
+    pub fn duration(self) -> chrono::Duration {
+        match self {
+            CheckInterval::Hourly => chrono::Duration::hours(1),
+            CheckInterval::SixHours => chrono::Duration::hours(6),
+            CheckInterval::TwelveHours => chrono::Duration::hours(12),
+            CheckInterval::Daily => chrono::Duration::days(1),
+        }
+    }
+}
+
+impl Default for CheckInterval {
+    fn default() -> CheckInterval {
+        CheckInterval::SixHours
+    }
+}
+
+impl std::fmt::Display for CheckInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            CheckInterval::Hourly => "Every hour",
+            CheckInterval::SixHours => "Every 6 hours",
+            CheckInterval::TwelveHours => "Every 12 hours",
+            CheckInterval::Daily => "Daily",
+        };
+        write!(f, "{}", s)
+    }
+}
@@ -0,0 +1,286 @@
+use super::{Config, WebhookEvent, WebhookFormat};
+use crate::error::ClientError;
+use crate::Result;
+use glob::Pattern;
+use isahc::http::Uri;
+
+/// Options of the config which are typed in as text, so they are validated
+/// before they are set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextOption {
+    Proxy,
+    MaxRetries,
+    ParseThreads,
+    BackupKeepCount,
+    BackupMaxSizeMb,
+    IgnorePatterns,
+    AbandonedAfterMonths,
+    WebhookUrl,
+    WebhookFormat,
+    WebhookEvents,
+}
+
+impl TextOption {
+    pub const ALL: [TextOption; 10] = [
+        TextOption::Proxy,
+        TextOption::MaxRetries,
+        TextOption::ParseThreads,
+        TextOption::BackupKeepCount,
+        TextOption::BackupMaxSizeMb,
+        TextOption::IgnorePatterns,
+        TextOption::AbandonedAfterMonths,
+        TextOption::WebhookUrl,
+        TextOption::WebhookFormat,
+        TextOption::WebhookEvents,
+    ];
+
+    /// Key of the option in the config file.
+    pub fn key(self) -> &'static str {
+        match self {
+            TextOption::Proxy => "proxy",
+            TextOption::MaxRetries => "max_retries",
+            TextOption::ParseThreads => "parse_threads",
+            TextOption::BackupKeepCount => "backups.keep_count",
+            TextOption::BackupMaxSizeMb => "backups.max_size_mb",
+            TextOption::IgnorePatterns => "addons.ignore_patterns",
+            TextOption::AbandonedAfterMonths => "addons.abandoned_after_months",
+            TextOption::WebhookUrl => "webhook.url",
+            TextOption::WebhookFormat => "webhook.format",
+            TextOption::WebhookEvents => "webhook.events",
+        }
+    }
+}
+
+impl std::str::FromStr for TextOption {
+    type Err = ClientError;
+
+    /// Parses the key of an option, eg. `backups.keep_count`.
+    fn from_str(s: &str) -> Result<Self> {
+        TextOption::ALL
+            .iter()
+            .copied()
+            .find(|option| option.key() == s.trim())
+            .ok_or_else(|| {
+                let keys = TextOption::ALL
+                    .iter()
+                    .map(|option| option.key())
+                    .collect::<Vec<_>>();
+
+                ClientError::Custom(format!(
+                    "'{}' isn't an option, expected one of {}",
+                    s,
+                    keys.join(", ")
+                ))
+            })
+    }
+}
+
+impl std::fmt::Display for TextOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl Config {
+    /// Returns `option` as text, empty if it isn't set. Ignore patterns and
+    /// webhook events are separated by commas.
+    pub fn text_option(&self, option: TextOption) -> String {
+        fn or_empty<T: ToString>(value: Option<T>) -> String {
+            value.map(|v| v.to_string()).unwrap_or_default()
+        }
+
+        match option {
+            TextOption::Proxy => or_empty(self.proxy.as_ref()),
+            TextOption::MaxRetries => or_empty(self.max_retries),
+            TextOption::ParseThreads => or_empty(self.parse_threads),
+            TextOption::BackupKeepCount => or_empty(self.backups.keep_count),
+            TextOption::BackupMaxSizeMb => or_empty(self.backups.max_size_mb),
+            TextOption::IgnorePatterns => self.addons.ignore_patterns.join(", "),
+            TextOption::AbandonedAfterMonths => or_empty(self.addons.abandoned_after_months),
+            TextOption::WebhookUrl => or_empty(self.webhook.url.as_ref()),
+            TextOption::WebhookFormat => self.webhook.format.to_string(),
+            TextOption::WebhookEvents => self
+                .webhook
+                .events
+                .iter()
+                .map(WebhookEvent::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+        }
+    }
+
+    /// Sets `option` from text, leaving the config untouched if it isn't valid.
+    /// An empty value unsets the option.
+    pub fn set_text_option(&mut self, option: TextOption, value: &str) -> Result<()> {
+        let value = value.trim();
+
+        let invalid =
+            |reason: &str| ClientError::Custom(format!("Invalid {}: {}", option.key(), reason));
+
+        match option {
+            TextOption::Proxy => {
+                if !value.is_empty() {
+                    let uri = value.parse::<Uri>().map_err(|e| invalid(&e.to_string()))?;
+
+                    if uri.scheme().is_none() || uri.host().is_none() {
+                        return Err(invalid("expected eg. socks5h://localhost:1080"));
+                    }
+                }
+
+                self.proxy = Some(value.to_string()).filter(|v| !v.is_empty());
+            }
+            TextOption::MaxRetries => self.max_retries = parse_number(value, invalid)?,
+            TextOption::ParseThreads => self.parse_threads = parse_number(value, invalid)?,
+            TextOption::BackupKeepCount => {
+                self.backups.keep_count = parse_number(value, invalid)?;
+            }
+            TextOption::BackupMaxSizeMb => {
+                self.backups.max_size_mb = parse_number(value, invalid)?;
+            }
+            TextOption::IgnorePatterns => {
+                let patterns = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|p| !p.is_empty())
+                    .map(|p| {
+                        Pattern::new(p)
+                            .map(|_| p.to_string())
+                            .map_err(|e| invalid(&format!("{} ({})", p, e)))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                self.addons.ignore_patterns = patterns;
+            }
+            TextOption::AbandonedAfterMonths => {
+                self.addons.abandoned_after_months = parse_number(value, invalid)?;
+            }
+            TextOption::WebhookUrl => {
+                if !value.is_empty() {
+                    let uri = value.parse::<Uri>().map_err(|e| invalid(&e.to_string()))?;
+
+                    if !matches!(uri.scheme_str(), Some("http") | Some("https")) {
+                        return Err(invalid("expected eg. https://discord.com/api/webhooks/..."));
+                    }
+                }
+
+                self.webhook.url = Some(value.to_string()).filter(|v| !v.is_empty());
+            }
+            TextOption::WebhookFormat => {
+                self.webhook.format = WebhookFormat::ALL
+                    .iter()
+                    .copied()
+                    .find(|f| value.is_empty() || f.to_string() == value.to_lowercase())
+                    .ok_or_else(|| invalid("expected json or discord"))?;
+            }
+            TextOption::WebhookEvents => {
+                let events = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|e| !e.is_empty())
+                    .map(|e| {
+                        WebhookEvent::ALL
+                            .iter()
+                            .copied()
+                            .find(|event| event.to_string() == e.to_lowercase())
+                            .ok_or_else(|| {
+                                invalid(&format!(
+                                    "{} (expected updates-found or updates-applied)",
+                                    e
+                                ))
+                            })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                self.webhook.events = events;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a whole number, `None` if `value` is empty.
+fn parse_number<T: std::str::FromStr>(
+    value: &str,
+    invalid: impl Fn(&str) -> ClientError,
+) -> Result<Option<T>> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| invalid("expected a whole number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_text_option() {
+        let mut config = Config::default();
+
+        config
+            .set_text_option(TextOption::Proxy, " socks5h://localhost:1080 ")
+            .unwrap();
+        assert_eq!(config.proxy.as_deref(), Some("socks5h://localhost:1080"));
+        assert!(config
+            .set_text_option(TextOption::Proxy, "localhost")
+            .is_err());
+        assert_eq!(config.proxy.as_deref(), Some("socks5h://localhost:1080"));
+        config.set_text_option(TextOption::Proxy, "").unwrap();
+        assert_eq!(config.proxy, None);
+
+        config.set_text_option(TextOption::MaxRetries, "3").unwrap();
+        assert_eq!(config.max_retries, Some(3));
+        assert!(config
+            .set_text_option(TextOption::MaxRetries, "-1")
+            .is_err());
+        assert!(config
+            .set_text_option(TextOption::MaxRetries, "many")
+            .is_err());
+        assert_eq!(config.text_option(TextOption::MaxRetries), "3");
+
+        config
+            .set_text_option(TextOption::IgnorePatterns, "MyDevAddon*, Test?,")
+            .unwrap();
+        assert_eq!(
+            config.addons.ignore_patterns,
+            vec!["MyDevAddon*".to_string(), "Test?".to_string()]
+        );
+        assert_eq!(
+            config.text_option(TextOption::IgnorePatterns),
+            "MyDevAddon*, Test?"
+        );
+        assert!(config
+            .set_text_option(TextOption::IgnorePatterns, "[Broken")
+            .is_err());
+
+        config
+            .set_text_option(TextOption::WebhookFormat, "Discord")
+            .unwrap();
+        assert_eq!(config.webhook.format, WebhookFormat::Discord);
+        config
+            .set_text_option(TextOption::WebhookEvents, "updates-applied")
+            .unwrap();
+        assert_eq!(config.webhook.events, vec![WebhookEvent::UpdatesApplied]);
+        assert!(config
+            .set_text_option(TextOption::WebhookEvents, "raid-night")
+            .is_err());
+        assert!(config
+            .set_text_option(TextOption::WebhookUrl, "ftp://example.com")
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_text_option() {
+        for option in TextOption::ALL.iter() {
+            assert_eq!(option.key().parse::<TextOption>().unwrap(), *option);
+        }
+
+        assert!("backups".parse::<TextOption>().is_err());
+        assert!("tokens.github".parse::<TextOption>().is_err());
+    }
+}
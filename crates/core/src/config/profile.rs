@@ -0,0 +1,18 @@
+use super::{Addons, Installation, Wow};
+use serde::{Deserialize, Serialize};
+
+/// A named setup of Ajour, eg. "Laptop", with its own installations of World of
+/// Warcraft, flavors and addon settings.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+
+    #[serde(default)]
+    pub wow: Wow,
+
+    #[serde(default)]
+    pub addons: Addons,
+
+    #[serde(default)]
+    pub installations: Vec<Installation>,
+}
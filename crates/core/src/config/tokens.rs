@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+/// API keys and tokens sent along with requests to the repositories.
+///
+/// Without them requests are unauthenticated, which GitHub limits to 60 an hour.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Tokens {
+    #[serde(default)]
+    pub curse: Option<String>,
+
+    /// Personal access token, which doesn't need any scopes.
+    #[serde(default)]
+    pub github: Option<String>,
+
+    #[serde(default)]
+    pub wago: Option<String>,
+}
+
+impl Tokens {
+    pub fn get(&self, token: Token) -> Option<&str> {
+        let value = match token {
+            Token::Curse => &self.curse,
+            Token::GitHub => &self.github,
+            Token::Wago => &self.wago,
+        };
+
+        value.as_deref()
+    }
+
+    pub fn set(&mut self, token: Token, value: String) {
+        let value = Some(value).filter(|v| !v.trim().is_empty());
+
+        match token {
+            Token::Curse => self.curse = value,
+            Token::GitHub => self.github = value,
+            Token::Wago => self.wago = value,
+        }
+    }
+
+    /// Returns the header authenticating a request to `host`, if we have a
+    /// token for it.
+    pub fn header(&self, host: &str) -> Option<(&'static str, String)> {
+        let token = Token::ALL
+            .iter()
+            .copied()
+            .find(|t| t.hosts().contains(&host))?;
+        let value = self.get(token)?.trim();
+
+        Some(match token {
+            Token::Curse => ("x-api-key", value.to_string()),
+            Token::GitHub => ("Authorization", format!("token {}", value)),
+            Token::Wago => ("api-key", value.to_string()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Token {
+    Curse,
+    GitHub,
+    Wago,
+}
+
+impl Token {
+    pub const ALL: [Token; 3] = [Token::Curse, Token::GitHub, Token::Wago];
+
+    fn hosts(self) -> &'static [&'static str] {
+        match self {
+            Token::Curse => &["addons-ecs.forgesvc.net", "api.curseforge.com"],
+            Token::GitHub => &["api.github.com"],
+            Token::Wago => &["data.wago.io", "wago.io"],
+        }
+    }
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Token::Curse => "CurseForge API key",
+            Token::GitHub => "GitHub access token",
+            Token::Wago => "Wago API key",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header() {
+        let mut tokens = Tokens::default();
+        tokens.set(Token::GitHub, "ghp_secret".to_string());
+        tokens.set(Token::Wago, "  ".to_string());
+
+        assert_eq!(
+            tokens.header("api.github.com"),
+            Some(("Authorization", "token ghp_secret".to_string()))
+        );
+        assert_eq!(tokens.wago, None);
+        assert_eq!(tokens.header("data.wago.io"), None);
+        assert_eq!(tokens.header("www.tukui.org"), None);
+    }
+}
@@ -0,0 +1,121 @@
+use chrono::{Duration, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+/// Struct for settings related to checking for and installing updates on a
+/// schedule, which the GUI and the `daemon` command share.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct ScheduledUpdates {
+    #[serde(default)]
+    pub schedule: UpdateSchedule,
+
+    /// When the scheduled updates last ran.
+    #[serde(default)]
+    pub last_run: Option<NaiveDateTime>,
+}
+
+impl ScheduledUpdates {
+    /// Returns when the updates are due next, or `None` if they aren't scheduled.
+    ///
+    /// The schedule counts from the last run, or from `since` if there hasn't been
+    /// one, eg. when the app was started.
+    pub fn next_run(&self, since: NaiveDateTime) -> Option<NaiveDateTime> {
+        let from = self.last_run.unwrap_or(since);
+
+        match self.schedule {
+            UpdateSchedule::Off => None,
+            UpdateSchedule::Hours(hours) => Some(from + Duration::hours(hours.max(1) as i64)),
+            UpdateSchedule::DailyAt(hour, minute) => {
+                let at = from
+                    .date()
+                    .and_time(NaiveTime::from_hms_opt(hour, minute, 0)?);
+
+                if at > from {
+                    Some(at)
+                } else {
+                    Some(at + Duration::days(1))
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the scheduled updates are due at `now`.
+    pub fn is_due(&self, since: NaiveDateTime, now: NaiveDateTime) -> bool {
+        self.next_run(since).map_or(false, |at| now >= at)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Hash)]
+pub enum UpdateSchedule {
+    Off,
+    /// Every amount of hours.
+    Hours(u32),
+    /// Every day at the hour and minute, in local time.
+    DailyAt(u32, u32),
+}
+
+impl UpdateSchedule {
+    /// Schedules offered in the settings. Others can be set in the config file.
+    pub const ALL: [UpdateSchedule; 7] = [
+        UpdateSchedule::Off,
+        UpdateSchedule::Hours(1),
+        UpdateSchedule::Hours(6),
+        UpdateSchedule::Hours(12),
+        UpdateSchedule::DailyAt(3, 0),
+        UpdateSchedule::DailyAt(5, 0),
+        UpdateSchedule::DailyAt(18, 0),
+    ];
+}
+
+impl Default for UpdateSchedule {
+    fn default() -> UpdateSchedule {
+        UpdateSchedule::Off
+    }
+}
+
+impl std::fmt::Display for UpdateSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpdateSchedule::Off => write!(f, "Off"),
+            UpdateSchedule::Hours(1) => write!(f, "Every hour"),
+            UpdateSchedule::Hours(hours) => write!(f, "Every {} hours", hours),
+            UpdateSchedule::DailyAt(hour, minute) => {
+                write!(f, "Daily at {:02}:{:02}", hour, minute)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd(2021, 3, day).and_hms(hour, minute, 0)
+    }
+
+    #[test]
+    fn test_next_run() {
+        let mut updates = ScheduledUpdates::default();
+        assert_eq!(updates.next_run(at(1, 12, 0)), None);
+
+        updates.schedule = UpdateSchedule::Hours(6);
+        assert_eq!(updates.next_run(at(1, 12, 0)), Some(at(1, 18, 0)));
+
+        updates.last_run = Some(at(1, 21, 30));
+        assert_eq!(updates.next_run(at(1, 12, 0)), Some(at(2, 3, 30)));
+
+        updates.schedule = UpdateSchedule::DailyAt(5, 0);
+        assert_eq!(updates.next_run(at(1, 12, 0)), Some(at(2, 5, 0)));
+
+        updates.last_run = Some(at(1, 4, 59));
+        assert_eq!(updates.next_run(at(1, 12, 0)), Some(at(1, 5, 0)));
+
+        // A run which was missed, eg. while the computer was off, is due right away.
+        assert!(updates.is_due(at(1, 12, 0), at(3, 9, 0)));
+        assert!(!updates.is_due(at(1, 12, 0), at(1, 4, 59)));
+
+        updates.schedule = UpdateSchedule::DailyAt(25, 0);
+        assert_eq!(updates.next_run(at(1, 12, 0)), None);
+    }
+}
@@ -0,0 +1,11 @@
+use crate::config::Flavor;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Struct for settings related to updating auras from wago.io.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Wago {
+    /// Account whose auras are updated, for each flavor.
+    #[serde(default)]
+    pub accounts: HashMap<Flavor, String>,
+}
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+/// Struct for settings related to the webhook notified about addon updates.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Webhook {
+    /// Url the events are posted to, nothing is posted if not set.
+    #[serde(default)]
+    pub url: Option<String>,
+
+    #[serde(default)]
+    pub format: WebhookFormat,
+
+    /// Events which are posted, all of them if empty.
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+}
+
+impl Webhook {
+    /// Returns true if `event` is posted.
+    pub fn is_subscribed(&self, event: WebhookEvent) -> bool {
+        self.url.is_some() && (self.events.is_empty() || self.events.contains(&event))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    /// The event as JSON, for any endpoint.
+    Json,
+    /// A message, for the webhooks of a Discord channel.
+    Discord,
+}
+
+impl WebhookFormat {
+    pub const ALL: [WebhookFormat; 2] = [WebhookFormat::Json, WebhookFormat::Discord];
+}
+
+impl Default for WebhookFormat {
+    fn default() -> WebhookFormat {
+        WebhookFormat::Json
+    }
+}
+
+impl std::fmt::Display for WebhookFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            WebhookFormat::Json => "json",
+            WebhookFormat::Discord => "discord",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum WebhookEvent {
+    UpdatesFound,
+    UpdatesApplied,
+}
+
+impl WebhookEvent {
+    pub const ALL: [WebhookEvent; 2] = [WebhookEvent::UpdatesFound, WebhookEvent::UpdatesApplied];
+}
+
+impl std::fmt::Display for WebhookEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            WebhookEvent::UpdatesFound => "updates-found",
+            WebhookEvent::UpdatesApplied => "updates-applied",
+        };
+        write!(f, "{}", s)
+    }
+}
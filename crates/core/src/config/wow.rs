@@ -1,5 +1,8 @@
+use crate::config::Addons;
+use crate::error::ClientError;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::str::FromStr;
 
 /// Struct for settings related to World of Warcraft.
 #[serde(default)]
@@ -10,6 +13,10 @@ pub struct Wow {
 
     #[serde(default)]
     pub flavor: Flavor,
+
+    /// Flavors which aren't managed by Ajour, even though they are installed.
+    #[serde(default)]
+    pub ignored_flavors: Vec<Flavor>,
 }
 
 impl Default for Wow {
@@ -17,43 +24,82 @@ impl Default for Wow {
         Wow {
             directory: None,
             flavor: Flavor::Retail,
+            ignored_flavors: vec![],
         }
     }
 }
 
+/// An installation of World of Warcraft, with its own addon settings.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Installation {
+    pub directory: PathBuf,
+
+    /// Flavor last selected for the installation.
+    #[serde(default)]
+    pub flavor: Flavor,
+
+    #[serde(default)]
+    pub addons: Addons,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Hash, PartialOrd, Ord)]
 pub enum Flavor {
     #[serde(alias = "retail", alias = "wow_retail")]
     Retail,
     RetailPTR,
     RetailBeta,
+    /// The experimental PTR of retail.
+    RetailXPTR,
+    /// The progression Classic, which moves on to each expansion.
     #[serde(alias = "classic", alias = "wow_classic")]
     Classic,
     ClassicPTR,
+    ClassicBeta,
+    /// The Classic which stays on the original game.
+    #[serde(alias = "classic_era")]
+    ClassicEra,
+    ClassicEraPTR,
 }
 
 impl Flavor {
-    pub const ALL: [Flavor; 5] = [
+    pub const ALL: [Flavor; 9] = [
         Flavor::Retail,
         Flavor::RetailPTR,
         Flavor::RetailBeta,
+        Flavor::RetailXPTR,
         Flavor::Classic,
         Flavor::ClassicPTR,
+        Flavor::ClassicBeta,
+        Flavor::ClassicEra,
+        Flavor::ClassicEraPTR,
     ];
 
     /// Returns flavor `String` in CurseForge format
     pub fn curse_format(self) -> String {
-        match self {
-            Flavor::Retail | Flavor::RetailPTR | Flavor::RetailBeta => "wow_retail".to_owned(),
-            Flavor::Classic | Flavor::ClassicPTR => "wow_classic".to_owned(),
+        match self.base_flavor() {
+            Flavor::Classic => "wow_burning_crusade".to_owned(),
+            Flavor::ClassicEra => "wow_classic".to_owned(),
+            _ => "wow_retail".to_owned(),
         }
     }
 
     /// Returns `Flavor` which self relates to.
     pub fn base_flavor(self) -> Flavor {
         match self {
-            Flavor::Retail | Flavor::RetailPTR | Flavor::RetailBeta => Flavor::Retail,
-            Flavor::Classic | Flavor::ClassicPTR => Flavor::Classic,
+            Flavor::Retail | Flavor::RetailPTR | Flavor::RetailBeta | Flavor::RetailXPTR => {
+                Flavor::Retail
+            }
+            Flavor::Classic | Flavor::ClassicPTR | Flavor::ClassicBeta => Flavor::Classic,
+            Flavor::ClassicEra | Flavor::ClassicEraPTR => Flavor::ClassicEra,
+        }
+    }
+
+    /// Returns the `Flavor` addons are listed under in the catalog, which only
+    /// knows of retail and classic.
+    pub fn catalog_flavor(self) -> Flavor {
+        match self.base_flavor() {
+            Flavor::Retail => Flavor::Retail,
+            _ => Flavor::Classic,
         }
     }
 
@@ -63,8 +109,12 @@ impl Flavor {
             Flavor::Retail => "_retail_".to_owned(),
             Flavor::RetailPTR => "_ptr_".to_owned(),
             Flavor::RetailBeta => "_beta_".to_owned(),
+            Flavor::RetailXPTR => "_xptr_".to_owned(),
             Flavor::Classic => "_classic_".to_owned(),
             Flavor::ClassicPTR => "_classic_ptr_".to_owned(),
+            Flavor::ClassicBeta => "_classic_beta_".to_owned(),
+            Flavor::ClassicEra => "_classic_era_".to_owned(),
+            Flavor::ClassicEraPTR => "_classic_era_ptr_".to_owned(),
         }
     }
 }
@@ -84,9 +134,36 @@ impl std::fmt::Display for Flavor {
                 Flavor::Retail => "Retail",
                 Flavor::RetailPTR => "Retail PTR",
                 Flavor::RetailBeta => "Retail Beta",
+                Flavor::RetailXPTR => "Retail XPTR",
                 Flavor::Classic => "Classic",
                 Flavor::ClassicPTR => "Classic PTR",
+                Flavor::ClassicBeta => "Classic Beta",
+                Flavor::ClassicEra => "Classic Era",
+                Flavor::ClassicEraPTR => "Classic Era PTR",
             }
         )
     }
 }
+
+impl FromStr for Flavor {
+    type Err = ClientError;
+
+    /// Parses a flavor from the names used on the command line, eg. `retail` or `classic_ptr`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "retail" | "wow_retail" => Ok(Flavor::Retail),
+            "retail_ptr" | "ptr" => Ok(Flavor::RetailPTR),
+            "retail_beta" | "beta" => Ok(Flavor::RetailBeta),
+            "retail_xptr" | "xptr" => Ok(Flavor::RetailXPTR),
+            "classic" | "wow_classic" => Ok(Flavor::Classic),
+            "classic_ptr" => Ok(Flavor::ClassicPTR),
+            "classic_beta" => Ok(Flavor::ClassicBeta),
+            "classic_era" | "era" => Ok(Flavor::ClassicEra),
+            "classic_era_ptr" | "era_ptr" => Ok(Flavor::ClassicEraPTR),
+            _ => Err(ClientError::Custom(format!(
+                "Unknown flavor '{}'. Valid flavors are: retail, retail_ptr, retail_beta, retail_xptr, classic, classic_ptr, classic_beta, classic_era, classic_era_ptr",
+                s
+            ))),
+        }
+    }
+}
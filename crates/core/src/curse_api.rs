@@ -1,9 +1,9 @@
 use crate::{
-    addon::Addon,
+    addon::{Addon, AddonDetails},
     config::Flavor,
     error::ClientError,
-    network::{post_json_async, request_async},
-    utility::{regex_html_tags_to_newline, regex_html_tags_to_space, truncate},
+    network::{post_json_async, request_async, status_error},
+    utility::format_changelog,
     Result,
 };
 use isahc::prelude::*;
@@ -33,10 +33,31 @@ pub struct File {
     pub release_type: u32,
     pub game_version_flavor: Option<String>,
     pub modules: Vec<Module>,
+    /// Fingerprint of the release file, used to verify the download.
+    #[serde(default)]
+    pub package_fingerprint: Option<u32>,
+    #[serde(default)]
+    pub dependencies: Vec<Dependency>,
     pub is_alternate: bool,
     pub game_version: Vec<String>,
 }
 
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dependency {
+    pub addon_id: u32,
+    #[serde(rename = "type")]
+    pub type_field: u32,
+}
+
+impl Dependency {
+    /// Curse marks dependencies that are required to run the addon with type 3,
+    /// as opposed to embedded libraries and optional dependencies.
+    pub fn is_required(&self) -> bool {
+        self.type_field == 3
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Module {
@@ -114,10 +135,11 @@ pub async fn fetch_remote_packages_by_fingerprint(fingerprints: &[u32]) -> Resul
         let fingerprint_info = resp.json()?;
         Ok(fingerprint_info)
     } else {
-        Err(ClientError::Custom(format!(
-            "Couldn't fetch details for addon. Server returned: {}",
-            resp.text()?
-        )))
+        Err(status_error(
+            "CurseForge",
+            "fetch details for addon",
+            &mut resp,
+        ))
     }
 }
 
@@ -128,10 +150,53 @@ pub async fn fetch_remote_packages_by_ids(curse_ids: &[u32]) -> Result<Vec<Packa
         let packages = resp.json()?;
         Ok(packages)
     } else {
-        Err(ClientError::Custom(format!(
-            "Couldn't fetch details for addon. Server returned: {}",
-            resp.text()?
-        )))
+        Err(status_error(
+            "CurseForge",
+            "fetch details for addon",
+            &mut resp,
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AddonInfo {
+    summary: String,
+    download_count: f64,
+    authors: Vec<Author>,
+    attachments: Vec<Attachment>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Author {
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Attachment {
+    url: String,
+}
+
+pub async fn fetch_addon_details(id: u32) -> Result<AddonDetails> {
+    let url = format!("{}/addon/{}", API_ENDPOINT, id);
+    let client = HttpClient::builder().build().unwrap();
+    let mut resp = request_async(&client, &url, vec![], Some(30)).await?;
+
+    if resp.status().is_success() {
+        let info: AddonInfo = resp.json()?;
+
+        Ok(AddonDetails {
+            summary: Some(info.summary),
+            authors: info.authors.into_iter().map(|a| a.name).collect(),
+            download_count: Some(info.download_count as u64),
+            screenshots: info.attachments.into_iter().map(|a| a.url).collect(),
+        })
+    } else {
+        Err(status_error(
+            "CurseForge",
+            "fetch details for addon",
+            &mut resp,
+        ))
     }
 }
 
@@ -143,13 +208,7 @@ pub async fn fetch_changelog(id: u32, file_id: i64) -> Result<(String, String)>
     if resp.status().is_success() {
         let changelog: String = resp.text()?;
 
-        let c = regex_html_tags_to_newline()
-            .replace_all(&changelog, "\n")
-            .to_string();
-        let c = regex_html_tags_to_space().replace_all(&c, "").to_string();
-        let c = truncate(&c, 2500).to_string();
-
-        return Ok((c, url));
+        return Ok((format_changelog(&changelog), url));
     }
 
     Ok(("No changelog found.".to_owned(), url))
@@ -163,10 +222,11 @@ pub async fn fetch_game_info() -> Result<GameInfo> {
         let game_info = resp.json()?;
         Ok(game_info)
     } else {
-        Err(ClientError::Custom(format!(
-            "Coudn't fetch game information. Server returned: {}",
-            resp.text()?
-        )))
+        Err(status_error(
+            "CurseForge",
+            "fetch game information",
+            &mut resp,
+        ))
     }
 }
 
@@ -0,0 +1,485 @@
+use crate::addon::Addon;
+use crate::config::{Config, Flavor, SyncRemote};
+use crate::fs::{config_dir, detect::installed_flavors};
+use crate::game::{client_builds, running_flavors, ClientBuild, Compatibility};
+use crate::logs::log_file;
+use crate::Result;
+
+use chrono::Local;
+use std::collections::HashMap;
+use std::fmt::{self, Write as _};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Failed,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warning => "warning",
+            CheckStatus::Failed => "failed",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Outcome of checking a part of the setup Ajour depends on.
+#[derive(Debug, Clone)]
+pub struct HealthCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl HealthCheck {
+    fn new(name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        HealthCheck {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+impl fmt::Display for HealthCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}: {}", self.status, self.name, self.detail)
+    }
+}
+
+/// Checks the directories Ajour reads and writes, and whether the game is running.
+pub fn health_checks(config: &Config) -> Vec<HealthCheck> {
+    let mut checks = vec![];
+
+    checks.push(match &config.wow.directory {
+        Some(directory) if directory.is_dir() => HealthCheck::new(
+            "World of Warcraft directory",
+            CheckStatus::Ok,
+            directory.display().to_string(),
+        ),
+        Some(directory) => HealthCheck::new(
+            "World of Warcraft directory",
+            CheckStatus::Failed,
+            format!("{} doesn't exist", directory.display()),
+        ),
+        None => HealthCheck::new(
+            "World of Warcraft directory",
+            CheckStatus::Failed,
+            "not chosen yet",
+        ),
+    });
+
+    if let Some(directory) = &config.wow.directory {
+        let installed = installed_flavors(directory);
+
+        checks.push(if installed.is_empty() {
+            HealthCheck::new(
+                "Flavors",
+                CheckStatus::Failed,
+                "none found, the directory should contain eg. _retail_",
+            )
+        } else {
+            HealthCheck::new("Flavors", CheckStatus::Ok, join(&installed))
+        });
+
+        for flavor in config.managed_flavors() {
+            if !installed.contains(&flavor) {
+                continue;
+            }
+
+            let name = format!("AddOns directory ({})", flavor);
+            checks.push(match config.get_addon_directory_for_flavor(&flavor) {
+                Some(directory) => writable_check(name, &directory),
+                None => HealthCheck::new(name, CheckStatus::Failed, "not found"),
+            });
+        }
+
+        let builds = client_builds(directory);
+        for flavor in config.managed_flavors() {
+            if !installed.contains(&flavor) {
+                continue;
+            }
+
+            let name = format!("Game client ({})", flavor);
+            checks.push(match builds.get(&flavor) {
+                Some(build) => HealthCheck::new(
+                    name,
+                    CheckStatus::Ok,
+                    format!("{}, interface {}", build.version, build.interface),
+                ),
+                None => HealthCheck::new(
+                    name,
+                    CheckStatus::Warning,
+                    "version unknown, addons can't be checked against it",
+                ),
+            });
+        }
+    }
+
+    checks.push(writable_check("Config directory", &config_dir()));
+
+    if let Some(directory) = &config.backup_directory {
+        checks.push(writable_check("Backup directory", directory));
+    }
+
+    let running = running_flavors();
+    checks.push(if running.is_empty() {
+        HealthCheck::new("Game", CheckStatus::Ok, "not running")
+    } else {
+        HealthCheck::new(
+            "Game",
+            CheckStatus::Warning,
+            format!(
+                "running for {}, addons can't be updated meanwhile",
+                join(&running)
+            ),
+        )
+    });
+
+    if config.offline {
+        checks.push(HealthCheck::new(
+            "Network",
+            CheckStatus::Warning,
+            "offline mode is on, no updates are checked",
+        ));
+    }
+
+    checks
+}
+
+/// Checks the `## Interface` of `addons` against the client of their flavor, listing
+/// the ones the game flags as out of date or which are made for another version of it.
+pub fn compatibility_checks(
+    builds: &HashMap<Flavor, ClientBuild>,
+    addons: &[(Flavor, Vec<Addon>)],
+) -> Vec<HealthCheck> {
+    let mut checks = vec![];
+
+    for (flavor, addons) in addons {
+        let build = match builds.get(flavor) {
+            Some(build) => build,
+            None => continue,
+        };
+
+        let incompatible = addons
+            .iter()
+            .filter_map(|addon| {
+                let interface = addon.interface()?;
+
+                match build.compatibility(interface) {
+                    Compatibility::Compatible => None,
+                    compatibility => Some(format!(
+                        "{} ({}, {})",
+                        addon.title(),
+                        interface,
+                        compatibility
+                    )),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let name = format!("Addon compatibility ({})", flavor);
+        checks.push(if incompatible.is_empty() {
+            HealthCheck::new(
+                name,
+                CheckStatus::Ok,
+                format!("all addons are made for interface {}", build.interface),
+            )
+        } else {
+            HealthCheck::new(
+                name,
+                CheckStatus::Warning,
+                format!(
+                    "the game is at interface {}, it might not load {}",
+                    build.interface,
+                    incompatible.join(", ")
+                ),
+            )
+        });
+    }
+
+    checks
+}
+
+/// Checks if a file can be created in `directory`.
+fn writable_check(name: impl Into<String>, directory: &Path) -> HealthCheck {
+    if !directory.is_dir() {
+        return HealthCheck::new(
+            name,
+            CheckStatus::Failed,
+            format!("{} doesn't exist", directory.display()),
+        );
+    }
+
+    let probe = directory.join(".ajour_write_check");
+    let result = File::create(&probe).and_then(|_| std::fs::remove_file(&probe));
+
+    match result {
+        Ok(_) => HealthCheck::new(name, CheckStatus::Ok, directory.display().to_string()),
+        Err(e) => HealthCheck::new(
+            name,
+            CheckStatus::Failed,
+            format!("{} isn't writable: {}", directory.display(), e),
+        ),
+    }
+}
+
+fn join(flavors: &[Flavor]) -> String {
+    flavors
+        .iter()
+        .map(Flavor::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Replaces paths and account names in text which is shared in bug reports.
+pub struct Redactor {
+    replacements: Vec<(String, String)>,
+}
+
+impl Redactor {
+    pub fn new(config: &Config) -> Redactor {
+        let mut replacements = vec![];
+
+        for (idx, directory) in config.installation_directories().iter().enumerate() {
+            replacements.push((
+                directory.display().to_string(),
+                format!("<wow-{}>", idx + 1),
+            ));
+        }
+
+        if let Some(directory) = &config.backup_directory {
+            replacements.push((directory.display().to_string(), "<backups>".to_string()));
+        }
+
+        replacements.push((config_dir().display().to_string(), "<config>".to_string()));
+
+        if let Some(home) = dirs_next::home_dir() {
+            replacements.push((home.display().to_string(), "~".to_string()));
+        }
+
+        let mut accounts = config.wago.accounts.values().cloned().collect::<Vec<_>>();
+        for flavor in Flavor::ALL.iter() {
+            if let Some(wtf) = config.get_wtf_directory_for_flavor(flavor) {
+                if let Ok(entries) = std::fs::read_dir(wtf.join("Account")) {
+                    accounts.extend(
+                        entries
+                            .flatten()
+                            .filter(|e| e.path().is_dir())
+                            .filter_map(|e| e.file_name().into_string().ok()),
+                    );
+                }
+            }
+        }
+        accounts.sort();
+        accounts.dedup();
+
+        for (idx, account) in accounts.into_iter().enumerate() {
+            replacements.push((account, format!("<account-{}>", idx + 1)));
+        }
+
+        Redactor::with_replacements(replacements)
+    }
+
+    fn with_replacements(mut replacements: Vec<(String, String)>) -> Redactor {
+        // Longer values first, so a directory is replaced before its parent.
+        replacements.retain(|(value, _)| !value.is_empty());
+        replacements.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+
+        Redactor { replacements }
+    }
+
+    pub fn redact(&self, text: &str) -> String {
+        self.replacements
+            .iter()
+            .fold(text.to_string(), |text, (value, replacement)| {
+                text.replace(value, replacement)
+            })
+    }
+}
+
+/// The config without tokens and proxy, which can hold credentials.
+fn sanitized_config(config: &Config) -> Result<String> {
+    let mut config = config.clone();
+
+    for value in [
+        &mut config.tokens.curse,
+        &mut config.tokens.github,
+        &mut config.tokens.wago,
+        &mut config.proxy,
+    ]
+    .iter_mut()
+    {
+        if value.is_some() {
+            **value = Some("<redacted>".to_string());
+        }
+    }
+
+    // Webhook urls hold the secret to post with.
+    if config.webhook.url.is_some() {
+        config.webhook.url = Some("<redacted>".to_string());
+    }
+
+    if let Some(SyncRemote::WebDav { password, .. }) = &mut config.cloud_sync.remote {
+        if password.is_some() {
+            *password = Some("<redacted>".to_string());
+        }
+    }
+
+    Ok(serde_yaml::to_string(&config)?)
+}
+
+fn environment(config: &Config, version: &str) -> String {
+    let mut text = String::new();
+
+    let _ = writeln!(text, "Ajour {}", version);
+    let _ = writeln!(
+        text,
+        "OS: {} ({})",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+
+    if let Some(directory) = &config.wow.directory {
+        let _ = writeln!(
+            text,
+            "Flavors found: {}",
+            join(&installed_flavors(directory))
+        );
+    }
+    let _ = writeln!(text, "Flavors managed: {}", join(&config.managed_flavors()));
+    if let Some(directory) = &config.wow.directory {
+        let mut builds = client_builds(directory).into_iter().collect::<Vec<_>>();
+        builds.sort_by_key(|(flavor, _)| *flavor);
+
+        let builds = builds
+            .iter()
+            .map(|(flavor, build)| format!("{} {}", flavor, build.version))
+            .collect::<Vec<_>>();
+        let _ = writeln!(text, "Game clients: {}", builds.join(", "));
+    }
+    let _ = writeln!(text, "Game running for: {}", join(&running_flavors()));
+
+    text
+}
+
+fn inventory(addons: &[(Flavor, Vec<Addon>)]) -> String {
+    let mut text = String::new();
+
+    for (flavor, addons) in addons {
+        let _ = writeln!(text, "## {} ({} addons)", flavor, addons.len());
+
+        for addon in addons {
+            let _ = writeln!(
+                text,
+                "{} {} [{}] {} (interface {})",
+                addon.title(),
+                addon.version().unwrap_or("-"),
+                addon
+                    .active_repository
+                    .map(|r| r.to_string())
+                    .unwrap_or_else(|| "unknown".to_string()),
+                addon.primary_folder_id,
+                addon
+                    .interface()
+                    .map(|i| i.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+            );
+        }
+
+        text.push('\n');
+    }
+
+    text
+}
+
+/// Writes a zip for bug reports to `destination`, holding the log, the config,
+/// the installed addons, the environment and the outcome of the health checks.
+/// Paths and account names are redacted, and tokens left out.
+///
+/// Returns the path of the zip.
+pub async fn create_bundle(
+    config: Config,
+    addons: Vec<(Flavor, Vec<Addon>)>,
+    version: String,
+    destination: PathBuf,
+) -> Result<PathBuf> {
+    let redactor = Redactor::new(&config);
+
+    let builds = config
+        .wow
+        .directory
+        .as_deref()
+        .map(client_builds)
+        .unwrap_or_default();
+
+    let mut checks = health_checks(&config);
+    checks.extend(compatibility_checks(&builds, &addons));
+
+    let checks = checks
+        .iter()
+        .map(HealthCheck::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let log = std::fs::read(log_file())
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default();
+
+    let files = [
+        ("health.txt", checks),
+        ("environment.txt", environment(&config, &version)),
+        ("config.yml", sanitized_config(&config)?),
+        ("addons.txt", inventory(&addons)),
+        ("ajour.log", log),
+    ];
+
+    let path = destination.join(format!(
+        "ajour_diagnostics_{}.zip",
+        Local::now().format("%Y-%m-%d_%H-%M-%S")
+    ));
+
+    let mut zip_writer = ZipWriter::new(BufWriter::new(File::create(&path)?));
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for (name, text) in files.iter() {
+        zip_writer.start_file(*name, options)?;
+        zip_writer.write_all(redactor.redact(text).as_bytes())?;
+    }
+
+    zip_writer.finish()?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        let redactor = Redactor::with_replacements(vec![
+            ("/home/jane".to_string(), "~".to_string()),
+            (
+                "/home/jane/Games/World of Warcraft".to_string(),
+                "<wow-1>".to_string(),
+            ),
+            ("JANEDOE".to_string(), "<account-1>".to_string()),
+            (String::new(), "<empty>".to_string()),
+        ]);
+
+        assert_eq!(
+            redactor.redact(
+                "/home/jane/Games/World of Warcraft/_retail_/WTF/Account/JANEDOE and /home/jane/.config"
+            ),
+            "<wow-1>/_retail_/WTF/Account/<account-1> and ~/.config"
+        );
+    }
+}
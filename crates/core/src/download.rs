@@ -0,0 +1,336 @@
+use crate::{config::Flavor, error::ClientError, Result};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Amount of downloads running at a time, others wait in the queue.
+pub const DEFAULT_CONCURRENT_DOWNLOADS: usize = 4;
+
+/// How often a queued download checks if it can start.
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Identifies a download by the flavor and primary folder id of the addon.
+pub type DownloadKey = (Flavor, String);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadStatus {
+    Queued,
+    Downloading,
+    Paused,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadProgress {
+    pub status: DownloadStatus,
+    pub bytes: u64,
+    pub total: Option<u64>,
+}
+
+impl DownloadProgress {
+    /// Returns how much has been downloaded between `0.0` and `1.0`, if the size
+    /// of the download is known.
+    pub fn fraction(&self) -> Option<f32> {
+        self.total
+            .filter(|total| *total > 0)
+            .map(|total| (self.bytes as f32 / total as f32).min(1.0))
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    /// Tells a download apart from a later one of the same addon.
+    id: u64,
+    progress: DownloadProgress,
+    /// Set once the download got a place among the running downloads.
+    started: bool,
+    paused: bool,
+    cancelled: bool,
+}
+
+#[derive(Debug)]
+struct Inner {
+    max_concurrent: usize,
+    running: usize,
+    next_id: u64,
+    entries: HashMap<DownloadKey, Entry>,
+}
+
+/// Queues addon downloads so only a few run at a time, and keeps track of their
+/// progress so it can be shown while they run.
+#[derive(Debug, Clone)]
+pub struct DownloadManager {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl Default for DownloadManager {
+    fn default() -> Self {
+        DownloadManager::new(DEFAULT_CONCURRENT_DOWNLOADS)
+    }
+}
+
+impl DownloadManager {
+    pub fn new(max_concurrent: usize) -> Self {
+        DownloadManager {
+            inner: Arc::new(Mutex::new(Inner {
+                max_concurrent: max_concurrent.max(1),
+                running: 0,
+                next_id: 0,
+                entries: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Adds a download to the queue. It's removed again once the returned
+    /// `Download` is dropped.
+    pub fn queue(&self, key: DownloadKey) -> Download {
+        let mut inner = self.inner.lock().unwrap();
+
+        let id = inner.next_id;
+        inner.next_id += 1;
+
+        inner.entries.insert(
+            key.clone(),
+            Entry {
+                id,
+                progress: DownloadProgress {
+                    status: DownloadStatus::Queued,
+                    bytes: 0,
+                    total: None,
+                },
+                started: false,
+                paused: false,
+                cancelled: false,
+            },
+        );
+
+        Download {
+            key,
+            id,
+            running: false,
+            manager: self.clone(),
+        }
+    }
+
+    pub fn progress(&self, key: &DownloadKey) -> Option<DownloadProgress> {
+        let inner = self.inner.lock().unwrap();
+
+        inner.entries.get(key).map(|entry| entry.progress.clone())
+    }
+
+    /// Returns true if any downloads are queued or running.
+    pub fn is_active(&self) -> bool {
+        !self.inner.lock().unwrap().entries.is_empty()
+    }
+
+    /// Pauses a queued or running download. A running download closes its
+    /// connection and keeps its place among the running downloads, a queued one
+    /// doesn't start until it's resumed.
+    pub fn pause(&self, key: &DownloadKey) {
+        let mut inner = self.inner.lock().unwrap();
+
+        if let Some(entry) = inner.entries.get_mut(key) {
+            entry.paused = true;
+            entry.progress.status = DownloadStatus::Paused;
+        }
+    }
+
+    /// Resumes a paused download, which continues from the bytes downloaded so far.
+    pub fn resume(&self, key: &DownloadKey) {
+        let mut inner = self.inner.lock().unwrap();
+
+        if let Some(entry) = inner.entries.get_mut(key) {
+            entry.paused = false;
+            entry.progress.status = if entry.started {
+                DownloadStatus::Downloading
+            } else {
+                DownloadStatus::Queued
+            };
+        }
+    }
+
+    /// Cancels a queued, running or paused download.
+    ///
+    /// The `.part` file downloaded so far is kept, so downloading the same url
+    /// again continues from it with a `Range` request, see `download_file`.
+    pub fn cancel(&self, key: &DownloadKey) {
+        let mut inner = self.inner.lock().unwrap();
+
+        if let Some(entry) = inner.entries.get_mut(key) {
+            entry.cancelled = true;
+        }
+    }
+}
+
+/// A download in the queue of a `DownloadManager`.
+#[derive(Debug)]
+pub struct Download {
+    key: DownloadKey,
+    id: u64,
+    running: bool,
+    manager: DownloadManager,
+}
+
+impl Download {
+    /// Waits until there is room for the download to run, and it isn't paused.
+    pub async fn start(&mut self) -> Result<()> {
+        loop {
+            {
+                let mut guard = self.manager.inner.lock().unwrap();
+                let inner = &mut *guard;
+
+                match inner.entries.get_mut(&self.key) {
+                    Some(entry) if entry.id == self.id && !entry.cancelled => {
+                        if !entry.paused && inner.running < inner.max_concurrent {
+                            entry.progress.status = DownloadStatus::Downloading;
+                            entry.started = true;
+                            inner.running += 1;
+                            self.running = true;
+
+                            return Ok(());
+                        }
+                    }
+                    _ => return Err(ClientError::Cancelled),
+                }
+            }
+
+            async_std::task::sleep(QUEUE_POLL_INTERVAL).await;
+        }
+    }
+
+    pub fn set_progress(&self, bytes: u64, total: Option<u64>) {
+        let mut inner = self.manager.inner.lock().unwrap();
+
+        if let Some(entry) = inner.entries.get_mut(&self.key) {
+            if entry.id == self.id {
+                entry.progress.bytes = bytes;
+                entry.progress.total = total;
+            }
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        let inner = self.manager.inner.lock().unwrap();
+
+        inner
+            .entries
+            .get(&self.key)
+            .map_or(false, |entry| entry.id == self.id && entry.paused)
+    }
+
+    /// Waits until the download is resumed. Errors if it's cancelled meanwhile.
+    pub async fn wait_while_paused(&self) -> Result<()> {
+        loop {
+            self.check_cancelled()?;
+
+            if !self.is_paused() {
+                return Ok(());
+            }
+
+            async_std::task::sleep(QUEUE_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Errors if the download has been cancelled.
+    pub fn check_cancelled(&self) -> Result<()> {
+        let inner = self.manager.inner.lock().unwrap();
+
+        match inner.entries.get(&self.key) {
+            Some(entry) if entry.id == self.id && !entry.cancelled => Ok(()),
+            _ => Err(ClientError::Cancelled),
+        }
+    }
+}
+
+impl Drop for Download {
+    fn drop(&mut self) {
+        let mut inner = self.manager.inner.lock().unwrap();
+
+        if self.running {
+            inner.running -= 1;
+        }
+
+        if inner.entries.get(&self.key).map(|entry| entry.id) == Some(self.id) {
+            inner.entries.remove(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_only_affects_latest_download() {
+        let manager = DownloadManager::new(1);
+        let key = (Flavor::Retail, "Addon".to_string());
+
+        let first = manager.queue(key.clone());
+        manager.cancel(&key);
+        assert!(first.check_cancelled().is_err());
+
+        // Queueing the addon again replaces the cancelled download.
+        let second = manager.queue(key.clone());
+        assert!(second.check_cancelled().is_ok());
+
+        drop(first);
+        assert!(manager.progress(&key).is_some());
+
+        drop(second);
+        assert!(!manager.is_active());
+    }
+
+    #[test]
+    fn test_pause_and_resume() {
+        let manager = DownloadManager::new(1);
+        let key = (Flavor::Retail, "Addon".to_string());
+
+        let mut download = manager.queue(key.clone());
+        manager.pause(&key);
+        assert!(download.is_paused());
+        assert_eq!(
+            manager.progress(&key).map(|p| p.status),
+            Some(DownloadStatus::Paused)
+        );
+
+        // A paused download isn't cancelled, and starts once resumed.
+        assert!(download.check_cancelled().is_ok());
+        manager.resume(&key);
+        assert_eq!(
+            manager.progress(&key).map(|p| p.status),
+            Some(DownloadStatus::Queued)
+        );
+
+        async_std::task::block_on(download.start()).unwrap();
+        manager.pause(&key);
+        manager.resume(&key);
+        assert_eq!(
+            manager.progress(&key).map(|p| p.status),
+            Some(DownloadStatus::Downloading)
+        );
+
+        manager.pause(&key);
+        manager.cancel(&key);
+        assert!(async_std::task::block_on(download.wait_while_paused()).is_err());
+    }
+
+    #[test]
+    fn test_queue_limit() {
+        let manager = DownloadManager::new(1);
+        let first_key = (Flavor::Retail, "First".to_string());
+        let second_key = (Flavor::Retail, "Second".to_string());
+
+        let mut first = manager.queue(first_key.clone());
+        let _second = manager.queue(second_key.clone());
+
+        async_std::task::block_on(first.start()).unwrap();
+
+        assert_eq!(
+            manager.progress(&first_key).map(|p| p.status),
+            Some(DownloadStatus::Downloading)
+        );
+        assert_eq!(
+            manager.progress(&second_key).map(|p| p.status),
+            Some(DownloadStatus::Queued)
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use std::{fmt, path::PathBuf};
+use crate::config::Flavor;
+use std::{fmt, io::ErrorKind, path::PathBuf};
 
 #[derive(Debug)]
 pub enum ClientError {
@@ -12,12 +13,109 @@ pub enum ClientError {
     LoadFileDoesntExist(PathBuf),
     LogError(String),
     FingerprintError(String),
+    /// A repository is down or rate limiting us.
+    Unavailable(String),
+    /// Offline mode is on, and the response wasn't cached.
+    Offline,
+    /// A repository answered `action` with an error status.
+    Repository {
+        repository: String,
+        action: String,
+        status: u16,
+        message: String,
+    },
+    /// A repository refused the request, usually because of a missing or invalid API token.
+    Unauthorized {
+        repository: String,
+    },
+    /// A repository kept rate limiting us after all retries.
+    RateLimited {
+        repository: String,
+    },
+    /// The connection was closed before the whole file was downloaded.
+    IncompleteDownload {
+        url: String,
+        expected: u64,
+        received: u64,
+    },
+    /// A downloaded archive doesn't match the fingerprint exposed by the repository.
+    ChecksumMismatch {
+        path: PathBuf,
+        expected: u32,
+        actual: u32,
+    },
+    /// An archive which can't be installed, eg. without any addon folders.
+    InvalidArchive(String),
+    /// A file or response we couldn't make sense of.
+    ParseError(String),
+    /// Reading or writing `path` failed.
+    Filesystem {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    NoWowDirectory,
+    /// World of Warcraft is running for the flavor, so its addons are left alone.
+    GameRunning(Flavor),
+    Cancelled,
 }
 
 impl ClientError {
     pub fn fingerprint(e: impl fmt::Display) -> ClientError {
         ClientError::FingerprintError(format!("{}", e))
     }
+
+    /// Returns a `Filesystem` error, keeping the path the io error is about.
+    pub fn filesystem(path: impl Into<PathBuf>, error: std::io::Error) -> ClientError {
+        ClientError::Filesystem {
+            path: path.into(),
+            error,
+        }
+    }
+
+    /// Returns `true` if the request failed because the repository couldn't be
+    /// reached, rather than because of the request itself.
+    pub fn is_unavailable(&self) -> bool {
+        matches!(
+            self,
+            Self::NetworkError(_) | Self::Unavailable(_) | Self::Offline | Self::RateLimited { .. }
+        )
+    }
+
+    /// Returns what the user can do about the error, if there's anything.
+    pub fn suggestion(&self) -> Option<&'static str> {
+        let is_permission_denied = |e: &std::io::Error| e.kind() == ErrorKind::PermissionDenied;
+
+        match self {
+            Self::NetworkError(_) => Some("Check your internet connection or proxy settings."),
+            Self::Unavailable(_) => Some("Try again in a few minutes."),
+            Self::Offline => Some("Turn off offline mode in the settings."),
+            Self::Unauthorized { .. } => Some("Check the API tokens in the settings."),
+            Self::RateLimited { .. } => {
+                Some("Try again in a few minutes, or add an API token in the settings.")
+            }
+            Self::IncompleteDownload { .. } | Self::ChecksumMismatch { .. } => {
+                Some("Try updating the addon again.")
+            }
+            Self::IoError(e) | Self::Filesystem { error: e, .. } if is_permission_denied(e) => {
+                Some("Make sure Ajour is allowed to write to your World of Warcraft directory, and close the game if it's running.")
+            }
+            Self::NoWowDirectory => {
+                Some("Launch Ajour and choose your World of Warcraft directory in the settings.")
+            }
+            Self::GameRunning(_) => Some("Close the game and try again."),
+            _ => None,
+        }
+    }
+
+    /// Returns the error followed by the suggestion, if any, for showing to the user.
+    pub fn user_message(&self) -> String {
+        let message = self.to_string();
+
+        match self.suggestion() {
+            Some(suggestion) => format!("{}. {}", message.trim_end_matches('.'), suggestion),
+            None => message,
+        }
+    }
 }
 
 impl fmt::Display for ClientError {
@@ -36,6 +134,52 @@ impl fmt::Display for ClientError {
             Self::LoadFileDoesntExist(x) => write!(f, "file doesn't exist: {:?}", x),
             Self::LogError(x) => write!(f, "{}", x),
             Self::FingerprintError(x) => write!(f, "{}", x),
+            Self::Unavailable(x) => write!(f, "{}", x),
+            Self::Offline => write!(f, "Not available while Ajour is offline."),
+            Self::Repository {
+                repository,
+                action,
+                status,
+                message,
+            } => {
+                write!(f, "Couldn't {}, {} returned {}", action, repository, status)?;
+
+                if !message.is_empty() {
+                    write!(f, ": {}", message)?;
+                }
+
+                Ok(())
+            }
+            Self::Unauthorized { repository } => {
+                write!(f, "{} refused the request", repository)
+            }
+            Self::RateLimited { repository } => {
+                write!(f, "{} is rate limiting requests", repository)
+            }
+            Self::IncompleteDownload {
+                url,
+                expected,
+                received,
+            } => write!(
+                f,
+                "Download of {} stopped after {} of {} bytes",
+                url, received, expected
+            ),
+            Self::ChecksumMismatch {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Checksum mismatch for {:?}, expected fingerprint {} but got {}",
+                path, expected, actual
+            ),
+            Self::InvalidArchive(x) => write!(f, "{}", x),
+            Self::ParseError(x) => write!(f, "{}", x),
+            Self::Filesystem { path, error } => write!(f, "{}: {:?}", error, path),
+            Self::NoWowDirectory => write!(f, "No WoW directory set"),
+            Self::GameRunning(flavor) => write!(f, "World of Warcraft {} is running", flavor),
+            Self::Cancelled => write!(f, "Download was cancelled"),
         }
     }
 }
@@ -87,3 +231,35 @@ impl From<log::SetLoggerError> for ClientError {
         Self::LogError(format!("{:?}", error))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_message() {
+        let error = ClientError::Repository {
+            repository: "CurseForge".to_string(),
+            action: "fetch details for addon".to_string(),
+            status: 404,
+            message: String::new(),
+        };
+        assert_eq!(
+            error.user_message(),
+            "Couldn't fetch details for addon, CurseForge returned 404"
+        );
+
+        let error = ClientError::filesystem(
+            "AddOns/Details",
+            std::io::Error::from(ErrorKind::PermissionDenied),
+        );
+        assert!(error
+            .user_message()
+            .ends_with("close the game if it's running."));
+
+        assert_eq!(
+            ClientError::Offline.user_message(),
+            "Not available while Ajour is offline. Turn off offline mode in the settings."
+        );
+    }
+}
@@ -1,11 +1,21 @@
+use super::archive::{
+    cache_archive, installed_archive, previous_archive, verify_archive, CachedArchive,
+};
+use super::installed::record_install;
+use super::snapshot::{record_snapshot, snapshot_directory};
 use crate::{
     addon::{Addon, AddonFolder},
+    config::Flavor,
+    error::ClientError,
+    network::download_file,
     parse::parse_toc_path,
     Result,
 };
-use std::collections::HashSet;
+use isahc::HttpClient;
+use std::ffi::OsStr;
 use std::fs::remove_dir_all;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Deletes an Addon and all dependencies from disk.
 pub fn delete_addons(addon_folders: &[AddonFolder]) -> Result<()> {
@@ -21,43 +31,337 @@ pub fn delete_addons(addon_folders: &[AddonFolder]) -> Result<()> {
 
 /// Unzips an `Addon` archive, and once that is done, it moves the content
 /// to the `to_directory`.
-/// At the end it will cleanup and remove the archive.
+/// At the end it will move the archive into the archive cache, so the addon
+/// can be rolled back later.
 pub async fn install_addon(
     addon: &Addon,
+    flavor: Flavor,
     from_directory: &PathBuf,
     to_directory: &PathBuf,
 ) -> Result<Vec<AddonFolder>> {
     let zip_path = from_directory.join(&addon.primary_folder_id);
-    let mut zip_file = std::fs::File::open(&zip_path)?;
-    let mut archive = zip::ZipArchive::new(&mut zip_file)?;
 
-    // Get all new top level folders
-    let new_top_level_folders = archive
-        .file_names()
-        .filter_map(|name| name.split('/').next())
-        .collect::<HashSet<_>>();
+    // An addon updated twice in the same batch keeps the snapshot of its first update.
+    let snapshot = snapshot_directory(to_directory, addon);
+    let is_snapshotted = snapshot.exists();
 
-    // Remove all new top level addon folders.
-    for folder in new_top_level_folders {
-        let path = to_directory.join(&folder);
+    let addon_folders = extract_archive(
+        &zip_path,
+        to_directory,
+        Some(&snapshot).filter(|_| !is_snapshotted),
+    )?;
 
-        if path.exists() {
-            let _ = std::fs::remove_dir_all(path);
+    if !is_snapshotted {
+        if let Err(e) = record_snapshot(addon, flavor, &snapshot, &addon_folders) {
+            log::error!("failed to record snapshot of {}: {}", addon.title(), e);
+        }
+    }
+
+    // Cleanup
+    cache_archive(addon, flavor, &zip_path)?;
+
+    if let Err(e) = record_install(addon, flavor) {
+        log::error!("failed to record install of {}: {}", addon.title(), e);
+    }
+
+    Ok(addon_folders)
+}
+
+/// Reinstalls the newest cached archive of an `Addon` which is older than the
+/// version currently installed.
+pub async fn rollback_addon(
+    addon: &Addon,
+    flavor: Flavor,
+    to_directory: &PathBuf,
+) -> Result<(CachedArchive, Vec<AddonFolder>)> {
+    let archive = previous_archive(addon, flavor)?.ok_or_else(|| {
+        ClientError::Custom(format!(
+            "No previous version of {} is available to roll back to",
+            addon.title()
+        ))
+    })?;
+
+    if let Some(fingerprint) = archive.fingerprint {
+        verify_archive(&archive.path, fingerprint)?;
+    }
+
+    let addon_folders = extract_archive(&archive.path, to_directory, None)?;
+
+    Ok((archive, addon_folders))
+}
+
+/// Reinstalls the cached archive of the version of an `Addon` which is installed,
+/// eg. to repair files which were modified or removed since.
+pub async fn reinstall_addon(
+    addon: &Addon,
+    flavor: Flavor,
+    to_directory: &PathBuf,
+) -> Result<Vec<AddonFolder>> {
+    let archive = installed_archive(addon, flavor)?.ok_or_else(|| {
+        ClientError::Custom(format!(
+            "The installed version of {} isn't cached to reinstall",
+            addon.title()
+        ))
+    })?;
+
+    if let Some(fingerprint) = archive.fingerprint {
+        verify_archive(&archive.path, fingerprint)?;
+    }
+
+    extract_archive(&archive.path, to_directory, None)
+}
+
+/// Installs an addon from an archive which isn't from a repository, eg. a beta build
+/// handed out by the author. The archive is left in place.
+///
+/// Errors if the archive doesn't contain at least one folder with a `.toc` file.
+pub async fn install_local_archive(
+    zip_path: &Path,
+    to_directory: &PathBuf,
+) -> Result<Vec<AddonFolder>> {
+    {
+        let mut zip_file = std::fs::File::open(&zip_path)?;
+        let archive = zip::ZipArchive::new(&mut zip_file)?;
+
+        let has_addon_folder = archive.file_names().any(|name| {
+            let components = name.split('/').collect::<Vec<_>>();
+
+            components.len() == 2
+                && Path::new(components[1])
+                    .extension()
+                    .map_or(false, |ext| ext == "toc")
+        });
+
+        if !has_addon_folder {
+            return Err(ClientError::InvalidArchive(
+                "Archive doesn't contain any addon folders".to_string(),
+            ));
         }
     }
 
+    extract_archive(zip_path, to_directory, None)
+}
+
+/// Installs an addon from a zip at `url`, which can either be a web address or a path
+/// on disk. Zips from the web are downloaded to `temp_directory` first.
+pub async fn install_from_url(
+    shared_client: &HttpClient,
+    url: &str,
+    temp_directory: &PathBuf,
+    to_directory: &PathBuf,
+) -> Result<Vec<AddonFolder>> {
+    let url = url.trim();
+
+    if url.starts_with("http://") || url.starts_with("https://") {
+        let zip_path = temp_directory.join("url-install.zip");
+
+        download_file(shared_client, url, &zip_path, None).await?;
+
+        let result = install_local_archive(&zip_path, to_directory).await;
+
+        // Cleanup
+        let _ = std::fs::remove_file(&zip_path);
+
+        result
+    } else {
+        let path = Path::new(url.trim_start_matches("file://"));
+
+        install_local_archive(path, to_directory).await
+    }
+}
+
+/// Extracts an addon archive into `to_directory`, replacing any existing folders
+/// contained in the archive. Returns the addon folders which were extracted.
+///
+/// The archive is extracted into a staging directory next to `to_directory` first,
+/// and existing folders are only replaced once everything has been extracted. If
+/// extraction fails, eg. because the disk is full, the installed addon is left as is.
+///
+/// The replaced folders are moved to `snapshot_directory` if given, so the update can
+/// be undone, and removed otherwise.
+fn extract_archive(
+    zip_path: &Path,
+    to_directory: &PathBuf,
+    snapshot_directory: Option<&PathBuf>,
+) -> Result<Vec<AddonFolder>> {
+    static STAGING_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    // Staging next to the addon directory keeps it on the same drive, so folders can
+    // be renamed into place.
+    let staging_root = to_directory
+        .parent()
+        .unwrap_or(to_directory)
+        .join(".ajour-staging");
+    let staging_directory = staging_root.join(format!(
+        "{}-{}",
+        std::process::id(),
+        STAGING_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let result = extract_staged(zip_path, to_directory, &staging_directory);
+
+    if let (Ok(_), Some(snapshot_directory)) = (&result, snapshot_directory) {
+        let replaced_directory = staging_directory.join("old");
+
+        if let Err(e) = move_replaced(&replaced_directory, snapshot_directory) {
+            log::error!("failed to snapshot replaced folders: {}", e);
+        }
+    }
+
+    // Removes the replaced folders, or the partial extraction if it failed.
+    let _ = remove_dir_all(&staging_directory);
+    let _ = std::fs::remove_dir(&staging_root);
+
+    result
+}
+
+fn extract_staged(
+    zip_path: &Path,
+    to_directory: &PathBuf,
+    staging_directory: &Path,
+) -> Result<Vec<AddonFolder>> {
+    let extracted_directory = staging_directory.join("new");
+    let replaced_directory = staging_directory.join("old");
+    for directory in [&extracted_directory, &replaced_directory].iter() {
+        std::fs::create_dir_all(directory).map_err(|e| ClientError::filesystem(*directory, e))?;
+    }
+
+    let toc_files = unzip(zip_path, &extracted_directory)?;
+
+    // Never replace a symlinked folder, since its target is usually an addon
+    // author's working copy.
+    for entry in std::fs::read_dir(&extracted_directory)? {
+        let name = entry?.file_name();
+        let target = to_directory.join(&name);
+
+        if target
+            .symlink_metadata()
+            .map_or(false, |m| m.file_type().is_symlink())
+        {
+            return Err(ClientError::Custom(format!(
+                "{} is a symlink and was left untouched",
+                target.display()
+            )));
+        }
+    }
+
+    // Swap the extracted folders into place one by one. If one of them can't be
+    // moved, the folders swapped so far are put back.
+    let mut swapped = vec![];
+    for entry in std::fs::read_dir(&extracted_directory)? {
+        let name = entry?.file_name();
+
+        if let Err(e) = swap_entry(
+            &name,
+            &extracted_directory,
+            &replaced_directory,
+            to_directory,
+        ) {
+            for name in swapped.iter().rev() {
+                let _ = remove_path(&to_directory.join(name));
+
+                let replaced = replaced_directory.join(name);
+                if replaced.exists() {
+                    let _ = std::fs::rename(&replaced, to_directory.join(name));
+                }
+            }
+
+            return Err(e);
+        }
+
+        swapped.push(name);
+    }
+
+    let toc_files = toc_files
+        .into_iter()
+        .map(|path| to_directory.join(path))
+        .collect::<Vec<_>>();
+    let addon_folders = toc_files.iter().filter_map(parse_toc_path).collect();
+
+    Ok(addon_folders)
+}
+
+/// Moves the existing `name` in `to_directory` aside, and the extracted one in its place.
+fn swap_entry(
+    name: &OsStr,
+    extracted_directory: &Path,
+    replaced_directory: &Path,
+    to_directory: &Path,
+) -> Result<()> {
+    let target = to_directory.join(name);
+    let replaced = replaced_directory.join(name);
+
+    // Folders are usually locked by antivirus software or the game itself.
+    let has_existing = target.exists();
+    if has_existing {
+        std::fs::rename(&target, &replaced).map_err(|e| ClientError::filesystem(&target, e))?;
+    }
+
+    if let Err(e) = std::fs::rename(extracted_directory.join(name), &target) {
+        if has_existing {
+            let _ = std::fs::rename(&replaced, &target);
+        }
+
+        return Err(ClientError::filesystem(&target, e));
+    }
+
+    Ok(())
+}
+
+/// Moves the folders replaced by an update into `snapshot_directory`.
+fn move_replaced(replaced_directory: &Path, snapshot_directory: &Path) -> std::io::Result<()> {
+    let mut entries = std::fs::read_dir(replaced_directory)?.peekable();
+
+    // Nothing to undo for a fresh install.
+    if entries.peek().is_none() {
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(snapshot_directory)?;
+
+    for entry in entries {
+        let name = entry?.file_name();
+        std::fs::rename(
+            replaced_directory.join(&name),
+            snapshot_directory.join(&name),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+/// Unzips the archive into `directory`. Returns the `.toc` files of top level folders,
+/// relative to `directory`.
+///
+/// Errors without extracting anything outside `directory` if an entry would end up
+/// outside of it, since archives come from third party CDNs.
+fn unzip(zip_path: &Path, directory: &Path) -> Result<Vec<PathBuf>> {
+    let mut zip_file = std::fs::File::open(&zip_path)?;
+    let mut archive = zip::ZipArchive::new(&mut zip_file)?;
+
     let mut toc_files = vec![];
 
     for i in 0..archive.len() {
         let mut file = archive.by_index(i)?;
-        let path = to_directory.join(file.sanitized_name());
+        let relative_path = entry_path(file.name())?;
+        if relative_path.as_os_str().is_empty() {
+            continue;
+        }
 
-        if let Some(ext) = path.extension() {
-            if let Ok(remainder) = path.strip_prefix(to_directory) {
-                if ext == "toc" && remainder.components().count() == 2 {
-                    toc_files.push(path.clone());
-                }
-            }
+        let path = directory.join(&relative_path);
+
+        if relative_path.extension().map_or(false, |ext| ext == "toc")
+            && relative_path.components().count() == 2
+        {
+            toc_files.push(relative_path.clone());
         }
 
         if file.is_dir() {
@@ -73,10 +377,113 @@ pub async fn install_addon(
         }
     }
 
-    // Cleanup
-    std::fs::remove_file(&zip_path)?;
+    Ok(toc_files)
+}
 
-    let addon_folders = toc_files.iter().filter_map(parse_toc_path).collect();
+/// Returns the path of an archive entry relative to the directory it's extracted to.
+///
+/// Absolute paths, drive letters and `..` components are rejected, since they could
+/// write outside of the directory.
+pub(super) fn entry_path(name: &str) -> Result<PathBuf> {
+    let invalid =
+        || ClientError::InvalidArchive(format!("Archive contains an invalid path: {}", name));
 
-    Ok(addon_folders)
+    if name.starts_with('/') || name.starts_with('\\') {
+        return Err(invalid());
+    }
+
+    let mut path = PathBuf::new();
+
+    for component in name.split(|c| c == '/' || c == '\\') {
+        match component {
+            "" | "." => {}
+            ".." => return Err(invalid()),
+            component if component.contains(':') => return Err(invalid()),
+            component => path.push(component),
+        }
+    }
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_entry_path() {
+        assert_eq!(
+            entry_path("Addon/Addon.toc").unwrap(),
+            PathBuf::from("Addon").join("Addon.toc")
+        );
+        assert_eq!(
+            entry_path("Addon\\Libs\\").unwrap(),
+            PathBuf::from("Addon").join("Libs")
+        );
+
+        assert!(entry_path("../Addon/Addon.toc").is_err());
+        assert!(entry_path("Addon/../../evil.lua").is_err());
+        assert!(entry_path("/etc/evil").is_err());
+        assert!(entry_path("\\evil").is_err());
+        assert!(entry_path("C:/Windows/evil.dll").is_err());
+        assert!(entry_path("./").unwrap().as_os_str().is_empty());
+    }
+
+    #[test]
+    fn test_extract_rejects_path_traversal() {
+        let directory = std::env::temp_dir().join(format!("ajour-zip-slip-{}", std::process::id()));
+        let addon_directory = directory.join("AddOns");
+        std::fs::create_dir_all(&addon_directory).unwrap();
+
+        let zip_path = directory.join("crafted.zip");
+        {
+            let mut zip = zip::ZipWriter::new(std::fs::File::create(&zip_path).unwrap());
+            let options = zip::write::FileOptions::default();
+
+            zip.start_file("Addon/Addon.toc", options).unwrap();
+            zip.write_all(b"## Title: Addon").unwrap();
+            zip.start_file("Addon/../../evil.lua", options).unwrap();
+            zip.write_all(b"print('evil')").unwrap();
+            zip.finish().unwrap();
+        }
+
+        let result = extract_archive(&zip_path, &addon_directory, None);
+
+        assert!(result.is_err());
+        assert!(!directory.join("evil.lua").exists());
+        // Nothing is installed from a rejected archive.
+        assert!(!addon_directory.join("Addon").exists());
+
+        let _ = remove_dir_all(&directory);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_extract_leaves_symlinks_alone() {
+        let directory = std::env::temp_dir().join(format!("ajour-symlink-{}", std::process::id()));
+        let addon_directory = directory.join("AddOns");
+        let checkout = directory.join("checkout");
+        std::fs::create_dir_all(&addon_directory).unwrap();
+        std::fs::create_dir_all(&checkout).unwrap();
+        std::fs::write(checkout.join("Addon.toc"), "## Title: Working copy").unwrap();
+        std::os::unix::fs::symlink(&checkout, addon_directory.join("Addon")).unwrap();
+
+        let zip_path = directory.join("release.zip");
+        {
+            let mut zip = zip::ZipWriter::new(std::fs::File::create(&zip_path).unwrap());
+            zip.start_file("Addon/Addon.toc", Default::default())
+                .unwrap();
+            zip.write_all(b"## Title: Release").unwrap();
+            zip.finish().unwrap();
+        }
+
+        assert!(extract_archive(&zip_path, &addon_directory, None).is_err());
+        assert_eq!(
+            std::fs::read_to_string(checkout.join("Addon.toc")).unwrap(),
+            "## Title: Working copy"
+        );
+
+        let _ = remove_dir_all(&directory);
+    }
 }
@@ -0,0 +1,279 @@
+use super::addon::entry_path;
+use super::{config_dir, PersistentData};
+use crate::{
+    addon::{Addon, Repository},
+    config::Flavor,
+    error::ClientError,
+    murmur2::calculate_fingerprint,
+    Result,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Amount of archives kept per addon for rolling back.
+const MAX_CACHED_ARCHIVES: usize = 3;
+
+lazy_static::lazy_static! {
+    /// Guards the read-modify-write of `ArchiveCache` since addons are installed concurrently.
+    static ref ARCHIVE_CACHE_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// An archive of a previously installed addon version.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct CachedArchive {
+    pub version: String,
+    pub file_id: Option<i64>,
+    pub path: PathBuf,
+    pub cached_at: SystemTime,
+    /// Fingerprint of the archive, to tell if it got corrupted since it was cached.
+    #[serde(default)]
+    pub fingerprint: Option<u32>,
+}
+
+/// Archives of installed addons, grouped by flavor and addon.
+#[derive(Serialize, Deserialize, Default)]
+pub struct ArchiveCache(HashMap<Flavor, HashMap<String, Vec<CachedArchive>>>);
+
+impl PersistentData for ArchiveCache {
+    fn relative_path() -> PathBuf {
+        PathBuf::from("archives.yml")
+    }
+}
+
+/// Returns the key the archives of an addon are stored under.
+///
+/// The repository id is used since the folders of an addon installed from the
+/// catalog aren't known until the archive is unpacked.
+pub(super) fn archive_key(addon: &Addon) -> Option<String> {
+    let repository = match addon.active_repository? {
+        Repository::Curse => "curse",
+        Repository::Tukui => "tukui",
+        Repository::WowI => "wowi",
+    };
+
+    addon
+        .repository_id()
+        .map(|id| format!("{}-{}", repository, id))
+}
+
+/// Moves the downloaded archive of an addon into the archive cache.
+///
+/// Only the latest `MAX_CACHED_ARCHIVES` archives are kept for each addon, older
+/// ones are removed from disk. Archives of addons without a repository are removed,
+/// since we are not able to tell their versions apart.
+pub fn cache_archive(addon: &Addon, flavor: Flavor, zip_path: &Path) -> Result<()> {
+    let (key, package) = match (archive_key(addon), addon.relevant_release_package()) {
+        (Some(key), Some(package)) => (key, package),
+        _ => {
+            std::fs::remove_file(zip_path)?;
+            return Ok(());
+        }
+    };
+
+    let directory = config_dir()
+        .join("archives")
+        .join(flavor.folder_name())
+        .join(&key);
+    std::fs::create_dir_all(&directory)?;
+
+    let file_name = match package.file_id {
+        Some(file_id) => format!("{}.zip", file_id),
+        None => format!(
+            "{}.zip",
+            package
+                .version
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() || c == '.' {
+                    c
+                } else {
+                    '_'
+                })
+                .collect::<String>()
+        ),
+    };
+    let path = directory.join(file_name);
+
+    // Renaming fails if the download directory is on another drive than the
+    // config directory, so fallback to copying the archive.
+    if std::fs::rename(zip_path, &path).is_err() {
+        std::fs::copy(zip_path, &path)?;
+        std::fs::remove_file(zip_path)?;
+    }
+
+    let _guard = ARCHIVE_CACHE_LOCK.lock().unwrap();
+
+    let mut cache: ArchiveCache = ArchiveCache::load_or_default()?;
+    let archives = cache.0.entry(flavor).or_default().entry(key).or_default();
+
+    archives.retain(|a| a.path != path);
+    archives.push(CachedArchive {
+        version: package.version.clone(),
+        file_id: package.file_id,
+        path,
+        cached_at: SystemTime::now(),
+        fingerprint: package.fingerprint,
+    });
+
+    // Newest archives first.
+    archives.sort_by(|a, b| b.cached_at.cmp(&a.cached_at));
+
+    if archives.len() > MAX_CACHED_ARCHIVES {
+        for archive in archives.split_off(MAX_CACHED_ARCHIVES) {
+            let _ = std::fs::remove_file(&archive.path);
+        }
+    }
+
+    cache.save()
+}
+
+/// Verifies the archive at `path` matches the fingerprint exposed by the repository.
+pub fn verify_archive(path: &Path, fingerprint: u32) -> Result<()> {
+    let actual = calculate_fingerprint(&std::fs::read(path)?);
+
+    if actual != fingerprint {
+        return Err(ClientError::ChecksumMismatch {
+            path: path.to_path_buf(),
+            expected: fingerprint,
+            actual,
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns the newest cached archive of the addon which is older than the
+/// installed version.
+pub fn previous_archive(addon: &Addon, flavor: Flavor) -> Result<Option<CachedArchive>> {
+    let archives = cached_archives(addon, flavor)?;
+
+    Ok(previous_of(&archives, addon).cloned())
+}
+
+/// Returns the cached archive of the version of the addon which is installed.
+pub fn installed_archive(addon: &Addon, flavor: Flavor) -> Result<Option<CachedArchive>> {
+    let archives = cached_archives(addon, flavor)?;
+
+    Ok(archives
+        .into_iter()
+        .find(|a| is_installed_version(a, addon)))
+}
+
+/// Returns the archives of the addon which are still on disk, newest first.
+fn cached_archives(addon: &Addon, flavor: Flavor) -> Result<Vec<CachedArchive>> {
+    let key = match archive_key(addon) {
+        Some(key) => key,
+        None => return Ok(vec![]),
+    };
+
+    let _guard = ARCHIVE_CACHE_LOCK.lock().unwrap();
+
+    let cache: ArchiveCache = ArchiveCache::load_or_default()?;
+    let archives = cache
+        .0
+        .get(&flavor)
+        .and_then(|archives| archives.get(&key))
+        .map(|archives| {
+            archives
+                .iter()
+                .filter(|a| a.path.exists())
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(archives)
+}
+
+/// Archives are sorted newest first, so the one cached before the installed
+/// version follows it. If the installed version isn't cached, eg. it was installed
+/// before archives were kept, the newest archive is the previous one.
+fn previous_of<'a>(archives: &'a [CachedArchive], addon: &Addon) -> Option<&'a CachedArchive> {
+    match archives.iter().position(|a| is_installed_version(a, addon)) {
+        Some(installed) => archives.get(installed + 1),
+        None => archives.first(),
+    }
+}
+
+fn is_installed_version(archive: &CachedArchive, addon: &Addon) -> bool {
+    match (archive.file_id, addon.file_id()) {
+        (Some(file_id), Some(installed_file_id)) => file_id == installed_file_id,
+        _ => Some(archive.version.as_str()) == addon.version(),
+    }
+}
+
+/// Compares the files of the archive at `path` with the files extracted from it
+/// into `addon_directory`. Returns the entries which were modified or removed since.
+///
+/// Files that aren't in the archive, eg. ones written by the addon itself, are
+/// left out.
+pub fn modified_entries(path: &Path, addon_directory: &Path) -> Result<Vec<String>> {
+    let mut zip_file = std::fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(&mut zip_file)?;
+
+    let mut modified = vec![];
+
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        if file.is_dir() {
+            continue;
+        }
+
+        let installed_path = addon_directory.join(entry_path(file.name())?);
+
+        // Sizes are compared first, so most modified files aren't read at all.
+        let is_intact = match std::fs::metadata(&installed_path) {
+            Ok(metadata) if metadata.len() == file.size() => {
+                let mut expected = Vec::with_capacity(file.size() as usize);
+                file.read_to_end(&mut expected)?;
+
+                std::fs::read(&installed_path)? == expected
+            }
+            _ => false,
+        };
+
+        if !is_intact {
+            modified.push(file.name().trim_end_matches('/').to_string());
+        }
+    }
+
+    Ok(modified)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive(version: &str, file_id: i64) -> CachedArchive {
+        CachedArchive {
+            version: version.to_string(),
+            file_id: Some(file_id),
+            path: PathBuf::from(format!("{}.zip", file_id)),
+            cached_at: SystemTime::UNIX_EPOCH,
+            fingerprint: None,
+        }
+    }
+
+    #[test]
+    fn test_previous_of() {
+        let archives = vec![archive("3.0", 3), archive("2.0", 2), archive("1.0", 1)];
+        let mut addon = Addon::empty("Details");
+
+        addon.repository_metadata.file_id = Some(3);
+        assert_eq!(previous_of(&archives, &addon), Some(&archives[1]));
+
+        // After rolling back, the archive older than the installed one is picked
+        // instead of the newer one.
+        addon.repository_metadata.file_id = Some(2);
+        assert_eq!(previous_of(&archives, &addon), Some(&archives[2]));
+
+        addon.repository_metadata.file_id = Some(1);
+        assert_eq!(previous_of(&archives, &addon), None);
+
+        addon.repository_metadata.file_id = Some(4);
+        assert_eq!(previous_of(&archives, &addon), Some(&archives[0]));
+    }
+}
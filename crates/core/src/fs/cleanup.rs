@@ -0,0 +1,184 @@
+use super::size_on_disk;
+use crate::{
+    addon::{Addon, AddonFolder},
+    Result,
+};
+use std::fs::{read_dir, remove_dir_all, remove_file};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeftoverKind {
+    /// A folder in `Interface/AddOns` which isn't part of any addon.
+    Folder,
+    /// A SavedVariables file of an addon which is no longer installed.
+    SavedVariables,
+}
+
+impl std::fmt::Display for LeftoverKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            LeftoverKind::Folder => "Folder",
+            LeftoverKind::SavedVariables => "SavedVariables",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A file or folder left behind by an addon, which can be deleted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leftover {
+    pub kind: LeftoverKind,
+    pub path: PathBuf,
+    /// Size on disk in bytes.
+    pub size: u64,
+}
+
+/// Finds the folders in `addon_directory` which aren't part of any of `addons`,
+/// and the SavedVariables files in `wtf_directory` of addons which aren't in
+/// `addon_directory`.
+///
+/// Folders are usually left behind when an addon drops a module or when its
+/// `.toc` can't be read. SavedVariables are never removed by the game, so they
+/// pile up for every addon that was ever installed.
+pub fn find_leftovers(
+    addon_directory: &Path,
+    wtf_directory: Option<&Path>,
+    addons: &[Addon],
+) -> Result<Vec<Leftover>> {
+    let mut leftovers = vec![];
+    let mut folder_names = vec![];
+
+    if addon_directory.exists() {
+        for entry in read_dir(addon_directory)?.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            // Skips hidden folders, like the one Ajour stages updates in.
+            if !path.is_dir() || name.starts_with('.') {
+                continue;
+            }
+
+            if !addons.iter().any(|a| a.provides_folder(&name)) {
+                leftovers.push(Leftover {
+                    kind: LeftoverKind::Folder,
+                    size: size_on_disk(&path),
+                    path,
+                });
+            }
+
+            folder_names.push(name);
+        }
+    }
+
+    if let Some(wtf_directory) = wtf_directory {
+        for path in saved_variables_files(wtf_directory) {
+            let addon_name = match saved_variables_addon(&path) {
+                Some(name) => name,
+                None => continue,
+            };
+
+            // The addons shipped with the game don't have a folder in AddOns.
+            if addon_name.starts_with("Blizzard_") {
+                continue;
+            }
+
+            if !folder_names
+                .iter()
+                .any(|f| f.eq_ignore_ascii_case(&addon_name))
+            {
+                leftovers.push(Leftover {
+                    kind: LeftoverKind::SavedVariables,
+                    size: size_on_disk(&path),
+                    path,
+                });
+            }
+        }
+    }
+
+    leftovers.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(leftovers)
+}
+
+/// Deletes the given leftovers from disk.
+pub fn delete_leftovers(leftovers: &[Leftover]) -> Result<()> {
+    for leftover in leftovers {
+        let path = &leftover.path;
+
+        if path.is_dir() {
+            remove_dir_all(path)?;
+        } else if path.exists() {
+            remove_file(path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Deletes the SavedVariables files of `folders` of every account and character
+/// in `wtf_directory`.
+pub fn delete_saved_variables(wtf_directory: &Path, folders: &[AddonFolder]) -> Result<()> {
+    for path in saved_variables_files(wtf_directory) {
+        let is_of_folder = saved_variables_addon(&path)
+            .map(|name| folders.iter().any(|f| f.id.eq_ignore_ascii_case(&name)))
+            .unwrap_or_default();
+
+        if is_of_folder {
+            remove_file(path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the SavedVariables files of every account and character, found in
+/// `WTF/Account/<account>/SavedVariables` and
+/// `WTF/Account/<account>/<realm>/<character>/SavedVariables`.
+fn saved_variables_files(wtf_directory: &Path) -> Vec<PathBuf> {
+    WalkDir::new(wtf_directory.join("Account"))
+        .min_depth(3)
+        .max_depth(5)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            e.path()
+                .parent()
+                .and_then(Path::file_name)
+                .and_then(|n| n.to_str())
+                .map(|n| n.eq_ignore_ascii_case("SavedVariables"))
+                .unwrap_or_default()
+        })
+        .map(|e| e.into_path())
+        .collect()
+}
+
+/// Returns the name of the addon a SavedVariables file belongs to, eg. `Details`
+/// for both `Details.lua` and its backup `Details.lua.bak`.
+fn saved_variables_addon(path: &Path) -> Option<String> {
+    let file_name = path.file_name()?.to_str()?;
+    let lowercase = file_name.to_ascii_lowercase();
+
+    [".lua", ".lua.bak"]
+        .iter()
+        .find(|ext| lowercase.ends_with(*ext))
+        .map(|ext| file_name[..file_name.len() - ext.len()].to_string())
+        .filter(|name| !name.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saved_variables_addon() {
+        let addon = |p: &str| saved_variables_addon(Path::new(p));
+
+        assert_eq!(addon("Details.lua"), Some("Details".to_string()));
+        assert_eq!(addon("Details.lua.bak"), Some("Details".to_string()));
+        assert_eq!(addon("WeakAuras.LUA"), Some("WeakAuras".to_string()));
+        assert_eq!(addon("Details.txt"), None);
+        assert_eq!(addon(".lua"), None);
+    }
+}
@@ -0,0 +1,157 @@
+use super::config_dir;
+use crate::{
+    addon::{
+        Addon, AddonFolder, AddonState, ReleaseChannel, Repository, RepositoryIdentifiers,
+        RepositoryMetadata,
+    },
+    config::Flavor,
+    fs::installed::InstallDates,
+    Result,
+};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+lazy_static::lazy_static! {
+    /// Guards writing the record of a flavor, which can be parsed again before the
+    /// last parse finished.
+    static ref ADDON_DATABASE_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// Addons of a flavor as they were when last parsed, along with their folders,
+/// fingerprints, repository ids and remote packages.
+///
+/// Shown right away on start, while the addon directory is parsed again. Each
+/// flavor is stored in a file of its own, so parsing one flavor doesn't write
+/// the addons of all the others again.
+#[derive(Serialize, Deserialize)]
+struct FlavorRecord {
+    /// Addon directory the addons were parsed from, since installations share flavors.
+    directory: PathBuf,
+    parsed_at: SystemTime,
+    addons: Vec<AddonRecord>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AddonRecord {
+    primary_folder_id: String,
+    folders: Vec<AddonFolder>,
+    state: AddonState,
+    release_channel: ReleaseChannel,
+    repository_identifiers: RepositoryIdentifiers,
+    active_repository: Option<Repository>,
+    repository_metadata: RepositoryMetadata,
+    install_dates: InstallDates,
+}
+
+impl FlavorRecord {
+    fn new(directory: &Path, addons: &[Addon], at: SystemTime) -> FlavorRecord {
+        FlavorRecord {
+            directory: directory.to_path_buf(),
+            parsed_at: at,
+            addons: addons.iter().map(AddonRecord::from_addon).collect(),
+        }
+    }
+
+    fn load(flavor: Flavor) -> Option<FlavorRecord> {
+        let file = std::fs::File::open(path(flavor)).ok()?;
+
+        serde_yaml::from_reader(file).ok()
+    }
+
+    fn addons(&self, directory: &Path) -> Option<Vec<Addon>> {
+        if self.directory != directory {
+            return None;
+        }
+
+        Some(self.addons.iter().map(AddonRecord::to_addon).collect())
+    }
+}
+
+impl AddonRecord {
+    fn from_addon(addon: &Addon) -> AddonRecord {
+        // States of tasks in progress don't outlive them, and the others follow
+        // from the preferences once loaded.
+        let state = match addon.state {
+            AddonState::Unknown | AddonState::Corrupted => addon.state.clone(),
+            _ => AddonState::Ajour(None),
+        };
+
+        AddonRecord {
+            primary_folder_id: addon.primary_folder_id.clone(),
+            folders: addon.folders.clone(),
+            state,
+            release_channel: addon.release_channel,
+            repository_identifiers: addon.repository_identifiers.clone(),
+            active_repository: addon.active_repository,
+            repository_metadata: addon.repository_metadata.clone(),
+            install_dates: addon.install_dates,
+        }
+    }
+
+    fn to_addon(&self) -> Addon {
+        let mut addon = Addon::empty(&self.primary_folder_id);
+        addon.folders = self.folders.clone();
+        addon.state = self.state.clone();
+        addon.release_channel = self.release_channel;
+        addon.repository_identifiers = self.repository_identifiers.clone();
+        addon.active_repository = self.active_repository;
+        addon.repository_metadata = self.repository_metadata.clone();
+        addon.install_dates = self.install_dates;
+
+        addon
+    }
+}
+
+fn path(flavor: Flavor) -> PathBuf {
+    config_dir()
+        .join("addons")
+        .join(format!("{}.yml", flavor.folder_name()))
+}
+
+/// Returns the addons of `flavor` last parsed from `directory`.
+pub fn load_addons(flavor: Flavor, directory: &Path) -> Option<Vec<Addon>> {
+    FlavorRecord::load(flavor)?.addons(directory)
+}
+
+/// Returns when the addons of `flavor` were last parsed.
+pub fn parsed_at(flavor: Flavor) -> Option<SystemTime> {
+    FlavorRecord::load(flavor).map(|r| r.parsed_at)
+}
+
+/// Records the addons just parsed from `directory`.
+pub(crate) fn record_addons(flavor: Flavor, directory: &Path, addons: &[Addon]) -> Result<()> {
+    let record = FlavorRecord::new(directory, addons, SystemTime::now());
+
+    let _guard = ADDON_DATABASE_LOCK.lock().unwrap();
+
+    std::fs::create_dir_all(config_dir().join("addons"))?;
+    std::fs::write(path(flavor), serde_yaml::to_string(&record)?)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_addons_of_directory() {
+        let mut addon = Addon::empty("Details");
+        addon.state = AddonState::Updatable;
+        addon.active_repository = Some(Repository::Curse);
+        addon.repository_identifiers.curse = Some(61284);
+
+        let live = PathBuf::from("/wow/_retail_/Interface/AddOns");
+        let record = FlavorRecord::new(&live, &[addon], SystemTime::now());
+
+        let addons = record.addons(&live).unwrap();
+        assert_eq!(addons[0].primary_folder_id, "Details");
+        assert_eq!(addons[0].curse_id(), Some(61284));
+        assert_eq!(addons[0].state, AddonState::Ajour(None));
+
+        let ptr = PathBuf::from("/wow/_ptr_/Interface/AddOns");
+        assert!(record.addons(&ptr).is_none());
+    }
+}
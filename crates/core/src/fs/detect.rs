@@ -0,0 +1,318 @@
+use crate::config::Flavor;
+use crate::utility::wow_path_resolution;
+use std::path::{Path, PathBuf};
+
+/// Folder World of Warcraft is installed into by the Battle.net launcher.
+const WOW_FOLDER: &str = "World of Warcraft";
+
+/// Returns the World of Warcraft directories found in the usual install
+/// locations of the current platform.
+///
+/// These are offered on first run, so the user doesn't have to find the
+/// directory themselves.
+pub fn wow_directories() -> Vec<PathBuf> {
+    let mut directories = vec![];
+
+    for candidate in candidates() {
+        if let Some(directory) = wow_path_resolution(Some(candidate)) {
+            if !directories.contains(&directory) {
+                directories.push(directory);
+            }
+        }
+    }
+
+    log::debug!("detected wow directories: {:?}", &directories);
+
+    directories
+}
+
+/// Returns the flavors installed in the World of Warcraft `directory`.
+pub fn installed_flavors(directory: &Path) -> Vec<Flavor> {
+    Flavor::ALL
+        .iter()
+        .copied()
+        .filter(|f| directory.join(f.folder_name()).is_dir())
+        .collect()
+}
+
+#[cfg(windows)]
+fn candidates() -> Vec<PathBuf> {
+    let mut candidates = registry_install_paths();
+
+    // The Battle.net agent keeps the install path of every game it manages.
+    if let Ok(program_data) = std::env::var("ProgramData") {
+        let product_db = PathBuf::from(&program_data)
+            .join("Battle.net")
+            .join("Agent")
+            .join("product.db");
+
+        if let Ok(bytes) = std::fs::read(product_db) {
+            candidates.extend(paths_from_product_db(&bytes));
+        }
+    }
+
+    for variable in &["ProgramFiles(x86)", "ProgramFiles"] {
+        if let Ok(program_files) = std::env::var(variable) {
+            candidates.push(PathBuf::from(&program_files).join(WOW_FOLDER));
+        }
+    }
+
+    candidates
+}
+
+#[cfg(target_os = "macos")]
+fn candidates() -> Vec<PathBuf> {
+    let mut candidates = vec![PathBuf::from("/Applications").join(WOW_FOLDER)];
+
+    if let Some(home) = dirs_next::home_dir() {
+        candidates.push(home.join("Applications").join(WOW_FOLDER));
+    }
+
+    candidates
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn candidates() -> Vec<PathBuf> {
+    let home = match dirs_next::home_dir() {
+        Some(home) => home,
+        None => return vec![],
+    };
+
+    // Wine prefixes, made by hand or by Lutris.
+    let mut prefixes = vec![home.join(".wine"), home.join("Games").join("*")];
+    if let Some(prefix) = std::env::var_os("WINEPREFIX") {
+        prefixes.push(PathBuf::from(prefix));
+    }
+
+    let mut candidates = vec![];
+    for games_directory in &[".config/lutris/games", ".local/share/lutris/games"] {
+        let pattern = home.join(games_directory).join("*.yml");
+
+        if let Ok(paths) = glob::glob(&pattern.to_string_lossy()) {
+            for path in paths.flatten() {
+                if let Ok(content) = std::fs::read_to_string(&path) {
+                    let (prefix, exe) = lutris_game_paths(&content);
+                    prefixes.extend(prefix);
+                    // The game might be WoW itself rather than Battle.net.
+                    candidates.extend(exe);
+                }
+            }
+        }
+    }
+
+    // Steam Proton makes a prefix per game in every library.
+    let steam_directories = [
+        home.join(".steam/steam"),
+        home.join(".local/share/Steam"),
+        home.join(".var/app/com.valvesoftware.Steam/.local/share/Steam"),
+    ];
+
+    let mut libraries: Vec<PathBuf> = vec![];
+    for steam_directory in steam_directories.iter() {
+        let mut found = vec![steam_directory.clone()];
+        if let Ok(content) =
+            std::fs::read_to_string(steam_directory.join("steamapps/libraryfolders.vdf"))
+        {
+            found.extend(steam_libraries(&content));
+        }
+
+        for library in found {
+            // `~/.steam/steam` usually links to one of the others.
+            let library = std::fs::canonicalize(&library).unwrap_or(library);
+            if library.exists() && !libraries.contains(&library) {
+                libraries.push(library);
+            }
+        }
+    }
+    prefixes.extend(
+        libraries
+            .iter()
+            .map(|l| l.join("steamapps/compatdata/*/pfx")),
+    );
+
+    for prefix in prefixes.iter() {
+        for program_files in &["Program Files (x86)", "Program Files"] {
+            let pattern = prefix.join("drive_c").join(program_files).join(WOW_FOLDER);
+
+            if let Ok(paths) = glob::glob(&pattern.to_string_lossy()) {
+                candidates.extend(paths.flatten());
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Returns the wine prefix and executable of a Lutris game configuration.
+#[cfg_attr(any(windows, target_os = "macos"), allow(dead_code))]
+fn lutris_game_paths(content: &str) -> (Option<PathBuf>, Option<PathBuf>) {
+    let config: serde_yaml::Value = match serde_yaml::from_str(content) {
+        Ok(config) => config,
+        Err(_) => return (None, None),
+    };
+
+    let path = |key: &str| config["game"][key].as_str().map(PathBuf::from);
+
+    (path("prefix"), path("exe"))
+}
+
+/// Returns the library folders listed in Steam's `libraryfolders.vdf`.
+///
+/// Newer versions of Steam list them as `"path"` under each library, older
+/// versions as numbered keys.
+#[cfg_attr(any(windows, target_os = "macos"), allow(dead_code))]
+fn steam_libraries(content: &str) -> Vec<PathBuf> {
+    let mut libraries = vec![];
+
+    for line in content.lines() {
+        let tokens: Vec<_> = line
+            .split('"')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        if let [key, value] = tokens.as_slice() {
+            let is_library = *key == "path" || key.chars().all(|c| c.is_ascii_digit());
+
+            if is_library && value.starts_with('/') {
+                libraries.push(PathBuf::from(value));
+            }
+        }
+    }
+
+    libraries
+}
+
+#[cfg(windows)]
+fn registry_install_paths() -> Vec<PathBuf> {
+    use std::process::Command;
+
+    let keys = [
+        r"HKLM\SOFTWARE\WOW6432Node\Blizzard Entertainment\World of Warcraft",
+        r"HKLM\SOFTWARE\Blizzard Entertainment\World of Warcraft",
+    ];
+
+    let mut paths = vec![];
+    for key in keys.iter() {
+        let output = match Command::new("reg")
+            .args(&["query", key, "/v", "InstallPath"])
+            .output()
+        {
+            Ok(output) if output.status.success() => output,
+            _ => continue,
+        };
+
+        // The value is printed as `InstallPath    REG_SZ    <path>`.
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for line in stdout.lines() {
+            if let Some(index) = line.find("REG_SZ") {
+                paths.push(PathBuf::from(line[index + "REG_SZ".len()..].trim()));
+            }
+        }
+    }
+
+    paths
+}
+
+/// Picks the paths out of the Battle.net agent database.
+///
+/// The database is protobuf, so rather than decoding it we look for anything
+/// starting with a drive letter and read it until the first unprintable byte.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn paths_from_product_db(bytes: &[u8]) -> Vec<PathBuf> {
+    let mut paths = vec![];
+
+    let mut i = 0;
+    while i + 2 < bytes.len() {
+        let is_drive = bytes[i].is_ascii_alphabetic()
+            && bytes[i + 1] == b':'
+            && (bytes[i + 2] == b'/' || bytes[i + 2] == b'\\');
+
+        if !is_drive {
+            i += 1;
+            continue;
+        }
+
+        let end = bytes[i..]
+            .iter()
+            .position(|b| !(b.is_ascii_graphic() || *b == b' '))
+            .map(|p| i + p)
+            .unwrap_or_else(|| bytes.len());
+
+        let path = String::from_utf8_lossy(&bytes[i..end]);
+        if path.contains(WOW_FOLDER) {
+            paths.push(PathBuf::from(path.as_ref()));
+        }
+
+        i = end;
+    }
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths_from_product_db() {
+        let mut bytes = b"\n\x07wow_ptr\x12\x1f".to_vec();
+        bytes.extend_from_slice(b"C:/Program Files (x86)/World of Warcraft");
+        bytes.extend_from_slice(b"\x1a\x04enUS\x12\x10D:\\Games\\Diablo\x00");
+        bytes.extend_from_slice(b"\x12\x17E:/World of Warcraft");
+
+        assert_eq!(
+            paths_from_product_db(&bytes),
+            vec![
+                PathBuf::from("C:/Program Files (x86)/World of Warcraft"),
+                PathBuf::from("E:/World of Warcraft"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lutris_game_paths() {
+        let content = r#"
+game:
+  exe: /home/user/Games/battlenet/drive_c/Program Files (x86)/Battle.net/Battle.net.exe
+  prefix: /home/user/Games/battlenet
+wine:
+  version: lutris-6.0-x86_64
+"#;
+
+        assert_eq!(
+            lutris_game_paths(content),
+            (
+                Some(PathBuf::from("/home/user/Games/battlenet")),
+                Some(PathBuf::from(
+                    "/home/user/Games/battlenet/drive_c/Program Files (x86)/Battle.net/Battle.net.exe"
+                )),
+            )
+        );
+        assert_eq!(lutris_game_paths("game: {}"), (None, None));
+    }
+
+    #[test]
+    fn test_steam_libraries() {
+        let content = r#"
+"libraryfolders"
+{
+	"contentstatsid"		"-1234"
+	"0"
+	{
+		"path"		"/home/user/.local/share/Steam"
+		"label"		""
+	}
+	"1"		"/mnt/games/SteamLibrary"
+}
+"#;
+
+        assert_eq!(
+            steam_libraries(content),
+            vec![
+                PathBuf::from("/home/user/.local/share/Steam"),
+                PathBuf::from("/mnt/games/SteamLibrary"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use super::PersistentData;
+use crate::config::Flavor;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// An update of an addon, kept until it has been seen in "What's New".
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct UpdateRecord {
+    pub flavor: Flavor,
+    pub id: String,
+    pub title: String,
+    pub old_version: Option<String>,
+    pub new_version: Option<String>,
+    /// Fetched once the update is done, so missing if that failed.
+    #[serde(default)]
+    pub changelog: Option<String>,
+    pub updated_at: SystemTime,
+}
+
+/// Updates of addons since "What's New" was last viewed, newest first.
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct UpdateHistory {
+    pub updates: Vec<UpdateRecord>,
+}
+
+impl PersistentData for UpdateHistory {
+    fn relative_path() -> PathBuf {
+        PathBuf::from("update_history.yml")
+    }
+}
+
+impl UpdateHistory {
+    pub fn record(&mut self, record: UpdateRecord) {
+        self.updates.insert(0, record);
+    }
+
+    /// Sets the changelog of the latest update of the addon.
+    pub fn set_changelog(&mut self, flavor: Flavor, id: &str, changelog: String) {
+        if let Some(record) = self
+            .updates
+            .iter_mut()
+            .find(|r| r.flavor == flavor && r.id == id)
+        {
+            record.changelog = Some(changelog);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_changelog() {
+        let record = |version: &str| UpdateRecord {
+            flavor: Flavor::Retail,
+            id: "Details".to_string(),
+            title: "Details!".to_string(),
+            old_version: None,
+            new_version: Some(version.to_string()),
+            changelog: None,
+            updated_at: SystemTime::now(),
+        };
+
+        let mut history = UpdateHistory::default();
+        history.record(record("1.0"));
+        history.record(record("1.1"));
+        history.set_changelog(Flavor::Retail, "Details", "Fixed bars".to_string());
+
+        assert_eq!(history.updates[0].new_version, Some("1.1".to_string()));
+        assert_eq!(history.updates[0].changelog, Some("Fixed bars".to_string()));
+        assert_eq!(history.updates[1].changelog, None);
+    }
+}
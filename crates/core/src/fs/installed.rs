@@ -0,0 +1,115 @@
+use super::archive::archive_key;
+use super::PersistentData;
+use crate::{addon::Addon, config::Flavor, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+lazy_static::lazy_static! {
+    /// Guards the read-modify-write of `InstalledAddons` since addons are installed concurrently.
+    static ref INSTALLED_ADDONS_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// When an addon was installed and last updated by Ajour.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct InstallDates {
+    /// Missing for addons installed before Ajour kept track of it.
+    pub installed_at: Option<SystemTime>,
+    pub updated_at: Option<SystemTime>,
+}
+
+/// Install dates of addons from a repository, grouped by flavor and addon.
+#[derive(Serialize, Deserialize, Default)]
+pub struct InstalledAddons(HashMap<Flavor, HashMap<String, InstallDates>>);
+
+impl PersistentData for InstalledAddons {
+    fn relative_path() -> PathBuf {
+        PathBuf::from("installed.yml")
+    }
+}
+
+impl InstalledAddons {
+    /// Records that `addon` was installed or updated at `at`.
+    ///
+    /// Addons without folders on disk are new installs, the others are updates.
+    fn record(&mut self, addon: &Addon, flavor: Flavor, at: SystemTime) {
+        let key = match archive_key(addon) {
+            Some(key) => key,
+            None => return,
+        };
+
+        let dates = self.0.entry(flavor).or_default().entry(key).or_default();
+        if addon.folders.is_empty() {
+            dates.installed_at = Some(at);
+        }
+        dates.updated_at = Some(at);
+    }
+
+    /// Sets the install dates of parsed addons.
+    pub fn apply(&self, flavor: Flavor, addons: &mut [Addon]) {
+        let dates = match self.0.get(&flavor) {
+            Some(dates) => dates,
+            None => return,
+        };
+
+        for addon in addons.iter_mut() {
+            if let Some(dates) = archive_key(addon).and_then(|key| dates.get(&key)) {
+                addon.install_dates = *dates;
+            }
+        }
+    }
+}
+
+/// Records that `addon` was installed or updated just now.
+pub(super) fn record_install(addon: &Addon, flavor: Flavor) -> Result<()> {
+    let _guard = INSTALLED_ADDONS_LOCK.lock().unwrap();
+
+    let mut installed = InstalledAddons::load_or_default()?;
+    installed.record(addon, flavor, SystemTime::now());
+    installed.save()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::addon::{AddonFolder, Repository};
+    use std::time::Duration;
+
+    #[test]
+    fn test_record() {
+        let mut addon = Addon::empty("Details");
+        addon.active_repository = Some(Repository::Tukui);
+        addon.repository_identifiers.tukui = Some("12".to_string());
+
+        let installed_at = SystemTime::UNIX_EPOCH;
+        let updated_at = installed_at + Duration::from_secs(60);
+
+        let mut installed = InstalledAddons::default();
+        installed.record(&addon, Flavor::Retail, installed_at);
+
+        addon.folders.push(AddonFolder::new(
+            "Details".to_string(),
+            "Details!".to_string(),
+            PathBuf::from("Details"),
+            None,
+            None,
+            None,
+            Default::default(),
+            vec![],
+        ));
+        installed.record(&addon, Flavor::Retail, updated_at);
+
+        let mut addons = vec![addon];
+        installed.apply(Flavor::Retail, &mut addons);
+
+        assert_eq!(
+            addons[0].install_dates,
+            InstallDates {
+                installed_at: Some(installed_at),
+                updated_at: Some(updated_at),
+            }
+        );
+    }
+}
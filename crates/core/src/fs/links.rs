@@ -0,0 +1,131 @@
+use super::PersistentData;
+use crate::{
+    addon::{AddonFolder, Repository},
+    config::Flavor,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// A repository an addon folder has been linked to.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct RepositoryLink {
+    pub repository: Repository,
+    pub id: String,
+    /// Set when the user changed the source of the addon, since it was matched
+    /// against the wrong project. Replaces the identifiers in the `.toc`.
+    #[serde(default)]
+    pub is_override: bool,
+}
+
+/// Repositories linked to addon folders which can't be identified from their `.toc`,
+/// eg. addons imported from another addon manager, or which were matched against
+/// the wrong project. Grouped by flavor and folder.
+#[derive(Serialize, Deserialize, Default)]
+pub struct RepositoryLinks(HashMap<Flavor, HashMap<String, RepositoryLink>>);
+
+impl PersistentData for RepositoryLinks {
+    fn relative_path() -> PathBuf {
+        PathBuf::from("links.yml")
+    }
+}
+
+impl RepositoryLinks {
+    pub fn insert(&mut self, flavor: Flavor, folder_id: String, link: RepositoryLink) {
+        self.0.entry(flavor).or_default().insert(folder_id, link);
+    }
+
+    /// Removes the sources the user chose for the folders, so they are matched
+    /// automatically again.
+    pub fn remove_overrides(&mut self, flavor: Flavor, folder_ids: &[String]) {
+        if let Some(links) = self.0.get_mut(&flavor) {
+            links.retain(|folder_id, link| !(link.is_override && folder_ids.contains(folder_id)));
+        }
+    }
+
+    /// Sets the repository identifiers of linked folders, unless their `.toc` already
+    /// has an identifier for the repository.
+    ///
+    /// Folders with a source chosen by the user only get that identifier, and aren't
+    /// fingerprinted, so they can't be matched against another project.
+    pub fn apply(&self, flavor: Flavor, folders: &mut [AddonFolder]) {
+        let links = match self.0.get(&flavor) {
+            Some(links) => links,
+            None => return,
+        };
+
+        for folder in folders.iter_mut() {
+            if let Some(link) = links.get(&folder.id) {
+                if link.is_override {
+                    folder.repository_identifiers = Default::default();
+                    folder.fingerprint = None;
+                }
+
+                let identifiers = &mut folder.repository_identifiers;
+
+                match link.repository {
+                    Repository::Curse => {
+                        if identifiers.curse.is_none() {
+                            identifiers.curse = link.id.parse().ok();
+                        }
+                    }
+                    Repository::Tukui => {
+                        if identifiers.tukui.is_none() {
+                            identifiers.tukui = Some(link.id.clone());
+                        }
+                    }
+                    Repository::WowI => {
+                        if identifiers.wowi.is_none() {
+                            identifiers.wowi = Some(link.id.clone());
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_override() {
+        let mut identifiers = crate::addon::RepositoryIdentifiers::default();
+        identifiers.curse = Some(1);
+        identifiers.tukui = Some("2".to_string());
+
+        let mut folder = AddonFolder::new(
+            "Addon".to_string(),
+            "Addon".to_string(),
+            PathBuf::from("Addon"),
+            None,
+            None,
+            None,
+            identifiers,
+            vec![],
+        );
+        folder.fingerprint = Some(1234);
+
+        let mut links = RepositoryLinks::default();
+        links.insert(
+            Flavor::Retail,
+            "Addon".to_string(),
+            RepositoryLink {
+                repository: Repository::Curse,
+                id: "3".to_string(),
+                is_override: true,
+            },
+        );
+
+        let mut folders = vec![folder];
+        links.apply(Flavor::Retail, &mut folders);
+
+        assert_eq!(folders[0].repository_identifiers.curse, Some(3));
+        assert_eq!(folders[0].repository_identifiers.tukui, None);
+        assert_eq!(folders[0].fingerprint, None);
+
+        links.remove_overrides(Flavor::Retail, &["Addon".to_string()]);
+        assert!(links.0[&Flavor::Retail].is_empty());
+    }
+}
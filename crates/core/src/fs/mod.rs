@@ -2,18 +2,34 @@ use lazy_static::lazy_static;
 #[cfg(not(windows))]
 use std::env;
 use std::sync::{Arc, Mutex};
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use walkdir::WalkDir;
 
 mod addon;
+pub mod archive;
 pub mod backup;
+pub mod cleanup;
+pub mod database;
+pub mod detect;
+pub mod history;
+pub mod installed;
+pub mod links;
+pub(crate) mod responses;
 mod save;
+pub mod snapshot;
 #[cfg(feature = "gui")]
 mod theme;
 
-pub use addon::{delete_addons, install_addon};
+pub use addon::{
+    delete_addons, install_addon, install_from_url, install_local_archive, reinstall_addon,
+    rollback_addon,
+};
 pub use save::PersistentData;
 #[cfg(feature = "gui")]
-pub use theme::load_user_themes;
+pub use theme::{install_user_theme, load_user_themes, themes_dir, user_themes_modified};
 
 lazy_static! {
     pub static ref CONFIG_DIR: Arc<Mutex<PathBuf>> = {
@@ -59,3 +75,33 @@ lazy_static! {
 pub fn config_dir() -> PathBuf {
     CONFIG_DIR.lock().unwrap().clone()
 }
+
+/// File next to the executable which makes Ajour portable.
+pub const PORTABLE_MARKER: &str = "portable.txt";
+
+/// Returns the directory next to the executable which a portable Ajour keeps
+/// its config, cache, themes and logs in.
+pub fn portable_dir() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+
+    exe.parent().map(|dir| dir.join("ajour_data"))
+}
+
+/// Returns true if `PORTABLE_MARKER` is next to the executable.
+pub fn has_portable_marker() -> bool {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(PORTABLE_MARKER)))
+        .map_or(false, |marker| marker.is_file())
+}
+
+/// Returns the size in bytes of the files in `path`.
+pub(crate) fn size_on_disk(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum()
+}
@@ -0,0 +1,63 @@
+use super::config_dir;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+/// A response body cached on disk, along with the validators the server sent
+/// to revalidate it with.
+///
+/// Responses are also used as is while offline.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct CachedResponse {
+    url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl CachedResponse {
+    /// Returns the cached response of `url`, if there is one.
+    pub fn load(url: &str) -> Option<CachedResponse> {
+        let file = std::fs::File::open(path(url, "yml")).ok()?;
+        let cached: CachedResponse = serde_yaml::from_reader(file).ok()?;
+
+        // Another url could have the same hash.
+        Some(cached).filter(|c| c.url == url)
+    }
+
+    pub fn body(&self) -> Result<Vec<u8>> {
+        Ok(std::fs::read(path(&self.url, "body"))?)
+    }
+
+    /// Caches the response of `url`. Responses without validators are requested
+    /// again as usual, but kept for when we're offline.
+    pub fn store(
+        url: &str,
+        etag: Option<String>,
+        last_modified: Option<String>,
+        body: &[u8],
+    ) -> Result<()> {
+        let cached = CachedResponse {
+            url: url.to_string(),
+            etag,
+            last_modified,
+        };
+
+        // The body is written first, so the validators are never stored without it.
+        std::fs::create_dir_all(config_dir().join("responses"))?;
+        std::fs::write(path(url, "body"), body)?;
+        std::fs::write(path(url, "yml"), serde_yaml::to_string(&cached)?)?;
+
+        Ok(())
+    }
+}
+
+fn path(url: &str, extension: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+
+    config_dir()
+        .join("responses")
+        .join(format!("{:x}.{}", hasher.finish(), extension))
+}
@@ -0,0 +1,349 @@
+use super::PersistentData;
+use crate::{
+    addon::{Addon, AddonFolder},
+    config::Flavor,
+    error::ClientError,
+    parse::{update_addon_fingerprint, FingerprintCollection},
+    Result,
+};
+use async_std::sync::{Arc, Mutex as AsyncMutex};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::remove_dir_all;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+/// Amount of batches of updates kept per flavor for undoing.
+const MAX_SNAPSHOT_BATCHES: usize = 3;
+
+/// Older snapshots are removed once the snapshots of a flavor take up more than this.
+const MAX_SNAPSHOTS_SIZE: u64 = 512 * 1024 * 1024;
+
+/// Batch the snapshots taken right now belong to.
+static BATCH: AtomicU64 = AtomicU64::new(0);
+
+lazy_static::lazy_static! {
+    /// Guards the read-modify-write of `Snapshots` since addons are updated concurrently.
+    static ref SNAPSHOTS_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// Folders of an addon as they were before it was updated.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Snapshot {
+    pub batch: u64,
+    pub addon_id: String,
+    pub title: String,
+    /// Version the addon was updated to, which undoing rolls back from.
+    pub updated_to: Option<String>,
+    /// Directory the replaced folders were moved to.
+    pub path: PathBuf,
+    /// Folders installed by the update, which are removed again when undone.
+    pub installed: Vec<String>,
+    pub size: u64,
+    pub taken_at: SystemTime,
+}
+
+/// Snapshots of updated addons, grouped by flavor and ordered from oldest to newest.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Snapshots(HashMap<Flavor, Vec<Snapshot>>);
+
+impl PersistentData for Snapshots {
+    fn relative_path() -> PathBuf {
+        PathBuf::from("snapshots.yml")
+    }
+}
+
+impl Snapshots {
+    /// Returns the snapshots of the last batch of updates of `flavor`.
+    pub fn last_batch(&self, flavor: Flavor) -> Vec<Snapshot> {
+        let snapshots = self.0.get(&flavor).map(Vec::as_slice).unwrap_or_default();
+        let last = snapshots.iter().map(|s| s.batch).max();
+
+        snapshots
+            .iter()
+            .filter(|s| Some(s.batch) == last)
+            .cloned()
+            .collect()
+    }
+
+    /// Adds `snapshot`, and returns the snapshots which no longer fit.
+    fn record(&mut self, flavor: Flavor, snapshot: Snapshot) -> Vec<Snapshot> {
+        let snapshots = self.0.entry(flavor).or_default();
+        snapshots.push(snapshot);
+
+        let mut batches = snapshots.iter().map(|s| s.batch).collect::<Vec<_>>();
+        batches.sort_unstable();
+        batches.dedup();
+
+        let newest = batches.last().copied().unwrap_or_default();
+        let oldest_kept = batches
+            .iter()
+            .rev()
+            .nth(MAX_SNAPSHOT_BATCHES - 1)
+            .copied()
+            .unwrap_or_default();
+
+        let mut pruned = vec![];
+        let mut size: u64 = snapshots.iter().map(|s| s.size).sum();
+
+        // The newest batch is always kept, so it can be undone.
+        while let Some(oldest) = snapshots.first() {
+            if oldest.batch == newest || (oldest.batch >= oldest_kept && size <= MAX_SNAPSHOTS_SIZE)
+            {
+                break;
+            }
+
+            size -= oldest.size;
+            pruned.push(snapshots.remove(0));
+        }
+
+        pruned
+    }
+
+    fn remove(&mut self, flavor: Flavor, snapshot: &Snapshot) {
+        if let Some(snapshots) = self.0.get_mut(&flavor) {
+            snapshots.retain(|s| !(s.batch == snapshot.batch && s.addon_id == snapshot.addon_id));
+        }
+    }
+}
+
+/// Starts a new batch of updates, eg. of "Update All". The updates until the next
+/// batch is started are undone together.
+pub fn start_batch() {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default();
+
+    // Batches started within the same millisecond still get their own.
+    let batch = now.max(BATCH.load(Ordering::Relaxed) + 1);
+    BATCH.store(batch, Ordering::Relaxed);
+}
+
+fn current_batch() -> u64 {
+    if BATCH.load(Ordering::Relaxed) == 0 {
+        start_batch();
+    }
+
+    BATCH.load(Ordering::Relaxed)
+}
+
+/// Returns the directory the folders replaced by updating `addon` are moved to.
+///
+/// Snapshots are kept next to the addon directory, so folders are moved rather
+/// than copied.
+pub(super) fn snapshot_directory(addon_directory: &Path, addon: &Addon) -> PathBuf {
+    addon_directory
+        .parent()
+        .unwrap_or(addon_directory)
+        .join(".ajour-snapshots")
+        .join(format!("{}-{}", current_batch(), addon.primary_folder_id))
+}
+
+/// Records the folders moved to `path` while updating `addon`, removing the
+/// oldest snapshots which no longer fit.
+pub(super) fn record_snapshot(
+    addon: &Addon,
+    flavor: Flavor,
+    path: &Path,
+    installed: &[AddonFolder],
+) -> Result<()> {
+    // Nothing was replaced, eg. when installing from the catalog.
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let size = WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum();
+
+    let snapshot = Snapshot {
+        batch: current_batch(),
+        addon_id: addon.primary_folder_id.clone(),
+        title: addon.title().to_string(),
+        updated_to: addon.relevant_release_package().map(|p| p.version.clone()),
+        path: path.to_path_buf(),
+        installed: installed.iter().map(|f| f.id.clone()).collect(),
+        size,
+        taken_at: SystemTime::now(),
+    };
+
+    let _guard = SNAPSHOTS_LOCK.lock().unwrap();
+
+    let mut snapshots = Snapshots::load_or_default()?;
+    let pruned = snapshots.record(flavor, snapshot);
+    snapshots.save()?;
+
+    for snapshot in pruned {
+        let _ = remove_dir_all(&snapshot.path);
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if there's an update of `flavor` to undo.
+pub fn can_undo(flavor: Flavor) -> bool {
+    Snapshots::load_or_default()
+        .map(|s| !s.last_batch(flavor).is_empty())
+        .unwrap_or_default()
+}
+
+/// Puts back the folders replaced by the last batch of updates of `flavor`, and
+/// removes the folders those updates installed. Returns the snapshots which were
+/// restored.
+///
+/// Addons which fail to restore are logged and kept, so undoing can be tried again.
+pub async fn undo_last_update(
+    fingerprint_collection: Arc<AsyncMutex<Option<FingerprintCollection>>>,
+    flavor: Flavor,
+    addon_directory: &Path,
+) -> Result<Vec<Snapshot>> {
+    let last_batch = {
+        let _guard = SNAPSHOTS_LOCK.lock().unwrap();
+        Snapshots::load_or_default()?.last_batch(flavor)
+    };
+
+    if last_batch.is_empty() {
+        return Err(ClientError::Custom("There's no update to undo".to_string()));
+    }
+
+    let mut restored = vec![];
+    let mut last_error = None;
+
+    for snapshot in last_batch {
+        match restore_snapshot(&snapshot, addon_directory) {
+            Ok(folders) => {
+                // Folders keep their modified time, so the cached fingerprints
+                // of the update would be used otherwise.
+                for folder in folders {
+                    let result = update_addon_fingerprint(
+                        fingerprint_collection.clone(),
+                        flavor,
+                        addon_directory,
+                        folder,
+                    )
+                    .await;
+
+                    if let Err(e) = result {
+                        log::error!("failed to fingerprint {}: {}", snapshot.title, e);
+                    }
+                }
+
+                restored.push(snapshot);
+            }
+            Err(e) => {
+                log::error!("failed to undo the update of {}: {}", snapshot.title, e);
+
+                last_error = Some(e);
+            }
+        }
+    }
+
+    {
+        let _guard = SNAPSHOTS_LOCK.lock().unwrap();
+
+        let mut snapshots = Snapshots::load_or_default()?;
+        for snapshot in restored.iter() {
+            snapshots.remove(flavor, snapshot);
+        }
+        snapshots.save()?;
+    }
+
+    match last_error {
+        Some(e) if restored.is_empty() => Err(e),
+        _ => Ok(restored),
+    }
+}
+
+/// Moves the folders of `snapshot` back into `addon_directory`. Returns the names
+/// of the restored folders.
+fn restore_snapshot(snapshot: &Snapshot, addon_directory: &Path) -> Result<Vec<String>> {
+    let mut names = vec![];
+    for entry in
+        std::fs::read_dir(&snapshot.path).map_err(|e| ClientError::filesystem(&snapshot.path, e))?
+    {
+        names.push(entry?.file_name());
+    }
+
+    // Remove what the update installed, which can include folders the previous
+    // version didn't have.
+    for name in snapshot.installed.iter() {
+        let path = addon_directory.join(name);
+
+        if path.is_dir() && !is_symlink(&path) {
+            remove_dir_all(&path).map_err(|e| ClientError::filesystem(&path, e))?;
+        }
+    }
+
+    for name in names.iter() {
+        let target = addon_directory.join(name);
+
+        if target.is_dir() && !is_symlink(&target) {
+            remove_dir_all(&target).map_err(|e| ClientError::filesystem(&target, e))?;
+        }
+
+        std::fs::rename(snapshot.path.join(name), &target)
+            .map_err(|e| ClientError::filesystem(&target, e))?;
+    }
+
+    let _ = remove_dir_all(&snapshot.path);
+
+    Ok(names
+        .into_iter()
+        .map(|n| n.to_string_lossy().to_string())
+        .collect())
+}
+
+fn is_symlink(path: &Path) -> bool {
+    path.symlink_metadata()
+        .map_or(false, |m| m.file_type().is_symlink())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(batch: u64, addon_id: &str, size: u64) -> Snapshot {
+        Snapshot {
+            batch,
+            addon_id: addon_id.to_string(),
+            title: addon_id.to_string(),
+            updated_to: None,
+            path: PathBuf::from(addon_id),
+            installed: vec![],
+            size,
+            taken_at: SystemTime::now(),
+        }
+    }
+
+    #[test]
+    fn test_record_prunes_oldest_batches() {
+        let mut snapshots = Snapshots::default();
+
+        for batch in 1..=MAX_SNAPSHOT_BATCHES as u64 {
+            assert!(snapshots
+                .record(Flavor::Retail, snapshot(batch, "Details", 10))
+                .is_empty());
+        }
+
+        let pruned = snapshots.record(Flavor::Retail, snapshot(9, "Details", 10));
+        assert_eq!(pruned.iter().map(|s| s.batch).collect::<Vec<_>>(), vec![1]);
+
+        snapshots.record(Flavor::Retail, snapshot(9, "WeakAuras", 10));
+        let last_batch = snapshots.last_batch(Flavor::Retail);
+        assert_eq!(last_batch.len(), 2);
+        assert!(snapshots.last_batch(Flavor::Classic).is_empty());
+
+        // The newest batch is kept, even if it's too large.
+        let pruned = snapshots.record(Flavor::Retail, snapshot(10, "Bagnon", MAX_SNAPSHOTS_SIZE));
+        assert_eq!(pruned.len(), 4);
+        assert_eq!(snapshots.last_batch(Flavor::Retail)[0].addon_id, "Bagnon");
+    }
+}
@@ -1,16 +1,29 @@
 use super::config_dir;
-use crate::theme::Theme;
+use crate::error::ClientError;
+use crate::theme::{export_theme, Theme};
+use crate::Result;
 
-use async_std::fs::{create_dir_all, read_dir, read_to_string};
+use async_std::fs::{create_dir_all, read_dir, read_to_string, write};
 use async_std::stream::StreamExt;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+use std::time::SystemTime;
 
-/// Loads all user defined `.yml` files from the themes
+/// Extensions of the theme files we load.
+const THEME_EXTENSIONS: [&str; 3] = ["yml", "yaml", "toml"];
+
+/// Folder in the config directory users can put theme files in.
+pub fn themes_dir() -> PathBuf {
+    config_dir().join("themes")
+}
+
+/// Loads all user defined `.yml`, `.yaml` and `.toml` files from the themes
 /// folder. Will only return themes that succesfully deserialize
-/// from yaml format to `Theme`.
+/// to `Theme`, the others are logged.
 pub async fn load_user_themes() -> Vec<Theme> {
     let mut themes = vec![];
 
-    let theme_dir = config_dir().join("themes");
+    let theme_dir = themes_dir();
 
     if !theme_dir.exists() {
         let _ = create_dir_all(&theme_dir).await;
@@ -23,11 +36,14 @@ pub async fn load_user_themes() -> Vec<Theme> {
 
                 let extension = path.extension().unwrap_or_default();
 
-                if extension == "yaml" || extension == "yml" {
-                    if let Ok(theme_str) = read_to_string(path).await {
-                        if let Ok(theme) = serde_yaml::from_str(&theme_str) {
-                            themes.push(theme);
-                        }
+                if !is_theme_extension(extension) {
+                    continue;
+                }
+
+                if let Ok(theme_str) = read_to_string(&path).await {
+                    match parse_theme(extension, &theme_str) {
+                        Ok(theme) => themes.push(theme),
+                        Err(e) => log::error!("failed to load theme {:?}: {}", &path, e),
                     }
                 }
             }
@@ -38,3 +54,66 @@ pub async fn load_user_themes() -> Vec<Theme> {
 
     themes
 }
+
+/// Writes `theme` into the themes folder, replacing an earlier version of it.
+pub async fn install_user_theme(theme: &Theme) -> Result<PathBuf> {
+    let theme_dir = themes_dir();
+    create_dir_all(&theme_dir)
+        .await
+        .map_err(|e| ClientError::filesystem(&theme_dir, e))?;
+
+    // Names can have characters which aren't allowed in file names.
+    let file_name = theme
+        .name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    let path = theme_dir.join(format!("{}.yml", file_name));
+
+    write(&path, export_theme(theme)?)
+        .await
+        .map_err(|e| ClientError::filesystem(&path, e))?;
+
+    Ok(path)
+}
+
+/// Returns the number of theme files in the themes folder and when the last of
+/// them was modified, which changes whenever a theme is added, edited or removed.
+pub fn user_themes_modified() -> Option<(usize, SystemTime)> {
+    let entries = std::fs::read_dir(themes_dir()).ok()?;
+
+    let mut count = 0;
+    let mut modified = SystemTime::UNIX_EPOCH;
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        if !is_theme_extension(entry.path().extension().unwrap_or_default()) {
+            continue;
+        }
+
+        count += 1;
+
+        if let Ok(time) = entry.metadata().and_then(|m| m.modified()) {
+            modified = modified.max(time);
+        }
+    }
+
+    Some((count, modified))
+}
+
+fn is_theme_extension(extension: &OsStr) -> bool {
+    THEME_EXTENSIONS.iter().any(|e| extension == *e)
+}
+
+fn parse_theme(extension: &OsStr, theme_str: &str) -> Result<Theme, String> {
+    if extension == "toml" {
+        toml::from_str(theme_str).map_err(|e| e.to_string())
+    } else {
+        serde_yaml::from_str(theme_str).map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,364 @@
+use crate::config::Flavor;
+use crate::localization::localized_string;
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Returns the flavors of World of Warcraft which are running.
+///
+/// Addons changed while the game is open end up half loaded, so updates and
+/// deletions wait for it to exit.
+pub fn running_flavors() -> Vec<Flavor> {
+    let mut flavors = vec![];
+
+    for process in processes() {
+        for flavor in process_flavors(&process) {
+            if !flavors.contains(&flavor) {
+                flavors.push(flavor);
+            }
+        }
+    }
+
+    if !flavors.is_empty() {
+        log::debug!("running flavors: {:?}", &flavors);
+    }
+
+    flavors
+}
+
+/// Returns the flavors a process could be the game client of. `process` is the
+/// name or path of its executable.
+fn process_flavors(process: &str) -> Vec<Flavor> {
+    let components = process
+        .split(|c| c == '/' || c == '\\')
+        .filter(|c| !c.is_empty())
+        .collect::<Vec<_>>();
+    let name = components
+        .last()
+        .copied()
+        .unwrap_or_default()
+        .to_lowercase();
+
+    // Executables on Windows and under Wine, followed by the apps on macOS.
+    let flavors: &[Flavor] = match name.trim_end_matches(".exe") {
+        "wow" | "wow-64" | "world of warcraft" => &[Flavor::Retail],
+        "wowt" | "world of warcraft test" => &[Flavor::RetailPTR, Flavor::RetailXPTR],
+        "wowb" | "world of warcraft beta" => &[Flavor::RetailBeta],
+        "wowclassic" | "world of warcraft classic" => &[Flavor::Classic, Flavor::ClassicEra],
+        "wowclassict" | "world of warcraft classic test" => {
+            &[Flavor::ClassicPTR, Flavor::ClassicEraPTR]
+        }
+        "wowclassicb" | "world of warcraft classic beta" => &[Flavor::ClassicBeta],
+        _ => &[],
+    };
+
+    // Flavors sharing an executable are told apart by the folder it's in, if the
+    // path is known.
+    let by_folder = flavors.iter().find(|f| {
+        let folder_name = f.folder_name();
+        components
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(&folder_name))
+    });
+
+    match by_folder {
+        Some(flavor) => vec![*flavor],
+        None => flavors.to_vec(),
+    }
+}
+
+/// The game client installed for a flavor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ClientBuild {
+    /// Eg. `9.0.5.38134`.
+    pub version: String,
+    /// Interface version addons made for the client declare in their `.toc`,
+    /// eg. 90005.
+    pub interface: u32,
+}
+
+impl ClientBuild {
+    fn new(version: &str) -> Option<Self> {
+        let mut parts = version.trim().split('.').map(|p| p.parse::<u32>().ok());
+
+        let (major, minor, patch) = (parts.next()??, parts.next()??, parts.next()??);
+        if minor > 99 || patch > 99 {
+            return None;
+        }
+
+        Some(ClientBuild {
+            version: version.trim().to_string(),
+            interface: major * 10000 + minor * 100 + patch,
+        })
+    }
+
+    /// Returns how well an addon made for `interface` fits the client.
+    pub fn compatibility(&self, interface: u32) -> Compatibility {
+        let expansion = interface / 10000;
+        let client_expansion = self.interface / 10000;
+
+        if expansion > client_expansion {
+            Compatibility::OtherGameVersion
+        } else if expansion < client_expansion {
+            Compatibility::BadlyOutOfDate
+        } else if interface < self.interface {
+            Compatibility::OutOfDate
+        } else {
+            Compatibility::Compatible
+        }
+    }
+}
+
+/// How well an addon fits the game client, going by its `## Interface`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Compatibility {
+    Compatible,
+    /// Made for an earlier patch. The game only loads it if out of date addons
+    /// are allowed.
+    OutOfDate,
+    /// Made for an earlier expansion, it's likely broken.
+    BadlyOutOfDate,
+    /// Made for a later version of the game than the client, eg. a retail
+    /// addon in Classic.
+    OtherGameVersion,
+}
+
+impl std::fmt::Display for Compatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Compatibility::Compatible => localized_string("compatibility-compatible"),
+            Compatibility::OutOfDate => localized_string("compatibility-out-of-date"),
+            Compatibility::BadlyOutOfDate => localized_string("compatibility-badly-out-of-date"),
+            Compatibility::OtherGameVersion => localized_string("compatibility-other-game-version"),
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Returns the builds of the game clients installed in the World of Warcraft
+/// `directory`, as the launcher keeps them in `.build.info`.
+pub fn client_builds(directory: &Path) -> HashMap<Flavor, ClientBuild> {
+    let builds = match std::fs::read_to_string(directory.join(".build.info")) {
+        Ok(content) => parse_build_info(&content),
+        Err(_) => return HashMap::new(),
+    };
+
+    let mut client_builds = HashMap::new();
+
+    for flavor in Flavor::ALL.iter() {
+        let folder = directory.join(flavor.folder_name());
+        if !folder.is_dir() {
+            continue;
+        }
+
+        // The launcher notes the product of each folder, which is the one it's
+        // known by otherwise.
+        let product = std::fs::read_to_string(folder.join(".flavor.info"))
+            .ok()
+            .and_then(|content| parse_flavor_info(&content))
+            .unwrap_or_else(|| product(*flavor).to_string());
+
+        if let Some((_, build)) = builds.iter().find(|(p, _)| p == &product) {
+            client_builds.insert(*flavor, build.clone());
+        }
+    }
+
+    log::debug!("client builds: {:?}", &client_builds);
+
+    client_builds
+}
+
+/// Product code of `flavor` in the launcher.
+fn product(flavor: Flavor) -> &'static str {
+    match flavor {
+        Flavor::Retail => "wow",
+        Flavor::RetailPTR => "wowt",
+        Flavor::RetailBeta => "wow_beta",
+        Flavor::RetailXPTR => "wowxptr",
+        Flavor::Classic => "wow_classic",
+        Flavor::ClassicPTR => "wow_classic_ptr",
+        Flavor::ClassicBeta => "wow_classic_beta",
+        Flavor::ClassicEra => "wow_classic_era",
+        Flavor::ClassicEraPTR => "wow_classic_era_ptr",
+    }
+}
+
+/// Returns the product and build of each row of `.build.info`, a table with
+/// columns split by `|` and headers like `Version!STRING:0`.
+fn parse_build_info(content: &str) -> Vec<(String, ClientBuild)> {
+    let mut lines = content.lines().filter(|l| !l.trim().is_empty());
+
+    let headers = match lines.next() {
+        Some(line) => line
+            .split('|')
+            .map(|h| h.split('!').next().unwrap_or_default().trim())
+            .collect::<Vec<_>>(),
+        None => return vec![],
+    };
+    let column = |name: &str| headers.iter().position(|h| h.eq_ignore_ascii_case(name));
+
+    let (version, product) = match (column("Version"), column("Product")) {
+        (Some(version), Some(product)) => (version, product),
+        _ => return vec![],
+    };
+
+    lines
+        .filter_map(|line| {
+            let values = line.split('|').collect::<Vec<_>>();
+            let build = ClientBuild::new(values.get(version)?)?;
+
+            Some((values.get(product)?.trim().to_string(), build))
+        })
+        .collect()
+}
+
+/// Returns the product of `.flavor.info`, the line below its header.
+fn parse_flavor_info(content: &str) -> Option<String> {
+    content
+        .lines()
+        .nth(1)
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(windows)]
+fn processes() -> Vec<String> {
+    use std::os::windows::process::CommandExt;
+    use std::process::Command;
+
+    // Keeps a console window from flashing up every time we check.
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let output = match Command::new("tasklist")
+        .args(&["/FO", "CSV", "/NH"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+    {
+        Ok(output) if output.status.success() => output,
+        _ => return vec![],
+    };
+
+    // Each line is `"Wow.exe","1234","Console","1","1,234,567 K"`.
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split(',').next())
+        .map(|name| name.trim_matches('"').to_string())
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+fn processes() -> Vec<String> {
+    use std::process::Command;
+
+    let output = match Command::new("ps").args(&["-Axo", "comm="]).output() {
+        Ok(output) if output.status.success() => output,
+        _ => return vec![],
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .collect()
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn processes() -> Vec<String> {
+    let entries = match std::fs::read_dir("/proc") {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+
+    // The game runs under Wine, which keeps the Windows path of the executable
+    // as the first argument.
+    entries
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.file_name()
+                .to_string_lossy()
+                .chars()
+                .all(|c| c.is_ascii_digit())
+        })
+        .filter_map(|e| std::fs::read(e.path().join("cmdline")).ok())
+        .filter_map(|cmdline| {
+            cmdline
+                .split(|b| *b == 0)
+                .next()
+                .map(|arg| String::from_utf8_lossy(arg).to_string())
+        })
+        .filter(|arg| !arg.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_flavors() {
+        assert_eq!(process_flavors("Wow.exe"), vec![Flavor::Retail]);
+        assert_eq!(
+            process_flavors("C:\\Program Files (x86)\\World of Warcraft\\_ptr_\\WowT.exe"),
+            vec![Flavor::RetailPTR]
+        );
+        assert_eq!(
+            process_flavors("WowClassic.exe"),
+            vec![Flavor::Classic, Flavor::ClassicEra]
+        );
+        assert_eq!(
+            process_flavors("D:/Games/World of Warcraft/_classic_era_/WowClassic.exe"),
+            vec![Flavor::ClassicEra]
+        );
+        assert_eq!(
+            process_flavors(
+                "/Applications/World of Warcraft/_retail_/World of Warcraft.app/Contents/MacOS/World of Warcraft"
+            ),
+            vec![Flavor::Retail]
+        );
+        assert!(process_flavors("/usr/bin/wowza").is_empty());
+        assert!(process_flavors("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_build_info() {
+        let content = "Branch!STRING:0|Active!DEC:1|Build Key!HEX:16|Tags!STRING:0|Version!STRING:0|Product!STRING:0
+eu|1|0a1b2c|Windows x86_64 EU? enUS speech?:Windows x86_64 EU? enUS text?|9.0.5.38134|wow
+eu|1|3d4e5f|Windows x86_64 EU? enUS speech?:Windows x86_64 EU? enUS text?|1.13.7.38631|wow_classic_era
+eu|1|6a7b8c||not-a-version|wowt
+";
+
+        let builds = parse_build_info(content);
+        assert_eq!(builds.len(), 2);
+        assert_eq!(builds[0].0, "wow");
+        assert_eq!(builds[0].1.version, "9.0.5.38134");
+        assert_eq!(builds[0].1.interface, 90005);
+        assert_eq!(builds[1].0, "wow_classic_era");
+        assert_eq!(builds[1].1.interface, 11307);
+
+        assert!(parse_build_info("Branch!STRING:0|Active!DEC:1\neu|1").is_empty());
+        assert!(parse_build_info("").is_empty());
+
+        assert_eq!(
+            parse_flavor_info("Product Flavor!STRING:0\nwow_classic\n"),
+            Some("wow_classic".to_string())
+        );
+        assert_eq!(parse_flavor_info("Product Flavor!STRING:0\n"), None);
+    }
+
+    #[test]
+    fn test_compatibility() {
+        let client = ClientBuild::new("9.0.5.38134").unwrap();
+
+        assert_eq!(client.compatibility(90005), Compatibility::Compatible);
+        assert_eq!(client.compatibility(90100), Compatibility::Compatible);
+        assert_eq!(client.compatibility(90002), Compatibility::OutOfDate);
+        assert_eq!(client.compatibility(80300), Compatibility::BadlyOutOfDate);
+
+        let client = ClientBuild::new("2.5.1.38835").unwrap();
+        assert_eq!(client.interface, 20501);
+        assert_eq!(client.compatibility(90005), Compatibility::OtherGameVersion);
+        assert_eq!(client.compatibility(11307), Compatibility::BadlyOutOfDate);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::config::Hooks;
+
+use serde_json::Value;
+use std::fmt;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    UpdateAllStarted,
+    AddonUpdated,
+    UpdateAllFinished,
+}
+
+impl HookEvent {
+    fn command(self, hooks: &Hooks) -> Option<&str> {
+        let command = match self {
+            HookEvent::UpdateAllStarted => &hooks.update_all_started,
+            HookEvent::AddonUpdated => &hooks.addon_updated,
+            HookEvent::UpdateAllFinished => &hooks.update_all_finished,
+        };
+
+        command.as_deref().filter(|c| !c.trim().is_empty())
+    }
+}
+
+impl fmt::Display for HookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            HookEvent::UpdateAllStarted => "update-all-started",
+            HookEvent::AddonUpdated => "addon-updated",
+            HookEvent::UpdateAllFinished => "update-all-finished",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Runs the command set for `event`, if any, and waits for it to exit.
+///
+/// `payload` is written to its stdin as JSON, along with the event. Fields of
+/// `payload` which aren't lists or objects are also set as environment variables,
+/// eg. `title` as `AJOUR_TITLE`. A failing command is logged, but doesn't stop
+/// the update.
+pub fn run_hook(hooks: &Hooks, event: HookEvent, mut payload: Value) {
+    let command = match event.command(hooks) {
+        Some(command) => command,
+        None => return,
+    };
+
+    if let Value::Object(fields) = &mut payload {
+        fields.insert("event".to_string(), Value::String(event.to_string()));
+    }
+
+    log::debug!("running {} hook: {}", event, command);
+
+    match spawn_hook(command, &payload).and_then(|child| child.wait_with_output()) {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => log::error!(
+            "{} hook exited with {}: {}",
+            event,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+        Err(e) => log::error!("{} hook failed to run: {}", event, e),
+    }
+}
+
+fn spawn_hook(command: &str, payload: &Value) -> std::io::Result<std::process::Child> {
+    #[cfg(windows)]
+    let mut process = {
+        let mut process = Command::new("cmd");
+        process.arg("/C").arg(command);
+        process
+    };
+
+    #[cfg(not(windows))]
+    let mut process = {
+        let mut process = Command::new("sh");
+        process.arg("-c").arg(command);
+        process
+    };
+
+    // Output is captured, so it doesn't end up in the results of the command line.
+    process
+        .envs(hook_env(payload))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = process.spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // Commands which don't read stdin close it early, which is fine.
+        let _ = stdin.write_all(payload.to_string().as_bytes());
+    }
+
+    Ok(child)
+}
+
+/// Environment variables of the fields of `payload` which aren't lists or objects.
+fn hook_env(payload: &Value) -> Vec<(String, String)> {
+    let fields = match payload {
+        Value::Object(fields) => fields,
+        _ => return vec![],
+    };
+
+    fields
+        .iter()
+        .filter_map(|(key, value)| {
+            let value = match value {
+                Value::String(value) => value.clone(),
+                Value::Number(value) => value.to_string(),
+                Value::Bool(value) => value.to_string(),
+                Value::Null => String::new(),
+                Value::Array(_) | Value::Object(_) => return None,
+            };
+
+            Some((format!("AJOUR_{}", key.to_uppercase()), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_hook_env() {
+        let payload = json!({
+            "event": "addon-updated",
+            "title": "Details!",
+            "old_version": null,
+            "updated": 3,
+            "addons": [],
+        });
+
+        let mut env = hook_env(&payload);
+        env.sort();
+
+        assert_eq!(
+            env,
+            vec![
+                ("AJOUR_EVENT".to_string(), "addon-updated".to_string()),
+                ("AJOUR_OLD_VERSION".to_string(), String::new()),
+                ("AJOUR_TITLE".to_string(), "Details!".to_string()),
+                ("AJOUR_UPDATED".to_string(), "3".to_string()),
+            ]
+        );
+    }
+}
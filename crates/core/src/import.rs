@@ -0,0 +1,387 @@
+use crate::{
+    addon::{Addon, ReleaseChannel, Repository},
+    config::{Config, Flavor},
+    curse_api,
+    error::ClientError,
+    fs::{
+        links::{RepositoryLink, RepositoryLinks},
+        PersistentData,
+    },
+    tukui_api, Result,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Version of the export format, bumped when it changes in a way older versions of
+/// Ajour can't read.
+pub const EXPORT_VERSION: u32 = 1;
+
+/// The addons managed by Ajour, exported so they can be installed on another machine.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AddonExport {
+    pub version: u32,
+    pub addons: Vec<ExportedAddon>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedAddon {
+    pub flavor: Flavor,
+    pub repository: Repository,
+    pub id: String,
+    pub title: String,
+    /// Primary folder of the addon, which its settings are stored under.
+    pub folder: String,
+    pub release_channel: ReleaseChannel,
+}
+
+impl ExportedAddon {
+    /// Returns `None` if the addon isn't from a repository.
+    pub fn new(addon: &Addon, flavor: Flavor) -> Option<Self> {
+        Some(ExportedAddon {
+            flavor,
+            repository: addon.active_repository?,
+            id: addon.repository_id()?,
+            title: addon.title().to_string(),
+            folder: addon.primary_folder_id.clone(),
+            release_channel: addon.release_channel,
+        })
+    }
+
+    /// Returns true if `addon` is installed from the same repository project.
+    pub fn matches(&self, addon: &Addon) -> bool {
+        addon.active_repository == Some(self.repository)
+            && addon.repository_id().as_deref() == Some(self.id.as_str())
+    }
+
+    /// Fetches the latest version of the addon from its repository.
+    pub async fn fetch(&self) -> Result<Addon> {
+        let mut addon = match self.repository {
+            Repository::Curse => {
+                let id = self.id.parse().map_err(|_| {
+                    ClientError::Custom(format!("Invalid Curse id {} for {}", self.id, self.title))
+                })?;
+
+                curse_api::latest_addon(id, self.flavor).await?
+            }
+            Repository::Tukui => {
+                let package = tukui_api::fetch_remote_package(&self.id, &self.flavor).await?;
+
+                Addon::from_tukui_package(self.id.clone(), &[], &package)
+            }
+            Repository::WowI => {
+                return Err(ClientError::Custom(format!(
+                    "Can't install {}, WowInterface isn't supported yet",
+                    self.title
+                )))
+            }
+        };
+
+        addon.release_channel = self.release_channel;
+
+        Ok(addon)
+    }
+}
+
+/// An addon installed by WowUp, as stored in its `addons.json`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WowUpAddon {
+    name: String,
+    provider_name: String,
+    external_id: Option<String>,
+    /// Comma separated names of the addon folders.
+    installed_folders: Option<String>,
+}
+
+/// The addons installed by the CurseForge client, as stored in the JSON it keeps
+/// of each game instance.
+#[derive(Debug, Deserialize)]
+struct CurseForgeInstance {
+    #[serde(rename = "installedAddons", default)]
+    installed_addons: Vec<CurseForgeAddon>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurseForgeAddon {
+    #[serde(rename = "addonID")]
+    addon_id: u32,
+    #[serde(rename = "installedFile")]
+    installed_file: Option<CurseForgeFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurseForgeFile {
+    #[serde(default)]
+    modules: Vec<CurseForgeModule>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurseForgeModule {
+    foldername: String,
+}
+
+/// An addon installed by another addon manager, with the folders it installed.
+struct ForeignAddon {
+    name: String,
+    /// Name of the source of the addon in the other addon manager.
+    source: String,
+    /// `None` if Ajour doesn't support the source.
+    link: Option<RepositoryLink>,
+    folders: Vec<String>,
+}
+
+/// Amount of addons linked and skipped during an import.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub linked: usize,
+    pub skipped: usize,
+}
+
+/// Returns the path to WowUp's `addons.json`, if WowUp is installed.
+pub fn wowup_addons_path() -> Option<PathBuf> {
+    let path = dirs_next::config_dir()?.join("WowUp").join("addons.json");
+
+    if path.exists() {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+/// Links the addons installed by WowUp to their repositories, so they can be
+/// updated by Ajour.
+///
+/// Addons are linked for every flavor which has their folders installed. Only addons
+/// from Curse and Tukui are imported.
+pub fn import_wowup_addons(path: &Path, config: &Config) -> Result<ImportSummary> {
+    let addons = parse_wowup_addons(&std::fs::read_to_string(path)?)?
+        .into_iter()
+        .map(|addon| ForeignAddon {
+            link: wowup_link(&addon),
+            folders: addon
+                .installed_folders
+                .as_deref()
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|folder| !folder.is_empty())
+                .map(str::to_string)
+                .collect(),
+            name: addon.name,
+            source: addon.provider_name,
+        })
+        .collect();
+
+    link_foreign_addons(addons, config)
+}
+
+/// Links the addons installed by the CurseForge client to their Curse projects,
+/// so they can be updated by Ajour.
+///
+/// Addons are linked for every flavor which has their folders installed.
+pub fn import_curseforge_addons(path: &Path, config: &Config) -> Result<ImportSummary> {
+    let addons = parse_curseforge_addons(&std::fs::read_to_string(path)?)?
+        .into_iter()
+        .map(|addon| ForeignAddon {
+            // The file only has the id of the project, not its name.
+            name: addon.addon_id.to_string(),
+            source: "CurseForge".to_string(),
+            link: Some(RepositoryLink {
+                repository: Repository::Curse,
+                id: addon.addon_id.to_string(),
+                is_override: false,
+            }),
+            folders: addon
+                .installed_file
+                .map(|file| file.modules.into_iter().map(|m| m.foldername).collect())
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    link_foreign_addons(addons, config)
+}
+
+fn link_foreign_addons(addons: Vec<ForeignAddon>, config: &Config) -> Result<ImportSummary> {
+    let mut links = RepositoryLinks::load_or_default()?;
+    let mut summary = ImportSummary::default();
+
+    for addon in addons {
+        let link = match addon.link {
+            Some(link) => link,
+            None => {
+                log::debug!(
+                    "skipping {} from {}, the source isn't supported",
+                    &addon.name,
+                    &addon.source
+                );
+
+                summary.skipped += 1;
+                continue;
+            }
+        };
+
+        let mut is_linked = false;
+
+        for flavor in Flavor::ALL.iter() {
+            let addon_directory = match config.get_addon_directory_for_flavor(flavor) {
+                Some(directory) if directory.exists() => directory,
+                _ => continue,
+            };
+
+            for folder in addon.folders.iter() {
+                if addon_directory.join(folder).exists() {
+                    links.insert(*flavor, folder.to_string(), link.clone());
+                    is_linked = true;
+                }
+            }
+        }
+
+        if is_linked {
+            summary.linked += 1;
+        } else {
+            log::debug!("skipping {}, its folders aren't installed", &addon.name);
+
+            summary.skipped += 1;
+        }
+    }
+
+    links.save()?;
+
+    Ok(summary)
+}
+
+fn parse_wowup_addons(json: &str) -> Result<Vec<WowUpAddon>> {
+    // WowUp stores its addons in an object keyed by the id of the addon.
+    let entries = match serde_json::from_str(json)? {
+        Value::Object(map) => map.into_iter().map(|(_, value)| value).collect(),
+        Value::Array(entries) => entries,
+        _ => {
+            return Err(ClientError::ParseError(
+                "Unrecognized WowUp addons file".to_string(),
+            ))
+        }
+    };
+
+    // Entries which aren't addons are skipped.
+    Ok(entries
+        .into_iter()
+        .filter_map(|entry| serde_json::from_value(entry).ok())
+        .collect())
+}
+
+fn parse_curseforge_addons(json: &str) -> Result<Vec<CurseForgeAddon>> {
+    let instance: CurseForgeInstance = serde_json::from_str(json)
+        .map_err(|_| ClientError::ParseError("Unrecognized CurseForge addons file".to_string()))?;
+
+    Ok(instance.installed_addons)
+}
+
+fn wowup_link(addon: &WowUpAddon) -> Option<RepositoryLink> {
+    let id = addon.external_id.clone()?;
+
+    let repository = match addon.provider_name.to_lowercase().as_str() {
+        "curse" => {
+            id.parse::<u32>().ok()?;
+
+            Repository::Curse
+        }
+        "tukui" => Repository::Tukui,
+        _ => return None,
+    };
+
+    Some(RepositoryLink {
+        repository,
+        id,
+        is_override: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_wowup_addons() {
+        let json = r#"{
+            "a1": {
+                "name": "Deadly Boss Mods",
+                "providerName": "Curse",
+                "externalId": "3358",
+                "installedFolders": "DBM-Core, DBM-GUI"
+            },
+            "a2": {
+                "name": "ElvUI",
+                "providerName": "TukUI",
+                "externalId": "-1",
+                "installedFolders": "ElvUI,ElvUI_OptionsUI"
+            },
+            "a3": {
+                "name": "WeakAuras",
+                "providerName": "GitHub",
+                "externalId": "WeakAuras/WeakAuras2",
+                "installedFolders": "WeakAuras"
+            },
+            "settings": true
+        }"#;
+
+        let addons = parse_wowup_addons(json).unwrap();
+        assert_eq!(addons.len(), 3);
+
+        let mut links = addons.iter().filter_map(wowup_link).collect::<Vec<_>>();
+        links.sort_by(|a, b| a.id.cmp(&b.id));
+
+        assert_eq!(
+            links,
+            vec![
+                RepositoryLink {
+                    repository: Repository::Tukui,
+                    id: "-1".to_string(),
+                    is_override: false,
+                },
+                RepositoryLink {
+                    repository: Repository::Curse,
+                    id: "3358".to_string(),
+                    is_override: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_curseforge_addons() {
+        let json = r#"{
+            "gameVersion": "9.0.2",
+            "installedAddons": [
+                {
+                    "addonID": 3358,
+                    "installedFile": {
+                        "id": 3147411,
+                        "modules": [
+                            { "foldername": "DBM-Core", "fingerprint": 1 },
+                            { "foldername": "DBM-GUI", "fingerprint": 2 }
+                        ]
+                    }
+                },
+                { "addonID": 13501 }
+            ]
+        }"#;
+
+        let addons = parse_curseforge_addons(json).unwrap();
+        assert_eq!(addons.len(), 2);
+        assert_eq!(addons[0].addon_id, 3358);
+
+        let folders = addons[0]
+            .installed_file
+            .as_ref()
+            .unwrap()
+            .modules
+            .iter()
+            .map(|m| m.foldername.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(folders, vec!["DBM-Core", "DBM-GUI"]);
+        assert!(addons[1].installed_file.is_none());
+
+        assert!(parse_curseforge_addons("[]").is_err());
+    }
+}
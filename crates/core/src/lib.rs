@@ -1,17 +1,33 @@
+pub mod abandoned;
 pub mod addon;
 pub mod backup;
 pub mod catalog;
 pub mod config;
 pub mod curse_api;
+pub mod diagnostics;
+pub mod download;
 pub mod error;
 pub mod fs;
+pub mod game;
+pub mod hooks;
+pub mod import;
+pub mod link;
+pub mod localization;
+pub mod logs;
 pub mod murmur2;
 pub mod network;
 pub mod parse;
+pub mod self_update;
+pub mod sync;
 #[cfg(feature = "gui")]
 pub mod theme;
 pub mod tukui_api;
+pub mod update;
 pub mod utility;
+pub mod verify;
+pub mod version;
+pub mod wago;
+pub mod webhook;
 
 use crate::error::ClientError;
 
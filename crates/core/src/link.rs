@@ -0,0 +1,309 @@
+use crate::catalog::Source;
+use crate::config::Flavor;
+use crate::error::ClientError;
+use crate::Result;
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Scheme of links to install addons with Ajour, eg. `ajour://install/curse/3358`.
+pub const AJOUR_SCHEME: &str = "ajour";
+
+/// Scheme of the "Install" links on CurseForge, eg.
+/// `curseforge://install?addonId=3358&fileId=3201`.
+pub const CURSEFORGE_SCHEME: &str = "curseforge";
+
+/// An addon of the catalog to install, from a link opened in the browser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstallLink {
+    pub source: Source,
+    pub id: u32,
+    /// Flavor the link was made for, if it tells.
+    pub flavor: Option<Flavor>,
+}
+
+/// Parses an install link. Understands `ajour://install/<source>/<id>`, the same
+/// with `source` and `id` in the query, and the CurseForge links which use
+/// `addonId`. A `flavor`, eg. `?flavor=classic`, is optional.
+pub fn parse_install_link(link: &str) -> Option<InstallLink> {
+    let link = link.trim();
+    let separator = link.find("://")?;
+    let scheme = link[..separator].to_lowercase();
+    let rest = &link[separator + 3..];
+
+    let (path, query) = match rest.find('?') {
+        Some(index) => (&rest[..index], &rest[index + 1..]),
+        None => (rest, ""),
+    };
+    let segments = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>();
+    let param = |name: &str| {
+        query
+            .split('&')
+            .filter_map(|pair| {
+                let mut pair = pair.splitn(2, '=');
+                Some((pair.next()?, pair.next().unwrap_or_default()))
+            })
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    };
+
+    if !segments
+        .first()
+        .map_or(false, |s| s.eq_ignore_ascii_case("install"))
+    {
+        return None;
+    }
+
+    let (source, id) = match scheme.as_str() {
+        AJOUR_SCHEME if segments.len() >= 3 => (parse_source(segments[1])?, segments[2]),
+        AJOUR_SCHEME => match param("source") {
+            Some(source) => (parse_source(source)?, param("id")?),
+            None => (Source::Curse, param("addonId")?),
+        },
+        CURSEFORGE_SCHEME => (Source::Curse, param("addonId")?),
+        _ => return None,
+    };
+
+    Some(InstallLink {
+        source,
+        id: id.parse().ok()?,
+        flavor: param("flavor").and_then(|f| Flavor::from_str(f).ok()),
+    })
+}
+
+fn parse_source(source: &str) -> Option<Source> {
+    match source.to_lowercase().as_str() {
+        "curse" | "curseforge" => Some(Source::Curse),
+        "tukui" => Some(Source::Tukui),
+        _ => None,
+    }
+}
+
+/// Registers Ajour as the handler of links with `schemes`, for the current user.
+/// Links are opened with `ajour open <link>`.
+pub fn register_link_handler(schemes: &[&str]) -> Result<()> {
+    let executable = executable()?;
+
+    for scheme in schemes {
+        register_scheme(scheme, &executable)?;
+    }
+
+    log::debug!("registered {:?} links to {:?}", schemes, &executable);
+
+    Ok(())
+}
+
+/// Removes Ajour as the handler of links with `schemes`. Schemes taken over by
+/// another app since are left alone.
+pub fn unregister_link_handler(schemes: &[&str]) -> Result<()> {
+    let executable = executable()?;
+
+    for scheme in schemes {
+        unregister_scheme(scheme, &executable)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the executable links are opened with.
+fn executable() -> Result<PathBuf> {
+    // The executable of an AppImage is inside its mount, which is gone once it exits.
+    if let Some(appimage) = std::env::var_os("APPIMAGE") {
+        return Ok(PathBuf::from(appimage));
+    }
+
+    Ok(std::env::current_exe()?)
+}
+
+#[cfg(windows)]
+fn reg(args: &[&str]) -> Result<String> {
+    use std::os::windows::process::CommandExt;
+    use std::process::Command;
+
+    // Keeps a console window from flashing up when registering from the GUI.
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let output = Command::new("reg")
+        .args(args)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(ClientError::Custom(format!(
+            "Failed to update the registry: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+#[cfg(windows)]
+fn register_scheme(scheme: &str, executable: &std::path::Path) -> Result<()> {
+    let key = format!("HKCU\\Software\\Classes\\{}", scheme);
+    let command = format!("\"{}\" open \"%1\"", executable.display());
+
+    reg(&["add", &key, "/ve", "/d", "URL:Ajour", "/f"])?;
+    reg(&["add", &key, "/v", "URL Protocol", "/d", "", "/f"])?;
+    reg(&[
+        "add",
+        &format!("{}\\shell\\open\\command", key),
+        "/ve",
+        "/d",
+        &command,
+        "/f",
+    ])?;
+
+    Ok(())
+}
+
+#[cfg(windows)]
+fn unregister_scheme(scheme: &str, executable: &std::path::Path) -> Result<()> {
+    let key = format!("HKCU\\Software\\Classes\\{}", scheme);
+
+    let is_ours = reg(&["query", &format!("{}\\shell\\open\\command", key), "/ve"])
+        .map_or(false, |output| {
+            output.contains(&executable.display().to_string())
+        });
+
+    if is_ours {
+        reg(&["delete", &key, "/f"])?;
+    }
+
+    Ok(())
+}
+
+/// Desktop entry links are opened with, apart from the one of the app, since it
+/// opens Ajour without the link.
+#[cfg(not(any(windows, target_os = "macos")))]
+fn desktop_entry_path() -> Result<PathBuf> {
+    let data_dir = dirs_next::data_dir()
+        .ok_or_else(|| ClientError::Custom("Couldn't find the data directory".to_string()))?;
+
+    Ok(data_dir.join("applications").join("ajour-links.desktop"))
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn register_scheme(scheme: &str, executable: &std::path::Path) -> Result<()> {
+    use std::process::Command;
+
+    let path = desktop_entry_path()?;
+
+    // The entry lists every scheme registered so far.
+    let mut schemes = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|entry| {
+            entry
+                .lines()
+                .find(|l| l.starts_with("MimeType="))
+                .map(|l| l.trim_start_matches("MimeType=").to_string())
+        })
+        .unwrap_or_default()
+        .split(';')
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    let mime_type = format!("x-scheme-handler/{}", scheme);
+    if !schemes.contains(&mime_type) {
+        schemes.push(mime_type.clone());
+    }
+
+    let entry = format!(
+        "[Desktop Entry]\nType=Application\nName=Ajour\nExec=\"{}\" open %u\nTerminal=false\nNoDisplay=true\nMimeType={};\n",
+        executable.display(),
+        schemes.join(";")
+    );
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| ClientError::filesystem(parent, e))?;
+    }
+    std::fs::write(&path, entry).map_err(|e| ClientError::filesystem(&path, e))?;
+
+    let status = Command::new("xdg-mime")
+        .args(&["default", "ajour-links.desktop", &mime_type])
+        .status();
+
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        _ => Err(ClientError::Custom(format!(
+            "Failed to make Ajour open {}:// links, is xdg-mime installed?",
+            scheme
+        ))),
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn unregister_scheme(scheme: &str, _executable: &std::path::Path) -> Result<()> {
+    let path = desktop_entry_path()?;
+
+    let entry = match std::fs::read_to_string(&path) {
+        Ok(entry) => entry,
+        Err(_) => return Ok(()),
+    };
+
+    // Links of a scheme are no longer opened by the entry once it doesn't list it.
+    let mime_type = format!("x-scheme-handler/{};", scheme);
+    let entry = entry.replace(&mime_type, "");
+
+    if entry.contains("x-scheme-handler/") {
+        std::fs::write(&path, entry).map_err(|e| ClientError::filesystem(&path, e))
+    } else {
+        std::fs::remove_file(&path).map_err(|e| ClientError::filesystem(&path, e))
+    }
+}
+
+/// Apps on macOS declare their schemes in the bundle, and receive the links as
+/// events rather than arguments.
+#[cfg(target_os = "macos")]
+fn register_scheme(_scheme: &str, _executable: &std::path::Path) -> Result<()> {
+    Err(ClientError::Custom(
+        "Opening links with Ajour isn't supported on macOS yet".to_string(),
+    ))
+}
+
+#[cfg(target_os = "macos")]
+fn unregister_scheme(_scheme: &str, _executable: &std::path::Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_install_link() {
+        let link = |source, id, flavor| Some(InstallLink { source, id, flavor });
+
+        assert_eq!(
+            parse_install_link("ajour://install/curse/3358"),
+            link(Source::Curse, 3358, None)
+        );
+        assert_eq!(
+            parse_install_link("ajour://install/tukui/1/?flavor=classic"),
+            link(Source::Tukui, 1, Some(Flavor::Classic))
+        );
+        assert_eq!(
+            parse_install_link("ajour://install?source=tukui&id=2"),
+            link(Source::Tukui, 2, None)
+        );
+        assert_eq!(
+            parse_install_link("curseforge://install?addonId=3358&fileId=3201"),
+            link(Source::Curse, 3358, None)
+        );
+        assert_eq!(
+            parse_install_link("Ajour://Install?addonid=61284&flavor=retail"),
+            link(Source::Curse, 61284, Some(Flavor::Retail))
+        );
+
+        assert_eq!(parse_install_link("ajour://install/wowi/1"), None);
+        assert_eq!(parse_install_link("ajour://remove/curse/3358"), None);
+        assert_eq!(parse_install_link("curseforge://install?fileId=3201"), None);
+        assert_eq!(parse_install_link("https://install/curse/3358"), None);
+        assert_eq!(parse_install_link("ajour://install/curse/elvui"), None);
+        assert_eq!(parse_install_link(""), None);
+    }
+}
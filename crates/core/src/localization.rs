@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+lazy_static::lazy_static! {
+    /// Strings of the bundled locales, by key.
+    static ref LOCALES: HashMap<Language, HashMap<String, String>> = Language::ALL
+        .iter()
+        .map(|language| {
+            let strings = serde_yaml::from_str(language.locale())
+                .expect("bundled locales are valid yaml");
+
+            (*language, strings)
+        })
+        .collect();
+
+    static ref LANGUAGE: RwLock<Language> = RwLock::new(Language::default());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Language {
+    English,
+    German,
+    French,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::German, Language::French];
+
+    /// Returns the language of the system if there's a locale for it, from the
+    /// environment on Linux and macOS. English otherwise.
+    pub fn system() -> Language {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        Language::ALL
+            .iter()
+            .copied()
+            .find(|l| locale.starts_with(l.code()))
+            .unwrap_or_default()
+    }
+
+    /// ISO 639-1 code of the language.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::French => "fr",
+        }
+    }
+
+    fn locale(self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locale/en.yml"),
+            Language::German => include_str!("../locale/de.yml"),
+            Language::French => include_str!("../locale/fr.yml"),
+        }
+    }
+}
+
+impl Default for Language {
+    fn default() -> Language {
+        Language::English
+    }
+}
+
+impl std::fmt::Display for Language {
+    /// Languages are shown in their own name, so they can be found without
+    /// understanding the current one.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+            Language::French => "Français",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Sets the language strings are localized to.
+pub fn set_language(language: Language) {
+    *LANGUAGE.write().unwrap() = language;
+}
+
+/// Returns the language strings are localized to.
+pub fn language() -> Language {
+    *LANGUAGE.read().unwrap()
+}
+
+/// Returns the string of `key` in the current language. Strings which aren't
+/// translated yet fall back to English, and unknown keys to the key itself.
+pub fn localized_string(key: &str) -> String {
+    string_in(language(), key)
+}
+
+/// Returns the string of `key` in the current language, with each `{name}` in it
+/// replaced by the value of `name` in `args`.
+pub fn localized_string_with(key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    replace_args(localized_string(key), args)
+}
+
+fn string_in(language: Language, key: &str) -> String {
+    [language, Language::English]
+        .iter()
+        .find_map(|l| LOCALES.get(l).and_then(|strings| strings.get(key)))
+        .cloned()
+        .unwrap_or_else(|| key.to_string())
+}
+
+fn replace_args(mut string: String, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    for (name, value) in args {
+        string = string.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+
+    string
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locales_match_english() {
+        let english = &LOCALES[&Language::English];
+
+        for language in Language::ALL.iter() {
+            for (key, string) in LOCALES[language].iter() {
+                let original = english
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{} has unknown key {}", language, key));
+
+                // Translations have to use the same placeholders.
+                let placeholders = |s: &str| {
+                    let mut names = s
+                        .split('{')
+                        .skip(1)
+                        .filter_map(|s| s.split('}').next())
+                        .map(str::to_string)
+                        .collect::<Vec<_>>();
+                    names.sort();
+                    names
+                };
+                assert_eq!(
+                    placeholders(string),
+                    placeholders(original),
+                    "{} has other placeholders in {}",
+                    language,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_localized_string_with() {
+        // The language isn't set, as it's shared with tests of localized strings.
+        assert_eq!(string_in(Language::German, "settings"), "Einstellungen");
+        assert_eq!(string_in(Language::German, "unknown-key"), "unknown-key");
+        assert_eq!(
+            replace_args(
+                string_in(Language::German, "addons-loaded"),
+                &[("count", &3), ("flavor", &"Retail"), ("size", &"1.4 MB")]
+            ),
+            "3 Retail-Addons geladen (1.4 MB belegt)"
+        );
+    }
+}
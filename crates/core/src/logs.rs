@@ -0,0 +1,236 @@
+use crate::fs::config_dir;
+use crate::Result;
+
+use log::Level;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Lines read from the end of the log, older lines are left out.
+const MAX_LINES: usize = 2000;
+
+/// Path of the log written by the GUI.
+pub fn log_file() -> PathBuf {
+    config_dir().join("ajour.log")
+}
+
+/// Log which is moved aside once it grows past `max_size` bytes. The logs before
+/// it are kept as `ajour.1.log`, `ajour.2.log` and so on, up to `keep_count`.
+pub struct RotatingLog {
+    path: PathBuf,
+    keep_count: usize,
+    max_size: u64,
+    file: File,
+    size: u64,
+}
+
+impl RotatingLog {
+    /// Starts a new log at `path`, keeping the one of the last run.
+    pub fn open(path: impl Into<PathBuf>, keep_count: usize, max_size: u64) -> io::Result<Self> {
+        let path = path.into();
+
+        rotate_logs(&path, keep_count)?;
+        let file = File::create(&path)?;
+
+        Ok(RotatingLog {
+            path,
+            keep_count,
+            max_size,
+            file,
+            size: 0,
+        })
+    }
+}
+
+impl Write for RotatingLog {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > self.max_size {
+            self.file.flush()?;
+            rotate_logs(&self.path, self.keep_count)?;
+
+            self.file = File::create(&self.path)?;
+            self.size = 0;
+        }
+
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Path of the `n`th log before the one at `path`.
+fn rotated_log(path: &Path, n: usize) -> PathBuf {
+    path.with_extension(format!("{}.log", n))
+}
+
+/// Moves each kept log one place back, dropping the oldest.
+fn rotate_logs(path: &Path, keep_count: usize) -> io::Result<()> {
+    if !path.exists() || keep_count == 0 {
+        return Ok(());
+    }
+
+    let _ = std::fs::remove_file(rotated_log(path, keep_count));
+
+    for n in (1..keep_count).rev() {
+        let from = rotated_log(path, n);
+        if from.exists() {
+            std::fs::rename(from, rotated_log(path, n + 1))?;
+        }
+    }
+
+    std::fs::rename(path, rotated_log(path, 1))
+}
+
+/// A record of the log, as formatted by the logger: `time [target][LEVEL] message`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine {
+    pub time: String,
+    pub target: String,
+    pub level: Level,
+    /// Message of the record, including the lines following it, eg. of a panic.
+    pub message: String,
+}
+
+impl LogLine {
+    fn parse(line: &str) -> Option<LogLine> {
+        let (time, rest) = line.split_once(" [")?;
+        let (target, rest) = rest.split_once("][")?;
+        let (level, message) = rest.split_once("] ")?;
+
+        Some(LogLine {
+            time: time.to_string(),
+            target: target.to_string(),
+            level: Level::from_str(level).ok()?,
+            message: message.to_string(),
+        })
+    }
+
+    /// The record as it's written in the log.
+    pub fn text(&self) -> String {
+        format!(
+            "{} [{}][{}] {}",
+            self.time, self.target, self.level, self.message
+        )
+    }
+}
+
+/// Parses the records of a log. Lines which don't start a record are added to
+/// the message of the record before them.
+pub fn parse_log(text: &str) -> Vec<LogLine> {
+    let mut lines = vec![];
+    append_log(&mut lines, text);
+
+    lines
+}
+
+/// Parses the records of `text` onto `lines`, eg. the part of a log written since
+/// it was last read. Lines which don't start a record are added to the message of
+/// the record before them, also if it was parsed before.
+pub fn append_log(lines: &mut Vec<LogLine>, text: &str) {
+    for line in text.lines() {
+        if let Some(record) = LogLine::parse(line) {
+            lines.push(record);
+        } else if let Some(last) = lines.last_mut() {
+            last.message.push('\n');
+            last.message.push_str(line);
+        }
+    }
+
+    if lines.len() > MAX_LINES {
+        lines.drain(..lines.len() - MAX_LINES);
+    }
+}
+
+/// Reads the records of the log at `path`.
+pub async fn read_log(path: impl AsRef<Path>) -> Result<Vec<LogLine>> {
+    let bytes = async_std::fs::read(path.as_ref()).await?;
+
+    Ok(parse_log(&String::from_utf8_lossy(&bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotating_log() {
+        let directory = std::env::temp_dir().join(format!("ajour-logs-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("ajour.log");
+        std::fs::write(&path, "last run").unwrap();
+
+        let mut rotating_log = RotatingLog::open(&path, 2, 10).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(directory.join("ajour.1.log")).unwrap(),
+            "last run"
+        );
+
+        rotating_log.write_all(b"0123456789").unwrap();
+        rotating_log.write_all(b"abc").unwrap();
+        rotating_log.write_all(b"def").unwrap();
+        rotating_log.flush().unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "abcdef");
+        assert_eq!(
+            std::fs::read_to_string(directory.join("ajour.1.log")).unwrap(),
+            "0123456789"
+        );
+        assert_eq!(
+            std::fs::read_to_string(directory.join("ajour.2.log")).unwrap(),
+            "last run"
+        );
+        assert!(!directory.join("ajour.3.log").exists());
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn test_parse_log() {
+        let text = "\
+12:00:00.001 [ajour][INFO] Ajour 1.0.0 has started.
+12:00:01.100 [ajour_core::network][WARN] rate limited [by] api.curseforge.com
+12:00:02.000 [panic][ERROR] thread 'main' panicked
+   0: backtrace
+not a [record] line";
+
+        let lines = parse_log(text);
+        assert_eq!(lines.len(), 3);
+
+        assert_eq!(lines[0].time, "12:00:00.001");
+        assert_eq!(lines[0].target, "ajour");
+        assert_eq!(lines[0].level, Level::Info);
+
+        assert_eq!(lines[1].target, "ajour_core::network");
+        assert_eq!(lines[1].level, Level::Warn);
+        assert_eq!(lines[1].message, "rate limited [by] api.curseforge.com");
+        assert_eq!(
+            lines[1].text(),
+            "12:00:01.100 [ajour_core::network][WARN] rate limited [by] api.curseforge.com"
+        );
+
+        assert_eq!(lines[2].level, Level::Error);
+        assert_eq!(
+            lines[2].message,
+            "thread 'main' panicked\n   0: backtrace\nnot a [record] line"
+        );
+    }
+
+    #[test]
+    fn test_append_log() {
+        let mut lines = parse_log("12:00:02.000 [panic][ERROR] thread 'main' panicked\n");
+
+        append_log(
+            &mut lines,
+            "   0: backtrace\n12:00:03.000 [ajour][INFO] Ajour 1.0.0 has started.\n",
+        );
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].message, "thread 'main' panicked\n   0: backtrace");
+        assert_eq!(lines[1].message, "Ajour 1.0.0 has started.");
+    }
+}
@@ -28,6 +28,17 @@ pub fn calculate_hash(data: &[u8], seed: u32) -> u32 {
     h ^ h >> 15
 }
 
+/// Calculates the fingerprint Curse uses for a file, which ignores whitespace.
+pub fn calculate_fingerprint(data: &[u8]) -> u32 {
+    let data = data
+        .iter()
+        .copied()
+        .filter(|&b| b != b' ' && b != b'\n' && b != b'\r' && b != b'\t')
+        .collect::<Vec<_>>();
+
+    calculate_hash(&data, 1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -39,4 +50,12 @@ mod tests {
         let res = calculate_hash(data, 1);
         assert_eq!(res, 851_628_572);
     }
+
+    #[test]
+    fn test_fingerprint_ignores_whitespace() {
+        let data = b"## Interface: 80300\r\n## Title: Addon\n\tlocal a = 1";
+        let stripped = b"##Interface:80300##Title:Addonlocala=1";
+
+        assert_eq!(calculate_fingerprint(data), calculate_hash(stripped, 1));
+    }
 }
@@ -1,107 +1,945 @@
-use crate::{addon::Addon, error::ClientError, Result};
+use crate::{
+    addon::Addon,
+    config::Tokens,
+    download::Download,
+    error::ClientError,
+    fs::{archive::verify_archive, responses::CachedResponse},
+    Result,
+};
 use async_std::{
-    fs::{create_dir_all, File},
-    io::copy,
+    fs::{create_dir_all, remove_file, rename, File, OpenOptions},
+    io::prelude::{ReadExt, WriteExt},
+};
+use isahc::http::{
+    header::{HeaderName, CONTENT_LENGTH, ETAG, LAST_MODIFIED, RETRY_AFTER},
+    HeaderMap, StatusCode, Uri,
 };
-use isahc::http::header::CONTENT_LENGTH;
 use isahc::prelude::*;
 use serde::Serialize;
-use std::path::PathBuf;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Amount of times a request is retried after a transient failure, unless
+/// configured otherwise.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Delay before the first retry, doubled on every following attempt.
+const BASE_RETRY_DELAY_MS: u64 = 500;
+
+/// Upper bound for the delay between two attempts, before jitter is added.
+const MAX_RETRY_DELAY_MS: u64 = 8_000;
+
+/// Upper bound for how long we wait when a host asks us to retry later.
+const MAX_RETRY_AFTER_SECS: u64 = 60;
+
+/// Requests sent to a host per second at most, so refreshing a lot of addons
+/// doesn't get us throttled.
+const MAX_REQUESTS_PER_SECOND: u32 = 10;
+
+/// Tukui throttles sooner than the other hosts.
+const MAX_TUKUI_REQUESTS_PER_SECOND: u32 = 4;
+
+/// Amount of times a request is retried. Set from the config when it's loaded.
+static MAX_RETRIES: AtomicU32 = AtomicU32::new(DEFAULT_MAX_RETRIES);
+
+/// Offline mode, in which no requests are sent. Set from the config when it's loaded.
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Set when a request couldn't connect at all, and cleared by the next one which did.
+static CONNECTION_LOST: AtomicBool = AtomicBool::new(false);
+
+lazy_static::lazy_static! {
+    /// Proxy used for all requests. Set from the config when it's loaded.
+    static ref PROXY: Mutex<Option<Uri>> = Mutex::new(None);
+
+    /// Tokens authenticating requests to the repositories. Set from the config
+    /// when it's loaded.
+    static ref TOKENS: Mutex<Tokens> = Mutex::new(Tokens::default());
+
+    static ref RATE_LIMITER: Mutex<RateLimiter> = Mutex::new(RateLimiter::default());
+
+    /// Requests in flight by url. Identical requests made meanwhile wait for the
+    /// response of the first one instead of being sent again.
+    static ref IN_FLIGHT: Mutex<HashMap<String, Arc<async_std::sync::Mutex<Option<BufferedResponse>>>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Spaces out the requests to each host, and pauses them when a host rate limits us.
+#[derive(Default)]
+struct RateLimiter {
+    hosts: HashMap<String, HostBudget>,
+}
+
+struct HostBudget {
+    next_request: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// Reserves the next request to `host`, returning how long to wait before sending it.
+    fn reserve(&mut self, host: &str, now: Instant) -> Duration {
+        let requests_per_second = if host.ends_with("tukui.org") {
+            MAX_TUKUI_REQUESTS_PER_SECOND
+        } else {
+            MAX_REQUESTS_PER_SECOND
+        };
+        let interval = Duration::from_secs(1) / requests_per_second;
+
+        let budget = self.budget(host, now);
+        let start = budget.next_request.max(now);
+        budget.next_request = start + interval;
+
+        start - now
+    }
+
+    /// Holds back requests to `host` for `duration`, after it rate limited us.
+    fn pause(&mut self, host: &str, now: Instant, duration: Duration) {
+        let budget = self.budget(host, now);
+        let until = now + duration;
+
+        budget.next_request = budget.next_request.max(until);
+        budget.paused_until = Some(until);
+    }
+
+    fn budget(&mut self, host: &str, now: Instant) -> &mut HostBudget {
+        self.hosts
+            .entry(host.to_string())
+            .or_insert_with(|| HostBudget {
+                next_request: now,
+                paused_until: None,
+            })
+    }
+
+    /// Returns the host paused the longest, and how long until it's resumed.
+    fn paused(&self, now: Instant) -> Option<(String, Duration)> {
+        self.hosts
+            .iter()
+            .filter_map(|(host, budget)| {
+                let until = budget.paused_until.filter(|until| *until > now)?;
+
+                Some((host.clone(), until - now))
+            })
+            .max_by_key(|(_, remaining)| *remaining)
+    }
+}
+
+/// Returns the host which is currently rate limiting us, and how long until
+/// requests to it are retried.
+pub fn rate_limited() -> Option<(String, Duration)> {
+    RATE_LIMITER.lock().unwrap().paused(Instant::now())
+}
+
+fn host(url: &str) -> Option<String> {
+    url.parse::<Uri>().ok()?.host().map(str::to_string)
+}
+
+/// Waits until a request to the host of `url` fits its budget.
+async fn wait_for_host(url: &str) {
+    let host = match host(url) {
+        Some(host) => host,
+        None => return,
+    };
+
+    let delay = RATE_LIMITER.lock().unwrap().reserve(&host, Instant::now());
+    if delay > Duration::from_millis(0) {
+        async_std::task::sleep(delay).await;
+    }
+}
+
+/// Returns the delay a rate limited response asks us to wait before retrying.
+/// Only the delay in seconds is supported, not a date.
+fn retry_after(resp: &Response<isahc::Body>) -> Option<Duration> {
+    let seconds = resp
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+
+    Some(Duration::from_secs(seconds.min(MAX_RETRY_AFTER_SECS)))
+}
+
+/// A response read into memory, so it can be shared between identical requests.
+#[derive(Clone)]
+struct BufferedResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+impl BufferedResponse {
+    fn to_response(&self) -> Response<isahc::Body> {
+        let mut resp = Response::new(self.body.clone().into());
+        *resp.status_mut() = self.status;
+        *resp.headers_mut() = self.headers.clone();
+
+        resp
+    }
+}
+
+/// Sets the proxy used for all requests, eg. `http://proxy:3128` or
+/// `socks5h://proxy:1080`.
+///
+/// Without a proxy, curl falls back to the `HTTPS_PROXY`, `HTTP_PROXY` and
+/// `ALL_PROXY` environment variables.
+pub fn set_proxy(proxy: Option<&str>) -> Result<()> {
+    let proxy = match proxy.map(str::trim).filter(|p| !p.is_empty()) {
+        Some(proxy) => Some(
+            proxy
+                .parse::<Uri>()
+                .map_err(|e| ClientError::Custom(format!("Invalid proxy {}: {}", proxy, e)))?,
+        ),
+        None => None,
+    };
+
+    *PROXY.lock().unwrap() = proxy;
+
+    Ok(())
+}
+
+pub(crate) fn proxy() -> Option<Uri> {
+    PROXY.lock().unwrap().clone()
+}
+
+/// Sets how many times a request is retried after a transient failure.
+/// `0` disables retrying.
+pub fn set_max_retries(max_retries: u32) {
+    MAX_RETRIES.store(max_retries, Ordering::Relaxed);
+}
+
+/// Sets the tokens sent to the repositories they belong to.
+pub fn set_tokens(tokens: Tokens) {
+    *TOKENS.lock().unwrap() = tokens;
+}
+
+/// Returns the header authenticating a request to `url`, if we have a token for
+/// its host.
+fn auth_header(url: &str) -> Option<(&'static str, String)> {
+    TOKENS.lock().unwrap().header(&host(url)?)
+}
+
+fn max_attempts() -> u32 {
+    // Retrying won't help until the connection is back.
+    if CONNECTION_LOST.load(Ordering::Relaxed) {
+        return 1;
+    }
+
+    MAX_RETRIES.load(Ordering::Relaxed) + 1
+}
+
+/// Turns offline mode on or off. While offline no requests are sent, and the
+/// cached responses are used instead.
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+/// Returns true if offline mode is on, or if we lost the connection. Either way
+/// responses come from the cache.
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed) || CONNECTION_LOST.load(Ordering::Relaxed)
+}
+
+/// Returns the cached response of `key` in place of a request which failed
+/// because we are offline, or the error if there is none.
+fn offline_fallback(key: &str, error: ClientError) -> Result<BufferedResponse> {
+    if !error.is_unavailable() {
+        return Err(error);
+    }
+
+    match CachedResponse::load(key).map(|c| c.body()) {
+        Some(Ok(body)) => {
+            log::debug!("{} couldn't be requested, using cached response", key);
+
+            Ok(BufferedResponse {
+                status: StatusCode::OK,
+                headers: HeaderMap::new(),
+                body,
+            })
+        }
+        _ => Err(error),
+    }
+}
+
+/// Keeps track of whether we could connect, from the result of a request.
+fn track_connection<T>(result: &std::result::Result<T, isahc::Error>) {
+    let connection_lost = matches!(
+        result,
+        Err(isahc::Error::ConnectFailed) | Err(isahc::Error::CouldntResolveHost)
+    );
+
+    if CONNECTION_LOST.swap(connection_lost, Ordering::Relaxed) != connection_lost {
+        if connection_lost {
+            log::warn!("connection lost, using cached responses");
+        } else {
+            log::info!("connection is back");
+        }
+    }
+}
+
+/// Returns true for statuses a CDN or API commonly returns while it's overloaded,
+/// which are likely to succeed when requested again.
+pub(crate) fn is_transient_status(status: StatusCode) -> bool {
+    matches!(status.as_u16(), 408 | 429 | 500 | 502 | 503 | 504)
+}
+
+/// Returns the error for an unsuccessful response of `repository` to `action`,
+/// eg. "fetch the catalog".
+pub(crate) fn status_error(
+    repository: &str,
+    action: &str,
+    resp: &mut Response<isahc::Body>,
+) -> ClientError {
+    let repository = repository.to_string();
+
+    match resp.status() {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            ClientError::Unauthorized { repository }
+        }
+        StatusCode::TOO_MANY_REQUESTS => ClientError::RateLimited { repository },
+        status if is_transient_status(status) => ClientError::Unavailable(format!(
+            "{} is unavailable. Server returned: {}",
+            repository, status
+        )),
+        status => {
+            // Error pages can be whole html documents.
+            let message = resp.text().unwrap_or_default();
+            let message = message.trim().chars().take(200).collect();
+
+            ClientError::Repository {
+                repository,
+                action: action.to_string(),
+                status: status.as_u16(),
+                message,
+            }
+        }
+    }
+}
+
+/// Returns true for errors caused by a flaky connection rather than the request itself.
+fn is_transient_error(error: &isahc::Error) -> bool {
+    matches!(
+        error,
+        isahc::Error::ConnectFailed
+            | isahc::Error::CouldntResolveHost
+            | isahc::Error::NoResponse
+            | isahc::Error::Timeout
+            | isahc::Error::Io(_)
+    )
+}
+
+/// Returns the delay before retrying after `attempt` failed attempts.
+///
+/// The delay grows exponentially and up to half of it is added as jitter, so
+/// concurrent downloads don't all retry at the same moment.
+fn retry_delay(attempt: u32) -> Duration {
+    let delay = BASE_RETRY_DELAY_MS
+        .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+        .min(MAX_RETRY_DELAY_MS);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as u64)
+        .unwrap_or_default();
+    let jitter = nanos % (delay / 2 + 1);
+
+    Duration::from_millis(delay + jitter)
+}
 
 /// Generic request function.
+///
+/// Responses with an `ETag` or `Last-Modified` header are cached on disk. Once
+/// cached, the request is made conditional and the cached body is returned if
+/// the server responds it wasn't modified, so unchanged API responses aren't
+/// downloaded again.
+///
+/// Identical requests made while one is in flight share its response.
 pub async fn request_async<T: ToString>(
     shared_client: &HttpClient,
     url: T,
     headers: Vec<(&str, &str)>,
     timeout: Option<u64>,
 ) -> Result<Response<isahc::Body>> {
-    // Sometimes a download url has a space.
     let url = url.to_string().replace(" ", "%20");
 
-    let mut request = Request::builder().uri(url);
+    // Requests with headers might not be identical, so they aren't shared.
+    if !headers.is_empty() {
+        return Ok(request_cached(shared_client, &url, headers, timeout)
+            .await?
+            .to_response());
+    }
 
-    for (name, value) in headers {
-        request = request.header(name, value);
+    let slot = IN_FLIGHT
+        .lock()
+        .unwrap()
+        .entry(url.clone())
+        .or_default()
+        .clone();
+    let mut shared = slot.lock().await;
+
+    let result = match shared.as_ref() {
+        Some(response) => {
+            log::trace!("{} was requested meanwhile, using its response", url);
+
+            Ok(response.clone())
+        }
+        None => {
+            let result = request_cached(shared_client, &url, headers, timeout).await;
+            if let Ok(response) = result.as_ref() {
+                *shared = Some(response.clone());
+            }
+
+            result
+        }
+    };
+    drop(shared);
+
+    // Later requests are sent again, rather than getting this response.
+    let mut in_flight = IN_FLIGHT.lock().unwrap();
+    if in_flight.get(&url).map_or(false, |s| Arc::ptr_eq(s, &slot)) {
+        in_flight.remove(&url);
     }
 
-    if let Some(timeout) = timeout {
-        request = request.timeout(std::time::Duration::from_secs(timeout));
+    Ok(result?.to_response())
+}
+
+/// Sends the request, revalidating the cached response of `url` if there is one.
+async fn request_cached(
+    shared_client: &HttpClient,
+    url: &str,
+    headers: Vec<(&str, &str)>,
+    timeout: Option<u64>,
+) -> Result<BufferedResponse> {
+    let cached = CachedResponse::load(url);
+
+    // Rebound so the validators, which borrow `cached`, can be added.
+    let mut headers: Vec<(&str, &str)> = headers;
+    if let Some(cached) = cached.as_ref() {
+        if let Some(etag) = cached.etag.as_deref() {
+            headers.push(("If-None-Match", etag));
+        }
+        if let Some(last_modified) = cached.last_modified.as_deref() {
+            headers.push(("If-Modified-Since", last_modified));
+        }
+    }
+
+    let resp = match send_with_retries(shared_client, url, headers, timeout).await {
+        Ok(resp) => resp,
+        Err(e) => return offline_fallback(url, e),
+    };
+    let (parts, mut body) = resp.into_parts();
+
+    if parts.status == StatusCode::NOT_MODIFIED {
+        if let Some(Ok(cached_body)) = cached.as_ref().map(CachedResponse::body) {
+            log::trace!("{} not modified, using cached response", url);
+
+            return Ok(BufferedResponse {
+                status: StatusCode::OK,
+                headers: parts.headers,
+                body: cached_body,
+            });
+        }
+    }
+
+    let mut bytes = vec![];
+    body.read_to_end(&mut bytes).await?;
+
+    if parts.status.is_success() {
+        let etag = header_value(&parts.headers, ETAG);
+        let last_modified = header_value(&parts.headers, LAST_MODIFIED);
+
+        if let Err(e) = CachedResponse::store(url, etag, last_modified, &bytes) {
+            log::warn!("failed to cache response of {}: {}", url, e);
+        }
+    }
+
+    Ok(BufferedResponse {
+        status: parts.status,
+        headers: parts.headers,
+        body: bytes,
+    })
+}
+
+fn header_value(headers: &HeaderMap, name: HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Sends a request, without caching.
+///
+/// Requests are spaced out per host. Requests failing with a transient error or
+/// status are retried with an exponential backoff, or after the delay the host
+/// asked for when it rate limits us. The response of the last attempt is returned
+/// if all of them fail with a transient status.
+async fn send_with_retries<T: ToString>(
+    shared_client: &HttpClient,
+    url: T,
+    headers: Vec<(&str, &str)>,
+    timeout: Option<u64>,
+) -> Result<Response<isahc::Body>> {
+    // Sometimes a download url has a space.
+    let url = url.to_string().replace(" ", "%20");
+
+    if OFFLINE.load(Ordering::Relaxed) {
+        return Err(ClientError::Offline);
     }
 
-    Ok(shared_client.send_async(request.body(())?).await?)
+    let max_attempts = max_attempts();
+    let mut attempt = 1;
+
+    let auth_header = auth_header(&url);
+
+    loop {
+        let mut request = Request::builder().uri(url.as_str());
+
+        for (name, value) in headers.iter() {
+            request = request.header(*name, *value);
+        }
+
+        if let Some((name, value)) = auth_header.as_ref() {
+            request = request.header(*name, value.as_str());
+        }
+
+        if let Some(timeout) = timeout {
+            request = request.timeout(Duration::from_secs(timeout));
+        }
+
+        if let Some(proxy) = proxy() {
+            request = request.proxy(proxy);
+        }
+
+        wait_for_host(&url).await;
+        let result = shared_client.send_async(request.body(())?).await;
+        track_connection(&result);
+
+        let failure = match &result {
+            Ok(resp) if is_transient_status(resp.status()) => Some(resp.status().to_string()),
+            Err(e) if is_transient_error(e) => Some(e.to_string()),
+            _ => None,
+        };
+
+        match failure {
+            Some(failure) if attempt < max_attempts => {
+                let mut delay = retry_delay(attempt);
+
+                // Requests to the same host are held back as well, so they
+                // don't get rate limited too.
+                if let Ok(resp) = result.as_ref() {
+                    if resp.status() == StatusCode::TOO_MANY_REQUESTS {
+                        delay = retry_after(resp).unwrap_or(delay);
+
+                        if let Some(host) = host(&url) {
+                            log::warn!("rate limited by {}, retrying in {:?}", host, delay);
+
+                            RATE_LIMITER
+                                .lock()
+                                .unwrap()
+                                .pause(&host, Instant::now(), delay);
+                        }
+                    }
+                }
+
+                log::warn!(
+                    "request to {} failed with {} (attempt {}/{}), retrying in {:?}",
+                    url,
+                    failure,
+                    attempt,
+                    max_attempts,
+                    delay
+                );
+
+                async_std::task::sleep(delay).await;
+                attempt += 1;
+            }
+            Some(failure) => {
+                if attempt > 1 {
+                    log::error!(
+                        "request to {} failed with {} after {} attempts",
+                        url,
+                        failure,
+                        attempt
+                    );
+                }
+
+                return Ok(result?);
+            }
+            None => return Ok(result?),
+        }
+    }
 }
 
 // Generic function for posting Json data
+//
+// Responses are cached by url and data, but only used while offline.
 pub async fn post_json_async<T: ToString, D: Serialize>(
     url: T,
     data: D,
     headers: Vec<(&str, &str)>,
     timeout: Option<u64>,
 ) -> Result<Response<isahc::Body>> {
+    let url = url.to_string();
+    let data = serde_json::to_vec(&data)?;
+
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    let cache_key = format!("{}#{:x}", url, hasher.finish());
+
+    if OFFLINE.load(Ordering::Relaxed) {
+        return Ok(offline_fallback(&cache_key, ClientError::Offline)?.to_response());
+    }
+
+    wait_for_host(&url).await;
+
     let mut request = Request::builder()
         .method("POST")
-        .uri(url.to_string())
+        .uri(url.as_str())
         .header("content-type", "application/json");
 
     for (name, value) in headers {
         request = request.header(name, value);
     }
 
+    if let Some((name, value)) = auth_header(&url) {
+        request = request.header(name, value);
+    }
+
     if let Some(timeout) = timeout {
-        request = request.timeout(std::time::Duration::from_secs(timeout));
+        request = request.timeout(Duration::from_secs(timeout));
+    }
+
+    if let Some(proxy) = proxy() {
+        request = request.proxy(proxy);
+    }
+
+    let result = request.body(data)?.send_async().await;
+    track_connection(&result);
+
+    let resp = match result {
+        Ok(resp) => resp,
+        Err(e) => return Ok(offline_fallback(&cache_key, e.into())?.to_response()),
+    };
+    let (parts, mut body) = resp.into_parts();
+
+    let mut bytes = vec![];
+    body.read_to_end(&mut bytes).await?;
+
+    if parts.status.is_success() {
+        if let Err(e) = CachedResponse::store(&cache_key, None, None, &bytes) {
+            log::warn!("failed to cache response of {}: {}", url, e);
+        }
     }
 
-    Ok(request
-        .body(serde_json::to_vec(&data)?)?
-        .send_async()
-        .await?)
+    Ok(BufferedResponse {
+        status: parts.status,
+        headers: parts.headers,
+        body: bytes,
+    }
+    .to_response())
 }
 
 /// Function to download a zip archive for a `Addon`.
 /// Note: Addon needs to have a `remote_url` to the file.
+///
+/// If the repository exposes a fingerprint of the file, the download is verified
+/// against it and removed if it doesn't match.
+///
+/// If a queued `download` is given, it waits for its turn before downloading.
+///
+/// Fails if the addon has no release package for its release channel, since
+/// unpacking would otherwise pick up whatever archive was left in `to_directory`.
 pub async fn download_addon(
     shared_client: &HttpClient,
     addon: &Addon,
     to_directory: &PathBuf,
+    mut download: Option<&mut Download>,
 ) -> Result<()> {
-    if let Some(package) = addon.relevant_release_package() {
-        log::debug!(
-            "downloading remote version {} for {}",
-            package.version,
-            &addon.primary_folder_id
-        );
-        let resp = request_async(shared_client, package.download_url.clone(), vec![], None).await?;
-        let (parts, body) = resp.into_parts();
-
-        // If response length doesn't equal content length, full file wasn't downloaded
-        // so error out
-        {
-            let content_length = parts
-                .headers
-                .get(CONTENT_LENGTH)
-                .map(|v| v.to_str().unwrap_or_default())
-                .unwrap_or_default()
-                .parse::<u64>()
-                .unwrap_or_default();
-
-            let body_length = body.len().unwrap_or_default();
-
-            if body_length != content_length {
-                return Err(ClientError::Custom(
-                    "Download failed, body len doesn't match content len".to_string(),
-                ));
-            }
+    let package = addon.relevant_release_package().ok_or_else(|| {
+        ClientError::Custom(format!(
+            "No {} release of {} to download",
+            addon.release_channel.to_string().to_lowercase(),
+            addon.title()
+        ))
+    })?;
+
+    log::debug!(
+        "downloading remote version {} for {}",
+        package.version,
+        &addon.primary_folder_id
+    );
+    let zip_path = to_directory.join(&addon.primary_folder_id);
+
+    // Never unpack an archive left over from an earlier download.
+    if zip_path.exists() {
+        remove_file(&zip_path).await?;
+    }
+
+    if let Some(download) = download.as_mut() {
+        download.start().await?;
+    }
+
+    download_file(
+        shared_client,
+        &package.download_url,
+        &zip_path,
+        download.as_deref(),
+    )
+    .await
+    .map_err(|e| download_error(addon, e))?;
+
+    if let Some(fingerprint) = package.fingerprint {
+        if let Err(e) = verify_archive(&zip_path, fingerprint) {
+            let _ = remove_file(&zip_path).await;
+
+            return Err(download_error(addon, e));
+        }
+    }
+
+    Ok(())
+}
+
+/// Names the addon in errors which would otherwise only mention its url or archive.
+fn download_error(addon: &Addon, error: ClientError) -> ClientError {
+    match error {
+        ClientError::Custom(message) => {
+            ClientError::Custom(format!("{}: {}", addon.title(), message))
         }
+        error => error,
+    }
+}
 
-        if !to_directory.exists() {
-            create_dir_all(to_directory).await?;
+/// Downloads the file at `url` to `to_path`, creating the parent directory
+/// if needed.
+///
+/// The file is downloaded to a `.part` file next to `to_path` first. If a previous
+/// download of the same url was interrupted, it's resumed with a `Range` request.
+/// Downloads interrupted while reading the body are resumed the same way, with
+/// the backoff used by `request_async`.
+///
+/// Progress is reported to `download`, which is also checked for cancellation.
+/// Once it's paused, the connection is closed and the rest is downloaded the same
+/// way after it's resumed.
+pub async fn download_file(
+    shared_client: &HttpClient,
+    url: &str,
+    to_path: &Path,
+    download: Option<&Download>,
+) -> Result<()> {
+    if let Some(dir) = to_path.parent() {
+        if !dir.exists() {
+            create_dir_all(dir).await?;
         }
+    }
 
-        let zip_path = to_directory.join(&addon.primary_folder_id);
-        let file = File::create(&zip_path).await?;
+    let part_path = part_path(url, to_path);
 
-        copy(body, file).await?;
+    let max_attempts = max_attempts();
+    let mut attempt = 1;
+
+    loop {
+        match download_part(shared_client, url, &part_path, download).await {
+            Ok(true) => break,
+            Ok(false) => {
+                if let Some(download) = download {
+                    download.wait_while_paused().await?;
+                }
+            }
+            Err(ClientError::IoError(e)) if attempt < max_attempts => {
+                let delay = retry_delay(attempt);
+
+                log::warn!(
+                    "download of {} was interrupted (attempt {}/{}), resuming in {:?}: {}",
+                    url,
+                    attempt,
+                    max_attempts,
+                    delay,
+                    e
+                );
+
+                async_std::task::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
     }
 
+    rename(&part_path, to_path).await?;
+
     Ok(())
 }
+
+/// Downloads `url` to `part_path`, continuing from the end of the file if it exists.
+///
+/// Returns `false` if the download was paused before it finished.
+async fn download_part(
+    shared_client: &HttpClient,
+    url: &str,
+    part_path: &Path,
+    download: Option<&Download>,
+) -> Result<bool> {
+    let resume_from = async_std::fs::metadata(part_path)
+        .await
+        .map(|m| m.len())
+        .unwrap_or_default();
+
+    let range = format!("bytes={}-", resume_from);
+    let headers = if resume_from > 0 {
+        log::debug!("resuming download of {} from byte {}", url, resume_from);
+
+        vec![("Range", range.as_str())]
+    } else {
+        vec![]
+    };
+
+    // Downloads are too large to cache, and are cached as archives once installed.
+    let mut resp = send_with_retries(shared_client, url, headers, None).await?;
+
+    if !resp.status().is_success() {
+        // The partial file is likely stale, so start over next time.
+        if resp.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            let _ = remove_file(part_path).await;
+        }
+
+        let host = host(url).unwrap_or_default();
+        return Err(status_error(&host, "download the file", &mut resp));
+    }
+
+    // Servers which don't support ranges send the whole file again.
+    let append = resp.status() == StatusCode::PARTIAL_CONTENT && resume_from > 0;
+    let (parts, mut body) = resp.into_parts();
+
+    // If response length doesn't equal content length, full file wasn't downloaded
+    // so error out
+    let content_length = parts
+        .headers
+        .get(CONTENT_LENGTH)
+        .map(|v| v.to_str().unwrap_or_default())
+        .unwrap_or_default()
+        .parse::<u64>()
+        .unwrap_or_default();
+
+    let body_length = body.len().unwrap_or_default();
+    if body_length != content_length {
+        return Err(ClientError::IncompleteDownload {
+            url: url.to_string(),
+            expected: content_length,
+            received: body_length,
+        });
+    }
+
+    // Errors writing the file aren't interruptions, so they aren't retried.
+    let fs_error = |e: std::io::Error| ClientError::filesystem(part_path, e);
+
+    let (mut file, mut bytes) = if append {
+        let file = OpenOptions::new()
+            .append(true)
+            .open(part_path)
+            .await
+            .map_err(fs_error)?;
+
+        (file, resume_from)
+    } else {
+        (File::create(part_path).await.map_err(fs_error)?, 0)
+    };
+
+    let total = Some(bytes + content_length).filter(|_| content_length > 0);
+    let mut buffer = vec![0; 64 * 1024];
+
+    loop {
+        if let Some(download) = download {
+            download.check_cancelled()?;
+
+            if download.is_paused() {
+                file.flush().await.map_err(fs_error)?;
+
+                return Ok(false);
+            }
+        }
+
+        let read = body.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+
+        file.write_all(&buffer[..read]).await.map_err(fs_error)?;
+        bytes += read as u64;
+
+        if let Some(download) = download {
+            download.set_progress(bytes, total);
+        }
+    }
+
+    file.flush().await.map_err(fs_error)?;
+
+    Ok(true)
+}
+
+/// Returns the path partial downloads of `url` to `to_path` are written to.
+///
+/// The url is part of the name, so a partial download of another version of an
+/// addon is never resumed.
+fn part_path(url: &str, to_path: &Path) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+
+    let file_name = to_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    to_path.with_file_name(format!("{}.{:x}.part", file_name, hasher.finish()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay() {
+        for attempt in 1..=8 {
+            let expected = BASE_RETRY_DELAY_MS
+                .saturating_mul(1 << (attempt - 1))
+                .min(MAX_RETRY_DELAY_MS);
+            let delay = retry_delay(attempt).as_millis() as u64;
+
+            assert!(delay >= expected);
+            assert!(delay <= expected + expected / 2);
+        }
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let mut limiter = RateLimiter::default();
+        let now = Instant::now();
+        let interval = Duration::from_secs(1) / MAX_REQUESTS_PER_SECOND;
+
+        assert_eq!(
+            limiter.reserve("addons-ecs.forgesvc.net", now),
+            Duration::from_secs(0)
+        );
+        assert_eq!(limiter.reserve("addons-ecs.forgesvc.net", now), interval);
+        assert_eq!(
+            limiter.reserve("www.tukui.org", now),
+            Duration::from_secs(0)
+        );
+        assert_eq!(limiter.paused(now), None);
+
+        limiter.pause("www.tukui.org", now, Duration::from_secs(5));
+        assert_eq!(
+            limiter.paused(now),
+            Some(("www.tukui.org".to_string(), Duration::from_secs(5)))
+        );
+        assert_eq!(
+            limiter.reserve("www.tukui.org", now),
+            Duration::from_secs(5)
+        );
+        assert_eq!(limiter.paused(now + Duration::from_secs(5)), None);
+    }
+}
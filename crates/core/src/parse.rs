@@ -1,13 +1,16 @@
 use crate::{
-    addon::{Addon, AddonFolder, AddonState, RepositoryIdentifiers},
-    config::Flavor,
+    addon::{Addon, AddonFolder, AddonState, Repository, RepositoryIdentifiers},
+    config::{Flavor, SourcePriority},
     curse_api::{
         fetch_game_info, fetch_remote_packages_by_fingerprint, fetch_remote_packages_by_ids,
         GameInfo,
     },
     error::ClientError,
-    fs::PersistentData,
-    murmur2::calculate_hash,
+    fs::{
+        database::record_addons, installed::InstalledAddons, links::RepositoryLinks, size_on_disk,
+        PersistentData,
+    },
+    murmur2::{calculate_fingerprint, calculate_hash},
     tukui_api::fetch_remote_package,
     Result,
 };
@@ -22,8 +25,65 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::SystemTime;
 
+/// Amount of threads addon folders are parsed with. Set from the config when it's
+/// loaded, `0` uses one per CPU.
+static PARSE_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+/// Addon folders fingerprinted, and found, since the progress was last reset.
+static FOLDERS_SCANNED: AtomicUsize = AtomicUsize::new(0);
+static FOLDERS_FOUND: AtomicUsize = AtomicUsize::new(0);
+
 lazy_static::lazy_static! {
     static ref CACHED_GAME_INFO: Mutex<Option<GameInfo>> = Mutex::new(None);
+
+    /// Pool along with the amount of threads it was built with.
+    static ref THREAD_POOL: std::sync::Mutex<Option<(usize, Arc<rayon::ThreadPool>)>> =
+        std::sync::Mutex::new(None);
+}
+
+/// Sets how many threads addon folders are parsed with. `0` uses one per CPU.
+pub fn set_parse_threads(threads: usize) {
+    PARSE_THREADS.store(threads, Ordering::Relaxed);
+}
+
+/// Returns how many addon folders have been fingerprinted out of the ones found,
+/// since `reset_scan_progress` was called.
+pub fn scan_progress() -> (usize, usize) {
+    (
+        FOLDERS_SCANNED.load(Ordering::Relaxed),
+        FOLDERS_FOUND.load(Ordering::Relaxed),
+    )
+}
+
+pub fn reset_scan_progress() {
+    FOLDERS_SCANNED.store(0, Ordering::Relaxed);
+    FOLDERS_FOUND.store(0, Ordering::Relaxed);
+}
+
+/// Returns the pool addon folders are parsed on, built again if the amount of
+/// threads changed.
+fn thread_pool() -> Result<Arc<rayon::ThreadPool>> {
+    let threads = PARSE_THREADS.load(Ordering::Relaxed);
+    let mut guard = THREAD_POOL.lock().unwrap();
+
+    if let Some((pool_threads, pool)) = guard.as_ref() {
+        if *pool_threads == threads {
+            return Ok(pool.clone());
+        }
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .thread_name(|i| format!("ajour-parse-{}", i))
+        .build()
+        .map_err(|e| ClientError::Custom(format!("Couldn't start threads for parsing: {}", e)))?;
+    let pool = Arc::new(pool);
+
+    log::debug!("parsing addons with {} threads", pool.current_num_threads());
+
+    *guard = Some((threads, pool.clone()));
+
+    Ok(pool)
 }
 
 #[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
@@ -31,6 +91,37 @@ pub struct Fingerprint {
     pub title: String,
     pub hash: Option<u32>,
     pub modified: SystemTime,
+    /// Size of the folder itself, not of its files.
+    #[serde(default)]
+    pub size: u64,
+    /// The folder as parsed from its `.toc`, reused until the `.toc` changes.
+    #[serde(default)]
+    pub toc: Option<CachedToc>,
+}
+
+/// Bumped when more is read from a `.toc`, so cached ones are parsed again.
+const TOC_CACHE_FORMAT: u32 = 1;
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct CachedToc {
+    path: PathBuf,
+    modified: SystemTime,
+    size: u64,
+    folder: AddonFolder,
+    #[serde(default)]
+    format: u32,
+}
+
+/// Returns when `path` was last modified and its size, which tell whether a
+/// cached scan of it is still valid.
+fn modified_and_size(path: &Path) -> (SystemTime, u64) {
+    match path.metadata() {
+        Ok(metadata) => (
+            metadata.modified().unwrap_or_else(|_| SystemTime::now()),
+            metadata.len(),
+        ),
+        Err(_) => (SystemTime::now(), 0),
+    }
 }
 
 #[derive(Serialize, Deserialize, Default)]
@@ -107,6 +198,7 @@ pub async fn read_addon_directory<P: AsRef<Path>>(
     fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
     root_dir: P,
     flavor: Flavor,
+    source_priority: SourcePriority,
 ) -> Result<Vec<Addon>> {
     log::debug!("{} - parsing addons folder", flavor);
 
@@ -144,6 +236,8 @@ pub async fn read_addon_directory<P: AsRef<Path>>(
         return Ok(vec![]);
     }
 
+    let _ = FOLDERS_FOUND.fetch_add(all_dirs.len(), Ordering::Relaxed);
+
     let ParsingPatterns {
         initial_inclusion_regex,
         extra_inclusion_regex,
@@ -160,51 +254,56 @@ pub async fn read_addon_directory<P: AsRef<Path>>(
     let fingerprint_collection = collection_guard.as_mut().unwrap();
     let fingerprints = fingerprint_collection.get_mut_for_flavor(flavor);
 
+    // Folders are fingerprinted and parsed across the threads of the pool.
+    let pool = thread_pool()?;
+
     // Each addon dir mapped to fingerprint struct.
     let num_cached = AtomicUsize::new(0);
-    let new_fingerprints: Vec<_> = all_dirs
-        .par_iter() // Easy parallelization
-        .map(|dir_name| {
-            let addon_dir = root_dir.join(dir_name);
-            let modified = if let Ok(metadata) = addon_dir.metadata() {
-                metadata.modified().unwrap_or_else(|_| SystemTime::now())
-            } else {
-                SystemTime::now()
-            };
+    let new_fingerprints: Vec<_> = pool.install(|| {
+        all_dirs
+            .par_iter() // Easy parallelization
+            .map(|dir_name| {
+                let _ = FOLDERS_SCANNED.fetch_add(1, Ordering::Relaxed);
 
-            // If we have a stored fingerprint on disk, we use that.
-            if let Some(fingerprint) = fingerprints
-                .iter()
-                .find(|f| &f.title == dir_name && f.modified == modified)
-            {
-                let _ = num_cached.fetch_add(1, Ordering::SeqCst);
-                fingerprint.to_owned()
-            } else {
-                let hash_result = fingerprint_addon_dir(
-                    &addon_dir,
-                    &initial_inclusion_regex,
-                    &extra_inclusion_regex,
-                    &file_parsing_regex,
-                );
+                let addon_dir = root_dir.join(dir_name);
+                let (modified, size) = modified_and_size(&addon_dir);
 
-                let hash = match hash_result {
-                    Ok(hash) => Some(hash),
-                    Err(e) => {
-                        log::error!("fingerprinting failed for {:?}: {}", addon_dir, e);
-                        None
+                // If we have a stored fingerprint on disk, we use that.
+                if let Some(fingerprint) = fingerprints
+                    .iter()
+                    .find(|f| &f.title == dir_name && f.modified == modified && f.size == size)
+                {
+                    let _ = num_cached.fetch_add(1, Ordering::SeqCst);
+                    fingerprint.to_owned()
+                } else {
+                    let hash_result = fingerprint_addon_dir(
+                        &addon_dir,
+                        &initial_inclusion_regex,
+                        &extra_inclusion_regex,
+                        &file_parsing_regex,
+                    );
+
+                    let hash = match hash_result {
+                        Ok(hash) => Some(hash),
+                        Err(e) => {
+                            log::error!("fingerprinting failed for {:?}: {}", addon_dir, e);
+                            None
+                        }
+                    };
+
+                    Fingerprint {
+                        title: dir_name.to_owned(),
+                        hash,
+                        modified,
+                        size,
+                        toc: None,
                     }
-                };
-
-                Fingerprint {
-                    title: dir_name.to_owned(),
-                    hash,
-                    modified,
                 }
-            }
-        })
-        // Note: we filter out cases where hashing has failed.
-        .filter(|f| f.hash.is_some())
-        .collect();
+            })
+            // Note: we filter out cases where hashing has failed.
+            .filter(|f| f.hash.is_some())
+            .collect()
+    });
 
     {
         let num_cached = num_cached.load(Ordering::Relaxed);
@@ -223,36 +322,81 @@ pub async fn read_addon_directory<P: AsRef<Path>>(
         );
     }
 
-    // Update our in memory collection and save to disk.
-    fingerprints.drain(..);
-    fingerprints.extend(new_fingerprints.clone());
-    let _ = fingerprint_collection.save();
-
     // Maps each `Fingerprint` to `AddonFolder`.
-    let mut addon_folders: Vec<_> = all_dirs
-        .par_iter()
-        .filter_map(|id| {
-            // Generate .toc path.
-            let toc_path = root_dir.join(&id).join(format!("{}.toc", id));
-            if !toc_path.exists() {
-                return None;
-            }
+    let num_cached = AtomicUsize::new(0);
+    let parsed: Vec<_> = pool.install(|| {
+        all_dirs
+            .par_iter()
+            .filter_map(|id| {
+                // Find the .toc file, folders without one aren't loaded by the game.
+                let toc_path = find_toc_path(&root_dir.join(&id), id, flavor)?;
+                let (modified, size) = modified_and_size(&toc_path);
+
+                let fingerprint = new_fingerprints.iter().find(|f| &f.title == id);
+
+                // The folder and its `.toc` are unchanged, so the `.toc` isn't read again.
+                let cached = fingerprint.and_then(|f| f.toc.as_ref()).filter(|toc| {
+                    toc.path == toc_path
+                        && toc.modified == modified
+                        && toc.size == size
+                        && toc.format == TOC_CACHE_FORMAT
+                });
+
+                let mut addon_folder = match cached {
+                    Some(toc) => {
+                        let _ = num_cached.fetch_add(1, Ordering::SeqCst);
+                        toc.folder.clone()
+                    }
+                    None => parse_toc_path(&toc_path)?,
+                };
 
-            // We add fingerprint to the addon.
-            let mut addon_folder = parse_toc_path(&toc_path)?;
-            addon_folder.fingerprint = new_fingerprints
-                .iter()
-                .find(|f| &f.title == id)
-                .map(|f| f.hash)
-                .flatten();
+                let toc = CachedToc {
+                    path: toc_path,
+                    modified,
+                    size,
+                    folder: addon_folder.clone(),
+                    format: TOC_CACHE_FORMAT,
+                };
 
-            Some(addon_folder)
-        })
-        .collect();
+                // We add fingerprint to the addon.
+                addon_folder.fingerprint = fingerprint.map(|f| f.hash).flatten();
+
+                Some((addon_folder, toc))
+            })
+            .collect()
+    });
+
+    log::debug!(
+        "{} - {} folders: {} cached, {} parsed from '.toc'",
+        flavor,
+        parsed.len(),
+        num_cached.load(Ordering::Relaxed),
+        parsed.len() - num_cached.load(Ordering::Relaxed)
+    );
+
+    let (mut addon_folders, tocs): (Vec<_>, Vec<_>) = parsed.into_iter().unzip();
+
+    // Update our in memory collection and save to disk.
+    fingerprints.drain(..);
+    fingerprints.extend(new_fingerprints.into_iter().map(|mut fingerprint| {
+        fingerprint.toc = tocs
+            .iter()
+            .find(|toc| toc.folder.id == fingerprint.title)
+            .cloned();
+        fingerprint
+    }));
+    let _ = fingerprint_collection.save();
 
     // Ensure addon folders are sorted alphabetically
     addon_folders.sort_by(|a, b| a.id.cmp(&b.id));
 
+    // Folders linked to a repository, eg. when imported from another addon manager,
+    // are identified as if their `.toc` had the repository id.
+    match RepositoryLinks::load_or_default() {
+        Ok(links) => links.apply(flavor, &mut addon_folders),
+        Err(e) => log::error!("{} - failed to load repository links: {}", flavor, e),
+    }
+
     log::debug!(
         "{} - {} successfully parsed from '.toc'",
         flavor,
@@ -262,29 +406,40 @@ pub async fn read_addon_directory<P: AsRef<Path>>(
     // Drop Mutex guard, collection is no longer needed
     drop(collection_guard);
 
-    // Filters the Tukui ids.
-    let tukui_ids: Vec<_> = addon_folders
-        .iter()
-        .filter_map(|folder| {
-            if let Some(tukui_id) = folder.repository_identifiers.tukui.clone() {
-                Some(tukui_id)
-            } else {
-                None
+    // Tukui ids, split by whether their addon is tried on Tukui before Curse.
+    let mut tukui_ids: Vec<String> = vec![];
+    for folder in addon_folders.iter() {
+        if let Some(tukui_id) = folder.repository_identifiers.tukui.as_ref() {
+            if !tukui_ids.contains(tukui_id) {
+                tukui_ids.push(tukui_id.clone());
             }
-        })
-        .collect();
+        }
+    }
+    let (preferred_tukui_ids, fallback_tukui_ids): (Vec<_>, Vec<_>) =
+        tukui_ids.into_iter().partition(|tukui_id| {
+            let ids = addon_folders
+                .iter()
+                .filter(|f| f.repository_identifiers.tukui.as_ref() == Some(tukui_id))
+                .map(|f| f.id.as_str());
 
-    log::debug!("{} - {} addons with tukui id", flavor, tukui_ids.len());
+            source_priority.preferred(ids) == Repository::Tukui
+        });
 
-    let mut tukui_addons = vec![];
-    // Loops each tukui_id and fetch a remote package from their api.
-    for id in tukui_ids {
-        if let Ok(package) = fetch_remote_package(&id, &flavor).await {
-            let addon = Addon::from_tukui_package(id.clone(), &addon_folders, &package);
+    log::debug!(
+        "{} - {} addons with tukui id, {} tried on curse first",
+        flavor,
+        preferred_tukui_ids.len() + fallback_tukui_ids.len(),
+        fallback_tukui_ids.len()
+    );
 
-            tukui_addons.push(addon);
-        }
-    }
+    let mut is_tukui_available = true;
+    let mut tukui_addons = fetch_tukui_addons(
+        &preferred_tukui_ids,
+        &addon_folders,
+        flavor,
+        &mut is_tukui_available,
+    )
+    .await;
 
     log::debug!(
         "{} - {} addons from tukui package metadata",
@@ -292,19 +447,17 @@ pub async fn read_addon_directory<P: AsRef<Path>>(
         tukui_addons.len()
     );
 
+    // Folders which Tukui didn't know, or couldn't be asked about, are tried on Curse.
+    let tukui_folder_ids = folder_ids(&tukui_addons);
+    let addon_folders: Vec<_> = addon_folders
+        .into_iter()
+        .filter(|f| !tukui_folder_ids.contains(&f.id))
+        .collect();
+
     // Filter out addons with fingerprints.
     let mut fingerprint_hashes: Vec<_> = addon_folders
         .iter()
-        .filter_map(|folder| {
-            // Removes any addon which has tukui_id.
-            if folder.repository_identifiers.tukui.is_some() {
-                None
-            } else if let Some(hash) = folder.fingerprint {
-                Some(hash)
-            } else {
-                None
-            }
-        })
+        .filter_map(|folder| folder.fingerprint)
         .collect();
     fingerprint_hashes.dedup();
 
@@ -314,8 +467,20 @@ pub async fn read_addon_directory<P: AsRef<Path>>(
         fingerprint_hashes.len()
     );
 
-    // Fetches fingerprint package from curse_api
-    let mut fingerprint_package = fetch_remote_packages_by_fingerprint(&fingerprint_hashes).await?;
+    // Fetches fingerprint package from curse_api. If Curse is unavailable the addons
+    // are still tried on Tukui below, instead of failing the whole refresh.
+    let mut fingerprint_package =
+        match fetch_remote_packages_by_fingerprint(&fingerprint_hashes).await {
+            Ok(fingerprint_package) => fingerprint_package,
+            Err(e) => {
+                log::error!(
+                    "{} - failed to fetch fingerprints from curse: {}",
+                    flavor,
+                    e
+                );
+                Default::default()
+            }
+        };
 
     // We had a case where a addon hash returned a minecraft addon.
     // So we filter out all matches which does not have a valid flavor.
@@ -397,10 +562,7 @@ pub async fn read_addon_directory<P: AsRef<Path>>(
                 .iter()
                 .any(|fa| fa.primary_folder_id != f.id)
         })
-        .filter(|f| {
-            f.repository_identifiers.tukui.is_none() && f.repository_identifiers.curse.is_some()
-        })
-        .map(|f| f.repository_identifiers.curse.unwrap())
+        .filter_map(|f| f.repository_identifiers.curse)
         .filter(|id| !curse_ids_from_match.contains(id))
         .collect();
     curse_ids_from_nonmatch.dedup();
@@ -478,6 +640,33 @@ pub async fn read_addon_directory<P: AsRef<Path>>(
         );
     }
 
+    // Addons tried on Curse first are looked up on Tukui, unless Curse found them.
+    let mut curse_folder_ids = folder_ids(&fingerprint_addons);
+    curse_folder_ids.extend(folder_ids(&curse_id_only_addons));
+    let fallback_tukui_ids: Vec<_> = fallback_tukui_ids
+        .into_iter()
+        .filter(|tukui_id| {
+            !addon_folders.iter().any(|f| {
+                f.repository_identifiers.tukui.as_ref() == Some(tukui_id)
+                    && curse_folder_ids.contains(&f.id)
+            })
+        })
+        .collect();
+    let remaining_folders: Vec<_> = addon_folders
+        .iter()
+        .filter(|f| !curse_folder_ids.contains(&f.id))
+        .cloned()
+        .collect();
+    tukui_addons.extend(
+        fetch_tukui_addons(
+            &fallback_tukui_ids,
+            &remaining_folders,
+            flavor,
+            &mut is_tukui_available,
+        )
+        .await,
+    );
+
     // Concats the different repo addons, and returns.
     let mut concatenated = [
         &tukui_addons[..],
@@ -515,9 +704,62 @@ pub async fn read_addon_directory<P: AsRef<Path>>(
 
     concatenated.extend(unknown_addons);
 
+    match InstalledAddons::load_or_default() {
+        Ok(installed) => installed.apply(flavor, &mut concatenated),
+        Err(e) => log::error!("{} - failed to load install dates: {}", flavor, e),
+    }
+
+    if let Err(e) = record_addons(flavor, root_dir, &concatenated) {
+        log::error!("{} - failed to record addons: {}", flavor, e);
+    }
+
     Ok(concatenated)
 }
 
+/// Fetches the Tukui packages of `tukui_ids`, and creates their addons.
+///
+/// Once Tukui is unavailable the remaining ids are skipped, since every request
+/// would only wait for the same failure.
+async fn fetch_tukui_addons(
+    tukui_ids: &[String],
+    addon_folders: &[AddonFolder],
+    flavor: Flavor,
+    is_available: &mut bool,
+) -> Vec<Addon> {
+    let mut addons = vec![];
+
+    for tukui_id in tukui_ids {
+        if !*is_available {
+            break;
+        }
+
+        match fetch_remote_package(tukui_id, &flavor).await {
+            Ok(package) => {
+                addons.push(Addon::from_tukui_package(
+                    tukui_id.clone(),
+                    addon_folders,
+                    &package,
+                ));
+            }
+            Err(e) if e.is_unavailable() => {
+                log::error!("{} - tukui is unavailable: {}", flavor, e);
+                *is_available = false;
+            }
+            Err(e) => log::debug!("{} - couldn't fetch tukui id {}: {}", flavor, tukui_id, e),
+        }
+    }
+
+    addons
+}
+
+/// Ids of the folders of `addons`.
+fn folder_ids(addons: &[Addon]) -> HashSet<String> {
+    addons
+        .iter()
+        .flat_map(|a| a.folders.iter().map(|f| f.id.clone()))
+        .collect()
+}
+
 pub async fn update_addon_fingerprint(
     fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
     flavor: Flavor,
@@ -554,16 +796,14 @@ pub async fn update_addon_fingerprint(
 
             let fingerprint_collection = collection_guard.as_mut().unwrap();
             let fingerprints = fingerprint_collection.get_mut_for_flavor(flavor);
-            let modified = if let Ok(metadata) = addon_path.metadata() {
-                metadata.modified().unwrap_or_else(|_| SystemTime::now())
-            } else {
-                SystemTime::now()
-            };
+            let (modified, size) = modified_and_size(&addon_path);
 
             fingerprints.iter_mut().for_each(|fingerprint| {
                 if fingerprint.title == addon_id {
                     fingerprint.hash = Some(hash);
                     fingerprint.modified = modified;
+                    fingerprint.size = size;
+                    fingerprint.toc = None;
                 }
             });
 
@@ -712,13 +952,9 @@ pub fn fingerprint_addon_dir(
     // Calculate fingerprints
     let mut fingerprints = vec![];
     for path in to_fingerprint.iter() {
-        let data: Vec<_> = std::fs::read(path)
-            .map_err(ClientError::fingerprint)?
-            .into_iter()
-            .filter(|&b| b != b' ' && b != b'\n' && b != b'\r' && b != b'\t')
-            .collect();
+        let data = std::fs::read(path).map_err(ClientError::fingerprint)?;
 
-        let hash = calculate_hash(&data, 1);
+        let hash = calculate_fingerprint(&data);
 
         fingerprints.push(hash);
     }
@@ -772,6 +1008,53 @@ where
     Some(current)
 }
 
+/// Returns the `.toc` file of an addon folder.
+///
+/// Besides `<folder>/<folder>.toc`, the name is matched case insensitively and with
+/// a flavor suffix, eg. `DBM-Core/dbm-core.toc` or `Details/Details-Classic.toc`, as
+/// packaged by addon managers other than Curse. Otherwise these folders are skipped
+/// before their fingerprints are matched against the Curse API.
+fn find_toc_path(addon_dir: &Path, id: &str, flavor: Flavor) -> Option<PathBuf> {
+    let toc_path = addon_dir.join(format!("{}.toc", id));
+    if toc_path.exists() {
+        return Some(toc_path);
+    }
+
+    let suffixes: &[&str] = match flavor.base_flavor() {
+        Flavor::Classic => &["bcc", "tbc", "classic"],
+        Flavor::ClassicEra => &["classic", "vanilla"],
+        _ => &["mainline"],
+    };
+
+    let id = id.to_lowercase();
+    let toc_paths = addon_dir
+        .read_dir()
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && path.extension().map_or(false, |ext| ext == "toc"))
+        .collect::<Vec<_>>();
+
+    toc_paths
+        .iter()
+        .find(|path| toc_stem(path) == id)
+        .or_else(|| {
+            toc_paths.iter().find(|path| {
+                let stem = toc_stem(path);
+
+                suffixes.iter().any(|suffix| {
+                    stem == format!("{}-{}", id, suffix) || stem == format!("{}_{}", id, suffix)
+                })
+            })
+        })
+        .cloned()
+}
+
+fn toc_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
 /// Helper function to parse a given TOC file
 /// (`DirEntry`) into a `Addon` struct.
 ///
@@ -796,6 +1079,7 @@ pub fn parse_toc_path(toc_path: &PathBuf) -> Option<AddonFolder> {
     let mut wowi_id: Option<String> = None;
     let mut tukui_id: Option<String> = None;
     let mut curse_id: Option<u32> = None;
+    let mut interface: Option<u32> = None;
 
     // TODO: We should save these somewere so we don't keep creating them.
     let re_toc = regex::Regex::new(r"^##\s*(?P<key>.*?)\s*:\s?(?P<value>.*)").unwrap();
@@ -814,6 +1098,7 @@ pub fn parse_toc_path(toc_path: &PathBuf) -> Option<AddonFolder> {
                     notes = Some(re_title.replace_all(&cap["value"], "$1").trim().to_string())
                 }
                 "Version" => version = Some(cap["value"].trim().to_owned()),
+                "Interface" => interface = cap["value"].trim().parse::<u32>().ok(),
                 // Names that must be loaded before this addon can be loaded.
                 "Dependencies" | "RequiredDeps" => {
                     dependencies.append(&mut split_dependencies_into_vec(&cap["value"]));
@@ -836,7 +1121,13 @@ pub fn parse_toc_path(toc_path: &PathBuf) -> Option<AddonFolder> {
         curse: curse_id,
     };
 
-    Some(AddonFolder::new(
+    // Addon authors often symlink their working copy into the addon directory.
+    let is_symlink = path
+        .symlink_metadata()
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or_default();
+
+    let mut addon_folder = AddonFolder::new(
         id.clone(),
         title.unwrap_or(id),
         path,
@@ -845,7 +1136,12 @@ pub fn parse_toc_path(toc_path: &PathBuf) -> Option<AddonFolder> {
         version,
         repository_identifiers,
         dependencies,
-    ))
+    );
+    addon_folder.is_symlink = is_symlink;
+    addon_folder.size = size_on_disk(&addon_folder.path);
+    addon_folder.interface = interface;
+
+    Some(addon_folder)
 }
 
 /// Helper function to split a comma separated string into `Vec<String>`.
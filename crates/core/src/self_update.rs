@@ -0,0 +1,248 @@
+use crate::localization::localized_string;
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Leftovers younger than this could belong to an update which is still running.
+const LEFTOVER_MIN_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Package managers Ajour can be installed with, which then also update it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageManager {
+    Flatpak,
+    Snap,
+    Nix,
+    Homebrew,
+    Scoop,
+    Chocolatey,
+    Winget,
+    /// Installed system wide, eg. from the AUR or a distribution package.
+    System,
+}
+
+impl PackageManager {
+    /// How to update Ajour with the package manager.
+    pub fn update_hint(self) -> String {
+        let hint = match self {
+            PackageManager::Flatpak => "flatpak update",
+            PackageManager::Snap => "snap refresh ajour",
+            PackageManager::Nix => "nix-env --upgrade ajour",
+            PackageManager::Homebrew => "brew upgrade ajour",
+            PackageManager::Scoop => "scoop update ajour",
+            PackageManager::Chocolatey => "choco upgrade ajour",
+            PackageManager::Winget => "winget upgrade ajour",
+            PackageManager::System => return localized_string("package-manager-system-hint"),
+        };
+
+        hint.to_string()
+    }
+}
+
+impl fmt::Display for PackageManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            PackageManager::Flatpak => "Flatpak",
+            PackageManager::Snap => "Snap",
+            PackageManager::Nix => "Nix",
+            PackageManager::Homebrew => "Homebrew",
+            PackageManager::Scoop => "Scoop",
+            PackageManager::Chocolatey => "Chocolatey",
+            PackageManager::Winget => "winget",
+            PackageManager::System => {
+                return write!(f, "{}", localized_string("package-manager-system"))
+            }
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Returns the package manager Ajour was installed with, if any, in which case
+/// it shouldn't update itself.
+pub fn package_manager() -> Option<PackageManager> {
+    let exe = std::env::current_exe().ok()?;
+
+    detect_package_manager(&exe, |name| std::env::var(name).ok())
+}
+
+/// Tells the package manager from the environment it sets for the apps it runs,
+/// or else from the path of the executable.
+fn detect_package_manager(
+    exe: &Path,
+    env: impl Fn(&str) -> Option<String>,
+) -> Option<PackageManager> {
+    if env("FLATPAK_ID").is_some() || Path::new("/.flatpak-info").exists() {
+        return Some(PackageManager::Flatpak);
+    }
+
+    if env("SNAP").is_some() {
+        return Some(PackageManager::Snap);
+    }
+
+    // Backslashes are compared as slashes, so Windows paths match the same.
+    let path = exe.to_string_lossy().to_lowercase().replace('\\', "/");
+
+    let patterns = [
+        ("/nix/store/", PackageManager::Nix),
+        ("/cellar/", PackageManager::Homebrew),
+        ("/homebrew/", PackageManager::Homebrew),
+        ("/linuxbrew/", PackageManager::Homebrew),
+        ("/scoop/apps/", PackageManager::Scoop),
+        ("/chocolatey/", PackageManager::Chocolatey),
+        ("/winget/packages/", PackageManager::Winget),
+    ];
+
+    for (pattern, package_manager) in patterns.iter() {
+        if path.contains(pattern) {
+            return Some(*package_manager);
+        }
+    }
+
+    if path.starts_with("/usr/bin/") || path.starts_with("/usr/lib/") {
+        return Some(PackageManager::System);
+    }
+
+    None
+}
+
+/// Removes the temporary binaries and partial downloads failed updates left next
+/// to the executable, returning the paths which were removed.
+///
+/// Installs of package managers are left alone, as they update Ajour themselves.
+pub fn remove_self_update_leftovers() -> Vec<PathBuf> {
+    if package_manager().is_some() {
+        return vec![];
+    }
+
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(_) => return vec![],
+    };
+
+    match exe.parent() {
+        Some(directory) => remove_leftovers(directory, &exe, LEFTOVER_MIN_AGE),
+        None => vec![],
+    }
+}
+
+/// An update of the executable `exe_name` downloads to `<exe_name>.part`, then
+/// moves it to `tmp_<exe_name>` before swapping it with the executable. Other
+/// files are left alone, as the directory can be shared, eg. with downloads.
+fn is_leftover(file_name: &str, exe_name: &str) -> bool {
+    file_name == format!("{}.part", exe_name) || file_name == format!("tmp_{}", exe_name)
+}
+
+fn remove_leftovers(directory: &Path, exe: &Path, min_age: Duration) -> Vec<PathBuf> {
+    let exe_name = match exe.file_name().and_then(|name| name.to_str()) {
+        Some(name) => name,
+        None => return vec![],
+    };
+
+    let entries = match std::fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::warn!("couldn't read {}: {}", directory.display(), e);
+            return vec![];
+        }
+    };
+
+    let now = SystemTime::now();
+    let mut removed = vec![];
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path == exe || !path.is_file() {
+            continue;
+        }
+
+        match path.file_name().and_then(|name| name.to_str()) {
+            Some(name) if is_leftover(name, exe_name) => {}
+            _ => continue,
+        }
+
+        let is_stale = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .map_or(false, |age| age >= min_age);
+
+        if !is_stale {
+            continue;
+        }
+
+        match std::fs::remove_file(&path) {
+            Ok(_) => removed.push(path),
+            Err(e) => log::warn!("couldn't remove {}: {}", path.display(), e),
+        }
+    }
+
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_leftovers() {
+        let directory =
+            std::env::temp_dir().join(format!("ajour-self-update-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let exe = directory.join("ajour");
+        let names = [
+            "ajour",
+            "tmp_ajour",
+            "ajour.part",
+            "ajour.yml",
+            "tmp_notes.txt",
+            "video.mkv.part",
+        ];
+        for name in names.iter() {
+            std::fs::write(directory.join(name), "").unwrap();
+        }
+
+        // Nothing is old enough to be removed yet.
+        assert!(remove_leftovers(&directory, &exe, LEFTOVER_MIN_AGE).is_empty());
+
+        let mut removed = remove_leftovers(&directory, &exe, Duration::from_secs(0));
+        removed.sort();
+        assert_eq!(
+            removed,
+            vec![directory.join("ajour.part"), directory.join("tmp_ajour")]
+        );
+        assert!(exe.exists());
+        for name in ["ajour.yml", "tmp_notes.txt", "video.mkv.part"].iter() {
+            assert!(directory.join(name).exists());
+        }
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn test_detect_package_manager() {
+        let no_env = |_: &str| None;
+
+        let detect = |path: &str| detect_package_manager(Path::new(path), no_env);
+
+        assert_eq!(
+            detect("/opt/homebrew/Cellar/ajour/1.0.0/bin/ajour"),
+            Some(PackageManager::Homebrew)
+        );
+        assert_eq!(
+            detect(r"C:\Users\jane\scoop\apps\ajour\current\ajour.exe"),
+            Some(PackageManager::Scoop)
+        );
+        assert_eq!(detect("/usr/bin/ajour"), Some(PackageManager::System));
+        assert_eq!(detect("/home/jane/Downloads/ajour"), None);
+        assert_eq!(detect(r"C:\Games\Ajour\ajour.exe"), None);
+
+        let snap_env = |name: &str| Some(name.to_string()).filter(|n| n == "SNAP");
+        assert_eq!(
+            detect_package_manager(Path::new("/snap/ajour/12/ajour"), snap_env),
+            Some(PackageManager::Snap)
+        );
+    }
+}
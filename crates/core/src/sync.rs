@@ -0,0 +1,418 @@
+use crate::config::{Config, SyncRemote, Tokens};
+use crate::error::ClientError;
+use crate::import::AddonExport;
+use crate::localization::localized_string;
+use crate::murmur2::calculate_hash;
+use crate::network::{is_offline, proxy};
+use crate::Result;
+
+use async_std::io::ReadExt;
+use isahc::auth::{Authentication, Credentials};
+use isahc::http::StatusCode;
+use isahc::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt;
+use std::time::Duration;
+
+/// Version of the snapshot format, bumped when it changes in a way older
+/// versions of Ajour can't read.
+pub const SYNC_VERSION: u32 = 1;
+
+/// Name of the synced file, in the gist or a WebDAV directory.
+pub const SYNC_FILE: &str = "ajour-sync.json";
+
+/// Settings which belong to the machine, and aren't synced.
+const LOCAL_SETTINGS: [&str; 13] = [
+    "version",
+    "installations",
+    "profile",
+    "profiles",
+    "backup_directory",
+    "window_size",
+    "tokens",
+    "proxy",
+    "cloud_sync",
+    "open_links",
+    "parse_threads",
+    "logging",
+    "hooks",
+];
+
+/// The settings and addons of a machine, as they are synced.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncSnapshot {
+    pub version: u32,
+    /// The config, without the settings which belong to the machine.
+    pub settings: Value,
+    pub addons: AddonExport,
+}
+
+impl SyncSnapshot {
+    pub fn new(config: &Config, mut addons: AddonExport) -> Result<Self> {
+        let mut settings = serde_json::to_value(config)?;
+
+        if let Value::Object(settings) = &mut settings {
+            for key in LOCAL_SETTINGS.iter() {
+                settings.remove(*key);
+            }
+
+            if let Some(Value::Object(wow)) = settings.get_mut("wow") {
+                wow.remove("directory");
+            }
+        }
+
+        // Sorted, so the fingerprint doesn't depend on the order addons were read in.
+        addons
+            .addons
+            .sort_by(|a, b| (a.flavor, &a.folder).cmp(&(b.flavor, &b.folder)));
+
+        Ok(SyncSnapshot {
+            version: SYNC_VERSION,
+            settings,
+            addons,
+        })
+    }
+
+    /// Tells snapshots apart, so changes since the last sync are noticed.
+    ///
+    /// The fingerprint is stored and compared across machines and versions of
+    /// Ajour, so it's hashed with murmur2 which unlike `DefaultHasher` is stable.
+    pub fn fingerprint(&self) -> String {
+        // Objects are serialized with sorted keys, so equal snapshots are
+        // serialized the same.
+        let text = serde_json::to_string(self).unwrap_or_default();
+
+        // Two seeds make for 64 bits, which keeps collisions out of the picture.
+        format!(
+            "{:08x}{:08x}",
+            calculate_hash(text.as_bytes(), 1),
+            calculate_hash(text.as_bytes(), 2)
+        )
+    }
+
+    /// Returns `config` with the synced settings, keeping the ones which belong
+    /// to the machine.
+    pub fn apply(&self, config: &Config) -> Result<Config> {
+        let mut merged = serde_json::to_value(config)?;
+
+        if let (Value::Object(merged), Value::Object(settings)) = (&mut merged, &self.settings) {
+            for (key, value) in settings {
+                if LOCAL_SETTINGS.contains(&key.as_str()) {
+                    continue;
+                }
+
+                match (merged.get_mut(key), value) {
+                    // Settings like `wow` are merged field by field, which keeps
+                    // the local World of Warcraft directory.
+                    (Some(Value::Object(local)), Value::Object(synced)) => {
+                        for (field, value) in synced {
+                            local.insert(field.clone(), value.clone());
+                        }
+                    }
+                    _ => {
+                        merged.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(serde_json::from_value(merged)?)
+    }
+}
+
+/// What changed since the last sync.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncState {
+    UpToDate,
+    LocalChanged,
+    RemoteChanged,
+    /// Both this machine and the remote changed, one of them has to give way.
+    Conflict,
+}
+
+impl SyncState {
+    /// Name of the state which isn't localized, for output read by scripts.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SyncState::UpToDate => "up to date",
+            SyncState::LocalChanged => "changed on this machine",
+            SyncState::RemoteChanged => "changed on the remote",
+            SyncState::Conflict => "changed on both this machine and the remote",
+        }
+    }
+}
+
+impl fmt::Display for SyncState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            SyncState::UpToDate => localized_string("sync-up-to-date"),
+            SyncState::LocalChanged => localized_string("sync-local-changed"),
+            SyncState::RemoteChanged => localized_string("sync-remote-changed"),
+            SyncState::Conflict => localized_string("sync-conflict"),
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Compares the fingerprints of this machine and the remote with the one of the
+/// last sync. `remote` is `None` if nothing was pushed yet.
+pub fn sync_state(last_synced: Option<&str>, local: &str, remote: Option<&str>) -> SyncState {
+    let remote = match remote {
+        Some(remote) => remote,
+        None => return SyncState::LocalChanged,
+    };
+
+    if local == remote {
+        return SyncState::UpToDate;
+    }
+
+    match (Some(local) == last_synced, Some(remote) == last_synced) {
+        (true, _) => SyncState::RemoteChanged,
+        (false, true) => SyncState::LocalChanged,
+        (false, false) => SyncState::Conflict,
+    }
+}
+
+/// Fetches the snapshot on `remote`, `None` if nothing was pushed yet.
+pub async fn fetch_snapshot(remote: &SyncRemote, tokens: &Tokens) -> Result<Option<SyncSnapshot>> {
+    let (status, body) = match remote {
+        SyncRemote::WebDav { .. } => send(remote, tokens, "GET", webdav_url(remote), None).await?,
+        SyncRemote::Gist { id: None } => return Ok(None),
+        SyncRemote::Gist { id: Some(id) } => {
+            let url = format!("https://api.github.com/gists/{}", id);
+            let (status, body) = send(remote, tokens, "GET", url, None).await?;
+
+            if !status.is_success() {
+                (status, body)
+            } else {
+                let gist: Value = serde_json::from_slice(&body)?;
+
+                match gist["files"][SYNC_FILE]["content"].as_str() {
+                    Some(content) => (status, content.as_bytes().to_vec()),
+                    None => return Ok(None),
+                }
+            }
+        }
+    };
+
+    match status {
+        StatusCode::NOT_FOUND => Ok(None),
+        status if status.is_success() => {
+            let snapshot: SyncSnapshot = serde_json::from_slice(&body)?;
+
+            if snapshot.version > SYNC_VERSION {
+                return Err(ClientError::Custom(format!(
+                    "{} was pushed by a newer version of Ajour",
+                    remote
+                )));
+            }
+
+            Ok(Some(snapshot))
+        }
+        status => Err(remote_error(remote, status)),
+    }
+}
+
+/// Pushes `snapshot` to `remote`, returning the remote, which holds the id of
+/// the gist once it's created.
+pub async fn push_snapshot(
+    remote: &SyncRemote,
+    tokens: &Tokens,
+    snapshot: &SyncSnapshot,
+) -> Result<SyncRemote> {
+    let content = serde_json::to_string_pretty(snapshot)?;
+
+    let (status, body, remote) = match remote {
+        SyncRemote::WebDav { .. } => {
+            let url = webdav_url(remote);
+            let (status, body) = send(remote, tokens, "PUT", url, Some(content)).await?;
+
+            (status, body, remote.clone())
+        }
+        SyncRemote::Gist { id } => {
+            let files = json!({ SYNC_FILE: { "content": content } });
+
+            let (method, url, data) = match id {
+                Some(id) => (
+                    "PATCH",
+                    format!("https://api.github.com/gists/{}", id),
+                    json!({ "files": files }),
+                ),
+                None => (
+                    "POST",
+                    "https://api.github.com/gists".to_string(),
+                    json!({
+                        "description": "Ajour sync",
+                        "public": false,
+                        "files": files,
+                    }),
+                ),
+            };
+
+            let (status, body) = send(remote, tokens, method, url, Some(data.to_string())).await?;
+
+            let id = match id {
+                Some(id) => Some(id.clone()),
+                None if status.is_success() => serde_json::from_slice::<Value>(&body)?["id"]
+                    .as_str()
+                    .map(str::to_string),
+                None => None,
+            };
+
+            (status, body, SyncRemote::Gist { id })
+        }
+    };
+
+    if !status.is_success() {
+        log::debug!("{} responded {}", remote, String::from_utf8_lossy(&body));
+
+        return Err(remote_error(&remote, status));
+    }
+
+    Ok(remote)
+}
+
+/// Url of the synced file, in the directory if the url is one.
+fn webdav_url(remote: &SyncRemote) -> String {
+    match remote {
+        SyncRemote::WebDav { url, .. } if url.ends_with('/') => format!("{}{}", url, SYNC_FILE),
+        SyncRemote::WebDav { url, .. } => url.clone(),
+        SyncRemote::Gist { .. } => String::new(),
+    }
+}
+
+fn remote_error(remote: &SyncRemote, status: StatusCode) -> ClientError {
+    let hint = match (remote, status) {
+        (SyncRemote::Gist { .. }, StatusCode::UNAUTHORIZED)
+        | (SyncRemote::Gist { .. }, StatusCode::NOT_FOUND) => {
+            ", the GitHub token needs the gist scope"
+        }
+        (SyncRemote::WebDav { .. }, StatusCode::UNAUTHORIZED) => ", check the credentials",
+        _ => "",
+    };
+
+    ClientError::Custom(format!("{} responded {}{}", remote, status, hint))
+}
+
+/// Sends a request to `remote`, which isn't cached, unlike the ones to the
+/// repositories.
+async fn send(
+    remote: &SyncRemote,
+    tokens: &Tokens,
+    method: &str,
+    url: String,
+    body: Option<String>,
+) -> Result<(StatusCode, Vec<u8>)> {
+    if is_offline() {
+        return Err(ClientError::Offline);
+    }
+
+    let mut request = Request::builder()
+        .method(method)
+        .uri(url.as_str())
+        .timeout(Duration::from_secs(30));
+
+    match remote {
+        SyncRemote::WebDav {
+            username: Some(username),
+            password,
+            ..
+        } => {
+            request =
+                request
+                    .authentication(Authentication::basic())
+                    .credentials(Credentials::new(
+                        username.as_str(),
+                        password.as_deref().unwrap_or_default(),
+                    ));
+        }
+        SyncRemote::WebDav { .. } => {}
+        SyncRemote::Gist { .. } => {
+            let token = tokens.github.as_deref().ok_or_else(|| {
+                ClientError::Custom("syncing to a gist needs a GitHub token".to_string())
+            })?;
+
+            request = request
+                .header("authorization", format!("token {}", token))
+                .header("accept", "application/vnd.github.v3+json");
+        }
+    }
+
+    if body.is_some() {
+        request = request.header("content-type", "application/json");
+    }
+
+    if let Some(proxy) = proxy() {
+        request = request.proxy(proxy);
+    }
+
+    let response = request.body(body.unwrap_or_default())?.send_async().await?;
+    let (parts, mut body) = response.into_parts();
+
+    let mut bytes = vec![];
+    body.read_to_end(&mut bytes).await?;
+
+    Ok((parts.status, bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_state() {
+        assert_eq!(sync_state(None, "a", None), SyncState::LocalChanged);
+        assert_eq!(sync_state(Some("a"), "b", Some("b")), SyncState::UpToDate);
+        assert_eq!(
+            sync_state(Some("a"), "a", Some("b")),
+            SyncState::RemoteChanged
+        );
+        assert_eq!(
+            sync_state(Some("a"), "b", Some("a")),
+            SyncState::LocalChanged
+        );
+        assert_eq!(sync_state(Some("a"), "b", Some("c")), SyncState::Conflict);
+        assert_eq!(sync_state(None, "b", Some("c")), SyncState::Conflict);
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        let snapshot = SyncSnapshot {
+            version: 1,
+            settings: json!({}),
+            addons: AddonExport {
+                version: 1,
+                addons: vec![],
+            },
+        };
+
+        assert_eq!(snapshot.fingerprint(), "e8e2c4088ba02ee7");
+    }
+
+    #[test]
+    fn test_apply_keeps_local_settings() {
+        let mut remote_config = Config::default();
+        remote_config.theme = Some("Dark".to_string());
+        remote_config.wow.directory = Some("/remote/World of Warcraft".into());
+        remote_config.tokens.github = Some("remote-token".to_string());
+
+        let export = AddonExport {
+            version: crate::import::EXPORT_VERSION,
+            addons: vec![],
+        };
+        let snapshot = SyncSnapshot::new(&remote_config, export).unwrap();
+
+        let mut local_config = Config::default();
+        local_config.wow.directory = Some("/local/World of Warcraft".into());
+        local_config.tokens.github = Some("local-token".to_string());
+
+        let merged = snapshot.apply(&local_config).unwrap();
+        assert_eq!(merged.theme.as_deref(), Some("Dark"));
+        assert_eq!(
+            merged.wow.directory,
+            Some("/local/World of Warcraft".into())
+        );
+        assert_eq!(merged.tokens.github.as_deref(), Some("local-token"));
+    }
+}
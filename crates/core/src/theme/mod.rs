@@ -1,6 +1,11 @@
+use crate::error::ClientError;
 use crate::fs;
-use de::deserialize_color_hex_string;
-use serde::Deserialize;
+use crate::network::{request_async, status_error};
+use crate::Result;
+use de::{deserialize_color_hex_string, serialize_color_hex_string};
+use isahc::prelude::*;
+use isahc::HttpClient;
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 
 pub async fn load_user_themes() -> Vec<Theme> {
@@ -9,45 +14,108 @@ pub async fn load_user_themes() -> Vec<Theme> {
     fs::load_user_themes().await
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// Downloads the theme file at `url` and installs it into the themes folder.
+/// The file has to be a valid theme, in YAML or TOML.
+pub async fn import_theme(shared_client: HttpClient, url: String) -> Result<Theme> {
+    let mut resp = request_async(&shared_client, url.trim(), vec![], Some(30)).await?;
+
+    if !resp.status().is_success() {
+        return Err(status_error(&url, "download the theme", &mut resp));
+    }
+
+    let theme_str = resp.text()?;
+    let theme = parse_theme(&theme_str).ok_or_else(|| {
+        ClientError::Custom(format!("{} isn't a theme Ajour can read", url.trim()))
+    })?;
+
+    fs::install_user_theme(&theme).await?;
+
+    log::debug!("imported theme {} from {}", &theme.name, url.trim());
+
+    Ok(theme)
+}
+
+/// Parses a theme shared as text, which is either YAML or TOML.
+pub fn parse_theme(theme_str: &str) -> Option<Theme> {
+    serde_yaml::from_str(theme_str)
+        .ok()
+        .or_else(|| toml::from_str(theme_str).ok())
+}
+
+/// Returns `theme` as YAML, the way it's written to a file in the themes folder.
+pub fn export_theme(theme: &Theme) -> Result<String> {
+    Ok(serde_yaml::to_string(theme)?)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Theme {
     pub name: String,
     pub palette: ColorPalette,
 }
 
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct BaseColors {
-    #[serde(deserialize_with = "deserialize_color_hex_string")]
+    #[serde(
+        deserialize_with = "deserialize_color_hex_string",
+        serialize_with = "serialize_color_hex_string"
+    )]
     pub background: iced_native::Color,
-    #[serde(deserialize_with = "deserialize_color_hex_string")]
+    #[serde(
+        deserialize_with = "deserialize_color_hex_string",
+        serialize_with = "serialize_color_hex_string"
+    )]
     pub foreground: iced_native::Color,
 }
 
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct NormalColors {
-    #[serde(deserialize_with = "deserialize_color_hex_string")]
+    #[serde(
+        deserialize_with = "deserialize_color_hex_string",
+        serialize_with = "serialize_color_hex_string"
+    )]
     pub primary: iced_native::Color,
-    #[serde(deserialize_with = "deserialize_color_hex_string")]
+    #[serde(
+        deserialize_with = "deserialize_color_hex_string",
+        serialize_with = "serialize_color_hex_string"
+    )]
     pub secondary: iced_native::Color,
-    #[serde(deserialize_with = "deserialize_color_hex_string")]
+    #[serde(
+        deserialize_with = "deserialize_color_hex_string",
+        serialize_with = "serialize_color_hex_string"
+    )]
     pub surface: iced_native::Color,
-    #[serde(deserialize_with = "deserialize_color_hex_string")]
+    #[serde(
+        deserialize_with = "deserialize_color_hex_string",
+        serialize_with = "serialize_color_hex_string"
+    )]
     pub error: iced_native::Color,
 }
 
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct BrightColors {
-    #[serde(deserialize_with = "deserialize_color_hex_string")]
+    #[serde(
+        deserialize_with = "deserialize_color_hex_string",
+        serialize_with = "serialize_color_hex_string"
+    )]
     pub primary: iced_native::Color,
-    #[serde(deserialize_with = "deserialize_color_hex_string")]
+    #[serde(
+        deserialize_with = "deserialize_color_hex_string",
+        serialize_with = "serialize_color_hex_string"
+    )]
     pub secondary: iced_native::Color,
-    #[serde(deserialize_with = "deserialize_color_hex_string")]
+    #[serde(
+        deserialize_with = "deserialize_color_hex_string",
+        serialize_with = "serialize_color_hex_string"
+    )]
     pub surface: iced_native::Color,
-    #[serde(deserialize_with = "deserialize_color_hex_string")]
+    #[serde(
+        deserialize_with = "deserialize_color_hex_string",
+        serialize_with = "serialize_color_hex_string"
+    )]
     pub error: iced_native::Color,
 }
 
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct ColorPalette {
     pub base: BaseColors,
     pub normal: NormalColors,
@@ -415,8 +483,26 @@ struct Color(iced_native::Color);
 mod de {
     use super::{hex_to_color, Color};
     use serde::de::{self, Error, Unexpected, Visitor};
+    use serde::ser::Serializer;
     use std::fmt;
 
+    pub fn serialize_color_hex_string<S>(
+        color: &iced_native::Color,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let channel = |c: f32| (c * 255.0).round() as u8;
+
+        serializer.serialize_str(&format!(
+            "#{:02x}{:02x}{:02x}",
+            channel(color.r),
+            channel(color.g),
+            channel(color.b)
+        ))
+    }
+
     pub fn deserialize_color_hex_string<'de, D>(
         deserializer: D,
     ) -> Result<iced_native::Color, D::Error>
@@ -451,7 +537,7 @@ mod de {
 
 #[cfg(test)]
 mod tests {
-    use super::{de::deserialize_color_hex_string, Theme};
+    use super::{de::deserialize_color_hex_string, export_theme, parse_theme, Theme};
     use serde::de::value::{Error, StrDeserializer};
     use serde::de::IntoDeserializer;
 
@@ -496,4 +582,19 @@ mod tests {
 
         serde_yaml::from_str::<Theme>(theme_str).unwrap();
     }
+
+    #[test]
+    fn test_theme_export_roundtrip() {
+        let theme = Theme::nord();
+
+        let exported = export_theme(&theme).unwrap();
+        let imported = parse_theme(&exported).unwrap();
+
+        assert_eq!(imported.name, theme.name);
+        assert_eq!(
+            export_theme(&imported).unwrap(),
+            exported,
+            "colors change on a roundtrip"
+        );
+    }
 }
@@ -1,9 +1,8 @@
 use crate::{
-    addon::Addon,
+    addon::{Addon, AddonDetails},
     config::Flavor,
-    error::ClientError,
-    network::request_async,
-    utility::{regex_html_tags_to_newline, regex_html_tags_to_space, truncate},
+    network::{request_async, status_error},
+    utility::format_changelog,
     Result,
 };
 use isahc::config::RedirectPolicy;
@@ -21,30 +20,35 @@ pub struct TukuiPackage {
     pub patch: Option<String>,
     pub author: Option<String>,
     pub small_desc: Option<String>,
+    #[serde(default)]
+    pub screenshot_url: Option<String>,
+    /// The API returns the count both as a number and as a string.
+    #[serde(default)]
+    pub downloads: Option<serde_json::Value>,
 }
 
 /// Return the tukui API endpoint.
 fn api_endpoint(id: &str, flavor: &Flavor) -> String {
-    match flavor {
-        Flavor::Retail | Flavor::RetailPTR | Flavor::RetailBeta => match id {
+    match flavor.base_flavor() {
+        Flavor::Retail => match id {
             "-1" => "https://www.tukui.org/api.php?ui=tukui".to_owned(),
             "-2" => "https://www.tukui.org/api.php?ui=elvui".to_owned(),
             _ => format!("https://www.tukui.org/api.php?addon={}", id),
         },
-        Flavor::Classic | Flavor::ClassicPTR => {
+        _ => {
             format!("https://www.tukui.org/api.php?classic-addon={}", id)
         }
     }
 }
 
 fn changelog_endpoint(id: &str, flavor: &Flavor) -> String {
-    match flavor {
-        Flavor::Retail | Flavor::RetailPTR | Flavor::RetailBeta => match id {
+    match flavor.base_flavor() {
+        Flavor::Retail => match id {
             "-1" => "https://www.tukui.org/ui/tukui/changelog".to_owned(),
             "-2" => "https://www.tukui.org/ui/elvui/changelog".to_owned(),
             _ => format!("https://www.tukui.org/addons.php?id={}&changelog", id),
         },
-        Flavor::Classic | Flavor::ClassicPTR => format!(
+        _ => format!(
             "https://www.tukui.org/classic-addons.php?id={}&changelog",
             id
         ),
@@ -67,13 +71,27 @@ pub async fn fetch_remote_package(id: &str, flavor: &Flavor) -> Result<TukuiPack
         let package = resp.json()?;
         Ok(package)
     } else {
-        Err(ClientError::Custom(format!(
-            "Couldn't fetch details for addon. Server returned: {}",
-            resp.text()?
-        )))
+        Err(status_error("Tukui", "fetch details for addon", &mut resp))
     }
 }
 
+pub async fn fetch_addon_details(id: &str, flavor: &Flavor) -> Result<AddonDetails> {
+    let package = fetch_remote_package(id, flavor).await?;
+
+    let download_count = package.downloads.as_ref().and_then(|downloads| {
+        downloads
+            .as_u64()
+            .or_else(|| downloads.as_str().and_then(|d| d.parse().ok()))
+    });
+
+    Ok(AddonDetails {
+        summary: package.small_desc,
+        authors: package.author.into_iter().collect(),
+        download_count,
+        screenshots: package.screenshot_url.into_iter().collect(),
+    })
+}
+
 pub async fn latest_addon(tukui_id: u32, flavor: Flavor) -> Result<Addon> {
     let tukui_id_string = tukui_id.to_string();
 
@@ -87,8 +105,8 @@ pub async fn latest_addon(tukui_id: u32, flavor: Flavor) -> Result<Addon> {
 pub async fn fetch_changelog(id: &str, flavor: &Flavor) -> Result<(String, String)> {
     let url = changelog_endpoint(id, &flavor);
 
-    match flavor {
-        Flavor::Retail | Flavor::RetailBeta | Flavor::RetailPTR => {
+    match flavor.base_flavor() {
+        Flavor::Retail => {
             // Only TukUI and ElvUI main addons has changelog which can be fetched.
             // The others is embeded into a page.
             if id == "-1" || id == "-2" {
@@ -98,13 +116,7 @@ pub async fn fetch_changelog(id: &str, flavor: &Flavor) -> Result<(String, Strin
                 if resp.status().is_success() {
                     let changelog: String = resp.text()?;
 
-                    let c = regex_html_tags_to_newline()
-                        .replace_all(&changelog, "\n")
-                        .to_string();
-                    let c = regex_html_tags_to_space().replace_all(&c, "").to_string();
-                    let c = truncate(&c, 2500).to_string();
-
-                    return Ok((c, url));
+                    return Ok((format_changelog(&changelog), url));
                 }
 
                 return Ok(("No changelog found".to_string(), url));
@@ -112,7 +124,7 @@ pub async fn fetch_changelog(id: &str, flavor: &Flavor) -> Result<(String, Strin
 
             Ok(("Please view this changelog in the browser by pressing 'Full Changelog' to the right".to_string(), url))
         }
-        Flavor::Classic | Flavor::ClassicPTR => Ok((
+        _ => Ok((
             "Please view this changelog in the browser by pressing 'Full Changelog' to the right"
                 .to_string(),
             url,
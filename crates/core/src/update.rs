@@ -0,0 +1,295 @@
+use crate::addon::{Addon, AddonState};
+use crate::config::{Addons, Config, Flavor};
+use crate::error::ClientError;
+use crate::localization::{localized_string, localized_string_with};
+use crate::parse::{read_addon_directory, FingerprintCollection};
+use crate::Result;
+
+use async_std::sync::{Arc, Mutex};
+use std::collections::HashSet;
+
+/// Applies the addon preferences from the config to freshly parsed addons of `flavor`
+/// and marks the addons with an update available as `Updatable`.
+///
+/// Both the GUI and the command line go through this, so they agree on which addons
+/// have an update.
+pub fn apply_addon_preferences(addons: &mut [Addon], preferences: &Addons, flavor: Flavor) {
+    let ignored_ids = preferences.ignored.get(&flavor);
+    let release_channels = preferences.release_channels.get(&flavor);
+
+    for addon in addons.iter_mut() {
+        // Check if we have saved release channel for addon.
+        if let Some(release_channel) =
+            release_channels.and_then(|c| c.get(&addon.primary_folder_id))
+        {
+            addon.release_channel = *release_channel;
+        } else {
+            // Else we try to determine the release_channel based of installed version.
+            for (release_channel, package) in addon.remote_packages() {
+                if package.file_id == addon.file_id() {
+                    addon.release_channel = release_channel.to_owned();
+                    break;
+                }
+            }
+        }
+
+        // Check if addon is updatable based on release channel.
+        if let Some(package) = addon.relevant_release_package() {
+            let is_rolled_back = preferences.is_rolled_back_from(
+                &flavor,
+                &addon.primary_folder_id,
+                &package.version,
+            );
+
+            if addon.is_updatable(package)
+                && addon.state != AddonState::Corrupted
+                && !is_rolled_back
+            {
+                addon.state = AddonState::Updatable;
+            }
+        }
+
+        // Pinned addons stay on their installed version, so they are never updatable.
+        if preferences.is_pinned(&flavor, &addon.primary_folder_id)
+            && matches!(addon.state, AddonState::Ajour(_) | AddonState::Updatable)
+        {
+            addon.state = AddonState::Pinned;
+        }
+
+        if ignored_ids.map_or(false, |ids| {
+            ids.iter().any(|id| id == &addon.primary_folder_id)
+        }) || preferences.is_ignored_by_pattern(addon)
+        {
+            addon.state = AddonState::Ignored;
+        }
+
+        // Updating a symlinked addon would replace the working copy it links to.
+        if addon.folders.iter().any(|f| f.is_symlink) {
+            addon.state = AddonState::Symlinked;
+        }
+    }
+}
+
+/// Why an addon with an update available isn't updated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    Ignored,
+    Pinned,
+    Symlinked,
+    RolledBack,
+}
+
+impl SkipReason {
+    /// Name of the reason which isn't localized, for output read by scripts.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SkipReason::Ignored => "ignored",
+            SkipReason::Pinned => "pinned",
+            SkipReason::Symlinked => "symlinked",
+            SkipReason::RolledBack => "rolled back",
+        }
+    }
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            SkipReason::Ignored => localized_string("skip-reason-ignored"),
+            SkipReason::Pinned => localized_string("skip-reason-pinned"),
+            SkipReason::Symlinked => localized_string("skip-reason-symlinked"),
+            SkipReason::RolledBack => localized_string("skip-reason-rolled-back"),
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Returns why `addon` isn't updated even though it has an update available,
+/// once `apply_addon_preferences` has been applied to it.
+pub fn skip_reason(addon: &Addon, preferences: &Addons, flavor: Flavor) -> Option<SkipReason> {
+    let package = addon.relevant_release_package()?;
+
+    if !addon.is_updatable(package) {
+        return None;
+    }
+
+    match addon.state {
+        AddonState::Ignored => Some(SkipReason::Ignored),
+        AddonState::Pinned => Some(SkipReason::Pinned),
+        AddonState::Symlinked => Some(SkipReason::Symlinked),
+        AddonState::Ajour(_)
+            if preferences.is_rolled_back_from(
+                &flavor,
+                &addon.primary_folder_id,
+                &package.version,
+            ) =>
+        {
+            Some(SkipReason::RolledBack)
+        }
+        _ => None,
+    }
+}
+
+/// Addons of each flavor with an update available, along with the ones which
+/// are skipped and the flavors which couldn't be parsed.
+#[derive(Debug, Default)]
+pub struct UpdateCheck {
+    pub updatable: Vec<(Flavor, Addon)>,
+    pub skipped: Vec<(Flavor, Addon, SkipReason)>,
+    pub failed: Vec<(Flavor, ClientError)>,
+}
+
+/// Parses the addons of each flavor and returns the ones with an update available.
+///
+/// Flavors without an addon directory on disk are skipped.
+pub async fn check_for_updates(
+    fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
+    config: &Config,
+    flavors: &[Flavor],
+) -> Result<Vec<(Flavor, Addon)>> {
+    let check = check_addons(fingerprint_collection, config, flavors).await;
+
+    match check.failed.into_iter().next() {
+        Some((_, error)) => Err(error),
+        None => Ok(check.updatable),
+    }
+}
+
+/// Parses the addons of each flavor, like `check_for_updates`, but keeps going
+/// if a flavor fails to parse.
+pub async fn check_addons(
+    fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
+    config: &Config,
+    flavors: &[Flavor],
+) -> UpdateCheck {
+    let mut check = UpdateCheck::default();
+
+    for flavor in flavors {
+        let addon_directory = match config.get_addon_directory_for_flavor(flavor) {
+            Some(dir) if dir.exists() => dir,
+            _ => continue,
+        };
+
+        let result = read_addon_directory(
+            fingerprint_collection.clone(),
+            &addon_directory,
+            *flavor,
+            config.addons.source_priority(flavor),
+        )
+        .await;
+
+        let mut addons = match result {
+            Ok(addons) => addons,
+            Err(error) => {
+                check.failed.push((*flavor, error));
+                continue;
+            }
+        };
+
+        apply_addon_preferences(&mut addons, &config.addons, *flavor);
+
+        for addon in addons {
+            if addon.state == AddonState::Updatable {
+                check.updatable.push((*flavor, addon));
+            } else if let Some(reason) = skip_reason(&addon, &config.addons, *flavor) {
+                check.skipped.push((*flavor, addon, reason));
+            }
+        }
+    }
+
+    check
+}
+
+/// Tracks the addons of a batch of updates, eg. "Update All", until each of
+/// them has either been updated or failed.
+#[derive(Debug, Default, Clone)]
+pub struct UpdateReport {
+    pending: HashSet<(Flavor, String)>,
+    pub updated: Vec<String>,
+    /// Titles of the addons which failed, along with the reason.
+    pub failed: Vec<(String, String)>,
+    pub skipped: Vec<(String, SkipReason)>,
+}
+
+impl UpdateReport {
+    pub fn start(&mut self, flavor: Flavor, id: &str) {
+        self.pending.insert((flavor, id.to_string()));
+    }
+
+    pub fn skip(&mut self, title: &str, reason: SkipReason) {
+        self.skipped.push((title.to_string(), reason));
+    }
+
+    /// Keeps tracking a pending addon whose primary folder changed with the update.
+    pub fn rename(&mut self, flavor: Flavor, from: &str, to: &str) {
+        if self.pending.remove(&(flavor, from.to_string())) {
+            self.pending.insert((flavor, to.to_string()));
+        }
+    }
+
+    /// Records the outcome of a pending addon, which failed if there's an `error`.
+    /// Addons which aren't part of the batch are left out.
+    pub fn finish(&mut self, flavor: Flavor, id: &str, title: &str, error: Option<&ClientError>) {
+        if !self.pending.remove(&(flavor, id.to_string())) {
+            return;
+        }
+
+        match error {
+            None => self.updated.push(title.to_string()),
+            Some(error) => self.failed.push((title.to_string(), error.user_message())),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Returns a line summarizing the outcome, eg. "Updated 3 addons, 1 failed".
+    pub fn summary(&self) -> String {
+        let key = if self.updated.len() == 1 {
+            "update-summary-one"
+        } else {
+            "update-summary"
+        };
+        let mut summary = localized_string_with(key, &[("count", &self.updated.len())]);
+
+        if !self.failed.is_empty() {
+            summary.push_str(&localized_string_with(
+                "update-summary-failed",
+                &[("count", &self.failed.len())],
+            ));
+        }
+
+        if !self.skipped.is_empty() {
+            summary.push_str(&localized_string_with(
+                "update-summary-skipped",
+                &[("count", &self.skipped.len())],
+            ));
+        }
+
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_report() {
+        let mut report = UpdateReport::default();
+        report.start(Flavor::Retail, "Details");
+        report.start(Flavor::Retail, "WeakAuras");
+        report.skip("Bagnon", SkipReason::Pinned);
+
+        report.finish(Flavor::Retail, "Details", "Details!", None);
+        assert!(!report.is_finished());
+
+        let error = ClientError::Cancelled;
+        report.finish(Flavor::Retail, "WeakAuras", "WeakAuras", Some(&error));
+        report.finish(Flavor::Classic, "Details", "Details!", None);
+
+        assert!(report.is_finished());
+        assert_eq!(report.updated, vec!["Details!".to_string()]);
+        assert_eq!(report.summary(), "Updated 1 addon, 1 failed, 1 skipped");
+    }
+}
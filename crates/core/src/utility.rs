@@ -1,4 +1,9 @@
-use crate::{network::request_async, Result};
+use crate::{
+    config::{Flavor, SelfUpdateChannel},
+    error::ClientError,
+    network::request_async,
+    Result,
+};
 use isahc::prelude::*;
 use regex::Regex;
 use serde::Deserialize;
@@ -31,38 +36,135 @@ pub fn regex_html_tags_to_space() -> Regex {
     regex::Regex::new(r"&nbsp;|&quot;|&lt;|&gt;|&amp;|gt;|lt;|&#x27;|<.+?>").unwrap()
 }
 
+/// Whether the characters of `query` appear in `text` in order, ignoring case
+/// and whitespace, so eg. "dbm" matches "Deadly Boss Mods".
+pub fn fuzzy_match(query: &str, text: &str) -> bool {
+    let mut text = text.chars().flat_map(char::to_lowercase);
+
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .all(|q| text.any(|t| t == q))
+}
+
+/// Turns a changelog written in HTML, BBCode or Markdown into plain text, which
+/// is shown in the changelog of an addon.
+pub fn format_changelog(changelog: &str) -> String {
+    let list_items = Regex::new(r"(?i)<li[^>]*>|\[\*\]").unwrap();
+    let markdown_list_items = Regex::new(r"(?m)^[ \t]*[-*][ \t]+").unwrap();
+    let line_breaks = Regex::new(r"(?im)<br ?/?>|</p>|</h\d>|</div>|^#+[ \t]*").unwrap();
+    let tags = Regex::new(r"<[^>]+>|\[/?[a-zA-Z]+\d?(=[^\]]*)?\]").unwrap();
+    let blank_lines = Regex::new(r"\n\s*\n(\s*\n)+").unwrap();
+
+    let text = list_items.replace_all(changelog, "\n• ");
+    let text = markdown_list_items.replace_all(&text, "• ");
+    let text = line_breaks.replace_all(&text, "\n");
+    let text = tags.replace_all(&text, "");
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&quot;", "\"")
+        .replace("&#x27;", "'")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&");
+    let text = blank_lines.replace_all(&text, "\n\n");
+
+    truncate(text.trim(), 2500).to_string()
+}
+
 #[derive(Deserialize)]
 struct Release {
     tag_name: String,
+    html_url: String,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    body: Option<String>,
 }
 
-pub async fn needs_update(current_version: &str) -> Result<Option<String>> {
-    log::debug!("checking for application update");
+/// A release of Ajour other than the one running.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppRelease {
+    pub version: String,
+    pub channel: SelfUpdateChannel,
+    /// Page of the release on GitHub, where it's downloaded from.
+    pub url: String,
+    /// What changed in the release, as plain text.
+    pub notes: String,
+}
+
+impl AppRelease {
+    /// Whether this is a beta or nightly build.
+    pub fn is_prerelease(&self) -> bool {
+        self.channel != SelfUpdateChannel::Stable
+    }
+}
+
+/// Returns the newest release of `channel`, from releases ordered newest first.
+///
+/// Nightly builds are prereleases tagged `nightly`, other prereleases are betas.
+/// Betas include stable releases and nightlies include both, so whichever is
+/// newest is offered.
+fn latest_release(releases: Vec<Release>, channel: SelfUpdateChannel) -> Option<AppRelease> {
+    releases
+        .into_iter()
+        .filter(|r| !r.draft)
+        .map(|r| {
+            let release_channel = if !r.prerelease {
+                SelfUpdateChannel::Stable
+            } else if r.tag_name.to_lowercase().contains("nightly") {
+                SelfUpdateChannel::Nightly
+            } else {
+                SelfUpdateChannel::Beta
+            };
+
+            AppRelease {
+                version: r.tag_name,
+                channel: release_channel,
+                url: r.html_url,
+                notes: r.body.as_deref().map(format_changelog).unwrap_or_default(),
+            }
+        })
+        .find(|r| r.channel <= channel)
+}
+
+/// Returns the newest release of `channel` if it isn't `current_version`.
+///
+/// Switching back to the stable channel from a beta offers the stable release,
+/// even though its version is lower.
+pub async fn needs_update(
+    current_version: &str,
+    channel: SelfUpdateChannel,
+) -> Result<Option<AppRelease>> {
+    log::debug!("checking for application update on the {} channel", channel);
 
     let client = HttpClient::new()?;
 
     let mut resp = request_async(
         &client,
-        "https://api.github.com/repos/casperstorm/ajour/releases/latest",
+        "https://api.github.com/repos/casperstorm/ajour/releases",
         vec![],
         None,
     )
     .await?;
 
-    let release: Release = resp.json()?;
+    let releases: Vec<Release> = resp.json()?;
 
-    if release.tag_name != current_version {
-        Ok(Some(release.tag_name))
-    } else {
-        Ok(None)
-    }
+    Ok(latest_release(releases, channel).filter(|r| r.version != current_version))
 }
 
 /// Logic to help pick the right World of Warcraft folder. We want the root folder.
 pub fn wow_path_resolution(path: Option<PathBuf>) -> Option<PathBuf> {
     if let Some(path) = path {
         // Known folders in World of Warcraft dir
-        let known_folders = ["_retail_", "_classic_", "_ptr_"];
+        let known_folders = Flavor::ALL
+            .iter()
+            .map(|f| f.folder_name())
+            .collect::<Vec<_>>();
 
         // If chosen path has any of the known Wow folders, we have the right one.
         for folder in known_folders.iter() {
@@ -86,10 +188,121 @@ pub fn wow_path_resolution(path: Option<PathBuf>) -> Option<PathBuf> {
     None
 }
 
+/// Copies `text` to the clipboard, with the tool the platform has for it.
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    #[cfg(windows)]
+    let tools: &[(&str, &[&str])] = &[("clip", &[])];
+    #[cfg(target_os = "macos")]
+    let tools: &[(&str, &[&str])] = &[("pbcopy", &[])];
+    #[cfg(not(any(windows, target_os = "macos")))]
+    let tools: &[(&str, &[&str])] = &[
+        ("wl-copy", &[]),
+        ("xclip", &["-selection", "clipboard"]),
+        ("xsel", &["--clipboard", "--input"]),
+    ];
+
+    for (tool, args) in tools {
+        let mut command = Command::new(tool);
+        command.args(*args).stdin(Stdio::piped());
+
+        // Keeps a console window from flashing up when copying from the GUI.
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            command.creation_flags(0x0800_0000);
+        }
+
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            // Not installed, try the next one.
+            Err(_) => continue,
+        };
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+
+    Err(ClientError::Custom(
+        "Failed to copy to the clipboard, no clipboard tool was found".to_string(),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fuzzy_match() {
+        assert!(fuzzy_match("dbm", "Deadly Boss Mods"));
+        assert!(fuzzy_match("boss mods", "Deadly Boss Mods"));
+        assert!(fuzzy_match("", "Details!"));
+        assert!(!fuzzy_match("mbd", "Deadly Boss Mods"));
+        assert!(!fuzzy_match("weakauras", "WeakAura"));
+    }
+
+    #[test]
+    fn test_format_changelog() {
+        let html =
+            "<h3>v1.2</h3><ul><li>Fixed &quot;bars&quot;</li><li>Added <b>bags</b></li></ul>";
+        assert_eq!(
+            format_changelog(html),
+            "v1.2\n\n• Fixed \"bars\"\n• Added bags"
+        );
+
+        let bbcode = "[b]v1.2[/b]\n[list][*]Fixed [url=https://a.b]bars[/url][*]Added bags[/list]";
+        assert_eq!(
+            format_changelog(bbcode),
+            "v1.2\n\n• Fixed bars\n• Added bags"
+        );
+
+        let markdown = "# v1.2\n\n- Fixed bars\n- Added bags";
+        assert_eq!(
+            format_changelog(markdown),
+            "v1.2\n\n• Fixed bars\n• Added bags"
+        );
+    }
+
+    #[test]
+    fn test_latest_release() {
+        let release = |tag: &str, prerelease: bool, draft: bool| Release {
+            tag_name: tag.to_string(),
+            html_url: format!("https://github.com/casperstorm/ajour/releases/tag/{}", tag),
+            prerelease,
+            draft,
+            body: Some(format!("## {}\n\n- Fixed bars", tag)),
+        };
+        let releases = || {
+            vec![
+                release("1.2.0", false, true),
+                release("nightly-2021-03-02", true, false),
+                release("1.1.0-beta.2", true, false),
+                release("1.0.0", false, false),
+            ]
+        };
+
+        let stable = latest_release(releases(), SelfUpdateChannel::Stable).unwrap();
+        assert_eq!(stable.version, "1.0.0");
+        assert_eq!(stable.notes, "1.0.0\n\n• Fixed bars");
+        assert!(!stable.is_prerelease());
+
+        let beta = latest_release(releases(), SelfUpdateChannel::Beta).unwrap();
+        assert_eq!(beta.version, "1.1.0-beta.2");
+        assert_eq!(beta.channel, SelfUpdateChannel::Beta);
+
+        let nightly = latest_release(releases(), SelfUpdateChannel::Nightly).unwrap();
+        assert_eq!(nightly.version, "nightly-2021-03-02");
+
+        assert_eq!(latest_release(vec![], SelfUpdateChannel::Stable), None);
+    }
+
     #[test]
     fn test_wow_path_resolution() {
         let classic_addon_path =
@@ -0,0 +1,244 @@
+use crate::addon::{Addon, Repository};
+use crate::config::Flavor;
+use crate::curse_api::{fetch_remote_packages_by_fingerprint, FingerprintInfo};
+use crate::fs::archive::{installed_archive, modified_entries, verify_archive};
+
+use serde::Serialize;
+use std::path::Path;
+
+/// Outcome of verifying an installed addon against the release package it was
+/// installed from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verification {
+    Intact,
+    /// Files of the release package which were modified or removed since it was
+    /// installed. Only the folders are known when verified by fingerprint.
+    Mismatched(Vec<String>),
+    /// There's nothing to verify against, eg. the archive of the installed version
+    /// isn't cached and the addon isn't from Curse.
+    Unverifiable,
+}
+
+impl Verification {
+    pub fn is_mismatched(&self) -> bool {
+        matches!(self, Verification::Mismatched(_))
+    }
+}
+
+/// Verifies the installed folders of `addons` against their release packages.
+/// Returns the verification of each addon along with its id.
+///
+/// Addons are compared file by file with the cached archive of the installed
+/// version. Curse addons without one are verified by the fingerprints of their
+/// folders instead, which are known from parsing the addon directory.
+pub async fn verify_addons(
+    addons: &[Addon],
+    flavor: Flavor,
+    addon_directory: &Path,
+) -> Vec<(String, Verification)> {
+    let mut verifications = vec![];
+    let mut by_fingerprint = vec![];
+
+    for addon in addons {
+        // Symlinked folders are working copies, which are meant to differ.
+        if addon.folders.iter().any(|f| f.is_symlink) {
+            verifications.push((addon.primary_folder_id.clone(), Verification::Unverifiable));
+            continue;
+        }
+
+        match verify_with_archive(addon, flavor, addon_directory) {
+            Some(verification) => {
+                verifications.push((addon.primary_folder_id.clone(), verification))
+            }
+            None if addon.active_repository == Some(Repository::Curse)
+                && addon.file_id().is_some()
+                && addon.folders.iter().all(|f| f.fingerprint.is_some()) =>
+            {
+                by_fingerprint.push(addon)
+            }
+            None => {
+                verifications.push((addon.primary_folder_id.clone(), Verification::Unverifiable))
+            }
+        }
+    }
+
+    if !by_fingerprint.is_empty() {
+        let mut fingerprints: Vec<_> = by_fingerprint
+            .iter()
+            .flat_map(|a| a.folders.iter().filter_map(|f| f.fingerprint))
+            .collect();
+        fingerprints.sort_unstable();
+        fingerprints.dedup();
+
+        let info = match fetch_remote_packages_by_fingerprint(&fingerprints).await {
+            Ok(info) => Some(info),
+            Err(e) => {
+                log::error!(
+                    "{} - failed to verify fingerprints with curse: {}",
+                    flavor,
+                    e
+                );
+                None
+            }
+        };
+
+        for addon in by_fingerprint {
+            let verification = info
+                .as_ref()
+                .map(|info| verify_with_fingerprints(addon, info))
+                .unwrap_or(Verification::Unverifiable);
+
+            verifications.push((addon.primary_folder_id.clone(), verification));
+        }
+    }
+
+    verifications
+}
+
+/// Verifies `addon` against the cached archive of its installed version. Returns
+/// `None` if there's no usable archive.
+fn verify_with_archive(
+    addon: &Addon,
+    flavor: Flavor,
+    addon_directory: &Path,
+) -> Option<Verification> {
+    let archive = match installed_archive(addon, flavor) {
+        Ok(archive) => archive?,
+        Err(e) => {
+            log::error!("failed to load the archive cache: {}", e);
+            return None;
+        }
+    };
+
+    // A corrupted archive says nothing about the installed files.
+    if let Some(fingerprint) = archive.fingerprint {
+        if let Err(e) = verify_archive(&archive.path, fingerprint) {
+            log::error!("cached archive of {} is unusable: {}", addon.title(), e);
+            return None;
+        }
+    }
+
+    match modified_entries(&archive.path, addon_directory) {
+        Ok(modified) if modified.is_empty() => Some(Verification::Intact),
+        Ok(modified) => Some(Verification::Mismatched(modified)),
+        Err(e) => {
+            log::error!(
+                "failed to compare {} with its archive: {}",
+                addon.title(),
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Verifies `addon` against the fingerprints Curse has of the file it was
+/// installed from.
+fn verify_with_fingerprints(addon: &Addon, info: &FingerprintInfo) -> Verification {
+    let file_id = addon.file_id();
+
+    if info
+        .exact_matches
+        .iter()
+        .any(|m| Some(m.file.id) == file_id)
+    {
+        return Verification::Intact;
+    }
+
+    // Folders which still match are enough to find the file by.
+    match info
+        .partial_matches
+        .iter()
+        .find(|m| Some(m.file.id) == file_id)
+    {
+        Some(partial_match) => {
+            let mismatched = partial_match
+                .file
+                .modules
+                .iter()
+                .filter(|module| {
+                    !addon.folders.iter().any(|f| {
+                        f.id == module.foldername && f.fingerprint == Some(module.fingerprint)
+                    })
+                })
+                .map(|module| module.foldername.clone())
+                .collect();
+
+            Verification::Mismatched(mismatched)
+        }
+        None => Verification::Unverifiable,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::addon::AddonFolder;
+    use crate::curse_api::{AddonFingerprintInfo, Module};
+    use std::path::PathBuf;
+
+    fn folder(id: &str, fingerprint: u32) -> AddonFolder {
+        let mut folder = AddonFolder::new(
+            id.to_string(),
+            id.to_string(),
+            PathBuf::from(id),
+            None,
+            None,
+            None,
+            Default::default(),
+            vec![],
+        );
+        folder.fingerprint = Some(fingerprint);
+        folder
+    }
+
+    fn fingerprint_info(file_id: i64, modules: &[(&str, u32)]) -> AddonFingerprintInfo {
+        let mut info = AddonFingerprintInfo::default();
+        info.file.id = file_id;
+        info.file.modules = modules
+            .iter()
+            .map(|(foldername, fingerprint)| Module {
+                foldername: foldername.to_string(),
+                fingerprint: *fingerprint,
+                ..Default::default()
+            })
+            .collect();
+
+        info
+    }
+
+    #[test]
+    fn test_verify_with_fingerprints() {
+        let mut addon = Addon::empty("DBM-Core");
+        addon.repository_metadata.file_id = Some(3201);
+        addon.folders = vec![folder("DBM-Core", 1), folder("DBM-GUI", 5)];
+
+        let mut info = FingerprintInfo::default();
+        assert_eq!(
+            verify_with_fingerprints(&addon, &info),
+            Verification::Unverifiable
+        );
+
+        info.partial_matches
+            .push(fingerprint_info(3201, &[("DBM-Core", 1), ("DBM-GUI", 2)]));
+        assert_eq!(
+            verify_with_fingerprints(&addon, &info),
+            Verification::Mismatched(vec!["DBM-GUI".to_string()])
+        );
+
+        info.exact_matches
+            .push(fingerprint_info(3201, &[("DBM-Core", 1), ("DBM-GUI", 5)]));
+        assert_eq!(
+            verify_with_fingerprints(&addon, &info),
+            Verification::Intact
+        );
+
+        // Matches of another file of the addon don't count.
+        addon.repository_metadata.file_id = Some(3202);
+        assert_eq!(
+            verify_with_fingerprints(&addon, &info),
+            Verification::Unverifiable
+        );
+    }
+}
@@ -0,0 +1,330 @@
+use std::cmp::Ordering;
+
+/// Compares two addon versions, understanding dotted versions such as semver,
+/// date based versions, `r` revisions, letter suffixes and alpha, beta and
+/// release candidate markers.
+///
+/// Returns `None` if either isn't a version we understand, or if they follow
+/// different schemes, eg. a date and a dotted version.
+pub fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    let a = Version::parse(a)?;
+    let b = Version::parse(b)?;
+
+    if a.scheme != b.scheme {
+        return None;
+    }
+
+    Some(a.cmp(&b))
+}
+
+/// Orders versions for sorting. Unlike `compare_versions` every two versions are
+/// ordered, so the order is total.
+///
+/// Dotted versions come before date based ones, and versions we don't understand
+/// come last. Versions which are the same, eg. `1.2` and `1.2.0`, are ordered by
+/// their text.
+pub fn version_order(a: &str, b: &str) -> Ordering {
+    let ordering = match (Version::parse(a), Version::parse(b)) {
+        (Some(parsed_a), Some(parsed_b)) => parsed_a
+            .scheme
+            .cmp(&parsed_b.scheme)
+            .then_with(|| parsed_a.cmp(&parsed_b)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    };
+
+    ordering.then_with(|| a.cmp(b))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Scheme {
+    Dotted,
+    Date,
+}
+
+/// Markers of a release before the final one, ordered from earliest to latest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum PreRelease {
+    Alpha,
+    Beta,
+    ReleaseCandidate,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Version {
+    scheme: Scheme,
+    numbers: Vec<u64>,
+    pre_release: Option<(PreRelease, u64)>,
+    /// A letter after the numbers, eg. `9.0.5a`, a fix released after `9.0.5`.
+    suffix: Option<char>,
+    revision: Option<u64>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Number(u64, usize),
+    Word(String),
+    Separator,
+}
+
+impl Version {
+    fn parse(version: &str) -> Option<Version> {
+        let version = version.trim().to_lowercase();
+        let version = version.trim_end_matches(".zip");
+        let tokens = tokenize(version);
+
+        // Anything before the first number is a name or a `v` prefix.
+        let start = tokens
+            .iter()
+            .position(|t| matches!(t, Token::Number(_, _)))?;
+        let mut tokens = tokens[start..].iter().peekable();
+
+        let mut numbers = vec![];
+        let mut first_digits = 0;
+        let mut pre_release = None;
+        let mut suffix = None;
+        let mut revision = None;
+
+        while let Some(token) = tokens.next() {
+            match token {
+                Token::Number(number, digits) if pre_release.is_none() && suffix.is_none() => {
+                    if numbers.is_empty() {
+                        first_digits = *digits;
+                    }
+                    numbers.push(*number);
+                }
+                Token::Separator => {}
+                Token::Word(word) => {
+                    let marker = match word.as_str() {
+                        "alpha" => Some(PreRelease::Alpha),
+                        "beta" => Some(PreRelease::Beta),
+                        "rc" | "pre" => Some(PreRelease::ReleaseCandidate),
+                        _ => None,
+                    };
+
+                    let next_number = match tokens.peek() {
+                        Some(Token::Number(number, _)) => Some(*number),
+                        _ => None,
+                    };
+
+                    if let Some(marker) = marker {
+                        pre_release = Some((marker, next_number.unwrap_or_default()));
+                    } else if matches!(word.as_str(), "r" | "rev" | "revision")
+                        && next_number.is_some()
+                    {
+                        revision = next_number;
+                    } else if word.len() == 1 && next_number.is_none() && suffix.is_none() {
+                        suffix = word.chars().next();
+                    } else {
+                        // Eg. a flavor or a commit, which tells nothing about the version.
+                        break;
+                    }
+
+                    if next_number.is_some() {
+                        tokens.next();
+                    }
+                }
+                Token::Number(_, _) => break,
+            }
+        }
+
+        // Date based versions start with a year, eg. `2021.03.15` or `20210315`.
+        let scheme = match first_digits {
+            8 if is_year(numbers[0] / 10000) => {
+                let date = numbers.remove(0);
+                numbers.splice(0..0, vec![date / 10000, date / 100 % 100, date % 100]);
+
+                Scheme::Date
+            }
+            4 if is_year(numbers[0]) && numbers.len() > 1 => Scheme::Date,
+            _ => Scheme::Dotted,
+        };
+
+        Some(Version {
+            scheme,
+            numbers,
+            pre_release,
+            suffix,
+            revision,
+        })
+    }
+}
+
+fn is_year(number: u64) -> bool {
+    (1990..=2100).contains(&number)
+}
+
+fn tokenize(version: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut chars = version.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() {
+            let mut digits = c.to_string();
+            while let Some(c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                digits.push(*c);
+                chars.next();
+            }
+
+            // Numbers too long to be a version part, eg. a hash, are left out.
+            if let Ok(number) = digits.parse() {
+                tokens.push(Token::Number(number, digits.len()));
+            }
+        } else if c.is_alphabetic() {
+            let mut word = c.to_string();
+            while let Some(c) = chars.peek().filter(|c| c.is_alphabetic()) {
+                word.push(*c);
+                chars.next();
+            }
+
+            tokens.push(Token::Word(word));
+        } else {
+            tokens.push(Token::Separator);
+        }
+    }
+
+    tokens
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Missing parts count as zero, so `1.2` is the same as `1.2.0`.
+        let len = self.numbers.len().max(other.numbers.len());
+        let part = |numbers: &[u64], i: usize| numbers.get(i).copied().unwrap_or_default();
+
+        for i in 0..len {
+            match part(&self.numbers, i).cmp(&part(&other.numbers, i)) {
+                Ordering::Equal => {}
+                ordering => return ordering,
+            }
+        }
+
+        // A pre-release comes before the release itself.
+        let pre_release = match (self.pre_release, other.pre_release) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(a), Some(b)) => a.cmp(&b),
+        };
+
+        pre_release
+            .then_with(|| self.suffix.cmp(&other.suffix))
+            .then_with(|| self.revision.cmp(&other.revision))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_versions() {
+        use Ordering::*;
+
+        // Versions of real addons, with the installed version first.
+        let corpus = [
+            // Stripping non-digits made these equal.
+            ("4.10.2", "41.0.2", Some(Less)),
+            ("41.0.2", "4.10.2", Some(Greater)),
+            // Semver and other dotted versions.
+            ("9.0.25", "9.0.26", Some(Less)),
+            ("9.0.26", "9.0.26", Some(Equal)),
+            ("v9.0.26", "9.0.26", Some(Equal)),
+            ("1.2", "1.2.0", Some(Equal)),
+            ("12.3", "12.21", Some(Less)),
+            ("3.2.0", "3.1.12", Some(Greater)),
+            ("v218.1", "v219", Some(Less)),
+            ("Rematch_4_10_15.zip", "4.10.16", Some(Less)),
+            (
+                "Plater-v3.0.58-Retail",
+                "Plater-v3.0.58-Retail",
+                Some(Equal),
+            ),
+            ("Plater-v3.0.57-Retail", "Plater-v3.0.58-Retail", Some(Less)),
+            ("2.5.1-classic", "2.5.1", Some(Equal)),
+            ("9.0.5-3-g8a1b2c3", "9.0.5", Some(Greater)),
+            // Pre-releases.
+            ("9.0.30 alpha", "9.0.30", Some(Less)),
+            ("3.2.1-beta2", "3.2.1-beta3", Some(Less)),
+            ("3.2.1-beta3", "3.2.1-rc1", Some(Less)),
+            ("3.2.1-alpha", "3.2.1-beta", Some(Less)),
+            ("3.2.1-rc2", "3.2.1", Some(Less)),
+            ("3.2.2-alpha1", "3.2.1", Some(Greater)),
+            // Letter suffixes are fixes after the release.
+            ("9.0.5", "9.0.5a", Some(Less)),
+            ("9.0.5a", "9.0.5b", Some(Less)),
+            ("9.0.5b", "9.0.6", Some(Less)),
+            // Revisions.
+            ("1.0.3r12", "1.0.3r13", Some(Less)),
+            ("1.0.3-r13", "1.0.3r13", Some(Equal)),
+            ("r12345", "r12346", Some(Less)),
+            ("r800", "r1000", Some(Less)),
+            // Date based versions.
+            ("2021.03.15", "2021.04.01", Some(Less)),
+            ("20201102", "2020.11.03", Some(Less)),
+            ("20210315", "2021-03-15", Some(Equal)),
+            ("v20210401.1", "v20210401.2", Some(Less)),
+            // Versions we can't compare.
+            ("20210315", "1.2.3", None),
+            ("2021.03.15", "9.0.5", None),
+            ("@project-version@", "1.2.3", None),
+            ("", "1.0", None),
+        ];
+
+        for (a, b, expected) in corpus.iter() {
+            assert_eq!(compare_versions(a, b), *expected, "{} against {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_version_order() {
+        let mut versions = vec![
+            "@project-version@",
+            "2021.03.15",
+            "10.0",
+            "1.2.0",
+            "9.0.5",
+            "20200101",
+            "1.2",
+            "",
+        ];
+        versions.sort_by(|a, b| version_order(a, b));
+
+        assert_eq!(
+            versions,
+            vec![
+                "1.2",
+                "1.2.0",
+                "9.0.5",
+                "10.0",
+                "20200101",
+                "2021.03.15",
+                "",
+                "@project-version@",
+            ]
+        );
+
+        // The order is transitive, and only equal for the same version.
+        for a in versions.iter() {
+            for b in versions.iter() {
+                assert_eq!(version_order(a, b) == Ordering::Equal, a == b);
+                assert_eq!(version_order(a, b), version_order(b, a).reverse());
+
+                for c in versions.iter() {
+                    if version_order(a, b) == Ordering::Less
+                        && version_order(b, c) == Ordering::Less
+                    {
+                        assert_eq!(version_order(a, c), Ordering::Less, "{} {} {}", a, b, c);
+                    }
+                }
+            }
+        }
+    }
+}
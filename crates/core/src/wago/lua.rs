@@ -0,0 +1,486 @@
+//! A small parser for the subset of Lua the game writes SavedVariables in.
+//!
+//! SavedVariables are plain assignments of literal values to global names, so
+//! there is no need for a full Lua implementation to read them.
+
+use crate::{error::ClientError, Result};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Nil,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Table(Table),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_table(&self) -> Option<&Table> {
+        match self {
+            Value::Table(t) => Some(t),
+            _ => None,
+        }
+    }
+}
+
+/// A Lua table, with its entries in the order they were written.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Table {
+    pub entries: Vec<(Value, Value)>,
+}
+
+impl Table {
+    /// Returns the value stored under the string key `key`.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k.as_str() == Some(key))
+            .map(|(_, v)| v)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(Value, Value)> {
+        self.entries.iter()
+    }
+}
+
+/// Parses the content of a SavedVariables file into its global variables.
+pub fn parse_saved_variables(source: &str) -> Result<HashMap<String, Value>> {
+    let mut parser = Parser::new(source);
+    let mut variables = HashMap::new();
+
+    loop {
+        parser.skip_whitespace();
+
+        if parser.is_at_end() {
+            break;
+        }
+
+        let name = parser
+            .identifier()
+            .ok_or_else(|| parser.error("expected a variable name"))?;
+        parser.expect(b'=')?;
+        let value = parser.value()?;
+
+        variables.insert(name, value);
+    }
+
+    Ok(variables)
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Parser {
+            bytes: source.as_bytes(),
+            position: 0,
+        }
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.position >= self.bytes.len()
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<u8> {
+        self.bytes.get(self.position + offset).copied()
+    }
+
+    fn error(&self, message: &str) -> ClientError {
+        ClientError::ParseError(format!(
+            "Couldn't parse Lua at byte {}: {}",
+            self.position, message
+        ))
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        self.skip_whitespace();
+
+        if self.peek() == Some(byte) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", byte as char)))
+        }
+    }
+
+    /// Skips whitespace and comments.
+    fn skip_whitespace(&mut self) {
+        while let Some(byte) = self.peek() {
+            if byte.is_ascii_whitespace() {
+                self.position += 1;
+            } else if byte == b'-' && self.peek_at(1) == Some(b'-') {
+                self.position += 2;
+
+                if let Some(level) = self.long_bracket_level() {
+                    let _ = self.long_string(level);
+                } else {
+                    while let Some(byte) = self.peek() {
+                        self.position += 1;
+                        if byte == b'\n' {
+                            break;
+                        }
+                    }
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn identifier(&mut self) -> Option<String> {
+        self.skip_whitespace();
+
+        let start = self.position;
+        while let Some(byte) = self.peek() {
+            let is_valid = byte == b'_'
+                || byte.is_ascii_alphabetic()
+                || (self.position > start && byte.is_ascii_digit());
+
+            if !is_valid {
+                break;
+            }
+            self.position += 1;
+        }
+
+        if self.position > start {
+            Some(String::from_utf8_lossy(&self.bytes[start..self.position]).to_string())
+        } else {
+            None
+        }
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some(b'{') => self.table().map(Value::Table),
+            Some(b'"') | Some(b'\'') => self.quoted_string().map(Value::String),
+            Some(b'[') => {
+                let level = self
+                    .long_bracket_level()
+                    .ok_or_else(|| self.error("expected a long string"))?;
+                self.long_string(level).map(Value::String)
+            }
+            Some(byte) if byte == b'-' || byte == b'.' || byte.is_ascii_digit() => {
+                self.number().map(Value::Number)
+            }
+            Some(_) => match self.identifier().as_deref() {
+                Some("true") => Ok(Value::Bool(true)),
+                Some("false") => Ok(Value::Bool(false)),
+                Some("nil") => Ok(Value::Nil),
+                Some("inf") => Ok(Value::Number(f64::INFINITY)),
+                _ => Err(self.error("expected a value")),
+            },
+            None => Err(self.error("unexpected end of file")),
+        }
+    }
+
+    fn table(&mut self) -> Result<Table> {
+        self.expect(b'{')?;
+
+        let mut table = Table::default();
+        let mut next_index = 1.0;
+
+        loop {
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(b'}') => {
+                    self.position += 1;
+                    break;
+                }
+                None => return Err(self.error("unclosed table")),
+                _ => {}
+            }
+
+            let is_key = self.peek() == Some(b'[') && self.long_bracket_level().is_none();
+
+            if is_key {
+                self.position += 1;
+                let key = self.value()?;
+                self.expect(b']')?;
+                self.expect(b'=')?;
+                let value = self.value()?;
+                table.entries.push((key, value));
+            } else {
+                let start = self.position;
+
+                // A bare name is a key if followed by `=`, otherwise it's a value
+                // such as `true`.
+                let key = self.identifier().filter(|_| {
+                    self.skip_whitespace();
+                    self.peek() == Some(b'=')
+                });
+
+                if let Some(key) = key {
+                    self.expect(b'=')?;
+                    let value = self.value()?;
+                    table.entries.push((Value::String(key), value));
+                } else {
+                    self.position = start;
+                    let value = self.value()?;
+                    table.entries.push((Value::Number(next_index), value));
+                    next_index += 1.0;
+                }
+            }
+
+            self.skip_whitespace();
+            if matches!(self.peek(), Some(b',') | Some(b';')) {
+                self.position += 1;
+            }
+        }
+
+        Ok(table)
+    }
+
+    fn quoted_string(&mut self) -> Result<String> {
+        let quote = self.peek().ok_or_else(|| self.error("expected a string"))?;
+        self.position += 1;
+
+        let mut bytes = vec![];
+
+        loop {
+            let byte = self.peek().ok_or_else(|| self.error("unclosed string"))?;
+            self.position += 1;
+
+            if byte == quote {
+                break;
+            }
+
+            if byte != b'\\' {
+                bytes.push(byte);
+                continue;
+            }
+
+            let escaped = self.peek().ok_or_else(|| self.error("unclosed string"))?;
+            self.position += 1;
+
+            match escaped {
+                b'n' | b'\n' => bytes.push(b'\n'),
+                b't' => bytes.push(b'\t'),
+                b'r' => bytes.push(b'\r'),
+                b'a' => bytes.push(0x07),
+                b'b' => bytes.push(0x08),
+                b'f' => bytes.push(0x0c),
+                b'v' => bytes.push(0x0b),
+                b'0'..=b'9' => {
+                    // Up to three decimal digits make up a byte.
+                    let mut code = u32::from(escaped - b'0');
+                    for _ in 0..2 {
+                        match self.peek() {
+                            Some(digit) if digit.is_ascii_digit() => {
+                                code = code * 10 + u32::from(digit - b'0');
+                                self.position += 1;
+                            }
+                            _ => break,
+                        }
+                    }
+                    bytes.push(code as u8);
+                }
+                other => bytes.push(other),
+            }
+        }
+
+        Ok(String::from_utf8_lossy(&bytes).to_string())
+    }
+
+    /// Returns the level of the long bracket starting at the current position,
+    /// eg. 2 for `[==[`.
+    fn long_bracket_level(&self) -> Option<usize> {
+        if self.peek() != Some(b'[') {
+            return None;
+        }
+
+        let mut level = 0;
+        while self.peek_at(level + 1) == Some(b'=') {
+            level += 1;
+        }
+
+        if self.peek_at(level + 1) == Some(b'[') {
+            Some(level)
+        } else {
+            None
+        }
+    }
+
+    fn long_string(&mut self, level: usize) -> Result<String> {
+        self.position += level + 2;
+
+        // A newline directly after the opening bracket isn't part of the string.
+        if self.peek() == Some(b'\r') {
+            self.position += 1;
+        }
+        if self.peek() == Some(b'\n') {
+            self.position += 1;
+        }
+
+        let closing = format!("]{}]", "=".repeat(level));
+        let rest = &self.bytes[self.position..];
+
+        let end = rest
+            .windows(closing.len())
+            .position(|w| w == closing.as_bytes())
+            .ok_or_else(|| self.error("unclosed long string"))?;
+
+        let string = String::from_utf8_lossy(&rest[..end]).to_string();
+        self.position += end + closing.len();
+
+        Ok(string)
+    }
+
+    fn number(&mut self) -> Result<f64> {
+        let start = self.position;
+
+        if self.peek() == Some(b'-') {
+            self.position += 1;
+        }
+
+        let is_hex =
+            self.peek() == Some(b'0') && matches!(self.peek_at(1), Some(b'x') | Some(b'X'));
+
+        if is_hex {
+            self.position += 2;
+            let digits_start = self.position;
+            while self.peek().map_or(false, |b| b.is_ascii_hexdigit()) {
+                self.position += 1;
+            }
+
+            let digits = String::from_utf8_lossy(&self.bytes[digits_start..self.position]);
+            let number = i64::from_str_radix(&digits, 16)
+                .map_err(|_| self.error("invalid hexadecimal number"))?
+                as f64;
+
+            return Ok(if self.bytes[start] == b'-' {
+                -number
+            } else {
+                number
+            });
+        }
+
+        while let Some(byte) = self.peek() {
+            let is_exponent_sign = matches!(byte, b'+' | b'-')
+                && matches!(self.bytes.get(self.position - 1), Some(b'e') | Some(b'E'));
+
+            if byte.is_ascii_digit() || matches!(byte, b'.' | b'e' | b'E') || is_exponent_sign {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+
+        // The game writes infinity as `inf` and `-inf`.
+        if self.bytes[self.position..].starts_with(b"inf") {
+            self.position += 3;
+            return Ok(if self.bytes[start] == b'-' {
+                f64::NEG_INFINITY
+            } else {
+                f64::INFINITY
+            });
+        }
+
+        let text = String::from_utf8_lossy(&self.bytes[start..self.position]);
+
+        text.parse::<f64>()
+            .map_err(|_| self.error(&format!("invalid number '{}'", text)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_saved_variables() {
+        let source = r#"
+-- A comment
+WeakAurasSaved = {
+    ["displays"] = {
+        ["My \"Aura\""] = {
+            ["url"] = "https://wago.io/abc/12",
+            ["version"] = 12,
+            ["ignoreWagoUpdate"] = false,
+            ["desc"] = [[multi
+line]],
+            ["color"] = { 1, 0.5, -1e-2, },
+        },
+    },
+    bare = 0x10,
+}
+EnabledOnce = nil
+"#;
+
+        let variables = parse_saved_variables(source).unwrap();
+        assert_eq!(variables.get("EnabledOnce"), Some(&Value::Nil));
+
+        let saved = variables.get("WeakAurasSaved").unwrap().as_table().unwrap();
+        assert_eq!(saved.get("bare"), Some(&Value::Number(16.0)));
+
+        let displays = saved.get("displays").unwrap().as_table().unwrap();
+        let (key, aura) = displays.iter().next().unwrap();
+        assert_eq!(key.as_str(), Some("My \"Aura\""));
+
+        let aura = aura.as_table().unwrap();
+        assert_eq!(
+            aura.get("url").and_then(Value::as_str),
+            Some("https://wago.io/abc/12")
+        );
+        assert_eq!(aura.get("version").and_then(Value::as_f64), Some(12.0));
+        assert_eq!(
+            aura.get("ignoreWagoUpdate").and_then(Value::as_bool),
+            Some(false)
+        );
+        assert_eq!(
+            aura.get("desc").and_then(Value::as_str),
+            Some("multi\nline")
+        );
+
+        let color = aura.get("color").unwrap().as_table().unwrap();
+        assert_eq!(
+            color.entries,
+            vec![
+                (Value::Number(1.0), Value::Number(1.0)),
+                (Value::Number(2.0), Value::Number(0.5)),
+                (Value::Number(3.0), Value::Number(-0.01)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_escapes() {
+        let variables = parse_saved_variables(r#"Name = "a\nb\\c\065\195\169""#).unwrap();
+
+        assert_eq!(
+            variables.get("Name").and_then(Value::as_str),
+            Some("a\nb\\cAé")
+        );
+    }
+}
@@ -0,0 +1,560 @@
+//! Updates of auras imported from [wago.io](https://wago.io).
+//!
+//! Like the WeakAuras Companion app, Ajour looks up newer versions of the
+//! WeakAuras and Plater profiles, scripts and mods found in the SavedVariables
+//! and writes them into a small addon. Both addons read it on login and offer
+//! the updates in-game.
+
+mod lua;
+pub mod plater;
+pub mod weak_auras;
+
+use crate::{
+    config::Flavor,
+    network::{request_async, status_error},
+    Result,
+};
+use isahc::config::RedirectPolicy;
+use isahc::prelude::*;
+use serde::Deserialize;
+use std::fs::{create_dir_all, read_dir, read_to_string, write};
+use std::path::{Path, PathBuf};
+
+const API_ENDPOINT: &str = "https://data.wago.io/api";
+
+/// Name of the folder in `Interface/AddOns` the companion addon is written to.
+pub const COMPANION_FOLDER: &str = "WeakAurasCompanion";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuraKind {
+    WeakAura,
+    /// A Plater profile, script or mod.
+    Plater,
+}
+
+impl AuraKind {
+    /// Name of the kind in the wago.io API.
+    fn api_name(self) -> &'static str {
+        match self {
+            AuraKind::WeakAura => "weakauras",
+            AuraKind::Plater => "plater",
+        }
+    }
+}
+
+impl std::fmt::Display for AuraKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            AuraKind::WeakAura => "WeakAura",
+            AuraKind::Plater => "Plater",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// An aura imported from wago.io.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aura {
+    pub kind: AuraKind,
+    /// Id of the aura on wago.io, as seen in its url.
+    pub slug: String,
+    /// Name of the aura in-game.
+    pub name: String,
+    pub version: u32,
+    pub semver: Option<String>,
+    /// Names of the aura and its children in-game.
+    pub ids: Vec<String>,
+    /// Unique ids of the aura and its children in-game.
+    pub uids: Vec<String>,
+    /// Set when the user chose to never update the aura in-game.
+    pub ignore_updates: bool,
+    /// A version the user chose to skip in-game.
+    pub skipped_version: Option<u32>,
+    /// The latest release on wago.io, once fetched.
+    pub release: Option<WagoRelease>,
+}
+
+impl Aura {
+    pub fn status(&self) -> AuraStatus {
+        match &self.release {
+            None => AuraStatus::Unknown,
+            Some(_) if self.ignore_updates => AuraStatus::Ignored,
+            Some(release) if release.version <= self.version => AuraStatus::UpToDate,
+            Some(release) if self.skipped_version.map_or(false, |v| v >= release.version) => {
+                AuraStatus::Skipped
+            }
+            Some(_) => AuraStatus::UpdateAvailable,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuraStatus {
+    UpToDate,
+    UpdateAvailable,
+    Ignored,
+    Skipped,
+    /// The aura wasn't found on wago.io, eg. because it was made private.
+    Unknown,
+}
+
+impl std::fmt::Display for AuraStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            AuraStatus::UpToDate => "Up to date",
+            AuraStatus::UpdateAvailable => "Update available",
+            AuraStatus::Ignored => "Ignored",
+            AuraStatus::Skipped => "Skipped",
+            AuraStatus::Unknown => "Unknown",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// The latest release of an aura on wago.io.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WagoRelease {
+    pub slug: String,
+    pub name: String,
+    #[serde(default)]
+    pub username: Option<String>,
+    pub version: u32,
+    #[serde(default)]
+    pub version_string: Option<String>,
+    #[serde(default)]
+    pub changelog: Option<WagoChangelog>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct WagoChangelog {
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+/// Returns the accounts in `wtf_directory` which have WeakAuras or Plater
+/// installed.
+pub fn accounts(wtf_directory: &Path) -> Vec<String> {
+    let mut accounts = accounts_with_saved_variables(wtf_directory, weak_auras::SAVED_VARIABLES);
+    accounts.extend(accounts_with_saved_variables(
+        wtf_directory,
+        plater::SAVED_VARIABLES,
+    ));
+
+    accounts.sort();
+    accounts.dedup();
+    accounts
+}
+
+/// Returns the WeakAuras and Plater auras of `account` which were imported from
+/// wago.io, along with their latest release.
+pub async fn check_for_updates(wtf_directory: PathBuf, account: String) -> Result<Vec<Aura>> {
+    let mut auras = weak_auras::parse_auras(&wtf_directory, &account)?;
+    let mut plater_auras = plater::parse_auras(&wtf_directory, &account)?;
+
+    if !auras.is_empty() {
+        fetch_releases(AuraKind::WeakAura, &mut auras).await?;
+    }
+
+    if !plater_auras.is_empty() {
+        fetch_releases(AuraKind::Plater, &mut plater_auras).await?;
+    }
+
+    auras.extend(plater_auras);
+    Ok(auras)
+}
+
+/// Returns the accounts in `wtf_directory` which have a SavedVariables file
+/// named `file_name`.
+fn accounts_with_saved_variables(wtf_directory: &Path, file_name: &str) -> Vec<String> {
+    let account_directory = wtf_directory.join("Account");
+
+    let mut accounts = read_dir(account_directory)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().join("SavedVariables").join(file_name).exists())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    accounts.sort();
+    accounts
+}
+
+/// Returns the slug of an aura from its wago.io url, eg. `abc` for
+/// `https://wago.io/abc/12`.
+fn slug_from_url(url: &str) -> Option<String> {
+    let path = url
+        .trim()
+        .strip_prefix("https://wago.io/")
+        .or_else(|| url.trim().strip_prefix("http://wago.io/"))?;
+
+    path.split('/')
+        .next()
+        .filter(|slug| !slug.is_empty())
+        .map(str::to_string)
+}
+
+fn http_client() -> Result<HttpClient> {
+    Ok(HttpClient::builder()
+        .redirect_policy(RedirectPolicy::Follow)
+        .max_connections_per_host(6)
+        .build()?)
+}
+
+/// Fetches the latest release of each of `auras`, which are all of `kind`, from
+/// wago.io.
+async fn fetch_releases(kind: AuraKind, auras: &mut [Aura]) -> Result<()> {
+    let client = http_client()?;
+
+    // Keeps the url at a reasonable length for users with many auras.
+    let slugs = auras.iter().map(|a| a.slug.clone()).collect::<Vec<_>>();
+
+    for chunk in slugs.chunks(50) {
+        let url = format!(
+            "{}/check/{}?ids={}",
+            API_ENDPOINT,
+            kind.api_name(),
+            chunk.join(",")
+        );
+        let mut resp = request_async(&client, &url, vec![], Some(30)).await?;
+
+        if !resp.status().is_success() {
+            return Err(status_error("wago.io", "check for updates", &mut resp));
+        }
+
+        let releases: Vec<WagoRelease> = resp.json()?;
+
+        for release in releases {
+            if let Some(aura) = auras.iter_mut().find(|a| a.slug == release.slug) {
+                aura.release = Some(release);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetches the import string of the latest release of an aura.
+async fn fetch_encoded(client: &HttpClient, slug: &str) -> Result<String> {
+    let url = format!("{}/raw/encoded?id={}", API_ENDPOINT, slug);
+    let mut resp = request_async(client, &url, vec![], Some(30)).await?;
+
+    if resp.status().is_success() {
+        Ok(resp.text()?)
+    } else {
+        Err(status_error(
+            "wago.io",
+            &format!("download {}", slug),
+            &mut resp,
+        ))
+    }
+}
+
+/// The updates written to the companion addon.
+#[derive(Debug, Clone, Default)]
+pub struct CompanionUpdate {
+    /// Amount of updates written.
+    pub written: usize,
+    /// Names of the auras which failed to download, and were left out.
+    pub failed: Vec<String>,
+}
+
+/// Downloads the auras with an update available and writes them to the
+/// companion addon in `addon_directory`, replacing the updates written before.
+///
+/// Auras which fail to download are logged and left out, so they don't hold
+/// back the updates of the others. The updates are installed the next time the
+/// game is started or the UI is reloaded.
+pub async fn update_companion(
+    addon_directory: impl AsRef<Path>,
+    flavor: Flavor,
+    auras: Vec<Aura>,
+) -> Result<CompanionUpdate> {
+    let client = http_client()?;
+
+    let mut updates = vec![];
+    let mut failed = vec![];
+    for aura in auras
+        .iter()
+        .filter(|a| a.status() == AuraStatus::UpdateAvailable)
+    {
+        match fetch_encoded(&client, &aura.slug).await {
+            Ok(encoded) => updates.push((aura, encoded)),
+            Err(e) => {
+                log::error!("failed to download the update of {}: {}", aura.name, e);
+                failed.push(aura.name.clone());
+            }
+        }
+    }
+
+    let addon_directory = addon_directory.as_ref();
+    let companion_directory = addon_directory.join(COMPANION_FOLDER);
+    create_dir_all(&companion_directory)?;
+
+    write(
+        companion_directory.join(format!("{}.toc", COMPANION_FOLDER)),
+        companion_toc(addon_directory, flavor),
+    )?;
+    write(
+        companion_directory.join("data.lua"),
+        companion_data(&updates),
+    )?;
+    write(companion_directory.join("init.lua"), COMPANION_INIT)?;
+
+    Ok(CompanionUpdate {
+        written: updates.len(),
+        failed,
+    })
+}
+
+const COMPANION_INIT: &str = r#"-- file generated automatically
+local loadedFrame = CreateFrame("FRAME")
+loadedFrame:RegisterEvent("ADDON_LOADED")
+loadedFrame:SetScript("OnEvent", function(_, _, addonName)
+  if addonName == "WeakAurasCompanion" then
+    local count = 0
+    for _, data in pairs(WeakAurasCompanionData) do
+      for _ in pairs(data.slugs) do
+        count = count + 1
+      end
+    end
+    if count > 0 then
+      print("WeakAuras Companion: " .. count .. " update(s) ready to be installed.")
+    end
+  end
+end)
+"#;
+
+fn companion_toc(addon_directory: &Path, flavor: Flavor) -> String {
+    format!(
+        "## Interface: {}
+## Title: WeakAuras Companion
+## Author: Ajour
+## Version: 1.0.0
+## Notes: Keeps your WeakAuras and Plater profiles updated
+## DefaultState: Enabled
+## LoadOnDemand: 0
+## OptionalDeps: WeakAuras, Plater
+
+data.lua
+init.lua
+",
+        interface_version(addon_directory, flavor)
+    )
+}
+
+/// Returns the interface version of the installed WeakAuras or Plater, so the
+/// companion addon isn't flagged as out of date by the game.
+fn interface_version(addon_directory: &Path, flavor: Flavor) -> String {
+    ["WeakAuras", "Plater"]
+        .iter()
+        .filter_map(|addon| {
+            read_to_string(addon_directory.join(addon).join(format!("{}.toc", addon))).ok()
+        })
+        .find_map(|toc| {
+            toc.lines()
+                .find_map(|l| l.strip_prefix("## Interface:"))
+                .map(|v| v.trim().to_string())
+        })
+        .unwrap_or_else(|| match flavor.base_flavor() {
+            Flavor::Classic => "20501".to_string(),
+            Flavor::ClassicEra => "11307".to_string(),
+            _ => "90002".to_string(),
+        })
+}
+
+fn companion_data(updates: &[(&Aura, String)]) -> String {
+    let mut weak_aura_slugs = String::new();
+    let mut plater_slugs = String::new();
+    let mut uids = String::new();
+    let mut ids = String::new();
+
+    for (aura, encoded) in updates {
+        match aura.kind {
+            AuraKind::WeakAura => {
+                weak_aura_slugs.push_str(&slug_data(aura, encoded));
+
+                for uid in aura.uids.iter() {
+                    uids.push_str(&format!(
+                        "      [{}] = {},\n",
+                        lua_string(uid),
+                        lua_string(&aura.slug)
+                    ));
+                }
+
+                for id in aura.ids.iter() {
+                    ids.push_str(&format!(
+                        "      [{}] = {},\n",
+                        lua_string(id),
+                        lua_string(&aura.slug)
+                    ));
+                }
+            }
+            AuraKind::Plater => plater_slugs.push_str(&slug_data(aura, encoded)),
+        }
+    }
+
+    format!(
+        "-- file generated automatically
+WeakAurasCompanionData = {{
+  WeakAuras = {{
+    slugs = {{
+{}    }},
+    uids = {{
+{}    }},
+    ids = {{
+{}    }},
+    stash = {{
+    }},
+  }},
+  Plater = {{
+    slugs = {{
+{}    }},
+    stash = {{
+    }},
+  }},
+}}
+
+-- Older versions of WeakAuras and Plater read the data from here.
+WeakAurasCompanion = WeakAurasCompanionData
+",
+        weak_aura_slugs, uids, ids, plater_slugs
+    )
+}
+
+/// Returns the entry of an update in the `slugs` table of the companion data.
+fn slug_data(aura: &Aura, encoded: &str) -> String {
+    let release = aura.release.as_ref();
+
+    format!(
+        "      [{}] = {{
+        name = {},
+        author = {},
+        encoded = {},
+        wagoVersion = {},
+        wagoSemver = {},
+        versionNote = {},
+      }},
+",
+        lua_string(&aura.slug),
+        lua_string(release.map(|r| r.name.as_str()).unwrap_or(&aura.name)),
+        lua_string(
+            release
+                .and_then(|r| r.username.as_deref())
+                .unwrap_or_default()
+        ),
+        lua_string(encoded.trim()),
+        lua_string(
+            &release
+                .map(|r| r.version)
+                .unwrap_or(aura.version)
+                .to_string()
+        ),
+        lua_string(
+            release
+                .and_then(|r| r.version_string.as_deref())
+                .unwrap_or_default()
+        ),
+        lua_string(
+            release
+                .and_then(|r| r.changelog.as_ref())
+                .and_then(|c| c.text.as_deref())
+                .unwrap_or_default()
+        ),
+    )
+}
+
+/// Quotes `s` as a Lua string.
+fn lua_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\0' => quoted.push_str("\\0"),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aura(version: u32) -> Aura {
+        Aura {
+            kind: AuraKind::WeakAura,
+            slug: "abc".to_string(),
+            name: "My Aura".to_string(),
+            version,
+            semver: None,
+            ids: vec!["My Aura".to_string()],
+            uids: vec![],
+            ignore_updates: false,
+            skipped_version: None,
+            release: None,
+        }
+    }
+
+    fn release(version: u32) -> WagoRelease {
+        WagoRelease {
+            slug: "abc".to_string(),
+            name: "My Aura".to_string(),
+            username: None,
+            version,
+            version_string: None,
+            changelog: None,
+        }
+    }
+
+    #[test]
+    fn test_aura_status() {
+        let mut a = aura(12);
+        assert_eq!(a.status(), AuraStatus::Unknown);
+
+        a.release = Some(release(12));
+        assert_eq!(a.status(), AuraStatus::UpToDate);
+
+        a.release = Some(release(13));
+        assert_eq!(a.status(), AuraStatus::UpdateAvailable);
+
+        a.skipped_version = Some(13);
+        assert_eq!(a.status(), AuraStatus::Skipped);
+
+        a.release = Some(release(14));
+        assert_eq!(a.status(), AuraStatus::UpdateAvailable);
+
+        a.ignore_updates = true;
+        assert_eq!(a.status(), AuraStatus::Ignored);
+    }
+
+    #[test]
+    fn test_slug_from_url() {
+        assert_eq!(
+            slug_from_url("https://wago.io/abc/12"),
+            Some("abc".to_string())
+        );
+        assert_eq!(
+            slug_from_url("https://wago.io/abc"),
+            Some("abc".to_string())
+        );
+        assert_eq!(slug_from_url("https://example.com/abc/12"), None);
+        assert_eq!(slug_from_url("https://wago.io/"), None);
+    }
+
+    #[test]
+    fn test_lua_string() {
+        assert_eq!(lua_string(r#"a "b" \ c"#), r#""a \"b\" \\ c""#);
+        assert_eq!(lua_string("a\nb"), r#""a\nb""#);
+    }
+}
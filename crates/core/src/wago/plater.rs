@@ -0,0 +1,179 @@
+use super::lua::{parse_saved_variables, Table, Value};
+use super::{slug_from_url, Aura, AuraKind};
+use crate::Result;
+use std::fs::read;
+use std::path::Path;
+
+pub(super) const SAVED_VARIABLES: &str = "Plater.lua";
+
+/// Reads the Plater profiles, scripts and mods of `account` which were imported
+/// from wago.io.
+///
+/// A script or mod found in several profiles shows up once, with the oldest
+/// version installed.
+pub fn parse_auras(wtf_directory: &Path, account: &str) -> Result<Vec<Aura>> {
+    let path = wtf_directory
+        .join("Account")
+        .join(account)
+        .join("SavedVariables")
+        .join(SAVED_VARIABLES);
+
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let source = read(&path)?;
+    let variables = parse_saved_variables(&String::from_utf8_lossy(&source))?;
+
+    let profiles = variables
+        .get("PlaterDB")
+        .and_then(Value::as_table)
+        .and_then(|t| t.get("profiles"))
+        .and_then(Value::as_table);
+
+    Ok(profiles.map(auras_from_profiles).unwrap_or_default())
+}
+
+fn auras_from_profiles(profiles: &Table) -> Vec<Aura> {
+    let mut auras: Vec<Aura> = vec![];
+
+    for (name, profile) in profiles.iter() {
+        let profile = match profile.as_table() {
+            Some(profile) => profile,
+            None => continue,
+        };
+
+        if let Some(name) = name.as_str() {
+            add_aura(&mut auras, name, profile);
+        }
+
+        // Scripts and mods are lists, named by their `Name`.
+        for key in ["script_data", "hook_data"].iter() {
+            let entries = profile
+                .get(key)
+                .and_then(Value::as_table)
+                .map(|t| t.entries.as_slice())
+                .unwrap_or_default();
+
+            for (_, entry) in entries {
+                let entry = match entry.as_table() {
+                    Some(entry) => entry,
+                    None => continue,
+                };
+
+                if let Some(name) = entry.get("Name").and_then(Value::as_str) {
+                    add_aura(&mut auras, name, entry);
+                }
+            }
+        }
+    }
+
+    auras.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    auras
+}
+
+/// Adds the profile, script or mod in `table` to `auras` if it was imported
+/// from wago.io.
+fn add_aura(auras: &mut Vec<Aura>, name: &str, table: &Table) {
+    let string = |key: &str| table.get(key).and_then(Value::as_str);
+    let number = |key: &str| table.get(key).and_then(Value::as_f64).map(|n| n as u32);
+
+    let slug = match string("url").and_then(slug_from_url) {
+        Some(slug) => slug,
+        None => return,
+    };
+
+    let version = number("version").unwrap_or_default();
+    let ignore_updates = table
+        .get("ignoreWagoUpdate")
+        .and_then(Value::as_bool)
+        .unwrap_or_default();
+    let skipped_version = number("skipWagoUpdate");
+
+    if let Some(aura) = auras.iter_mut().find(|a| a.slug == slug) {
+        if version < aura.version {
+            aura.version = version;
+            aura.semver = string("semver").map(str::to_string);
+        }
+
+        if !aura.ids.iter().any(|id| id == name) {
+            aura.ids.push(name.to_string());
+        }
+        aura.ignore_updates |= ignore_updates;
+        aura.skipped_version = aura.skipped_version.max(skipped_version);
+    } else {
+        auras.push(Aura {
+            kind: AuraKind::Plater,
+            slug,
+            name: name.to_string(),
+            version,
+            semver: string("semver").map(str::to_string),
+            ids: vec![name.to_string()],
+            uids: vec![],
+            ignore_updates,
+            skipped_version,
+            release: None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auras_from_profiles() {
+        let source = r#"
+PlaterDB = {
+    ["profiles"] = {
+        ["Default"] = {
+            ["script_data"] = {
+                {
+                    ["Name"] = "Cast Glow",
+                    ["url"] = "https://wago.io/script/4",
+                    ["version"] = 4,
+                },
+                {
+                    ["Name"] = "Local Script",
+                },
+            },
+        },
+        ["Imported"] = {
+            ["url"] = "https://wago.io/profile/7",
+            ["version"] = 7,
+            ["semver"] = "1.0.6",
+            ["hook_data"] = {
+                {
+                    ["Name"] = "Cast Glow",
+                    ["url"] = "https://wago.io/script/2",
+                    ["version"] = 2,
+                    ["semver"] = "1.0.1",
+                },
+            },
+        },
+    },
+}
+"#;
+
+        let variables = parse_saved_variables(source).unwrap();
+        let profiles = variables
+            .get("PlaterDB")
+            .and_then(Value::as_table)
+            .and_then(|t| t.get("profiles"))
+            .and_then(Value::as_table)
+            .unwrap();
+
+        let auras = auras_from_profiles(profiles);
+        assert_eq!(auras.len(), 2);
+
+        assert_eq!(auras[0].name, "Cast Glow");
+        assert_eq!(auras[0].slug, "script");
+        assert_eq!(auras[0].version, 2);
+        assert_eq!(auras[0].semver, Some("1.0.1".to_string()));
+
+        assert_eq!(auras[1].name, "Imported");
+        assert_eq!(auras[1].slug, "profile");
+        assert_eq!(auras[1].version, 7);
+        assert_eq!(auras[1].kind, AuraKind::Plater);
+    }
+}
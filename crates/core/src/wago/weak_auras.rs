@@ -0,0 +1,146 @@
+use super::lua::{parse_saved_variables, Table, Value};
+use super::{slug_from_url, Aura, AuraKind};
+use crate::Result;
+use std::fs::read;
+use std::path::Path;
+
+pub(super) const SAVED_VARIABLES: &str = "WeakAuras.lua";
+
+/// Reads the auras of `account` which were imported from wago.io.
+///
+/// An aura imported as a group shows up once, with the names of its children
+/// in `ids`.
+pub fn parse_auras(wtf_directory: &Path, account: &str) -> Result<Vec<Aura>> {
+    let path = wtf_directory
+        .join("Account")
+        .join(account)
+        .join("SavedVariables")
+        .join(SAVED_VARIABLES);
+
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let source = read(&path)?;
+    let variables = parse_saved_variables(&String::from_utf8_lossy(&source))?;
+
+    let displays = variables
+        .get("WeakAurasSaved")
+        .and_then(Value::as_table)
+        .and_then(|t| t.get("displays"))
+        .and_then(Value::as_table);
+
+    Ok(displays.map(auras_from_displays).unwrap_or_default())
+}
+
+fn auras_from_displays(displays: &Table) -> Vec<Aura> {
+    let mut auras: Vec<Aura> = vec![];
+
+    for (_, display) in displays.iter() {
+        let display = match display.as_table() {
+            Some(display) => display,
+            None => continue,
+        };
+
+        let string = |key: &str| display.get(key).and_then(Value::as_str);
+        let number = |key: &str| display.get(key).and_then(Value::as_f64).map(|n| n as u32);
+
+        let (slug, id) = match (string("url").and_then(slug_from_url), string("id")) {
+            (Some(slug), Some(id)) => (slug, id.to_string()),
+            _ => continue,
+        };
+
+        let version = number("version").unwrap_or_default();
+        let uid = string("uid").map(str::to_string);
+        let ignore_updates = display
+            .get("ignoreWagoUpdate")
+            .and_then(Value::as_bool)
+            .unwrap_or_default();
+        let skipped_version = number("skipWagoUpdate");
+
+        // Children of a group share the url of the group.
+        if let Some(aura) = auras.iter_mut().find(|a| a.slug == slug) {
+            // The group itself has no parent and names the aura.
+            if string("parent").is_none() {
+                aura.name = id.clone();
+                aura.semver = string("semver").map(str::to_string);
+            }
+
+            aura.ids.push(id);
+            aura.uids.extend(uid);
+            aura.version = aura.version.max(version);
+            aura.ignore_updates |= ignore_updates;
+            aura.skipped_version = aura.skipped_version.max(skipped_version);
+        } else {
+            auras.push(Aura {
+                kind: AuraKind::WeakAura,
+                slug,
+                name: id.clone(),
+                version,
+                semver: string("semver").map(str::to_string),
+                ids: vec![id],
+                uids: uid.into_iter().collect(),
+                ignore_updates,
+                skipped_version,
+                release: None,
+            });
+        }
+    }
+
+    auras.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    auras
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auras_from_displays() {
+        let source = r#"
+WeakAurasSaved = {
+    ["displays"] = {
+        ["Child"] = {
+            ["id"] = "Child",
+            ["uid"] = "b",
+            ["parent"] = "Group",
+            ["url"] = "https://wago.io/abc/3",
+            ["version"] = 3,
+        },
+        ["Group"] = {
+            ["id"] = "Group",
+            ["uid"] = "a",
+            ["url"] = "https://wago.io/abc/3",
+            ["version"] = 3,
+            ["semver"] = "1.0.2",
+            ["skipWagoUpdate"] = 4,
+        },
+        ["Local"] = {
+            ["id"] = "Local",
+            ["uid"] = "c",
+        },
+    },
+}
+"#;
+
+        let variables = parse_saved_variables(source).unwrap();
+        let displays = variables
+            .get("WeakAurasSaved")
+            .and_then(Value::as_table)
+            .and_then(|t| t.get("displays"))
+            .and_then(Value::as_table)
+            .unwrap();
+
+        let auras = auras_from_displays(displays);
+        assert_eq!(auras.len(), 1);
+
+        let aura = &auras[0];
+        assert_eq!(aura.slug, "abc");
+        assert_eq!(aura.name, "Group");
+        assert_eq!(aura.version, 3);
+        assert_eq!(aura.ids, vec!["Child".to_string(), "Group".to_string()]);
+        assert_eq!(aura.uids, vec!["b".to_string(), "a".to_string()]);
+        assert_eq!(aura.skipped_version, Some(4));
+        assert_eq!(aura.semver, Some("1.0.2".to_string()));
+    }
+}
@@ -0,0 +1,167 @@
+use crate::addon::Addon;
+use crate::config::{Flavor, Webhook, WebhookEvent, WebhookFormat};
+use crate::error::ClientError;
+use crate::network::{is_offline, proxy};
+use crate::Result;
+
+use isahc::prelude::*;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Discord rejects messages longer than this.
+const MAX_DISCORD_LENGTH: usize = 2000;
+
+/// An addon as it's posted to the webhook.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookAddon {
+    pub title: String,
+    pub flavor: Option<Flavor>,
+    pub old_version: Option<String>,
+    pub new_version: Option<String>,
+}
+
+impl WebhookAddon {
+    /// Versions are the installed version and the one of the release channel
+    /// of the addon.
+    pub fn new(addon: &Addon, flavor: Flavor) -> Self {
+        WebhookAddon {
+            title: addon.title().to_string(),
+            flavor: Some(flavor),
+            old_version: addon.version().map(str::to_string),
+            new_version: addon.relevant_release_package().map(|p| p.version.clone()),
+        }
+    }
+
+    /// An addon of which only the title is known.
+    pub fn titled(title: impl Into<String>) -> Self {
+        WebhookAddon {
+            title: title.into(),
+            flavor: None,
+            old_version: None,
+            new_version: None,
+        }
+    }
+}
+
+/// Posts `event` to the webhook, if it's subscribed to it and there are `addons`.
+///
+/// Unlike the requests to the repositories the post isn't cached, so a webhook
+/// which couldn't be reached is never reported as sent.
+pub async fn send_webhook(
+    webhook: &Webhook,
+    event: WebhookEvent,
+    addons: &[WebhookAddon],
+) -> Result<()> {
+    let url = match webhook.url.as_deref() {
+        Some(url) if webhook.is_subscribed(event) && !addons.is_empty() => url,
+        _ => return Ok(()),
+    };
+
+    if is_offline() {
+        return Err(ClientError::Offline);
+    }
+
+    let mut request = Request::builder()
+        .method("POST")
+        .uri(url)
+        .header("content-type", "application/json")
+        .timeout(Duration::from_secs(30));
+
+    if let Some(proxy) = proxy() {
+        request = request.proxy(proxy);
+    }
+
+    let body = serde_json::to_vec(&payload(webhook.format, event, addons))?;
+    let response = request.body(body)?.send_async().await?;
+
+    if !response.status().is_success() {
+        return Err(ClientError::Custom(format!(
+            "Webhook responded {}",
+            response.status()
+        )));
+    }
+
+    Ok(())
+}
+
+fn payload(format: WebhookFormat, event: WebhookEvent, addons: &[WebhookAddon]) -> Value {
+    match format {
+        WebhookFormat::Json => json!({
+            "event": event.to_string(),
+            "addons": addons,
+        }),
+        WebhookFormat::Discord => {
+            let heading = match event {
+                WebhookEvent::UpdatesFound => "addon updates are available",
+                WebhookEvent::UpdatesApplied => "addons were updated",
+            };
+
+            let mut content = format!("**{} {}**", addons.len(), heading);
+
+            for addon in addons {
+                let mut line = format!("\n- {}", addon.title);
+
+                if let Some(new_version) = &addon.new_version {
+                    line.push_str(&format!(
+                        " {} -> {}",
+                        addon.old_version.as_deref().unwrap_or("?"),
+                        new_version
+                    ));
+                }
+
+                if let Some(flavor) = addon.flavor {
+                    line.push_str(&format!(" ({})", flavor));
+                }
+
+                // Leaves room for the ellipsis.
+                if content.len() + line.len() > MAX_DISCORD_LENGTH - 20 {
+                    content.push_str("\n...");
+                    break;
+                }
+
+                content.push_str(&line);
+            }
+
+            json!({
+                "username": "Ajour",
+                "content": content,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload() {
+        let addons = vec![WebhookAddon {
+            title: "Details!".to_string(),
+            flavor: Some(Flavor::Retail),
+            old_version: Some("1.0".to_string()),
+            new_version: Some("1.1".to_string()),
+        }];
+
+        let json = payload(WebhookFormat::Json, WebhookEvent::UpdatesFound, &addons);
+        assert_eq!(json["event"], "updates-found");
+        assert_eq!(json["addons"][0]["new_version"], "1.1");
+
+        let discord = payload(
+            WebhookFormat::Discord,
+            WebhookEvent::UpdatesApplied,
+            &addons,
+        );
+        assert_eq!(
+            discord["content"],
+            "**1 addons were updated**\n- Details! 1.0 -> 1.1 (Retail)"
+        );
+
+        let many = vec![WebhookAddon::titled("x".repeat(100)); 50];
+        let discord = payload(WebhookFormat::Discord, WebhookEvent::UpdatesApplied, &many);
+        let content = discord["content"].as_str().unwrap();
+        assert!(content.len() <= MAX_DISCORD_LENGTH);
+        assert!(content.ends_with("\n..."));
+    }
+}
@@ -1,12 +1,18 @@
 use crate::VERSION;
 
+use ajour_core::backup::BackupContent;
+use ajour_core::config::{Flavor, LogLevel};
+use ajour_core::localization::{localized_string, localized_string_with};
+
 use structopt::{
-    clap::{self, AppSettings},
+    clap::{self, AppSettings, Shell},
     StructOpt,
 };
 
 use std::env;
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
 
 pub fn get_opts() -> Result<Opts, clap::Error> {
     let args = env::args_os();
@@ -14,6 +20,12 @@ pub fn get_opts() -> Result<Opts, clap::Error> {
     Opts::from_iter_safe(args)
 }
 
+/// Help of an argument or command in the language of Ajour. Clap keeps borrowing
+/// it until the process exits, so it's leaked.
+fn help(key: &str) -> &'static str {
+    Box::leak(localized_string(key).into_boxed_str())
+}
+
 #[allow(unused_variables)]
 pub fn validate_opts_or_exit(
     opts_result: Result<Opts, clap::Error>,
@@ -67,16 +79,499 @@ pub fn validate_opts_or_exit(
             author = env!("CARGO_PKG_AUTHORS"),
             setting = AppSettings::DisableHelpSubcommand)]
 pub struct Opts {
-    #[structopt(long = "data", help = "Path to a custom data directory for the app")]
+    #[structopt(
+        long = "data",
+        env = "AJOUR_DATA_DIR",
+        help = help("cli-data-directory")
+    )]
     pub data_directory: Option<PathBuf>,
-    #[structopt(long = "aa", help = "Enable / Disable Anti-aliasing (true / false)")]
+    #[structopt(
+        long,
+        help = help("cli-portable")
+    )]
+    pub portable: bool,
+    #[structopt(long, help = help("cli-profile"))]
+    pub profile: Option<String>,
+    #[structopt(long = "aa", help = help("cli-antialiasing"))]
     pub antialiasing: Option<bool>,
+    #[structopt(
+        long,
+        default_value = "text",
+        help = help("cli-output")
+    )]
+    pub output: OutputFormat,
+    #[structopt(
+        long,
+        env = "AJOUR_LOG_LEVEL",
+        help = help("cli-log-level")
+    )]
+    pub log_level: Option<LogLevel>,
+    #[structopt(
+        short,
+        long,
+        help = help("cli-quiet")
+    )]
+    pub quiet: bool,
     #[structopt(subcommand)]
     pub command: Option<Command>,
 }
 
+/// How commands print their results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(localized_string_with(
+                "cli-invalid-output",
+                &[("value", &s)],
+            )),
+        }
+    }
+}
+
+/// Time between runs of `daemon`, eg. `30m`, `6h` or `1d`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interval(pub Duration);
+
+impl FromStr for Interval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || localized_string_with("cli-invalid-interval", &[("value", &s)]);
+
+        let s = s.trim().to_lowercase();
+        let unit = s.chars().last().ok_or_else(invalid)?;
+        let amount: u64 = s[..s.len() - unit.len_utf8()]
+            .parse()
+            .map_err(|_| invalid())?;
+
+        let seconds = match unit {
+            'm' => amount * 60,
+            'h' => amount * 60 * 60,
+            'd' => amount * 60 * 60 * 24,
+            _ => return Err(invalid()),
+        };
+
+        // Checking more often than this only puts load on the repositories.
+        if seconds < 10 * 60 {
+            return Err(localized_string("cli-interval-too-short"));
+        }
+
+        Ok(Interval(Duration::from_secs(seconds)))
+    }
+}
+
 #[derive(Debug, StructOpt)]
 pub enum Command {
-    /// Update all addons from the command line then exit
-    Update,
+    /// Create, list and restore backups of the AddOns and WTF folders then exit
+    #[structopt(about = help("cli-backup"))]
+    Backup {
+        #[structopt(subcommand)]
+        action: BackupAction,
+    },
+    /// Check for addon updates without installing them then exit
+    #[structopt(about = help("cli-check"))]
+    Check {
+        #[structopt(long, help = help("cli-check-notify"))]
+        notify: bool,
+    },
+    /// Update all addons, or a single one, from the command line then exit
+    #[structopt(about = help("cli-update"))]
+    Update {
+        #[structopt(
+            help = help("cli-update-addon")
+        )]
+        addon: Option<String>,
+        #[structopt(
+            long,
+            requires = "addon",
+            help = help("cli-update-flavor")
+        )]
+        flavor: Option<Flavor>,
+        #[structopt(
+            long,
+            default_value = "4",
+            help = help("cli-concurrency")
+        )]
+        concurrency: usize,
+        #[structopt(
+            long,
+            help = help("cli-update-wait")
+        )]
+        wait: bool,
+        #[structopt(
+            long,
+            help = help("cli-update-dry-run")
+        )]
+        dry_run: bool,
+    },
+    /// Keep running, and update all addons every interval or on the schedule set in Ajour
+    #[structopt(about = help("cli-daemon"))]
+    Daemon {
+        #[structopt(
+            long,
+            help = help("cli-daemon-interval")
+        )]
+        interval: Option<Interval>,
+        #[structopt(
+            long,
+            default_value = "4",
+            help = help("cli-concurrency")
+        )]
+        concurrency: usize,
+        #[structopt(long, help = help("cli-daemon-notify"))]
+        notify: bool,
+    },
+    /// Check the setup Ajour depends on, eg. that the addon directories are writable, then exit
+    #[structopt(about = help("cli-doctor"))]
+    Doctor {
+        #[structopt(
+            long,
+            help = help("cli-doctor-bundle")
+        )]
+        bundle: bool,
+        #[structopt(
+            long,
+            requires = "bundle",
+            help = help("cli-doctor-destination")
+        )]
+        destination: Option<PathBuf>,
+    },
+    /// Export the installed addons to a file, which can be imported on another machine, then exit
+    #[structopt(about = help("cli-export"))]
+    Export {
+        #[structopt(long, help = help("cli-export-file"))]
+        file: PathBuf,
+    },
+    /// Install the missing addons of an export, or import addons installed by WowUp or the CurseForge client, then exit
+    #[structopt(about = help("cli-import"))]
+    Import {
+        #[structopt(
+            long,
+            required_unless = "wowup",
+            help = help("cli-import-file")
+        )]
+        file: Option<PathBuf>,
+        #[structopt(
+            long,
+            help = help("cli-import-wowup")
+        )]
+        wowup: bool,
+        #[structopt(
+            long,
+            conflicts_with = "wowup",
+            help = help("cli-import-curseforge")
+        )]
+        curseforge: bool,
+    },
+    /// Install an addon from the catalog by name, slug or id, or from a zip, then exit
+    #[structopt(about = help("cli-install"))]
+    Install {
+        #[structopt(
+            required_unless = "url",
+            help = help("cli-install-addon")
+        )]
+        addon: Option<String>,
+        #[structopt(
+            long,
+            conflicts_with = "addon",
+            help = help("cli-install-url")
+        )]
+        url: Option<String>,
+        #[structopt(
+            long,
+            default_value = "retail",
+            help = help("cli-install-flavor")
+        )]
+        flavor: Flavor,
+        #[structopt(
+            long,
+            help = help("cli-install-no-deps")
+        )]
+        no_deps: bool,
+    },
+    /// List installed addons and whether they have an update available then exit
+    #[structopt(about = help("cli-list"))]
+    List {
+        #[structopt(
+            long = "flavor",
+            help = help("cli-list-flavors")
+        )]
+        flavors: Vec<Flavor>,
+        #[structopt(long, help = help("cli-list-outdated"))]
+        outdated: bool,
+        #[structopt(
+            long,
+            help = help("cli-list-incompatible")
+        )]
+        incompatible: bool,
+        #[structopt(
+            long,
+            help = help("cli-list-abandoned")
+        )]
+        abandoned: bool,
+    },
+    /// Pin an addon to its installed version, so it's skipped when updating, then exit
+    #[structopt(about = help("cli-pin"))]
+    Pin {
+        #[structopt(help = help("cli-installed-addon"))]
+        addon: String,
+        #[structopt(
+            long,
+            default_value = "retail",
+            help = help("cli-installed-flavor")
+        )]
+        flavor: Flavor,
+    },
+    /// Unpin an addon pinned with `pin` then exit
+    #[structopt(about = help("cli-unpin"))]
+    Unpin {
+        #[structopt(help = help("cli-installed-addon"))]
+        addon: String,
+        #[structopt(
+            long,
+            default_value = "retail",
+            help = help("cli-installed-flavor")
+        )]
+        flavor: Flavor,
+    },
+    /// Roll back an addon to the previously installed version then exit
+    #[structopt(about = help("cli-rollback"))]
+    Rollback {
+        #[structopt(help = help("cli-installed-addon"))]
+        addon: String,
+        #[structopt(
+            long,
+            default_value = "retail",
+            help = help("cli-installed-flavor")
+        )]
+        flavor: Flavor,
+    },
+    /// Verify installed addons against their release packages then exit
+    #[structopt(about = help("cli-verify"))]
+    Verify {
+        #[structopt(
+            long = "flavor",
+            help = help("cli-verify-flavors")
+        )]
+        flavors: Vec<Flavor>,
+        #[structopt(long, help = help("cli-verify-repair"))]
+        repair: bool,
+    },
+    /// Open an install link in Ajour, which is started if it isn't running
+    #[structopt(about = help("cli-open"))]
+    Open {
+        #[structopt(help = help("cli-open-link"))]
+        link: String,
+    },
+    /// Make Ajour open or stop opening install links from the browser then exit
+    #[structopt(about = help("cli-links"))]
+    Links {
+        #[structopt(subcommand)]
+        action: LinksAction,
+    },
+    /// Sync the config and addons with a WebDAV server or a GitHub gist then exit
+    #[structopt(about = help("cli-sync"))]
+    Sync {
+        #[structopt(subcommand)]
+        action: SyncAction,
+    },
+    /// Browse and update the installed addons in the terminal
+    #[structopt(about = help("cli-tui"))]
+    Tui,
+    /// Print the completions of a shell for all commands then exit
+    #[structopt(about = help("cli-completions"))]
+    Completions {
+        #[structopt(
+            possible_values = &Shell::variants(),
+            case_insensitive = true,
+            help = help("cli-completions-shell")
+        )]
+        shell: Shell,
+    },
+    /// Print a man page of Ajour and its commands then exit
+    #[structopt(about = help("cli-manpage"))]
+    Manpage,
+    /// Show, set or detect the World of Warcraft directory then exit
+    #[structopt(about = help("cli-path"))]
+    Path {
+        #[structopt(subcommand)]
+        action: PathAction,
+    },
+    /// Show or change the options of the config then exit
+    #[structopt(about = help("cli-config"))]
+    Config {
+        #[structopt(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+pub enum ConfigAction {
+    /// Print the value of an option
+    #[structopt(about = help("cli-config-get"))]
+    Get {
+        #[structopt(help = help("cli-config-key"))]
+        key: String,
+    },
+    /// Set an option, an empty value unsets it
+    #[structopt(about = help("cli-config-set"))]
+    Set {
+        #[structopt(help = help("cli-config-key"))]
+        key: String,
+        #[structopt(
+            help = help("cli-config-set-value")
+        )]
+        value: String,
+    },
+    /// Print the options which can be set, along with their values
+    #[structopt(about = help("cli-config-list"))]
+    List,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum PathAction {
+    /// Print the World of Warcraft directory in use
+    #[structopt(about = help("cli-path-get"))]
+    Get,
+    /// Set the World of Warcraft directory
+    #[structopt(about = help("cli-path-set"))]
+    Set {
+        #[structopt(help = help("cli-path-set-directory"))]
+        directory: PathBuf,
+    },
+    /// Print the World of Warcraft directories found in the usual install locations
+    #[structopt(about = help("cli-path-detect"))]
+    Detect {
+        #[structopt(long, help = help("cli-path-detect-set"))]
+        set: bool,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+pub enum SyncAction {
+    /// Choose the remote to sync with
+    #[structopt(about = help("cli-sync-setup"))]
+    Setup {
+        #[structopt(
+            help = help("cli-sync-setup-remote")
+        )]
+        remote: String,
+    },
+    /// Push the config and addons of this machine to the remote
+    #[structopt(about = help("cli-sync-push"))]
+    Push {
+        #[structopt(
+            long,
+            help = help("cli-sync-push-force")
+        )]
+        force: bool,
+    },
+    /// Pull the config from the remote, and install the addons missing here
+    #[structopt(about = help("cli-sync-pull"))]
+    Pull {
+        #[structopt(
+            long,
+            help = help("cli-sync-pull-force")
+        )]
+        force: bool,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+pub enum LinksAction {
+    /// Open ajour:// links with Ajour
+    #[structopt(about = help("cli-links-register"))]
+    Register {
+        #[structopt(long, help = help("cli-links-register-curseforge"))]
+        curseforge: bool,
+    },
+    /// Stop opening ajour:// and curseforge:// links with Ajour
+    #[structopt(about = help("cli-links-unregister"))]
+    Unregister,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum BackupAction {
+    /// Backup AddOns and WTF folders, or the folders passed with --include, then exit
+    #[structopt(about = help("cli-backup-create"))]
+    Create {
+        #[structopt(
+            help = help("cli-backup-destination")
+        )]
+        destination: Option<PathBuf>,
+        #[structopt(
+            long = "flavor",
+            help = help("cli-backup-flavors")
+        )]
+        flavors: Vec<Flavor>,
+        #[structopt(
+            long,
+            use_delimiter = true,
+            help = help("cli-backup-include")
+        )]
+        include: Vec<BackupContent>,
+    },
+    /// List the backups in the backup directory chosen in Ajour then exit
+    #[structopt(about = help("cli-backup-list"))]
+    List,
+    /// Restore AddOns and WTF folders of a flavor from a backup then exit
+    #[structopt(about = help("cli-backup-restore"))]
+    Restore {
+        #[structopt(
+            help = help("cli-backup-restore-backup")
+        )]
+        backup: String,
+        #[structopt(
+            long,
+            default_value = "retail",
+            help = help("cli-backup-restore-flavor")
+        )]
+        flavor: Flavor,
+        #[structopt(
+            long,
+            use_delimiter = true,
+            help = help("cli-backup-restore-include")
+        )]
+        include: Vec<BackupContent>,
+        #[structopt(long, help = help("cli-backup-restore-yes"))]
+        yes: bool,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_subcommands() {
+        let opts = Opts::from_iter_safe(&["ajour", "backup", "list"]).unwrap();
+        assert!(matches!(
+            opts.command,
+            Some(Command::Backup {
+                action: BackupAction::List
+            })
+        ));
+
+        let opts = Opts::from_iter_safe(&["ajour", "backup", "create", "list"]).unwrap();
+        match opts.command {
+            Some(Command::Backup {
+                action: BackupAction::Create { destination, .. },
+            }) => assert_eq!(destination, Some(PathBuf::from("list"))),
+            command => panic!("expected backup create, got {:?}", command),
+        }
+
+        // A directory is only taken by `backup create`.
+        assert!(Opts::from_iter_safe(&["ajour", "backup", "/srv/backups"]).is_err());
+        assert!(Opts::from_iter_safe(&["ajour", "backup"]).is_err());
+    }
 }
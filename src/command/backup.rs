@@ -0,0 +1,313 @@
+use super::output::{print_json, print_result};
+use crate::cli::OutputFormat;
+
+use ajour_core::backup::{
+    backup_folders, flavor_backup_folders, list_backups as list_archives, prune_backups, Archive,
+    BackupContent,
+};
+use ajour_core::config::{load_config, Flavor};
+use ajour_core::error::ClientError;
+use ajour_core::localization::{localized_string, localized_string_with};
+use ajour_core::Result;
+
+use async_std::task;
+
+use chrono::NaiveDateTime;
+
+use serde::Serialize;
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Format of the ids of backups, which is the time they were made as of.
+const BACKUP_ID_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+
+/// A backup as printed by `backup list`.
+#[derive(Debug, Serialize)]
+struct ListedBackup {
+    id: String,
+    path: PathBuf,
+    as_of: NaiveDateTime,
+    size: Option<u64>,
+}
+
+impl From<Archive> for ListedBackup {
+    fn from(archive: Archive) -> Self {
+        ListedBackup {
+            id: archive.as_of.format(BACKUP_ID_FORMAT).to_string(),
+            size: archive.path.metadata().map(|m| m.len()).ok(),
+            path: archive.path,
+            as_of: archive.as_of,
+        }
+    }
+}
+
+/// Backs up the `Interface/AddOns` and `WTF` folders of the given flavors, or the
+/// folders of `include`
+///
+/// If no flavors are given, all flavors are backed up. If no destination is given,
+/// the backup directory chosen in Ajour is used and old backups are pruned according
+/// to the retention policy.
+pub fn backup(
+    destination: Option<PathBuf>,
+    flavors: Vec<Flavor>,
+    include: Vec<BackupContent>,
+    output: OutputFormat,
+) -> Result<()> {
+    task::block_on(async {
+        let config = load_config().await?;
+
+        if config.wow.directory.is_none() {
+            return Err(ClientError::NoWowDirectory);
+        }
+
+        // Retention policy only applies to the backup directory chosen in Ajour
+        let is_backup_directory = destination.is_none() || destination == config.backup_directory;
+
+        let destination = destination
+            .or_else(|| config.backup_directory.clone())
+            .ok_or_else(|| {
+                ClientError::Custom(localized_string("cli-no-backup-directory-pass-destination"))
+            })?;
+
+        if !destination.is_dir() {
+            return Err(ClientError::Custom(localized_string_with(
+                "cli-backup-destination-not-directory",
+                &[("destination", &format!("{:?}", destination))],
+            )));
+        }
+
+        let flavors = if flavors.is_empty() {
+            Flavor::ALL.to_vec()
+        } else {
+            flavors
+        };
+
+        let include = or_default_content(include);
+        let src_folders = flavor_backup_folders(&config, &flavors, &include);
+
+        if src_folders.is_empty() {
+            return Err(ClientError::Custom(localized_string_with(
+                "cli-no-folders-to-backup",
+                &[("folders", &content_names(&include))],
+            )));
+        }
+
+        log::info!(
+            "{}",
+            localized_string_with(
+                "cli-backing-up",
+                &[
+                    ("count", &src_folders.len()),
+                    ("destination", &format!("{:?}", &destination))
+                ]
+            )
+        );
+
+        let as_of = backup_folders(src_folders, destination.clone()).await?;
+
+        let pruned = if is_backup_directory {
+            prune_backups(&destination, &config.backups)?
+        } else {
+            vec![]
+        };
+
+        log::info!(
+            "{}",
+            localized_string_with(
+                "cli-backup-completed",
+                &[("time", &as_of.format("%Y-%m-%d %H:%M:%S"))]
+            )
+        );
+
+        print_json(
+            output,
+            &serde_json::json!({
+                "id": as_of.format(BACKUP_ID_FORMAT).to_string(),
+                "destination": destination,
+                "as_of": as_of,
+                "pruned": pruned,
+            }),
+        )?;
+
+        Result::Ok(())
+    })
+}
+
+/// Lists the backups in the backup directory chosen in Ajour, newest first
+pub fn list_backups(output: OutputFormat) -> Result<()> {
+    task::block_on(async {
+        let config = load_config().await?;
+
+        let backup_directory = config
+            .backup_directory
+            .as_ref()
+            .ok_or_else(no_backup_directory)?;
+
+        let backups = list_archives(backup_directory)
+            .into_iter()
+            .map(ListedBackup::from)
+            .collect::<Vec<_>>();
+
+        if backups.is_empty() {
+            print_result(
+                output,
+                &localized_string_with(
+                    "cli-no-backups",
+                    &[("directory", &format!("{:?}", backup_directory))],
+                ),
+            );
+        }
+
+        for backup in backups.iter() {
+            print_result(
+                output,
+                &format!(
+                    "{}  {}  {:.1} MB",
+                    backup.id,
+                    backup.as_of.format("%Y-%m-%d %H:%M:%S"),
+                    backup.size.unwrap_or_default() as f64 / (1024.0 * 1024.0)
+                ),
+            );
+        }
+
+        print_json(output, &backups)
+    })
+}
+
+/// Restores the `Interface/AddOns` and `WTF` folders of a flavor, or the folders
+/// of `include`, from a backup
+///
+/// `backup` is the id of a backup in the backup directory chosen in Ajour, or the
+/// path to a backup archive. Unless `yes` is passed, the user is asked to confirm
+/// before the folders are replaced. A backup of the current folders is saved next
+/// to the archive first.
+pub fn restore_backup(
+    backup: String,
+    flavor: Flavor,
+    include: Vec<BackupContent>,
+    yes: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    task::block_on(async {
+        let config = load_config().await?;
+
+        if config.wow.directory.is_none() {
+            return Err(ClientError::NoWowDirectory);
+        }
+
+        let file = find_backup(&backup, config.backup_directory.as_deref())?;
+        let include = or_default_content(include);
+
+        // The prompt would end up in the json output
+        if !yes && output == OutputFormat::Json {
+            return Err(ClientError::Custom(localized_string(
+                "cli-restore-needs-yes",
+            )));
+        }
+
+        if !yes {
+            print!(
+                "{} ",
+                localized_string_with(
+                    "cli-restore-prompt",
+                    &[
+                        ("flavor", &flavor),
+                        ("folders", &content_names(&include)),
+                        ("file", &format!("{:?}", file))
+                    ]
+                )
+            );
+            io::stdout().flush()?;
+
+            let mut answer = String::new();
+            io::stdin().read_line(&mut answer)?;
+
+            if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+                log::info!("{}", localized_string("cli-restore-cancelled"));
+                return Ok(());
+            }
+        }
+
+        log::info!(
+            "{}",
+            localized_string_with(
+                "cli-restoring",
+                &[("flavor", &flavor), ("file", &format!("{:?}", &file))]
+            )
+        );
+
+        let safety_backup =
+            ajour_core::backup::restore_backup(file.clone(), flavor, include, config).await?;
+
+        if let Some(as_of) = safety_backup {
+            log::info!(
+                "{}",
+                localized_string_with(
+                    "cli-previous-folders-backed-up",
+                    &[("time", &as_of.format("%Y-%m-%d %H:%M:%S"))]
+                )
+            );
+        }
+
+        log::info!("{}", localized_string("cli-restore-completed"));
+
+        print_json(
+            output,
+            &serde_json::json!({
+                "archive": file,
+                "flavor": flavor,
+                "safety_backup": safety_backup,
+            }),
+        )?;
+
+        Result::Ok(())
+    })
+}
+
+/// Finds the archive of `backup`, which is either the id of a backup in
+/// `backup_directory` or the path to an archive.
+fn find_backup(backup: &str, backup_directory: Option<&Path>) -> Result<PathBuf> {
+    let path = PathBuf::from(backup);
+    if path.is_file() {
+        return Ok(path);
+    }
+
+    let backup_directory = backup_directory.ok_or_else(no_backup_directory)?;
+
+    list_archives(backup_directory)
+        .into_iter()
+        .find(|archive| archive.as_of.format(BACKUP_ID_FORMAT).to_string() == backup)
+        .map(|archive| archive.path)
+        .ok_or_else(|| {
+            ClientError::Custom(localized_string_with(
+                "cli-backup-not-found",
+                &[
+                    ("backup", &backup),
+                    ("directory", &format!("{:?}", backup_directory)),
+                ],
+            ))
+        })
+}
+
+/// Returns the AddOns and WTF folders if no folders were chosen.
+fn or_default_content(include: Vec<BackupContent>) -> Vec<BackupContent> {
+    if include.is_empty() {
+        BackupContent::DEFAULT.to_vec()
+    } else {
+        include
+    }
+}
+
+/// Names of the folders of `include`, eg. "addons, wtf".
+fn content_names(include: &[BackupContent]) -> String {
+    include
+        .iter()
+        .map(BackupContent::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn no_backup_directory() -> ClientError {
+    ClientError::Custom(localized_string("cli-no-backup-directory"))
+}
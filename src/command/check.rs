@@ -0,0 +1,84 @@
+use super::output::{print_json, print_result, set_exit_code, AddonResult, ExitCode, Outcome};
+use crate::cli::OutputFormat;
+use crate::log_error;
+use crate::notification::notify_updates;
+
+use ajour_core::config::{load_config, WebhookEvent};
+use ajour_core::error::ClientError;
+use ajour_core::localization::{localized_string, localized_string_with};
+use ajour_core::update::check_for_updates;
+use ajour_core::webhook::{send_webhook, WebhookAddon};
+use ajour_core::Result;
+
+use async_std::sync::{Arc, Mutex};
+use async_std::task;
+
+/// Checks all flavors for addon updates without installing them
+///
+/// If `notify` is set, a desktop notification is shown when updates are
+/// available. Clicking it opens Ajour.
+pub fn check(notify: bool, output: OutputFormat) -> Result<()> {
+    log::info!("{}", localized_string("cli-checking-for-updates"));
+
+    task::block_on(async {
+        let config = load_config().await?;
+
+        if config.wow.directory.is_none() {
+            return Err(ClientError::NoWowDirectory);
+        }
+
+        // Fingerprint cache will be fetched during `read_addon_directory`
+        let fingerprint_collection: Arc<Mutex<_>> = Default::default();
+
+        let updates =
+            check_for_updates(fingerprint_collection, &config, &config.managed_flavors()).await?;
+
+        print_result(
+            output,
+            &localized_string_with("cli-addons-updatable", &[("count", &updates.len())]),
+        );
+
+        for (flavor, addon) in updates.iter() {
+            print_result(
+                output,
+                &format!(
+                    "\t{} - {}, {} -> {}",
+                    &addon.primary_folder_id,
+                    flavor,
+                    addon.version().unwrap_or_default(),
+                    addon
+                        .relevant_release_package()
+                        .map(|p| p.version.clone())
+                        .unwrap_or_default()
+                ),
+            );
+        }
+
+        let addon_results = updates
+            .iter()
+            .map(|(flavor, addon)| AddonResult::new(addon, *flavor, Outcome::UpdateAvailable))
+            .collect::<Vec<_>>();
+
+        print_json(output, &addon_results)?;
+
+        if !updates.is_empty() {
+            set_exit_code(ExitCode::UpdatesAvailable);
+        }
+
+        if notify {
+            notify_updates(&updates, true);
+        }
+
+        let webhook_addons = updates
+            .iter()
+            .map(|(flavor, addon)| WebhookAddon::new(addon, *flavor))
+            .collect::<Vec<_>>();
+        if let Err(e) =
+            send_webhook(&config.webhook, WebhookEvent::UpdatesFound, &webhook_addons).await
+        {
+            log_error(&e);
+        }
+
+        Result::Ok(())
+    })
+}
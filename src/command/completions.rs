@@ -0,0 +1,118 @@
+use crate::cli::Opts;
+use crate::VERSION;
+
+use ajour_core::Result;
+
+use structopt::clap::{self, Shell};
+use structopt::StructOpt;
+
+use std::io::{self, Write};
+
+/// Name of the binary, which completions and the man page are written for.
+const BIN_NAME: &str = "ajour";
+
+/// Prints the completions of `shell` for all commands to stdout
+pub fn completions(shell: Shell) -> Result<()> {
+    Opts::clap().gen_completions_to(BIN_NAME, shell, &mut io::stdout());
+
+    Ok(())
+}
+
+/// Prints a man page to stdout, made up of the help of Ajour and of each command
+pub fn manpage() -> Result<()> {
+    let mut page = String::new();
+
+    page.push_str(&format!(
+        ".TH AJOUR 1 \"\" \"Ajour {}\" \"User Commands\"\n",
+        VERSION
+    ));
+    page.push_str(".SH NAME\n");
+    page.push_str(&format!(
+        "{} \\- {}\n",
+        BIN_NAME,
+        escape(env!("CARGO_PKG_DESCRIPTION"))
+    ));
+    page.push_str(".SH DESCRIPTION\n");
+    page.push_str(&preformatted(&help(&[])));
+
+    let commands = subcommands(&[]);
+    if !commands.is_empty() {
+        page.push_str(".SH COMMANDS\n");
+    }
+
+    for command in commands {
+        page.push_str(&format!(".SS {} {}\n", BIN_NAME, command.join(" ")));
+        page.push_str(&preformatted(&help(&command)));
+    }
+
+    io::stdout().write_all(page.as_bytes())?;
+
+    Ok(())
+}
+
+/// Help of the command at `path`, eg. `["backup", "restore"]`, as `--help` prints it.
+fn help(path: &[String]) -> String {
+    let args = std::iter::once(BIN_NAME.to_string())
+        .chain(path.iter().cloned())
+        .chain(std::iter::once("--help".to_string()));
+
+    match Opts::clap().get_matches_from_safe(args) {
+        Err(e) if e.kind == clap::ErrorKind::HelpDisplayed => e.message,
+        _ => String::new(),
+    }
+}
+
+/// Paths of the commands below the one at `path`, followed by their own commands.
+///
+/// They're read from the help, as clap doesn't expose its subcommands, which
+/// keeps the man page in sync with the commands without listing them here.
+fn subcommands(path: &[String]) -> Vec<Vec<String>> {
+    let help = help(path);
+
+    let names = help
+        .lines()
+        .skip_while(|line| line.trim_end() != "SUBCOMMANDS:")
+        .skip(1)
+        .take_while(|line| line.starts_with(' '))
+        .filter(|line| line.starts_with("    ") && !line.starts_with("     "))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    let mut commands = vec![];
+
+    for name in names {
+        let mut command = path.to_vec();
+        command.push(name);
+
+        let nested = subcommands(&command);
+        commands.push(command);
+        commands.extend(nested);
+    }
+
+    commands
+}
+
+/// Text which roff shows as is, keeping the alignment of the help.
+fn preformatted(text: &str) -> String {
+    let mut roff = String::from(".nf\n");
+
+    for line in text.lines() {
+        roff.push_str(&escape(line));
+        roff.push('\n');
+    }
+
+    roff.push_str(".fi\n");
+    roff
+}
+
+/// Escapes backslashes, and lines which roff would read as a request.
+fn escape(line: &str) -> String {
+    let line = line.replace('\\', "\\e");
+
+    if line.starts_with('.') || line.starts_with('\'') {
+        format!("\\&{}", line)
+    } else {
+        line
+    }
+}
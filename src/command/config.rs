@@ -0,0 +1,84 @@
+use super::output::{print_json, print_result};
+use crate::cli::OutputFormat;
+
+use ajour_core::config::{load_config, TextOption};
+use ajour_core::fs::PersistentData;
+use ajour_core::Result;
+
+use async_std::task;
+
+use serde::Serialize;
+
+/// An option of the config as printed by `config`.
+#[derive(Debug, Serialize)]
+struct ConfigOption {
+    key: &'static str,
+    value: String,
+}
+
+impl ConfigOption {
+    fn print(&self, output: OutputFormat) {
+        print_result(output, &format!("{} = {}", self.key, self.value));
+    }
+}
+
+/// Prints the value of the option with `key`, empty if it isn't set
+pub fn get_config_option(key: String, output: OutputFormat) -> Result<()> {
+    let option = key.parse::<TextOption>()?;
+
+    task::block_on(async {
+        let config = load_config().await?;
+
+        let config_option = ConfigOption {
+            key: option.key(),
+            value: config.text_option(option),
+        };
+        config_option.print(output);
+
+        print_json(output, &config_option)
+    })
+}
+
+/// Sets the option with `key`, validated the same way as in the settings of
+/// Ajour. An empty value unsets it.
+pub fn set_config_option(key: String, value: String, output: OutputFormat) -> Result<()> {
+    let option = key.parse::<TextOption>()?;
+
+    task::block_on(async {
+        let mut config = load_config().await?;
+
+        config.set_text_option(option, &value)?;
+
+        // Persist the newly updated config.
+        config.save()?;
+
+        let config_option = ConfigOption {
+            key: option.key(),
+            value: config.text_option(option),
+        };
+        config_option.print(output);
+
+        print_json(output, &config_option)
+    })
+}
+
+/// Prints every option which can be set with `config set`, along with its value
+pub fn list_config_options(output: OutputFormat) -> Result<()> {
+    task::block_on(async {
+        let config = load_config().await?;
+
+        let config_options = TextOption::ALL
+            .iter()
+            .map(|option| ConfigOption {
+                key: option.key(),
+                value: config.text_option(*option),
+            })
+            .collect::<Vec<_>>();
+
+        for config_option in config_options.iter() {
+            config_option.print(output);
+        }
+
+        print_json(output, &config_options)
+    })
+}
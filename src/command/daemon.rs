@@ -0,0 +1,124 @@
+use super::output::print_json;
+use super::update_addons::update_all;
+use crate::cli::{Interval, OutputFormat};
+use crate::instance::{self, Request};
+use crate::log_error;
+use crate::notification::notify_update_report;
+
+use ajour_core::config::{load_config, UpdateSchedule};
+use ajour_core::error::ClientError;
+use ajour_core::fs::PersistentData;
+use ajour_core::localization::{localized_string, localized_string_with};
+use ajour_core::Result;
+
+use async_std::task;
+use chrono::{Local, NaiveDateTime};
+use std::time::Duration;
+
+/// How often the daemon wakes up to see if updating is due.
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Stays running, and updates all addons every `interval`, or on the update
+/// schedule of the config if it's `None`.
+///
+/// Each run goes through `update`, so pinned and ignored addons are skipped and
+/// updating waits for World of Warcraft to exit. While the GUI is open, it's handed
+/// the runs instead. A failed run is logged, and tried again on the next interval.
+pub fn daemon(
+    interval: Option<Interval>,
+    concurrency: usize,
+    notify: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    task::block_on(async {
+        let started_at = Local::now().naive_local();
+
+        match interval {
+            Some(interval) => log::info!(
+                "{}",
+                localized_string_with(
+                    "cli-updating-every",
+                    &[("minutes", &interval.0.as_secs() / 60)]
+                )
+            ),
+            None => {
+                let schedule = load_config().await?.scheduled_updates.schedule;
+
+                if schedule == UpdateSchedule::Off {
+                    return Err(ClientError::Custom(localized_string(
+                        "cli-no-update-schedule",
+                    )));
+                }
+
+                log::info!(
+                    "{}",
+                    localized_string_with("cli-updating-on-schedule", &[("schedule", &schedule)])
+                );
+            }
+        }
+
+        let mut last_run: Option<NaiveDateTime> = None;
+
+        loop {
+            let now = Local::now().naive_local();
+
+            // The schedule is read on every poll, so changes made in the settings
+            // are picked up.
+            let is_due = match interval {
+                Some(interval) => last_run.map_or(true, |at| {
+                    (now - at).to_std().unwrap_or_default() >= interval.0
+                }),
+                None => match load_config().await {
+                    Ok(config) => config.scheduled_updates.is_due(started_at, now),
+                    Err(e) => {
+                        log_error(&e);
+                        false
+                    }
+                },
+            };
+
+            if is_due {
+                last_run = Some(now);
+                record_last_run(now).await;
+
+                if instance::send(Request::UpdateAll) {
+                    log::info!("{}", localized_string("cli-ajour-is-open"));
+
+                    task::sleep(POLL_INTERVAL).await;
+                    continue;
+                }
+
+                log::info!("{}", localized_string("cli-checking-for-updates"));
+
+                match update_all(concurrency, true, false).await {
+                    Ok((report, addon_results)) => {
+                        print_json(output, &addon_results)?;
+
+                        if notify {
+                            notify_update_report(&report);
+                        }
+                    }
+                    Err(e) => log_error(&e),
+                }
+            }
+
+            task::sleep(POLL_INTERVAL).await;
+        }
+    })
+}
+
+/// Records the run in the config, so the GUI shows it and its own schedule
+/// doesn't run again right after.
+async fn record_last_run(at: NaiveDateTime) {
+    let result = match load_config().await {
+        Ok(mut config) => {
+            config.scheduled_updates.last_run = Some(at);
+            config.save()
+        }
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = result {
+        log_error(&e);
+    }
+}
@@ -0,0 +1,114 @@
+use super::output::{print_json, print_result};
+use crate::cli::OutputFormat;
+use crate::VERSION;
+
+use ajour_core::config::load_config;
+use ajour_core::diagnostics::{compatibility_checks, create_bundle, health_checks, CheckStatus};
+use ajour_core::error::ClientError;
+use ajour_core::game::client_builds;
+use ajour_core::localization::localized_string_with;
+use ajour_core::parse::read_addon_directory;
+use ajour_core::Result;
+
+use async_std::sync::{Arc, Mutex};
+use async_std::task;
+
+use serde::Serialize;
+
+use std::path::PathBuf;
+
+/// A health check as printed by `doctor`.
+#[derive(Debug, Serialize)]
+struct CheckResult {
+    name: String,
+    status: String,
+    detail: String,
+}
+
+/// Checks the setup Ajour depends on, printing the outcome of each check
+///
+/// The addons are checked against the installed game client too, so addons the
+/// game won't load as they're out of date are listed.
+///
+/// With `bundle`, a zip for bug reports is written to `destination`, the current
+/// directory if not set. Fails if any of the checks failed.
+pub fn doctor(bundle: bool, destination: Option<PathBuf>, output: OutputFormat) -> Result<()> {
+    task::block_on(async {
+        let config = load_config().await?;
+
+        let mut checks = health_checks(&config);
+
+        // Fingerprint cache will be fetched during `read_addon_directory`
+        let fingerprint_collection: Arc<Mutex<_>> = Default::default();
+
+        let mut addons = vec![];
+
+        for flavor in config.managed_flavors() {
+            let addon_directory = match config.get_addon_directory_for_flavor(&flavor) {
+                Some(dir) if dir.exists() => dir,
+                _ => continue,
+            };
+
+            let result = read_addon_directory(
+                fingerprint_collection.clone(),
+                &addon_directory,
+                flavor,
+                config.addons.source_priority(&flavor),
+            )
+            .await;
+
+            // The checks above already tell what's wrong with the directory.
+            match result {
+                Ok(flavor_addons) => addons.push((flavor, flavor_addons)),
+                Err(e) => log::debug!("couldn't read {} addons: {}", flavor, e),
+            }
+        }
+
+        if let Some(directory) = &config.wow.directory {
+            checks.extend(compatibility_checks(&client_builds(directory), &addons));
+        }
+
+        for check in checks.iter() {
+            print_result(output, &check.to_string());
+        }
+
+        let num_failed = checks
+            .iter()
+            .filter(|c| c.status == CheckStatus::Failed)
+            .count();
+
+        if bundle {
+            let destination = match destination {
+                Some(destination) => destination,
+                None => std::env::current_dir()?,
+            };
+
+            let path = create_bundle(config, addons, VERSION.to_string(), destination).await?;
+
+            print_result(
+                output,
+                &localized_string_with("cli-diagnostics-saved", &[("path", &path.display())]),
+            );
+        }
+
+        let checks = checks
+            .into_iter()
+            .map(|check| CheckResult {
+                name: check.name,
+                status: check.status.to_string(),
+                detail: check.detail,
+            })
+            .collect::<Vec<_>>();
+
+        print_json(output, &checks)?;
+
+        if num_failed > 0 {
+            return Err(ClientError::Custom(localized_string_with(
+                "cli-health-checks-failed",
+                &[("count", &num_failed)],
+            )));
+        }
+
+        Ok(())
+    })
+}
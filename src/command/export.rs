@@ -0,0 +1,85 @@
+use super::output::print_json;
+use crate::cli::OutputFormat;
+
+use ajour_core::config::{load_config, Config, Flavor};
+use ajour_core::error::ClientError;
+use ajour_core::import::{AddonExport, ExportedAddon, EXPORT_VERSION};
+use ajour_core::localization::localized_string_with;
+use ajour_core::parse::read_addon_directory;
+use ajour_core::update::apply_addon_preferences;
+use ajour_core::Result;
+
+use async_std::sync::{Arc, Mutex};
+use async_std::task;
+
+use std::path::PathBuf;
+
+/// Writes the addons managed by Ajour to `file`, so they can be installed on another
+/// machine with `import`
+///
+/// The repository, project id and release channel of every addon of every flavor is
+/// exported. Addons which aren't from a repository are left out.
+pub fn export_addons(file: PathBuf, output: OutputFormat) -> Result<()> {
+    task::block_on(async {
+        let config = load_config().await?;
+
+        let export = collect_export(&config).await?;
+
+        std::fs::write(&file, serde_json::to_vec_pretty(&export)?)?;
+
+        log::info!(
+            "{}",
+            localized_string_with(
+                "cli-exported-addons",
+                &[("count", &export.addons.len()), ("file", &file.display())]
+            )
+        );
+
+        print_json(
+            output,
+            &serde_json::json!({
+                "file": file,
+                "exported": export.addons.len(),
+            }),
+        )?;
+
+        Result::Ok(())
+    })
+}
+
+/// Reads the addons of every flavor, with the release channels chosen by the user.
+pub(super) async fn collect_export(config: &Config) -> Result<AddonExport> {
+    if config.wow.directory.is_none() {
+        return Err(ClientError::NoWowDirectory);
+    }
+
+    // Fingerprint cache will be fetched during `read_addon_directory`
+    let fingerprint_collection: Arc<Mutex<_>> = Default::default();
+
+    let mut exported = vec![];
+
+    for flavor in Flavor::ALL.iter() {
+        let addon_directory = match config.get_addon_directory_for_flavor(flavor) {
+            Some(dir) if dir.exists() => dir,
+            _ => continue,
+        };
+
+        let mut addons = read_addon_directory(
+            fingerprint_collection.clone(),
+            &addon_directory,
+            *flavor,
+            config.addons.source_priority(flavor),
+        )
+        .await?;
+
+        // Applies the release channels chosen by the user
+        apply_addon_preferences(&mut addons, &config.addons, *flavor);
+
+        exported.extend(addons.iter().filter_map(|a| ExportedAddon::new(a, *flavor)));
+    }
+
+    Ok(AddonExport {
+        version: EXPORT_VERSION,
+        addons: exported,
+    })
+}
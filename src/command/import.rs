@@ -0,0 +1,267 @@
+use super::install::install_remote_addon;
+use super::output::{print_json, set_exit_code, AddonResult, ExitCode, Outcome};
+use crate::cli::OutputFormat;
+use crate::log_error;
+
+use ajour_core::addon::ReleaseChannel;
+use ajour_core::config::{load_config, Flavor};
+use ajour_core::error::ClientError;
+use ajour_core::import::{
+    import_curseforge_addons, import_wowup_addons, wowup_addons_path, AddonExport, ExportedAddon,
+    ImportSummary, EXPORT_VERSION,
+};
+use ajour_core::localization::{localized_string, localized_string_with};
+use ajour_core::parse::{read_addon_directory, FingerprintCollection};
+use ajour_core::Result;
+
+use async_std::sync::{Arc, Mutex};
+use async_std::task;
+
+use isahc::config::RedirectPolicy;
+use isahc::prelude::*;
+
+use std::path::{Path, PathBuf};
+
+/// Installs the addons of a file written by `export` which aren't installed yet
+///
+/// Addons are installed with the exported release channel, which is saved to the
+/// config as well.
+pub fn import_addons(file: PathBuf, output: OutputFormat) -> Result<()> {
+    task::block_on(async {
+        let export: AddonExport = serde_json::from_slice(&std::fs::read(&file)?)?;
+
+        if export.version > EXPORT_VERSION {
+            return Err(ClientError::Custom(localized_string_with(
+                "cli-newer-export",
+                &[("file", &file.display())],
+            )));
+        }
+
+        install_export(&export, output).await
+    })
+}
+
+/// Installs the addons of `export` which aren't installed yet, with their release
+/// channel.
+pub(super) async fn install_export(export: &AddonExport, output: OutputFormat) -> Result<()> {
+    let mut config = load_config().await?;
+
+    if config.wow.directory.is_none() {
+        return Err(ClientError::NoWowDirectory);
+    }
+
+    let shared_client = HttpClient::builder()
+        .redirect_policy(RedirectPolicy::Follow)
+        .max_connections_per_host(6)
+        .build()
+        .unwrap();
+
+    // Fingerprint cache will be fetched during `read_addon_directory`
+    let fingerprint_collection: Arc<Mutex<_>> = Default::default();
+
+    let mut results = vec![];
+
+    for flavor in Flavor::ALL.iter().copied() {
+        let exported = export
+            .addons
+            .iter()
+            .filter(|a| a.flavor == flavor)
+            .collect::<Vec<_>>();
+
+        if exported.is_empty() {
+            continue;
+        }
+
+        let addon_directory = match config.get_addon_directory_for_flavor(&flavor) {
+            Some(dir) if dir.exists() => dir,
+            _ => {
+                log::warn!(
+                    "{}",
+                    localized_string_with(
+                        "cli-skipping-flavor",
+                        &[("count", &exported.len()), ("flavor", &flavor)]
+                    )
+                );
+                continue;
+            }
+        };
+
+        // Directory to temporarily save downloaded addons
+        let temp_directory = config
+            .get_download_directory_for_flavor(flavor)
+            .expect("Expected a valid path");
+
+        let installed = read_addon_directory(
+            fingerprint_collection.clone(),
+            &addon_directory,
+            flavor,
+            config.addons.source_priority(&flavor),
+        )
+        .await?;
+
+        for exported in exported {
+            if installed.iter().any(|a| exported.matches(a)) {
+                log::debug!("{} is already installed for {}", &exported.title, flavor);
+                continue;
+            }
+
+            log::info!(
+                "{}",
+                localized_string_with(
+                    "cli-installing-for",
+                    &[("title", &exported.title), ("flavor", &flavor)]
+                )
+            );
+
+            let result = install_exported(
+                &shared_client,
+                fingerprint_collection.clone(),
+                exported,
+                &temp_directory,
+                &addon_directory,
+            )
+            .await;
+
+            match result {
+                Ok(mut addon_result) => {
+                    if exported.release_channel != ReleaseChannel::Stable {
+                        config
+                            .addons
+                            .release_channels
+                            .entry(flavor)
+                            .or_default()
+                            .insert(exported.folder.clone(), exported.release_channel);
+                    }
+
+                    addon_result.old_version = None;
+                    results.push(addon_result);
+                }
+                Err(error) => {
+                    log_error(&error);
+
+                    results.push(AddonResult {
+                        id: exported.folder.clone(),
+                        title: exported.title.clone(),
+                        flavor,
+                        old_version: None,
+                        new_version: None,
+                        result: Outcome::Failed,
+                        error: Some(error.to_string()),
+                        reason: None,
+                    });
+                }
+            }
+        }
+    }
+
+    // Persist the newly updated config.
+    config.save()?;
+
+    let installed = results
+        .iter()
+        .filter(|r| r.result == Outcome::Installed)
+        .count();
+
+    log::info!(
+        "{}",
+        localized_string_with(
+            "cli-installed-addons",
+            &[
+                ("count", &installed),
+                ("failed", &results.len() - installed)
+            ]
+        )
+    );
+
+    if installed < results.len() {
+        set_exit_code(ExitCode::PartialFailure);
+    }
+
+    print_json(output, &results)?;
+
+    Ok(())
+}
+
+async fn install_exported(
+    shared_client: &HttpClient,
+    fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
+    exported: &ExportedAddon,
+    temp_directory: &PathBuf,
+    addon_directory: &PathBuf,
+) -> Result<AddonResult> {
+    let addon = exported.fetch().await?;
+
+    install_remote_addon(
+        shared_client,
+        fingerprint_collection,
+        &addon,
+        exported.flavor,
+        temp_directory,
+        addon_directory,
+    )
+    .await?;
+
+    Ok(AddonResult::new(
+        &addon,
+        exported.flavor,
+        Outcome::Installed,
+    ))
+}
+
+/// Links addons installed by WowUp to their repositories, so Ajour can update them
+///
+/// `file` defaults to the `addons.json` in WowUp's data directory.
+pub fn import_wowup(file: Option<PathBuf>, output: OutputFormat) -> Result<()> {
+    task::block_on(async {
+        let config = load_config().await?;
+
+        let path = file
+            .or_else(wowup_addons_path)
+            .ok_or_else(|| ClientError::Custom(localized_string("cli-wowup-addons-not-found")))?;
+
+        log::info!(
+            "{}",
+            localized_string_with("cli-importing-from", &[("path", &path.display())])
+        );
+
+        let summary = import_wowup_addons(&path, &config)?;
+
+        print_import_summary(&path, summary, output)
+    })
+}
+
+/// Links addons installed by the CurseForge client to their Curse projects, so
+/// Ajour can update them
+pub fn import_curseforge(file: PathBuf, output: OutputFormat) -> Result<()> {
+    task::block_on(async {
+        let config = load_config().await?;
+
+        log::info!(
+            "{}",
+            localized_string_with("cli-importing-from", &[("path", &file.display())])
+        );
+
+        let summary = import_curseforge_addons(&file, &config)?;
+
+        print_import_summary(&file, summary, output)
+    })
+}
+
+fn print_import_summary(path: &Path, summary: ImportSummary, output: OutputFormat) -> Result<()> {
+    log::info!(
+        "{}",
+        localized_string_with(
+            "cli-imported-addons",
+            &[("count", &summary.linked), ("skipped", &summary.skipped)]
+        )
+    );
+
+    print_json(
+        output,
+        &serde_json::json!({
+            "file": path,
+            "linked": summary.linked,
+            "skipped": summary.skipped,
+        }),
+    )
+}
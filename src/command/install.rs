@@ -0,0 +1,371 @@
+use super::fingerprint_folders;
+use super::output::{print_json, AddonResult, Outcome};
+use crate::cli::OutputFormat;
+
+use ajour_core::addon::{Addon, AddonFolder};
+use ajour_core::catalog::{get_catalog, CatalogAddon, Source};
+use ajour_core::config::{load_config, Flavor};
+use ajour_core::curse_api;
+use ajour_core::error::ClientError;
+use ajour_core::fs::{self, install_addon};
+use ajour_core::localization::localized_string_with;
+use ajour_core::network::download_addon;
+use ajour_core::parse::{read_addon_directory, FingerprintCollection};
+use ajour_core::tukui_api;
+use ajour_core::Result;
+
+use async_std::sync::{Arc, Mutex};
+use async_std::task;
+
+use isahc::config::RedirectPolicy;
+use isahc::prelude::*;
+
+use std::path::PathBuf;
+
+/// Installs an addon from the catalog
+///
+/// `query` can either be the id, the name or the slug (as seen in the website url)
+/// of the addon.
+///
+/// Unless `no_dependencies` is set, addons required by the addon which aren't
+/// installed yet are installed along with it.
+pub fn install_from_catalog(
+    query: String,
+    flavor: Flavor,
+    no_dependencies: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    log::info!(
+        "{}",
+        localized_string_with("cli-looking-up", &[("query", &query)])
+    );
+
+    task::block_on(async {
+        let config = load_config().await?;
+
+        // Only returns None if the path isn't set in the config
+        let addon_directory = config
+            .get_addon_directory_for_flavor(&flavor)
+            .ok_or(ClientError::NoWowDirectory)?;
+
+        // Directory to temporarily save downloaded addon
+        let temp_directory = config
+            .get_download_directory_for_flavor(flavor)
+            .expect("Expected a valid path");
+
+        let catalog = get_catalog().await?;
+
+        let catalog_addon =
+            find_catalog_addon(&catalog.addons, &query, flavor).ok_or_else(|| {
+                ClientError::Custom(localized_string_with(
+                    "cli-not-in-catalog",
+                    &[("query", &query), ("flavor", &flavor)],
+                ))
+            })?;
+
+        log::info!(
+            "{}",
+            localized_string_with(
+                "cli-installing-from-for",
+                &[
+                    ("title", &catalog_addon.name),
+                    ("source", &catalog_addon.source),
+                    ("flavor", &flavor)
+                ]
+            )
+        );
+
+        let mut addon = match catalog_addon.source {
+            Source::Curse => curse_api::latest_addon(catalog_addon.id, flavor).await?,
+            Source::Tukui => tukui_api::latest_addon(catalog_addon.id, flavor).await?,
+        };
+
+        let shared_client = HttpClient::builder()
+            .redirect_policy(RedirectPolicy::Follow)
+            .max_connections_per_host(6)
+            .build()
+            .unwrap();
+
+        // Fingerprint cache will be loaded when the first folder is fingerprinted
+        let fingerprint_collection: Arc<Mutex<_>> = Default::default();
+
+        // Installed addons are only needed to find missing dependencies
+        let mut installed = if no_dependencies {
+            vec![]
+        } else {
+            read_addon_directory(
+                fingerprint_collection.clone(),
+                &addon_directory,
+                flavor,
+                config.addons.source_priority(&flavor),
+            )
+            .await?
+        };
+
+        addon.folders = install_remote_addon(
+            &shared_client,
+            fingerprint_collection.clone(),
+            &addon,
+            flavor,
+            &temp_directory,
+            &addon_directory,
+        )
+        .await?;
+
+        if !no_dependencies {
+            install_missing_dependencies(
+                &shared_client,
+                fingerprint_collection,
+                &addon,
+                &mut installed,
+                flavor,
+                &temp_directory,
+                &addon_directory,
+            )
+            .await;
+
+            let missing_folders = addon.missing_folder_dependencies(&installed);
+            if !missing_folders.is_empty() {
+                log::warn!(
+                    "{}",
+                    localized_string_with(
+                        "cli-requires-missing",
+                        &[
+                            ("title", &catalog_addon.name),
+                            ("folders", &missing_folders.join(", "))
+                        ]
+                    )
+                );
+            }
+        }
+
+        log::info!(
+            "{}",
+            localized_string_with(
+                "cli-installed-version",
+                &[
+                    ("title", &catalog_addon.name),
+                    (
+                        "version",
+                        &addon
+                            .relevant_release_package()
+                            .map(|p| p.version.clone())
+                            .unwrap_or_default()
+                    )
+                ]
+            )
+        );
+
+        let mut addon_result = AddonResult::new(&addon, flavor, Outcome::Installed);
+        addon_result.old_version = None;
+
+        print_json(output, &addon_result)?;
+
+        Result::Ok(())
+    })
+}
+
+/// Installs an addon from a zip at `url`, which can be a web address or a path on disk
+///
+/// The addon won't be linked to a repository unless its folders are recognized when
+/// fingerprinted.
+pub fn install_from_url(url: String, flavor: Flavor, output: OutputFormat) -> Result<()> {
+    task::block_on(async {
+        let config = load_config().await?;
+
+        // Only returns None if the path isn't set in the config
+        let addon_directory = config
+            .get_addon_directory_for_flavor(&flavor)
+            .ok_or(ClientError::NoWowDirectory)?;
+
+        // Directory to temporarily save downloaded addon
+        let temp_directory = config
+            .get_download_directory_for_flavor(flavor)
+            .expect("Expected a valid path");
+
+        log::info!(
+            "{}",
+            localized_string_with(
+                "cli-installing-for",
+                &[("title", &url), ("flavor", &flavor)]
+            )
+        );
+
+        let shared_client = HttpClient::builder()
+            .redirect_policy(RedirectPolicy::Follow)
+            .max_connections_per_host(6)
+            .build()
+            .unwrap();
+
+        let folders =
+            fs::install_from_url(&shared_client, &url, &temp_directory, &addon_directory).await?;
+
+        // Fingerprint cache will be loaded when the first folder is fingerprinted
+        let fingerprint_collection: Arc<Mutex<_>> = Default::default();
+
+        fingerprint_folders(fingerprint_collection, flavor, &addon_directory, &folders).await;
+
+        let folder_ids = folders.iter().map(|f| f.id.clone()).collect::<Vec<_>>();
+
+        log::info!(
+            "{}",
+            localized_string_with(
+                "cli-installed-folders",
+                &[("folders", &folder_ids.join(", "))]
+            )
+        );
+
+        print_json(
+            output,
+            &serde_json::json!({
+                "url": url,
+                "flavor": flavor,
+                "folders": folder_ids,
+                "result": Outcome::Installed,
+            }),
+        )?;
+
+        Result::Ok(())
+    })
+}
+
+/// Downloads and installs `addon` from its repository, then fingerprints its folders.
+pub(super) async fn install_remote_addon(
+    shared_client: &HttpClient,
+    fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
+    addon: &Addon,
+    flavor: Flavor,
+    temp_directory: &PathBuf,
+    addon_directory: &PathBuf,
+) -> Result<Vec<AddonFolder>> {
+    // Download the addon to the temp directory
+    download_addon(shared_client, addon, temp_directory, None).await?;
+
+    // Extracts addon from the downloaded archive to the addon directory and caches the archive
+    let folders = install_addon(addon, flavor, temp_directory, addon_directory).await?;
+
+    fingerprint_folders(fingerprint_collection, flavor, addon_directory, &folders).await;
+
+    Ok(folders)
+}
+
+/// Installs the addons required by `addon` which aren't part of `installed`,
+/// including the ones required by those in turn.
+///
+/// Failing to install a dependency doesn't fail the install of `addon`, so errors
+/// are only logged.
+async fn install_missing_dependencies(
+    shared_client: &HttpClient,
+    fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
+    addon: &Addon,
+    installed: &mut Vec<Addon>,
+    flavor: Flavor,
+    temp_directory: &PathBuf,
+    addon_directory: &PathBuf,
+) {
+    let mut queue = addon.missing_dependencies(installed);
+    let mut attempted = vec![];
+
+    while let Some(id) = queue.pop() {
+        if attempted.contains(&id) {
+            continue;
+        }
+        attempted.push(id);
+
+        log::info!(
+            "{}",
+            localized_string_with(
+                "cli-installing-dependency",
+                &[("title", &id), ("addon", &addon.title())]
+            )
+        );
+
+        let mut dependency = match curse_api::latest_addon(id, flavor).await {
+            Ok(dependency) => dependency,
+            Err(e) => {
+                log::error!(
+                    "{}",
+                    localized_string_with(
+                        "cli-dependency-not-found",
+                        &[("title", &id), ("error", &e)]
+                    )
+                );
+                continue;
+            }
+        };
+
+        match install_remote_addon(
+            shared_client,
+            fingerprint_collection.clone(),
+            &dependency,
+            flavor,
+            temp_directory,
+            addon_directory,
+        )
+        .await
+        {
+            Ok(folders) => {
+                log::info!(
+                    "{}",
+                    localized_string_with(
+                        "cli-installed-successfully",
+                        &[("title", &dependency.title())]
+                    )
+                );
+
+                dependency.folders = folders;
+                queue.extend(dependency.missing_dependencies(installed));
+                installed.push(dependency);
+            }
+            Err(e) => log::error!(
+                "{}",
+                localized_string_with(
+                    "cli-install-failed",
+                    &[("title", &dependency.title()), ("error", &e)]
+                )
+            ),
+        }
+    }
+}
+
+/// Finds the catalog addon best matching `query` for the given flavor.
+///
+/// An exact id match wins, otherwise addons are matched on name or slug. If several
+/// addons share the name, the most downloaded one is picked.
+fn find_catalog_addon<'a>(
+    addons: &'a [CatalogAddon],
+    query: &str,
+    flavor: Flavor,
+) -> Option<&'a CatalogAddon> {
+    let query = query.trim().to_lowercase();
+
+    let addons = addons
+        .iter()
+        .filter(|a| a.flavors.iter().any(|f| *f == flavor.catalog_flavor()));
+
+    // Ids are looked up on every source, while names match the most downloaded one.
+    if let Ok(id) = query.parse::<u32>() {
+        if let Some(addon) = addons
+            .clone()
+            .flat_map(|a| a.entries())
+            .find(|a| a.id == id)
+        {
+            return Some(addon);
+        }
+    }
+
+    addons
+        .filter(|a| {
+            let name = a.name.to_lowercase();
+            let slug = a
+                .website_url
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_lowercase();
+
+            name == query || slug == query || name.replace(' ', "-") == query
+        })
+        .max_by_key(|a| a.number_of_downloads)
+}
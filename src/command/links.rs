@@ -0,0 +1,52 @@
+use super::output::print_json;
+use crate::cli::OutputFormat;
+
+use ajour_core::config::load_config;
+use ajour_core::fs::PersistentData;
+use ajour_core::link::{
+    register_link_handler, unregister_link_handler, AJOUR_SCHEME, CURSEFORGE_SCHEME,
+};
+use ajour_core::localization::{localized_string, localized_string_with};
+use ajour_core::Result;
+
+use async_std::task;
+
+/// Registers Ajour to open `ajour://` links, and those of CurseForge if `curseforge`
+/// is set
+pub fn register_links(curseforge: bool, output: OutputFormat) -> Result<()> {
+    let mut schemes = vec![AJOUR_SCHEME];
+    if curseforge {
+        schemes.push(CURSEFORGE_SCHEME);
+    }
+
+    register_link_handler(&schemes)?;
+    set_open_links(true)?;
+
+    log::info!(
+        "{}",
+        localized_string_with("cli-opens-links", &[("schemes", &schemes.join(":// and "))])
+    );
+
+    print_json(output, &schemes)
+}
+
+/// Stops Ajour from opening `ajour://` and `curseforge://` links
+pub fn unregister_links(output: OutputFormat) -> Result<()> {
+    let schemes = [AJOUR_SCHEME, CURSEFORGE_SCHEME];
+
+    unregister_link_handler(&schemes)?;
+    set_open_links(false)?;
+
+    log::info!("{}", localized_string("cli-no-longer-opens-links"));
+
+    print_json(output, &schemes)
+}
+
+/// Keeps the setting of the GUI in line.
+fn set_open_links(open_links: bool) -> Result<()> {
+    task::block_on(async {
+        let mut config = load_config().await?;
+        config.open_links = open_links;
+        config.save()
+    })
+}
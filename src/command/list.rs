@@ -0,0 +1,242 @@
+use super::output::{print_json, set_exit_code, ExitCode};
+use crate::cli::OutputFormat;
+
+use ajour_core::abandoned::is_abandoned;
+use ajour_core::addon::AddonState;
+use ajour_core::config::{load_config, Flavor};
+use ajour_core::error::ClientError;
+use ajour_core::game::{client_builds, Compatibility};
+use ajour_core::localization::{localized_string, localized_string_with};
+use ajour_core::parse::read_addon_directory;
+use ajour_core::update::apply_addon_preferences;
+use ajour_core::Result;
+
+use async_std::sync::{Arc, Mutex};
+use async_std::task;
+
+use serde::Serialize;
+
+/// An installed addon as printed by `list`.
+#[derive(Debug, Serialize)]
+struct ListedAddon {
+    id: String,
+    title: String,
+    flavor: Flavor,
+    installed_version: Option<String>,
+    remote_version: Option<String>,
+    source: Option<String>,
+    outdated: bool,
+    ignored: bool,
+    pinned: bool,
+    symlinked: bool,
+    /// Version of the game the addon was made for, from its `.toc`.
+    interface: Option<u32>,
+    /// How well the addon fits the game client, if its version is known.
+    compatibility: Option<Compatibility>,
+    /// No release for a long time, and made for an old expansion.
+    abandoned: bool,
+}
+
+impl ListedAddon {
+    fn is_incompatible(&self) -> bool {
+        self.compatibility
+            .map_or(false, |c| c != Compatibility::Compatible)
+    }
+}
+
+/// Lists the installed addons of the given flavors along with their update status
+///
+/// If no flavors are given, all flavors with an addon directory are listed. Addons
+/// are outdated if `update` would update them, and incompatible if the game flags
+/// them as out of date or they're made for another version of it. Addons are
+/// abandoned if they had no release in `addons.abandoned_after_months` and are made
+/// for an old expansion.
+pub fn list_addons(
+    flavors: Vec<Flavor>,
+    outdated: bool,
+    incompatible: bool,
+    abandoned: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    task::block_on(async {
+        let config = load_config().await?;
+
+        let builds = match &config.wow.directory {
+            Some(directory) => client_builds(directory),
+            None => return Err(ClientError::NoWowDirectory),
+        };
+
+        let flavors = if flavors.is_empty() {
+            Flavor::ALL.to_vec()
+        } else {
+            flavors
+        };
+
+        // Fingerprint cache will be fetched during `read_addon_directory`
+        let fingerprint_collection: Arc<Mutex<_>> = Default::default();
+
+        let abandoned_after_months = config.addons.abandoned_after_months();
+
+        let mut listed = vec![];
+
+        for flavor in flavors {
+            let addon_directory = match config.get_addon_directory_for_flavor(&flavor) {
+                Some(dir) if dir.exists() => dir,
+                _ => continue,
+            };
+
+            let mut addons = read_addon_directory(
+                fingerprint_collection.clone(),
+                &addon_directory,
+                flavor,
+                config.addons.source_priority(&flavor),
+            )
+            .await?;
+
+            // Same rules as `update`
+            apply_addon_preferences(&mut addons, &config.addons, flavor);
+
+            for addon in addons {
+                let package = addon.relevant_release_package();
+                let interface = addon.interface();
+                let client_build = builds.get(&flavor);
+
+                listed.push(ListedAddon {
+                    id: addon.primary_folder_id.clone(),
+                    title: addon.title().to_string(),
+                    flavor,
+                    installed_version: addon.version().map(str::to_string),
+                    remote_version: package.map(|p| p.version.clone()),
+                    source: addon.active_repository.map(|r| r.to_string()),
+                    outdated: addon.state == AddonState::Updatable,
+                    ignored: addon.state == AddonState::Ignored,
+                    pinned: addon.state == AddonState::Pinned,
+                    symlinked: addon.state == AddonState::Symlinked,
+                    interface,
+                    compatibility: client_build
+                        .zip(interface)
+                        .map(|(build, interface)| build.compatibility(interface)),
+                    abandoned: is_abandoned(&addon, client_build, abandoned_after_months),
+                });
+            }
+        }
+
+        if outdated {
+            listed.retain(|a| a.outdated);
+
+            if !listed.is_empty() {
+                set_exit_code(ExitCode::UpdatesAvailable);
+            }
+        }
+
+        if incompatible {
+            listed.retain(ListedAddon::is_incompatible);
+        }
+
+        if abandoned {
+            listed.retain(|a| a.abandoned);
+        }
+
+        listed.sort_by(|a, b| {
+            a.flavor
+                .cmp(&b.flavor)
+                .then_with(|| a.title.to_lowercase().cmp(&b.title.to_lowercase()))
+        });
+
+        if output == OutputFormat::Text {
+            print_table(&listed);
+        }
+
+        print_json(output, &listed)
+    })
+}
+
+/// Prints the addons as a table with aligned columns.
+fn print_table(addons: &[ListedAddon]) {
+    let header = [
+        localized_string("cli-column-name"),
+        localized_string("cli-column-installed"),
+        localized_string("column-remote"),
+        localized_string("column-source"),
+        localized_string("cli-column-flavor"),
+        localized_string("column-interface"),
+    ];
+
+    let rows = addons
+        .iter()
+        .map(|a| {
+            let remote_version = match (&a.remote_version, a.outdated, a.ignored) {
+                (Some(version), true, _) => format!("{} *", version),
+                (Some(version), _, true) => {
+                    localized_string_with("cli-version-ignored", &[("version", version)])
+                }
+                (Some(version), ..) if a.pinned => {
+                    localized_string_with("cli-version-pinned", &[("version", version)])
+                }
+                (Some(version), ..) if a.symlinked => {
+                    localized_string_with("cli-version-symlinked", &[("version", version)])
+                }
+                (Some(version), ..) => version.clone(),
+                (None, ..) => "-".to_string(),
+            };
+
+            let interface = match (a.interface, a.compatibility) {
+                (Some(interface), _) if a.abandoned => {
+                    localized_string_with("cli-interface-abandoned", &[("interface", &interface)])
+                }
+                (Some(interface), Some(c)) if c != Compatibility::Compatible => {
+                    format!("{} ({})", interface, c)
+                }
+                (Some(interface), _) => interface.to_string(),
+                (None, _) => "-".to_string(),
+            };
+
+            [
+                a.title.clone(),
+                a.installed_version
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+                remote_version,
+                a.source.clone().unwrap_or_else(|| "-".to_string()),
+                a.flavor.to_string(),
+                interface,
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let mut widths = header.iter().map(|h| h.chars().count()).collect::<Vec<_>>();
+    for row in rows.iter() {
+        for (width, column) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(column.chars().count());
+        }
+    }
+
+    let format_row = |columns: Vec<&str>| {
+        columns
+            .iter()
+            .zip(widths.iter())
+            .map(|(column, width)| format!("{:<width$}", column, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!(
+        "{}",
+        format_row(header.iter().map(String::as_str).collect())
+    );
+    for row in rows.iter() {
+        println!("{}", format_row(row.iter().map(String::as_str).collect()));
+    }
+
+    let num_outdated = addons.iter().filter(|a| a.outdated).count();
+    println!();
+    println!(
+        "{}",
+        localized_string_with(
+            "cli-list-summary",
+            &[("count", &addons.len()), ("outdated", &num_outdated)]
+        )
+    );
+}
@@ -0,0 +1,149 @@
+mod backup;
+mod check;
+mod completions;
+mod config;
+mod daemon;
+mod doctor;
+mod export;
+mod import;
+mod install;
+mod links;
+mod list;
+mod output;
+mod path;
+mod pin;
+mod progress;
+mod rollback;
+mod sync;
+mod tui;
+mod update_addons;
+mod verify;
+
+pub use backup::{backup, list_backups, restore_backup};
+pub use check::check;
+pub use completions::{completions, manpage};
+pub use config::{get_config_option, list_config_options, set_config_option};
+pub use daemon::daemon;
+pub use doctor::doctor;
+pub use export::export_addons;
+pub use import::{import_addons, import_curseforge, import_wowup};
+pub use install::{install_from_catalog, install_from_url};
+pub use links::{register_links, unregister_links};
+pub use list::list_addons;
+pub use output::{exit_code, print_json, set_exit_code, ExitCode};
+pub use path::{detect_path, get_path, set_path};
+pub use pin::{pin, unpin};
+pub use progress::print_line;
+pub use rollback::rollback;
+pub use sync::sync;
+pub use tui::tui;
+pub use update_addons::{update_addon_by_name, update_all_addons};
+pub use verify::verify;
+
+use crate::log_error;
+
+use ajour_core::addon::{Addon, AddonFolder};
+use ajour_core::config::{Config, Flavor};
+use ajour_core::error::ClientError;
+use ajour_core::localization::localized_string_with;
+use ajour_core::parse::{read_addon_directory, update_addon_fingerprint, FingerprintCollection};
+use ajour_core::update::apply_addon_preferences;
+use ajour_core::Result;
+
+use async_std::sync::{Arc, Mutex};
+
+use futures::future::join_all;
+
+use std::path::PathBuf;
+
+/// Reads the addons of the given flavors with an addon directory, along with their
+/// update status, sorted by flavor and title.
+async fn read_addons(
+    config: &Config,
+    fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
+    flavors: &[Flavor],
+) -> Result<Vec<(Flavor, Addon)>> {
+    let mut addons = vec![];
+
+    for flavor in flavors.iter().copied() {
+        let addon_directory = match config.get_addon_directory_for_flavor(&flavor) {
+            Some(dir) if dir.exists() => dir,
+            _ => continue,
+        };
+
+        let mut flavor_addons = read_addon_directory(
+            fingerprint_collection.clone(),
+            &addon_directory,
+            flavor,
+            config.addons.source_priority(&flavor),
+        )
+        .await?;
+
+        // Same rules as `update`
+        apply_addon_preferences(&mut flavor_addons, &config.addons, flavor);
+
+        addons.extend(flavor_addons.into_iter().map(|addon| (flavor, addon)));
+    }
+
+    addons.sort_by(|(a_flavor, a), (b_flavor, b)| {
+        a_flavor
+            .cmp(b_flavor)
+            .then_with(|| a.title().to_lowercase().cmp(&b.title().to_lowercase()))
+    });
+
+    Ok(addons)
+}
+
+/// Finds the installed addon of `flavor` whose folder name or title is `query`,
+/// read the same way as `read_addons`.
+async fn find_installed_addon(
+    config: &Config,
+    fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
+    flavor: Flavor,
+    query: &str,
+) -> Result<Addon> {
+    if config.get_addon_directory_for_flavor(&flavor).is_none() {
+        return Err(ClientError::NoWowDirectory);
+    }
+
+    let query_lowercase = query.to_lowercase();
+
+    read_addons(config, fingerprint_collection, &[flavor])
+        .await?
+        .into_iter()
+        .map(|(_, addon)| addon)
+        .find(|a| {
+            a.primary_folder_id.to_lowercase() == query_lowercase
+                || a.title().to_lowercase() == query_lowercase
+        })
+        .ok_or_else(|| {
+            ClientError::Custom(localized_string_with(
+                "cli-addon-not-installed",
+                &[("flavor", &flavor), ("query", &query)],
+            ))
+        })
+}
+
+/// Refingerprints the given folders concurrently, saving them to the cache.
+async fn fingerprint_folders(
+    fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
+    flavor: Flavor,
+    addon_directory: &PathBuf,
+    folders: &[AddonFolder],
+) {
+    for result in join_all(folders.iter().map(|f| {
+        update_addon_fingerprint(
+            fingerprint_collection.clone(),
+            flavor,
+            addon_directory,
+            f.id.clone(),
+        )
+    }))
+    .await
+    {
+        if let Err(e) = result {
+            // Log any errors fingerprinting the folder
+            log_error(&e);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use super::progress::print_line;
+use crate::cli::OutputFormat;
+
+use ajour_core::addon::Addon;
+use ajour_core::config::Flavor;
+use ajour_core::error::ClientError;
+use ajour_core::update::SkipReason;
+use ajour_core::Result;
+
+use serde::Serialize;
+
+use std::sync::atomic::{AtomicI32, Ordering};
+
+/// Code Ajour exits with once the command is done.
+static EXIT_CODE: AtomicI32 = AtomicI32::new(ExitCode::Success as i32);
+
+/// Exit codes of commands, which scripts can branch on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Success = 0,
+    /// The command failed, or its arguments are invalid.
+    Error = 1,
+    /// `check` found updates, or `list --outdated` listed addons.
+    UpdatesAvailable = 2,
+    /// The command ran, but failed for some of the addons.
+    PartialFailure = 3,
+    /// World of Warcraft is running for a flavor the command had to change.
+    GameRunning = 4,
+}
+
+impl ExitCode {
+    /// Exit code of a command which failed with `error`.
+    pub fn from_error(error: &ClientError) -> Self {
+        match error {
+            ClientError::GameRunning(_) => ExitCode::GameRunning,
+            _ => ExitCode::Error,
+        }
+    }
+}
+
+/// Sets the code Ajour exits with once the command is done.
+pub fn set_exit_code(code: ExitCode) {
+    EXIT_CODE.store(code as i32, Ordering::Relaxed);
+}
+
+pub fn exit_code() -> i32 {
+    EXIT_CODE.load(Ordering::Relaxed)
+}
+
+/// Result of a command for a single addon, printed with `--output json`.
+#[derive(Debug, Serialize)]
+pub struct AddonResult {
+    pub id: String,
+    pub title: String,
+    pub flavor: Flavor,
+    pub old_version: Option<String>,
+    pub new_version: Option<String>,
+    pub result: Outcome,
+    pub error: Option<String>,
+    /// Why the addon was skipped, eg. because it's pinned.
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    UpdateAvailable,
+    Updated,
+    Installed,
+    RolledBack,
+    Skipped,
+    Failed,
+}
+
+impl AddonResult {
+    /// Versions default to the installed version and the version of the release
+    /// channel of the addon.
+    pub fn new(addon: &Addon, flavor: Flavor, result: Outcome) -> Self {
+        AddonResult {
+            id: addon.primary_folder_id.clone(),
+            title: addon.title().to_string(),
+            flavor,
+            old_version: addon.version().map(str::to_string),
+            new_version: addon.relevant_release_package().map(|p| p.version.clone()),
+            result,
+            error: None,
+            reason: None,
+        }
+    }
+
+    pub fn skip(&mut self, reason: SkipReason) {
+        self.result = Outcome::Skipped;
+        self.reason = Some(reason.as_str().to_string());
+    }
+
+    pub fn fail(&mut self, error: &ClientError) {
+        self.result = Outcome::Failed;
+        self.error = Some(error.to_string());
+    }
+}
+
+/// Prints a line of the result of a command to stdout if the output format is
+/// text. Unlike the log, results are printed with `--quiet` as well.
+pub fn print_result(output: OutputFormat, line: &str) {
+    if output == OutputFormat::Text {
+        print_line(line);
+    }
+}
+
+/// Prints `value` to stdout if the output format is json.
+///
+/// Logging is sent to stderr in that case, so stdout only contains the result.
+pub fn print_json<T: Serialize>(output: OutputFormat, value: &T) -> Result<()> {
+    if output == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(value)?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use super::output::{print_json, print_result};
+use crate::cli::OutputFormat;
+
+use ajour_core::config::{load_config, Flavor};
+use ajour_core::error::ClientError;
+use ajour_core::fs::detect::{installed_flavors, wow_directories};
+use ajour_core::fs::PersistentData;
+use ajour_core::localization::{localized_string, localized_string_with};
+use ajour_core::utility::wow_path_resolution;
+use ajour_core::Result;
+
+use async_std::task;
+
+use serde::Serialize;
+
+use std::path::PathBuf;
+
+/// A World of Warcraft directory as printed by `path`.
+#[derive(Debug, Serialize)]
+struct WowDirectory {
+    directory: PathBuf,
+    flavors: Vec<Flavor>,
+}
+
+impl WowDirectory {
+    fn new(directory: PathBuf) -> Self {
+        let flavors = installed_flavors(&directory);
+
+        WowDirectory { directory, flavors }
+    }
+
+    fn print(&self, output: OutputFormat) {
+        let flavors = self
+            .flavors
+            .iter()
+            .map(Flavor::to_string)
+            .collect::<Vec<_>>();
+
+        print_result(
+            output,
+            &format!("{} ({})", self.directory.display(), flavors.join(", ")),
+        );
+    }
+}
+
+/// Prints the World of Warcraft directory in use, along with its installed flavors
+pub fn get_path(output: OutputFormat) -> Result<()> {
+    task::block_on(async {
+        let config = load_config().await?;
+
+        let directory = config
+            .wow
+            .directory
+            .clone()
+            .ok_or(ClientError::NoWowDirectory)?;
+
+        let wow_directory = WowDirectory::new(directory);
+        wow_directory.print(output);
+
+        print_json(output, &wow_directory)
+    })
+}
+
+/// Sets the World of Warcraft directory, as choosing it in Ajour does
+///
+/// `path` can also be a folder inside it, eg. the `_retail_` folder. It has to
+/// contain at least one flavor.
+pub fn set_path(path: PathBuf, output: OutputFormat) -> Result<()> {
+    let directory = wow_path_resolution(Some(path.clone())).ok_or_else(|| {
+        ClientError::Custom(localized_string_with(
+            "cli-not-wow-directory",
+            &[("path", &path.display())],
+        ))
+    })?;
+
+    let wow_directory = WowDirectory::new(directory);
+
+    if wow_directory.flavors.is_empty() {
+        return Err(ClientError::Custom(localized_string_with(
+            "cli-no-flavor-installed",
+            &[("directory", &wow_directory.directory.display())],
+        )));
+    }
+
+    task::block_on(async {
+        let mut config = load_config().await?;
+
+        config.set_wow_directory(wow_directory.directory.clone());
+
+        // Ajour shows the flavor in use, so it has to be installed.
+        if !wow_directory.flavors.contains(&config.wow.flavor) {
+            config.wow.flavor = wow_directory.flavors[0];
+        }
+
+        // Persist the newly updated config.
+        config.save()?;
+
+        log::info!("{}", localized_string("cli-wow-directory-set"));
+        wow_directory.print(output);
+
+        print_json(output, &wow_directory)
+    })
+}
+
+/// Prints the World of Warcraft directories found in the usual install locations,
+/// setting the first one if `set` is passed
+pub fn detect_path(set: bool, output: OutputFormat) -> Result<()> {
+    let detected = wow_directories()
+        .into_iter()
+        .map(WowDirectory::new)
+        .filter(|d| !d.flavors.is_empty())
+        .collect::<Vec<_>>();
+
+    if detected.is_empty() {
+        print_result(output, &localized_string("cli-wow-not-found"));
+    } else {
+        print_result(output, &localized_string("cli-found-wow"));
+
+        for wow_directory in detected.iter() {
+            wow_directory.print(output);
+        }
+    }
+
+    if !set {
+        return print_json(output, &detected);
+    }
+
+    match detected.into_iter().next() {
+        Some(wow_directory) => set_path(wow_directory.directory, output),
+        None => Err(ClientError::NoWowDirectory),
+    }
+}
@@ -0,0 +1,77 @@
+use super::find_installed_addon;
+use super::output::print_json;
+use crate::cli::OutputFormat;
+
+use ajour_core::config::{load_config, Flavor};
+use ajour_core::fs::PersistentData;
+use ajour_core::localization::{localized_string, localized_string_with};
+use ajour_core::Result;
+
+use async_std::task;
+
+/// Pins an installed addon to its installed version, so `update` and "Update All"
+/// skip it
+///
+/// `query` is matched against the folder name and title of the installed addons.
+pub fn pin(query: String, flavor: Flavor, output: OutputFormat) -> Result<()> {
+    set_pinned(query, flavor, true, output)
+}
+
+/// Unpins an addon pinned with `pin`, so it's updated again
+pub fn unpin(query: String, flavor: Flavor, output: OutputFormat) -> Result<()> {
+    set_pinned(query, flavor, false, output)
+}
+
+fn set_pinned(query: String, flavor: Flavor, pinned: bool, output: OutputFormat) -> Result<()> {
+    task::block_on(async {
+        let mut config = load_config().await?;
+
+        // Fingerprint cache will be fetched during `read_addon_directory`
+        let addon = find_installed_addon(&config, Default::default(), flavor, &query).await?;
+
+        let pinned_addon_ids = config.addons.pinned.entry(flavor).or_default();
+        pinned_addon_ids.retain(|id| id != &addon.primary_folder_id);
+
+        if pinned {
+            pinned_addon_ids.push(addon.primary_folder_id.clone());
+
+            log::info!(
+                "{}",
+                localized_string_with(
+                    "cli-pinned-to",
+                    &[
+                        ("title", &addon.title()),
+                        (
+                            "version",
+                            &addon
+                                .version()
+                                .map(str::to_string)
+                                .unwrap_or_else(|| localized_string("cli-its-installed-version"))
+                        )
+                    ]
+                )
+            );
+        } else {
+            log::info!(
+                "{}",
+                localized_string_with("cli-no-longer-pinned", &[("title", &addon.title())])
+            );
+        }
+
+        // Persist the newly updated config.
+        config.save()?;
+
+        print_json(
+            output,
+            &serde_json::json!({
+                "id": addon.primary_folder_id,
+                "title": addon.title(),
+                "flavor": flavor,
+                "version": addon.version(),
+                "pinned": pinned,
+            }),
+        )?;
+
+        Result::Ok(())
+    })
+}
@@ -0,0 +1,199 @@
+use ajour_core::download::{DownloadKey, DownloadManager, DownloadStatus};
+
+use async_std::task;
+
+use crossterm::cursor::MoveUp;
+use crossterm::queue;
+use crossterm::terminal::{Clear, ClearType};
+
+use std::io::{self, Write};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// How often the bars are drawn while addons are updated.
+const DRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Width of a bar, without the title and percentage.
+const BAR_WIDTH: usize = 30;
+
+/// Width the titles of the addons are padded or cut to.
+const TITLE_WIDTH: usize = 28;
+
+lazy_static::lazy_static! {
+    static ref BARS: Mutex<Option<Bars>> = Mutex::new(None);
+}
+
+/// Where an addon is in its update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Waiting,
+    Downloading,
+    Unpacking,
+    Done,
+}
+
+struct Bar {
+    key: DownloadKey,
+    title: String,
+    phase: Phase,
+}
+
+/// Bars of the addons being updated, drawn below the log.
+struct Bars {
+    bars: Vec<Bar>,
+    manager: DownloadManager,
+    /// Amount of lines drawn last, which are cleared before drawing again.
+    drawn: usize,
+}
+
+impl Bars {
+    fn lines(&self) -> Vec<String> {
+        let mut lines = self
+            .bars
+            .iter()
+            .filter(|bar| matches!(bar.phase, Phase::Downloading | Phase::Unpacking))
+            .map(|bar| {
+                let title = bar.title.chars().take(TITLE_WIDTH).collect::<String>();
+
+                let progress = self.manager.progress(&bar.key);
+                let state = match (bar.phase, &progress) {
+                    (Phase::Unpacking, _) => "unpacking...".to_string(),
+                    (_, Some(p)) if p.status == DownloadStatus::Queued => "queued".to_string(),
+                    (_, Some(p)) => match p.fraction() {
+                        Some(fraction) => {
+                            let filled = (fraction * BAR_WIDTH as f32) as usize;
+
+                            format!(
+                                "[{}{}] {:>3}%",
+                                "#".repeat(filled),
+                                "-".repeat(BAR_WIDTH - filled),
+                                (fraction * 100.0) as usize
+                            )
+                        }
+                        None => format!("{} KB", p.bytes / 1024),
+                    },
+                    (_, None) => "starting...".to_string(),
+                };
+
+                format!("  {:<width$} {}", title, state, width = TITLE_WIDTH)
+            })
+            .collect::<Vec<_>>();
+
+        let num_done = self
+            .bars
+            .iter()
+            .filter(|bar| bar.phase == Phase::Done)
+            .count();
+        lines.push(format!(
+            "Finished {} of {} addons",
+            num_done,
+            self.bars.len()
+        ));
+
+        lines
+    }
+
+    /// Clears the lines drawn last, leaving the cursor where they started.
+    fn clear(&mut self, out: &mut impl Write) -> io::Result<()> {
+        if self.drawn > 0 {
+            queue!(
+                out,
+                MoveUp(self.drawn as u16),
+                Clear(ClearType::FromCursorDown)
+            )
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+        }
+
+        self.drawn = 0;
+        Ok(())
+    }
+
+    fn draw(&mut self, out: &mut impl Write) -> io::Result<()> {
+        self.clear(out)?;
+
+        let lines = self.lines();
+        for line in lines.iter() {
+            writeln!(out, "{}", line)?;
+        }
+
+        self.drawn = lines.len();
+        out.flush()
+    }
+}
+
+/// Returns true if progress can be drawn, which is only the case in a terminal,
+/// and if it wouldn't be the only thing printed in quiet mode.
+pub fn is_supported() -> bool {
+    atty::is(atty::Stream::Stdout) && log::max_level() >= log::LevelFilter::Info
+}
+
+/// Starts drawing a bar for each of `addons` until `finish` is called. Their
+/// downloads are followed through `manager`.
+pub fn start(manager: DownloadManager, addons: Vec<(DownloadKey, String)>) {
+    let bars = addons
+        .into_iter()
+        .map(|(key, title)| Bar {
+            key,
+            title,
+            phase: Phase::Waiting,
+        })
+        .collect();
+
+    *BARS.lock().unwrap() = Some(Bars {
+        bars,
+        manager,
+        drawn: 0,
+    });
+
+    task::spawn(async {
+        while draw() {
+            task::sleep(DRAW_INTERVAL).await;
+        }
+    });
+}
+
+/// Draws the bars, returning false once they're finished.
+fn draw() -> bool {
+    match BARS.lock().unwrap().as_mut() {
+        Some(bars) => {
+            let _ = bars.draw(&mut io::stdout());
+            true
+        }
+        None => false,
+    }
+}
+
+/// Moves the addon with `key` to `phase`, if bars are drawn.
+pub fn set_phase(key: &DownloadKey, phase: Phase) {
+    if let Some(bars) = BARS.lock().unwrap().as_mut() {
+        if let Some(bar) = bars.bars.iter_mut().find(|bar| &bar.key == key) {
+            bar.phase = phase;
+        }
+    }
+}
+
+/// Stops drawing the bars, and clears them.
+pub fn finish() {
+    if let Some(mut bars) = BARS.lock().unwrap().take() {
+        let mut stdout = io::stdout();
+
+        let _ = bars.clear(&mut stdout);
+        let _ = stdout.flush();
+    }
+}
+
+/// Prints a line of the log above the bars, so they don't get mixed up.
+pub fn print_line(line: &str) {
+    let mut stdout = io::stdout();
+
+    match BARS.lock().unwrap().as_mut() {
+        Some(bars) => {
+            let _ = bars.clear(&mut stdout);
+            let _ = writeln!(stdout, "{}", line);
+            let _ = bars.draw(&mut stdout);
+        }
+        None => {
+            let _ = writeln!(stdout, "{}", line);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use super::output::{print_json, AddonResult, Outcome};
+use super::{find_installed_addon, fingerprint_folders};
+use crate::cli::OutputFormat;
+
+use ajour_core::config::{load_config, Flavor};
+use ajour_core::error::ClientError;
+use ajour_core::fs::{rollback_addon, PersistentData};
+use ajour_core::localization::localized_string_with;
+use ajour_core::Result;
+
+use async_std::sync::{Arc, Mutex};
+use async_std::task;
+
+/// Rolls back an installed addon to the previous version in the archive cache
+///
+/// `query` is matched against the folder name and title of the installed addons.
+/// The version rolled back from is stored in the config, so it won't be offered as
+/// an update again.
+pub fn rollback(query: String, flavor: Flavor, output: OutputFormat) -> Result<()> {
+    task::block_on(async {
+        let mut config = load_config().await?;
+
+        // Only returns None if the path isn't set in the config
+        let addon_directory = config
+            .get_addon_directory_for_flavor(&flavor)
+            .ok_or(ClientError::NoWowDirectory)?;
+
+        // Fingerprint cache will be fetched during `read_addon_directory`
+        let fingerprint_collection: Arc<Mutex<_>> = Default::default();
+
+        // The release channel preference is applied, so we store the version an
+        // update would otherwise install
+        let addon =
+            find_installed_addon(&config, fingerprint_collection.clone(), flavor, &query).await?;
+
+        let rolled_back_from = addon.relevant_release_package().map(|p| p.version.clone());
+
+        let (archive, folders) = rollback_addon(&addon, flavor, &addon_directory).await?;
+
+        if let Some(version) = rolled_back_from {
+            config
+                .addons
+                .rolled_back
+                .entry(flavor)
+                .or_default()
+                .insert(addon.primary_folder_id.clone(), version);
+
+            // Persist the newly updated config.
+            config.save()?;
+        }
+
+        fingerprint_folders(fingerprint_collection, flavor, &addon_directory, &folders).await;
+
+        log::info!(
+            "{}",
+            localized_string_with(
+                "cli-rolled-back",
+                &[("title", &addon.title()), ("version", &archive.version)]
+            )
+        );
+
+        let mut addon_result = AddonResult::new(&addon, flavor, Outcome::RolledBack);
+        addon_result.new_version = Some(archive.version);
+
+        print_json(output, &addon_result)?;
+
+        Result::Ok(())
+    })
+}
@@ -0,0 +1,149 @@
+use super::export::collect_export;
+use super::import::install_export;
+use super::output::print_json;
+use crate::cli::{OutputFormat, SyncAction};
+
+use ajour_core::config::{load_config, CloudSync, SyncRemote};
+use ajour_core::error::ClientError;
+use ajour_core::fs::PersistentData;
+use ajour_core::localization::{localized_string, localized_string_with};
+use ajour_core::sync::{fetch_snapshot, push_snapshot, sync_state, SyncSnapshot, SyncState};
+use ajour_core::Result;
+
+use async_std::task;
+
+/// Syncs the config and the addons with a remote, so several machines are set up
+/// the same
+///
+/// Pushing or pulling fails if both sides changed since the last sync, unless
+/// `force` is passed. Pulled addons are installed if they are missing, but local
+/// addons aren't removed.
+pub fn sync(action: SyncAction, output: OutputFormat) -> Result<()> {
+    task::block_on(async {
+        let mut config = load_config().await?;
+
+        let (remote, force, is_push) = match action {
+            SyncAction::Setup { remote } => {
+                let remote = match remote.as_str() {
+                    "off" => None,
+                    remote => Some(remote.parse::<SyncRemote>()?),
+                };
+
+                match &remote {
+                    Some(remote) => log::info!(
+                        "{}",
+                        localized_string_with("cli-syncing-with", &[("remote", &remote)])
+                    ),
+                    None => log::info!("{}", localized_string("cli-syncing-off")),
+                }
+
+                config.cloud_sync = CloudSync {
+                    remote: remote.clone(),
+                    last_synced: None,
+                };
+                config.save()?;
+
+                return print_json(
+                    output,
+                    &serde_json::json!({ "remote": remote.map(|r| r.to_string()) }),
+                );
+            }
+            SyncAction::Push { force } => (config.cloud_sync.remote.clone(), force, true),
+            SyncAction::Pull { force } => (config.cloud_sync.remote.clone(), force, false),
+        };
+
+        let remote =
+            remote.ok_or_else(|| ClientError::Custom(localized_string("cli-no-sync-remote")))?;
+
+        let local = SyncSnapshot::new(&config, collect_export(&config).await?)?;
+        let remote_snapshot = fetch_snapshot(&remote, &config.tokens).await?;
+
+        let local_fingerprint = local.fingerprint();
+        let remote_fingerprint = remote_snapshot.as_ref().map(SyncSnapshot::fingerprint);
+
+        let state = sync_state(
+            config.cloud_sync.last_synced.as_deref(),
+            &local_fingerprint,
+            remote_fingerprint.as_deref(),
+        );
+
+        log::info!(
+            "{}",
+            localized_string_with("cli-sync-state", &[("remote", &remote), ("state", &state)])
+        );
+
+        // The first pull of a machine is merged, as nothing was synced before.
+        let is_first_pull = !is_push && config.cloud_sync.last_synced.is_none();
+
+        let is_blocked = match (state, is_push) {
+            _ if is_first_pull => false,
+            (SyncState::Conflict, _) => true,
+            (SyncState::RemoteChanged, true) | (SyncState::LocalChanged, false) => true,
+            _ => false,
+        };
+
+        if is_blocked && !force {
+            let advice = if is_push {
+                localized_string("cli-sync-pull-first")
+            } else {
+                localized_string("cli-sync-push-first")
+            };
+
+            return Err(ClientError::Custom(localized_string_with(
+                "cli-sync-blocked",
+                &[("remote", &remote), ("state", &state), ("advice", &advice)],
+            )));
+        }
+
+        if is_push {
+            if state != SyncState::UpToDate {
+                config.cloud_sync.remote =
+                    Some(push_snapshot(&remote, &config.tokens, &local).await?);
+            }
+
+            config.cloud_sync.last_synced = Some(local_fingerprint);
+            config.save()?;
+
+            log::info!(
+                "{}",
+                localized_string_with(
+                    "cli-pushed-addons",
+                    &[("count", &local.addons.addons.len()), ("remote", &remote)]
+                )
+            );
+
+            return print_json(
+                output,
+                &serde_json::json!({
+                    "remote": remote.to_string(),
+                    "state": state.as_str(),
+                    "addons": local.addons.addons.len(),
+                }),
+            );
+        }
+
+        let snapshot = remote_snapshot.ok_or_else(|| {
+            ClientError::Custom(localized_string_with(
+                "cli-nothing-pushed",
+                &[("remote", &remote)],
+            ))
+        })?;
+
+        if state != SyncState::UpToDate {
+            config = snapshot.apply(&config)?;
+        }
+
+        config.cloud_sync.last_synced = remote_fingerprint;
+        config.save()?;
+
+        log::info!(
+            "{}",
+            localized_string_with(
+                "cli-pulled-addons",
+                &[("count", &snapshot.addons.addons.len())]
+            )
+        );
+
+        install_export(&snapshot.addons, output).await
+    })
+}
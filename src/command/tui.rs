@@ -0,0 +1,581 @@
+use super::output::{AddonResult, Outcome};
+use super::read_addons;
+use super::update_addons::{update_addon, update_all};
+use crate::log_error;
+
+use ajour_core::addon::{Addon, AddonState};
+use ajour_core::config::{load_config, Flavor};
+use ajour_core::error::ClientError;
+use ajour_core::game::running_flavors;
+use ajour_core::hooks::{run_hook, HookEvent};
+use ajour_core::localization::{localized_string, localized_string_with};
+use ajour_core::logs::{append_log, log_file, LogLine};
+use ajour_core::parse::FingerprintCollection;
+use ajour_core::Result;
+
+use async_std::sync::{Arc, Mutex};
+use async_std::task;
+
+use crossterm::cursor::Show;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+
+use futures::Future;
+
+use isahc::config::RedirectPolicy;
+use isahc::prelude::*;
+
+use tui::backend::CrosstermBackend;
+use tui::layout::{Constraint, Direction, Layout};
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Paragraph, Row, Table, TableState};
+use tui::{Frame, Terminal};
+
+use std::io::{self, Read, Seek, SeekFrom, Stdout};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::Duration;
+
+/// How long to wait for a key before drawing again, so the results of work done
+/// in the background show up.
+const TICK: Duration = Duration::from_millis(250);
+
+/// Amount of addons updated at the same time by update all.
+const CONCURRENCY: usize = 4;
+
+const COLUMNS: [Constraint; 5] = [
+    Constraint::Percentage(35),
+    Constraint::Percentage(15),
+    Constraint::Percentage(15),
+    Constraint::Percentage(10),
+    Constraint::Percentage(25),
+];
+
+/// What happened to an addon since the list was loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Status {
+    Idle,
+    Updating,
+    Updated,
+    Failed(String),
+}
+
+struct Entry {
+    flavor: Flavor,
+    addon: Addon,
+    status: Status,
+}
+
+impl Entry {
+    fn status_text(&self) -> String {
+        match &self.status {
+            Status::Updating => localized_string("updating"),
+            Status::Updated => localized_string("updated"),
+            Status::Failed(e) => localized_string_with("failed-with", &[("error", e)]),
+            Status::Idle => localized_string(match self.addon.state {
+                AddonState::Updatable => "update-available",
+                AddonState::Ignored => "ignored",
+                AddonState::Pinned => "pinned",
+                AddonState::Symlinked => "symlinked",
+                AddonState::Corrupted => "corrupted",
+                AddonState::Unknown => "unknown",
+                _ => "up-to-date",
+            }),
+        }
+    }
+
+    fn style(&self) -> Style {
+        let color = match &self.status {
+            Status::Updating => Color::Cyan,
+            Status::Updated => Color::Green,
+            Status::Failed(_) => Color::Red,
+            Status::Idle if self.addon.state == AddonState::Updatable => Color::Yellow,
+            Status::Idle => Color::Reset,
+        };
+
+        Style::default().fg(color)
+    }
+}
+
+/// Results of the work done in the background.
+enum Work {
+    Loaded(Result<Vec<(Flavor, Addon)>>),
+    Updated(Flavor, String, Result<()>),
+    UpdatedAll(Result<String>),
+}
+
+struct App {
+    entries: Vec<Entry>,
+    table_state: TableState,
+    /// Shown in the footer, eg. the summary of update all.
+    message: String,
+    /// Amount of work running in the background.
+    pending: usize,
+    /// Set once quitting was asked for while updates are running.
+    quit_requested: bool,
+    show_log: bool,
+    log: Vec<LogLine>,
+    /// Position in the log file up to which it was read into `log`.
+    log_offset: u64,
+    sender: Sender<Work>,
+    fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
+    shared_client: Arc<HttpClient>,
+}
+
+impl App {
+    fn new(sender: Sender<Work>) -> Self {
+        App {
+            entries: vec![],
+            table_state: TableState::default(),
+            message: String::new(),
+            pending: 0,
+            quit_requested: false,
+            show_log: false,
+            log: vec![],
+            log_offset: 0,
+            sender,
+            // Fingerprint cache will be fetched during `read_addon_directory`
+            fingerprint_collection: Default::default(),
+            // API request will get limited to 6 per host
+            shared_client: Arc::new(
+                HttpClient::builder()
+                    .redirect_policy(RedirectPolicy::Follow)
+                    .max_connections_per_host(6)
+                    .build()
+                    .unwrap(),
+            ),
+        }
+    }
+
+    /// Runs `work` on its own thread, the result is handled by `finish`.
+    fn spawn<F, Fut>(&mut self, work: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Work>,
+    {
+        let sender = self.sender.clone();
+        self.pending += 1;
+
+        thread::spawn(move || {
+            let _ = sender.send(task::block_on(work()));
+        });
+    }
+
+    fn refresh(&mut self) {
+        if self.pending > 0 {
+            self.message = localized_string("cli-wait-for-updates");
+            return;
+        }
+
+        self.message = localized_string("cli-checking-for-updates");
+
+        let fingerprint_collection = self.fingerprint_collection.clone();
+        self.spawn(move || async move { Work::Loaded(load_addons(fingerprint_collection).await) });
+    }
+
+    fn update_selected(&mut self) {
+        let entry = match self.table_state.selected() {
+            Some(index) => &mut self.entries[index],
+            None => return,
+        };
+
+        if entry.addon.state != AddonState::Updatable || entry.status != Status::Idle {
+            return;
+        }
+
+        // Addons changed while the game is open end up half loaded.
+        if running_flavors().contains(&entry.flavor) {
+            self.message = ClientError::GameRunning(entry.flavor).user_message();
+            return;
+        }
+
+        entry.status = Status::Updating;
+
+        let shared_client = self.shared_client.clone();
+        let fingerprint_collection = self.fingerprint_collection.clone();
+        let flavor = entry.flavor;
+        let addon = entry.addon.clone();
+
+        self.spawn(move || async move {
+            let id = addon.primary_folder_id.clone();
+            let result = update_single(shared_client, fingerprint_collection, flavor, addon).await;
+
+            Work::Updated(flavor, id, result)
+        });
+    }
+
+    fn update_all(&mut self) {
+        if self.pending > 0 {
+            self.message = localized_string("cli-wait-for-updates");
+            return;
+        }
+
+        for entry in self.entries.iter_mut() {
+            if entry.addon.state == AddonState::Updatable && entry.status == Status::Idle {
+                entry.status = Status::Updating;
+            }
+        }
+
+        self.message = localized_string("cli-updating");
+
+        self.spawn(|| async {
+            let result = update_all(CONCURRENCY, false, false)
+                .await
+                .map(|(report, _)| report.summary());
+
+            Work::UpdatedAll(result)
+        });
+    }
+
+    fn finish(&mut self, work: Work) {
+        self.pending -= 1;
+
+        match work {
+            Work::Loaded(Ok(addons)) => {
+                let num_updatable = addons
+                    .iter()
+                    .filter(|(_, addon)| addon.state == AddonState::Updatable)
+                    .count();
+                self.message = localized_string_with(
+                    "cli-tui-summary",
+                    &[("count", &addons.len()), ("updatable", &num_updatable)],
+                );
+
+                self.entries = addons
+                    .into_iter()
+                    .map(|(flavor, addon)| Entry {
+                        flavor,
+                        addon,
+                        status: Status::Idle,
+                    })
+                    .collect();
+
+                let selected = match self.table_state.selected() {
+                    _ if self.entries.is_empty() => None,
+                    Some(index) => Some(index.min(self.entries.len() - 1)),
+                    None => Some(0),
+                };
+                self.table_state.select(selected);
+            }
+            Work::Loaded(Err(e)) => {
+                log_error(&e);
+                self.message = e.user_message();
+            }
+            Work::Updated(flavor, id, result) => {
+                let entry = self
+                    .entries
+                    .iter_mut()
+                    .find(|e| e.flavor == flavor && e.addon.primary_folder_id == id);
+
+                if let Some(entry) = entry {
+                    entry.status = match result {
+                        Ok(_) => Status::Updated,
+                        Err(e) => {
+                            log_error(&e);
+                            Status::Failed(e.user_message())
+                        }
+                    };
+                }
+            }
+            Work::UpdatedAll(result) => {
+                match result {
+                    Ok(summary) => self.message = summary,
+                    Err(e) => {
+                        log_error(&e);
+                        self.message = e.user_message();
+                    }
+                }
+
+                // Reloaded, so the list shows the installed versions.
+                let message = std::mem::take(&mut self.message);
+                self.refresh();
+                self.message = message;
+            }
+        }
+    }
+
+    /// Handles a key, returning `false` once Ajour should quit.
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Char('q') | KeyCode::Esc => {
+                // Quitting halfway leaves the addons being updated half written.
+                if self.pending == 0 || self.quit_requested {
+                    return false;
+                }
+
+                self.quit_requested = true;
+                self.message = localized_string("cli-tui-quit-while-updating");
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.select(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.select(1),
+            KeyCode::PageUp => self.select(-10),
+            KeyCode::PageDown => self.select(10),
+            KeyCode::Char('r') => self.refresh(),
+            KeyCode::Char('u') | KeyCode::Enter => self.update_selected(),
+            KeyCode::Char('a') => self.update_all(),
+            KeyCode::Char('l') => self.show_log = !self.show_log,
+            _ => {}
+        }
+
+        true
+    }
+
+    /// Moves the selection by `offset` rows, stopping at the first and last one.
+    fn select(&mut self, offset: isize) {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let index = self.table_state.selected().unwrap_or(0) as isize + offset;
+        let index = index.max(0).min(self.entries.len() as isize - 1);
+
+        self.table_state.select(Some(index as usize));
+    }
+
+    /// Reads what was written to the log since it was last read. The log is read
+    /// again from the start once it shrinks, eg. after it was rotated.
+    fn read_log(&mut self) {
+        let mut file = match std::fs::File::open(log_file()) {
+            Ok(file) => file,
+            Err(_) => return,
+        };
+        let len = match file.metadata() {
+            Ok(metadata) => metadata.len(),
+            Err(_) => return,
+        };
+
+        if len < self.log_offset {
+            self.log.clear();
+            self.log_offset = 0;
+        }
+
+        if len == self.log_offset {
+            return;
+        }
+
+        let mut bytes = vec![];
+        if file.seek(SeekFrom::Start(self.log_offset)).is_err()
+            || file.read_to_end(&mut bytes).is_err()
+        {
+            return;
+        }
+
+        // A line still being written is read once it's complete.
+        let complete = match bytes.iter().rposition(|&b| b == b'\n') {
+            Some(index) => index + 1,
+            None => return,
+        };
+
+        self.log_offset += complete as u64;
+        append_log(&mut self.log, &String::from_utf8_lossy(&bytes[..complete]));
+    }
+}
+
+/// Puts the terminal back the way it was, also if drawing panics.
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen, Show);
+    }
+}
+
+fn terminal_error(e: impl std::fmt::Display) -> ClientError {
+    ClientError::Custom(localized_string_with(
+        "cli-terminal-setup-failed",
+        &[("error", &e)],
+    ))
+}
+
+/// Shows the installed addons in the terminal, which can be refreshed and updated
+/// one by one or all at once, along with the log.
+///
+/// Everything is logged to the log of the data directory, as the terminal is
+/// taken by the addons.
+pub fn tui() -> Result<()> {
+    let (sender, receiver) = mpsc::channel();
+
+    let mut app = App::new(sender);
+    app.refresh();
+
+    enable_raw_mode().map_err(terminal_error)?;
+    let _guard = TerminalGuard;
+    execute!(io::stdout(), EnterAlternateScreen).map_err(terminal_error)?;
+
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+
+    loop {
+        while let Ok(work) = receiver.try_recv() {
+            app.finish(work);
+        }
+
+        if app.show_log {
+            app.read_log();
+        }
+
+        terminal.draw(|f| draw(f, &mut app))?;
+
+        if event::poll(TICK).map_err(terminal_error)? {
+            if let Event::Key(key) = event::read().map_err(terminal_error)? {
+                if !app.handle_key(key) {
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn draw(f: &mut Frame<CrosstermBackend<Stdout>>, app: &mut App) {
+    let mut constraints = vec![Constraint::Min(5)];
+    if app.show_log {
+        constraints.push(Constraint::Percentage(40));
+    }
+    constraints.push(Constraint::Length(1));
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(f.size());
+
+    let header = Row::new(vec![
+        localized_string("cli-column-name"),
+        localized_string("cli-column-installed"),
+        localized_string("column-remote"),
+        localized_string("cli-column-flavor"),
+        localized_string("column-status"),
+    ])
+    .style(Style::default().add_modifier(Modifier::BOLD));
+
+    let rows = app.entries.iter().map(|entry| {
+        let package = entry.addon.relevant_release_package();
+
+        Row::new(vec![
+            entry.addon.title().to_string(),
+            entry.addon.version().unwrap_or("-").to_string(),
+            package.map_or_else(|| "-".to_string(), |p| p.version.clone()),
+            entry.flavor.to_string(),
+            entry.status_text(),
+        ])
+        .style(entry.style())
+    });
+
+    let table = Table::new(rows)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", localized_string("cli-tui-addons"))),
+        )
+        .widths(&COLUMNS)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    f.render_stateful_widget(table, chunks[0], &mut app.table_state);
+
+    if app.show_log {
+        let area = chunks[1];
+        let height = area.height.saturating_sub(2) as usize;
+
+        let mut lines = app
+            .log
+            .iter()
+            .flat_map(|line| {
+                let color = match line.level {
+                    log::Level::Error => Color::Red,
+                    log::Level::Warn => Color::Yellow,
+                    _ => Color::Reset,
+                };
+
+                line.text()
+                    .lines()
+                    .map(|text| {
+                        Spans::from(Span::styled(text.to_string(), Style::default().fg(color)))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // Only the end of the log fits.
+        if lines.len() > height {
+            lines.drain(..lines.len() - height);
+        }
+
+        let log = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", localized_string("cli-tui-log"))),
+        );
+        f.render_widget(log, area);
+    }
+
+    let footer = Paragraph::new(Spans::from(vec![
+        Span::styled(
+            localized_string("cli-tui-keys"),
+            Style::default().add_modifier(Modifier::DIM),
+        ),
+        Span::raw("  "),
+        Span::raw(app.message.as_str()),
+    ]));
+    f.render_widget(footer, chunks[chunks.len() - 1]);
+}
+
+/// Reads the addons of all flavors with an addon directory, along with their
+/// update status.
+async fn load_addons(
+    fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
+) -> Result<Vec<(Flavor, Addon)>> {
+    let config = load_config().await?;
+
+    if config.wow.directory.is_none() {
+        return Err(ClientError::NoWowDirectory);
+    }
+
+    read_addons(&config, fingerprint_collection, &Flavor::ALL).await
+}
+
+/// Updates a single addon, running the hook of updated addons as `update` does.
+async fn update_single(
+    shared_client: Arc<HttpClient>,
+    fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
+    flavor: Flavor,
+    addon: Addon,
+) -> Result<()> {
+    let config = load_config().await?;
+
+    let addon_directory = config
+        .get_addon_directory_for_flavor(&flavor)
+        .ok_or(ClientError::NoWowDirectory)?;
+    let temp_directory = config
+        .get_download_directory_for_flavor(flavor)
+        .ok_or(ClientError::NoWowDirectory)?;
+
+    let addon_result = AddonResult::new(&addon, flavor, Outcome::Updated);
+
+    update_addon(
+        (
+            shared_client,
+            fingerprint_collection,
+            flavor,
+            addon,
+            temp_directory,
+            addon_directory,
+        ),
+        None,
+    )
+    .await?;
+
+    run_hook(
+        &config.hooks,
+        HookEvent::AddonUpdated,
+        serde_json::to_value(&addon_result)?,
+    );
+
+    Ok(())
+}
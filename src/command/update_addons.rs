@@ -0,0 +1,772 @@
+#![allow(clippy::type_complexity)]
+
+use super::output::{print_json, print_result, set_exit_code, AddonResult, ExitCode, Outcome};
+use super::progress::{self, Phase};
+use super::read_addons;
+use crate::cli::OutputFormat;
+use crate::instance::{self, Request};
+use crate::log_error;
+
+use ajour_core::addon::{Addon, AddonState};
+use ajour_core::backup::{backup_folders, flavor_backup_folders, prune_backups, BackupContent};
+use ajour_core::config::{load_config, Flavor, WebhookEvent};
+use ajour_core::download::{Download, DownloadManager};
+use ajour_core::error::ClientError;
+use ajour_core::fs::{install_addon, snapshot};
+use ajour_core::game::running_flavors;
+use ajour_core::hooks::{run_hook, HookEvent};
+use ajour_core::localization::{localized_string, localized_string_with};
+use ajour_core::network::download_addon;
+use ajour_core::parse::{update_addon_fingerprint, FingerprintCollection};
+use ajour_core::update::{check_addons, skip_reason, UpdateReport};
+use ajour_core::utility::fuzzy_match;
+use ajour_core::webhook::{send_webhook, WebhookAddon};
+use ajour_core::Result;
+
+use async_std::sync::{Arc, Mutex};
+use async_std::task;
+
+use futures::future::join_all;
+use futures::stream::{self, StreamExt};
+
+use isahc::config::RedirectPolicy;
+use isahc::prelude::*;
+
+use serde_json::json;
+
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Updates all addons with an update available
+///
+/// At most `concurrency` addons are downloaded and unpacked at the same time. Each
+/// addon is updated on its own task, so unpacking one addon overlaps with downloading
+/// the next.
+///
+/// Fails if World of Warcraft is running for a flavor with updates, unless `wait`
+/// is set, in which case updating starts once the game exits.
+///
+/// If the GUI is open, it's handed the update instead so both don't write to the
+/// same folders. Only results printed as JSON need this to update by itself.
+///
+/// In a terminal, the progress of each addon is drawn below the log.
+///
+/// If `dry_run` is set, the addons which would be updated are printed instead,
+/// without downloading or writing anything.
+pub fn update_all_addons(
+    concurrency: usize,
+    wait: bool,
+    dry_run: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    if dry_run {
+        log::info!("{}", localized_string("cli-checking-for-updates"));
+
+        return task::block_on(preview_all(output));
+    }
+
+    if output == OutputFormat::Text && instance::send(Request::UpdateAll) {
+        log::info!("{}", localized_string("cli-ajour-is-open"));
+
+        return Ok(());
+    }
+
+    log::info!("{}", localized_string("cli-checking-for-updates"));
+
+    task::block_on(async {
+        let show_progress = output == OutputFormat::Text && progress::is_supported();
+        let (_, addon_results) = update_all(concurrency, wait, show_progress).await?;
+
+        if addon_results.iter().any(|r| r.result == Outcome::Failed) {
+            set_exit_code(ExitCode::PartialFailure);
+        }
+
+        print_json(output, &addon_results)
+    })
+}
+
+/// Updates the installed addon matching `query` if it has an update available
+///
+/// `query` is matched against the folder name and title of the addons of `flavor`,
+/// or all flavors. Exact matches are preferred over titles containing it, and those
+/// over titles with its letters in order, eg. "dbm" for Deadly Boss Mods. If several
+/// addons match equally well, the user is asked to choose one.
+///
+/// If `dry_run` is set, the update is only printed.
+pub fn update_addon_by_name(
+    query: String,
+    flavor: Option<Flavor>,
+    wait: bool,
+    dry_run: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    task::block_on(async {
+        let config = load_config().await?;
+
+        if config.wow.directory.is_none() {
+            return Err(ClientError::NoWowDirectory);
+        }
+
+        // Fingerprint cache will be fetched during `read_addon_directory`
+        let fingerprint_collection: Arc<Mutex<_>> = Default::default();
+
+        let flavors = flavor.map_or_else(|| config.managed_flavors(), |f| vec![f]);
+        let addons = read_addons(&config, fingerprint_collection.clone(), &flavors).await?;
+
+        let (flavor, addon) = choose_addon(&query, addons, output)?;
+
+        if addon.state != AddonState::Updatable {
+            let mut addon_result = AddonResult::new(&addon, flavor, Outcome::Skipped);
+
+            match skip_reason(&addon, &config.addons, flavor) {
+                Some(reason) => {
+                    log::info!(
+                        "{}",
+                        localized_string_with(
+                            "cli-addon-skipped",
+                            &[("title", &addon.title()), ("reason", &reason)]
+                        )
+                    );
+                    addon_result.skip(reason);
+                }
+                None => {
+                    log::info!(
+                        "{}",
+                        localized_string_with("cli-addon-up-to-date", &[("title", &addon.title())])
+                    );
+                    addon_result.reason = Some("up to date".to_string());
+                }
+            }
+
+            return print_json(output, &addon_result);
+        }
+
+        if dry_run {
+            let addon_result = AddonResult::new(&addon, flavor, Outcome::UpdateAvailable);
+
+            print_result(
+                output,
+                &localized_string_with(
+                    "cli-would-be-updated",
+                    &[
+                        ("title", &addon_result.title),
+                        (
+                            "old",
+                            &addon_result.old_version.as_deref().unwrap_or_default(),
+                        ),
+                        (
+                            "new",
+                            &addon_result.new_version.as_deref().unwrap_or_default(),
+                        ),
+                    ],
+                ),
+            );
+            set_exit_code(ExitCode::UpdatesAvailable);
+
+            return print_json(output, &addon_result);
+        }
+
+        // Addons changed while the game is open end up half loaded.
+        if running_flavors().contains(&flavor) {
+            if !wait {
+                log::info!("{}", localized_string("cli-pass-wait"));
+
+                return Err(ClientError::GameRunning(flavor));
+            }
+
+            log::info!(
+                "{}",
+                localized_string_with("cli-waiting-for-game", &[("flavor", &flavor)])
+            );
+
+            while running_flavors().contains(&flavor) {
+                task::sleep(Duration::from_secs(5)).await;
+            }
+        }
+
+        // Only returns None if the path isn't set in the config
+        let addon_directory = config
+            .get_addon_directory_for_flavor(&flavor)
+            .expect("Expected a valid path");
+        let temp_directory = config
+            .get_download_directory_for_flavor(flavor)
+            .expect("Expected a valid path");
+
+        let shared_client = Arc::new(
+            HttpClient::builder()
+                .redirect_policy(RedirectPolicy::Follow)
+                .build()
+                .unwrap(),
+        );
+
+        let mut addon_result = AddonResult::new(&addon, flavor, Outcome::Updated);
+
+        log::info!(
+            "{}",
+            localized_string_with(
+                "cli-updating-addon",
+                &[
+                    ("title", &addon.title()),
+                    (
+                        "old",
+                        &addon_result.old_version.as_deref().unwrap_or_default()
+                    ),
+                    (
+                        "new",
+                        &addon_result.new_version.as_deref().unwrap_or_default()
+                    )
+                ]
+            )
+        );
+
+        let result = update_addon(
+            (
+                shared_client,
+                fingerprint_collection,
+                flavor,
+                addon,
+                temp_directory,
+                addon_directory,
+            ),
+            None,
+        )
+        .await;
+
+        match result {
+            Ok(_) => {
+                log::info!(
+                    "{}",
+                    localized_string_with("cli-addon-updated", &[("title", &addon_result.title)])
+                );
+
+                run_hook(
+                    &config.hooks,
+                    HookEvent::AddonUpdated,
+                    serde_json::to_value(&addon_result)?,
+                );
+            }
+            Err(e) => {
+                log_error(&e);
+
+                addon_result.fail(&e);
+                set_exit_code(ExitCode::PartialFailure);
+            }
+        }
+
+        print_json(output, &addon_result)
+    })
+}
+
+/// Prints which addons `update_all_addons` would update and skip, and what else it
+/// would do first, without downloading or writing anything.
+async fn preview_all(output: OutputFormat) -> Result<()> {
+    let config = load_config().await?;
+
+    if config.wow.directory.is_none() {
+        return Err(ClientError::NoWowDirectory);
+    }
+
+    // Fingerprint cache will be fetched during `read_addon_directory`
+    let fingerprint_collection: Arc<Mutex<_>> = Default::default();
+
+    let check = check_addons(fingerprint_collection, &config, &config.managed_flavors()).await;
+
+    for (flavor, error) in check.failed.iter() {
+        log::error!(
+            "{}",
+            localized_string_with(
+                "cli-couldnt-check",
+                &[("flavor", &flavor), ("error", &error.user_message())]
+            )
+        );
+    }
+
+    let mut addon_results = vec![];
+
+    for (flavor, addon, reason) in check.skipped.iter() {
+        log::info!(
+            "\t{}",
+            localized_string_with(
+                "cli-skipped-as",
+                &[
+                    ("id", &addon.primary_folder_id),
+                    ("flavor", &flavor),
+                    ("reason", &reason)
+                ]
+            )
+        );
+
+        let mut addon_result = AddonResult::new(addon, *flavor, Outcome::Skipped);
+        addon_result.skip(*reason);
+        addon_results.push(addon_result);
+    }
+
+    print_result(
+        output,
+        &localized_string_with(
+            "cli-addons-would-be-updated",
+            &[("count", &check.updatable.len())],
+        ),
+    );
+
+    for (flavor, addon) in check.updatable.iter() {
+        let addon_result = AddonResult::new(addon, *flavor, Outcome::UpdateAvailable);
+
+        print_result(
+            output,
+            &format!(
+                "\t{} - {}, {} -> {}",
+                &addon_result.id,
+                flavor,
+                addon_result.old_version.as_deref().unwrap_or_default(),
+                addon_result.new_version.as_deref().unwrap_or_default()
+            ),
+        );
+
+        addon_results.push(addon_result);
+    }
+
+    if !check.updatable.is_empty() {
+        if config.backups.before_update_all && config.backup_directory.is_some() {
+            log::info!("{}", localized_string("cli-backup-would-be-made"));
+        }
+
+        let running = running_flavors();
+        if let Some((flavor, _)) = check.updatable.iter().find(|(f, _)| running.contains(f)) {
+            log::info!(
+                "{}",
+                localized_string_with("cli-game-running-pass-wait", &[("flavor", &flavor)])
+            );
+        }
+
+        set_exit_code(ExitCode::UpdatesAvailable);
+    }
+
+    print_json(output, &addon_results)
+}
+
+/// Picks the addon best matching `query`, asking the user to choose if several
+/// match equally well.
+fn choose_addon(
+    query: &str,
+    addons: Vec<(Flavor, Addon)>,
+    output: OutputFormat,
+) -> Result<(Flavor, Addon)> {
+    let query_lowercase = query.trim().to_lowercase();
+
+    // Ranks how well an addon matches, higher is better.
+    let rank = |addon: &Addon| {
+        let title = addon.title().to_lowercase();
+
+        if addon.primary_folder_id.to_lowercase() == query_lowercase || title == query_lowercase {
+            Some(2)
+        } else if title.contains(&query_lowercase) {
+            Some(1)
+        } else if fuzzy_match(&query_lowercase, &title) {
+            Some(0)
+        } else {
+            None
+        }
+    };
+
+    let best = addons.iter().filter_map(|(_, a)| rank(a)).max();
+
+    let mut matches = addons
+        .into_iter()
+        .filter(|(_, a)| best.is_some() && rank(a) == best)
+        .collect::<Vec<_>>();
+
+    match matches.len() {
+        0 => Err(ClientError::Custom(localized_string_with(
+            "cli-no-addon-matches",
+            &[("query", &query)],
+        ))),
+        1 => Ok(matches.remove(0)),
+        // The prompt would end up in the json output, or nobody is there to answer.
+        _ if output == OutputFormat::Json || !atty::is(atty::Stream::Stdin) => {
+            let titles = matches
+                .iter()
+                .map(|(flavor, a)| format!("{} ({}, {})", a.title(), a.primary_folder_id, flavor))
+                .collect::<Vec<_>>();
+
+            Err(ClientError::Custom(localized_string_with(
+                "cli-matches-several",
+                &[("query", &query), ("addons", &titles.join(", "))],
+            )))
+        }
+        _ => {
+            println!(
+                "{}",
+                localized_string_with("cli-matches-several-choose", &[("query", &query)])
+            );
+            for (i, (flavor, addon)) in matches.iter().enumerate() {
+                println!(
+                    "  {}) {} ({}, {})",
+                    i + 1,
+                    addon.title(),
+                    addon.primary_folder_id,
+                    flavor
+                );
+            }
+            print!(
+                "{} ",
+                localized_string_with("cli-which-one", &[("count", &matches.len())])
+            );
+            io::stdout().flush()?;
+
+            let mut answer = String::new();
+            io::stdin().read_line(&mut answer)?;
+
+            match answer.trim().parse::<usize>() {
+                Ok(n) if n >= 1 && n <= matches.len() => Ok(matches.remove(n - 1)),
+                _ => Err(ClientError::Custom(localized_string("cli-no-addon-chosen"))),
+            }
+        }
+    }
+}
+
+/// Updates all addons as `update_all_addons` does, returning the report along with
+/// the result of each addon. Progress is drawn if `show_progress` is set.
+pub(super) async fn update_all(
+    concurrency: usize,
+    wait: bool,
+    show_progress: bool,
+) -> Result<(UpdateReport, Vec<AddonResult>)> {
+    let config = load_config().await?;
+
+    // Fingerprint cache will be fetched during `read_addon_directory`
+    let fingerprint_collection: Arc<Mutex<_>> = Default::default();
+
+    let mut addons_to_update = vec![];
+
+    // API request will get limited to 6 per host
+    let shared_client = Arc::new(
+        HttpClient::builder()
+            .redirect_policy(RedirectPolicy::Follow)
+            .max_connections_per_host(6)
+            .build()
+            .unwrap(),
+    );
+
+    if config.wow.directory.is_none() {
+        return Err(ClientError::NoWowDirectory);
+    }
+
+    // Check addons of all flavors for updates, the same way Ajour does. A flavor
+    // which can't be parsed doesn't keep the others from updating.
+    let check = check_addons(
+        fingerprint_collection.clone(),
+        &config,
+        &config.managed_flavors(),
+    )
+    .await;
+
+    for (flavor, error) in check.failed.iter() {
+        log::error!(
+            "{}",
+            localized_string_with(
+                "cli-couldnt-check",
+                &[("flavor", &flavor), ("error", &error.user_message())]
+            )
+        );
+    }
+
+    // Addons changed while the game is open end up half loaded.
+    let mut is_waiting = false;
+    loop {
+        let running = running_flavors();
+        let blocking = match check.updatable.iter().find(|(f, _)| running.contains(f)) {
+            Some((flavor, _)) => *flavor,
+            None => break,
+        };
+
+        if !wait {
+            log::info!("{}", localized_string("cli-pass-wait"));
+
+            return Err(ClientError::GameRunning(blocking));
+        }
+
+        if !is_waiting {
+            log::info!(
+                "{}",
+                localized_string_with("cli-waiting-for-game", &[("flavor", &blocking)])
+            );
+            is_waiting = true;
+        }
+
+        task::sleep(Duration::from_secs(5)).await;
+    }
+
+    let mut report = UpdateReport::default();
+    let mut addon_results = vec![];
+
+    for (flavor, addon, reason) in check.skipped.iter() {
+        report.skip(addon.title(), *reason);
+
+        let mut addon_result = AddonResult::new(addon, *flavor, Outcome::Skipped);
+        addon_result.skip(*reason);
+        addon_results.push(addon_result);
+    }
+
+    for (flavor, addon) in check.updatable {
+        report.start(flavor, &addon.primary_folder_id);
+
+        // Only returns None if the path isn't set in the config
+        let addon_directory = config
+            .get_addon_directory_for_flavor(&flavor)
+            .expect("Expected a valid path");
+
+        // Directory to temporarily save downloaded addon
+        let temp_directory = config
+            .get_download_directory_for_flavor(flavor)
+            .expect("Expected a valid path");
+
+        addons_to_update.push((
+            shared_client.clone(),
+            fingerprint_collection.clone(),
+            flavor,
+            addon,
+            temp_directory,
+            addon_directory,
+        ));
+    }
+
+    let num_updates = addons_to_update.len();
+
+    log::info!(
+        "{}",
+        localized_string_with("cli-addons-updatable", &[("count", &num_updates)])
+    );
+
+    addons_to_update
+        .iter()
+        .for_each(|(_, _, flavor, addon, ..)| {
+            let current_version = addon.version().unwrap_or_default();
+            let new_version = addon
+                .relevant_release_package()
+                .map(|p| p.version.clone())
+                .unwrap_or_default();
+
+            log::info!(
+                "\t{} - {}, {} -> {}",
+                &addon.primary_folder_id,
+                flavor,
+                current_version,
+                new_version
+            );
+        });
+
+    if num_updates > 0 {
+        // Backup before updating if chosen in the config
+        if config.backups.before_update_all {
+            if let Some(dest) = config.backup_directory.as_ref() {
+                log::info!("{}", localized_string("cli-backing-up-before-update"));
+
+                let src_folders =
+                    flavor_backup_folders(&config, &Flavor::ALL[..], &BackupContent::DEFAULT);
+                backup_folders(src_folders, dest.clone()).await?;
+                prune_backups(dest, &config.backups)?;
+            }
+        }
+
+        let addons = addons_to_update
+            .iter()
+            .map(|(_, _, flavor, addon, ..)| {
+                AddonResult::new(addon, *flavor, Outcome::UpdateAvailable)
+            })
+            .collect::<Vec<_>>();
+        run_hook(
+            &config.hooks,
+            HookEvent::UpdateAllStarted,
+            json!({ "count": num_updates, "addons": addons }),
+        );
+
+        log::info!("{}", localized_string("cli-updating"));
+
+        // The updates can be undone together from Ajour.
+        snapshot::start_batch();
+    }
+
+    let download_manager = DownloadManager::new(concurrency.max(1));
+
+    if show_progress && num_updates > 0 {
+        let addons = addons_to_update
+            .iter()
+            .map(|(_, _, flavor, addon, ..)| {
+                (
+                    (*flavor, addon.primary_folder_id.clone()),
+                    addon.title().to_string(),
+                )
+            })
+            .collect();
+
+        progress::start(download_manager.clone(), addons);
+    }
+
+    // Call `update_addon` on each addon, running up to `concurrency` at once
+    let results = stream::iter(addons_to_update.into_iter().map(|update| {
+        let addon_result = AddonResult::new(&update.3, update.2, Outcome::Updated);
+        let key = (update.2, update.3.primary_folder_id.clone());
+        let download = download_manager.queue(key.clone());
+
+        task::spawn(async move {
+            let result = update_addon(update, Some(download)).await;
+            progress::set_phase(&key, Phase::Done);
+
+            if result.is_ok() {
+                log::info!(
+                    "{}",
+                    localized_string_with(
+                        "cli-updated-to",
+                        &[
+                            ("title", &addon_result.title),
+                            (
+                                "version",
+                                &addon_result.new_version.as_deref().unwrap_or_default()
+                            )
+                        ]
+                    )
+                );
+            }
+
+            (addon_result, result)
+        })
+    }))
+    .buffer_unordered(concurrency.max(1))
+    .collect::<Vec<_>>()
+    .await;
+
+    progress::finish();
+
+    for (mut addon_result, result) in results {
+        // Log any errors updating an addon, the others are updated regardless
+        if let Err(e) = &result {
+            log_error(e);
+
+            addon_result.fail(e);
+        } else {
+            run_hook(
+                &config.hooks,
+                HookEvent::AddonUpdated,
+                serde_json::to_value(&addon_result)?,
+            );
+        }
+
+        report.finish(
+            addon_result.flavor,
+            &addon_result.id,
+            &addon_result.title,
+            result.as_ref().err(),
+        );
+        addon_results.push(addon_result);
+    }
+
+    if num_updates > 0 {
+        run_hook(
+            &config.hooks,
+            HookEvent::UpdateAllFinished,
+            json!({
+                "updated": report.updated.len(),
+                "failed": report.failed.len(),
+                "skipped": report.skipped.len(),
+                "addons": addon_results,
+            }),
+        );
+
+        let updated = addon_results
+            .iter()
+            .filter(|r| r.result == Outcome::Updated)
+            .map(|r| WebhookAddon {
+                title: r.title.clone(),
+                flavor: Some(r.flavor),
+                old_version: r.old_version.clone(),
+                new_version: r.new_version.clone(),
+            })
+            .collect::<Vec<_>>();
+        if let Err(e) = send_webhook(&config.webhook, WebhookEvent::UpdatesApplied, &updated).await
+        {
+            log_error(&e);
+        }
+    }
+
+    if num_updates == 0 && report.skipped.is_empty() && check.failed.is_empty() {
+        log::info!("{}", localized_string("cli-all-up-to-date"));
+    } else {
+        log::info!("{}", report.summary());
+
+        for (title, reason) in report.failed.iter() {
+            log::error!(
+                "\t{}",
+                localized_string_with("addon-failed", &[("title", &title), ("reason", &reason)])
+            );
+        }
+
+        for (title, reason) in report.skipped.iter() {
+            log::info!(
+                "\t{}",
+                localized_string_with("addon-skipped", &[("title", &title), ("reason", &reason)])
+            );
+        }
+    }
+
+    Ok((report, addon_results))
+}
+
+/// Updates an addon
+///
+/// Downloads the latest file, extracts it and refingerprints the addon, saving it to the cache.
+/// The progress of the download is kept in `download`, if passed.
+pub(super) async fn update_addon(
+    (shared_client, fingerprint_collection, flavor, addon, temp_directory, addon_directory): (
+        Arc<HttpClient>,
+        Arc<Mutex<Option<FingerprintCollection>>>,
+        Flavor,
+        Addon,
+        PathBuf,
+        PathBuf,
+    ),
+    mut download: Option<Download>,
+) -> Result<()> {
+    let key = (flavor, addon.primary_folder_id.clone());
+    progress::set_phase(&key, Phase::Downloading);
+
+    // Download the update to the temp directory
+    download_addon(&shared_client, &addon, &temp_directory, download.as_mut()).await?;
+
+    // Makes room in the queue for the next download while this one is unpacked.
+    drop(download);
+    progress::set_phase(&key, Phase::Unpacking);
+
+    // Extracts addon from the downloaded archive to the addon directory and caches the archive
+    install_addon(&addon, flavor, &temp_directory, &addon_directory).await?;
+
+    // Stores each folder name we need to fingerprint
+    let mut folders_to_fingerprint = vec![];
+
+    // Store all folder names
+    folders_to_fingerprint.extend(addon.folders.iter().map(|f| {
+        (
+            fingerprint_collection.clone(),
+            flavor,
+            &addon_directory,
+            f.id.clone(),
+        )
+    }));
+
+    // Call `update_addon_fingerprint` on each folder concurrently
+    for result in join_all(folders_to_fingerprint.into_iter().map(
+        |(fingerprint_collection, flavor, addon_dir, addon_id)| {
+            update_addon_fingerprint(fingerprint_collection, flavor, addon_dir, addon_id)
+        },
+    ))
+    .await
+    {
+        if let Err(e) = result {
+            // Log any errors fingerprinting the folder
+            log_error(&e);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,227 @@
+use super::fingerprint_folders;
+use super::output::{print_json, print_result, set_exit_code, ExitCode};
+use super::update_addons::update_addon;
+use crate::cli::OutputFormat;
+use crate::log_error;
+
+use ajour_core::addon::Addon;
+use ajour_core::config::{load_config, Flavor};
+use ajour_core::error::ClientError;
+use ajour_core::fs::{archive::installed_archive, reinstall_addon};
+use ajour_core::localization::localized_string_with;
+use ajour_core::parse::{read_addon_directory, FingerprintCollection};
+use ajour_core::verify::{verify_addons, Verification};
+use ajour_core::Result;
+
+use async_std::sync::{Arc, Mutex};
+use async_std::task;
+
+use isahc::config::RedirectPolicy;
+use isahc::prelude::*;
+
+use serde::Serialize;
+
+use std::path::PathBuf;
+
+/// An installed addon as printed by `verify`.
+#[derive(Debug, Serialize)]
+struct VerifiedAddon {
+    id: String,
+    title: String,
+    flavor: Flavor,
+    version: Option<String>,
+    verification: Verification,
+    repaired: bool,
+    error: Option<String>,
+}
+
+/// Verifies the installed addons of the given flavors against their release packages
+///
+/// If no flavors are given, all flavors with an addon directory are verified. With
+/// `repair`, addons which don't match are reinstalled from the cached archive of the
+/// installed version, or downloaded again from their repository if there's none.
+pub fn verify(flavors: Vec<Flavor>, repair: bool, output: OutputFormat) -> Result<()> {
+    task::block_on(async {
+        let config = load_config().await?;
+
+        if config.wow.directory.is_none() {
+            return Err(ClientError::NoWowDirectory);
+        }
+
+        let flavors = if flavors.is_empty() {
+            Flavor::ALL.to_vec()
+        } else {
+            flavors
+        };
+
+        // Fingerprint cache will be fetched during `read_addon_directory`
+        let fingerprint_collection: Arc<Mutex<_>> = Default::default();
+
+        let shared_client = Arc::new(
+            HttpClient::builder()
+                .redirect_policy(RedirectPolicy::Follow)
+                .max_connections_per_host(6)
+                .build()
+                .unwrap(),
+        );
+
+        let mut verified = vec![];
+
+        for flavor in flavors {
+            let addon_directory = match config.get_addon_directory_for_flavor(&flavor) {
+                Some(dir) if dir.exists() => dir,
+                _ => continue,
+            };
+
+            let addons = read_addon_directory(
+                fingerprint_collection.clone(),
+                &addon_directory,
+                flavor,
+                config.addons.source_priority(&flavor),
+            )
+            .await?;
+
+            log::info!(
+                "{}",
+                localized_string_with(
+                    "cli-verifying",
+                    &[("count", &addons.len()), ("flavor", &flavor)]
+                )
+            );
+
+            for (id, verification) in verify_addons(&addons, flavor, &addon_directory).await {
+                let addon = match addons.iter().find(|a| a.primary_folder_id == id) {
+                    Some(addon) => addon,
+                    None => continue,
+                };
+
+                let mut verified_addon = VerifiedAddon {
+                    id,
+                    title: addon.title().to_string(),
+                    flavor,
+                    version: addon.version().map(str::to_string),
+                    verification,
+                    repaired: false,
+                    error: None,
+                };
+
+                if let Verification::Mismatched(files) = &verified_addon.verification {
+                    print_result(
+                        output,
+                        &format!(
+                            "\t{}",
+                            localized_string_with(
+                                "cli-mismatched-files",
+                                &[
+                                    ("id", &addon.primary_folder_id),
+                                    ("flavor", &flavor),
+                                    ("files", &files.join(", "))
+                                ]
+                            )
+                        ),
+                    );
+
+                    if repair {
+                        // Directory to temporarily save downloaded addon
+                        let temp_directory = config
+                            .get_download_directory_for_flavor(flavor)
+                            .expect("Expected a valid path");
+
+                        let result = repair_addon(
+                            shared_client.clone(),
+                            fingerprint_collection.clone(),
+                            flavor,
+                            addon,
+                            temp_directory,
+                            &addon_directory,
+                        )
+                        .await;
+
+                        match result {
+                            Ok(()) => verified_addon.repaired = true,
+                            Err(e) => {
+                                log_error(&e);
+
+                                verified_addon.error = Some(e.to_string());
+                            }
+                        }
+                    }
+                }
+
+                verified.push(verified_addon);
+            }
+        }
+
+        let num_mismatched = verified
+            .iter()
+            .filter(|a| a.verification.is_mismatched())
+            .count();
+        let num_unverifiable = verified
+            .iter()
+            .filter(|a| a.verification == Verification::Unverifiable)
+            .count();
+
+        if num_mismatched == 0 {
+            print_result(
+                output,
+                &localized_string_with("cli-all-match", &[("count", &num_unverifiable)]),
+            );
+        } else if repair {
+            let num_repaired = verified.iter().filter(|a| a.repaired).count();
+
+            print_result(
+                output,
+                &localized_string_with(
+                    "cli-mismatched-repaired",
+                    &[("count", &num_mismatched), ("repaired", &num_repaired)],
+                ),
+            );
+        } else {
+            print_result(
+                output,
+                &localized_string_with("cli-mismatched-run-repair", &[("count", &num_mismatched)]),
+            );
+        }
+
+        // Mismatches are only resolved once every one of them is repaired.
+        if verified
+            .iter()
+            .any(|a| a.verification.is_mismatched() && !a.repaired)
+        {
+            set_exit_code(ExitCode::PartialFailure);
+        }
+
+        print_json(output, &verified)
+    })
+}
+
+/// Reinstalls an addon which doesn't match its release package.
+async fn repair_addon(
+    shared_client: Arc<HttpClient>,
+    fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
+    flavor: Flavor,
+    addon: &Addon,
+    temp_directory: PathBuf,
+    addon_directory: &PathBuf,
+) -> Result<()> {
+    if installed_archive(addon, flavor)?.is_some() {
+        let folders = reinstall_addon(addon, flavor, addon_directory).await?;
+
+        fingerprint_folders(fingerprint_collection, flavor, addon_directory, &folders).await;
+
+        Ok(())
+    } else {
+        update_addon(
+            (
+                shared_client,
+                fingerprint_collection,
+                flavor,
+                addon.clone(),
+                temp_directory,
+                addon_directory.clone(),
+            ),
+            None,
+        )
+        .await
+    }
+}
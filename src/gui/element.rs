@@ -2,48 +2,94 @@
 
 use {
     super::{
-        style, AddonVersionKey, AjourMode, AjourState, BackupState, CatalogColumnKey,
-        CatalogColumnState, CatalogInstallStatus, CatalogRow, Changelog, ColumnKey, ColumnSettings,
-        ColumnState, DirectoryType, ExpandType, Interaction, Message, ReleaseChannel, ScaleState,
-        SortDirection, ThemeState,
+        style, AddonStatusFilter, AddonVersionKey, AddonsFilterState, AdvancedSettingsState,
+        AjourMode, AjourState, BackupState, CatalogColumnKey, CatalogColumnState,
+        CatalogInstallStatus, CatalogRow, ChangeSourceState, Changelog, CleanupState, ColumnKey,
+        ColumnSettings, ColumnState, DirectoryType, ExpandType, GameState, InstallFromUrlState,
+        InstallLinkState, InstallationState, Interaction, KeyboardState, LogsState, Message,
+        NotificationState, OnboardingState, OnboardingStep, PreviewedUpdate, ProfileState,
+        ReleaseChannel, ReleaseNotesState, ScaleState, ScheduledUpdateState, SelectionState,
+        SortDirection, ThemeState, ToastKind, ToastState, UpdatePreviewState, WagoState,
+        WhatsNewState,
     },
+    crate::tray,
     crate::VERSION,
     ajour_core::{
-        addon::{Addon, AddonState, Repository},
+        addon::{Addon, AddonDetails, AddonState, Repository},
         catalog::Catalog,
-        config::{Config, Flavor},
+        config::{
+            BackupSchedule, CheckInterval, Config, Flavor, RowDensity, SelfUpdateChannel, Token,
+            UpdateSchedule,
+        },
+        download::{DownloadProgress, DownloadStatus},
+        game::{ClientBuild, Compatibility},
+        link::InstallLink,
+        localization::{localized_string, localized_string_with, Language},
+        network,
+        self_update::PackageManager,
         theme::ColorPalette,
+        update::UpdateReport,
+        utility::AppRelease,
+        wago::AuraStatus,
     },
     chrono::prelude::*,
     iced::{
-        button, scrollable, Align, Button, Checkbox, Column, Container, Element,
-        HorizontalAlignment, Length, PickList, Row, Scrollable, Space, Text, VerticalAlignment,
+        button, pick_list, scrollable, text_input, Align, Button, Checkbox, Column, Container,
+        Element, HorizontalAlignment, Length, PickList, ProgressBar, Row, Scrollable, Space, Text,
+        TextInput, VerticalAlignment,
     },
+    log::Level,
     num_format::{Locale, ToFormattedString},
+    std::sync::atomic::{AtomicU16, Ordering},
+    std::time::SystemTime,
     widgets::{header, Header},
 };
 
 // Default values used on multiple elements.
-pub static DEFAULT_FONT_SIZE: u16 = 14;
+pub const DEFAULT_FONT_SIZE: u16 = 14;
 pub static DEFAULT_PADDING: u16 = 10;
 
+pub const MIN_FONT_SIZE: u16 = 10;
+pub const MAX_FONT_SIZE: u16 = 24;
+
+/// Font size chosen in the settings, which text is sized relative to.
+static FONT_SIZE: AtomicU16 = AtomicU16::new(DEFAULT_FONT_SIZE);
+
+/// Returns the font size of text, as chosen in the settings.
+pub fn font_size() -> u16 {
+    FONT_SIZE.load(Ordering::Relaxed)
+}
+
+pub fn set_font_size(size: u16) {
+    FONT_SIZE.store(size, Ordering::Relaxed);
+}
+
 /// Container for settings.
 pub fn settings_container<'a, 'b>(
     color_palette: ColorPalette,
     directory_button_state: &'a mut button::State,
+    installation_state: &'a mut InstallationState,
+    profile_state: &'a mut ProfileState,
     config: &Config,
     theme_state: &'a mut ThemeState,
+    language_picklist_state: &'a mut pick_list::State<Language>,
     scale_state: &'a mut ScaleState,
     backup_state: &'a mut BackupState,
+    notification_state: &'a mut NotificationState,
+    scheduled_update_state: &'a mut ScheduledUpdateState,
+    source_priority_picklist_state: &'a mut pick_list::State<Repository>,
+    self_update_channel_picklist_state: &'a mut pick_list::State<SelfUpdateChannel>,
+    token_input_states: &'a mut [text_input::State; 3],
+    advanced_settings_state: &'a mut AdvancedSettingsState,
     column_settings: &'a mut ColumnSettings,
     column_config: &'b [(ColumnKey, Length, bool)],
 ) -> Container<'a, Message> {
     // Title for the World of Warcraft directory selection.
-    let directory_info_text = Text::new("World of Warcraft directory").size(14);
+    let directory_info_text = Text::new(localized_string("wow-directory")).size(font_size());
 
     // Directory button for World of Warcraft directory selection.
     let directory_button_title_container =
-        Container::new(Text::new("Select Directory").size(DEFAULT_FONT_SIZE))
+        Container::new(Text::new(localized_string("select-directory")).size(font_size()))
             .width(Length::FillPortion(1))
             .center_x()
             .align_x(Align::Center);
@@ -62,9 +108,10 @@ pub fn settings_container<'a, 'b>(
         .directory
         .as_ref()
         .and_then(|p| p.to_str())
-        .unwrap_or("No directory is set");
+        .map(str::to_string)
+        .unwrap_or_else(|| localized_string("no-directory-set"));
     let directory_data_text = Text::new(path_str)
-        .size(14)
+        .size(font_size())
         .vertical_alignment(VerticalAlignment::Center);
     let directory_data_text_container = Container::new(directory_data_text)
         .height(Length::Units(25))
@@ -77,8 +124,95 @@ pub fn settings_container<'a, 'b>(
         .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
         .push(directory_data_text_container);
 
+    // Installations of World of Warcraft to switch between.
+    let installations = config
+        .installation_directories()
+        .iter()
+        .map(|d| d.display().to_string())
+        .collect::<Vec<_>>();
+    let current_installation = installations.first().cloned();
+
+    let add_installation_button: Element<Interaction> = Button::new(
+        &mut installation_state.add_btn_state,
+        Text::new(localized_string("add-installation")).size(font_size()),
+    )
+    .style(style::DefaultBoxedButton(color_palette))
+    .on_press(Interaction::OpenDirectory(DirectoryType::Installation))
+    .into();
+
+    let mut installation_row = Row::new()
+        .align_items(Align::Center)
+        .push(add_installation_button.map(Message::Interaction));
+
+    if installations.len() > 1 {
+        let installation_pick_list: Element<Interaction> = PickList::new(
+            &mut installation_state.picklist_state,
+            installations,
+            current_installation,
+            Interaction::InstallationSelected,
+        )
+        .text_size(font_size())
+        .width(Length::Units(300))
+        .style(style::PickList(color_palette))
+        .into();
+
+        installation_row = installation_row
+            .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+            .push(installation_pick_list.map(Message::Interaction));
+    }
+
+    // Profiles, each with their own installations, flavors and addon settings.
+    let mut profile_name_input = TextInput::new(
+        &mut profile_state.name_input_state,
+        &localized_string("profile-name"),
+        &profile_state.name,
+        Interaction::ProfileNameInput,
+    )
+    .size(font_size())
+    .padding(6)
+    .width(Length::Units(200))
+    .style(style::CatalogQueryInput(color_palette));
+
+    let mut add_profile_button = Button::new(
+        &mut profile_state.add_btn_state,
+        Text::new(localized_string("add-profile")).size(font_size()),
+    )
+    .style(style::DefaultBoxedButton(color_palette));
+
+    if !profile_state.name.trim().is_empty() {
+        profile_name_input = profile_name_input.on_submit(Interaction::AddProfile);
+        add_profile_button = add_profile_button.on_press(Interaction::AddProfile);
+    }
+
+    let profile_name_input: Element<Interaction> = profile_name_input.into();
+    let add_profile_button: Element<Interaction> = add_profile_button.into();
+    let mut profile_row = Row::new()
+        .align_items(Align::Center)
+        .push(profile_name_input.map(Message::Interaction))
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(add_profile_button.map(Message::Interaction));
+
+    let profiles = config.profile_names();
+    if profiles.len() > 1 {
+        let current_profile = profiles.first().cloned();
+        let profile_pick_list: Element<Interaction> = PickList::new(
+            &mut profile_state.picklist_state,
+            profiles,
+            current_profile,
+            Interaction::ProfileSelected,
+        )
+        .text_size(font_size())
+        .width(Length::Units(200))
+        .style(style::PickList(color_palette))
+        .into();
+
+        profile_row = profile_row
+            .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+            .push(profile_pick_list.map(Message::Interaction));
+    }
+
     // Title for the theme pick list.
-    let theme_info_text = Text::new("Theme").size(14);
+    let theme_info_text = Text::new(localized_string("theme")).size(font_size());
     let theme_info_row = Row::new().push(theme_info_text);
 
     let theme_names = theme_state
@@ -93,21 +227,217 @@ pub fn settings_container<'a, 'b>(
         Some(theme_state.current_theme_name.clone()),
         Message::ThemeSelected,
     )
-    .text_size(14)
+    .text_size(font_size())
     .width(Length::Units(100))
     .style(style::PickList(color_palette));
 
+    // Copies the current theme, to share it.
+    let export_theme_button: Element<Interaction> = Button::new(
+        &mut theme_state.export_btn_state,
+        Text::new(localized_string("copy-theme")).size(font_size()),
+    )
+    .style(style::DefaultBoxedButton(color_palette))
+    .on_press(Interaction::ExportTheme)
+    .into();
+
     // Data row for theme picker list.
-    let theme_data_row = Row::new().push(theme_pick_list);
+    let theme_data_row = Row::new()
+        .align_items(Align::Center)
+        .push(theme_pick_list)
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(export_theme_button.map(Message::Interaction));
+
+    // Imports a theme shared as a link to its file.
+    let mut theme_url_input = TextInput::new(
+        &mut theme_state.import_url_state,
+        &localized_string("theme-url"),
+        &theme_state.import_url,
+        Interaction::ThemeImportUrlInput,
+    )
+    .size(font_size())
+    .padding(6)
+    .width(Length::Units(200))
+    .style(style::CatalogQueryInput(color_palette));
+
+    let mut import_theme_button = Button::new(
+        &mut theme_state.import_btn_state,
+        Text::new(localized_string("import")).size(font_size()),
+    )
+    .style(style::DefaultBoxedButton(color_palette));
+
+    if !theme_state.importing && !theme_state.import_url.trim().is_empty() {
+        theme_url_input = theme_url_input.on_submit(Interaction::ImportTheme);
+        import_theme_button = import_theme_button.on_press(Interaction::ImportTheme);
+    }
+
+    let theme_url_input: Element<Interaction> = theme_url_input.into();
+    let import_theme_button: Element<Interaction> = import_theme_button.into();
+    let theme_import_row = Row::new()
+        .align_items(Align::Center)
+        .push(theme_url_input.map(Message::Interaction))
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(import_theme_button.map(Message::Interaction));
+
+    let theme_status = if theme_state.importing {
+        localized_string("importing")
+    } else {
+        theme_state.status.clone().unwrap_or_default()
+    };
+    let theme_status_container = Container::new(Text::new(theme_status).size(font_size()))
+        .style(style::NormalForegroundContainer(color_palette));
+
+    // Title for the language pick list.
+    let language_info_text = Text::new(localized_string("language")).size(font_size());
+    let language_info_row = Row::new().push(language_info_text);
+
+    let language_pick_list = PickList::new(
+        language_picklist_state,
+        &Language::ALL[..],
+        Some(config.language.unwrap_or_else(Language::system)),
+        Message::LanguageSelected,
+    )
+    .text_size(font_size())
+    .width(Length::Units(100))
+    .style(style::PickList(color_palette));
+    let language_data_row = Row::new().push(language_pick_list);
+
+    // Releases of Ajour offered as updates.
+    let self_update_channel_info_text =
+        Text::new(localized_string("ajour-updates")).size(font_size());
+    let self_update_channel_info_row = Row::new().push(self_update_channel_info_text);
+
+    let self_update_channel_pick_list = PickList::new(
+        self_update_channel_picklist_state,
+        &SelfUpdateChannel::ALL[..],
+        Some(config.self_update_channel),
+        Message::SelfUpdateChannelSelected,
+    )
+    .text_size(font_size())
+    .width(Length::Units(100))
+    .style(style::PickList(color_palette));
+    let self_update_channel_row = Row::new().push(self_update_channel_pick_list);
+
+    // Title for the update notifications.
+    let notifications_info_text = Text::new(localized_string("notifications")).size(font_size());
+    let notifications_info_row = Row::new().push(notifications_info_text);
+
+    let notifications_checkbox = Checkbox::new(
+        config.notifications.enabled,
+        localized_string("notify-about-updates"),
+        |is_checked| Message::Interaction(Interaction::ToggleUpdateNotifications(is_checked)),
+    )
+    .text_size(font_size())
+    .spacing(5)
+    .style(style::DefaultCheckbox(color_palette));
+    let notifications_checkbox_row = Row::new().push(notifications_checkbox);
+
+    // How often to check for updates in the background.
+    let check_interval_pick_list = PickList::new(
+        &mut notification_state.interval_picklist_state,
+        &CheckInterval::ALL[..],
+        Some(config.notifications.interval),
+        Message::CheckIntervalSelected,
+    )
+    .text_size(font_size())
+    .width(Length::Units(130))
+    .style(style::PickList(color_palette));
+    let check_interval_row = Row::new().push(check_interval_pick_list);
+
+    // Title for the scheduled updates.
+    let scheduled_updates_info_text =
+        Text::new(localized_string("install-updates")).size(font_size());
+    let scheduled_updates_info_row = Row::new().push(scheduled_updates_info_text);
+
+    // How often updates are checked for and installed while Ajour is open.
+    let update_schedule_pick_list = PickList::new(
+        &mut scheduled_update_state.schedule_picklist_state,
+        &UpdateSchedule::ALL[..],
+        Some(config.scheduled_updates.schedule),
+        Message::UpdateScheduleSelected,
+    )
+    .text_size(font_size())
+    .width(Length::Units(130))
+    .style(style::PickList(color_palette));
+    let update_schedule_row = Row::new().push(update_schedule_pick_list);
+
+    let offline_checkbox = Checkbox::new(
+        config.offline,
+        localized_string("offline-mode"),
+        |is_checked| Message::Interaction(Interaction::ToggleOffline(is_checked)),
+    )
+    .text_size(font_size())
+    .spacing(5)
+    .style(style::DefaultCheckbox(color_palette));
+    let offline_checkbox_row = Row::new().push(offline_checkbox);
+
+    let run_after_game_exits_checkbox = Checkbox::new(
+        config.run_after_game_exits,
+        localized_string("update-after-game-exits"),
+        |is_checked| Message::Interaction(Interaction::ToggleRunAfterGameExits(is_checked)),
+    )
+    .text_size(font_size())
+    .spacing(5)
+    .style(style::DefaultCheckbox(color_palette));
+    let run_after_game_exits_row = Row::new().push(run_after_game_exits_checkbox);
+
+    let open_links_checkbox = Checkbox::new(
+        config.open_links,
+        localized_string("open-install-links"),
+        |is_checked| Message::Interaction(Interaction::ToggleOpenLinks(is_checked)),
+    )
+    .text_size(font_size())
+    .spacing(5)
+    .style(style::DefaultCheckbox(color_palette));
+    let open_links_row = Row::new().push(open_links_checkbox);
+
+    let tray_icon_checkbox = Checkbox::new(
+        config.tray_icon,
+        localized_string("tray-icon"),
+        |is_checked| Message::Interaction(Interaction::ToggleTrayIcon(is_checked)),
+    )
+    .text_size(font_size())
+    .spacing(5)
+    .style(style::DefaultCheckbox(color_palette));
+    let tray_icon_row = Row::new().push(tray_icon_checkbox);
+
+    let close_to_tray_checkbox = Checkbox::new(
+        config.close_to_tray,
+        localized_string("close-to-tray"),
+        |is_checked| Message::Interaction(Interaction::ToggleCloseToTray(is_checked)),
+    )
+    .text_size(font_size())
+    .spacing(5)
+    .style(style::DefaultCheckbox(color_palette));
+    let close_to_tray_row = Row::new().push(close_to_tray_checkbox);
+
+    // Repository tried first for addons which are on both Curse and Tukui.
+    let source_priority_info_text =
+        Text::new(localized_string("preferred-source")).size(font_size());
+    let source_priority_info_row = Row::new().push(source_priority_info_text);
+
+    let preferred_source = config
+        .addons
+        .source_priority(&config.wow.flavor)
+        .preferred(std::iter::empty());
+    let source_priority_pick_list = PickList::new(
+        source_priority_picklist_state,
+        &ChangeSourceState::REPOSITORIES[..],
+        Some(preferred_source),
+        Message::SourcePrioritySelected,
+    )
+    .text_size(font_size())
+    .width(Length::Units(100))
+    .style(style::PickList(color_palette));
+    let source_priority_row = Row::new().push(source_priority_pick_list);
 
     // Scale buttons for application scale factoring.
     let (scale_title_row, scale_buttons_row) = {
-        let scale_title = Text::new("UI Scale").size(DEFAULT_FONT_SIZE);
+        let scale_title = Text::new(localized_string("ui-scale")).size(font_size());
         let scale_title_row = Row::new().push(scale_title);
 
         let scale_down_button: Element<Interaction> = Button::new(
             &mut scale_state.down_btn_state,
-            Text::new("  -  ").size(DEFAULT_FONT_SIZE),
+            Text::new("  -  ").size(font_size()),
         )
         .style(style::DefaultBoxedButton(color_palette))
         .on_press(Interaction::ScaleDown)
@@ -115,14 +445,14 @@ pub fn settings_container<'a, 'b>(
 
         let scale_up_button: Element<Interaction> = Button::new(
             &mut scale_state.up_btn_state,
-            Text::new("  +  ").size(DEFAULT_FONT_SIZE),
+            Text::new("  +  ").size(font_size()),
         )
         .style(style::DefaultBoxedButton(color_palette))
         .on_press(Interaction::ScaleUp)
         .into();
 
         let current_scale_text = Text::new(format!("  {:.2}  ", scale_state.scale))
-            .size(DEFAULT_FONT_SIZE)
+            .size(font_size())
             .vertical_alignment(VerticalAlignment::Center);
         let current_scale_container = Container::new(current_scale_text)
             .height(Length::Units(25))
@@ -138,17 +468,75 @@ pub fn settings_container<'a, 'b>(
         (scale_title_row, scale_buttons_row)
     };
 
+    // Font size buttons, next to the scale since they go together.
+    let (font_size_title_row, font_size_buttons_row) = {
+        let font_size_title = Text::new(localized_string("font-size")).size(font_size());
+        let font_size_title_row = Row::new().push(font_size_title);
+
+        let font_size_down_button: Element<Interaction> = Button::new(
+            &mut scale_state.font_down_btn_state,
+            Text::new("  -  ").size(font_size()),
+        )
+        .style(style::DefaultBoxedButton(color_palette))
+        .on_press(Interaction::FontSizeDown)
+        .into();
+
+        let font_size_up_button: Element<Interaction> = Button::new(
+            &mut scale_state.font_up_btn_state,
+            Text::new("  +  ").size(font_size()),
+        )
+        .style(style::DefaultBoxedButton(color_palette))
+        .on_press(Interaction::FontSizeUp)
+        .into();
+
+        let current_font_size_text = Text::new(format!("  {}  ", font_size()))
+            .size(font_size())
+            .vertical_alignment(VerticalAlignment::Center);
+        let current_font_size_container = Container::new(current_font_size_text)
+            .height(Length::Units(25))
+            .center_y()
+            .style(style::BrightBackgroundContainer(color_palette));
+
+        let font_size_buttons_row = Row::new()
+            .push(font_size_down_button.map(Message::Interaction))
+            .push(current_font_size_container)
+            .push(font_size_up_button.map(Message::Interaction));
+
+        (font_size_title_row, font_size_buttons_row)
+    };
+
+    // Density of the rows in My Addons.
+    let row_density_info_text = Text::new(localized_string("row-density")).size(font_size());
+    let row_density_info_row = Row::new().push(row_density_info_text);
+
+    let row_density_pick_list = PickList::new(
+        &mut scale_state.density_picklist_state,
+        &RowDensity::ALL[..],
+        Some(config.row_density),
+        Message::RowDensitySelected,
+    )
+    .text_size(font_size())
+    .width(Length::Units(100))
+    .style(style::PickList(color_palette));
+    let row_density_row = Row::new().push(row_density_pick_list);
+
     // Small space below content.
     let bottom_space = Space::new(Length::FillPortion(1), Length::Units(DEFAULT_PADDING));
 
-    let (backup_title_row, backup_directory_row, backup_now_row) = {
+    let (
+        backup_title_row,
+        backup_directory_row,
+        backup_now_row,
+        backup_schedule_row,
+        backup_restore_row,
+    ) = {
         // Title for the Backup section.
-        let backup_title_text = Text::new("Backup").size(DEFAULT_FONT_SIZE);
+        let backup_title_text = Text::new(localized_string("backup")).size(font_size());
         let backup_title_row = Row::new().push(backup_title_text);
 
         // Directory button for Backup directory selection.
         let directory_button_title_container =
-            Container::new(Text::new("Select Directory").size(DEFAULT_FONT_SIZE))
+            Container::new(Text::new(localized_string("select-directory")).size(font_size()))
                 .width(Length::FillPortion(1))
                 .center_x()
                 .align_x(Align::Center);
@@ -167,9 +555,10 @@ pub fn settings_container<'a, 'b>(
             .backup_directory
             .as_ref()
             .and_then(|p| p.to_str())
-            .unwrap_or("No directory is set");
+            .map(str::to_string)
+            .unwrap_or_else(|| localized_string("no-directory-set"));
         let directory_data_text = Text::new(path_str)
-            .size(DEFAULT_FONT_SIZE)
+            .size(font_size())
             .vertical_alignment(VerticalAlignment::Center);
         let directory_data_text_container = Container::new(directory_data_text)
             .height(Length::Units(25))
@@ -187,11 +576,17 @@ pub fn settings_container<'a, 'b>(
         // directory is chosen
         let mut backup_now_row = Row::new();
 
+        // Row to choose the backup schedule, only shown if a directory is chosen.
+        let mut backup_schedule_row = Row::new().align_items(Align::Center);
+
+        // Row to restore a backup, only shown if a directory is chosen.
+        let mut backup_restore_row = Row::new().align_items(Align::Center);
+
         // Show button / last backup info if directory is shown, otherwise
         // show description about the backup process
         if config.backup_directory.is_some() {
             let backup_button_title_container =
-                Container::new(Text::new("Backup Now").size(DEFAULT_FONT_SIZE))
+                Container::new(Text::new(localized_string("backup-now")).size(font_size()))
                     .width(Length::FillPortion(1))
                     .center_x()
                     .align_x(Align::Center);
@@ -209,17 +604,17 @@ pub fn settings_container<'a, 'b>(
             }
 
             let backup_status_text = if backup_state.backing_up {
-                Text::new("Backing up...")
-                    .size(DEFAULT_FONT_SIZE)
+                Text::new(localized_string("backing-up"))
+                    .size(font_size())
                     .vertical_alignment(VerticalAlignment::Center)
             } else {
                 let as_of = backup_state
                     .last_backup
                     .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string())
-                    .unwrap_or_else(|| "Never".to_string());
+                    .unwrap_or_else(|| localized_string("never"));
 
-                Text::new(&format!("Last backup: {}", as_of))
-                    .size(DEFAULT_FONT_SIZE)
+                Text::new(localized_string_with("last-backup", &[("time", &as_of)]))
+                    .size(font_size())
                     .vertical_alignment(VerticalAlignment::Center)
             };
 
@@ -234,11 +629,134 @@ pub fn settings_container<'a, 'b>(
                 .push(backup_button.map(Message::Interaction))
                 .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
                 .push(backup_status_text_container);
+
+            let schedule_pick_list = PickList::new(
+                &mut backup_state.schedule_picklist_state,
+                &BackupSchedule::ALL[..],
+                Some(config.backups.schedule),
+                Message::BackupScheduleSelected,
+            )
+            .text_size(font_size())
+            .width(Length::Units(100))
+            .style(style::PickList(color_palette));
+
+            let schedule_text = Text::new(localized_string("scheduled-backups"))
+                .size(font_size())
+                .vertical_alignment(VerticalAlignment::Center);
+            let schedule_text_container = Container::new(schedule_text)
+                .height(Length::Units(25))
+                .center_y()
+                .style(style::NormalForegroundContainer(color_palette));
+
+            let before_update_all_checkbox = Checkbox::new(
+                config.backups.before_update_all,
+                localized_string("backup-before-update-all"),
+                |is_checked| {
+                    Message::Interaction(Interaction::ToggleBackupBeforeUpdateAll(is_checked))
+                },
+            )
+            .text_size(font_size())
+            .spacing(5)
+            .style(style::DefaultCheckbox(color_palette));
+
+            backup_schedule_row = backup_schedule_row
+                .push(schedule_pick_list)
+                .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+                .push(schedule_text_container)
+                .push(Space::new(
+                    Length::Units(DEFAULT_PADDING + DEFAULT_PADDING),
+                    Length::Units(0),
+                ))
+                .push(before_update_all_checkbox);
+
+            let restore_pick_list = PickList::new(
+                &mut backup_state.restore_picklist_state,
+                &backup_state.archives[..],
+                backup_state.restore_archive.clone(),
+                Message::RestoreBackupSelected,
+            )
+            .text_size(font_size())
+            .width(Length::Units(150))
+            .style(style::PickList(color_palette));
+
+            let restore_button_title = if backup_state.confirming_restore {
+                localized_string("confirm")
+            } else {
+                localized_string("restore")
+            };
+            let restore_button_title_container =
+                Container::new(Text::new(restore_button_title).size(font_size()))
+                    .width(Length::FillPortion(1))
+                    .center_x()
+                    .align_x(Align::Center);
+            let mut restore_button = Button::new(
+                &mut backup_state.restore_btn_state,
+                restore_button_title_container,
+            )
+            .width(Length::Units(100))
+            .style(style::DefaultBoxedButton(color_palette));
+
+            // Only allow restoring while no other backup task is running.
+            if backup_state.restore_archive.is_some()
+                && !backup_state.restoring
+                && !backup_state.backing_up
+                && config.wow.directory.is_some()
+            {
+                restore_button = restore_button.on_press(if backup_state.confirming_restore {
+                    Interaction::ConfirmRestoreBackup
+                } else {
+                    Interaction::RestoreBackup
+                });
+            }
+
+            let restore_status_text = if backup_state.restoring {
+                localized_string("restoring")
+            } else if backup_state.confirming_restore {
+                localized_string_with("restore-confirmation", &[("flavor", &config.wow.flavor)])
+            } else {
+                localized_string_with("restore-description", &[("flavor", &config.wow.flavor)])
+            };
+            let restore_status_text_container = Container::new(
+                Text::new(&restore_status_text)
+                    .size(font_size())
+                    .vertical_alignment(VerticalAlignment::Center),
+            )
+            .height(Length::Units(25))
+            .center_y()
+            .style(style::NormalForegroundContainer(color_palette));
+
+            let restore_button: Element<Interaction> = restore_button.into();
+
+            backup_restore_row = backup_restore_row
+                .push(restore_pick_list)
+                .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+                .push(restore_button.map(Message::Interaction));
+
+            if backup_state.confirming_restore {
+                let cancel_button: Element<Interaction> = Button::new(
+                    &mut backup_state.cancel_restore_btn_state,
+                    Container::new(Text::new(localized_string("cancel")).size(font_size()))
+                        .width(Length::FillPortion(1))
+                        .center_x()
+                        .align_x(Align::Center),
+                )
+                .width(Length::Units(100))
+                .style(style::DefaultBoxedButton(color_palette))
+                .on_press(Interaction::CancelRestoreBackup)
+                .into();
+
+                backup_restore_row = backup_restore_row
+                    .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+                    .push(cancel_button.map(Message::Interaction));
+            }
+
+            backup_restore_row = backup_restore_row
+                .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+                .push(restore_status_text_container);
         } else {
-            let backup_status_text =
-                Text::new("Back up your AddOns and WTF folder to the chosen directory")
-                    .size(DEFAULT_FONT_SIZE)
-                    .vertical_alignment(VerticalAlignment::Center);
+            let backup_status_text = Text::new(localized_string("backup-description"))
+                .size(font_size())
+                .vertical_alignment(VerticalAlignment::Center);
 
             let backup_status_text_container = Container::new(backup_status_text)
                 .height(Length::Units(25))
@@ -248,12 +766,149 @@ pub fn settings_container<'a, 'b>(
             backup_now_row = backup_now_row.push(backup_status_text_container);
         }
 
-        (backup_title_row, backup_directory_row, backup_now_row)
+        (
+            backup_title_row,
+            backup_directory_row,
+            backup_now_row,
+            backup_schedule_row,
+            backup_restore_row,
+        )
+    };
+
+    // Title for the API tokens section.
+    let tokens_title_text = Text::new(localized_string("api-tokens")).size(font_size());
+    let tokens_title_row = Row::new().push(tokens_title_text);
+
+    // Tokens are sent with the requests to their repository, which then allows
+    // more requests than it does without them.
+    let mut tokens_column = Column::new().spacing(5);
+    for (token, input_state) in Token::ALL
+        .iter()
+        .copied()
+        .zip(token_input_states.iter_mut())
+    {
+        let token_input: Element<Interaction> = TextInput::new(
+            input_state,
+            &format!("{}...", token),
+            config.tokens.get(token).unwrap_or_default(),
+            move |value| Interaction::TokenInput(token, value),
+        )
+        .password()
+        .size(font_size())
+        .padding(5)
+        .width(Length::Units(250))
+        .style(style::CatalogQueryInput(color_palette))
+        .into();
+
+        tokens_column = tokens_column.push(token_input.map(Message::Interaction));
+    }
+
+    // Title for the Advanced section.
+    let advanced_title_text = Text::new(localized_string("advanced")).size(font_size());
+    let advanced_title_row = Row::new().push(advanced_title_text);
+
+    // Options which otherwise are only found in the config file, labeled with
+    // their key in it.
+    let mut advanced_column = Column::new().spacing(5);
+    for (option, value, input_state) in advanced_settings_state.inputs.iter_mut() {
+        let option = *option;
+
+        let label_container = Container::new(
+            Text::new(option.key())
+                .size(font_size())
+                .vertical_alignment(VerticalAlignment::Center),
+        )
+        .width(Length::Units(220))
+        .height(Length::Units(25))
+        .center_y()
+        .style(style::NormalForegroundContainer(color_palette));
+
+        let option_input: Element<Interaction> =
+            TextInput::new(input_state, "", value, move |value| {
+                Interaction::AdvancedOptionInput(option, value)
+            })
+            .on_submit(Interaction::SaveAdvancedOptions)
+            .size(font_size())
+            .padding(5)
+            .width(Length::Units(250))
+            .style(style::CatalogQueryInput(color_palette))
+            .into();
+
+        let row = Row::new()
+            .align_items(Align::Center)
+            .push(label_container)
+            .push(option_input.map(Message::Interaction));
+
+        advanced_column = advanced_column.push(row);
+    }
+
+    let advanced_save_button: Element<Interaction> = Button::new(
+        &mut advanced_settings_state.save_btn_state,
+        Container::new(Text::new(localized_string("save")).size(font_size()))
+            .width(Length::FillPortion(1))
+            .center_x()
+            .align_x(Align::Center),
+    )
+    .width(Length::Units(100))
+    .style(style::DefaultBoxedButton(color_palette))
+    .on_press(Interaction::SaveAdvancedOptions)
+    .into();
+
+    // Escape hatch for the options which aren't shown.
+    let open_config_button: Element<Interaction> = Button::new(
+        &mut advanced_settings_state.open_config_btn_state,
+        Text::new(localized_string("open-config-folder")).size(font_size()),
+    )
+    .style(style::DefaultBoxedButton(color_palette))
+    .on_press(Interaction::OpenConfigDirectory)
+    .into();
+
+    let logs_button: Element<Interaction> = Button::new(
+        &mut advanced_settings_state.logs_btn_state,
+        Text::new(localized_string("logs")).size(font_size()),
+    )
+    .style(style::DefaultBoxedButton(color_palette))
+    .on_press(Interaction::ToggleLogs)
+    .into();
+
+    // Zip of the log, config and addons for bug reports.
+    let diagnostics_button: Element<Interaction> = Button::new(
+        &mut advanced_settings_state.diagnostics_btn_state,
+        Text::new(localized_string("generate-diagnostics")).size(font_size()),
+    )
+    .style(style::DefaultBoxedButton(color_palette))
+    .on_press(Interaction::GenerateDiagnostics)
+    .into();
+
+    let advanced_status = match &advanced_settings_state.error {
+        Some(error) => error.clone(),
+        None if advanced_settings_state.is_saved => localized_string("saved"),
+        None => String::new(),
     };
+    let advanced_status_container = Container::new(
+        Text::new(advanced_status)
+            .size(font_size())
+            .vertical_alignment(VerticalAlignment::Center),
+    )
+    .height(Length::Units(25))
+    .center_y()
+    .style(style::NormalForegroundContainer(color_palette));
+
+    let advanced_buttons_row = Row::new()
+        .align_items(Align::Center)
+        .push(advanced_save_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(open_config_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(logs_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(diagnostics_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(advanced_status_container);
 
     let (columns_title_row, columns_scrollable) = {
         // Title for the Columns section.
-        let columns_title_text = Text::new("Columns").size(DEFAULT_FONT_SIZE);
+        let columns_title_text = Text::new(localized_string("columns")).size(font_size());
         let columns_title_row = Row::new().push(columns_title_text).padding(DEFAULT_PADDING);
 
         // Scrollable for column selections
@@ -280,7 +935,7 @@ pub fn settings_container<'a, 'b>(
 
             let mut left_button = Button::new(
                 &mut column.up_btn_state,
-                Text::new(" ▲ ").size(11).color(if !is_first {
+                Text::new(" ▲ ").size(font_size() - 3).color(if !is_first {
                     color_palette.bright.primary
                 } else {
                     color_palette.normal.primary
@@ -293,7 +948,7 @@ pub fn settings_container<'a, 'b>(
 
             let mut right_button = Button::new(
                 &mut column.down_btn_state,
-                Text::new(" ▼ ").size(11).color(if !is_last {
+                Text::new(" ▼ ").size(font_size() - 3).color(if !is_last {
                     color_palette.bright.primary
                 } else {
                     color_palette.normal.primary
@@ -317,7 +972,7 @@ pub fn settings_container<'a, 'b>(
             let mut checkbox = Checkbox::new(is_checked, title.clone(), move |is_checked| {
                 Message::Interaction(Interaction::ToggleColumn(is_checked, column_key))
             })
-            .text_size(DEFAULT_FONT_SIZE)
+            .text_size(font_size())
             .spacing(5);
 
             if column_key == ColumnKey::Title {
@@ -343,11 +998,20 @@ pub fn settings_container<'a, 'b>(
     };
 
     // Colum wrapping all the settings content.
-    let left_column = Column::new()
+    let mut left_column = Column::new()
         .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
         .push(directory_info_text)
         .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
         .push(path_data_row)
+        .push(Space::new(Length::Units(0), Length::Units(5)))
+        .push(installation_row)
+        .push(Space::new(
+            Length::Units(0),
+            Length::Units(DEFAULT_PADDING + DEFAULT_PADDING),
+        ))
+        .push(Text::new(localized_string("profile")).size(font_size()))
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(profile_row)
         .push(Space::new(
             Length::Units(0),
             Length::Units(DEFAULT_PADDING + DEFAULT_PADDING),
@@ -357,6 +1021,26 @@ pub fn settings_container<'a, 'b>(
         .push(backup_now_row)
         .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
         .push(backup_directory_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(backup_schedule_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(backup_restore_row)
+        .push(Space::new(
+            Length::Units(0),
+            Length::Units(DEFAULT_PADDING + DEFAULT_PADDING),
+        ))
+        .push(tokens_title_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(tokens_column)
+        .push(Space::new(
+            Length::Units(0),
+            Length::Units(DEFAULT_PADDING + DEFAULT_PADDING),
+        ))
+        .push(advanced_title_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(advanced_column)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(advanced_buttons_row)
         .push(bottom_space);
 
     let middle_column = Column::new()
@@ -365,9 +1049,65 @@ pub fn settings_container<'a, 'b>(
         .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
         .push(scale_buttons_row)
         .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(font_size_title_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(font_size_buttons_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(row_density_info_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(row_density_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
         .push(theme_info_row)
         .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
-        .push(theme_data_row);
+        .push(theme_data_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(theme_import_row)
+        .push(Space::new(
+            Length::Units(0),
+            Length::Units(DEFAULT_PADDING / 2),
+        ))
+        .push(theme_status_container)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(language_info_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(language_data_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(self_update_channel_info_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(self_update_channel_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(notifications_info_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(notifications_checkbox_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(check_interval_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(scheduled_updates_info_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(update_schedule_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(source_priority_info_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(source_priority_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(offline_checkbox_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(run_after_game_exits_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(open_links_row);
+
+    // The window can only be hidden to the tray on Windows.
+    if tray::is_supported() {
+        left_column = left_column
+            .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+            .push(tray_icon_row);
+
+        if cfg!(windows) && config.tray_icon {
+            left_column = left_column
+                .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+                .push(close_to_tray_row);
+        }
+    }
 
     let left_spacer = Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0));
     let right_spacer = Space::new(Length::Units(DEFAULT_PADDING + 5), Length::Units(0));
@@ -407,14 +1147,32 @@ pub fn settings_container<'a, 'b>(
         .padding(DEFAULT_PADDING + DEFAULT_PADDING)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn addon_data_cell<'a, 'b>(
     color_palette: ColorPalette,
     addon: &'a mut Addon,
+    download_progress: Option<DownloadProgress>,
     is_addon_expanded: bool,
     expand_type: &'a ExpandType,
     column_config: &'b [(ColumnKey, Length, bool)],
+    dependents: &'b [String],
+    shared_folders: &'b [String],
+    is_delete_confirmation: bool,
+    delete_saved_variables: bool,
+    is_ignored_by_pattern: bool,
+    is_selected: bool,
+    is_focused: bool,
+    row_density: RowDensity,
+    preferred_source: Option<Repository>,
+    details: Option<&'b AddonDetails>,
+    change_source_state: Option<&'a mut ChangeSourceState>,
+    client_build: Option<&'b ClientBuild>,
+    abandoned: Option<(u32, &'b [String])>,
 ) -> Container<'a, Message> {
-    let default_height = Length::Units(26);
+    let (default_height, cell_padding) = match row_density {
+        RowDensity::Normal => (Length::Units(26), 5),
+        RowDensity::Compact => (Length::Units(font_size() + 4), 1),
+    };
 
     let mut row_containers = vec![];
 
@@ -423,6 +1181,16 @@ pub fn addon_data_cell<'a, 'b>(
     let notes = addon.notes().map(str::to_string);
     let website_url = addon.website_url().map(str::to_string);
 
+    // The game flags addons made for an earlier patch, and likely can't load ones
+    // made for another version of it.
+    let compatibility = match (client_build, addon.interface()) {
+        (Some(build), Some(interface)) => match build.compatibility(interface) {
+            Compatibility::Compatible => None,
+            compatibility => Some((compatibility, interface, build.interface)),
+        },
+        _ => None,
+    };
+
     // Check if current addon is expanded.
     let addon_cloned = addon.clone();
     let version = addon
@@ -435,7 +1203,7 @@ pub fn addon_data_cell<'a, 'b>(
     } else {
         String::from("-")
     };
-    let remote_version = Text::new(remote_version).size(DEFAULT_FONT_SIZE);
+    let remote_version = Text::new(remote_version).size(font_size());
 
     if let Some((idx, width)) = column_config
         .iter()
@@ -449,14 +1217,17 @@ pub fn addon_data_cell<'a, 'b>(
         })
         .next()
     {
-        let title = Text::new(addon.title()).size(DEFAULT_FONT_SIZE);
+        let title = Text::new(addon.title()).size(font_size());
         let mut title_button = Button::new(&mut addon.details_btn_state, title).on_press(
             Interaction::Expand(ExpandType::Details(addon_cloned.clone())),
         );
 
         if release_package.as_deref().is_some() {}
 
-        if is_addon_expanded && matches!(expand_type, ExpandType::Details(_)) {
+        if is_selected
+            || is_focused
+            || (is_addon_expanded && matches!(expand_type, ExpandType::Details(_)))
+        {
             title_button = title_button.style(style::SelectedBrightTextButton(color_palette));
         } else {
             title_button = title_button.style(style::BrightTextButton(color_palette));
@@ -469,15 +1240,31 @@ pub fn addon_data_cell<'a, 'b>(
             .spacing(3)
             .align_items(Align::Center);
 
-        if addon.release_channel != ReleaseChannel::Stable {
+        // Compact rows leave the release channel to its column.
+        if addon.release_channel != ReleaseChannel::Stable && row_density == RowDensity::Normal {
             let release_channel =
-                Container::new(Text::new(addon.release_channel.to_string()).size(10))
+                Container::new(Text::new(addon.release_channel.to_string()).size(font_size() - 4))
                     .style(style::ChannelBadge(color_palette))
                     .padding(3);
 
             title_row = title_row.push(release_channel);
         }
 
+        let badge_text = match compatibility {
+            _ if abandoned.is_some() => Some(localized_string("abandoned")),
+            Some((Compatibility::OtherGameVersion, ..)) => Some(localized_string("incompatible")),
+            Some(_) => Some(localized_string("out-of-date")),
+            None => None,
+        };
+
+        if let Some(badge_text) = badge_text {
+            let badge = Container::new(Text::new(badge_text).size(font_size() - 4))
+                .style(style::ChannelBadge(color_palette))
+                .padding(3);
+
+            title_row = title_row.push(badge);
+        }
+
         let title_container = Container::new(title_row)
             .height(default_height)
             .width(*width)
@@ -499,7 +1286,7 @@ pub fn addon_data_cell<'a, 'b>(
         })
         .next()
     {
-        let installed_version = Text::new(version).size(DEFAULT_FONT_SIZE);
+        let installed_version = Text::new(version).size(font_size());
         let mut local_version_button = Button::new(&mut addon.local_btn_state, installed_version)
             .style(style::BrightTextButton(color_palette));
 
@@ -625,12 +1412,21 @@ pub fn addon_data_cell<'a, 'b>(
         })
         .next()
     {
-        let channel = Text::new(addon.release_channel.to_string()).size(DEFAULT_FONT_SIZE);
+        let id = addon.primary_folder_id.clone();
+        let channel = PickList::new(
+            &mut addon.pick_release_channel_row_state,
+            &ReleaseChannel::ALL[..],
+            Some(addon.release_channel),
+            move |release_channel| Message::ReleaseChannelSelected((id.clone(), release_channel)),
+        )
+        .text_size(font_size())
+        .width(Length::Fill)
+        .style(style::SecondaryPickList(color_palette));
         let channel_container = Container::new(channel)
             .height(default_height)
             .width(*width)
             .center_y()
-            .padding(5)
+            .padding(cell_padding)
             .style(style::NormalForegroundContainer(color_palette));
 
         row_containers.push((idx, channel_container));
@@ -648,12 +1444,12 @@ pub fn addon_data_cell<'a, 'b>(
         })
         .next()
     {
-        let author = Text::new(author.as_deref().unwrap_or("-")).size(DEFAULT_FONT_SIZE);
+        let author = Text::new(author.as_deref().unwrap_or("-")).size(font_size());
         let author_container = Container::new(author)
             .height(default_height)
             .width(*width)
             .center_y()
-            .padding(5)
+            .padding(cell_padding)
             .style(style::NormalForegroundContainer(color_palette));
 
         row_containers.push((idx, author_container));
@@ -671,13 +1467,12 @@ pub fn addon_data_cell<'a, 'b>(
         })
         .next()
     {
-        let game_version =
-            Text::new(game_version.as_deref().unwrap_or("-")).size(DEFAULT_FONT_SIZE);
+        let game_version = Text::new(game_version.as_deref().unwrap_or("-")).size(font_size());
         let game_version_container = Container::new(game_version)
             .height(default_height)
             .width(*width)
             .center_y()
-            .padding(5)
+            .padding(cell_padding)
             .style(style::NormalForegroundContainer(color_palette));
 
         row_containers.push((idx, game_version_container));
@@ -696,7 +1491,7 @@ pub fn addon_data_cell<'a, 'b>(
         .next()
     {
         let release_date_text: String = if let Some(package) = release_package {
-            let f = timeago::Formatter::new();
+            let f = time_formatter();
             let now = Local::now();
 
             if let Some(time) = package.date_time.as_ref() {
@@ -707,12 +1502,12 @@ pub fn addon_data_cell<'a, 'b>(
         } else {
             "-".to_string()
         };
-        let release_date_text = Text::new(release_date_text).size(DEFAULT_FONT_SIZE);
+        let release_date_text = Text::new(release_date_text).size(font_size());
         let game_version_container = Container::new(release_date_text)
             .height(default_height)
             .width(*width)
             .center_y()
-            .padding(5)
+            .padding(cell_padding)
             .style(style::NormalForegroundContainer(color_palette));
 
         row_containers.push((idx, game_version_container));
@@ -722,7 +1517,7 @@ pub fn addon_data_cell<'a, 'b>(
         .iter()
         .enumerate()
         .filter_map(|(idx, (key, width, hidden))| {
-            if *key == ColumnKey::Status && !hidden {
+            if *key == ColumnKey::Source && !hidden {
                 Some((idx, width))
             } else {
                 None
@@ -730,33 +1525,170 @@ pub fn addon_data_cell<'a, 'b>(
         })
         .next()
     {
-        let update_button_container = match &addon.state {
-            AddonState::Ajour(string) => Container::new(
-                Text::new(string.clone().unwrap_or_else(|| "".to_string())).size(DEFAULT_FONT_SIZE),
-            )
+        let source = addon
+            .active_repository
+            .map(|r| r.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let source_container = Container::new(Text::new(source).size(font_size()))
             .height(default_height)
             .width(*width)
             .center_y()
-            .center_x()
-            .style(style::NormalForegroundContainer(color_palette)),
+            .padding(cell_padding)
+            .style(style::NormalForegroundContainer(color_palette));
+
+        row_containers.push((idx, source_container));
+    }
+
+    if let Some((idx, width)) = column_config
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, (key, width, hidden))| {
+            if *key == ColumnKey::Size && !hidden {
+                Some((idx, width))
+            } else {
+                None
+            }
+        })
+        .next()
+    {
+        let size = Text::new(format_size(addon.size())).size(font_size());
+        let size_container = Container::new(size)
+            .height(default_height)
+            .width(*width)
+            .center_y()
+            .padding(cell_padding)
+            .style(style::NormalForegroundContainer(color_palette));
+
+        row_containers.push((idx, size_container));
+    }
+
+    if let Some((idx, width)) = column_config
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, (key, width, hidden))| {
+            if *key == ColumnKey::InstalledAt && !hidden {
+                Some((idx, width))
+            } else {
+                None
+            }
+        })
+        .next()
+    {
+        let installed_at_text = addon
+            .install_dates
+            .installed_at
+            .map(format_time_ago)
+            .unwrap_or_else(|| "-".to_string());
+        let installed_at_text = Text::new(installed_at_text).size(font_size());
+        let installed_at_container = Container::new(installed_at_text)
+            .height(default_height)
+            .width(*width)
+            .center_y()
+            .padding(cell_padding)
+            .style(style::NormalForegroundContainer(color_palette));
+
+        row_containers.push((idx, installed_at_container));
+    }
+
+    if let Some((idx, width)) = column_config
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, (key, width, hidden))| {
+            if *key == ColumnKey::UpdatedAt && !hidden {
+                Some((idx, width))
+            } else {
+                None
+            }
+        })
+        .next()
+    {
+        let updated_at_text = addon
+            .install_dates
+            .updated_at
+            .map(format_time_ago)
+            .unwrap_or_else(|| "-".to_string());
+        let updated_at_text = Text::new(updated_at_text).size(font_size());
+        let updated_at_container = Container::new(updated_at_text)
+            .height(default_height)
+            .width(*width)
+            .center_y()
+            .padding(cell_padding)
+            .style(style::NormalForegroundContainer(color_palette));
+
+        row_containers.push((idx, updated_at_container));
+    }
+
+    if let Some((idx, width)) = column_config
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, (key, width, hidden))| {
+            if *key == ColumnKey::Interface && !hidden {
+                Some((idx, width))
+            } else {
+                None
+            }
+        })
+        .next()
+    {
+        let interface_text = addon
+            .interface()
+            .map(|i| i.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let interface_text = Text::new(interface_text).size(font_size());
+        let interface_container = Container::new(interface_text)
+            .height(default_height)
+            .width(*width)
+            .center_y()
+            .padding(cell_padding)
+            .style(style::NormalForegroundContainer(color_palette));
+
+        row_containers.push((idx, interface_container));
+    }
+
+    if let Some((idx, width)) = column_config
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, (key, width, hidden))| {
+            if *key == ColumnKey::Status && !hidden {
+                Some((idx, width))
+            } else {
+                None
+            }
+        })
+        .next()
+    {
+        let update_button_container = match &addon.state {
+            AddonState::Ajour(string) => Container::new(
+                Text::new(string.clone().unwrap_or_else(|| "".to_string())).size(font_size()),
+            )
+            .height(default_height)
+            .width(*width)
+            .center_y()
+            .center_x()
+            .style(style::NormalForegroundContainer(color_palette)),
             AddonState::Updatable | AddonState::Corrupted => {
                 let id = addon.primary_folder_id.clone();
                 let text = if addon.state == AddonState::Updatable {
-                    "Update"
+                    localized_string("update")
                 } else {
-                    "Repair"
+                    localized_string("repair")
                 };
 
-                let update_wrapper = Container::new(Text::new(text).size(DEFAULT_FONT_SIZE))
+                let update_wrapper = Container::new(Text::new(text).size(font_size()))
                     .width(*width)
                     .center_x()
                     .align_x(Align::Center);
-                let update_button: Element<Interaction> =
-                    Button::new(&mut addon.update_btn_state, update_wrapper)
-                        .width(Length::FillPortion(1))
-                        .style(style::SecondaryButton(color_palette))
-                        .on_press(Interaction::Update(id))
-                        .into();
+                let mut update_button = Button::new(&mut addon.update_btn_state, update_wrapper)
+                    .width(Length::FillPortion(1))
+                    .padding(cell_padding)
+                    .style(style::SecondaryButton(color_palette));
+
+                // Updates need a connection.
+                if !network::is_offline() {
+                    update_button = update_button.on_press(Interaction::Update(id));
+                }
+
+                let update_button: Element<Interaction> = update_button.into();
 
                 Container::new(update_button.map(Message::Interaction))
                     .height(default_height)
@@ -766,42 +1698,122 @@ pub fn addon_data_cell<'a, 'b>(
                     .style(style::BrightForegroundContainer(color_palette))
             }
             AddonState::Downloading => {
-                Container::new(Text::new("Downloading").size(DEFAULT_FONT_SIZE))
+                let id = addon.primary_folder_id.clone();
+
+                let is_paused = download_progress
+                    .as_ref()
+                    .map_or(false, |p| p.status == DownloadStatus::Paused);
+
+                let progress: Element<Message> = match download_progress {
+                    Some(progress) if progress.status == DownloadStatus::Downloading => {
+                        ProgressBar::new(0.0..=1.0, progress.fraction().unwrap_or_default())
+                            .height(Length::Units(6))
+                            .style(style::ProgressBar(color_palette))
+                            .into()
+                    }
+                    Some(progress) if progress.status == DownloadStatus::Paused => {
+                        Text::new(localized_string("paused"))
+                            .size(font_size())
+                            .into()
+                    }
+                    Some(_) => Text::new(localized_string("queued"))
+                        .size(font_size())
+                        .into(),
+                    None => Text::new(localized_string("downloading"))
+                        .size(font_size())
+                        .into(),
+                };
+
+                let (pause_text, pause_interaction) = if is_paused {
+                    (">", Interaction::ResumeDownload(id.clone()))
+                } else {
+                    ("||", Interaction::PauseDownload(id.clone()))
+                };
+                let pause_button: Element<Interaction> = Button::new(
+                    &mut addon.pause_download_btn_state,
+                    Text::new(pause_text).size(font_size()),
+                )
+                .style(style::DefaultButton(color_palette))
+                .on_press(pause_interaction)
+                .into();
+
+                let cancel_button: Element<Interaction> = Button::new(
+                    &mut addon.update_btn_state,
+                    Text::new("x").size(font_size()),
+                )
+                .style(style::DefaultButton(color_palette))
+                .on_press(Interaction::CancelDownload(id))
+                .into();
+
+                let row = Row::new()
+                    .push(Container::new(progress).width(Length::Fill).center_x())
+                    .push(pause_button.map(Message::Interaction))
+                    .push(cancel_button.map(Message::Interaction))
+                    .align_items(Align::Center)
+                    .spacing(5);
+
+                Container::new(row)
                     .height(default_height)
                     .width(*width)
                     .center_y()
                     .center_x()
-                    .padding(5)
+                    .padding(cell_padding)
+                    .style(style::NormalForegroundContainer(color_palette))
+            }
+            AddonState::Unpacking => {
+                Container::new(Text::new(localized_string("unpacking")).size(font_size()))
+                    .height(default_height)
+                    .width(*width)
+                    .center_y()
+                    .center_x()
+                    .padding(cell_padding)
+                    .style(style::NormalForegroundContainer(color_palette))
+            }
+            AddonState::Fingerprint => {
+                Container::new(Text::new(localized_string("hashing")).size(font_size()))
+                    .height(default_height)
+                    .width(*width)
+                    .center_y()
+                    .center_x()
+                    .padding(cell_padding)
+                    .style(style::NormalForegroundContainer(color_palette))
+            }
+            AddonState::Ignored => {
+                Container::new(Text::new(localized_string("ignored")).size(font_size()))
+                    .height(default_height)
+                    .width(*width)
+                    .center_y()
+                    .center_x()
+                    .padding(cell_padding)
+                    .style(style::NormalForegroundContainer(color_palette))
+            }
+            AddonState::Pinned => {
+                Container::new(Text::new(localized_string("pinned")).size(font_size()))
+                    .height(default_height)
+                    .width(*width)
+                    .center_y()
+                    .center_x()
+                    .padding(cell_padding)
+                    .style(style::NormalForegroundContainer(color_palette))
+            }
+            AddonState::Symlinked => {
+                Container::new(Text::new(localized_string("symlinked")).size(font_size()))
+                    .height(default_height)
+                    .width(*width)
+                    .center_y()
+                    .center_x()
+                    .padding(cell_padding)
+                    .style(style::NormalForegroundContainer(color_palette))
+            }
+            AddonState::Unknown => {
+                Container::new(Text::new(localized_string("unknown")).size(font_size()))
+                    .height(default_height)
+                    .width(*width)
+                    .center_y()
+                    .center_x()
+                    .padding(cell_padding)
                     .style(style::NormalForegroundContainer(color_palette))
             }
-            AddonState::Unpacking => Container::new(Text::new("Unpacking").size(DEFAULT_FONT_SIZE))
-                .height(default_height)
-                .width(*width)
-                .center_y()
-                .center_x()
-                .padding(5)
-                .style(style::NormalForegroundContainer(color_palette)),
-            AddonState::Fingerprint => Container::new(Text::new("Hashing").size(DEFAULT_FONT_SIZE))
-                .height(default_height)
-                .width(*width)
-                .center_y()
-                .center_x()
-                .padding(5)
-                .style(style::NormalForegroundContainer(color_palette)),
-            AddonState::Ignored => Container::new(Text::new("Ignored").size(DEFAULT_FONT_SIZE))
-                .height(default_height)
-                .width(*width)
-                .center_y()
-                .center_x()
-                .padding(5)
-                .style(style::NormalForegroundContainer(color_palette)),
-            AddonState::Unknown => Container::new(Text::new("Unknown").size(DEFAULT_FONT_SIZE))
-                .height(default_height)
-                .width(*width)
-                .center_y()
-                .center_x()
-                .padding(5)
-                .style(style::NormalForegroundContainer(color_palette)),
         };
 
         row_containers.push((idx, update_button_container));
@@ -825,18 +1837,20 @@ pub fn addon_data_cell<'a, 'b>(
     if is_addon_expanded {
         match expand_type {
             ExpandType::Changelog(changelog) => {
+                let loading = localized_string("loading");
                 let changelog_text = match changelog {
                     Changelog::Some(_, payload, _) => &payload.changelog,
-                    _ => "Loading...",
+                    _ => &loading,
                 };
 
-                let changelog_title_text = Text::new("Changelog").size(DEFAULT_FONT_SIZE);
+                let changelog_title_text =
+                    Text::new(localized_string("changelog")).size(font_size());
                 let changelog_title_container = Container::new(changelog_title_text)
                     .style(style::BrightForegroundContainer(color_palette));
 
                 let mut full_changelog_button = Button::new(
                     &mut addon.full_changelog_btn_state,
-                    Text::new("Full Changelog").size(DEFAULT_FONT_SIZE),
+                    Text::new(localized_string("full-changelog")).size(font_size()),
                 )
                 .style(style::DefaultButton(color_palette));
 
@@ -857,7 +1871,7 @@ pub fn addon_data_cell<'a, 'b>(
                 let column = Column::new()
                     .push(changelog_title_container)
                     .push(Space::new(Length::Units(0), Length::Units(12)))
-                    .push(Text::new(changelog_text).size(DEFAULT_FONT_SIZE))
+                    .push(Text::new(changelog_text).size(font_size()))
                     .push(Space::new(Length::Units(0), Length::Units(8)))
                     .push(button_row)
                     .push(Space::new(Length::Units(0), Length::Units(4)));
@@ -880,55 +1894,70 @@ pub fn addon_data_cell<'a, 'b>(
                     .push(row);
             }
             ExpandType::Details(_) => {
-                let notes = notes.unwrap_or_else(|| "No description for addon.".to_string());
-                let author = author.unwrap_or_else(|| "-".to_string());
+                // Prefer the details from the repository, once fetched.
+                let notes = details
+                    .and_then(|d| d.summary.clone())
+                    .filter(|s| !s.is_empty())
+                    .or(notes)
+                    .unwrap_or_else(|| localized_string("no-description"));
+                let author = details
+                    .map(|d| d.authors.join(", "))
+                    .filter(|a| !a.is_empty())
+                    .or(author)
+                    .unwrap_or_else(|| "-".to_string());
                 let left_spacer = Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0));
                 let space = Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING * 2));
                 let bottom_space = Space::new(Length::Units(0), Length::Units(4));
-                let notes_title_text = Text::new("Summary").size(DEFAULT_FONT_SIZE);
-                let notes_text = Text::new(notes).size(DEFAULT_FONT_SIZE);
-                let author_text = Text::new(author).size(DEFAULT_FONT_SIZE);
-                let author_title_text = Text::new("Author(s)").size(DEFAULT_FONT_SIZE);
+                let notes_title_text = Text::new(localized_string("summary")).size(font_size());
+                let notes_text = Text::new(notes).size(font_size());
+                let author_text = Text::new(author).size(font_size());
+                let author_title_text = Text::new(localized_string("authors")).size(font_size());
                 let author_title_container = Container::new(author_title_text)
                     .style(style::BrightForegroundContainer(color_palette));
                 let notes_title_container = Container::new(notes_title_text)
                     .style(style::BrightForegroundContainer(color_palette));
 
                 let release_date_text: String = if let Some(package) = release_package {
-                    let f = timeago::Formatter::new();
+                    let f = time_formatter();
                     let now = Local::now();
 
                     if let Some(time) = package.date_time.as_ref() {
-                        format!("is {}", f.convert_chrono(*time, now))
+                        localized_string_with(
+                            "released-ago",
+                            &[("time", &f.convert_chrono(*time, now))],
+                        )
                     } else {
                         "".to_string()
                     }
                 } else {
-                    "has no avaiable release".to_string()
+                    localized_string("no-release")
                 };
-                let release_date_text = Text::new(release_date_text).size(DEFAULT_FONT_SIZE);
+                let release_date_text = Text::new(release_date_text).size(font_size());
                 let release_date_text_container = Container::new(release_date_text)
                     .center_y()
                     .padding(5)
                     .style(style::NormalForegroundContainer(color_palette));
 
                 let release_channel_title =
-                    Text::new("Remote release channel").size(DEFAULT_FONT_SIZE);
+                    Text::new(localized_string("remote-release-channel")).size(font_size());
                 let release_channel_title_container = Container::new(release_channel_title)
                     .style(style::BrightForegroundContainer(color_palette));
+                let id = addon.primary_folder_id.clone();
                 let release_channel_list = PickList::new(
                     &mut addon.pick_release_channel_state,
                     &ReleaseChannel::ALL[..],
                     Some(addon.release_channel),
-                    Message::ReleaseChannelSelected,
+                    move |release_channel| {
+                        Message::ReleaseChannelSelected((id.clone(), release_channel))
+                    },
                 )
-                .text_size(14)
+                .text_size(font_size())
                 .width(Length::Units(100))
                 .style(style::PickList(color_palette));
 
                 let mut website_button = Button::new(
                     &mut addon.website_btn_state,
-                    Text::new("Website").size(DEFAULT_FONT_SIZE),
+                    Text::new(localized_string("website")).size(font_size()),
                 )
                 .style(style::DefaultButton(color_palette));
 
@@ -938,45 +1967,142 @@ pub fn addon_data_cell<'a, 'b>(
 
                 let website_button: Element<Interaction> = website_button.into();
 
+                let change_source_button: Element<Interaction> = Button::new(
+                    &mut addon.change_source_btn_state,
+                    Text::new(localized_string("change-source")).size(font_size()),
+                )
+                .on_press(Interaction::ToggleChangeSource(
+                    addon.primary_folder_id.clone(),
+                ))
+                .style(style::DefaultButton(color_palette))
+                .into();
+
+                let screenshots = details
+                    .map(|d| d.screenshots.as_slice())
+                    .unwrap_or_default();
+                let mut screenshots_button = Button::new(
+                    &mut addon.screenshots_btn_state,
+                    Text::new(localized_string_with(
+                        "screenshots",
+                        &[("count", &screenshots.len())],
+                    ))
+                    .size(font_size()),
+                )
+                .style(style::DefaultButton(color_palette));
+
+                if let Some(screenshot) = screenshots.first() {
+                    screenshots_button =
+                        screenshots_button.on_press(Interaction::OpenLink(screenshot.clone()));
+                }
+
+                let screenshots_button: Element<Interaction> = screenshots_button.into();
+
+                let mut changelog_button = Button::new(
+                    &mut addon.changelog_btn_state,
+                    Text::new(localized_string("changelog")).size(font_size()),
+                )
+                .style(style::DefaultButton(color_palette));
+
+                // Shows the changelog of the pending update, if there is one.
+                if matches!(
+                    addon.active_repository,
+                    Some(Repository::Curse) | Some(Repository::Tukui)
+                ) {
+                    let key = if addon.state == AddonState::Updatable {
+                        AddonVersionKey::Remote
+                    } else {
+                        AddonVersionKey::Local
+                    };
+
+                    changelog_button = changelog_button.on_press(Interaction::Expand(
+                        ExpandType::Changelog(Changelog::Request(addon_cloned.clone(), key)),
+                    ));
+                }
+
+                let changelog_button: Element<Interaction> = changelog_button.into();
+
                 let mut force_download_button = Button::new(
                     &mut addon.force_btn_state,
-                    Text::new("Force update").size(DEFAULT_FONT_SIZE),
+                    Text::new(localized_string("force-update")).size(font_size()),
                 )
                 .style(style::DefaultButton(color_palette));
 
                 // If we have a release package on addon, enable force update.
-                if release_package.is_some() {
+                // Symlinked addons are never overwritten.
+                let is_symlinked = addon.state == AddonState::Symlinked;
+                if release_package.is_some() && !is_symlinked && !network::is_offline() {
                     force_download_button = force_download_button
                         .on_press(Interaction::Update(addon.primary_folder_id.clone()));
                 }
 
                 let force_download_button: Element<Interaction> = force_download_button.into();
 
+                let mut rollback_button = Button::new(
+                    &mut addon.rollback_btn_state,
+                    Text::new(localized_string("rollback")).size(font_size()),
+                )
+                .style(style::DefaultButton(color_palette));
+
+                // Only addons from a repository have their archives cached.
+                if addon.active_repository.is_some() && !is_symlinked {
+                    rollback_button = rollback_button
+                        .on_press(Interaction::Rollback(addon.primary_folder_id.clone()));
+                }
+
+                let rollback_button: Element<Interaction> = rollback_button.into();
+
                 let is_ignored = addon.state == AddonState::Ignored;
                 let ignore_button_text = if is_ignored {
-                    Text::new("Unignore").size(DEFAULT_FONT_SIZE)
+                    Text::new(localized_string("unignore")).size(font_size())
                 } else {
-                    Text::new("Ignore").size(DEFAULT_FONT_SIZE)
+                    Text::new(localized_string("ignore")).size(font_size())
                 };
 
                 let mut ignore_button =
                     Button::new(&mut addon.ignore_btn_state, ignore_button_text)
-                        .on_press(Interaction::Ignore(addon.primary_folder_id.clone()))
                         .style(style::DefaultButton(color_palette));
 
-                if is_ignored {
+                // Addons ignored by a pattern in the config stay ignored until the
+                // pattern is removed.
+                if is_ignored && !is_ignored_by_pattern {
                     ignore_button = ignore_button
                         .on_press(Interaction::Unignore(addon.primary_folder_id.clone()));
-                } else {
+                } else if !is_ignored && !is_symlinked {
                     ignore_button = ignore_button
                         .on_press(Interaction::Ignore(addon.primary_folder_id.clone()));
                 }
 
                 let ignore_button: Element<Interaction> = ignore_button.into();
 
+                let is_pinned = addon.state == AddonState::Pinned;
+                let pin_button_text = if is_pinned {
+                    Text::new(localized_string("unpin")).size(font_size())
+                } else {
+                    Text::new(localized_string("pin-version")).size(font_size())
+                };
+
+                let mut pin_button = Button::new(&mut addon.pin_btn_state, pin_button_text)
+                    .style(style::DefaultButton(color_palette));
+
+                // Only addons from a repository can be updated, so only they can be pinned.
+                if is_pinned {
+                    pin_button =
+                        pin_button.on_press(Interaction::Unpin(addon.primary_folder_id.clone()));
+                } else if matches!(addon.state, AddonState::Ajour(_) | AddonState::Updatable) {
+                    pin_button =
+                        pin_button.on_press(Interaction::Pin(addon.primary_folder_id.clone()));
+                }
+
+                let pin_button: Element<Interaction> = pin_button.into();
+
+                let delete_button_text = if is_delete_confirmation {
+                    localized_string("confirm-delete")
+                } else {
+                    localized_string("delete")
+                };
                 let delete_button: Element<Interaction> = Button::new(
                     &mut addon.delete_btn_state,
-                    Text::new("Delete").size(DEFAULT_FONT_SIZE),
+                    Text::new(delete_button_text).size(font_size()),
                 )
                 .on_press(Interaction::Delete(addon.primary_folder_id.clone()))
                 .style(style::DefaultDeleteButton(color_palette))
@@ -990,13 +2116,23 @@ pub fn addon_data_cell<'a, 'b>(
                     .push(Space::new(Length::Fill, Length::Units(0)))
                     .push(website_button.map(Message::Interaction))
                     .push(Space::new(Length::Units(5), Length::Units(0)))
+                    .push(change_source_button.map(Message::Interaction))
+                    .push(Space::new(Length::Units(5), Length::Units(0)))
+                    .push(screenshots_button.map(Message::Interaction))
+                    .push(Space::new(Length::Units(5), Length::Units(0)))
+                    .push(changelog_button.map(Message::Interaction))
+                    .push(Space::new(Length::Units(5), Length::Units(0)))
                     .push(force_download_button.map(Message::Interaction))
                     .push(Space::new(Length::Units(5), Length::Units(0)))
+                    .push(rollback_button.map(Message::Interaction))
+                    .push(Space::new(Length::Units(5), Length::Units(0)))
+                    .push(pin_button.map(Message::Interaction))
+                    .push(Space::new(Length::Units(5), Length::Units(0)))
                     .push(ignore_button.map(Message::Interaction))
                     .push(Space::new(Length::Units(5), Length::Units(0)))
                     .push(delete_button.map(Message::Interaction))
                     .width(Length::Fill);
-                let column = Column::new()
+                let mut column = Column::new()
                     .push(author_title_container)
                     .push(Space::new(Length::Units(0), Length::Units(3)))
                     .push(author_text)
@@ -1007,10 +2143,293 @@ pub fn addon_data_cell<'a, 'b>(
                     .push(Space::new(Length::Units(0), Length::Units(15)))
                     .push(release_channel_title_container)
                     .push(Space::new(Length::Units(0), Length::Units(3)))
-                    .push(test_row)
-                    .push(space)
-                    .push(button_row)
-                    .push(bottom_space);
+                    .push(test_row);
+
+                // Takes effect on the next refresh, falling back to the other
+                // repository if the preferred one is unavailable.
+                if let Some(preferred_source) = preferred_source {
+                    let preferred_source_title =
+                        Text::new(localized_string("preferred-source")).size(font_size());
+                    let preferred_source_title_container = Container::new(preferred_source_title)
+                        .style(style::BrightForegroundContainer(color_palette));
+                    let id = addon.primary_folder_id.clone();
+                    let preferred_source_list = PickList::new(
+                        &mut addon.pick_preferred_source_state,
+                        &ChangeSourceState::REPOSITORIES[..],
+                        Some(preferred_source),
+                        move |repository| {
+                            Message::PreferredSourceSelected((id.clone(), repository))
+                        },
+                    )
+                    .text_size(font_size())
+                    .width(Length::Units(100))
+                    .style(style::PickList(color_palette));
+
+                    column = column
+                        .push(Space::new(Length::Units(0), Length::Units(15)))
+                        .push(preferred_source_title_container)
+                        .push(Space::new(Length::Units(0), Length::Units(3)))
+                        .push(preferred_source_list);
+                }
+
+                if let Some(download_count) = details.and_then(|d| d.download_count) {
+                    let downloads_title_text =
+                        Text::new(localized_string("downloads")).size(font_size());
+                    let downloads_title_container = Container::new(downloads_title_text)
+                        .style(style::BrightForegroundContainer(color_palette));
+                    let downloads_text = Text::new(download_count.to_formatted_string(&Locale::en))
+                        .size(font_size());
+
+                    column = column
+                        .push(Space::new(Length::Units(0), Length::Units(15)))
+                        .push(downloads_title_container)
+                        .push(Space::new(Length::Units(0), Length::Units(3)))
+                        .push(downloads_text);
+                }
+
+                // How stale the addon is, and how long the pending update has been out.
+                let mut history = vec![];
+                if let Some(installed_at) = addon.install_dates.installed_at {
+                    history.push(localized_string_with(
+                        "installed-ago",
+                        &[("time", &format_time_ago(installed_at))],
+                    ));
+                }
+                if let Some(updated_at) = addon.install_dates.updated_at {
+                    history.push(localized_string_with(
+                        "last-updated-ago",
+                        &[("time", &format_time_ago(updated_at))],
+                    ));
+                }
+                if addon.state == AddonState::Updatable {
+                    if let Some(time) = release_package.and_then(|p| p.date_time) {
+                        let f = time_formatter();
+                        history.push(localized_string_with(
+                            "update-released-ago",
+                            &[("time", &f.convert_chrono(time, Local::now()))],
+                        ));
+                    }
+                }
+
+                if !history.is_empty() {
+                    let history_title_text =
+                        Text::new(localized_string("history")).size(font_size());
+                    let history_title_container = Container::new(history_title_text)
+                        .style(style::BrightForegroundContainer(color_palette));
+                    let history_text = Text::new(history.join(", ")).size(font_size());
+
+                    column = column
+                        .push(Space::new(Length::Units(0), Length::Units(15)))
+                        .push(history_title_container)
+                        .push(Space::new(Length::Units(0), Length::Units(3)))
+                        .push(history_text);
+                }
+
+                if !dependents.is_empty() {
+                    let dependents_title_text =
+                        Text::new(localized_string("required-by")).size(font_size());
+                    let dependents_title_container = Container::new(dependents_title_text)
+                        .style(style::BrightForegroundContainer(color_palette));
+                    let dependents_text = Text::new(dependents.join(", ")).size(font_size());
+
+                    column = column
+                        .push(Space::new(Length::Units(0), Length::Units(15)))
+                        .push(dependents_title_container)
+                        .push(Space::new(Length::Units(0), Length::Units(3)))
+                        .push(dependents_text);
+                }
+
+                if let Some(state) = change_source_state {
+                    let source_title_text = Text::new(localized_string("source")).size(font_size());
+                    let source_title_container = Container::new(source_title_text)
+                        .style(style::BrightForegroundContainer(color_palette));
+
+                    let repository_list = PickList::new(
+                        &mut state.repository_picklist_state,
+                        &ChangeSourceState::REPOSITORIES[..],
+                        Some(state.repository),
+                        Message::ChangeSourceRepositorySelected,
+                    )
+                    .text_size(font_size())
+                    .width(Length::Units(100))
+                    .style(style::PickList(color_palette));
+
+                    let id = addon.primary_folder_id.clone();
+                    let mut id_input = TextInput::new(
+                        &mut state.id_input_state,
+                        &localized_string("project-id"),
+                        &state.id,
+                        Interaction::ChangeSourceIdInput,
+                    )
+                    .size(font_size())
+                    .padding(5)
+                    .width(Length::Units(150))
+                    .style(style::CatalogQueryInput(color_palette));
+
+                    let mut save_button = Button::new(
+                        &mut state.save_btn_state,
+                        Text::new(localized_string("save")).size(font_size()),
+                    )
+                    .style(style::DefaultButton(color_palette));
+
+                    if !state.id.trim().is_empty() {
+                        id_input = id_input.on_submit(Interaction::SaveSource(id.clone()));
+                        save_button = save_button.on_press(Interaction::SaveSource(id.clone()));
+                    }
+
+                    let id_input: Element<Interaction> = id_input.into();
+                    let save_button: Element<Interaction> = save_button.into();
+
+                    let reset_button: Element<Interaction> = Button::new(
+                        &mut state.reset_btn_state,
+                        Text::new(localized_string("match-automatically")).size(font_size()),
+                    )
+                    .on_press(Interaction::ResetSource(id))
+                    .style(style::DefaultButton(color_palette))
+                    .into();
+
+                    let mut source_row = Row::new()
+                        .push(repository_list)
+                        .push(Space::new(Length::Units(5), Length::Units(0)))
+                        .push(id_input.map(Message::Interaction))
+                        .push(Space::new(Length::Units(5), Length::Units(0)))
+                        .push(save_button.map(Message::Interaction))
+                        .push(Space::new(Length::Units(5), Length::Units(0)))
+                        .push(reset_button.map(Message::Interaction))
+                        .align_items(Align::Center);
+
+                    if let Some(error) = &state.error {
+                        let error_text = Text::new(error).size(font_size());
+                        let error_container = Container::new(error_text)
+                            .padding(5)
+                            .style(style::NormalErrorForegroundContainer(color_palette));
+
+                        source_row = source_row
+                            .push(Space::new(Length::Units(5), Length::Units(0)))
+                            .push(error_container);
+                    }
+
+                    column = column
+                        .push(Space::new(Length::Units(0), Length::Units(15)))
+                        .push(source_title_container)
+                        .push(Space::new(Length::Units(0), Length::Units(3)))
+                        .push(source_row);
+                }
+
+                // Updating either addon replaces the shared folders, and deleting
+                // this one keeps them.
+                if !shared_folders.is_empty() {
+                    let shared_title_text =
+                        Text::new(localized_string("shared-folders")).size(font_size());
+                    let shared_title_container = Container::new(shared_title_text)
+                        .style(style::BrightForegroundContainer(color_palette));
+                    let shared_text = Text::new(shared_folders.join(", ")).size(font_size());
+
+                    column = column
+                        .push(Space::new(Length::Units(0), Length::Units(15)))
+                        .push(shared_title_container)
+                        .push(Space::new(Length::Units(0), Length::Units(3)))
+                        .push(shared_text);
+                }
+
+                if let Some((compatibility, interface, client_interface)) = compatibility {
+                    let compatibility_title_text =
+                        Text::new(localized_string("compatibility")).size(font_size());
+                    let compatibility_title_container = Container::new(compatibility_title_text)
+                        .style(style::BrightForegroundContainer(color_palette));
+                    let explanation = localized_string(match compatibility {
+                        Compatibility::OutOfDate => "compatibility-out-of-date-explanation",
+                        Compatibility::BadlyOutOfDate => {
+                            "compatibility-badly-out-of-date-explanation"
+                        }
+                        _ => "compatibility-other-game-version-explanation",
+                    });
+                    let compatibility_text = Text::new(localized_string_with(
+                        "compatibility-details",
+                        &[
+                            ("interface", &interface),
+                            ("client", &client_interface),
+                            ("compatibility", &compatibility),
+                            ("explanation", &explanation),
+                        ],
+                    ))
+                    .size(font_size());
+
+                    column = column
+                        .push(Space::new(Length::Units(0), Length::Units(15)))
+                        .push(compatibility_title_container)
+                        .push(Space::new(Length::Units(0), Length::Units(3)))
+                        .push(compatibility_text);
+                }
+
+                if let Some((months, alternatives)) = abandoned {
+                    let abandoned_title_text =
+                        Text::new(localized_string("abandoned")).size(font_size());
+                    let abandoned_title_container = Container::new(abandoned_title_text)
+                        .style(style::BrightForegroundContainer(color_palette));
+                    let mut explanation =
+                        localized_string_with("abandoned-details", &[("months", &months)]);
+                    if !alternatives.is_empty() {
+                        explanation.push(' ');
+                        explanation.push_str(&localized_string_with(
+                            "abandoned-alternatives",
+                            &[("addons", &alternatives.join(", "))],
+                        ));
+                    }
+                    let abandoned_text = Text::new(explanation).size(font_size());
+
+                    column = column
+                        .push(Space::new(Length::Units(0), Length::Units(15)))
+                        .push(abandoned_title_container)
+                        .push(Space::new(Length::Units(0), Length::Units(3)))
+                        .push(abandoned_text);
+                }
+
+                if is_delete_confirmation {
+                    let mut warning = localized_string_with(
+                        "delete-warning",
+                        &[("title", &addon_cloned.title())],
+                    );
+                    if !dependents.is_empty() {
+                        warning.push(' ');
+                        warning.push_str(&localized_string("delete-warning-dependents"));
+                    }
+                    let warning_text = Text::new(warning).size(font_size());
+                    let warning_container = Container::new(warning_text)
+                        .style(style::BrightForegroundContainer(color_palette));
+
+                    let saved_variables_checkbox: Element<Interaction> = Checkbox::new(
+                        delete_saved_variables,
+                        localized_string("also-delete-saved-variables"),
+                        Interaction::ToggleDeleteSavedVariables,
+                    )
+                    .text_size(font_size())
+                    .spacing(5)
+                    .style(style::DefaultCheckbox(color_palette))
+                    .into();
+
+                    let cancel_button: Element<Interaction> = Button::new(
+                        &mut addon.cancel_delete_btn_state,
+                        Text::new(localized_string("cancel")).size(font_size()),
+                    )
+                    .on_press(Interaction::CancelDelete)
+                    .style(style::DefaultButton(color_palette))
+                    .into();
+
+                    let confirmation_row = Row::new()
+                        .push(saved_variables_checkbox.map(Message::Interaction))
+                        .push(Space::new(Length::Units(10), Length::Units(0)))
+                        .push(cancel_button.map(Message::Interaction))
+                        .align_items(Align::Center);
+
+                    column = column
+                        .push(Space::new(Length::Units(0), Length::Units(15)))
+                        .push(warning_container)
+                        .push(Space::new(Length::Units(0), Length::Units(5)))
+                        .push(confirmation_row);
+                }
+
+                let column = column.push(space).push(button_row).push(bottom_space);
                 let details_container = Container::new(column)
                     .width(Length::Fill)
                     .padding(20)
@@ -1033,9 +2452,12 @@ pub fn addon_data_cell<'a, 'b>(
         }
     }
 
-    Container::new(addon_column)
-        .width(Length::Fill)
-        .style(style::Row(color_palette))
+    let container = Container::new(addon_column).width(Length::Fill);
+    if is_selected {
+        container.style(style::SelectedRow(color_palette))
+    } else {
+        container.style(style::Row(color_palette))
+    }
 }
 
 fn row_title<T: PartialEq>(
@@ -1078,9 +2500,7 @@ pub fn addon_row_titles<'a>(
 
         let mut row_header = Button::new(
             &mut column.btn_state,
-            Text::new(row_title)
-                .size(DEFAULT_FONT_SIZE)
-                .width(Length::Fill),
+            Text::new(row_title).size(font_size()).width(Length::Fill),
         )
         .width(Length::Fill)
         .on_press(Interaction::SortColumn(column_key));
@@ -1116,27 +2536,185 @@ pub fn addon_row_titles<'a>(
     })
 }
 
+/// Container with the actions for the addons selected in My Addons.
+pub fn selection_container<'a>(
+    color_palette: ColorPalette,
+    selection_state: &'a mut SelectionState,
+    addons: &[Addon],
+    delete_saved_variables: bool,
+) -> Container<'a, Message> {
+    let selected: Vec<_> = addons
+        .iter()
+        .filter(|a| selection_state.is_selected(&a.primary_folder_id))
+        .collect();
+
+    let selected_text = Text::new(localized_string_with(
+        "selected-count",
+        &[("count", &selected.len())],
+    ))
+    .size(font_size());
+    let selected_container = Container::new(selected_text)
+        .center_y()
+        .padding(5)
+        .style(style::NormalBackgroundContainer(color_palette));
+
+    let mut update_button = Button::new(
+        &mut selection_state.update_btn_state,
+        Text::new(localized_string("update")).size(font_size()),
+    )
+    .style(style::DefaultBoxedButton(color_palette));
+
+    if selected.iter().any(|a| a.state == AddonState::Updatable) && !network::is_offline() {
+        update_button = update_button.on_press(Interaction::UpdateSelected);
+    }
+
+    let update_button: Element<Interaction> = update_button.into();
+
+    let mut ignore_button = Button::new(
+        &mut selection_state.ignore_btn_state,
+        Text::new(localized_string("ignore")).size(font_size()),
+    )
+    .style(style::DefaultBoxedButton(color_palette));
+
+    if selected
+        .iter()
+        .any(|a| !matches!(a.state, AddonState::Ignored | AddonState::Symlinked))
+    {
+        ignore_button = ignore_button.on_press(Interaction::IgnoreSelected);
+    }
+
+    let ignore_button: Element<Interaction> = ignore_button.into();
+
+    let release_channel_list = PickList::new(
+        &mut selection_state.release_channel_picklist_state,
+        &ReleaseChannel::ALL[..],
+        None,
+        Message::SelectionReleaseChannelSelected,
+    )
+    .text_size(font_size())
+    .width(Length::Units(100))
+    .style(style::PickList(color_palette));
+    let release_channel_text = Text::new(localized_string("release-channel")).size(font_size());
+    let release_channel_container = Container::new(release_channel_text)
+        .center_y()
+        .padding(5)
+        .style(style::NormalBackgroundContainer(color_palette));
+
+    let delete_button_text = if selection_state.is_delete_confirmation {
+        localized_string("confirm-delete")
+    } else {
+        localized_string("delete")
+    };
+    let delete_button: Element<Interaction> = Button::new(
+        &mut selection_state.delete_btn_state,
+        Text::new(delete_button_text).size(font_size()),
+    )
+    .on_press(Interaction::DeleteSelected)
+    .style(style::DefaultDeleteButton(color_palette))
+    .into();
+
+    let clear_button: Element<Interaction> = Button::new(
+        &mut selection_state.clear_btn_state,
+        Text::new(localized_string("clear-selection")).size(font_size()),
+    )
+    .on_press(Interaction::ClearSelection)
+    .style(style::DefaultButton(color_palette))
+    .into();
+
+    let mut row = Row::new()
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(selected_container)
+        .push(Space::new(Length::Fill, Length::Units(0)));
+
+    if selection_state.is_delete_confirmation {
+        // Warn if addons which aren't selected depend on the selected ones.
+        let has_dependents = selected
+            .iter()
+            .flat_map(|a| a.dependents(addons))
+            .any(|d| !selection_state.is_selected(&d.primary_folder_id));
+        if has_dependents {
+            let warning_text =
+                Text::new(localized_string("required-by-other-addons")).size(font_size());
+            let warning_container = Container::new(warning_text)
+                .center_y()
+                .padding(5)
+                .style(style::NormalErrorBackgroundContainer(color_palette));
+
+            row = row
+                .push(warning_container)
+                .push(Space::new(Length::Units(10), Length::Units(0)));
+        }
+
+        let saved_variables_checkbox: Element<Interaction> = Checkbox::new(
+            delete_saved_variables,
+            localized_string("also-delete-saved-variables"),
+            Interaction::ToggleDeleteSavedVariables,
+        )
+        .text_size(font_size())
+        .spacing(5)
+        .style(style::DefaultCheckbox(color_palette))
+        .into();
+
+        row = row
+            .push(saved_variables_checkbox.map(Message::Interaction))
+            .push(Space::new(Length::Units(10), Length::Units(0)));
+    }
+
+    let row = row
+        .push(update_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(ignore_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(release_channel_container)
+        .push(release_channel_list)
+        .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(delete_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(clear_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .align_items(Align::Center);
+
+    Container::new(row)
+        .width(Length::Fill)
+        .padding(5)
+        .style(style::NormalBackgroundContainer(color_palette))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn menu_addons_container<'a>(
     color_palette: ColorPalette,
     update_all_button_state: &'a mut button::State,
+    undo_button_state: &'a mut button::State,
     refresh_button_state: &'a mut button::State,
+    cleanup_button_state: &'a mut button::State,
+    verify_button_state: &'a mut button::State,
+    whats_new_button_state: &'a mut button::State,
+    update_preview_button_state: &'a mut button::State,
     state: &AjourState,
     addons: &[Addon],
     config: &'a mut Config,
+    is_showing_cleanup: bool,
+    is_showing_whats_new: bool,
+    whats_new_count: usize,
+    is_showing_update_preview: bool,
+    filter_state: &'a mut AddonsFilterState,
+    has_other_flavors: bool,
+    other_flavors_updatable: bool,
+    can_undo: bool,
+    next_scheduled_update: Option<NaiveDateTime>,
 ) -> Container<'a, Message> {
     // A row contain general settings.
     let mut settings_row = Row::new().height(Length::Units(35));
 
     let mut update_all_button = Button::new(
         update_all_button_state,
-        Text::new("Update All").size(DEFAULT_FONT_SIZE),
+        Text::new(localized_string("update-all")).size(font_size()),
     )
     .style(style::DefaultButton(color_palette));
 
     let mut refresh_button = Button::new(
         refresh_button_state,
-        Text::new("Refresh").size(DEFAULT_FONT_SIZE),
+        Text::new(localized_string("refresh")).size(font_size()),
     )
     .style(style::DefaultButton(color_palette));
 
@@ -1153,13 +2731,45 @@ pub fn menu_addons_container<'a>(
         .any(|a| matches!(a.state, AddonState::Updatable));
 
     // Enable update_all_button if:
-    //   - We have addons.
     //   - No addon is performing any task.
-    //   - We have updatable addons.
-    if !addons.is_empty() && !addons_performing_actions && any_addon_updatable {
+    //   - We have updatable addons, in any flavor if all are updated.
+    //   - We aren't offline.
+    if !addons_performing_actions
+        && (any_addon_updatable || other_flavors_updatable)
+        && !network::is_offline()
+    {
         update_all_button = update_all_button.on_press(Interaction::UpdateAll);
     }
 
+    let update_preview_button_text = if is_showing_update_preview {
+        localized_string("close-preview")
+    } else {
+        localized_string("preview-updates")
+    };
+    let mut update_preview_button = Button::new(
+        update_preview_button_state,
+        Text::new(update_preview_button_text).size(font_size()),
+    )
+    .style(style::DefaultButton(color_palette));
+
+    // Enable update_preview_button if "Update All" has anything to update, as
+    // nothing is downloaded it doesn't matter if we're offline.
+    if is_showing_update_preview || any_addon_updatable || other_flavors_updatable {
+        update_preview_button = update_preview_button.on_press(Interaction::ToggleUpdatePreview);
+    }
+
+    let mut undo_button = Button::new(
+        undo_button_state,
+        Text::new(localized_string("undo-update")).size(font_size()),
+    )
+    .style(style::DefaultButton(color_palette));
+
+    // Enable undo_button if the last update left a snapshot, and nothing is
+    // being updated or parsed.
+    if can_undo && !addons_performing_actions && !ajour_performing_actions {
+        undo_button = undo_button.on_press(Interaction::UndoLastUpdate);
+    }
+
     // Enable refresh_button if:
     //   - No addon is performing any task.
     //   - Ajour isn't loading
@@ -1170,24 +2780,106 @@ pub fn menu_addons_container<'a>(
         refresh_button = refresh_button.on_press(Interaction::Refresh);
     }
 
+    let cleanup_button_text = if is_showing_cleanup {
+        localized_string("close-clean-up")
+    } else {
+        localized_string("clean-up")
+    };
+    let mut cleanup_button = Button::new(
+        cleanup_button_state,
+        Text::new(cleanup_button_text).size(font_size()),
+    )
+    .style(style::DefaultButton(color_palette));
+
+    // Enable cleanup_button if the addons are loaded and left alone, so folders
+    // of addons being installed aren't mistaken for leftovers.
+    if is_showing_cleanup || (!addons_performing_actions && matches!(state, AjourState::Idle)) {
+        cleanup_button = cleanup_button.on_press(Interaction::ToggleCleanup);
+    }
+
+    let mut verify_button = Button::new(
+        verify_button_state,
+        Text::new(localized_string("verify")).size(font_size()),
+    )
+    .style(style::DefaultButton(color_palette));
+
+    // Enable verify_button if the addons are loaded and left alone, since files
+    // being extracted would be mistaken for modified ones.
+    if !addons_performing_actions && matches!(state, AjourState::Idle) {
+        verify_button = verify_button.on_press(Interaction::VerifyAddons);
+    }
+
+    let whats_new_button_text = if is_showing_whats_new {
+        localized_string("close-whats-new")
+    } else if whats_new_count > 0 {
+        localized_string_with("whats-new-count", &[("count", &whats_new_count)])
+    } else {
+        localized_string("whats-new")
+    };
+    let mut whats_new_button = Button::new(
+        whats_new_button_state,
+        Text::new(whats_new_button_text).size(font_size()),
+    )
+    .style(style::DefaultButton(color_palette));
+
+    if is_showing_whats_new || whats_new_count > 0 {
+        whats_new_button = whats_new_button.on_press(Interaction::ToggleWhatsNew);
+    }
+
     let update_all_button: Element<Interaction> = update_all_button.into();
+    let update_preview_button: Element<Interaction> = update_preview_button.into();
+    let undo_button: Element<Interaction> = undo_button.into();
     let refresh_button: Element<Interaction> = refresh_button.into();
+    let cleanup_button: Element<Interaction> = cleanup_button.into();
+    let verify_button: Element<Interaction> = verify_button.into();
+    let whats_new_button: Element<Interaction> = whats_new_button.into();
 
     // Displays text depending on the state of the app.
     let flavor = config.wow.flavor;
     let ignored_addons = config.addons.ignored.get(&flavor);
     let parent_addons_count = addons
         .iter()
-        .filter(|a| !a.is_ignored(ignored_addons))
+        .filter(|a| !a.is_ignored(ignored_addons) && !config.addons.is_ignored_by_pattern(a))
         .count();
+    let total_size: u64 = addons.iter().map(Addon::size).sum();
+
+    // When updates were last installed on schedule, and when they are next.
+    let now = Local::now().naive_local();
+    let mut schedule_text = String::new();
+    if let Some(last_run) = config.scheduled_updates.last_run {
+        schedule_text.push_str(&format!(
+            " · {}",
+            localized_string_with(
+                "last-checked",
+                &[("time", &format_schedule_time(last_run, now))]
+            )
+        ));
+    }
+    if let Some(next_run) = next_scheduled_update {
+        // A run which is due starts within the minute.
+        schedule_text.push_str(&format!(
+            " · {}",
+            localized_string_with(
+                "next-check",
+                &[("time", &format_schedule_time(next_run.max(now), now))]
+            )
+        ));
+    }
 
     let status_text = match state {
         AjourState::Idle => Text::new(format!(
-            "{} {} addons loaded",
-            parent_addons_count,
-            config.wow.flavor.to_string()
+            "{}{}",
+            localized_string_with(
+                "addons-loaded",
+                &[
+                    ("count", &parent_addons_count),
+                    ("flavor", &config.wow.flavor),
+                    ("size", &format_size(total_size)),
+                ]
+            ),
+            schedule_text,
         ))
-        .size(DEFAULT_FONT_SIZE),
+        .size(font_size()),
         _ => Text::new(""),
     };
 
@@ -1196,6 +2888,52 @@ pub fn menu_addons_container<'a>(
         .padding(5)
         .style(style::NormalBackgroundContainer(color_palette));
 
+    let query_input: Element<Interaction> = TextInput::new(
+        &mut filter_state.query_state,
+        &localized_string("filter-addons"),
+        filter_state.query.as_deref().unwrap_or_default(),
+        Interaction::AddonsQuery,
+    )
+    .size(font_size())
+    .padding(5)
+    .width(Length::Units(150))
+    .style(style::CatalogQueryInput(color_palette))
+    .into();
+
+    let status_filter_picklist: Element<Interaction> = PickList::new(
+        &mut filter_state.status_picklist_state,
+        &AddonStatusFilter::ALL[..],
+        Some(filter_state.status),
+        Interaction::AddonStatusFilterSelected,
+    )
+    .text_size(font_size())
+    .width(Length::Units(130))
+    .style(style::PickList(color_palette))
+    .into();
+
+    let filter_row = Row::new()
+        .push(query_input.map(Message::Interaction))
+        .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(status_filter_picklist.map(Message::Interaction))
+        .align_items(Align::Center);
+    let filter_container = Container::new(filter_row)
+        .center_y()
+        .height(Length::Fill)
+        .style(style::NormalBackgroundContainer(color_palette));
+
+    let update_all_flavors_checkbox = Checkbox::new(
+        config.update_all_flavors,
+        localized_string("update-all-flavors"),
+        |is_checked| Message::Interaction(Interaction::ToggleUpdateAllFlavors(is_checked)),
+    )
+    .text_size(font_size())
+    .spacing(5)
+    .style(style::DefaultCheckbox(color_palette));
+    let update_all_flavors_container = Container::new(update_all_flavors_checkbox)
+        .center_y()
+        .height(Length::Fill)
+        .style(style::NormalBackgroundContainer(color_palette));
+
     // Surrounds the elements with spacers, in order to make the GUI look good.
     settings_row = settings_row
         .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
@@ -1203,7 +2941,28 @@ pub fn menu_addons_container<'a>(
         .push(Space::new(Length::Units(7), Length::Units(0)))
         .push(update_all_button.map(Message::Interaction))
         .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(update_preview_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(undo_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(cleanup_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(verify_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(whats_new_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(7), Length::Units(0)))
         .push(status_container)
+        .push(Space::new(Length::Fill, Length::Units(0)));
+
+    // Only offered if there's more than the current flavor to update.
+    if has_other_flavors {
+        settings_row = settings_row
+            .push(update_all_flavors_container)
+            .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)));
+    }
+
+    settings_row = settings_row
+        .push(filter_container)
         .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)));
 
     // Add space above settings_row.
@@ -1225,12 +2984,9 @@ pub fn menu_container<'a>(
     settings_button_state: &'a mut button::State,
     addon_mode_button_state: &'a mut button::State,
     catalog_mode_btn_state: &'a mut button::State,
-    retail_btn_state: &'a mut button::State,
-    retail_ptr_btn_state: &'a mut button::State,
-    retail_beta_btn_state: &'a mut button::State,
-    classic_btn_state: &'a mut button::State,
-    classic_ptr_btn_state: &'a mut button::State,
-    needs_update: Option<&'a str>,
+    wago_mode_btn_state: &'a mut button::State,
+    flavor_btn_states: &'a mut [(Flavor, button::State)],
+    needs_update: Option<&AppRelease>,
     new_release_button_state: &'a mut button::State,
 ) -> Container<'a, Message> {
     // A row contain general settings.
@@ -1238,13 +2994,19 @@ pub fn menu_container<'a>(
 
     let mut addons_mode_button = Button::new(
         addon_mode_button_state,
-        Text::new("My Addons").size(DEFAULT_FONT_SIZE),
+        Text::new(localized_string("my-addons")).size(font_size()),
     )
     .style(style::DisabledDefaultButton(color_palette));
 
     let mut catalog_mode_button = Button::new(
         catalog_mode_btn_state,
-        Text::new("Catalog").size(DEFAULT_FONT_SIZE),
+        Text::new(localized_string("catalog")).size(font_size()),
+    )
+    .style(style::DisabledDefaultButton(color_palette));
+
+    let mut wago_mode_button = Button::new(
+        wago_mode_btn_state,
+        Text::new(localized_string("wago")).size(font_size()),
     )
     .style(style::DisabledDefaultButton(color_palette));
 
@@ -1253,11 +3015,18 @@ pub fn menu_container<'a>(
             addons_mode_button =
                 addons_mode_button.style(style::SelectedDefaultButton(color_palette));
             catalog_mode_button = catalog_mode_button.style(style::DefaultButton(color_palette));
+            wago_mode_button = wago_mode_button.style(style::DefaultButton(color_palette));
         }
         AjourMode::Catalog => {
             addons_mode_button = addons_mode_button.style(style::DefaultButton(color_palette));
             catalog_mode_button =
                 catalog_mode_button.style(style::SelectedDefaultButton(color_palette));
+            wago_mode_button = wago_mode_button.style(style::DefaultButton(color_palette));
+        }
+        AjourMode::Wago => {
+            addons_mode_button = addons_mode_button.style(style::DefaultButton(color_palette));
+            catalog_mode_button = catalog_mode_button.style(style::DefaultButton(color_palette));
+            wago_mode_button = wago_mode_button.style(style::SelectedDefaultButton(color_palette));
         }
     }
 
@@ -1267,132 +3036,50 @@ pub fn menu_container<'a>(
             addons_mode_button.on_press(Interaction::ModeSelected(AjourMode::MyAddons));
         catalog_mode_button =
             catalog_mode_button.on_press(Interaction::ModeSelected(AjourMode::Catalog));
+        wago_mode_button = wago_mode_button.on_press(Interaction::ModeSelected(AjourMode::Wago));
     } else {
         addons_mode_button = addons_mode_button.style(style::DisabledDefaultButton(color_palette));
         catalog_mode_button =
             catalog_mode_button.style(style::DisabledDefaultButton(color_palette));
+        wago_mode_button = wago_mode_button.style(style::DisabledDefaultButton(color_palette));
     }
 
     let addons_mode_button: Element<Interaction> = addons_mode_button.into();
     let catalog_mode_button: Element<Interaction> = catalog_mode_button.into();
+    let wago_mode_button: Element<Interaction> = wago_mode_button.into();
 
     let segmented_mode_control_container = Row::new()
         .push(addons_mode_button.map(Message::Interaction))
         .push(catalog_mode_button.map(Message::Interaction))
+        .push(wago_mode_button.map(Message::Interaction))
         .spacing(1);
 
-    let mut retail_button = Button::new(
-        retail_btn_state,
-        Text::new(Flavor::Retail.to_string()).size(DEFAULT_FONT_SIZE),
-    )
-    .style(style::DisabledDefaultButton(color_palette))
-    .on_press(Interaction::FlavorSelected(Flavor::Retail));
-
-    let mut retail_ptr_button = Button::new(
-        retail_ptr_btn_state,
-        Text::new(Flavor::RetailPTR.to_string()).size(DEFAULT_FONT_SIZE),
-    )
-    .style(style::DisabledDefaultButton(color_palette))
-    .on_press(Interaction::FlavorSelected(Flavor::RetailPTR));
-
-    let mut retail_beta_button = Button::new(
-        retail_beta_btn_state,
-        Text::new(Flavor::RetailBeta.to_string()).size(DEFAULT_FONT_SIZE),
-    )
-    .style(style::DisabledDefaultButton(color_palette))
-    .on_press(Interaction::FlavorSelected(Flavor::RetailBeta));
-
-    let mut classic_button = Button::new(
-        classic_btn_state,
-        Text::new(Flavor::Classic.to_string()).size(DEFAULT_FONT_SIZE),
-    )
-    .style(style::DisabledDefaultButton(color_palette))
-    .on_press(Interaction::FlavorSelected(Flavor::Classic));
-
-    let mut classic_ptr_button = Button::new(
-        classic_ptr_btn_state,
-        Text::new(Flavor::ClassicPTR.to_string()).size(DEFAULT_FONT_SIZE),
-    )
-    .style(style::DisabledDefaultButton(color_palette))
-    .on_press(Interaction::FlavorSelected(Flavor::ClassicPTR));
-
     let disable_flavor_buttons = matches!(state, AjourState::Welcome | AjourState::Loading);
 
-    if !disable_flavor_buttons {
-        match config.wow.flavor {
-            Flavor::Retail => {
-                retail_button = retail_button.style(style::SelectedDefaultButton(color_palette));
-                retail_ptr_button = retail_ptr_button.style(style::DefaultButton(color_palette));
-                retail_beta_button = retail_beta_button.style(style::DefaultButton(color_palette));
-                classic_button = classic_button.style(style::DefaultButton(color_palette));
-                classic_ptr_button = classic_ptr_button.style(style::DefaultButton(color_palette));
-            }
-            Flavor::RetailPTR => {
-                retail_button = retail_button.style(style::DefaultButton(color_palette));
-                retail_ptr_button =
-                    retail_ptr_button.style(style::SelectedDefaultButton(color_palette));
-                retail_beta_button = retail_beta_button.style(style::DefaultButton(color_palette));
-                classic_button = classic_button.style(style::DefaultButton(color_palette));
-                classic_ptr_button = classic_ptr_button.style(style::DefaultButton(color_palette));
-            }
-            Flavor::RetailBeta => {
-                retail_button = retail_button.style(style::DefaultButton(color_palette));
-                retail_ptr_button = retail_ptr_button.style(style::DefaultButton(color_palette));
-                retail_beta_button =
-                    retail_beta_button.style(style::SelectedDefaultButton(color_palette));
-                classic_button = classic_button.style(style::DefaultButton(color_palette));
-                classic_ptr_button = classic_ptr_button.style(style::DefaultButton(color_palette));
-            }
-            Flavor::Classic => {
-                retail_button = retail_button.style(style::DefaultButton(color_palette));
-                retail_ptr_button = retail_ptr_button.style(style::DefaultButton(color_palette));
-                retail_beta_button = retail_beta_button.style(style::DefaultButton(color_palette));
-                classic_button = classic_button.style(style::SelectedDefaultButton(color_palette));
-                classic_ptr_button = classic_ptr_button.style(style::DefaultButton(color_palette));
-            }
-            Flavor::ClassicPTR => {
-                retail_button = retail_button.style(style::DefaultButton(color_palette));
-                retail_ptr_button = retail_ptr_button.style(style::DefaultButton(color_palette));
-                retail_beta_button = retail_beta_button.style(style::DefaultButton(color_palette));
-                classic_button = classic_button.style(style::DefaultButton(color_palette));
-                classic_ptr_button =
-                    classic_ptr_button.style(style::SelectedDefaultButton(color_palette));
-            }
-        }
-    }
-
-    let retail_button: Element<Interaction> = retail_button.into();
-    let retail_ptr_button: Element<Interaction> = retail_ptr_button.into();
-    let retail_beta_button: Element<Interaction> = retail_beta_button.into();
-    let classic_button: Element<Interaction> = classic_button.into();
-    let classic_ptr_button: Element<Interaction> = classic_ptr_button.into();
-
+    // A tab for each flavor found in the World of Warcraft directory.
     let mut segmented_flavor_control_container = Row::new();
 
     if valid_flavors.len() > 1 {
-        if valid_flavors.iter().any(|f| *f == Flavor::Retail) {
-            segmented_flavor_control_container =
-                segmented_flavor_control_container.push(retail_button.map(Message::Interaction))
-        }
-
-        if valid_flavors.iter().any(|f| *f == Flavor::RetailPTR) {
-            segmented_flavor_control_container =
-                segmented_flavor_control_container.push(retail_ptr_button.map(Message::Interaction))
-        }
-
-        if valid_flavors.iter().any(|f| *f == Flavor::RetailBeta) {
-            segmented_flavor_control_container = segmented_flavor_control_container
-                .push(retail_beta_button.map(Message::Interaction))
-        }
+        for (flavor, btn_state) in flavor_btn_states
+            .iter_mut()
+            .filter(|(f, _)| valid_flavors.contains(f))
+        {
+            let mut flavor_button =
+                Button::new(btn_state, Text::new(flavor.to_string()).size(font_size()))
+                    .style(style::DisabledDefaultButton(color_palette))
+                    .on_press(Interaction::FlavorSelected(*flavor));
+
+            if !disable_flavor_buttons {
+                flavor_button = if *flavor == config.wow.flavor {
+                    flavor_button.style(style::SelectedDefaultButton(color_palette))
+                } else {
+                    flavor_button.style(style::DefaultButton(color_palette))
+                };
+            }
 
-        if valid_flavors.iter().any(|f| *f == Flavor::Classic) {
+            let flavor_button: Element<Interaction> = flavor_button.into();
             segmented_flavor_control_container =
-                segmented_flavor_control_container.push(classic_button.map(Message::Interaction))
-        }
-
-        if valid_flavors.iter().any(|f| *f == Flavor::ClassicPTR) {
-            segmented_flavor_control_container = segmented_flavor_control_container
-                .push(classic_ptr_button.map(Message::Interaction))
+                segmented_flavor_control_container.push(flavor_button.map(Message::Interaction));
         }
 
         segmented_flavor_control_container = segmented_flavor_control_container.spacing(1);
@@ -1400,7 +3087,7 @@ pub fn menu_container<'a>(
 
     // Displays an error, if any has occured.
     let error_text = if let AjourState::Error(e) = state {
-        Text::new(e.to_string()).size(DEFAULT_FONT_SIZE)
+        Text::new(e.user_message()).size(font_size())
     } else {
         // Display nothing.
         Text::new("")
@@ -1413,12 +3100,27 @@ pub fn menu_container<'a>(
         .width(Length::Fill)
         .style(style::NormalErrorForegroundContainer(color_palette));
 
-    let version_text = Text::new(if let Some(new_version) = needs_update {
-        format!("New Ajour version available {} > {}", VERSION, new_version)
+    let version_text = Text::new(if let Some(release) = needs_update {
+        // Betas and nightlies are labeled, as they are less tested.
+        if release.is_prerelease() {
+            localized_string_with(
+                "new-prerelease-available",
+                &[
+                    ("channel", &release.channel.to_string().to_lowercase()),
+                    ("current", &VERSION),
+                    ("version", &release.version),
+                ],
+            )
+        } else {
+            localized_string_with(
+                "new-release-available",
+                &[("current", &VERSION), ("version", &release.version)],
+            )
+        }
     } else {
         VERSION.to_owned()
     })
-    .size(DEFAULT_FONT_SIZE)
+    .size(font_size())
     .horizontal_alignment(HorizontalAlignment::Right);
 
     let version_container = Container::new(version_text)
@@ -1428,9 +3130,9 @@ pub fn menu_container<'a>(
 
     let settings_button: Element<Interaction> = Button::new(
         settings_button_state,
-        Text::new("Settings")
+        Text::new(localized_string("settings"))
             .horizontal_alignment(HorizontalAlignment::Center)
-            .size(DEFAULT_FONT_SIZE),
+            .size(font_size()),
     )
     .style(style::DefaultButton(color_palette))
     .on_press(Interaction::Settings)
@@ -1446,17 +3148,16 @@ pub fn menu_container<'a>(
         .push(error_container)
         .push(version_container);
 
-    // Add download button to latest github release page if Ajour update is available.
+    // Add a button showing what changed if Ajour update is available, which is
+    // downloaded from there.
     if needs_update.is_some() {
         let mut new_release_button = Button::new(
             new_release_button_state,
-            Text::new("Download").size(DEFAULT_FONT_SIZE),
+            Text::new(localized_string("release-notes")).size(font_size()),
         )
         .style(style::SecondaryButton(color_palette));
 
-        new_release_button = new_release_button.on_press(Interaction::OpenLink(
-            "https://github.com/casperstorm/ajour/releases/latest".to_owned(),
-        ));
+        new_release_button = new_release_button.on_press(Interaction::ToggleReleaseNotes);
 
         let new_release_button: Element<Interaction> = new_release_button.into();
 
@@ -1481,14 +3182,696 @@ pub fn menu_container<'a>(
     Container::new(settings_column).style(style::BrightForegroundContainer(color_palette))
 }
 
+/// Banner shown while offline, either because offline mode is on or because the
+/// connection was lost.
+pub fn offline_banner<'a>(
+    color_palette: ColorPalette,
+    is_offline_mode: bool,
+) -> Container<'a, Message> {
+    let text = if is_offline_mode {
+        localized_string("offline-mode-banner")
+    } else {
+        localized_string("connection-lost-banner")
+    };
+
+    Container::new(Text::new(text).size(font_size()))
+        .width(Length::Fill)
+        .center_x()
+        .padding(5)
+        .style(style::NormalErrorForegroundContainer(color_palette))
+}
+
+/// Banner shown while updates or deletions of `flavor` are held back because the
+/// game was running.
+pub fn game_running_banner<'a>(
+    color_palette: ColorPalette,
+    game_state: &'a mut GameState,
+    flavor: Flavor,
+    run_after_game_exits: bool,
+) -> Container<'a, Message> {
+    let deferred = game_state
+        .deferred
+        .iter()
+        .filter(|a| a.flavor == flavor)
+        .collect::<Vec<_>>();
+    let is_blocked = deferred.iter().any(|a| a.is_blocked(&game_state.running));
+
+    let key = match (is_blocked, run_after_game_exits) {
+        (true, true) => "game-running-deferred",
+        (true, false) => "game-running-held-back",
+        (false, _) => "game-exited-deferred",
+    };
+    let text = localized_string_with(key, &[("count", &deferred.len())]);
+
+    let run_button: Element<Interaction> = Button::new(
+        &mut game_state.run_btn_state,
+        Text::new(if is_blocked {
+            localized_string("run-anyway")
+        } else {
+            localized_string("run-now")
+        })
+        .size(font_size()),
+    )
+    .style(style::DefaultButton(color_palette))
+    .on_press(Interaction::RunDeferred)
+    .into();
+
+    let cancel_button: Element<Interaction> = Button::new(
+        &mut game_state.cancel_btn_state,
+        Text::new(localized_string("cancel")).size(font_size()),
+    )
+    .style(style::DefaultButton(color_palette))
+    .on_press(Interaction::CancelDeferred)
+    .into();
+
+    let row = Row::new()
+        .push(Text::new(text).size(font_size()))
+        .push(Space::new(Length::Fill, Length::Units(0)))
+        .push(run_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(cancel_button.map(Message::Interaction))
+        .align_items(Align::Center);
+
+    Container::new(row)
+        .width(Length::Fill)
+        .padding(5)
+        .style(style::NormalErrorForegroundContainer(color_palette))
+}
+
+/// Asks to confirm installing the addon of an install link opened from the browser.
+pub fn install_link_banner<'a>(
+    color_palette: ColorPalette,
+    install_link_state: &'a mut InstallLinkState,
+    link: InstallLink,
+    catalog: Option<&Catalog>,
+    flavor: Flavor,
+) -> Container<'a, Message> {
+    // The name is known once the catalog is loaded.
+    let name = catalog
+        .and_then(|c| {
+            c.addons
+                .iter()
+                .find(|a| a.source == link.source && a.id == link.id)
+        })
+        .map(|a| a.name.clone())
+        .unwrap_or_else(|| localized_string_with("addon-id", &[("id", &link.id)]));
+
+    let text = localized_string_with(
+        "install-link",
+        &[
+            ("name", &name),
+            ("source", &link.source),
+            ("flavor", &link.flavor.unwrap_or(flavor)),
+        ],
+    );
+
+    let install_button: Element<Interaction> = Button::new(
+        &mut install_link_state.install_btn_state,
+        Text::new(localized_string("install")).size(font_size()),
+    )
+    .style(style::DefaultButton(color_palette))
+    .on_press(Interaction::InstallFromLink)
+    .into();
+
+    let dismiss_button: Element<Interaction> = Button::new(
+        &mut install_link_state.dismiss_btn_state,
+        Text::new(localized_string("cancel")).size(font_size()),
+    )
+    .style(style::DefaultButton(color_palette))
+    .on_press(Interaction::DismissInstallLink)
+    .into();
+
+    let row = Row::new()
+        .push(Text::new(text).size(font_size()))
+        .push(Space::new(Length::Fill, Length::Units(0)))
+        .push(install_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(dismiss_button.map(Message::Interaction))
+        .align_items(Align::Center);
+
+    Container::new(row)
+        .width(Length::Fill)
+        .padding(5)
+        .style(style::NormalBackgroundContainer(color_palette))
+}
+
+/// Summary of the last "Update All", listing the addons which failed along with
+/// the reason, and the ones which were skipped.
+pub fn update_report_container<'a>(
+    color_palette: ColorPalette,
+    report: &UpdateReport,
+    dismiss_btn_state: &'a mut button::State,
+) -> Container<'a, Message> {
+    let dismiss_button: Element<Interaction> =
+        Button::new(dismiss_btn_state, Text::new("x").size(font_size()))
+            .style(style::DefaultButton(color_palette))
+            .on_press(Interaction::DismissUpdateReport)
+            .into();
+
+    let summary_row = Row::new()
+        .push(Text::new(report.summary()).size(font_size()))
+        .push(Space::new(Length::Fill, Length::Units(0)))
+        .push(dismiss_button.map(Message::Interaction))
+        .align_items(Align::Center);
+
+    let mut column = Column::new().push(summary_row).spacing(2);
+
+    for (title, reason) in report.failed.iter() {
+        let text = Text::new(localized_string_with(
+            "addon-failed",
+            &[("title", title), ("reason", reason)],
+        ))
+        .size(font_size());
+        let container =
+            Container::new(text).style(style::NormalErrorForegroundContainer(color_palette));
+
+        column = column.push(container);
+    }
+
+    for (title, reason) in report.skipped.iter() {
+        let text = Text::new(localized_string_with(
+            "addon-skipped",
+            &[("title", title), ("reason", reason)],
+        ))
+        .size(font_size());
+
+        column = column.push(text);
+    }
+
+    Container::new(column)
+        .width(Length::Fill)
+        .padding(5)
+        .style(style::NormalForegroundContainer(color_palette))
+}
+
+/// Toasts stacked on top of each other, newest last.
+pub fn toasts_container<'a>(
+    color_palette: ColorPalette,
+    state: &'a mut ToastState,
+) -> Container<'a, Message> {
+    let mut column = Column::new().spacing(2);
+
+    for toast in state.toasts.iter_mut() {
+        let mut row = Row::new()
+            .push(Text::new(&toast.text).size(font_size()))
+            .push(Space::new(Length::Fill, Length::Units(0)))
+            .align_items(Align::Center)
+            .spacing(DEFAULT_PADDING);
+
+        if let Some((title, _)) = &toast.action {
+            let action_button: Element<Interaction> = Button::new(
+                &mut toast.action_btn_state,
+                Text::new(title).size(font_size()),
+            )
+            .style(style::DefaultBoxedButton(color_palette))
+            .on_press(Interaction::ToastAction(toast.id))
+            .into();
+
+            row = row.push(action_button.map(Message::Interaction));
+        }
+
+        let dismiss_button: Element<Interaction> = Button::new(
+            &mut toast.dismiss_btn_state,
+            Text::new("x").size(font_size()),
+        )
+        .style(style::DefaultButton(color_palette))
+        .on_press(Interaction::DismissToast(toast.id))
+        .into();
+
+        row = row.push(dismiss_button.map(Message::Interaction));
+
+        let container = Container::new(row).width(Length::Fill).padding(5);
+        let container = match toast.kind {
+            ToastKind::Info => container.style(style::NormalForegroundContainer(color_palette)),
+            ToastKind::Success => {
+                container.style(style::NormalPrimaryForegroundContainer(color_palette))
+            }
+            ToastKind::Error => {
+                container.style(style::NormalErrorForegroundContainer(color_palette))
+            }
+        };
+
+        column = column.push(container);
+    }
+
+    let row = Row::new()
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(column)
+        .push(Space::new(
+            Length::Units(DEFAULT_PADDING + 5),
+            Length::Units(0),
+        ));
+
+    Container::new(row).width(Length::Fill)
+}
+
 pub fn status_container<'a>(
     color_palette: ColorPalette,
     title: &str,
     description: &str,
-    onboarding_directory_btn_state: Option<&'a mut button::State>,
 ) -> Container<'a, Message> {
+    let colum = status_column(color_palette, title, description);
+
+    Container::new(colum)
+        .center_y()
+        .center_x()
+        .width(Length::Fill)
+        .height(Length::Fill)
+}
+
+/// What changed in a new release of Ajour, with buttons to download or skip it.
+pub fn release_notes_container<'a>(
+    color_palette: ColorPalette,
+    release: &AppRelease,
+    package_manager: Option<PackageManager>,
+    state: &'a mut ReleaseNotesState,
+) -> Container<'a, Message> {
+    let title =
+        Text::new(format!("Ajour {} ({})", release.version, release.channel)).size(font_size());
+
+    // Installs of package managers are updated with them, as replacing the
+    // executable would break them.
+    let download_element: Element<Interaction> = match package_manager {
+        Some(package_manager) => Container::new(
+            Text::new(localized_string_with(
+                "installed-with-package-manager",
+                &[
+                    ("manager", &package_manager),
+                    ("hint", &package_manager.update_hint()),
+                ],
+            ))
+            .size(font_size()),
+        )
+        .style(style::BrightForegroundContainer(color_palette))
+        .into(),
+        None => Button::new(
+            &mut state.download_btn_state,
+            Text::new(localized_string("download")).size(font_size()),
+        )
+        .style(style::SecondaryButton(color_palette))
+        .on_press(Interaction::OpenLink(release.url.clone()))
+        .into(),
+    };
+
+    let skip_button: Element<Interaction> = Button::new(
+        &mut state.skip_btn_state,
+        Text::new(localized_string("skip-version")).size(font_size()),
+    )
+    .style(style::DefaultBoxedButton(color_palette))
+    .on_press(Interaction::SkipRelease)
+    .into();
+
+    let close_button: Element<Interaction> = Button::new(
+        &mut state.close_btn_state,
+        Text::new(localized_string("close")).size(font_size()),
+    )
+    .style(style::DefaultBoxedButton(color_palette))
+    .on_press(Interaction::ToggleReleaseNotes)
+    .into();
+
+    let title_row = Row::new()
+        .align_items(Align::Center)
+        .spacing(DEFAULT_PADDING)
+        .push(title)
+        .push(Space::new(Length::Fill, Length::Units(0)))
+        .push(download_element.map(Message::Interaction))
+        .push(skip_button.map(Message::Interaction))
+        .push(close_button.map(Message::Interaction));
+
+    let no_changelog = localized_string("no-changelog");
+    let notes = if release.notes.is_empty() {
+        &no_changelog
+    } else {
+        &release.notes
+    };
+
+    let notes_scrollable = Scrollable::new(&mut state.scrollable_state)
+        .height(Length::Units(250))
+        .style(style::Scrollable(color_palette))
+        .push(Text::new(notes).size(font_size()));
+
+    let column = Column::new()
+        .push(title_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(notes_scrollable);
+
+    let container = Container::new(column)
+        .width(Length::Fill)
+        .padding(10)
+        .style(style::NormalForegroundContainer(color_palette));
+
+    let row = Row::new()
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(container)
+        .push(Space::new(
+            Length::Units(DEFAULT_PADDING + 5),
+            Length::Units(0),
+        ));
+
+    Container::new(row).width(Length::Fill)
+}
+
+/// The records of the log, newest first, filtered by level and module.
+pub fn logs_container<'a>(
+    color_palette: ColorPalette,
+    state: &'a mut LogsState,
+) -> Container<'a, Message> {
+    let title = Text::new(localized_string("logs")).size(font_size());
+
+    let level_pick_list: Element<Interaction> = PickList::new(
+        &mut state.level_picklist_state,
+        &LogsState::LEVELS[..],
+        Some(state.level),
+        Interaction::LogLevelSelected,
+    )
+    .text_size(font_size())
+    .width(Length::Units(100))
+    .style(style::PickList(color_palette))
+    .into();
+
+    let module_input: Element<Interaction> = TextInput::new(
+        &mut state.module_input_state,
+        &localized_string("module"),
+        &state.module,
+        Interaction::LogModuleInput,
+    )
+    .size(font_size())
+    .padding(5)
+    .width(Length::Units(200))
+    .style(style::CatalogQueryInput(color_palette))
+    .into();
+
+    // Copies the selected lines, or all shown lines if none is selected.
+    let copy_title = if state.selection.is_some() {
+        localized_string("copy-selection")
+    } else {
+        localized_string("copy-all")
+    };
+    let copy_button: Element<Interaction> = Button::new(
+        &mut state.copy_btn_state,
+        Text::new(copy_title).size(font_size()),
+    )
+    .style(style::DefaultBoxedButton(color_palette))
+    .on_press(Interaction::CopyLogLines)
+    .into();
+
+    let open_folder_button: Element<Interaction> = Button::new(
+        &mut state.open_folder_btn_state,
+        Text::new(localized_string("open-log-folder")).size(font_size()),
+    )
+    .style(style::DefaultBoxedButton(color_palette))
+    .on_press(Interaction::OpenConfigDirectory)
+    .into();
+
+    let close_button: Element<Interaction> = Button::new(
+        &mut state.close_btn_state,
+        Text::new(localized_string("close")).size(font_size()),
+    )
+    .style(style::DefaultBoxedButton(color_palette))
+    .on_press(Interaction::ToggleLogs)
+    .into();
+
+    let title_row = Row::new()
+        .align_items(Align::Center)
+        .spacing(DEFAULT_PADDING)
+        .push(title)
+        .push(Space::new(Length::Fill, Length::Units(0)))
+        .push(level_pick_list.map(Message::Interaction))
+        .push(module_input.map(Message::Interaction))
+        .push(copy_button.map(Message::Interaction))
+        .push(open_folder_button.map(Message::Interaction))
+        .push(close_button.map(Message::Interaction));
+
+    // Filters are applied up front, as the button states are borrowed below.
+    let flags = state
+        .lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| (state.is_shown(line), state.is_selected(idx)))
+        .collect::<Vec<_>>();
+
+    let mut lines_scrollable = Scrollable::new(&mut state.scrollable_state)
+        .height(Length::Units(300))
+        .style(style::Scrollable(color_palette));
+
+    for ((idx, line), btn_state) in state
+        .lines
+        .iter()
+        .enumerate()
+        .zip(state.line_btn_states.iter_mut())
+        .rev()
+    {
+        let (is_shown, is_selected) = flags[idx];
+        if !is_shown {
+            continue;
+        }
+
+        let mut text = Text::new(line.text()).size(font_size());
+        if line.level <= Level::Warn {
+            text = text.color(color_palette.normal.error);
+        }
+
+        let mut line_button = Button::new(btn_state, text)
+            .width(Length::Fill)
+            .on_press(Interaction::SelectLogLine(idx));
+        line_button = if is_selected {
+            line_button.style(style::SelectedDefaultButton(color_palette))
+        } else {
+            line_button.style(style::DefaultButton(color_palette))
+        };
+
+        let line_button: Element<Interaction> = line_button.into();
+
+        lines_scrollable = lines_scrollable.push(line_button.map(Message::Interaction));
+    }
+
+    let column = Column::new()
+        .push(title_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+        .push(lines_scrollable);
+
+    let container = Container::new(column)
+        .width(Length::Fill)
+        .padding(10)
+        .style(style::NormalForegroundContainer(color_palette));
+
+    let row = Row::new()
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(container)
+        .push(Space::new(
+            Length::Units(DEFAULT_PADDING + 5),
+            Length::Units(0),
+        ));
+
+    Container::new(row).width(Length::Fill)
+}
+
+/// Container shown on first run, which walks through picking the World of
+/// Warcraft directory, the flavors to manage and the look of Ajour.
+pub fn welcome_container<'a>(
+    color_palette: ColorPalette,
+    state: &'a mut OnboardingState,
+    theme_state: &'a mut ThemeState,
+    scale: f64,
+) -> Container<'a, Message> {
+    let directory = state
+        .directory
+        .as_ref()
+        .map(|d| d.display().to_string())
+        .unwrap_or_default();
+
+    let mut colum = match state.step {
+        OnboardingStep::Directory => {
+            let description = if state.detected_directories.is_empty() {
+                localized_string("onboarding-select-directory")
+            } else {
+                localized_string("onboarding-detected-directories")
+            };
+            let mut colum =
+                status_column(color_palette, &localized_string("welcome"), &description)
+                    .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+                    .align_items(Align::Center);
+
+            for (directory, btn_state) in state.detected_directories.iter_mut() {
+                let detected_button: Element<Interaction> = Button::new(
+                    btn_state,
+                    Text::new(directory.display().to_string()).size(font_size()),
+                )
+                .style(style::DefaultBoxedButton(color_palette))
+                .on_press(Interaction::DetectedDirectorySelected(directory.clone()))
+                .into();
+
+                colum = colum
+                    .push(detected_button.map(Message::Interaction))
+                    .push(Space::new(Length::Units(0), Length::Units(5)));
+            }
+
+            let onboarding_button_title_container =
+                Container::new(Text::new(localized_string("select-directory")).size(font_size()))
+                    .width(Length::Units(100))
+                    .center_x()
+                    .align_x(Align::Center);
+            let onboarding_button: Element<Interaction> = Button::new(
+                &mut state.directory_btn_state,
+                onboarding_button_title_container,
+            )
+            .width(Length::Units(100))
+            .style(style::DefaultButton(color_palette))
+            .on_press(Interaction::OpenDirectory(DirectoryType::Wow))
+            .into();
+
+            colum
+                .push(Space::new(Length::Units(0), Length::Units(5)))
+                .push(onboarding_button.map(Message::Interaction))
+        }
+        OnboardingStep::Flavors => {
+            let description = if state.flavors.is_empty() {
+                localized_string_with("onboarding-no-flavors", &[("directory", &directory)])
+            } else {
+                localized_string_with("onboarding-flavors", &[("directory", &directory)])
+            };
+            let mut flavors_column = Column::new().spacing(5);
+            for (flavor, is_managed) in state.flavors.iter().copied() {
+                let checkbox = Checkbox::new(is_managed, flavor.to_string(), move |is_checked| {
+                    Message::Interaction(Interaction::OnboardingFlavorToggled(flavor, is_checked))
+                })
+                .text_size(font_size())
+                .spacing(5)
+                .style(style::DefaultCheckbox(color_palette));
+
+                flavors_column = flavors_column.push(checkbox);
+            }
+
+            status_column(color_palette, &localized_string("welcome"), &description)
+                .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+                .align_items(Align::Center)
+                .push(flavors_column)
+        }
+        OnboardingStep::Appearance => {
+            let theme_names = theme_state
+                .themes
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<_>>();
+            let theme_pick_list = PickList::new(
+                &mut state.theme_picklist_state,
+                theme_names,
+                Some(theme_state.current_theme_name.clone()),
+                Message::ThemeSelected,
+            )
+            .text_size(font_size())
+            .width(Length::Units(100))
+            .style(style::PickList(color_palette));
+
+            let scale_down_button: Element<Interaction> = Button::new(
+                &mut state.scale_down_btn_state,
+                Text::new("  -  ").size(font_size()),
+            )
+            .style(style::DefaultBoxedButton(color_palette))
+            .on_press(Interaction::ScaleDown)
+            .into();
+            let scale_up_button: Element<Interaction> = Button::new(
+                &mut state.scale_up_btn_state,
+                Text::new("  +  ").size(font_size()),
+            )
+            .style(style::DefaultBoxedButton(color_palette))
+            .on_press(Interaction::ScaleUp)
+            .into();
+            let current_scale_container = Container::new(
+                Text::new(format!("  {:.2}  ", scale))
+                    .size(font_size())
+                    .vertical_alignment(VerticalAlignment::Center),
+            )
+            .height(Length::Units(25))
+            .center_y()
+            .style(style::BrightBackgroundContainer(color_palette));
+
+            let appearance_row = Row::new()
+                .align_items(Align::Center)
+                .push(Text::new(localized_string("theme")).size(font_size()))
+                .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+                .push(theme_pick_list)
+                .push(Space::new(
+                    Length::Units(DEFAULT_PADDING + DEFAULT_PADDING),
+                    Length::Units(0),
+                ))
+                .push(Text::new(localized_string("ui-scale")).size(font_size()))
+                .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+                .push(scale_down_button.map(Message::Interaction))
+                .push(current_scale_container)
+                .push(scale_up_button.map(Message::Interaction));
+
+            status_column(
+                color_palette,
+                &localized_string("welcome"),
+                &localized_string("onboarding-appearance"),
+            )
+            .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+            .align_items(Align::Center)
+            .push(appearance_row)
+        }
+    };
+
+    // Buttons to move between the steps, once a directory is picked.
+    if state.directory.is_some() {
+        let has_managed_flavors = state.flavors.iter().any(|(_, managed)| *managed);
+        let next_title = if state.step == OnboardingStep::Appearance {
+            localized_string("finish")
+        } else {
+            localized_string("next")
+        };
+
+        let mut back_button = Button::new(
+            &mut state.back_btn_state,
+            Container::new(Text::new(localized_string("back")).size(font_size()))
+                .width(Length::Units(100))
+                .center_x(),
+        )
+        .width(Length::Units(100))
+        .style(style::DefaultButton(color_palette));
+        if state.step != OnboardingStep::Directory {
+            back_button = back_button.on_press(Interaction::OnboardingBack);
+        }
+
+        let mut next_button = Button::new(
+            &mut state.next_btn_state,
+            Container::new(Text::new(next_title).size(font_size()))
+                .width(Length::Units(100))
+                .center_x(),
+        )
+        .width(Length::Units(100))
+        .style(style::DefaultBoxedButton(color_palette));
+        if has_managed_flavors {
+            next_button = next_button.on_press(Interaction::OnboardingNext);
+        }
+
+        let back_button: Element<Interaction> = back_button.into();
+        let next_button: Element<Interaction> = next_button.into();
+
+        let buttons_row = Row::new()
+            .push(back_button.map(Message::Interaction))
+            .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+            .push(next_button.map(Message::Interaction));
+
+        colum = colum
+            .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
+            .push(buttons_row);
+    }
+
+    Container::new(colum)
+        .center_y()
+        .center_x()
+        .width(Length::Fill)
+        .height(Length::Fill)
+}
+
+fn status_column<'a>(
+    color_palette: ColorPalette,
+    title: &str,
+    description: &str,
+) -> Column<'a, Message> {
     let title = Text::new(title)
-        .size(DEFAULT_FONT_SIZE)
+        .size(font_size())
         .width(Length::Fill)
         .horizontal_alignment(HorizontalAlignment::Center);
     let title_container = Container::new(title)
@@ -1496,42 +3879,149 @@ pub fn status_container<'a>(
         .style(style::BrightBackgroundContainer(color_palette));
 
     let description = Text::new(description)
-        .size(DEFAULT_FONT_SIZE)
+        .size(font_size())
         .width(Length::Fill)
         .horizontal_alignment(HorizontalAlignment::Center);
     let description_container = Container::new(description)
         .width(Length::Fill)
         .style(style::NormalBackgroundContainer(color_palette));
 
-    let mut colum = Column::new()
-        .push(title_container)
-        .push(Space::new(Length::Units(0), Length::Units(2)))
-        .push(description_container);
+    Column::new()
+        .push(title_container)
+        .push(Space::new(Length::Units(0), Length::Units(2)))
+        .push(description_container)
+}
+
+/// Container with buttons for moving between pages of catalog results.
+pub fn catalog_paging_container<'a>(
+    color_palette: ColorPalette,
+    previous_page_btn_state: &'a mut button::State,
+    next_page_btn_state: &'a mut button::State,
+    page: usize,
+    total_pages: usize,
+) -> Container<'a, Message> {
+    let mut previous_page_button = Button::new(
+        previous_page_btn_state,
+        Text::new(localized_string("previous")).size(font_size()),
+    )
+    .style(style::DefaultButton(color_palette));
+
+    let mut next_page_button = Button::new(
+        next_page_btn_state,
+        Text::new(localized_string("next")).size(font_size()),
+    )
+    .style(style::DefaultButton(color_palette));
+
+    // Only enable the buttons if there is a page to move to.
+    if page > 0 {
+        previous_page_button =
+            previous_page_button.on_press(Interaction::CatalogPageSelected(page - 1));
+    }
+
+    if page + 1 < total_pages {
+        next_page_button = next_page_button.on_press(Interaction::CatalogPageSelected(page + 1));
+    }
+
+    let previous_page_button: Element<Interaction> = previous_page_button.into();
+    let next_page_button: Element<Interaction> = next_page_button.into();
+
+    let page_text = Text::new(localized_string_with(
+        "page-of",
+        &[("page", &(page + 1)), ("total", &total_pages)],
+    ))
+    .size(font_size());
+    let page_text_container = Container::new(page_text)
+        .center_y()
+        .padding(5)
+        .style(style::NormalBackgroundContainer(color_palette));
+
+    let paging_row = Row::new()
+        .height(Length::Units(35))
+        .align_items(Align::Center)
+        .push(previous_page_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(page_text_container)
+        .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(next_page_button.map(Message::Interaction));
+
+    Container::new(paging_row)
+        .width(Length::Fill)
+        .center_x()
+        .padding(5)
+}
+
+/// Row to install an addon from a zip on the web or on disk.
+pub fn install_from_url_row<'a>(
+    color_palette: ColorPalette,
+    state: &'a mut InstallFromUrlState,
+    flavor: Flavor,
+) -> Container<'a, Message> {
+    let mut url_input = TextInput::new(
+        &mut state.url_input_state,
+        &localized_string("zip-url"),
+        &state.url,
+        Interaction::InstallFromUrlInput,
+    )
+    .size(font_size())
+    .padding(10)
+    .width(Length::FillPortion(3))
+    .style(style::CatalogQueryInput(color_palette));
+
+    let mut browse_button = Button::new(
+        &mut state.browse_btn_state,
+        Text::new(localized_string("browse")).size(font_size()),
+    )
+    .style(style::DefaultBoxedButton(color_palette));
 
-    if let (_, Some(btn_state)) = (AjourState::Welcome, onboarding_directory_btn_state) {
-        let onboarding_button_title_container =
-            Container::new(Text::new("Select Directory").size(DEFAULT_FONT_SIZE))
-                .width(Length::Units(100))
-                .center_x()
-                .align_x(Align::Center);
-        let onboarding_button: Element<Interaction> =
-            Button::new(btn_state, onboarding_button_title_container)
-                .width(Length::Units(100))
-                .style(style::DefaultButton(color_palette))
-                .on_press(Interaction::OpenDirectory(DirectoryType::Wow))
-                .into();
+    let mut install_button = Button::new(
+        &mut state.install_btn_state,
+        Text::new(localized_string_with("install-for", &[("flavor", &flavor)])).size(font_size()),
+    )
+    .style(style::DefaultBoxedButton(color_palette));
 
-        colum = colum
-            .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)))
-            .push(onboarding_button.map(Message::Interaction))
-            .align_items(Align::Center);
+    // Only allow one install at a time.
+    if !state.installing {
+        browse_button = browse_button.on_press(Interaction::BrowseArchive);
+
+        if !state.url.trim().is_empty() {
+            url_input = url_input.on_submit(Interaction::InstallFromUrl);
+            install_button = install_button.on_press(Interaction::InstallFromUrl);
+        }
     }
 
-    Container::new(colum)
+    let url_input: Element<Interaction> = url_input.into();
+    let browse_button: Element<Interaction> = browse_button.into();
+    let install_button: Element<Interaction> = install_button.into();
+
+    let status = if state.installing {
+        localized_string("installing")
+    } else {
+        state.status.clone().unwrap_or_default()
+    };
+    let status_text_container = Container::new(Text::new(status).size(font_size()))
         .center_y()
-        .center_x()
+        .width(Length::FillPortion(1))
+        .style(style::NormalForegroundContainer(color_palette));
+
+    let row = Row::new()
+        .align_items(Align::Center)
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(url_input.map(Message::Interaction))
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(browse_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(install_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(status_text_container)
+        .push(Space::new(
+            Length::Units(DEFAULT_PADDING + 5),
+            Length::Units(0),
+        ));
+
+    Container::new(row)
         .width(Length::Fill)
-        .height(Length::Fill)
+        .height(Length::Units(35))
+        .center_y()
 }
 
 pub fn catalog_row_titles<'a>(
@@ -1557,9 +4047,7 @@ pub fn catalog_row_titles<'a>(
 
         let mut row_header = Button::new(
             &mut column.btn_state,
-            Text::new(row_title)
-                .size(DEFAULT_FONT_SIZE)
-                .width(Length::Fill),
+            Text::new(row_title).size(font_size()).width(Length::Fill),
         )
         .width(Length::Fill);
 
@@ -1615,10 +4103,12 @@ pub fn catalog_data_cell<'a, 'b>(
     let addon_data = &addon.addon;
     let website_state = &mut addon.website_state;
     let install_button_state = &mut addon.install_button_state;
+    let source_picklist_state = &mut addon.source_picklist_state;
+    let sources = &addon.sources;
 
     let flavor_exists_for_addon = addon_data
         .flavors
-        .contains(&config.wow.flavor.base_flavor());
+        .contains(&config.wow.flavor.catalog_flavor());
 
     if let Some((idx, width)) = column_config
         .iter()
@@ -1638,25 +4128,25 @@ pub fn catalog_data_cell<'a, 'b>(
             .map(|(_, status)| *status);
 
         let install_text = Text::new(if !flavor_exists_for_addon {
-            "N/A"
+            localized_string("not-available")
         } else {
             match status {
-                Some(CatalogInstallStatus::Downloading) => "Downloading",
-                Some(CatalogInstallStatus::Unpacking) => "Unpacking",
-                Some(CatalogInstallStatus::Fingerprint) => "Hashing",
-                Some(CatalogInstallStatus::Completed) => "Installed",
-                Some(CatalogInstallStatus::Retry) => "Retry",
-                Some(CatalogInstallStatus::Unavilable) => "Unavailable",
+                Some(CatalogInstallStatus::Downloading) => localized_string("downloading"),
+                Some(CatalogInstallStatus::Unpacking) => localized_string("unpacking"),
+                Some(CatalogInstallStatus::Fingerprint) => localized_string("hashing"),
+                Some(CatalogInstallStatus::Completed) => localized_string("installed"),
+                Some(CatalogInstallStatus::Retry) => localized_string("retry"),
+                Some(CatalogInstallStatus::Unavilable) => localized_string("unavailable"),
                 None => {
                     if installed_for_flavor {
-                        "Installed"
+                        localized_string("installed")
                     } else {
-                        "Install"
+                        localized_string("install")
                     }
                 }
             }
         })
-        .size(DEFAULT_FONT_SIZE);
+        .size(font_size());
 
         let install_wrapper = Container::new(install_text)
             .width(*width)
@@ -1668,6 +4158,7 @@ pub fn catalog_data_cell<'a, 'b>(
             .width(*width);
 
         if flavor_exists_for_addon
+            && !network::is_offline()
             && (status == Some(CatalogInstallStatus::Retry)
                 || (status == None && !installed_for_flavor))
         {
@@ -1701,7 +4192,7 @@ pub fn catalog_data_cell<'a, 'b>(
         })
         .next()
     {
-        let title = Text::new(&addon_data.name).size(DEFAULT_FONT_SIZE);
+        let title = Text::new(&addon_data.name).size(font_size());
         let title_button: Element<Interaction> = Button::new(website_state, title)
             .style(style::BrightTextButton(color_palette))
             .on_press(Interaction::OpenLink(addon_data.website_url.clone()))
@@ -1728,7 +4219,7 @@ pub fn catalog_data_cell<'a, 'b>(
         })
         .next()
     {
-        let description = Text::new(&addon_data.summary).size(DEFAULT_FONT_SIZE);
+        let description = Text::new(&addon_data.summary).size(font_size());
         let description_container = Container::new(description)
             .height(default_height)
             .width(*width)
@@ -1751,7 +4242,27 @@ pub fn catalog_data_cell<'a, 'b>(
         })
         .next()
     {
-        let source = Text::new(&format!("{}", addon_data.source)).size(DEFAULT_FONT_SIZE);
+        // Addons on several sources can be installed from either of them.
+        let source: Element<Message> = if sources.len() > 1 {
+            let (source, id) = (addon_data.source, addon_data.id);
+
+            PickList::new(
+                source_picklist_state,
+                &sources[..],
+                Some(source),
+                move |selected_source| {
+                    Message::CatalogAddonSourceSelected((source, id, selected_source))
+                },
+            )
+            .text_size(font_size())
+            .width(Length::Fill)
+            .style(style::PickList(color_palette))
+            .into()
+        } else {
+            Text::new(&format!("{}", addon_data.source))
+                .size(font_size())
+                .into()
+        };
         let source_container = Container::new(source)
             .height(default_height)
             .width(*width)
@@ -1776,13 +4287,13 @@ pub fn catalog_data_cell<'a, 'b>(
         .next()
     {
         let release_date_text: String = if let Some(date_released) = addon_data.date_released {
-            let f = timeago::Formatter::new();
+            let f = time_formatter();
             let now = Local::now();
             f.convert_chrono(date_released, now)
         } else {
             "-".to_string()
         };
-        let release_date_text = Text::new(release_date_text).size(DEFAULT_FONT_SIZE);
+        let release_date_text = Text::new(release_date_text).size(font_size());
         let game_version_container = Container::new(release_date_text)
             .height(default_height)
             .width(*width)
@@ -1810,7 +4321,7 @@ pub fn catalog_data_cell<'a, 'b>(
                 .number_of_downloads
                 .to_formatted_string(&Locale::en),
         )
-        .size(DEFAULT_FONT_SIZE);
+        .size(font_size());
         let num_downloads_container = Container::new(num_downloads)
             .height(default_height)
             .width(*width)
@@ -1848,3 +4359,556 @@ pub fn addon_scrollable(
         .height(Length::FillPortion(1))
         .style(style::Scrollable(color_palette))
 }
+
+/// Container listing the addons updated since "What's New" was last viewed,
+/// along with their changelogs.
+pub fn whats_new_container<'a>(
+    color_palette: ColorPalette,
+    state: &'a mut WhatsNewState,
+) -> Container<'a, Message> {
+    let status = Text::new(localized_string_with(
+        "whats-new-status",
+        &[("count", &state.history.updates.len())],
+    ))
+    .size(font_size());
+    let status_container = Container::new(status)
+        .center_y()
+        .padding(5)
+        .style(style::NormalBackgroundContainer(color_palette));
+
+    let title_row = Row::new()
+        .height(Length::Units(35))
+        .align_items(Align::Center)
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(status_container);
+
+    let mut scrollable = addon_scrollable(color_palette, &mut state.scrollable_state);
+
+    let formatter = time_formatter();
+    let no_changelog = localized_string("no-changelog");
+    for record in state.history.updates.iter() {
+        let versions = localized_string_with(
+            "version-change",
+            &[
+                ("old", &record.old_version.as_deref().unwrap_or("-")),
+                ("new", &record.new_version.as_deref().unwrap_or("-")),
+            ],
+        );
+        let updated = record
+            .updated_at
+            .elapsed()
+            .map(|elapsed| formatter.convert(elapsed))
+            .unwrap_or_default();
+
+        let title_row = Row::new()
+            .push(
+                Container::new(Text::new(&record.title).size(font_size()))
+                    .style(style::BrightForegroundContainer(color_palette)),
+            )
+            .push(Space::new(Length::Units(10), Length::Units(0)))
+            .push(Text::new(versions).size(font_size()))
+            .push(Space::new(Length::Fill, Length::Units(0)))
+            .push(Text::new(updated).size(font_size()));
+
+        let changelog = record.changelog.as_deref().unwrap_or(&no_changelog);
+
+        let column = Column::new()
+            .push(title_row)
+            .push(Space::new(Length::Units(0), Length::Units(8)))
+            .push(Text::new(changelog).size(font_size()));
+        let record_container = Container::new(column)
+            .width(Length::Fill)
+            .padding(10)
+            .style(style::NormalForegroundContainer(color_palette));
+
+        let row = Row::new()
+            .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+            .push(record_container)
+            .push(Space::new(
+                Length::Units(DEFAULT_PADDING + 5),
+                Length::Units(0),
+            ));
+
+        scrollable = scrollable
+            .push(row)
+            .push(Space::new(Length::Units(0), Length::Units(1)));
+    }
+
+    let column = Column::new()
+        .push(title_row)
+        .push(Space::new(Length::Units(0), Length::Units(5)))
+        .push(scrollable);
+
+    Container::new(column).height(Length::FillPortion(1))
+}
+
+/// What "Update All" would do, listing the addons it would update and skip.
+pub fn update_preview_container<'a>(
+    color_palette: ColorPalette,
+    state: &'a mut UpdatePreviewState,
+    previewed: &[PreviewedUpdate],
+    backup_first: bool,
+) -> Container<'a, Message> {
+    let num_updates = previewed.iter().filter(|p| p.skipped.is_none()).count();
+    let mut status = localized_string_with("update-preview-status", &[("count", &num_updates)]);
+    if backup_first && num_updates > 0 {
+        status.push(' ');
+        status.push_str(&localized_string("update-preview-backup"));
+    }
+
+    let status_container = Container::new(Text::new(status).size(font_size()))
+        .center_y()
+        .padding(5)
+        .style(style::NormalBackgroundContainer(color_palette));
+
+    let title_row = Row::new()
+        .height(Length::Units(35))
+        .align_items(Align::Center)
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(status_container);
+
+    let mut scrollable = addon_scrollable(color_palette, &mut state.scrollable_state);
+
+    for update in previewed.iter() {
+        let versions = localized_string_with(
+            "version-change",
+            &[
+                ("old", &update.old_version.as_deref().unwrap_or("-")),
+                ("new", &update.new_version.as_deref().unwrap_or("-")),
+            ],
+        );
+        let outcome = match update.skipped {
+            Some(reason) => localized_string_with("skipped-reason", &[("reason", &reason)]),
+            None => localized_string("to-be-updated"),
+        };
+
+        let title_container = Container::new(Text::new(&update.title).size(font_size()))
+            .style(style::BrightForegroundContainer(color_palette));
+
+        let row = Row::new()
+            .push(title_container)
+            .push(Space::new(Length::Units(10), Length::Units(0)))
+            .push(Text::new(versions).size(font_size()))
+            .push(Space::new(Length::Units(10), Length::Units(0)))
+            .push(Text::new(update.flavor.to_string()).size(font_size()))
+            .push(Space::new(Length::Fill, Length::Units(0)))
+            .push(Text::new(outcome).size(font_size()));
+        let update_container = Container::new(row)
+            .width(Length::Fill)
+            .padding(10)
+            .style(style::NormalForegroundContainer(color_palette));
+
+        let row = Row::new()
+            .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+            .push(update_container)
+            .push(Space::new(
+                Length::Units(DEFAULT_PADDING + 5),
+                Length::Units(0),
+            ));
+
+        scrollable = scrollable
+            .push(row)
+            .push(Space::new(Length::Units(0), Length::Units(1)));
+    }
+
+    let column = Column::new()
+        .push(title_row)
+        .push(Space::new(Length::Units(0), Length::Units(5)))
+        .push(scrollable);
+
+    Container::new(column).height(Length::FillPortion(1))
+}
+
+/// Key held for shortcuts, as it's called on the platform.
+#[cfg(target_os = "macos")]
+const COMMAND_KEY: &str = "Cmd";
+#[cfg(not(target_os = "macos"))]
+const COMMAND_KEY: &str = "Ctrl";
+
+/// Cheat sheet of the keyboard shortcuts, opened with F1.
+pub fn shortcuts_container<'a>(
+    color_palette: ColorPalette,
+    state: &'a mut KeyboardState,
+) -> Container<'a, Message> {
+    let shortcuts = [
+        ("▲ / ▼".to_string(), "shortcut-navigate"),
+        ("Enter".to_string(), "shortcut-update"),
+        (format!("{}+U", COMMAND_KEY), "shortcut-update-all"),
+        (format!("{}+F", COMMAND_KEY), "shortcut-search"),
+        ("Esc".to_string(), "shortcut-close"),
+        ("F1".to_string(), "shortcut-help"),
+    ];
+
+    let title = Text::new(localized_string("keyboard-shortcuts")).size(font_size());
+    let close_button: Element<Interaction> = Button::new(
+        &mut state.close_btn_state,
+        Text::new(localized_string("close")).size(font_size()),
+    )
+    .style(style::DefaultBoxedButton(color_palette))
+    .on_press(Interaction::ToggleShortcuts)
+    .into();
+
+    let title_row = Row::new()
+        .align_items(Align::Center)
+        .push(title)
+        .push(Space::new(Length::Fill, Length::Units(0)))
+        .push(close_button.map(Message::Interaction));
+
+    let mut column = Column::new()
+        .push(title_row)
+        .push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)));
+
+    for (keys, description) in shortcuts.iter() {
+        let keys_container = Container::new(Text::new(keys).size(font_size()))
+            .width(Length::Units(100))
+            .style(style::BrightForegroundContainer(color_palette));
+
+        let row = Row::new()
+            .push(keys_container)
+            .push(Text::new(localized_string(description)).size(font_size()));
+
+        column = column
+            .push(row)
+            .push(Space::new(Length::Units(0), Length::Units(4)));
+    }
+
+    let container = Container::new(column)
+        .width(Length::Fill)
+        .padding(10)
+        .style(style::NormalForegroundContainer(color_palette));
+
+    let row = Row::new()
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(container)
+        .push(Space::new(
+            Length::Units(DEFAULT_PADDING + 5),
+            Length::Units(0),
+        ));
+
+    Container::new(row).width(Length::Fill)
+}
+
+/// Lists the leftovers found by a clean up, so they can be deleted selectively.
+pub fn cleanup_container<'a>(
+    color_palette: ColorPalette,
+    state: &'a mut CleanupState,
+) -> Container<'a, Message> {
+    let selected = state
+        .leftovers
+        .iter()
+        .filter(|(_, s)| *s)
+        .collect::<Vec<_>>();
+    let selected_size = selected.iter().map(|(l, _)| l.size).sum::<u64>();
+
+    let status = if state.scanning {
+        localized_string("looking-for-leftovers")
+    } else if let Some(status) = &state.status {
+        status.clone()
+    } else if state.leftovers.is_empty() {
+        localized_string("no-leftovers")
+    } else {
+        localized_string_with(
+            "leftovers-selected",
+            &[
+                ("selected", &selected.len()),
+                ("count", &state.leftovers.len()),
+                ("size", &format_size(selected_size)),
+            ],
+        )
+    };
+    let status_container = Container::new(Text::new(status).size(font_size()))
+        .center_y()
+        .padding(5)
+        .style(style::NormalBackgroundContainer(color_palette));
+
+    let mut delete_button = Button::new(
+        &mut state.delete_btn_state,
+        Text::new(localized_string("delete-selected")).size(font_size()),
+    )
+    .style(style::DefaultDeleteButton(color_palette));
+
+    if !state.scanning && !selected.is_empty() {
+        delete_button = delete_button.on_press(Interaction::DeleteLeftovers);
+    }
+
+    let delete_button: Element<Interaction> = delete_button.into();
+
+    let title_row = Row::new()
+        .height(Length::Units(35))
+        .align_items(Align::Center)
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(delete_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(status_container);
+
+    let mut scrollable = addon_scrollable(color_palette, &mut state.scrollable_state);
+
+    for (idx, (leftover, is_selected)) in state.leftovers.iter().enumerate() {
+        let checkbox = Checkbox::new(
+            *is_selected,
+            leftover.path.display().to_string(),
+            move |is_checked| Message::Interaction(Interaction::ToggleLeftover(idx, is_checked)),
+        )
+        .text_size(font_size())
+        .spacing(5)
+        .style(style::DefaultCheckbox(color_palette));
+
+        let checkbox_container = Container::new(checkbox)
+            .width(Length::Fill)
+            .center_y()
+            .padding(5)
+            .style(style::NormalForegroundContainer(color_palette));
+
+        let kind_container = Container::new(Text::new(leftover.kind.to_string()).size(font_size()))
+            .width(Length::Units(110))
+            .center_y()
+            .padding(5)
+            .style(style::NormalForegroundContainer(color_palette));
+
+        let size_container =
+            Container::new(Text::new(format_size(leftover.size)).size(font_size()))
+                .width(Length::Units(85))
+                .center_y()
+                .padding(5)
+                .style(style::NormalForegroundContainer(color_palette));
+
+        let row = Row::new()
+            .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+            .push(checkbox_container)
+            .push(kind_container)
+            .push(size_container)
+            .push(Space::new(
+                Length::Units(DEFAULT_PADDING + 5),
+                Length::Units(0),
+            ))
+            .spacing(1);
+
+        scrollable = scrollable.push(
+            Container::new(row)
+                .width(Length::Fill)
+                .style(style::Row(color_palette)),
+        );
+    }
+
+    let column = Column::new()
+        .push(title_row)
+        .push(Space::new(Length::Units(0), Length::Units(5)))
+        .push(scrollable);
+
+    Container::new(column).height(Length::FillPortion(1))
+}
+
+pub fn wago_container<'a>(
+    color_palette: ColorPalette,
+    state: &'a mut WagoState,
+    chosen_account: Option<&String>,
+) -> Container<'a, Message> {
+    let updates = state
+        .auras
+        .iter()
+        .filter(|a| a.status() == AuraStatus::UpdateAvailable)
+        .count();
+
+    let status = if state.checking {
+        localized_string("checking-wago")
+    } else if state.updating {
+        localized_string("downloading-updates")
+    } else if let Some(status) = &state.status {
+        status.clone()
+    } else if state.accounts.is_empty() {
+        localized_string("no-wago-accounts")
+    } else if state.auras.is_empty() {
+        localized_string("no-wago-auras")
+    } else {
+        localized_string_with(
+            "auras-updatable",
+            &[("count", &updates), ("total", &state.auras.len())],
+        )
+    };
+    let status_container = Container::new(Text::new(status).size(font_size()))
+        .center_y()
+        .padding(5)
+        .style(style::NormalBackgroundContainer(color_palette));
+
+    let account_picklist: Element<Interaction> = PickList::new(
+        &mut state.account_picklist_state,
+        &state.accounts,
+        chosen_account.cloned(),
+        Interaction::WagoAccountSelected,
+    )
+    .text_size(font_size())
+    .width(Length::Units(150))
+    .style(style::PickList(color_palette))
+    .into();
+
+    let is_busy = state.checking || state.updating;
+
+    let mut refresh_button = Button::new(
+        &mut state.refresh_btn_state,
+        Text::new(localized_string("check-for-updates")).size(font_size()),
+    )
+    .style(style::DefaultButton(color_palette));
+
+    if !is_busy && chosen_account.is_some() {
+        refresh_button = refresh_button.on_press(Interaction::CheckWago);
+    }
+
+    let refresh_button: Element<Interaction> = refresh_button.into();
+
+    let mut update_button = Button::new(
+        &mut state.update_btn_state,
+        Text::new(localized_string("update")).size(font_size()),
+    )
+    .style(style::DefaultButton(color_palette));
+
+    if !is_busy && updates > 0 && !network::is_offline() {
+        update_button = update_button.on_press(Interaction::UpdateWago);
+    }
+
+    let update_button: Element<Interaction> = update_button.into();
+
+    let title_row = Row::new()
+        .height(Length::Units(35))
+        .align_items(Align::Center)
+        .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+        .push(account_picklist.map(Message::Interaction))
+        .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(refresh_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(update_button.map(Message::Interaction))
+        .push(Space::new(Length::Units(7), Length::Units(0)))
+        .push(status_container);
+
+    let mut scrollable = addon_scrollable(color_palette, &mut state.scrollable_state);
+
+    for aura in state.auras.iter() {
+        let installed_version = aura
+            .semver
+            .clone()
+            .unwrap_or_else(|| aura.version.to_string());
+        let remote_version = aura
+            .release
+            .as_ref()
+            .map(|r| {
+                r.version_string
+                    .clone()
+                    .unwrap_or_else(|| r.version.to_string())
+            })
+            .unwrap_or_default();
+        let author = aura
+            .release
+            .as_ref()
+            .and_then(|r| r.username.clone())
+            .unwrap_or_default();
+
+        let name_container = Container::new(Text::new(&aura.name).size(font_size()))
+            .width(Length::Fill)
+            .center_y()
+            .padding(5)
+            .style(style::NormalForegroundContainer(color_palette));
+
+        let kind_container = Container::new(Text::new(aura.kind.to_string()).size(font_size()))
+            .width(Length::Units(85))
+            .center_y()
+            .padding(5)
+            .style(style::NormalForegroundContainer(color_palette));
+
+        let author_container = Container::new(Text::new(author).size(font_size()))
+            .width(Length::Units(110))
+            .center_y()
+            .padding(5)
+            .style(style::NormalForegroundContainer(color_palette));
+
+        let installed_version_container =
+            Container::new(Text::new(installed_version).size(font_size()))
+                .width(Length::Units(85))
+                .center_y()
+                .padding(5)
+                .style(style::NormalForegroundContainer(color_palette));
+
+        let remote_version_container = Container::new(Text::new(remote_version).size(font_size()))
+            .width(Length::Units(85))
+            .center_y()
+            .padding(5)
+            .style(style::NormalForegroundContainer(color_palette));
+
+        let status_container =
+            Container::new(Text::new(aura.status().to_string()).size(font_size()))
+                .width(Length::Units(110))
+                .center_y()
+                .padding(5)
+                .style(style::NormalForegroundContainer(color_palette));
+
+        let row = Row::new()
+            .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
+            .push(name_container)
+            .push(kind_container)
+            .push(author_container)
+            .push(installed_version_container)
+            .push(remote_version_container)
+            .push(status_container)
+            .push(Space::new(
+                Length::Units(DEFAULT_PADDING + 5),
+                Length::Units(0),
+            ))
+            .spacing(1);
+
+        scrollable = scrollable.push(
+            Container::new(row)
+                .width(Length::Fill)
+                .style(style::Row(color_palette)),
+        );
+    }
+
+    let column = Column::new()
+        .push(title_row)
+        .push(Space::new(Length::Units(0), Length::Units(5)))
+        .push(scrollable);
+
+    Container::new(column).height(Length::FillPortion(1))
+}
+
+/// Formats how long ago `time` was, eg. `3 days ago`.
+fn format_time_ago(time: SystemTime) -> String {
+    let elapsed = time.elapsed().unwrap_or_default();
+
+    time_formatter().convert(elapsed)
+}
+
+/// Formats relative times, eg. `3 days ago`, in the language of the GUI.
+fn time_formatter() -> timeago::Formatter<timeago::BoxedLanguage> {
+    let language: timeago::BoxedLanguage = match localization::language() {
+        Language::English => Box::new(timeago::English),
+        Language::German => Box::new(timeago::languages::german::German),
+        Language::French => Box::new(timeago::languages::french::French),
+    };
+
+    timeago::Formatter::with_language(language)
+}
+
+/// Formats a size in bytes, eg. `1.4 MB`.
+fn format_size(bytes: u64) -> String {
+    let units = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit < units.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, units[unit])
+    } else {
+        format!("{:.1} {}", size, units[unit])
+    }
+}
+
+/// Formats a time of the update schedule, with the day if it's not today.
+fn format_schedule_time(at: NaiveDateTime, now: NaiveDateTime) -> String {
+    if at.date() == now.date() {
+        at.format("%H:%M").to_string()
+    } else {
+        at.format("%a %H:%M").to_string()
+    }
+}
@@ -3,35 +3,59 @@ mod style;
 mod update;
 
 use crate::cli::Opts;
+use crate::instance::{self, Claim, Instance, Request};
+use crate::tray::{self, Tray};
 use crate::VERSION;
 use ajour_core::{
-    addon::{Addon, AddonFolder, AddonVersionKey, ReleaseChannel},
+    abandoned,
+    addon::{
+        Addon, AddonDetails, AddonFolder, AddonState, AddonVersionKey, ReleaseChannel, Repository,
+    },
+    backup::Archive,
     catalog::get_catalog,
     catalog::{self, Catalog, CatalogAddon},
-    config::{load_config, ColumnConfigV2, Config, Flavor},
+    config::{
+        load_config, BackupSchedule, CheckInterval, ColumnConfigV2, Config, Flavor, RowDensity,
+        SelfUpdateChannel, TextOption, Token, UpdateSchedule,
+    },
+    download::DownloadManager,
     error::ClientError,
-    fs::PersistentData,
-    parse::FingerprintCollection,
+    fs::{
+        archive::CachedArchive, cleanup::Leftover, history::UpdateHistory, snapshot::Snapshot,
+        user_themes_modified, PersistentData,
+    },
+    game::{ClientBuild, Compatibility},
+    link::InstallLink,
+    localization::{self, localized_string, localized_string_with, Language},
+    logs::LogLine,
+    network,
+    parse::{self, FingerprintCollection},
+    self_update::{package_manager, PackageManager},
     theme::{load_user_themes, Theme},
-    utility::needs_update,
+    update::{skip_reason, SkipReason, UpdateReport},
+    utility::{fuzzy_match, AppRelease},
+    verify::Verification,
+    wago::{Aura, CompanionUpdate},
     Result,
 };
 use async_std::sync::{Arc, Mutex};
 use chrono::NaiveDateTime;
 use iced::{
-    button, pick_list, scrollable, text_input, Application, Column, Command, Container, Element,
-    Length, PickList, Row, Settings, Space, Subscription, TextInput,
+    button, pick_list, scrollable, text_input, Application, Button, Column, Command, Container,
+    Element, Length, PickList, Row, Settings, Space, Subscription, Text, TextInput,
 };
 use image::ImageFormat;
 use isahc::{
     config::{Configurable, RedirectPolicy},
     HttpClient,
 };
-use std::collections::HashMap;
+use log::Level;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
 use widgets::header;
 
-use element::{DEFAULT_FONT_SIZE, DEFAULT_PADDING};
+use element::{font_size, DEFAULT_PADDING};
 static WINDOW_ICON: &[u8] = include_bytes!("../../resources/windows/ajour.ico");
 
 #[derive(Debug)]
@@ -46,6 +70,7 @@ pub enum AjourState {
 pub enum AjourMode {
     MyAddons,
     Catalog,
+    Wago,
 }
 
 impl std::fmt::Display for AjourMode {
@@ -54,8 +79,9 @@ impl std::fmt::Display for AjourMode {
             f,
             "{}",
             match self {
-                AjourMode::MyAddons => "My Addons",
-                AjourMode::Catalog => "Catalog",
+                AjourMode::MyAddons => localized_string("my-addons"),
+                AjourMode::Catalog => localized_string("catalog"),
+                AjourMode::Wago => localized_string("wago"),
             }
         )
     }
@@ -70,16 +96,39 @@ pub enum Interaction {
     OpenDirectory(DirectoryType),
     OpenLink(String),
     Refresh,
+    Rollback(String),
+    UndoLastUpdate,
+    VerifyAddons,
+    RunDeferred,
+    CancelDeferred,
+    InstallFromLink,
+    DismissInstallLink,
     Settings,
+    ToggleShortcuts,
     Unignore(String),
+    Pin(String),
+    Unpin(String),
     Update(String),
+    PauseDownload(String),
+    ResumeDownload(String),
+    CancelDownload(String),
     UpdateAll,
     SortColumn(ColumnKey),
     SortCatalogColumn(CatalogColumnKey),
     FlavorSelected(Flavor),
+    InstallationSelected(String),
+    ProfileSelected(String),
+    ProfileNameInput(String),
+    AddProfile,
+    DetectedDirectorySelected(PathBuf),
+    OnboardingFlavorToggled(Flavor, bool),
+    OnboardingBack,
+    OnboardingNext,
     ResizeColumn(AjourMode, header::ResizeEvent),
     ScaleUp,
     ScaleDown,
+    FontSizeUp,
+    FontSizeDown,
     Backup,
     ToggleColumn(bool, ColumnKey),
     MoveColumnLeft(ColumnKey),
@@ -90,6 +139,60 @@ pub enum Interaction {
     CatalogCategorySelected(CatalogCategory),
     CatalogResultSizeSelected(CatalogResultSize),
     CatalogSourceSelected(CatalogSource),
+    CatalogPageSelected(usize),
+    ToggleBackupBeforeUpdateAll(bool),
+    RestoreBackup,
+    ConfirmRestoreBackup,
+    CancelRestoreBackup,
+    ToggleUpdateNotifications(bool),
+    ToggleOffline(bool),
+    ToggleRunAfterGameExits(bool),
+    ToggleOpenLinks(bool),
+    ToggleTrayIcon(bool),
+    ToggleCloseToTray(bool),
+    DismissUpdateReport,
+    DismissToast(usize),
+    ToastAction(usize),
+    ToggleLogs,
+    ToggleReleaseNotes,
+    SkipRelease,
+    GenerateDiagnostics,
+    LogLevelSelected(Level),
+    LogModuleInput(String),
+    SelectLogLine(usize),
+    CopyLogLines,
+    ToggleInstallFromUrl,
+    InstallFromUrlInput(String),
+    BrowseArchive,
+    InstallFromUrl,
+    ThemeImportUrlInput(String),
+    ImportTheme,
+    ExportTheme,
+    ToggleCleanup,
+    ToggleWhatsNew,
+    ToggleUpdatePreview,
+    AddonsQuery(String),
+    AddonStatusFilterSelected(AddonStatusFilter),
+    UpdateSelected,
+    IgnoreSelected,
+    DeleteSelected,
+    ClearSelection,
+    CancelDelete,
+    ToggleChangeSource(String),
+    ChangeSourceIdInput(String),
+    TokenInput(Token, String),
+    AdvancedOptionInput(TextOption, String),
+    SaveAdvancedOptions,
+    OpenConfigDirectory,
+    SaveSource(String),
+    ResetSource(String),
+    ToggleDeleteSavedVariables(bool),
+    ToggleUpdateAllFlavors(bool),
+    ToggleLeftover(usize, bool),
+    DeleteLeftovers,
+    WagoAccountSelected(String),
+    CheckWago,
+    UpdateWago,
 }
 
 #[derive(Debug)]
@@ -98,23 +201,62 @@ pub enum Message {
     DownloadedAddon((DownloadReason, Flavor, String, Result<()>)),
     Error(ClientError),
     Interaction(Interaction),
-    NeedsUpdate(Result<Option<String>>),
+    NeedsUpdate(Result<Option<AppRelease>>),
     None(()),
     Parse(Result<Config>),
     ParsedAddons((Flavor, Result<Vec<Addon>>)),
     UpdateFingerprint((DownloadReason, Flavor, String, Result<()>)),
     ThemeSelected(String),
-    ReleaseChannelSelected(ReleaseChannel),
+    ReleaseChannelSelected((String, ReleaseChannel)),
+    CatalogAddonSourceSelected((catalog::Source, u32, catalog::Source)),
+    SourcePrioritySelected(Repository),
+    PreferredSourceSelected((String, Repository)),
+    SelectionReleaseChannelSelected(ReleaseChannel),
+    ChangeSourceRepositorySelected(Repository),
     ThemesLoaded(Vec<Theme>),
+    ThemeImported(Result<Theme>),
     UnpackedAddon((DownloadReason, Flavor, String, Result<Vec<AddonFolder>>)),
+    RolledBackAddon((Flavor, String, Result<(CachedArchive, Vec<AddonFolder>)>)),
+    UndoneLastUpdate((Flavor, Result<Vec<Snapshot>>)),
+    VerifiedAddons((Flavor, Vec<(String, Verification)>)),
     UpdateWowDirectory(Option<PathBuf>),
+    DetectedWowDirectories(Vec<PathBuf>),
+    AddInstallation(Option<PathBuf>),
     UpdateBackupDirectory(Option<PathBuf>),
     RuntimeEvent(iced_native::Event),
     LatestBackup(Option<NaiveDateTime>),
     BackupFinished(Result<NaiveDateTime>),
+    BackupScheduleSelected(BackupSchedule),
+    RestoreBackupSelected(Archive),
+    RestoreFinished(Result<Option<NaiveDateTime>>),
+    CheckedForUpdates(Result<Vec<(Flavor, Addon)>>),
+    CheckIntervalSelected(CheckInterval),
+    LanguageSelected(Language),
+    RowDensitySelected(RowDensity),
+    SelfUpdateChannelSelected(SelfUpdateChannel),
+    UpdateScheduleSelected(UpdateSchedule),
+    ArchiveChosen(Option<PathBuf>),
+    InstalledFromUrl(Result<Vec<AddonFolder>>),
+    LeftoversFound(Result<Vec<Leftover>>),
+    WagoChecked((Flavor, Result<Vec<Aura>>)),
+    CompanionWritten(Result<CompanionUpdate>),
+    Tick(Instant),
+    CheckGame(Instant),
+    CheckThemes(Instant),
+    ExpireToasts(Instant),
+    ReadLogs(Instant),
+    LogsRead(Result<Vec<LogLine>>),
+    DiagnosticsGenerated(Result<PathBuf>),
+    InstanceRequest(Request),
+    TrayAction(tray::Action),
+    RunningFlavors(Vec<Flavor>),
+    DownloadProgress(Instant),
+    LoadingProgress(Instant),
     CatalogDownloaded(Result<Catalog>),
     CatalogInstallAddonFetched((Flavor, u32, Result<Addon>)),
     FetchedCurseChangelog((Addon, AddonVersionKey, Result<(String, String)>)),
+    FetchedUpdateChangelog((Flavor, String, Result<Option<(String, String)>>)),
+    FetchedAddonDetails((Repository, String, Result<AddonDetails>)),
     FetchedTukuiChangelog((Addon, AddonVersionKey, Result<(String, String)>)),
 }
 
@@ -124,34 +266,80 @@ pub struct Ajour {
     config: Config,
     valid_flavors: Vec<Flavor>,
     directory_btn_state: button::State,
+    installation_state: InstallationState,
+    profile_state: ProfileState,
     expanded_type: ExpandType,
     is_showing_settings: bool,
-    needs_update: Option<String>,
+    needs_update: Option<AppRelease>,
     new_release_button_state: button::State,
+    release_notes_state: ReleaseNotesState,
+    /// Package manager Ajour was installed with, which updates it instead.
+    package_manager: Option<PackageManager>,
     refresh_btn_state: button::State,
     settings_btn_state: button::State,
     shared_client: Arc<HttpClient>,
     state: AjourState,
     mode: AjourMode,
     update_all_btn_state: button::State,
+    undo_btn_state: button::State,
+    /// Flavors with an update which can be undone.
+    undoable_flavors: HashSet<Flavor>,
+    verify_btn_state: button::State,
     header_state: HeaderState,
     theme_state: ThemeState,
+    language_picklist_state: pick_list::State<Language>,
     fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
-    retail_btn_state: button::State,
-    retail_ptr_btn_state: button::State,
-    retail_beta_btn_state: button::State,
-    classic_btn_state: button::State,
-    classic_ptr_btn_state: button::State,
+    /// The game client of each flavor, which addons are checked against.
+    client_builds: HashMap<Flavor, ClientBuild>,
+    flavor_btn_states: Vec<(Flavor, button::State)>,
     addon_mode_btn_state: button::State,
     catalog_mode_btn_state: button::State,
+    wago_mode_btn_state: button::State,
     scale_state: ScaleState,
     backup_state: BackupState,
     column_settings: ColumnSettings,
-    onboarding_directory_btn_state: button::State,
+    onboarding_state: OnboardingState,
     catalog: Option<Catalog>,
     catalog_install_statuses: Vec<(Flavor, u32, CatalogInstallStatus)>,
     catalog_search_state: CatalogSearchState,
     catalog_header_state: CatalogHeaderState,
+    install_from_url_state: InstallFromUrlState,
+    cleanup_state: CleanupState,
+    cleanup_btn_state: button::State,
+    whats_new_state: WhatsNewState,
+    whats_new_btn_state: button::State,
+    update_preview_state: UpdatePreviewState,
+    update_preview_btn_state: button::State,
+    game_state: GameState,
+    install_link_state: InstallLinkState,
+    keyboard_state: KeyboardState,
+    wago_state: WagoState,
+    addons_filter_state: AddonsFilterState,
+    selection_state: SelectionState,
+    change_source_state: ChangeSourceState,
+    notification_state: NotificationState,
+    scheduled_update_state: ScheduledUpdateState,
+    source_priority_picklist_state: pick_list::State<Repository>,
+    self_update_channel_picklist_state: pick_list::State<SelfUpdateChannel>,
+    token_input_states: [text_input::State; 3],
+    advanced_settings_state: AdvancedSettingsState,
+    download_manager: DownloadManager,
+    /// Outcome of the last "Update All", shown once every addon has finished.
+    update_report: Option<UpdateReport>,
+    update_report_btn_state: button::State,
+    toast_state: ToastState,
+    logs_state: LogsState,
+    /// Id of the addon the user is asked to confirm deleting.
+    delete_confirmation: Option<String>,
+    /// Whether deleting addons also deletes their SavedVariables.
+    delete_saved_variables: bool,
+    /// Details fetched from the repository of addons, by repository and id.
+    addon_details: HashMap<(Repository, String), AddonDetails>,
+    /// Set if this is the single instance of Ajour, which instances launched
+    /// later hand their requests over to.
+    instance: Option<Instance>,
+    /// Icon in the system tray, if enabled.
+    tray: Option<Tray>,
 }
 
 impl Default for Ajour {
@@ -162,10 +350,14 @@ impl Default for Ajour {
             config: Config::default(),
             valid_flavors: Vec::new(),
             directory_btn_state: Default::default(),
+            installation_state: Default::default(),
+            profile_state: Default::default(),
             expanded_type: ExpandType::None,
             is_showing_settings: false,
             needs_update: None,
             new_release_button_state: Default::default(),
+            release_notes_state: Default::default(),
+            package_manager: package_manager(),
             refresh_btn_state: Default::default(),
             settings_btn_state: Default::default(),
             shared_client: Arc::new(
@@ -178,24 +370,62 @@ impl Default for Ajour {
             state: AjourState::Loading,
             mode: AjourMode::MyAddons,
             update_all_btn_state: Default::default(),
+            undo_btn_state: Default::default(),
+            undoable_flavors: Default::default(),
+            verify_btn_state: Default::default(),
             header_state: Default::default(),
             theme_state: Default::default(),
+            language_picklist_state: Default::default(),
             fingerprint_collection: Arc::new(Mutex::new(None)),
-            retail_btn_state: Default::default(),
-            retail_ptr_btn_state: Default::default(),
-            retail_beta_btn_state: Default::default(),
-            classic_btn_state: Default::default(),
-            classic_ptr_btn_state: Default::default(),
+            client_builds: HashMap::new(),
+            flavor_btn_states: Flavor::ALL
+                .iter()
+                .map(|f| (*f, Default::default()))
+                .collect(),
             addon_mode_btn_state: Default::default(),
             catalog_mode_btn_state: Default::default(),
+            wago_mode_btn_state: Default::default(),
             scale_state: Default::default(),
             backup_state: Default::default(),
             column_settings: Default::default(),
-            onboarding_directory_btn_state: Default::default(),
+            onboarding_state: Default::default(),
             catalog: None,
             catalog_install_statuses: vec![],
             catalog_search_state: Default::default(),
             catalog_header_state: Default::default(),
+            install_from_url_state: Default::default(),
+            cleanup_state: Default::default(),
+            cleanup_btn_state: Default::default(),
+            whats_new_state: WhatsNewState {
+                history: UpdateHistory::load_or_default().unwrap_or_default(),
+                ..Default::default()
+            },
+            whats_new_btn_state: Default::default(),
+            update_preview_state: Default::default(),
+            update_preview_btn_state: Default::default(),
+            game_state: Default::default(),
+            install_link_state: Default::default(),
+            keyboard_state: Default::default(),
+            wago_state: Default::default(),
+            addons_filter_state: Default::default(),
+            selection_state: Default::default(),
+            change_source_state: Default::default(),
+            notification_state: Default::default(),
+            scheduled_update_state: Default::default(),
+            source_priority_picklist_state: Default::default(),
+            self_update_channel_picklist_state: Default::default(),
+            token_input_states: Default::default(),
+            advanced_settings_state: Default::default(),
+            download_manager: Default::default(),
+            update_report: None,
+            update_report_btn_state: Default::default(),
+            toast_state: Default::default(),
+            logs_state: Default::default(),
+            delete_confirmation: None,
+            delete_saved_variables: false,
+            addon_details: Default::default(),
+            instance: None,
+            tray: None,
         }
     }
 }
@@ -203,17 +433,29 @@ impl Default for Ajour {
 impl Application for Ajour {
     type Executor = iced::executor::Default;
     type Message = Message;
-    type Flags = ();
+    type Flags = Launch;
 
-    fn new(_flags: ()) -> (Self, Command<Message>) {
-        let init_commands = vec![
+    fn new(launch: Launch) -> (Self, Command<Message>) {
+        let mut init_commands = vec![
             Command::perform(load_config(), Message::Parse),
-            Command::perform(needs_update(VERSION), Message::NeedsUpdate),
             Command::perform(load_user_themes(), Message::ThemesLoaded),
             Command::perform(get_catalog(), Message::CatalogDownloaded),
         ];
 
-        (Ajour::default(), Command::batch(init_commands))
+        // Opened as if it was handed over by another instance.
+        if let Some(link) = launch.link {
+            init_commands.push(Command::perform(
+                async { Request::Open(link) },
+                Message::InstanceRequest,
+            ));
+        }
+
+        let ajour = Ajour {
+            instance: launch.instance,
+            ..Ajour::default()
+        };
+
+        (ajour, Command::batch(init_commands))
     }
 
     fn title(&self) -> String {
@@ -225,7 +467,63 @@ impl Application for Ajour {
     }
 
     fn subscription(&self) -> Subscription<Self::Message> {
-        iced_native::subscription::events().map(Message::RuntimeEvent)
+        let mut subscriptions = vec![
+            iced_native::subscription::events().map(Message::RuntimeEvent),
+            // Drives scheduled tasks, such as backups.
+            iced_futures::time::every(Duration::from_secs(60)).map(Message::Tick),
+        ];
+
+        // Redraws download progress while addons are downloading.
+        if self.download_manager.is_active() {
+            subscriptions.push(
+                iced_futures::time::every(Duration::from_millis(250))
+                    .map(Message::DownloadProgress),
+            );
+        }
+
+        // Requests of instances of Ajour launched after this one.
+        if let Some(instance) = &self.instance {
+            subscriptions.push(instance.requests().map(Message::InstanceRequest));
+        }
+
+        // Actions chosen in the menu of the tray icon.
+        if let Some(tray) = &self.tray {
+            subscriptions.push(tray.actions().map(Message::TrayAction));
+        }
+
+        // Watches for the game to exit while actions are held back.
+        if !self.game_state.deferred.is_empty() {
+            subscriptions
+                .push(iced_futures::time::every(Duration::from_secs(5)).map(Message::CheckGame));
+        }
+
+        // Reloads user themes as their files change, while one is in use or the
+        // theme picker is shown.
+        if self.is_showing_settings || self.theme_state.is_user_theme_selected() {
+            subscriptions
+                .push(iced_futures::time::every(Duration::from_secs(2)).map(Message::CheckThemes));
+        }
+
+        // Hides toasts once they have been shown for a while.
+        if !self.toast_state.toasts.is_empty() {
+            subscriptions
+                .push(iced_futures::time::every(Duration::from_secs(1)).map(Message::ExpireToasts));
+        }
+
+        // Tails the log while it's shown.
+        if self.logs_state.is_shown {
+            subscriptions
+                .push(iced_futures::time::every(Duration::from_secs(1)).map(Message::ReadLogs));
+        }
+
+        // Redraws the loading status, which shows when we are rate limited.
+        if matches!(self.state, AjourState::Loading) {
+            subscriptions.push(
+                iced_futures::time::every(Duration::from_secs(1)).map(Message::LoadingProgress),
+            );
+        }
+
+        Subscription::batch(subscriptions)
     }
 
     fn update(&mut self, message: Message) -> Command<Message> {
@@ -271,12 +569,9 @@ impl Application for Ajour {
             &mut self.settings_btn_state,
             &mut self.addon_mode_btn_state,
             &mut self.catalog_mode_btn_state,
-            &mut self.retail_btn_state,
-            &mut self.retail_ptr_btn_state,
-            &mut self.retail_beta_btn_state,
-            &mut self.classic_btn_state,
-            &mut self.classic_ptr_btn_state,
-            self.needs_update.as_deref(),
+            &mut self.wago_mode_btn_state,
+            &mut self.flavor_btn_states,
+            self.needs_update.as_ref(),
             &mut self.new_release_button_state,
         );
 
@@ -286,16 +581,71 @@ impl Application for Ajour {
         // This column gathers all the other elements together.
         let mut content = Column::new().push(menu_container);
 
+        // Addons might be out of date and can't be updated while offline.
+        if network::is_offline() {
+            content = content.push(element::offline_banner(color_palette, self.config.offline));
+        }
+
+        // Addon of an install link opened from the browser.
+        if let Some(link) = self.install_link_state.link {
+            content = content.push(element::install_link_banner(
+                color_palette,
+                &mut self.install_link_state,
+                link,
+                self.catalog.as_ref(),
+                self.config.wow.flavor,
+            ));
+        }
+
+        // Updates and deletions held back while the game is running.
+        if self
+            .game_state
+            .deferred
+            .iter()
+            .any(|a| a.flavor == self.config.wow.flavor)
+        {
+            content = content.push(element::game_running_banner(
+                color_palette,
+                &mut self.game_state,
+                self.config.wow.flavor,
+                self.config.run_after_game_exits,
+            ));
+        }
+
+        if let Some(report) = self.update_report.as_ref().filter(|r| r.is_finished()) {
+            content = content.push(element::update_report_container(
+                color_palette,
+                report,
+                &mut self.update_report_btn_state,
+            ));
+        }
+
+        if !self.toast_state.toasts.is_empty() {
+            content = content.push(element::toasts_container(
+                color_palette,
+                &mut self.toast_state,
+            ));
+        }
+
         // This ensure we only draw settings, when we need to.
         if self.is_showing_settings {
             // Settings container, containing all data releated to settings.
             let settings_container = element::settings_container(
                 color_palette,
                 &mut self.directory_btn_state,
+                &mut self.installation_state,
+                &mut self.profile_state,
                 &cloned_config,
                 &mut self.theme_state,
+                &mut self.language_picklist_state,
                 &mut self.scale_state,
                 &mut self.backup_state,
+                &mut self.notification_state,
+                &mut self.scheduled_update_state,
+                &mut self.source_priority_picklist_state,
+                &mut self.self_update_channel_picklist_state,
+                &mut self.token_input_states,
+                &mut self.advanced_settings_state,
                 &mut self.column_settings,
                 &column_config,
             );
@@ -307,11 +657,67 @@ impl Application for Ajour {
             content = content.push(settings_container).push(space);
         }
 
+        if self.keyboard_state.is_showing_shortcuts {
+            let shortcuts_container =
+                element::shortcuts_container(color_palette, &mut self.keyboard_state);
+
+            content = content
+                .push(shortcuts_container)
+                .push(Space::new(Length::Fill, Length::Units(DEFAULT_PADDING)));
+        }
+
+        if let Some(release) = self
+            .needs_update
+            .as_ref()
+            .filter(|_| self.release_notes_state.is_shown)
+        {
+            let release_notes_container = element::release_notes_container(
+                color_palette,
+                release,
+                self.package_manager,
+                &mut self.release_notes_state,
+            );
+
+            content = content
+                .push(release_notes_container)
+                .push(Space::new(Length::Fill, Length::Units(DEFAULT_PADDING)));
+        }
+
+        if self.logs_state.is_shown {
+            let logs_container = element::logs_container(color_palette, &mut self.logs_state);
+
+            content = content
+                .push(logs_container)
+                .push(Space::new(Length::Fill, Length::Units(DEFAULT_PADDING)));
+        }
+
         // Spacer between menu and content.
         content = content.push(Space::new(Length::Units(0), Length::Units(DEFAULT_PADDING)));
 
         match self.mode {
             AjourMode::MyAddons => {
+                // Whether "Update All" has anything to update in the other flavors.
+                let other_flavors_updatable = cloned_config.update_all_flavors
+                    && self.addons.iter().any(|(f, addons)| {
+                        *f != flavor
+                            && self.valid_flavors.contains(f)
+                            && addons.iter().any(|a| a.state == AddonState::Updatable)
+                    });
+                let has_other_flavors = self.valid_flavors.len() > 1;
+
+                // What "Update All" would do, if previewed.
+                let previewed_updates = if self.update_preview_state.is_shown {
+                    let flavors = if cloned_config.update_all_flavors {
+                        self.valid_flavors.clone()
+                    } else {
+                        vec![flavor]
+                    };
+
+                    preview_updates(&self.addons, &flavors, &cloned_config)
+                } else {
+                    vec![]
+                };
+
                 // Get mutable addons for current flavor.
                 let addons = self.addons.entry(flavor).or_default();
 
@@ -322,13 +728,41 @@ impl Application for Ajour {
                 let menu_addons_container = element::menu_addons_container(
                     color_palette,
                     &mut self.update_all_btn_state,
+                    &mut self.undo_btn_state,
                     &mut self.refresh_btn_state,
+                    &mut self.cleanup_btn_state,
+                    &mut self.verify_btn_state,
+                    &mut self.whats_new_btn_state,
+                    &mut self.update_preview_btn_state,
                     &self.state,
                     addons,
                     &mut self.config,
+                    self.cleanup_state.is_shown,
+                    self.whats_new_state.is_shown,
+                    self.whats_new_state.history.updates.len(),
+                    self.update_preview_state.is_shown,
+                    &mut self.addons_filter_state,
+                    has_other_flavors,
+                    other_flavors_updatable,
+                    self.undoable_flavors.contains(&flavor),
+                    self.config
+                        .scheduled_updates
+                        .next_run(self.scheduled_update_state.started_at),
                 );
                 content = content.push(menu_addons_container);
 
+                // Actions for the selected addons.
+                let selected_ids = self.selection_state.ids.clone();
+                if !selected_ids.is_empty() {
+                    let selection_container = element::selection_container(
+                        color_palette,
+                        &mut self.selection_state,
+                        addons,
+                        self.delete_saved_variables,
+                    );
+                    content = content.push(selection_container);
+                }
+
                 // Addon row titles is a row of titles above the addon scrollable.
                 // This is to add titles above each section of the addon row, to let
                 // the user easily identify what the value is.
@@ -346,8 +780,59 @@ impl Application for Ajour {
                 let mut addons_scrollable =
                     element::addon_scrollable(color_palette, &mut self.addons_scrollable_state);
 
-                // Loops though the addons.
-                for addon in addons {
+                // Titles of the addons depending on the addon shown in details.
+                let dependents: Vec<String> = match &self.expanded_type {
+                    ExpandType::Details(a) => a
+                        .dependents(addons)
+                        .iter()
+                        .map(|a| a.title().to_string())
+                        .collect(),
+                    _ => vec![],
+                };
+
+                // Folders of the addon shown in details which other addons ship as well.
+                let shared_folders: Vec<String> = match &self.expanded_type {
+                    ExpandType::Details(a) => a
+                        .shared_folders(addons)
+                        .iter()
+                        .map(|(folder, providers)| {
+                            let titles: Vec<_> = providers.iter().map(|p| p.title()).collect();
+                            localized_string_with(
+                                "shared-folder",
+                                &[("folder", &folder.id), ("addons", &titles.join(", "))],
+                            )
+                        })
+                        .collect(),
+                    _ => vec![],
+                };
+
+                let client_build = self.client_builds.get(&flavor);
+                let abandoned_after_months = cloned_config.addons.abandoned_after_months();
+
+                // Catalog addons which could replace the addon shown in details, if
+                // it looks abandoned.
+                let alternatives: Vec<String> = match (&self.expanded_type, &self.catalog) {
+                    (ExpandType::Details(a), Some(catalog))
+                        if abandoned::is_abandoned(a, client_build, abandoned_after_months) =>
+                    {
+                        abandoned::alternatives(a, addons, catalog, flavor, abandoned_after_months)
+                            .iter()
+                            .map(|c| c.name.clone())
+                            .collect()
+                    }
+                    _ => vec![],
+                };
+
+                // Loops though the addons, or only the ignored ones if filtered.
+                // Loops though the addons matching the filters.
+                let filter_state = &self.addons_filter_state;
+                let source_priority = cloned_config.addons.source_priority(&flavor);
+                // The source of one addon at a time can be changed.
+                let mut change_source_state = Some(&mut self.change_source_state);
+                for addon in addons
+                    .iter_mut()
+                    .filter(|a| filter_state.matches(a, client_build, abandoned_after_months))
+                {
                     // Checks if the current addon is expanded.
                     let is_addon_expanded = match &self.expanded_type {
                         ExpandType::Details(a) => a.primary_folder_id == addon.primary_folder_id,
@@ -367,12 +852,63 @@ impl Application for Ajour {
 
                     // A container cell which has all data about the current addon.
                     // If the addon is expanded, then this is also included in this container.
+                    let download_progress = self
+                        .download_manager
+                        .progress(&(flavor, addon.primary_folder_id.clone()));
+
+                    let details = match (addon.active_repository, addon.repository_id()) {
+                        (Some(repository), Some(id)) => self.addon_details.get(&(repository, id)),
+                        _ => None,
+                    };
+
+                    let is_changing_source = change_source_state.as_ref().map_or(false, |s| {
+                        s.addon_id.as_ref() == Some(&addon.primary_folder_id)
+                    });
+                    let source_state = if is_changing_source {
+                        change_source_state.take()
+                    } else {
+                        None
+                    };
+
+                    // Only addons on Tukui can also be found on Curse, so only they
+                    // have a source to prefer.
+                    let preferred_source = if addon
+                        .folders
+                        .iter()
+                        .any(|f| f.repository_identifiers.tukui.is_some())
+                    {
+                        Some(source_priority.preferred(addon.folders.iter().map(|f| f.id.as_str())))
+                    } else {
+                        None
+                    };
+
+                    let abandoned =
+                        if abandoned::is_abandoned(addon, client_build, abandoned_after_months) {
+                            Some((abandoned_after_months, &alternatives[..]))
+                        } else {
+                            None
+                        };
+
                     let addon_data_cell = element::addon_data_cell(
                         color_palette,
                         addon,
+                        download_progress,
                         is_addon_expanded,
                         &self.expanded_type,
                         &column_config,
+                        &dependents,
+                        &shared_folders,
+                        self.delete_confirmation.as_ref() == Some(&addon.primary_folder_id),
+                        self.delete_saved_variables,
+                        cloned_config.addons.is_ignored_by_pattern(addon),
+                        selected_ids.contains(&addon.primary_folder_id),
+                        self.keyboard_state.focused_id.as_ref() == Some(&addon.primary_folder_id),
+                        cloned_config.row_density,
+                        preferred_source,
+                        details,
+                        source_state,
+                        client_build,
+                        abandoned,
                     );
 
                     // Adds the addon data cell to the scrollable.
@@ -384,7 +920,28 @@ impl Application for Ajour {
                     Space::new(Length::FillPortion(1), Length::Units(DEFAULT_PADDING));
 
                 // Adds the rest of the elements to the content column.
-                if has_addons {
+                // The leftovers of a clean up are shown instead of the addons.
+                if self.cleanup_state.is_shown {
+                    let cleanup_container =
+                        element::cleanup_container(color_palette, &mut self.cleanup_state);
+
+                    content = content.push(cleanup_container).push(bottom_space)
+                } else if self.whats_new_state.is_shown {
+                    let whats_new_container =
+                        element::whats_new_container(color_palette, &mut self.whats_new_state);
+
+                    content = content.push(whats_new_container).push(bottom_space)
+                } else if self.update_preview_state.is_shown {
+                    let update_preview_container = element::update_preview_container(
+                        color_palette,
+                        &mut self.update_preview_state,
+                        &previewed_updates,
+                        cloned_config.backups.before_update_all
+                            && cloned_config.backup_directory.is_some(),
+                    );
+
+                    content = content.push(update_preview_container).push(bottom_space)
+                } else if has_addons {
                     content = content
                         .push(addon_row_titles)
                         .push(addons_scrollable)
@@ -404,11 +961,11 @@ impl Application for Ajour {
 
                     let catalog_query = TextInput::new(
                         &mut self.catalog_search_state.query_state,
-                        "Search for an addon...",
+                        &localized_string("search-for-addon"),
                         query,
                         Interaction::CatalogQuery,
                     )
-                    .size(DEFAULT_FONT_SIZE)
+                    .size(font_size())
                     .padding(10)
                     .width(Length::FillPortion(3))
                     .style(style::CatalogQueryInput(color_palette));
@@ -421,7 +978,7 @@ impl Application for Ajour {
                         Some(self.catalog_search_state.source),
                         Interaction::CatalogSourceSelected,
                     )
-                    .text_size(14)
+                    .text_size(font_size())
                     .width(Length::Fill)
                     .style(style::SecondaryPickList(color_palette));
 
@@ -439,7 +996,7 @@ impl Application for Ajour {
                         Some(self.catalog_search_state.category.clone()),
                         Interaction::CatalogCategorySelected,
                     )
-                    .text_size(14)
+                    .text_size(font_size())
                     .width(Length::Fill)
                     .style(style::SecondaryPickList(color_palette));
 
@@ -457,7 +1014,7 @@ impl Application for Ajour {
                         Some(self.catalog_search_state.result_size),
                         Interaction::CatalogResultSizeSelected,
                     )
-                    .text_size(14)
+                    .text_size(font_size())
                     .width(Length::Fill)
                     .style(style::SecondaryPickList(color_palette));
 
@@ -469,12 +1026,26 @@ impl Application for Ajour {
                             .height(Length::Fill)
                             .width(Length::FillPortion(1));
 
+                    let install_from_url_button: Element<Interaction> = Button::new(
+                        &mut self.catalog_search_state.install_from_url_btn_state,
+                        Text::new(localized_string("install-from-url")).size(font_size()),
+                    )
+                    .style(style::SecondaryButton(color_palette))
+                    .on_press(Interaction::ToggleInstallFromUrl)
+                    .into();
+                    let install_from_url_container =
+                        Container::new(install_from_url_button.map(Message::Interaction))
+                            .center_y()
+                            .style(style::NormalForegroundContainer(color_palette))
+                            .height(Length::Fill);
+
                     let catalog_query_row = Row::new()
                         .push(Space::new(Length::Units(DEFAULT_PADDING), Length::Units(0)))
                         .push(catalog_query.map(Message::Interaction))
                         .push(source_picklist_container)
                         .push(category_picklist_container)
                         .push(result_size_picklist_container)
+                        .push(install_from_url_container)
                         .push(Space::new(
                             Length::Units(DEFAULT_PADDING + 5),
                             Length::Units(0),
@@ -486,6 +1057,21 @@ impl Application for Ajour {
                         .height(Length::Units(35))
                         .center_y();
 
+                    content = content.push(catalog_query_container);
+
+                    // Shown below the search when toggled.
+                    if self.install_from_url_state.is_shown {
+                        let install_from_url_row = element::install_from_url_row(
+                            color_palette,
+                            &mut self.install_from_url_state,
+                            flavor,
+                        );
+
+                        content = content
+                            .push(Space::new(Length::Fill, Length::Units(5)))
+                            .push(install_from_url_row);
+                    }
+
                     let catalog_row_titles = element::catalog_row_titles(
                         color_palette,
                         catalog,
@@ -501,10 +1087,14 @@ impl Application for Ajour {
                     );
 
                     for addon in self.catalog_search_state.catalog_rows.iter_mut() {
-                        // TODO: We should make this prettier with new sources coming in.
-                        let installed_for_flavor = addons.iter().any(|a| {
-                            a.curse_id() == Some(addon.addon.id)
-                                || a.tukui_id() == Some(&addon.addon.id.to_string())
+                        // Installed from any of the sources the addon is on.
+                        let installed_for_flavor = addon.addon.entries().any(|entry| {
+                            addons.iter().any(|a| match entry.source {
+                                catalog::Source::Curse => a.curse_id() == Some(entry.id),
+                                catalog::Source::Tukui => {
+                                    a.tukui_id() == Some(&entry.id.to_string())
+                                }
+                            })
                         });
 
                         let statuses = self
@@ -526,55 +1116,91 @@ impl Application for Ajour {
                         catalog_scrollable = catalog_scrollable.push(catalog_data_cell);
                     }
 
-                    // Bottom space below the scrollable.
-                    let bottom_space =
-                        Space::new(Length::FillPortion(1), Length::Units(DEFAULT_PADDING));
+                    let total_pages = self.catalog_search_state.total_pages();
+                    let paging_container = element::catalog_paging_container(
+                        color_palette,
+                        &mut self.catalog_search_state.previous_page_btn_state,
+                        &mut self.catalog_search_state.next_page_btn_state,
+                        self.catalog_search_state.page,
+                        total_pages,
+                    );
 
                     content = content
-                        .push(catalog_query_container)
                         .push(Space::new(Length::Fill, Length::Units(5)))
                         .push(catalog_row_titles)
                         .push(catalog_scrollable)
-                        .push(bottom_space)
+                        .push(paging_container)
                 }
             }
+            AjourMode::Wago => {
+                let wago_container = element::wago_container(
+                    color_palette,
+                    &mut self.wago_state,
+                    cloned_config.wago.accounts.get(&flavor),
+                );
+
+                content = content.push(wago_container);
+            }
         }
 
         // Status messages.
         let container: Option<Container<Message>> = match self.state {
-            AjourState::Welcome => Some(element::status_container(
+            AjourState::Welcome => Some(element::welcome_container(
                 color_palette,
-                "Welcome to Ajour!",
-                "Please select your World of Warcraft directory",
-                Some(&mut self.onboarding_directory_btn_state),
+                &mut self.onboarding_state,
+                &mut self.theme_state,
+                self.scale_state.scale,
             )),
             AjourState::Idle => match self.mode {
                 AjourMode::MyAddons => {
                     if !has_addons {
                         Some(element::status_container(
                             color_palette,
-                            "Woops!",
-                            &format!("You have no {} addons.", flavor.to_string().to_lowercase()),
-                            None,
+                            &localized_string("woops"),
+                            &localized_string_with(
+                                "no-addons-for-flavor",
+                                &[("flavor", &flavor.to_string().to_lowercase())],
+                            ),
                         ))
                     } else {
                         None
                     }
                 }
-                AjourMode::Catalog => None,
+                AjourMode::Catalog | AjourMode::Wago => None,
             },
-            AjourState::Loading => match self.mode {
-                AjourMode::MyAddons => Some(element::status_container(
+            AjourState::Loading => match (self.mode, network::rate_limited()) {
+                (AjourMode::Wago, _) => None,
+                // Addons from the last parse are shown meanwhile.
+                (AjourMode::MyAddons, None) if has_addons => None,
+                (_, Some((host, retry_in))) => Some(element::status_container(
                     color_palette,
-                    "Loading..",
-                    "Currently parsing addons.",
-                    None,
+                    &localized_string("loading"),
+                    &localized_string_with(
+                        "rate-limited-retrying",
+                        &[("host", &host), ("seconds", &(retry_in.as_secs() + 1))],
+                    ),
                 )),
-                AjourMode::Catalog => Some(element::status_container(
+                (AjourMode::MyAddons, None) => {
+                    let (scanned, found) = parse::scan_progress();
+                    let description = if found > 0 {
+                        localized_string_with(
+                            "scanned-folders",
+                            &[("scanned", &scanned), ("count", &found)],
+                        )
+                    } else {
+                        localized_string("parsing-addons")
+                    };
+
+                    Some(element::status_container(
+                        color_palette,
+                        &localized_string("loading"),
+                        &description,
+                    ))
+                }
+                (AjourMode::Catalog, None) => Some(element::status_container(
                     color_palette,
-                    "Loading..",
-                    "Currently loading addon catalog.",
-                    None,
+                    &localized_string("loading"),
+                    &localized_string("loading-catalog"),
                 )),
             },
             _ => None,
@@ -593,12 +1219,39 @@ impl Application for Ajour {
     }
 }
 
+/// What the GUI is started with.
+#[derive(Default)]
+pub struct Launch {
+    instance: Option<Instance>,
+    /// Install link to open once started, passed to `open`.
+    link: Option<String>,
+}
+
 /// Starts the GUI.
 /// This function does not return.
-pub fn run(opts: Opts) {
+pub fn run(opts: Opts, link: Option<String>) {
+    // Two instances would race on the config and the addon folders, so launching Ajour
+    // again switches to the one running.
+    let instance = match instance::claim() {
+        Claim::Claimed(instance) => Some(instance),
+        Claim::Running => {
+            log::info!("Ajour is already running, switching to it.");
+
+            if let Some(link) = link {
+                instance::send(Request::Open(link));
+            }
+
+            instance::focus_running_window();
+            return;
+        }
+        Claim::Unavailable => None,
+    };
+
     let config: Config = Config::load_or_default().expect("loading config on application startup");
 
-    let mut settings = Settings::default();
+    localization::set_language(config.language.unwrap_or_else(Language::system));
+
+    let mut settings = Settings::with_flags(Launch { instance, link });
     settings.window.size = config.window_size.unwrap_or((900, 620));
 
     #[cfg(feature = "wgpu")]
@@ -650,6 +1303,8 @@ pub enum ExpandType {
 #[derive(Debug, Clone, Copy)]
 pub enum DirectoryType {
     Wow,
+    /// An additional installation of World of Warcraft.
+    Installation,
     Backup,
 }
 
@@ -663,6 +1318,11 @@ pub enum ColumnKey {
     Author,
     GameVersion,
     DateReleased,
+    Source,
+    Size,
+    InstalledAt,
+    UpdatedAt,
+    Interface,
 }
 
 impl ColumnKey {
@@ -670,17 +1330,22 @@ impl ColumnKey {
         use ColumnKey::*;
 
         let title = match self {
-            Title => "Addon",
-            LocalVersion => "Local",
-            RemoteVersion => "Remote",
-            Status => "Status",
-            Channel => "Channel",
-            Author => "Author",
-            GameVersion => "Game Version",
-            DateReleased => "Latest Release",
+            Title => "column-addon",
+            LocalVersion => "column-local",
+            RemoteVersion => "column-remote",
+            Status => "column-status",
+            Channel => "column-channel",
+            Author => "column-author",
+            GameVersion => "column-game-version",
+            DateReleased => "column-latest-release",
+            Source => "column-source",
+            Size => "column-size",
+            InstalledAt => "column-installed",
+            UpdatedAt => "column-last-updated",
+            Interface => "column-interface",
         };
 
-        title.to_string()
+        localized_string(title)
     }
 
     fn as_string(self) -> String {
@@ -695,6 +1360,11 @@ impl ColumnKey {
             Author => "author",
             GameVersion => "game_version",
             DateReleased => "date_released",
+            Source => "source",
+            Size => "size",
+            InstalledAt => "installed_at",
+            UpdatedAt => "updated_at",
+            Interface => "interface",
         };
 
         s.to_string()
@@ -712,6 +1382,11 @@ impl From<&str> for ColumnKey {
             "author" => ColumnKey::Author,
             "game_version" => ColumnKey::GameVersion,
             "date_released" => ColumnKey::DateReleased,
+            "source" => ColumnKey::Source,
+            "size" => ColumnKey::Size,
+            "installed_at" => ColumnKey::InstalledAt,
+            "updated_at" => ColumnKey::UpdatedAt,
+            "interface" => ColumnKey::Interface,
             _ => panic!(format!("Unknown ColumnKey for {}", s)),
         }
     }
@@ -787,7 +1462,7 @@ impl Default for HeaderState {
                     key: ColumnKey::Channel,
                     btn_state: Default::default(),
                     width: Length::Units(85),
-                    hidden: true,
+                    hidden: false,
                     order: 4,
                 },
                 ColumnState {
@@ -811,6 +1486,41 @@ impl Default for HeaderState {
                     hidden: true,
                     order: 7,
                 },
+                ColumnState {
+                    key: ColumnKey::Source,
+                    btn_state: Default::default(),
+                    width: Length::Units(110),
+                    hidden: true,
+                    order: 8,
+                },
+                ColumnState {
+                    key: ColumnKey::Size,
+                    btn_state: Default::default(),
+                    width: Length::Units(85),
+                    hidden: true,
+                    order: 9,
+                },
+                ColumnState {
+                    key: ColumnKey::InstalledAt,
+                    btn_state: Default::default(),
+                    width: Length::Units(110),
+                    hidden: true,
+                    order: 10,
+                },
+                ColumnState {
+                    key: ColumnKey::UpdatedAt,
+                    btn_state: Default::default(),
+                    width: Length::Units(110),
+                    hidden: true,
+                    order: 11,
+                },
+                ColumnState {
+                    key: ColumnKey::Interface,
+                    btn_state: Default::default(),
+                    width: Length::Units(85),
+                    hidden: true,
+                    order: 12,
+                },
             ],
         }
     }
@@ -899,6 +1609,36 @@ impl Default for ColumnSettings {
                     up_btn_state: Default::default(),
                     down_btn_state: Default::default(),
                 },
+                ColumnSettingState {
+                    key: ColumnKey::Source,
+                    order: 8,
+                    up_btn_state: Default::default(),
+                    down_btn_state: Default::default(),
+                },
+                ColumnSettingState {
+                    key: ColumnKey::Size,
+                    order: 9,
+                    up_btn_state: Default::default(),
+                    down_btn_state: Default::default(),
+                },
+                ColumnSettingState {
+                    key: ColumnKey::InstalledAt,
+                    order: 10,
+                    up_btn_state: Default::default(),
+                    down_btn_state: Default::default(),
+                },
+                ColumnSettingState {
+                    key: ColumnKey::UpdatedAt,
+                    order: 11,
+                    up_btn_state: Default::default(),
+                    down_btn_state: Default::default(),
+                },
+                ColumnSettingState {
+                    key: ColumnKey::Interface,
+                    order: 12,
+                    up_btn_state: Default::default(),
+                    down_btn_state: Default::default(),
+                },
             ],
         }
     }
@@ -926,15 +1666,15 @@ impl CatalogColumnKey {
         use CatalogColumnKey::*;
 
         let title = match self {
-            Title => "Addon",
-            Description => "Description",
-            Source => "Source",
-            NumDownloads => "# Downloads",
-            DateReleased => "Latest Release",
-            CatalogColumnKey::Install => "Status",
+            Title => "column-addon",
+            Description => "column-description",
+            Source => "column-source",
+            NumDownloads => "column-downloads",
+            DateReleased => "column-latest-release",
+            CatalogColumnKey::Install => "column-status",
         };
 
-        title.to_string()
+        localized_string(title)
     }
 
     fn as_string(self) -> String {
@@ -1059,6 +1799,11 @@ pub struct CatalogSearchState {
     pub source: CatalogSource,
     pub sources: Vec<CatalogSource>,
     pub sources_state: pick_list::State<CatalogSource>,
+    pub page: usize,
+    pub total_results: usize,
+    pub previous_page_btn_state: button::State,
+    pub next_page_btn_state: button::State,
+    pub install_from_url_btn_state: button::State,
 }
 
 impl Default for CatalogSearchState {
@@ -1077,13 +1822,30 @@ impl Default for CatalogSearchState {
             source: CatalogSource::All,
             sources: CatalogSource::all(),
             sources_state: Default::default(),
+            page: 0,
+            total_results: 0,
+            previous_page_btn_state: Default::default(),
+            next_page_btn_state: Default::default(),
+            install_from_url_btn_state: Default::default(),
         }
     }
 }
 
+impl CatalogSearchState {
+    /// Returns the number of pages needed to show all results with the selected result size.
+    pub fn total_pages(&self) -> usize {
+        let result_size = self.result_size.as_usize();
+
+        ((self.total_results + result_size - 1) / result_size).max(1)
+    }
+}
+
 pub struct CatalogRow {
     website_state: button::State,
     install_button_state: button::State,
+    source_picklist_state: pick_list::State<catalog::Source>,
+    /// Sources the addon is on, which can be picked from if there are several.
+    sources: Vec<catalog::Source>,
     addon: CatalogAddon,
 }
 
@@ -1092,6 +1854,8 @@ impl From<CatalogAddon> for CatalogRow {
         Self {
             website_state: Default::default(),
             install_button_state: Default::default(),
+            source_picklist_state: Default::default(),
+            sources: addon.sources(),
             addon,
         }
     }
@@ -1116,7 +1880,7 @@ pub enum CatalogCategory {
 impl std::fmt::Display for CatalogCategory {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            CatalogCategory::All => write!(f, "All Categories"),
+            CatalogCategory::All => write!(f, "{}", localized_string("all-categories")),
             CatalogCategory::Choice(name) => write!(f, "{}", name),
         }
     }
@@ -1164,7 +1928,11 @@ impl CatalogResultSize {
 
 impl std::fmt::Display for CatalogResultSize {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Results: {}", self.as_usize())
+        write!(
+            f,
+            "{}",
+            localized_string_with("results", &[("count", &self.as_usize())])
+        )
     }
 }
 
@@ -1179,8 +1947,7 @@ impl CatalogSource {
         vec![
             CatalogSource::All,
             CatalogSource::Choice(catalog::Source::Curse),
-            // FIXME: Uncomment once Tukui catalog is enabled
-            //CatalogSource::Choice(catalog::Source::Tukui),
+            CatalogSource::Choice(catalog::Source::Tukui),
         ]
     }
 }
@@ -1188,10 +1955,10 @@ impl CatalogSource {
 impl std::fmt::Display for CatalogSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
-            CatalogSource::All => "All Sources",
+            CatalogSource::All => localized_string("all-sources"),
             CatalogSource::Choice(source) => match source {
-                catalog::Source::Curse => "Curse",
-                catalog::Source::Tukui => "Tukui",
+                catalog::Source::Curse => "Curse".to_string(),
+                catalog::Source::Tukui => "Tukui".to_string(),
             },
         };
         write!(f, "{}", s)
@@ -1202,6 +1969,22 @@ pub struct ThemeState {
     themes: Vec<(String, Theme)>,
     current_theme_name: String,
     pick_list_state: pick_list::State<String>,
+    /// Names of the themes loaded from the themes folder.
+    user_theme_names: Vec<String>,
+    /// Number of theme files and when they were last modified, as of loading them.
+    user_themes_modified: Option<(usize, SystemTime)>,
+    import_url: String,
+    importing: bool,
+    status: Option<String>,
+    import_url_state: text_input::State,
+    import_btn_state: button::State,
+    export_btn_state: button::State,
+}
+
+impl ThemeState {
+    fn is_user_theme_selected(&self) -> bool {
+        self.user_theme_names.contains(&self.current_theme_name)
+    }
 }
 
 impl Default for ThemeState {
@@ -1225,6 +2008,14 @@ impl Default for ThemeState {
             themes,
             current_theme_name: "Dark".to_string(),
             pick_list_state: Default::default(),
+            user_theme_names: vec![],
+            user_themes_modified: None,
+            import_url: String::new(),
+            importing: false,
+            status: None,
+            import_url_state: Default::default(),
+            import_btn_state: Default::default(),
+            export_btn_state: Default::default(),
         }
     }
 }
@@ -1233,6 +2024,30 @@ pub struct ScaleState {
     scale: f64,
     up_btn_state: button::State,
     down_btn_state: button::State,
+    font_up_btn_state: button::State,
+    font_down_btn_state: button::State,
+    density_picklist_state: pick_list::State<RowDensity>,
+}
+
+/// Returns the scale of the desktop, for when none is set in the settings.
+///
+/// Windows and macOS tell the scale of each monitor, which iced applies on top
+/// of ours. X11 doesn't, so we fall back to the one set for GTK and Qt apps.
+fn system_scale() -> f64 {
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        let scale = ["GDK_SCALE", "QT_SCALE_FACTOR"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find_map(|value| value.trim().parse::<f64>().ok())
+            .filter(|scale| *scale > 0.0);
+
+        if let Some(scale) = scale {
+            return scale.max(0.5).min(2.0);
+        }
+    }
+
+    1.0
 }
 
 impl Default for ScaleState {
@@ -1240,7 +2055,10 @@ impl Default for ScaleState {
         ScaleState {
             scale: 1.0,
             up_btn_state: Default::default(),
+            font_up_btn_state: Default::default(),
+            font_down_btn_state: Default::default(),
             down_btn_state: Default::default(),
+            density_picklist_state: Default::default(),
         }
     }
 }
@@ -1249,8 +2067,646 @@ impl Default for ScaleState {
 pub struct BackupState {
     backing_up: bool,
     last_backup: Option<NaiveDateTime>,
+    /// When a backup last failed, so a scheduled backup isn't tried again right away.
+    last_failed_backup: Option<NaiveDateTime>,
+    latest_backup_loaded: bool,
+    update_all_pending: bool,
     directory_btn_state: button::State,
     backup_now_btn_state: button::State,
+    schedule_picklist_state: pick_list::State<BackupSchedule>,
+    archives: Vec<Archive>,
+    restore_archive: Option<Archive>,
+    confirming_restore: bool,
+    restoring: bool,
+    restore_picklist_state: pick_list::State<Archive>,
+    restore_btn_state: button::State,
+    cancel_restore_btn_state: button::State,
+}
+
+#[derive(Default)]
+pub struct NotificationState {
+    checking: bool,
+    last_check: Option<Instant>,
+    /// Versions which have already been notified about.
+    notified: HashSet<(Flavor, String, String)>,
+    interval_picklist_state: pick_list::State<CheckInterval>,
+}
+
+pub struct ScheduledUpdateState {
+    /// When the app was started, which the schedule counts from until it first runs.
+    started_at: NaiveDateTime,
+    /// Set while refreshing for a scheduled run or an update handed over by the
+    /// command line, the updates found are installed once the addons are parsed.
+    pending: bool,
+    schedule_picklist_state: pick_list::State<UpdateSchedule>,
+}
+
+impl Default for ScheduledUpdateState {
+    fn default() -> Self {
+        ScheduledUpdateState {
+            started_at: chrono::Local::now().naive_local(),
+            pending: false,
+            schedule_picklist_state: Default::default(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct InstallFromUrlState {
+    is_shown: bool,
+    url: String,
+    installing: bool,
+    status: Option<String>,
+    url_input_state: text_input::State,
+    browse_btn_state: button::State,
+    install_btn_state: button::State,
+}
+
+#[derive(Default)]
+pub struct ProfileState {
+    picklist_state: pick_list::State<String>,
+    name_input_state: text_input::State,
+    name: String,
+    add_btn_state: button::State,
+}
+
+#[derive(Default)]
+pub struct InstallationState {
+    picklist_state: pick_list::State<String>,
+    add_btn_state: button::State,
+}
+
+#[derive(Default)]
+pub struct CleanupState {
+    is_shown: bool,
+    scanning: bool,
+    /// Leftovers found and whether they are selected for deletion.
+    leftovers: Vec<(Leftover, bool)>,
+    status: Option<String>,
+    scrollable_state: scrollable::State,
+    delete_btn_state: button::State,
+}
+
+#[derive(Default)]
+pub struct AddonsFilterState {
+    query: Option<String>,
+    query_state: text_input::State,
+    status: AddonStatusFilter,
+    status_picklist_state: pick_list::State<AddonStatusFilter>,
+}
+
+impl AddonsFilterState {
+    /// Whether the addon is shown in My Addons. The query is fuzzy matched
+    /// against the title and author of the addon.
+    fn matches(
+        &self,
+        addon: &Addon,
+        client_build: Option<&ClientBuild>,
+        abandoned_after_months: u32,
+    ) -> bool {
+        let matches_query = match &self.query {
+            Some(query) => {
+                fuzzy_match(query, addon.title())
+                    || addon.author().map_or(false, |a| fuzzy_match(query, a))
+            }
+            None => true,
+        };
+
+        matches_query
+            && self
+                .status
+                .matches(addon, client_build, abandoned_after_months)
+    }
+}
+
+/// The project the user is linking an addon to, since it was matched against
+/// the wrong one.
+pub struct ChangeSourceState {
+    /// Id of the addon whose source is being changed.
+    addon_id: Option<String>,
+    repository: Repository,
+    id: String,
+    error: Option<String>,
+    repository_picklist_state: pick_list::State<Repository>,
+    id_input_state: text_input::State,
+    save_btn_state: button::State,
+    reset_btn_state: button::State,
+}
+
+impl ChangeSourceState {
+    /// Repositories Ajour can update addons from.
+    const REPOSITORIES: [Repository; 2] = [Repository::Curse, Repository::Tukui];
+}
+
+impl Default for ChangeSourceState {
+    fn default() -> Self {
+        ChangeSourceState {
+            addon_id: None,
+            repository: Repository::Curse,
+            id: Default::default(),
+            error: None,
+            repository_picklist_state: Default::default(),
+            id_input_state: Default::default(),
+            save_btn_state: Default::default(),
+            reset_btn_state: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Error,
+}
+
+/// A message shown above the content, such as the result of an update or an
+/// error, which otherwise would only be logged.
+pub struct Toast {
+    id: usize,
+    kind: ToastKind,
+    text: String,
+    /// Title of a button next to the toast, and what pressing it does, eg.
+    /// retrying what failed.
+    action: Option<(String, Interaction)>,
+    shown_at: Instant,
+    action_btn_state: button::State,
+    dismiss_btn_state: button::State,
+}
+
+#[derive(Default)]
+pub struct ToastState {
+    toasts: Vec<Toast>,
+    next_id: usize,
+}
+
+impl ToastState {
+    /// Toasts shown at once, the oldest is hidden to make room for a new one.
+    const MAX_TOASTS: usize = 4;
+
+    /// How long toasts are shown, errors are shown until they are dismissed.
+    const TIMEOUT: Duration = Duration::from_secs(8);
+
+    fn info(&mut self, text: impl Into<String>) {
+        self.push(ToastKind::Info, text.into(), None);
+    }
+
+    fn success(&mut self, text: impl Into<String>) {
+        self.push(ToastKind::Success, text.into(), None);
+    }
+
+    fn error(&mut self, text: impl Into<String>, retry: Option<Interaction>) {
+        let action = retry.map(|interaction| (localized_string("retry"), interaction));
+        self.push(ToastKind::Error, text.into(), action);
+    }
+
+    /// Shows a toast, unless one with the same text is shown already.
+    fn push(&mut self, kind: ToastKind, text: String, action: Option<(String, Interaction)>) {
+        if let Some(toast) = self.toasts.iter_mut().find(|t| t.text == text) {
+            toast.shown_at = Instant::now();
+            return;
+        }
+
+        if self.toasts.len() >= Self::MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+
+        self.toasts.push(Toast {
+            id: self.next_id,
+            kind,
+            text,
+            action,
+            shown_at: Instant::now(),
+            action_btn_state: Default::default(),
+            dismiss_btn_state: Default::default(),
+        });
+        self.next_id += 1;
+    }
+
+    /// Removes the toast, returning it.
+    fn dismiss(&mut self, id: usize) -> Option<Toast> {
+        let index = self.toasts.iter().position(|t| t.id == id)?;
+        Some(self.toasts.remove(index))
+    }
+
+    fn expire(&mut self, now: Instant) {
+        self.toasts.retain(|t| {
+            t.kind == ToastKind::Error || now.duration_since(t.shown_at) < Self::TIMEOUT
+        });
+    }
+}
+
+/// Steps of the onboarding shown on first launch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OnboardingStep {
+    /// The World of Warcraft directory is picked among the detected ones or
+    /// selected by hand.
+    Directory,
+    /// The flavors installed in the directory are picked to be managed.
+    Flavors,
+    Appearance,
+}
+
+impl Default for OnboardingStep {
+    fn default() -> Self {
+        OnboardingStep::Directory
+    }
+}
+
+#[derive(Default)]
+pub struct OnboardingState {
+    step: OnboardingStep,
+    directory: Option<PathBuf>,
+    /// Flavors installed in the directory, and whether they are managed.
+    flavors: Vec<(Flavor, bool)>,
+    detected_directories: Vec<(PathBuf, button::State)>,
+    directory_btn_state: button::State,
+    back_btn_state: button::State,
+    next_btn_state: button::State,
+    theme_picklist_state: pick_list::State<String>,
+    scale_up_btn_state: button::State,
+    scale_down_btn_state: button::State,
+}
+
+/// Options typed in under "Advanced" in the settings. They are only written to
+/// the config once all of them are valid.
+#[derive(Default)]
+pub struct AdvancedSettingsState {
+    inputs: Vec<(TextOption, String, text_input::State)>,
+    error: Option<String>,
+    is_saved: bool,
+    save_btn_state: button::State,
+    open_config_btn_state: button::State,
+    logs_btn_state: button::State,
+    diagnostics_btn_state: button::State,
+}
+
+impl AdvancedSettingsState {
+    /// Fills the inputs with the options in `config`.
+    fn reset(&mut self, config: &Config) {
+        self.inputs = TextOption::ALL
+            .iter()
+            .map(|option| (*option, config.text_option(*option), Default::default()))
+            .collect();
+        self.error = None;
+        self.is_saved = false;
+    }
+}
+
+/// Prompt showing what changed in a new release of Ajour, before downloading it.
+#[derive(Default)]
+pub struct ReleaseNotesState {
+    is_shown: bool,
+    scrollable_state: scrollable::State,
+    download_btn_state: button::State,
+    skip_btn_state: button::State,
+    close_btn_state: button::State,
+}
+
+/// The log viewer, which reads the log again every second while it's shown.
+pub struct LogsState {
+    is_shown: bool,
+    lines: Vec<LogLine>,
+    /// Least severe level shown.
+    level: Level,
+    /// Shows only the records of modules containing this.
+    module: String,
+    /// Lines selected for copying, from the line clicked first to the one
+    /// clicked last with shift held, as indices into `lines`.
+    selection: Option<(usize, usize)>,
+    line_btn_states: Vec<button::State>,
+    level_picklist_state: pick_list::State<Level>,
+    module_input_state: text_input::State,
+    scrollable_state: scrollable::State,
+    copy_btn_state: button::State,
+    open_folder_btn_state: button::State,
+    close_btn_state: button::State,
+}
+
+impl LogsState {
+    const LEVELS: [Level; 5] = [
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    /// Whether `line` matches the level and module filters.
+    fn is_shown(&self, line: &LogLine) -> bool {
+        line.level <= self.level
+            && line
+                .target
+                .to_lowercase()
+                .contains(&self.module.trim().to_lowercase())
+    }
+
+    fn is_selected(&self, idx: usize) -> bool {
+        match self.selection {
+            Some((anchor, last)) => (anchor.min(last)..=anchor.max(last)).contains(&idx),
+            None => false,
+        }
+    }
+
+    /// The selected lines which are shown, or every shown line if none is
+    /// selected, as they are written in the log.
+    fn copy_text(&self) -> String {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter(|(idx, line)| {
+                self.is_shown(line) && (self.selection.is_none() || self.is_selected(*idx))
+            })
+            .map(|(_, line)| line.text())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Default for LogsState {
+    fn default() -> Self {
+        LogsState {
+            is_shown: false,
+            lines: vec![],
+            level: Level::Trace,
+            module: String::new(),
+            selection: None,
+            line_btn_states: vec![],
+            level_picklist_state: Default::default(),
+            module_input_state: Default::default(),
+            scrollable_state: Default::default(),
+            copy_btn_state: Default::default(),
+            open_folder_btn_state: Default::default(),
+            close_btn_state: Default::default(),
+        }
+    }
+}
+
+/// Moving through My Addons with the keyboard.
+#[derive(Default)]
+pub struct KeyboardState {
+    /// The addon moved to with the arrow keys, which Enter updates.
+    focused_id: Option<String>,
+    is_showing_shortcuts: bool,
+    close_btn_state: button::State,
+}
+
+/// Addons selected in My Addons by clicking them with ctrl or shift held, to act
+/// on all of them at once.
+#[derive(Default)]
+pub struct SelectionState {
+    ids: Vec<String>,
+    /// The addon last clicked, which a shift click selects from.
+    anchor: Option<String>,
+    is_control_pressed: bool,
+    is_shift_pressed: bool,
+    /// Whether the user is asked to confirm deleting the selected addons.
+    is_delete_confirmation: bool,
+    update_btn_state: button::State,
+    ignore_btn_state: button::State,
+    delete_btn_state: button::State,
+    clear_btn_state: button::State,
+    release_channel_picklist_state: pick_list::State<ReleaseChannel>,
+}
+
+impl SelectionState {
+    fn is_selected(&self, id: &str) -> bool {
+        self.ids.iter().any(|i| i == id)
+    }
+
+    fn is_selecting(&self) -> bool {
+        self.is_control_pressed || self.is_shift_pressed
+    }
+
+    /// Selects the clicked addon. Ctrl toggles the addon, shift also selects the
+    /// addons shown between it and the addon last clicked.
+    fn select(&mut self, id: &str, shown_ids: &[&str]) {
+        self.is_delete_confirmation = false;
+
+        let anchor = self
+            .anchor
+            .as_deref()
+            .and_then(|anchor| shown_ids.iter().position(|i| *i == anchor));
+        let clicked = shown_ids.iter().position(|i| *i == id);
+
+        match (self.is_shift_pressed, anchor, clicked) {
+            (true, Some(anchor), Some(clicked)) => {
+                let range = if anchor <= clicked {
+                    anchor..=clicked
+                } else {
+                    clicked..=anchor
+                };
+
+                for shown_id in &shown_ids[range] {
+                    if !self.is_selected(shown_id) {
+                        self.ids.push(shown_id.to_string());
+                    }
+                }
+            }
+            _ => {
+                if self.is_selected(id) {
+                    self.ids.retain(|i| i != id);
+                } else {
+                    self.ids.push(id.to_string());
+                }
+
+                self.anchor = Some(id.to_string());
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.ids.clear();
+        self.anchor = None;
+        self.is_delete_confirmation = false;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddonStatusFilter {
+    All,
+    UpToDate,
+    Updatable,
+    Ignored,
+    Unknown,
+    /// Flagged by the game as out of date, or made for another version of it.
+    OutOfDate,
+    /// No release for a long time, and made for an old expansion.
+    Abandoned,
+}
+
+impl AddonStatusFilter {
+    const ALL: [AddonStatusFilter; 7] = [
+        AddonStatusFilter::All,
+        AddonStatusFilter::UpToDate,
+        AddonStatusFilter::Updatable,
+        AddonStatusFilter::Ignored,
+        AddonStatusFilter::Unknown,
+        AddonStatusFilter::OutOfDate,
+        AddonStatusFilter::Abandoned,
+    ];
+
+    /// `client_build` is the game client of the flavor of `addon`, if known.
+    fn matches(
+        self,
+        addon: &Addon,
+        client_build: Option<&ClientBuild>,
+        abandoned_after_months: u32,
+    ) -> bool {
+        let state = &addon.state;
+
+        match self {
+            AddonStatusFilter::All => true,
+            AddonStatusFilter::UpToDate => matches!(state, AddonState::Ajour(_)),
+            AddonStatusFilter::Updatable => state == &AddonState::Updatable,
+            AddonStatusFilter::Ignored => state == &AddonState::Ignored,
+            AddonStatusFilter::Unknown => state == &AddonState::Unknown,
+            AddonStatusFilter::OutOfDate => match (client_build, addon.interface()) {
+                (Some(build), Some(interface)) => {
+                    build.compatibility(interface) != Compatibility::Compatible
+                }
+                _ => false,
+            },
+            AddonStatusFilter::Abandoned => {
+                abandoned::is_abandoned(addon, client_build, abandoned_after_months)
+            }
+        }
+    }
+}
+
+impl Default for AddonStatusFilter {
+    fn default() -> Self {
+        AddonStatusFilter::All
+    }
+}
+
+impl std::fmt::Display for AddonStatusFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            AddonStatusFilter::All => localized_string("filter-all"),
+            AddonStatusFilter::UpToDate => localized_string("up-to-date"),
+            AddonStatusFilter::Updatable => localized_string("update-available"),
+            AddonStatusFilter::Ignored => localized_string("ignored"),
+            AddonStatusFilter::Unknown => localized_string("unknown"),
+            AddonStatusFilter::OutOfDate => localized_string("out-of-date"),
+            AddonStatusFilter::Abandoned => localized_string("abandoned"),
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// An update or deletion held back since the game was running.
+pub struct DeferredAction {
+    /// Flavor selected when the action was started.
+    flavor: Flavor,
+    /// Flavors of the game which were running.
+    blocked_by: Vec<Flavor>,
+    interaction: Interaction,
+}
+
+impl DeferredAction {
+    fn is_blocked(&self, running: &[Flavor]) -> bool {
+        self.blocked_by.iter().any(|f| running.contains(f))
+    }
+}
+
+#[derive(Default)]
+pub struct GameState {
+    deferred: Vec<DeferredAction>,
+    /// Flavors of the game which were running when last checked.
+    running: Vec<Flavor>,
+    /// Set while the held back actions run, so they aren't held back again.
+    is_running_deferred: bool,
+    run_btn_state: button::State,
+    cancel_btn_state: button::State,
+}
+
+#[derive(Default)]
+pub struct InstallLinkState {
+    /// Addon of an install link opened from the browser, which the user is asked
+    /// to confirm installing.
+    link: Option<InstallLink>,
+    install_btn_state: button::State,
+    dismiss_btn_state: button::State,
+}
+
+#[derive(Default)]
+pub struct WhatsNewState {
+    is_shown: bool,
+    history: UpdateHistory,
+    scrollable_state: scrollable::State,
+}
+
+#[derive(Default)]
+pub struct UpdatePreviewState {
+    is_shown: bool,
+    scrollable_state: scrollable::State,
+}
+
+/// An addon as "Preview updates" lists it, either to be updated by "Update All"
+/// or skipped by it.
+#[derive(Debug, Clone)]
+pub struct PreviewedUpdate {
+    flavor: Flavor,
+    title: String,
+    old_version: Option<String>,
+    new_version: Option<String>,
+    skipped: Option<SkipReason>,
+}
+
+/// What "Update All" would do with the addons of `flavors`, without changing anything.
+fn preview_updates(
+    addons: &HashMap<Flavor, Vec<Addon>>,
+    flavors: &[Flavor],
+    config: &Config,
+) -> Vec<PreviewedUpdate> {
+    let mut previewed = vec![];
+
+    for flavor in flavors {
+        let ignored_ids = config.addons.ignored.get(flavor);
+
+        for addon in addons.get(flavor).into_iter().flatten() {
+            let is_ignored = ignored_ids.map_or(false, |ids| {
+                ids.iter().any(|i| i == &addon.primary_folder_id)
+            });
+
+            let skipped = if addon.state == AddonState::Updatable && !is_ignored {
+                None
+            } else if let Some(reason) = skip_reason(addon, &config.addons, *flavor) {
+                Some(reason)
+            } else {
+                continue;
+            };
+
+            previewed.push(PreviewedUpdate {
+                flavor: *flavor,
+                title: addon.title().to_string(),
+                old_version: addon.version().map(str::to_string),
+                new_version: addon.relevant_release_package().map(|p| p.version.clone()),
+                skipped,
+            });
+        }
+    }
+
+    // Addons to be updated first.
+    previewed.sort_by_key(|p| (p.skipped.is_some(), p.flavor, p.title.to_lowercase()));
+
+    previewed
+}
+
+#[derive(Default)]
+pub struct WagoState {
+    /// Accounts of the current flavor with WeakAuras or Plater installed.
+    accounts: Vec<String>,
+    account_picklist_state: pick_list::State<String>,
+    /// Auras imported from wago.io, of the chosen account.
+    auras: Vec<Aura>,
+    checking: bool,
+    updating: bool,
+    status: Option<String>,
+    scrollable_state: scrollable::State,
+    refresh_btn_state: button::State,
+    update_btn_state: button::State,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -1,5 +1,7 @@
 use ajour_core::theme::ColorPalette;
-use iced::{button, checkbox, container, pick_list, scrollable, text_input, Background, Color};
+use iced::{
+    button, checkbox, container, pick_list, progress_bar, scrollable, text_input, Background, Color,
+};
 
 pub struct BrightForegroundContainer(pub ColorPalette);
 impl container::StyleSheet for BrightForegroundContainer {
@@ -82,6 +84,17 @@ impl container::StyleSheet for NormalErrorForegroundContainer {
     }
 }
 
+pub struct NormalPrimaryForegroundContainer(pub ColorPalette);
+impl container::StyleSheet for NormalPrimaryForegroundContainer {
+    fn style(&self) -> container::Style {
+        container::Style {
+            background: Some(Background::Color(self.0.base.foreground)),
+            text_color: Some(self.0.normal.primary),
+            ..container::Style::default()
+        }
+    }
+}
+
 pub struct BrightTextButton(pub ColorPalette);
 impl button::StyleSheet for BrightTextButton {
     fn active(&self) -> button::Style {
@@ -396,6 +409,16 @@ impl container::StyleSheet for Row {
     }
 }
 
+pub struct SelectedRow(pub ColorPalette);
+impl container::StyleSheet for SelectedRow {
+    fn style(&self) -> container::Style {
+        container::Style {
+            background: Some(Background::Color(self.0.normal.primary)),
+            ..container::Style::default()
+        }
+    }
+}
+
 pub struct Scrollable(pub ColorPalette);
 impl scrollable::StyleSheet for Scrollable {
     fn active(&self) -> scrollable::Scrollbar {
@@ -555,6 +578,17 @@ impl container::StyleSheet for ChannelBadge {
     }
 }
 
+pub struct ProgressBar(pub ColorPalette);
+impl progress_bar::StyleSheet for ProgressBar {
+    fn style(&self) -> progress_bar::Style {
+        progress_bar::Style {
+            background: Background::Color(self.0.base.background),
+            bar: Background::Color(self.0.bright.primary),
+            border_radius: 2,
+        }
+    }
+}
+
 pub struct DefaultCheckbox(pub ColorPalette);
 impl checkbox::StyleSheet for DefaultCheckbox {
     fn active(&self, _is_checked: bool) -> checkbox::Style {
@@ -1,31 +1,71 @@
 use {
     super::{
-        AddonVersionKey, Ajour, AjourMode, AjourState, CatalogCategory, CatalogColumnKey,
-        CatalogInstallStatus, CatalogRow, CatalogSource, Changelog, ChangelogPayload, ColumnKey,
-        DirectoryType, DownloadReason, ExpandType, Interaction, Message, SortDirection,
+        element, system_scale, AddonVersionKey, Ajour, AjourMode, AjourState, CatalogCategory,
+        CatalogColumnKey, CatalogInstallStatus, CatalogRow, CatalogSource, ChangeSourceState,
+        Changelog, ChangelogPayload, ColumnKey, DeferredAction, DirectoryType, DownloadReason,
+        ExpandType, Interaction, Message, OnboardingStep, SortDirection, ToastKind,
     },
+    crate::instance::{self, Request},
+    crate::notification::notify_updates,
+    crate::tray,
+    crate::VERSION,
     ajour_core::{
-        addon::{Addon, AddonFolder, AddonState, Repository},
-        backup::{backup_folders, latest_backup, BackupFolder},
+        addon::{Addon, AddonDetails, AddonFolder, AddonState, Repository},
+        backup::{
+            backup_folders, flavor_backup_folders, is_backup_due, latest_backup, list_backups,
+            prune_backups, restore_backup, BackupContent,
+        },
         catalog,
-        config::{load_config, ColumnConfig, ColumnConfigV2, Flavor},
+        config::{
+            load_config, ColumnConfig, ColumnConfigV2, Config, Flavor, Hooks, SourcePriority,
+            WebhookEvent,
+        },
         curse_api,
-        fs::{delete_addons, install_addon, PersistentData},
-        network::download_addon,
-        parse::{read_addon_directory, update_addon_fingerprint, FingerprintCollection},
+        diagnostics::create_bundle,
+        download::Download,
+        error::ClientError,
+        fs::{
+            archive::CachedArchive,
+            cleanup::{delete_leftovers, delete_saved_variables, find_leftovers, Leftover},
+            config_dir, database, delete_addons, detect,
+            history::UpdateRecord,
+            install_addon, install_from_url,
+            links::{RepositoryLink, RepositoryLinks},
+            rollback_addon,
+            snapshot::{self, Snapshot},
+            user_themes_modified, PersistentData,
+        },
+        game,
+        hooks::{run_hook, HookEvent},
+        link::{parse_install_link, register_link_handler, unregister_link_handler, AJOUR_SCHEME},
+        localization::{self, localized_string, localized_string_with},
+        logs::{log_file, read_log},
+        network::{self, download_addon},
+        parse::{self, read_addon_directory, update_addon_fingerprint, FingerprintCollection},
+        theme::{export_theme, import_theme, load_user_themes},
         tukui_api,
-        utility::wow_path_resolution,
+        update::{apply_addon_preferences, check_for_updates, skip_reason, UpdateReport},
+        utility::{copy_to_clipboard, needs_update, wow_path_resolution},
+        verify::{verify_addons, Verification},
+        wago::{self, update_companion, Aura},
+        webhook::{send_webhook, WebhookAddon},
         Result,
     },
     async_std::sync::{Arc, Mutex},
-    iced::{Command, Length},
+    iced::{text_input, Command, Length},
+    iced_native::keyboard::KeyCode,
     isahc::HttpClient,
     native_dialog::*,
+    serde_json::json,
     std::collections::{HashMap, HashSet},
     std::path::{Path, PathBuf},
+    std::time::SystemTime,
     widgets::header::ResizeEvent,
 };
 
+/// Hours to wait before a scheduled backup which failed is tried again.
+const BACKUP_RETRY_HOURS: i64 = 1;
+
 pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Message>> {
     match message {
         Message::Parse(Ok(config)) => {
@@ -36,6 +76,8 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
             // which is provided by the config.
             ajour.config = config;
 
+            update_tray(ajour);
+
             // Set column widths from the config
             match &ajour.config.column_config {
                 ColumnConfig::V1 {
@@ -171,11 +213,24 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
             ajour.theme_state.current_theme_name =
                 ajour.config.theme.as_deref().unwrap_or("Dark").to_string();
 
-            // Use scale from config. Set to 1.0 if not defined.
-            ajour.scale_state.scale = ajour.config.scale.unwrap_or(1.0);
+            // Use scale from config. Set to the one of the desktop if not defined.
+            ajour.scale_state.scale = ajour.config.scale.unwrap_or_else(system_scale);
+
+            // Use font size from config.
+            element::set_font_size(
+                ajour
+                    .config
+                    .font_size
+                    .unwrap_or(element::DEFAULT_FONT_SIZE)
+                    .max(element::MIN_FONT_SIZE)
+                    .min(element::MAX_FONT_SIZE),
+            );
 
             // Begin to parse addon folder(s).
-            let mut commands = vec![];
+            let mut commands = vec![Command::perform(
+                needs_update(VERSION, ajour.config.self_update_channel),
+                Message::NeedsUpdate,
+            )];
 
             // If a backup directory is selected, find the latest backup
             if let Some(dir) = &ajour.config.backup_directory {
@@ -185,8 +240,19 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
                 ));
             }
 
-            let flavors = &Flavor::ALL[..];
-            for flavor in flavors {
+            parse::reset_scan_progress();
+
+            // The addons are checked against the game client of their flavor.
+            ajour.client_builds = ajour
+                .config
+                .wow
+                .directory
+                .as_deref()
+                .map(game::client_builds)
+                .unwrap_or_default();
+
+            let flavors = ajour.config.managed_flavors();
+            for flavor in flavors.iter() {
                 if let Some(addon_directory) = ajour.config.get_addon_directory_for_flavor(flavor) {
                     log::debug!(
                         "preparing to parse addons in {:?}",
@@ -199,11 +265,22 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
                         ajour.valid_flavors.dedup();
                     }
 
+                    // Addons as they were last parsed are shown until they are parsed again.
+                    if !ajour.addons.contains_key(flavor) {
+                        if let Some(mut addons) = database::load_addons(*flavor, &addon_directory) {
+                            apply_addon_preferences(&mut addons, &ajour.config.addons, *flavor);
+                            sort_addons(&mut addons, SortDirection::Desc, ColumnKey::Status);
+
+                            ajour.addons.insert(*flavor, addons);
+                        }
+                    }
+
                     commands.push(Command::perform(
                         perform_read_addon_directory(
                             ajour.fingerprint_collection.clone(),
                             addon_directory.clone(),
                             *flavor,
+                            ajour.config.addons.source_priority(flavor),
                         ),
                         Message::ParsedAddons,
                     ));
@@ -212,6 +289,13 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
 
                     // Assume we are welcoming a user because directory is not set.
                     ajour.state = AjourState::Welcome;
+                    ajour.onboarding_state = Default::default();
+
+                    // Look for World of Warcraft, to offer it in the welcome screen.
+                    commands.push(Command::perform(
+                        async { detect::wow_directories() },
+                        Message::DetectedWowDirectories,
+                    ));
                     break;
                 }
             }
@@ -240,6 +324,7 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
 
             // Cleans the addons.
             ajour.addons = HashMap::new();
+            ajour.selection_state.clear();
             // Prepare state for loading.
             ajour.state = AjourState::Loading;
 
@@ -252,6 +337,13 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
 
             // Remove the expanded addon.
             ajour.expanded_type = ExpandType::None;
+
+            // Pick up backups made outside of Ajour, and options edited in the
+            // config file.
+            if ajour.is_showing_settings {
+                refresh_backup_archives(ajour);
+                ajour.advanced_settings_state.reset(&ajour.config);
+            }
         }
         Message::Interaction(Interaction::Ignore(id)) => {
             log::debug!("Interaction::Ignore({})", &id);
@@ -290,7 +382,19 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
             if let Some(addon) = addons.iter_mut().find(|a| a.primary_folder_id == id) {
                 // Check if addon is updatable.
                 if let Some(package) = addon.relevant_release_package() {
-                    if addon.is_updatable(package) {
+                    let is_rolled_back = ajour.config.addons.is_rolled_back_from(
+                        &flavor,
+                        &addon.primary_folder_id,
+                        &package.version,
+                    );
+
+                    if ajour
+                        .config
+                        .addons
+                        .is_pinned(&flavor, &addon.primary_folder_id)
+                    {
+                        addon.state = AddonState::Pinned;
+                    } else if addon.is_updatable(package) && !is_rolled_back {
                         addon.state = AddonState::Updatable;
                     } else {
                         addon.state = AddonState::Ajour(None);
@@ -305,11 +409,61 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
             // Persist the newly updated config.
             let _ = &ajour.config.save();
         }
+        Message::Interaction(Interaction::Pin(id)) => {
+            log::debug!("Interaction::Pin({})", &id);
+
+            let flavor = ajour.config.wow.flavor;
+            let addons = ajour.addons.entry(flavor).or_default();
+
+            if let Some(addon) = addons.iter_mut().find(|a| a.primary_folder_id == id) {
+                addon.state = AddonState::Pinned;
+
+                // Update the config.
+                let pinned_addon_ids = ajour.config.addons.pinned.entry(flavor).or_default();
+                if !pinned_addon_ids.contains(&id) {
+                    pinned_addon_ids.push(id);
+                }
+
+                // Persist the newly updated config.
+                let _ = &ajour.config.save();
+            }
+        }
+        Message::Interaction(Interaction::Unpin(id)) => {
+            log::debug!("Interaction::Unpin({})", &id);
+
+            // Update ajour state.
+            let flavor = ajour.config.wow.flavor;
+            let addons = ajour.addons.entry(flavor).or_default();
+            if let Some(addon) = addons.iter_mut().find(|a| a.primary_folder_id == id) {
+                addon.state = AddonState::Ajour(None);
+
+                // Check if addon is updatable.
+                if let Some(package) = addon.relevant_release_package() {
+                    let is_rolled_back = ajour.config.addons.is_rolled_back_from(
+                        &flavor,
+                        &addon.primary_folder_id,
+                        &package.version,
+                    );
+
+                    if addon.is_updatable(package) && !is_rolled_back {
+                        addon.state = AddonState::Updatable;
+                    }
+                }
+            };
+
+            // Update the config.
+            let pinned_addon_ids = ajour.config.addons.pinned.entry(flavor).or_default();
+            pinned_addon_ids.retain(|i| i != &id);
+
+            // Persist the newly updated config.
+            let _ = &ajour.config.save();
+        }
         Message::Interaction(Interaction::OpenDirectory(dir_type)) => {
             log::debug!("Interaction::OpenDirectory({:?})", dir_type);
 
             let message = match dir_type {
                 DirectoryType::Wow => Message::UpdateWowDirectory,
+                DirectoryType::Installation => Message::AddInstallation,
                 DirectoryType::Backup => Message::UpdateBackupDirectory,
             };
 
@@ -330,12 +484,27 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
             let path = wow_path_resolution(chosen_path);
             log::debug!("Message::UpdateWowDirectory(Resolution({:?}))", &path);
 
+            // The directory is only saved once the onboarding is finished.
+            if matches!(ajour.state, AjourState::Welcome) {
+                if let Some(path) = path {
+                    let state = &mut ajour.onboarding_state;
+                    state.flavors = detect::installed_flavors(&path)
+                        .into_iter()
+                        .map(|f| (f, true))
+                        .collect();
+                    state.directory = Some(path);
+                    state.step = OnboardingStep::Flavors;
+                }
+
+                return Ok(Command::none());
+            }
+
             // Clear addons.
             ajour.addons = HashMap::new();
 
-            if path.is_some() {
+            if let Some(path) = path {
                 // Update the path for World of Warcraft.
-                ajour.config.wow.directory = path;
+                ajour.config.set_wow_directory(path);
                 // Persist the newly updated config.
                 let _ = &ajour.config.save();
                 // Set loading state.
@@ -344,18 +513,141 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
                 return Ok(Command::perform(load_config(), Message::Parse));
             }
         }
+        Message::DetectedWowDirectories(directories) => {
+            log::debug!("Message::DetectedWowDirectories({:?})", &directories);
+
+            ajour.onboarding_state.detected_directories = directories
+                .into_iter()
+                .map(|d| (d, Default::default()))
+                .collect();
+        }
+        Message::Interaction(Interaction::DetectedDirectorySelected(path)) => {
+            log::debug!("Interaction::DetectedDirectorySelected({:?})", &path);
+
+            return handle_message(ajour, Message::UpdateWowDirectory(Some(path)));
+        }
+        Message::Interaction(Interaction::OnboardingFlavorToggled(flavor, is_checked)) => {
+            log::debug!(
+                "Interaction::OnboardingFlavorToggled({}, {})",
+                flavor,
+                is_checked
+            );
+
+            let flavors = &mut ajour.onboarding_state.flavors;
+            if let Some((_, managed)) = flavors.iter_mut().find(|(f, _)| *f == flavor) {
+                *managed = is_checked;
+            }
+        }
+        Message::Interaction(Interaction::OnboardingBack) => {
+            log::debug!("Interaction::OnboardingBack");
+
+            let state = &mut ajour.onboarding_state;
+            state.step = match state.step {
+                OnboardingStep::Appearance => OnboardingStep::Flavors,
+                _ => OnboardingStep::Directory,
+            };
+        }
+        Message::Interaction(Interaction::OnboardingNext) => {
+            log::debug!("Interaction::OnboardingNext");
+
+            let state = &mut ajour.onboarding_state;
+            let managed_flavors: Vec<Flavor> = state
+                .flavors
+                .iter()
+                .filter(|(_, managed)| *managed)
+                .map(|(f, _)| *f)
+                .collect();
+
+            match state.step {
+                OnboardingStep::Directory if state.directory.is_some() => {
+                    state.step = OnboardingStep::Flavors;
+                }
+                OnboardingStep::Flavors if !managed_flavors.is_empty() => {
+                    state.step = OnboardingStep::Appearance;
+                }
+                OnboardingStep::Appearance => {
+                    // Theme and scale are saved as they are picked, the rest
+                    // once the onboarding is finished.
+                    if let Some(directory) = state.directory.clone() {
+                        ajour.config.wow.ignored_flavors = state
+                            .flavors
+                            .iter()
+                            .filter(|(_, managed)| !managed)
+                            .map(|(f, _)| *f)
+                            .collect();
+                        if let Some(flavor) = managed_flavors.first() {
+                            ajour.config.wow.flavor = *flavor;
+                        }
+                        ajour.config.set_wow_directory(directory);
+                        ajour.config.save()?;
+
+                        // The initial scan shows its progress while loading.
+                        ajour.addons = HashMap::new();
+                        ajour.state = AjourState::Loading;
+                        return Ok(Command::perform(load_config(), Message::Parse));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Message::AddInstallation(chosen_path) => {
+            log::debug!("Message::AddInstallation(Chosen({:?}))", &chosen_path);
+
+            if let Some(path) = wow_path_resolution(chosen_path) {
+                return Ok(switch_installation(ajour, path));
+            }
+        }
+        Message::Interaction(Interaction::InstallationSelected(directory)) => {
+            log::debug!("Interaction::InstallationSelected({})", &directory);
+
+            let directory = PathBuf::from(directory);
+
+            if ajour.config.wow.directory.as_ref() != Some(&directory) {
+                return Ok(switch_installation(ajour, directory));
+            }
+        }
+        Message::Interaction(Interaction::ProfileNameInput(name)) => {
+            ajour.profile_state.name = name;
+        }
+        Message::Interaction(Interaction::AddProfile) => {
+            let name = ajour.profile_state.name.trim().to_string();
+            log::debug!("Interaction::AddProfile({})", &name);
+
+            ajour.config.add_profile(name)?;
+            let _ = &ajour.config.save();
+
+            ajour.profile_state.name = String::new();
+        }
+        Message::Interaction(Interaction::ProfileSelected(name)) => {
+            log::debug!("Interaction::ProfileSelected({})", &name);
+
+            if ajour.config.profile.as_ref() != Some(&name) {
+                ajour.config.switch_profile(&name)?;
+
+                return Ok(reload_config(ajour));
+            }
+        }
         Message::Interaction(Interaction::FlavorSelected(flavor)) => {
             log::debug!("Interaction::FlavorSelected({})", flavor);
             // Close settings if shown.
             ajour.is_showing_settings = false;
             // Close details if shown.
             ajour.expanded_type = ExpandType::None;
+            // Close clean up, since the leftovers are of the previous flavor.
+            ajour.cleanup_state.is_shown = false;
+            // Forget the auras and selected addons, since they are of the previous flavor.
+            ajour.wago_state = Default::default();
+            ajour.selection_state.clear();
             // Update the game flavor
             ajour.config.wow.flavor = flavor;
             // Persist the newly updated config.
             let _ = &ajour.config.save();
             // Update catalog
             query_and_sort_catalog(ajour);
+
+            if ajour.mode == AjourMode::Wago {
+                return Ok(check_wago(ajour));
+            }
         }
         Message::Interaction(Interaction::ModeSelected(mode)) => {
             log::debug!("Interaction::ModeSelected({:?})", mode);
@@ -376,15 +668,58 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
                 AjourMode::MyAddons => {
                     ajour.state = AjourState::Idle;
                 }
+                AjourMode::Wago => {
+                    ajour.state = AjourState::Idle;
+
+                    // Checks for updates the first time the tab is opened.
+                    let state = &ajour.wago_state;
+                    if state.auras.is_empty() && !state.checking {
+                        return Ok(check_wago(ajour));
+                    }
+                }
             }
         }
 
         Message::Interaction(Interaction::Expand(expand_type)) => {
             // Close settings if shown.
             ajour.is_showing_settings = false;
+            // Forget a pending delete warning.
+            ajour.delete_confirmation = None;
 
             // An addon can be exanded in two ways.
             match &expand_type {
+                ExpandType::Details(a) if ajour.selection_state.is_selecting() => {
+                    log::debug!(
+                        "Interaction::Expand(Details({:?})) - selecting",
+                        &a.primary_folder_id
+                    );
+
+                    // Selects in the order the addons are shown.
+                    let flavor = ajour.config.wow.flavor;
+                    let filter_state = &ajour.addons_filter_state;
+                    let client_build = ajour.client_builds.get(&flavor);
+                    let shown_ids: Vec<&str> = ajour
+                        .addons
+                        .get(&flavor)
+                        .map(|addons| {
+                            addons
+                                .iter()
+                                .filter(|a| {
+                                    filter_state.matches(
+                                        a,
+                                        client_build,
+                                        ajour.config.addons.abandoned_after_months(),
+                                    )
+                                })
+                                .map(|a| a.primary_folder_id.as_str())
+                                .collect()
+                        })
+                        .unwrap_or_default();
+
+                    ajour
+                        .selection_state
+                        .select(&a.primary_folder_id, &shown_ids);
+                }
                 ExpandType::Details(a) => {
                     log::debug!("Interaction::Expand(Details({:?}))", &a.primary_folder_id);
                     let should_close = match &ajour.expanded_type {
@@ -396,6 +731,22 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
                         ajour.expanded_type = ExpandType::None;
                     } else {
                         ajour.expanded_type = expand_type.clone();
+
+                        // Details from the repository are fetched once, when first shown.
+                        if let (Some(repository), Some(id)) =
+                            (a.active_repository, a.repository_id())
+                        {
+                            if !ajour.addon_details.contains_key(&(repository, id.clone())) {
+                                return Ok(Command::perform(
+                                    perform_fetch_addon_details(
+                                        repository,
+                                        id,
+                                        ajour.config.wow.flavor,
+                                    ),
+                                    Message::FetchedAddonDetails,
+                                ));
+                            }
+                        }
                     }
                 }
                 ExpandType::Changelog(changelog) => match changelog {
@@ -484,19 +835,214 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
 
             // Close settings if shown.
             ajour.is_showing_settings = false;
-            // Close details if shown.
-            ajour.expanded_type = ExpandType::None;
 
             let flavor = ajour.config.wow.flavor;
             let addons = ajour.addons.entry(flavor).or_default();
 
             if let Some(addon) = addons.iter().find(|a| a.primary_folder_id == id).cloned() {
+                // Ask for confirmation first. The details stay open, and pressing
+                // delete again removes the addon.
+                if ajour.delete_confirmation.as_ref() != Some(&id) {
+                    ajour.delete_confirmation = Some(id);
+
+                    return Ok(Command::none());
+                }
+
+                ajour.delete_confirmation = None;
+                // Close details if shown.
+                ajour.expanded_type = ExpandType::None;
+
+                if defer_while_game_running(ajour, &[flavor], Interaction::Delete(id)) {
+                    return Ok(Command::none());
+                }
+
+                let addons = ajour.addons.entry(flavor).or_default();
+
+                // Folders shared with other addons, eg. libraries, are kept.
+                let folders: Vec<_> = addon.owned_folders(addons).into_iter().cloned().collect();
+
                 // Remove from local state.
                 addons.retain(|a| a.primary_folder_id != addon.primary_folder_id);
 
                 // Delete addon(s) from disk.
-                let _ = delete_addons(&addon.folders);
+                delete_folders(ajour, flavor, &folders);
+            }
+        }
+        Message::Interaction(Interaction::ToggleChangeSource(id)) => {
+            log::debug!("Interaction::ToggleChangeSource({})", &id);
+
+            let state = &mut ajour.change_source_state;
+            if state.addon_id.as_ref() == Some(&id) {
+                state.addon_id = None;
+                return Ok(Command::none());
+            }
+
+            // Starts from the project the addon is matched against.
+            let flavor = ajour.config.wow.flavor;
+            let addon = ajour
+                .addons
+                .get(&flavor)
+                .and_then(|addons| addons.iter().find(|a| a.primary_folder_id == id));
+            if let Some(addon) = addon {
+                state.repository = addon
+                    .active_repository
+                    .filter(|r| ChangeSourceState::REPOSITORIES.contains(r))
+                    .unwrap_or(Repository::Curse);
+                state.id = addon.repository_id().unwrap_or_default();
+                state.error = None;
+                state.addon_id = Some(id);
+            }
+        }
+        Message::Interaction(Interaction::ChangeSourceIdInput(id)) => {
+            ajour.change_source_state.id = id;
+            ajour.change_source_state.error = None;
+        }
+        Message::Interaction(Interaction::TokenInput(token, value)) => {
+            // The value is a secret, so it isn't logged.
+            log::debug!("Interaction::TokenInput({})", token);
+
+            ajour.config.tokens.set(token, value);
+            network::set_tokens(ajour.config.tokens.clone());
+
+            // Persist the newly updated config.
+            let _ = &ajour.config.save();
+        }
+        Message::Interaction(Interaction::AdvancedOptionInput(option, value)) => {
+            let state = &mut ajour.advanced_settings_state;
+            state.is_saved = false;
+
+            if let Some((_, input, _)) = state.inputs.iter_mut().find(|(o, ..)| *o == option) {
+                *input = value;
+            }
+        }
+        Message::Interaction(Interaction::SaveAdvancedOptions) => {
+            log::debug!("Interaction::SaveAdvancedOptions");
+
+            // Options are set on a copy, so nothing is saved if any is invalid.
+            let mut config = ajour.config.clone();
+            let result = ajour
+                .advanced_settings_state
+                .inputs
+                .iter()
+                .try_for_each(|(option, value, _)| config.set_text_option(*option, value));
+
+            if let Err(error) = result {
+                ajour.advanced_settings_state.error = Some(error.to_string());
+                return Ok(Command::none());
+            }
+
+            network::set_proxy(config.proxy.as_deref())?;
+            network::set_max_retries(config.max_retries.unwrap_or(network::DEFAULT_MAX_RETRIES));
+            parse::set_parse_threads(config.parse_threads.unwrap_or_default());
+
+            ajour.config = config;
+            ajour.config.save()?;
+
+            ajour.advanced_settings_state.reset(&ajour.config);
+            ajour.advanced_settings_state.is_saved = true;
+        }
+        Message::Interaction(Interaction::OpenConfigDirectory) => {
+            log::debug!("Interaction::OpenConfigDirectory");
+
+            return Ok(Command::perform(
+                async {
+                    let _ = opener::open(config_dir());
+                },
+                Message::None,
+            ));
+        }
+        Message::ChangeSourceRepositorySelected(repository) => {
+            log::debug!("Message::ChangeSourceRepositorySelected({})", repository);
+
+            ajour.change_source_state.repository = repository;
+            ajour.change_source_state.error = None;
+        }
+        Message::Interaction(Interaction::SaveSource(id)) => {
+            log::debug!("Interaction::SaveSource({})", &id);
+
+            let state = &mut ajour.change_source_state;
+            let repository = state.repository;
+            let project_id = state.id.trim().to_string();
+
+            if project_id.is_empty() {
+                return Ok(Command::none());
+            }
+            if repository == Repository::Curse && project_id.parse::<u32>().is_err() {
+                state.error = Some(localized_string("curse-ids-are-numbers"));
+                return Ok(Command::none());
+            }
+
+            let flavor = ajour.config.wow.flavor;
+            let folder_ids = match ajour
+                .addons
+                .get(&flavor)
+                .and_then(|addons| addons.iter().find(|a| a.primary_folder_id == id))
+            {
+                Some(addon) => addon
+                    .folders
+                    .iter()
+                    .map(|f| f.id.clone())
+                    .collect::<Vec<_>>(),
+                None => return Ok(Command::none()),
+            };
+
+            // Every folder is linked, since the folders of a wrongly matched addon
+            // might not be grouped right either.
+            let mut links = RepositoryLinks::load_or_default()?;
+            for folder_id in folder_ids {
+                links.insert(
+                    flavor,
+                    folder_id,
+                    RepositoryLink {
+                        repository,
+                        id: project_id.clone(),
+                        is_override: true,
+                    },
+                );
             }
+            links.save()?;
+
+            ajour.change_source_state = Default::default();
+
+            // Parse the addons again, so the addon is matched against the new source.
+            return handle_message(ajour, Message::Interaction(Interaction::Refresh));
+        }
+        Message::Interaction(Interaction::ResetSource(id)) => {
+            log::debug!("Interaction::ResetSource({})", &id);
+
+            let flavor = ajour.config.wow.flavor;
+            let folder_ids = match ajour
+                .addons
+                .get(&flavor)
+                .and_then(|addons| addons.iter().find(|a| a.primary_folder_id == id))
+            {
+                Some(addon) => addon
+                    .folders
+                    .iter()
+                    .map(|f| f.id.clone())
+                    .collect::<Vec<_>>(),
+                None => return Ok(Command::none()),
+            };
+
+            let mut links = RepositoryLinks::load_or_default()?;
+            links.remove_overrides(flavor, &folder_ids);
+            links.save()?;
+
+            ajour.change_source_state = Default::default();
+
+            // Parse the addons again, so the addon is matched automatically.
+            return handle_message(ajour, Message::Interaction(Interaction::Refresh));
+        }
+        Message::Interaction(Interaction::CancelDelete) => {
+            log::debug!("Interaction::CancelDelete");
+
+            ajour.delete_confirmation = None;
+            ajour.selection_state.is_delete_confirmation = false;
+        }
+        Message::Interaction(Interaction::ToggleDeleteSavedVariables(is_checked)) => {
+            log::debug!("Interaction::ToggleDeleteSavedVariables({})", is_checked);
+
+            ajour.delete_saved_variables = is_checked;
         }
         Message::Interaction(Interaction::Update(id)) => {
             log::debug!("Interaction::Update({})", &id);
@@ -507,6 +1053,12 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
             ajour.expanded_type = ExpandType::None;
 
             let flavor = ajour.config.wow.flavor;
+            if defer_while_game_running(ajour, &[flavor], Interaction::Update(id.clone())) {
+                return Ok(Command::none());
+            }
+
+            start_update_batch(ajour);
+
             let addons = ajour.addons.entry(flavor).or_default();
             let to_directory = ajour
                 .config
@@ -519,6 +1071,7 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
                         perform_download_addon(
                             DownloadReason::Update,
                             ajour.shared_client.clone(),
+                            ajour.download_manager.queue((flavor, id.clone())),
                             flavor,
                             addon.clone(),
                             to_directory,
@@ -528,97 +1081,406 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
                 }
             }
         }
-        Message::Interaction(Interaction::UpdateAll) => {
-            log::debug!("Interaction::UpdateAll");
-
-            // Close settings if shown.
-            ajour.is_showing_settings = false;
-            // Close details if shown.
-            ajour.expanded_type = ExpandType::None;
+        Message::Interaction(Interaction::UpdateSelected) => {
+            log::debug!("Interaction::UpdateSelected");
 
-            // Update all updatable addons, expect ignored.
-            let flavor = ajour.config.wow.flavor;
-            let ignored_ids = ajour.config.addons.ignored.entry(flavor).or_default();
-            let mut addons: Vec<_> = ajour
-                .addons
-                .entry(flavor)
-                .or_default()
-                .iter_mut()
-                .filter(|a| !ignored_ids.iter().any(|i| i == &a.primary_folder_id))
-                .collect();
+            let ids = selected_addon_ids(ajour, |a| a.state == AddonState::Updatable);
+            ajour.selection_state.clear();
 
             let mut commands = vec![];
-            for addon in addons.iter_mut() {
-                if addon.state == AddonState::Updatable {
-                    if let Some(to_directory) =
-                        ajour.config.get_download_directory_for_flavor(flavor)
-                    {
-                        addon.state = AddonState::Downloading;
-                        let addon = addon.clone();
-                        commands.push(Command::perform(
-                            perform_download_addon(
-                                DownloadReason::Update,
-                                ajour.shared_client.clone(),
-                                flavor,
-                                addon,
-                                to_directory,
-                            ),
-                            Message::DownloadedAddon,
-                        ))
-                    }
-                }
+            for id in ids {
+                commands.push(handle_message(
+                    ajour,
+                    Message::Interaction(Interaction::Update(id)),
+                )?);
             }
+
             return Ok(Command::batch(commands));
         }
-        Message::ParsedAddons((flavor, result)) => {
-            // if our selected flavor returns (either ok or error) - we change to idle.
-            if flavor == ajour.config.wow.flavor {
-                ajour.state = AjourState::Idle;
+        Message::Interaction(Interaction::IgnoreSelected) => {
+            log::debug!("Interaction::IgnoreSelected");
+
+            // Symlinked addons can't be ignored, same as in the details.
+            let ids = selected_addon_ids(ajour, |a| {
+                !matches!(a.state, AddonState::Ignored | AddonState::Symlinked)
+            });
+            ajour.selection_state.clear();
+
+            for id in ids {
+                handle_message(ajour, Message::Interaction(Interaction::Ignore(id)))?;
             }
+        }
+        Message::Interaction(Interaction::DeleteSelected) => {
+            log::debug!("Interaction::DeleteSelected");
 
-            if let Ok(addons) = result {
-                log::debug!("Message::ParsedAddons({}, {} addons)", flavor, addons.len(),);
+            // Close settings if shown.
+            ajour.is_showing_settings = false;
 
-                // Ignored addon ids.
-                let ignored_ids = ajour.config.addons.ignored.entry(flavor).or_default();
+            let flavor = ajour.config.wow.flavor;
 
-                // Check if addons is updatable.
-                let release_channels = ajour
-                    .config
-                    .addons
-                    .release_channels
-                    .entry(flavor)
-                    .or_default();
-                let mut addons = addons
-                    .into_iter()
-                    .map(|mut a| {
-                        // Check if we have saved release channel for addon.
-                        if let Some(release_channel) = release_channels.get(&a.primary_folder_id) {
-                            a.release_channel = *release_channel;
-                        } else {
-                            // Else we try to determine the release_channel based of installed version.
-                            for (release_channel, package) in a.remote_packages() {
-                                if package.file_id == a.file_id() {
-                                    a.release_channel = release_channel.to_owned();
-                                    break;
-                                }
-                            }
+            // Ask for confirmation first, pressing delete again removes the addons.
+            if !ajour.selection_state.is_delete_confirmation {
+                ajour.selection_state.is_delete_confirmation = true;
+
+                return Ok(Command::none());
+            }
+
+            // Close details if shown.
+            ajour.expanded_type = ExpandType::None;
+
+            // Each addon is held back on its own, since the selection can change
+            // until they run.
+            let blocked_by = running_game_flavors(ajour, &[flavor]);
+            if !blocked_by.is_empty() {
+                for id in selected_addon_ids(ajour, |_| true) {
+                    ajour.game_state.deferred.push(DeferredAction {
+                        flavor,
+                        blocked_by: blocked_by.clone(),
+                        interaction: Interaction::Delete(id),
+                    });
+                }
+                ajour.selection_state.clear();
+
+                return Ok(Command::none());
+            }
+
+            let addons = ajour.addons.entry(flavor).or_default();
+            let selection_state = &mut ajour.selection_state;
+
+            let (selected, remaining): (Vec<Addon>, Vec<Addon>) = addons
+                .drain(..)
+                .partition(|a| selection_state.is_selected(&a.primary_folder_id));
+            selection_state.clear();
+
+            // Folders shared with the addons which aren't deleted, eg. libraries, are kept.
+            let folders: Vec<_> = selected
+                .iter()
+                .flat_map(|a| a.owned_folders(&remaining))
+                .cloned()
+                .collect();
+
+            // Remove from local state.
+            *addons = remaining;
+
+            // Delete addon(s) from disk.
+            delete_folders(ajour, flavor, &folders);
+        }
+        Message::Interaction(Interaction::ClearSelection) => {
+            log::debug!("Interaction::ClearSelection");
+
+            ajour.selection_state.clear();
+        }
+        Message::Interaction(Interaction::PauseDownload(id)) => {
+            log::debug!("Interaction::PauseDownload({})", &id);
+
+            let flavor = ajour.config.wow.flavor;
+            ajour.download_manager.pause(&(flavor, id));
+        }
+        Message::Interaction(Interaction::ResumeDownload(id)) => {
+            log::debug!("Interaction::ResumeDownload({})", &id);
+
+            let flavor = ajour.config.wow.flavor;
+            ajour.download_manager.resume(&(flavor, id));
+        }
+        Message::Interaction(Interaction::CancelDownload(id)) => {
+            log::debug!("Interaction::CancelDownload({})", &id);
+
+            let flavor = ajour.config.wow.flavor;
+            ajour.download_manager.cancel(&(flavor, id.clone()));
+
+            // The addon can be updated again right away, which resumes the download.
+            let addons = ajour.addons.entry(flavor).or_default();
+            if let Some(addon) = addons.iter_mut().find(|a| a.primary_folder_id == id) {
+                if addon.state == AddonState::Downloading {
+                    addon.state = AddonState::Updatable;
+                }
+            }
+        }
+        Message::Interaction(Interaction::Rollback(id)) => {
+            log::debug!("Interaction::Rollback({})", &id);
+
+            // Close settings if shown.
+            ajour.is_showing_settings = false;
+            // Close details if shown.
+            ajour.expanded_type = ExpandType::None;
+
+            let flavor = ajour.config.wow.flavor;
+            let addons = ajour.addons.entry(flavor).or_default();
+            let to_directory = ajour
+                .config
+                .get_addon_directory_for_flavor(&flavor)
+                .expect("Expected a valid path");
+
+            if let Some(addon) = addons.iter_mut().find(|a| a.primary_folder_id == id) {
+                addon.state = AddonState::Unpacking;
+
+                return Ok(Command::perform(
+                    perform_rollback_addon(flavor, addon.clone(), to_directory),
+                    Message::RolledBackAddon,
+                ));
+            }
+        }
+        Message::RolledBackAddon((flavor, id, result)) => {
+            log::debug!(
+                "Message::RolledBackAddon(({}, {}, error: {}))",
+                flavor,
+                &id,
+                result.is_err()
+            );
+
+            let addon_directory = ajour
+                .config
+                .get_addon_directory_for_flavor(&flavor)
+                .expect("Expected a valid path");
+
+            let addons = ajour.addons.entry(flavor).or_default();
+            if let Some(addon) = addons.iter_mut().find(|a| a.primary_folder_id == id) {
+                match result {
+                    Ok((archive, mut folders)) => {
+                        // Remember the version we rolled back from, so it isn't
+                        // offered as an update again.
+                        if let Some(package) = addon.relevant_release_package() {
+                            ajour
+                                .config
+                                .addons
+                                .rolled_back
+                                .entry(flavor)
+                                .or_default()
+                                .insert(addon.primary_folder_id.clone(), package.version.clone());
+
+                            // Persist the newly updated config.
+                            let _ = ajour.config.save();
                         }
 
-                        // Check if addon is updatable based on release channel.
-                        if let Some(package) = a.relevant_release_package() {
-                            if a.is_updatable(package) && a.state != AddonState::Corrupted {
-                                a.state = AddonState::Updatable;
-                            }
+                        if !folders.is_empty() {
+                            folders.sort_by(|a, b| a.id.cmp(&b.id));
+                            addon.folders = folders;
                         }
 
-                        if ignored_ids.iter().any(|ia| &a.primary_folder_id == ia) {
-                            a.state = AddonState::Ignored;
-                        };
+                        addon.set_version(archive.version);
+                        addon.state = AddonState::Fingerprint;
+
+                        let mut commands = vec![];
+
+                        for folder in &addon.folders {
+                            commands.push(Command::perform(
+                                perform_hash_addon(
+                                    DownloadReason::Update,
+                                    addon_directory.clone(),
+                                    folder.id.clone(),
+                                    ajour.fingerprint_collection.clone(),
+                                    flavor,
+                                ),
+                                Message::UpdateFingerprint,
+                            ));
+                        }
+
+                        return Ok(Command::batch(commands));
+                    }
+                    Err(error) => {
+                        log::error!("{}", error);
+
+                        addon.state = AddonState::Ajour(Some(localized_string("error")));
+                        ajour.state = AjourState::Error(error);
+                    }
+                }
+            }
+        }
+        Message::Interaction(Interaction::UndoLastUpdate) => {
+            log::debug!("Interaction::UndoLastUpdate");
+
+            // Close settings if shown.
+            ajour.is_showing_settings = false;
+            // Close details if shown.
+            ajour.expanded_type = ExpandType::None;
+
+            let flavor = ajour.config.wow.flavor;
+            let addon_directory = ajour
+                .config
+                .get_addon_directory_for_flavor(&flavor)
+                .expect("Expected a valid path");
+
+            ajour.state = AjourState::Loading;
+
+            return Ok(Command::perform(
+                perform_undo_last_update(
+                    ajour.fingerprint_collection.clone(),
+                    flavor,
+                    addon_directory,
+                ),
+                Message::UndoneLastUpdate,
+            ));
+        }
+        Message::UndoneLastUpdate((flavor, result)) => {
+            log::debug!(
+                "Message::UndoneLastUpdate(({}, error: {}))",
+                flavor,
+                result.is_err()
+            );
+
+            match result {
+                Ok(snapshots) => {
+                    // Remember the versions we undid, so they aren't offered as
+                    // an update again.
+                    let rolled_back = ajour.config.addons.rolled_back.entry(flavor).or_default();
+                    for snapshot in snapshots {
+                        if let Some(version) = snapshot.updated_to {
+                            rolled_back.insert(snapshot.addon_id, version);
+                        }
+                    }
+
+                    // Persist the newly updated config.
+                    let _ = ajour.config.save();
+
+                    ajour.update_report = None;
+                    if !snapshot::can_undo(flavor) {
+                        ajour.undoable_flavors.remove(&flavor);
+                    }
 
-                        a
-                    })
-                    .collect::<Vec<Addon>>();
+                    return handle_message(ajour, Message::Interaction(Interaction::Refresh));
+                }
+                Err(error) => {
+                    log::error!("{}", error);
+
+                    ajour.state = AjourState::Error(error);
+                }
+            }
+        }
+        Message::Interaction(Interaction::VerifyAddons) => {
+            log::debug!("Interaction::VerifyAddons");
+
+            // Close settings if shown.
+            ajour.is_showing_settings = false;
+            // Close details if shown.
+            ajour.expanded_type = ExpandType::None;
+
+            let flavor = ajour.config.wow.flavor;
+            let addon_directory = ajour
+                .config
+                .get_addon_directory_for_flavor(&flavor)
+                .expect("Expected a valid path");
+
+            // Ignored and symlinked addons are left as they are.
+            let addons = ajour
+                .addons
+                .entry(flavor)
+                .or_default()
+                .iter()
+                .filter(|a| {
+                    matches!(
+                        a.state,
+                        AddonState::Ajour(_)
+                            | AddonState::Updatable
+                            | AddonState::Pinned
+                            | AddonState::Corrupted
+                    )
+                })
+                .cloned()
+                .collect();
+
+            ajour.state = AjourState::Loading;
+
+            return Ok(Command::perform(
+                perform_verify_addons(flavor, addons, addon_directory),
+                Message::VerifiedAddons,
+            ));
+        }
+        Message::VerifiedAddons((flavor, verifications)) => {
+            log::debug!(
+                "Message::VerifiedAddons(({}, {} addons))",
+                flavor,
+                verifications.len()
+            );
+
+            let addons = ajour.addons.entry(flavor).or_default();
+            let mut mismatched = 0;
+
+            for (id, verification) in verifications {
+                if let Verification::Mismatched(files) = verification {
+                    if let Some(addon) = addons.iter_mut().find(|a| a.primary_folder_id == id) {
+                        log::info!(
+                            "{} doesn't match its release package: {}",
+                            addon.title(),
+                            files.join(", ")
+                        );
+
+                        // Offers to repair the addon, which reinstalls it.
+                        addon.state = AddonState::Corrupted;
+                        mismatched += 1;
+                    }
+                }
+            }
+
+            log::info!("{} - {} addons need to be repaired", flavor, mismatched);
+
+            ajour.state = AjourState::Idle;
+        }
+        Message::Interaction(Interaction::UpdateAll) => {
+            log::debug!("Interaction::UpdateAll");
+
+            // Close settings if shown.
+            ajour.is_showing_settings = false;
+            // Close details if shown.
+            ajour.expanded_type = ExpandType::None;
+            // Close the update preview, the addons are updated as it showed.
+            ajour.update_preview_state.is_shown = false;
+
+            let flavors = if ajour.config.update_all_flavors {
+                ajour.valid_flavors.clone()
+            } else {
+                vec![ajour.config.wow.flavor]
+            };
+            if defer_while_game_running(ajour, &flavors, Interaction::UpdateAll) {
+                return Ok(Command::none());
+            }
+
+            // Backup first if chosen, updating will continue once the backup has finished.
+            if ajour.config.backups.before_update_all
+                && !ajour.backup_state.backing_up
+                && ajour.config.backup_directory.is_some()
+            {
+                ajour.backup_state.update_all_pending = true;
+
+                return Ok(start_backup(ajour));
+            }
+
+            return Ok(update_all_addons(ajour));
+        }
+        Message::ParsedAddons((flavor, result)) => {
+            // if our selected flavor returns (either ok or error) - we change to idle.
+            if flavor == ajour.config.wow.flavor {
+                ajour.state = AjourState::Idle;
+            }
+
+            if let Ok(mut addons) = result {
+                log::debug!("Message::ParsedAddons({}, {} addons)", flavor, addons.len(),);
+
+                if snapshot::can_undo(flavor) {
+                    ajour.undoable_flavors.insert(flavor);
+                } else {
+                    ajour.undoable_flavors.remove(&flavor);
+                }
+
+                // Apply release channels and ignored addons from the config, and
+                // check if addons are updatable.
+                apply_addon_preferences(&mut addons, &ajour.config.addons, flavor);
+
+                // Updates started on the addons shown from the last parse carry over.
+                if let Some(previous) = ajour.addons.get(&flavor) {
+                    for addon in addons.iter_mut() {
+                        if let Some(previous) = previous.iter().find(|a| {
+                            a.primary_folder_id == addon.primary_folder_id
+                                && matches!(
+                                    a.state,
+                                    AddonState::Downloading
+                                        | AddonState::Unpacking
+                                        | AddonState::Fingerprint
+                                )
+                        }) {
+                            addon.state = previous.state.clone();
+                        }
+                    }
+                }
 
                 // Sort the addons.
                 sort_addons(&mut addons, SortDirection::Desc, ColumnKey::Status);
@@ -632,13 +1494,36 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
 
                 // Insert the addons into the HashMap.
                 ajour.addons.insert(flavor, addons);
-            } else {
-                log::error!(
-                    "Message::ParsedAddons({}) - {}",
-                    flavor,
-                    result.err().unwrap(),
+            } else if let Err(error) = result {
+                log::error!("Message::ParsedAddons({}) - {}", flavor, error);
+
+                ajour.toast_state.error(
+                    localized_string_with(
+                        "couldnt-load-addons",
+                        &[("flavor", &flavor), ("error", &error.user_message())],
+                    ),
+                    Some(Interaction::Refresh),
                 );
             }
+
+            // Install what a scheduled refresh found once the shown addons are parsed.
+            // Flavors still being parsed are updated on the next run.
+            if ajour.scheduled_update_state.pending && flavor == ajour.config.wow.flavor {
+                ajour.scheduled_update_state.pending = false;
+
+                let any_updatable = ajour
+                    .addons
+                    .iter()
+                    .filter(|(f, _)| ajour.config.update_all_flavors || **f == flavor)
+                    .flat_map(|(_, addons)| addons)
+                    .any(|a| a.state == AddonState::Updatable);
+
+                if any_updatable {
+                    log::debug!("Message::ParsedAddons - installing scheduled updates");
+
+                    return handle_message(ajour, Message::Interaction(Interaction::UpdateAll));
+                }
+            }
         }
         Message::DownloadedAddon((reason, flavor, id, result)) => {
             log::debug!(
@@ -691,9 +1576,44 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
                         }
                     }
                     Err(error) => {
-                        log::error!("{}", error);
+                        // The addon is no longer downloading if the download was cancelled.
+                        if addon.state == AddonState::Downloading {
+                            log::error!("failed to download {}: {}", addon.title(), error);
 
-                        ajour.state = AjourState::Error(error);
+                            let retry = if reason == DownloadReason::Update {
+                                Some(Interaction::Update(id.clone()))
+                            } else {
+                                None
+                            };
+                            ajour.toast_state.error(
+                                localized_string_with(
+                                    "couldnt-download",
+                                    &[("title", &addon.title()), ("error", &error.user_message())],
+                                ),
+                                retry,
+                            );
+
+                            finish_update_report(
+                                ajour.update_report.as_mut(),
+                                &ajour.config,
+                                flavor,
+                                addon,
+                                Some(&error),
+                            );
+
+                            ajour.state = AjourState::Error(error);
+                            addon.state = AddonState::Ajour(Some(localized_string("error")));
+                        } else {
+                            log::debug!("download of {} was cancelled", &id);
+
+                            finish_update_report(
+                                ajour.update_report.as_mut(),
+                                &ajour.config,
+                                flavor,
+                                addon,
+                                Some(&ClientError::Cancelled),
+                            );
+                        }
 
                         // Update catalog status for addon
                         if reason == DownloadReason::Install {
@@ -762,6 +1682,9 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
                                 //TODO: Can this crash? What do we do in that case.
                                 folders.get(0).map(|f| f.id.clone()).unwrap()
                             };
+                            if let Some(report) = ajour.update_report.as_mut() {
+                                report.rename(flavor, &addon.primary_folder_id, &primary_folder_id);
+                            }
                             addon.primary_folder_id = primary_folder_id;
                             addon.folders = folders;
                         }
@@ -776,18 +1699,69 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
                             );
                         }
 
+                        // An update replaces a rolled back version, so stop skipping updates.
+                        if reason == DownloadReason::Update {
+                            let rolled_back =
+                                ajour.config.addons.rolled_back.entry(flavor).or_default();
+
+                            if rolled_back.remove(&addon.primary_folder_id).is_some() {
+                                // Persist the newly updated config.
+                                let _ = ajour.config.save();
+                            }
+                        }
+
                         addon.state = AddonState::Fingerprint;
 
+                        let old_version = addon.version().map(str::to_string);
                         let mut version = None;
                         if let Some(package) = addon.relevant_release_package() {
                             version = Some(package.version.clone());
                         }
-                        if let Some(version) = version {
+                        if let Some(version) = version.clone() {
                             addon.set_version(version);
                         }
 
+                        // The unpacking recorded the same dates on disk.
+                        let now = SystemTime::now();
+                        if reason == DownloadReason::Install {
+                            addon.install_dates.installed_at = Some(now);
+                        }
+                        addon.install_dates.updated_at = Some(now);
+
                         let mut commands = vec![];
 
+                        // Keeps the update for "What's New", along with its changelog.
+                        if reason == DownloadReason::Update {
+                            spawn_hook(
+                                &ajour.config.hooks,
+                                HookEvent::AddonUpdated,
+                                json!({
+                                    "id": addon.primary_folder_id,
+                                    "title": addon.title(),
+                                    "flavor": flavor,
+                                    "old_version": old_version,
+                                    "new_version": version,
+                                }),
+                            );
+
+                            let history = &mut ajour.whats_new_state.history;
+                            history.record(UpdateRecord {
+                                flavor,
+                                id: addon.primary_folder_id.clone(),
+                                title: addon.title().to_string(),
+                                old_version,
+                                new_version: version,
+                                changelog: None,
+                                updated_at: now,
+                            });
+                            let _ = history.save();
+
+                            commands.push(Command::perform(
+                                perform_fetch_update_changelog(flavor, addon.clone()),
+                                Message::FetchedUpdateChangelog,
+                            ));
+                        }
+
                         for folder in &addon.folders {
                             commands.push(Command::perform(
                                 perform_hash_addon(
@@ -807,8 +1781,24 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
                         return Ok(Command::batch(commands));
                     }
                     Err(err) => {
+                        finish_update_report(
+                            ajour.update_report.as_mut(),
+                            &ajour.config,
+                            flavor,
+                            addon,
+                            Some(&err),
+                        );
+
+                        ajour.toast_state.error(
+                            localized_string_with(
+                                "couldnt-unpack",
+                                &[("title", &addon.title()), ("error", &err.user_message())],
+                            ),
+                            None,
+                        );
+
                         ajour.state = AjourState::Error(err);
-                        addon.state = AddonState::Ajour(Some("Error".to_owned()));
+                        addon.state = AddonState::Ajour(Some(localized_string("error")));
 
                         // Update catalog status for addon
                         if reason == DownloadReason::Install {
@@ -842,10 +1832,33 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
 
             let addons = ajour.addons.entry(flavor).or_default();
             if let Some(addon) = addons.iter_mut().find(|a| a.primary_folder_id == id) {
+                finish_update_report(
+                    ajour.update_report.as_mut(),
+                    &ajour.config,
+                    flavor,
+                    addon,
+                    result.as_ref().err(),
+                );
+
                 if result.is_ok() {
-                    addon.state = AddonState::Ajour(Some("Completed".to_owned()));
+                    addon.state = AddonState::Ajour(Some(localized_string("completed")));
 
-                    // Update catalog status for addon
+                    // "Update All" sums up its updates in its report instead.
+                    if ajour.update_report.is_none() {
+                        let key = match reason {
+                            DownloadReason::Install => "installed-addon",
+                            _ => "updated-addon",
+                        };
+                        ajour
+                            .toast_state
+                            .success(localized_string_with(key, &[("title", &addon.title())]));
+                    }
+
+                    if reason == DownloadReason::Update && snapshot::can_undo(flavor) {
+                        ajour.undoable_flavors.insert(flavor);
+                    }
+
+                    // Update catalog status for addon
                     if reason == DownloadReason::Install {
                         update_catalog_install_status(
                             &mut ajour.catalog_install_statuses,
@@ -855,7 +1868,7 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
                         );
                     }
                 } else {
-                    addon.state = AddonState::Ajour(Some("Error".to_owned()));
+                    addon.state = AddonState::Ajour(Some(localized_string("error")));
 
                     // Update catalog status for addon
                     if reason == DownloadReason::Install {
@@ -876,10 +1889,74 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
                 addons.retain(|a| a.primary_folder_id != id)
             }
         }
-        Message::NeedsUpdate(Ok(newer_version)) => {
-            log::debug!("Message::NeedsUpdate({:?})", &newer_version);
+        Message::NeedsUpdate(Ok(release)) => {
+            log::debug!("Message::NeedsUpdate({:?})", &release);
+
+            // A release the user chose to skip isn't offered.
+            let release = release.filter(|r| {
+                ajour.config.skipped_self_update.as_deref() != Some(r.version.as_str())
+            });
+
+            // Checked again on refresh, but only mentioned once.
+            if let Some(release) = release
+                .as_ref()
+                .filter(|r| Some(*r) != ajour.needs_update.as_ref())
+            {
+                let text = if release.is_prerelease() {
+                    localized_string_with(
+                        "ajour-prerelease-available",
+                        &[("version", &release.version), ("channel", &release.channel)],
+                    )
+                } else {
+                    localized_string_with("ajour-available", &[("version", &release.version)])
+                };
+
+                ajour.toast_state.push(
+                    ToastKind::Info,
+                    text,
+                    Some((
+                        localized_string("release-notes"),
+                        Interaction::ToggleReleaseNotes,
+                    )),
+                );
+            }
+
+            if release.is_none() {
+                ajour.release_notes_state.is_shown = false;
+            }
+
+            ajour.needs_update = release;
+        }
+        Message::Interaction(Interaction::ToggleReleaseNotes) => {
+            log::debug!("Interaction::ToggleReleaseNotes");
+
+            let state = &mut ajour.release_notes_state;
+            state.is_shown = !state.is_shown && ajour.needs_update.is_some();
+        }
+        Message::Interaction(Interaction::SkipRelease) => {
+            log::debug!("Interaction::SkipRelease");
+
+            if let Some(release) = ajour.needs_update.take() {
+                ajour.config.skipped_self_update = Some(release.version);
+
+                // Persist the newly updated config.
+                let _ = ajour.config.save();
+            }
+
+            ajour.release_notes_state.is_shown = false;
+        }
+        Message::SelfUpdateChannelSelected(channel) => {
+            log::debug!("Message::SelfUpdateChannelSelected({})", channel);
+
+            ajour.config.self_update_channel = channel;
+
+            // Persist the newly updated config.
+            let _ = ajour.config.save();
 
-            ajour.needs_update = newer_version;
+            return Ok(Command::perform(
+                needs_update(VERSION, channel),
+                Message::NeedsUpdate,
+            ));
         }
         Message::Interaction(Interaction::SortColumn(column_key)) => {
             // Close settings if shown.
@@ -907,257 +1984,1306 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
                 sort_direction = SortDirection::Desc;
             }
 
-            log::debug!(
-                "Interaction::SortColumn({:?}, {:?})",
-                column_key,
-                sort_direction
-            );
+            log::debug!(
+                "Interaction::SortColumn({:?}, {:?})",
+                column_key,
+                sort_direction
+            );
+
+            let flavor = ajour.config.wow.flavor;
+            let mut addons = ajour.addons.entry(flavor).or_default();
+
+            sort_addons(&mut addons, sort_direction, column_key);
+
+            ajour.header_state.previous_sort_direction = Some(sort_direction);
+            ajour.header_state.previous_column_key = Some(column_key);
+        }
+        Message::Interaction(Interaction::SortCatalogColumn(column_key)) => {
+            // Close settings if shown.
+            ajour.is_showing_settings = false;
+
+            // First time clicking a column should sort it in Ascending order, otherwise
+            // flip the sort direction.
+            let mut sort_direction = SortDirection::Asc;
+
+            if let Some(previous_column_key) = ajour.catalog_header_state.previous_column_key {
+                if column_key == previous_column_key {
+                    if let Some(previous_sort_direction) =
+                        ajour.catalog_header_state.previous_sort_direction
+                    {
+                        sort_direction = previous_sort_direction.toggle()
+                    }
+                }
+            }
+
+            // Exception would be first time ever sorting and sorting by title.
+            // Since its already sorting in Asc by default, we should sort Desc.
+            if ajour.catalog_header_state.previous_column_key.is_none()
+                && column_key == CatalogColumnKey::Title
+            {
+                sort_direction = SortDirection::Desc;
+            }
+
+            log::debug!(
+                "Interaction::SortCatalogColumn({:?}, {:?})",
+                column_key,
+                sort_direction
+            );
+
+            ajour.catalog_header_state.previous_sort_direction = Some(sort_direction);
+            ajour.catalog_header_state.previous_column_key = Some(column_key);
+
+            query_and_sort_catalog(ajour);
+        }
+        Message::ReleaseChannelSelected((id, release_channel)) => {
+            log::debug!(
+                "Message::ReleaseChannelSelected({}, {:?})",
+                &id,
+                release_channel
+            );
+
+            let flavor = ajour.config.wow.flavor;
+            let addons = ajour.addons.entry(flavor).or_default();
+            if let Some(addon) = addons.iter_mut().find(|a| a.primary_folder_id == id) {
+                addon.release_channel = release_channel;
+
+                // Check if addon is updatable. Pinned addons stay pinned on any channel.
+                if let Some(package) = addon
+                    .relevant_release_package()
+                    .filter(|_| addon.state != AddonState::Pinned)
+                {
+                    if addon.is_updatable(package) {
+                        addon.state = AddonState::Updatable;
+                    } else {
+                        addon.state = AddonState::Ajour(None);
+                    }
+                }
+
+                // Update config with the newly changed release channel.
+                ajour
+                    .config
+                    .addons
+                    .release_channels
+                    .entry(flavor)
+                    .or_default()
+                    .insert(addon.primary_folder_id.clone(), release_channel);
+
+                // Persist the newly updated config.
+                let _ = &ajour.config.save();
+            }
+        }
+        Message::SourcePrioritySelected(repository) => {
+            log::debug!("Message::SourcePrioritySelected({})", repository);
+
+            let mut source_priority = vec![repository];
+            source_priority.extend(
+                ChangeSourceState::REPOSITORIES
+                    .iter()
+                    .copied()
+                    .filter(|r| *r != repository),
+            );
+            ajour.config.addons.source_priority = source_priority;
+
+            // Persist the newly updated config.
+            let _ = &ajour.config.save();
+
+            // Parse the addons again, so they are matched against the preferred source.
+            return handle_message(ajour, Message::Interaction(Interaction::Refresh));
+        }
+        Message::PreferredSourceSelected((id, repository)) => {
+            log::debug!("Message::PreferredSourceSelected({}, {})", &id, repository);
+
+            let flavor = ajour.config.wow.flavor;
+            let folder_ids = match ajour
+                .addons
+                .get(&flavor)
+                .and_then(|addons| addons.iter().find(|a| a.primary_folder_id == id))
+            {
+                Some(addon) => addon
+                    .folders
+                    .iter()
+                    .map(|f| f.id.clone())
+                    .collect::<Vec<_>>(),
+                None => return Ok(Command::none()),
+            };
+
+            let preferred_sources = ajour
+                .config
+                .addons
+                .preferred_sources
+                .entry(flavor)
+                .or_default();
+            for folder_id in folder_ids.iter() {
+                preferred_sources.remove(folder_id);
+            }
+
+            // The addon only needs an override if it differs from the global order.
+            let default_source = ajour
+                .config
+                .addons
+                .source_priority(&flavor)
+                .preferred(folder_ids.iter().map(String::as_str));
+            if repository != default_source {
+                ajour
+                    .config
+                    .addons
+                    .preferred_sources
+                    .entry(flavor)
+                    .or_default()
+                    .insert(id, repository);
+            }
+
+            // Persist the newly updated config.
+            let _ = &ajour.config.save();
+
+            // Parse the addons again, so the addon is matched against the preferred source.
+            return handle_message(ajour, Message::Interaction(Interaction::Refresh));
+        }
+        Message::SelectionReleaseChannelSelected(release_channel) => {
+            log::debug!(
+                "Message::SelectionReleaseChannelSelected({:?})",
+                release_channel
+            );
+
+            // Only addons from a repository have release channels.
+            let ids = selected_addon_ids(ajour, |a| a.active_repository.is_some());
+            for id in ids {
+                handle_message(
+                    ajour,
+                    Message::ReleaseChannelSelected((id, release_channel)),
+                )?;
+            }
+        }
+        Message::ThemeSelected(theme_name) => {
+            log::debug!("Message::ThemeSelected({:?})", &theme_name);
+
+            ajour.theme_state.current_theme_name = theme_name.clone();
+
+            ajour.config.theme = Some(theme_name);
+            let _ = ajour.config.save();
+        }
+        Message::LanguageSelected(language) => {
+            log::debug!("Message::LanguageSelected({:?})", language);
+
+            localization::set_language(language);
+
+            ajour.config.language = Some(language);
+
+            // Persist the newly updated config.
+            let _ = ajour.config.save();
+        }
+        Message::ThemesLoaded(mut themes) => {
+            log::debug!("Message::ThemesLoaded({} themes)", themes.len());
+
+            themes.sort();
+
+            // Replace the user themes loaded before, as they may have been edited
+            // or removed since.
+            let theme_state = &mut ajour.theme_state;
+            let user_theme_names = std::mem::take(&mut theme_state.user_theme_names);
+            theme_state
+                .themes
+                .retain(|(name, _)| !user_theme_names.contains(name));
+
+            for theme in themes {
+                // Built-in themes can't be replaced.
+                if theme_state
+                    .themes
+                    .iter()
+                    .any(|(name, _)| name == &theme.name)
+                {
+                    log::warn!(
+                        "skipped user theme {}, as it has a built-in name",
+                        &theme.name
+                    );
+                    continue;
+                }
+
+                theme_state.user_theme_names.push(theme.name.clone());
+                theme_state.themes.push((theme.name.clone(), theme));
+            }
+
+            theme_state.user_themes_modified = user_themes_modified();
+        }
+        Message::Interaction(Interaction::ThemeImportUrlInput(url)) => {
+            ajour.theme_state.import_url = url;
+        }
+        Message::Interaction(Interaction::ImportTheme) => {
+            let url = ajour.theme_state.import_url.trim().to_string();
+            log::debug!("Interaction::ImportTheme({})", &url);
+
+            ajour.theme_state.importing = true;
+            ajour.theme_state.status = None;
+
+            return Ok(Command::perform(
+                import_theme(ajour.shared_client.clone(), url),
+                Message::ThemeImported,
+            ));
+        }
+        Message::ThemeImported(result) => {
+            let state = &mut ajour.theme_state;
+            state.importing = false;
+
+            match result {
+                Ok(theme) => {
+                    log::debug!("Message::ThemeImported({})", &theme.name);
+
+                    state.status = Some(localized_string_with(
+                        "theme-imported",
+                        &[("name", &theme.name)],
+                    ));
+                    state.import_url.clear();
+
+                    // Switch to the imported theme, once it's loaded with the others.
+                    state.current_theme_name = theme.name.clone();
+                    ajour.config.theme = Some(theme.name);
+
+                    // Persist the newly updated config.
+                    let _ = ajour.config.save();
+
+                    return Ok(Command::perform(load_user_themes(), Message::ThemesLoaded));
+                }
+                Err(error) => {
+                    log::error!("{}", error);
+
+                    state.status = Some(error.user_message());
+                }
+            }
+        }
+        Message::Interaction(Interaction::ExportTheme) => {
+            log::debug!("Interaction::ExportTheme");
+
+            let state = &mut ajour.theme_state;
+            let theme = state
+                .themes
+                .iter()
+                .find(|(name, _)| name == &state.current_theme_name)
+                .map(|(_, theme)| theme);
+
+            if let Some(theme) = theme {
+                let result = export_theme(theme).and_then(|text| copy_to_clipboard(&text));
+
+                state.status = Some(match result {
+                    Ok(()) => localized_string_with("theme-copied", &[("name", &theme.name)]),
+                    Err(error) => {
+                        log::error!("{}", error);
+
+                        error.user_message()
+                    }
+                });
+            }
+        }
+        Message::Interaction(Interaction::ResizeColumn(column_type, event)) => match event {
+            ResizeEvent::ResizeColumn {
+                left_name,
+                left_width,
+                right_name,
+                right_width,
+            } => match column_type {
+                AjourMode::MyAddons => {
+                    let left_key = ColumnKey::from(left_name.as_str());
+                    let right_key = ColumnKey::from(right_name.as_str());
+
+                    if let Some(column) = ajour
+                        .header_state
+                        .columns
+                        .iter_mut()
+                        .find(|c| c.key == left_key && left_key != ColumnKey::Title)
+                    {
+                        column.width = Length::Units(left_width);
+                    }
+
+                    if let Some(column) = ajour
+                        .header_state
+                        .columns
+                        .iter_mut()
+                        .find(|c| c.key == right_key && right_key != ColumnKey::Title)
+                    {
+                        column.width = Length::Units(right_width);
+                    }
+                }
+                AjourMode::Catalog => {
+                    let left_key = CatalogColumnKey::from(left_name.as_str());
+                    let right_key = CatalogColumnKey::from(right_name.as_str());
+
+                    if let Some(column) =
+                        ajour.catalog_header_state.columns.iter_mut().find(|c| {
+                            c.key == left_key && left_key != CatalogColumnKey::Description
+                        })
+                    {
+                        column.width = Length::Units(left_width);
+                    }
+
+                    if let Some(column) =
+                        ajour.catalog_header_state.columns.iter_mut().find(|c| {
+                            c.key == right_key && right_key != CatalogColumnKey::Description
+                        })
+                    {
+                        column.width = Length::Units(right_width);
+                    }
+                }
+            },
+            ResizeEvent::Finished => {
+                // Persist changes to config
+                save_column_configs(ajour);
+            }
+        },
+        Message::Interaction(Interaction::ScaleUp) => {
+            let prev_scale = ajour.scale_state.scale;
+
+            ajour.scale_state.scale = (prev_scale + 0.1).min(2.0);
+
+            ajour.config.scale = Some(ajour.scale_state.scale);
+            let _ = ajour.config.save();
+
+            log::debug!(
+                "Interaction::ScaleUp({} -> {})",
+                prev_scale,
+                ajour.scale_state.scale
+            );
+        }
+        Message::Interaction(Interaction::ScaleDown) => {
+            let prev_scale = ajour.scale_state.scale;
+
+            ajour.scale_state.scale = (prev_scale - 0.1).max(0.5);
+
+            ajour.config.scale = Some(ajour.scale_state.scale);
+            let _ = ajour.config.save();
+
+            log::debug!(
+                "Interaction::ScaleDown({} -> {})",
+                prev_scale,
+                ajour.scale_state.scale
+            );
+        }
+        Message::Interaction(Interaction::FontSizeUp) => {
+            let prev_font_size = element::font_size();
+
+            element::set_font_size((prev_font_size + 1).min(element::MAX_FONT_SIZE));
+
+            ajour.config.font_size = Some(element::font_size());
+            let _ = ajour.config.save();
+
+            log::debug!(
+                "Interaction::FontSizeUp({} -> {})",
+                prev_font_size,
+                element::font_size()
+            );
+        }
+        Message::Interaction(Interaction::FontSizeDown) => {
+            let prev_font_size = element::font_size();
+
+            element::set_font_size((prev_font_size - 1).max(element::MIN_FONT_SIZE));
+
+            ajour.config.font_size = Some(element::font_size());
+            let _ = ajour.config.save();
+
+            log::debug!(
+                "Interaction::FontSizeDown({} -> {})",
+                prev_font_size,
+                element::font_size()
+            );
+        }
+        Message::UpdateBackupDirectory(path) => {
+            log::debug!("Message::UpdateBackupDirectory({:?})", &path);
+
+            if let Some(path) = path {
+                // Update the backup directory path.
+                ajour.config.backup_directory = Some(path.clone());
+                // Persist the newly updated config.
+                let _ = &ajour.config.save();
+
+                // Check if a latest backup exists in path
+                return Ok(Command::perform(latest_backup(path), Message::LatestBackup));
+            }
+        }
+
+        Message::Interaction(Interaction::Backup) => {
+            log::debug!("Interaction::Backup");
+
+            return Ok(start_backup(ajour));
+        }
+        Message::Tick(now) => {
+            let mut commands = vec![];
+
+            let is_retry_due = ajour.backup_state.last_failed_backup.map_or(true, |at| {
+                chrono::Local::now().naive_local() - at
+                    >= chrono::Duration::hours(BACKUP_RETRY_HOURS)
+            });
+            let is_due = ajour.backup_state.latest_backup_loaded
+                && is_backup_due(&ajour.config.backups, ajour.backup_state.last_backup)
+                && is_retry_due;
+
+            // Run a scheduled backup if it's due and we are able to.
+            if is_due
+                && !ajour.backup_state.backing_up
+                && ajour.config.backup_directory.is_some()
+                && ajour.config.wow.directory.is_some()
+            {
+                log::debug!("Message::Tick - starting scheduled backup");
+
+                commands.push(start_backup(ajour));
+            }
+
+            // Check for updates in the background if notifications are enabled. The
+            // first check happens one interval after launch, since addons were just parsed.
+            if ajour.config.notifications.enabled
+                && !ajour.config.offline
+                && ajour.config.wow.directory.is_some()
+            {
+                let interval = ajour
+                    .config
+                    .notifications
+                    .interval
+                    .duration()
+                    .to_std()
+                    .unwrap_or_default();
+                let last_check = *ajour.notification_state.last_check.get_or_insert(now);
+
+                if !ajour.notification_state.checking && now.duration_since(last_check) >= interval
+                {
+                    log::debug!("Message::Tick - checking for updates");
+
+                    ajour.notification_state.checking = true;
+                    ajour.notification_state.last_check = Some(now);
+
+                    commands.push(Command::perform(
+                        perform_check_for_updates(
+                            ajour.fingerprint_collection.clone(),
+                            ajour.config.clone(),
+                        ),
+                        Message::CheckedForUpdates,
+                    ));
+                }
+            }
+
+            // Refresh and install the updates found if they are scheduled and due,
+            // unless the addons are busy.
+            let now_local = chrono::Local::now().naive_local();
+            let is_due = ajour
+                .config
+                .scheduled_updates
+                .is_due(ajour.scheduled_update_state.started_at, now_local);
+            let is_busy = !matches!(ajour.state, AjourState::Idle)
+                || ajour.scheduled_update_state.pending
+                || ajour.addons.values().flatten().any(|a| {
+                    matches!(
+                        a.state,
+                        AddonState::Downloading | AddonState::Unpacking | AddonState::Fingerprint
+                    )
+                });
+
+            if is_due && !is_busy && !ajour.config.offline && ajour.config.wow.directory.is_some() {
+                log::debug!("Message::Tick - running scheduled updates");
+
+                ajour.config.scheduled_updates.last_run = Some(now_local);
+                ajour.scheduled_update_state.pending = true;
+
+                // Persist the newly updated config.
+                let _ = ajour.config.save();
+
+                commands.push(handle_message(
+                    ajour,
+                    Message::Interaction(Interaction::Refresh),
+                )?);
+            }
+
+            return Ok(Command::batch(commands));
+        }
+        Message::DownloadProgress(_) => {
+            // Nothing to update, the progress is read from the download manager when
+            // the view is drawn.
+        }
+        Message::LoadingProgress(_) => {
+            // The rate limit is read from the network layer when the view is drawn,
+            // and mentioned once in a toast.
+            if let Some((host, _)) = network::rate_limited() {
+                ajour
+                    .toast_state
+                    .info(localized_string_with("rate-limited", &[("host", &host)]));
+            }
+        }
+        Message::ExpireToasts(now) => {
+            ajour.toast_state.expire(now);
+        }
+        Message::Interaction(Interaction::ToggleLogs) => {
+            log::debug!("Interaction::ToggleLogs");
+
+            let state = &mut ajour.logs_state;
+            state.is_shown = !state.is_shown;
+            state.selection = None;
+
+            if state.is_shown {
+                return Ok(Command::perform(read_log(log_file()), Message::LogsRead));
+            }
+        }
+        Message::ReadLogs(_) => {
+            return Ok(Command::perform(read_log(log_file()), Message::LogsRead));
+        }
+        Message::LogsRead(Ok(lines)) => {
+            let state = &mut ajour.logs_state;
+
+            // Older lines are left out as the log grows, which moves the selected ones.
+            if state.lines.first() != lines.first() {
+                state.selection = None;
+            }
+
+            state
+                .line_btn_states
+                .resize_with(lines.len(), Default::default);
+            state.lines = lines;
+        }
+        Message::Interaction(Interaction::GenerateDiagnostics) => {
+            log::debug!("Interaction::GenerateDiagnostics");
+
+            let addons = ajour
+                .addons
+                .iter()
+                .map(|(flavor, addons)| (*flavor, addons.clone()))
+                .collect();
+
+            return Ok(Command::perform(
+                create_bundle(
+                    ajour.config.clone(),
+                    addons,
+                    VERSION.to_string(),
+                    config_dir(),
+                ),
+                Message::DiagnosticsGenerated,
+            ));
+        }
+        Message::DiagnosticsGenerated(Ok(path)) => {
+            log::debug!("Message::DiagnosticsGenerated({:?})", &path);
+
+            ajour.toast_state.push(
+                ToastKind::Success,
+                localized_string_with("saved-diagnostics", &[("path", &path.display())]),
+                Some((
+                    localized_string("open-config-folder"),
+                    Interaction::OpenConfigDirectory,
+                )),
+            );
+        }
+        Message::Interaction(Interaction::LogLevelSelected(level)) => {
+            log::debug!("Interaction::LogLevelSelected({})", level);
+
+            ajour.logs_state.level = level;
+        }
+        Message::Interaction(Interaction::LogModuleInput(module)) => {
+            ajour.logs_state.module = module;
+        }
+        Message::Interaction(Interaction::SelectLogLine(idx)) => {
+            let state = &mut ajour.logs_state;
+
+            state.selection = match state.selection {
+                Some((anchor, _)) if ajour.selection_state.is_shift_pressed => Some((anchor, idx)),
+                Some((anchor, last)) if anchor == idx && last == idx => None,
+                _ => Some((idx, idx)),
+            };
+        }
+        Message::Interaction(Interaction::CopyLogLines) => {
+            log::debug!("Interaction::CopyLogLines");
+
+            let text = ajour.logs_state.copy_text();
+            let count = text.lines().count();
+
+            if let Err(error) = copy_to_clipboard(&text) {
+                ajour.toast_state.error(error.user_message(), None);
+            } else {
+                ajour
+                    .toast_state
+                    .success(localized_string_with("copied-lines", &[("count", &count)]));
+            }
+        }
+        Message::Interaction(Interaction::DismissToast(id)) => {
+            log::debug!("Interaction::DismissToast({})", id);
+
+            ajour.toast_state.dismiss(id);
+        }
+        Message::Interaction(Interaction::ToastAction(id)) => {
+            log::debug!("Interaction::ToastAction({})", id);
+
+            if let Some((_, interaction)) = ajour.toast_state.dismiss(id).and_then(|t| t.action) {
+                return handle_message(ajour, Message::Interaction(interaction));
+            }
+        }
+        Message::LatestBackup(as_of) => {
+            log::debug!("Message::LatestBackup({:?})", &as_of);
+
+            ajour.backup_state.last_backup = as_of;
+            ajour.backup_state.latest_backup_loaded = true;
+
+            refresh_backup_archives(ajour);
+        }
+        Message::BackupFinished(Ok(as_of)) => {
+            log::debug!("Message::BackupFinished({})", as_of.format("%H:%M:%S"));
+
+            ajour.backup_state.backing_up = false;
+            ajour.backup_state.last_backup = Some(as_of);
+            ajour.backup_state.last_failed_backup = None;
+
+            // Remove old backups according to the retention policy.
+            if let Some(dest) = ajour.config.backup_directory.as_ref() {
+                if let Err(error) = prune_backups(dest, &ajour.config.backups) {
+                    log::error!("{}", error);
+                }
+            }
+
+            refresh_backup_archives(ajour);
+
+            // Continue with updating all addons if the backup was made before it.
+            if ajour.backup_state.update_all_pending {
+                ajour.backup_state.update_all_pending = false;
+
+                return Ok(update_all_addons(ajour));
+            }
+        }
+        Message::BackupFinished(Err(error)) => {
+            log::error!("{}", error);
+
+            ajour.backup_state.backing_up = false;
+            ajour.backup_state.last_failed_backup = Some(chrono::Local::now().naive_local());
+
+            // Don't update when the backup before it failed.
+            ajour.backup_state.update_all_pending = false;
+
+            ajour.state = AjourState::Error(error);
+        }
+        Message::InstanceRequest(request) => {
+            log::debug!("Message::InstanceRequest({:?})", &request);
+
+            match request {
+                // The launched instance brings the window forward itself where
+                // it's allowed to.
+                Request::Focus => {}
+                Request::Open(link) => match parse_install_link(&link) {
+                    Some(link) => ajour.install_link_state.link = Some(link),
+                    None => {
+                        ajour.state = AjourState::Error(ClientError::Custom(
+                            localized_string_with("cant-open-link", &[("link", &link)]),
+                        ));
+                    }
+                },
+                Request::UpdateAll => {
+                    let is_busy = !matches!(ajour.state, AjourState::Idle)
+                        || ajour.scheduled_update_state.pending;
+
+                    // Refresh first, the command line expects the latest updates.
+                    if !is_busy && ajour.config.wow.directory.is_some() {
+                        ajour.scheduled_update_state.pending = true;
+
+                        return handle_message(ajour, Message::Interaction(Interaction::Refresh));
+                    }
+                }
+            }
+        }
+        Message::UpdateScheduleSelected(schedule) => {
+            log::debug!("Message::UpdateScheduleSelected({})", schedule);
+
+            ajour.config.scheduled_updates.schedule = schedule;
+
+            // Persist the newly updated config.
+            let _ = ajour.config.save();
+        }
+        Message::BackupScheduleSelected(schedule) => {
+            log::debug!("Message::BackupScheduleSelected({})", schedule);
+
+            ajour.config.backups.schedule = schedule;
+
+            // Persist the newly updated config.
+            let _ = ajour.config.save();
+        }
+        Message::Interaction(Interaction::ToggleBackupBeforeUpdateAll(is_checked)) => {
+            log::debug!("Interaction::ToggleBackupBeforeUpdateAll({})", is_checked);
+
+            ajour.config.backups.before_update_all = is_checked;
+
+            // Persist the newly updated config.
+            let _ = ajour.config.save();
+        }
+        Message::RestoreBackupSelected(archive) => {
+            log::debug!("Message::RestoreBackupSelected({:?})", &archive.path);
+
+            ajour.backup_state.restore_archive = Some(archive);
+            ajour.backup_state.confirming_restore = false;
+        }
+        Message::Interaction(Interaction::RestoreBackup) => {
+            log::debug!("Interaction::RestoreBackup");
+
+            // Restoring replaces folders, so ask for confirmation first.
+            ajour.backup_state.confirming_restore = ajour.backup_state.restore_archive.is_some();
+        }
+        Message::Interaction(Interaction::CancelRestoreBackup) => {
+            log::debug!("Interaction::CancelRestoreBackup");
+
+            ajour.backup_state.confirming_restore = false;
+        }
+        Message::Interaction(Interaction::ConfirmRestoreBackup) => {
+            log::debug!("Interaction::ConfirmRestoreBackup");
+
+            ajour.backup_state.confirming_restore = false;
+
+            if let Some(archive) = ajour.backup_state.restore_archive.clone() {
+                ajour.backup_state.restoring = true;
+
+                let flavor = ajour.config.wow.flavor;
+
+                return Ok(Command::perform(
+                    restore_backup(
+                        archive.path,
+                        flavor,
+                        BackupContent::DEFAULT.to_vec(),
+                        ajour.config.clone(),
+                    ),
+                    Message::RestoreFinished,
+                ));
+            }
+        }
+        Message::RestoreFinished(Ok(snapshot)) => {
+            log::debug!("Message::RestoreFinished({:?})", &snapshot);
+
+            ajour.backup_state.restoring = false;
+
+            // A backup of the replaced folders was made before restoring.
+            if snapshot.is_some() {
+                ajour.backup_state.last_backup = snapshot;
+            }
+
+            refresh_backup_archives(ajour);
+
+            // Reparse the addons, since they have been replaced.
+            return handle_message(ajour, Message::Interaction(Interaction::Refresh));
+        }
+        Message::RestoreFinished(Err(error)) => {
+            log::error!("{}", error);
+
+            ajour.backup_state.restoring = false;
+
+            ajour.state = AjourState::Error(error);
+        }
+        Message::CheckedForUpdates(Ok(updates)) => {
+            log::debug!("Message::CheckedForUpdates({} updates)", updates.len());
+
+            ajour.notification_state.checking = false;
+
+            let mut new_updates = vec![];
+
+            for (flavor, addon) in updates {
+                let version = addon
+                    .relevant_release_package()
+                    .map(|p| p.version.clone())
+                    .unwrap_or_default();
+
+                let addons = ajour.addons.entry(flavor).or_default();
+                let existing = addons
+                    .iter_mut()
+                    .find(|a| a.primary_folder_id == addon.primary_folder_id);
+
+                // Versions already shown as updatable don't need a notification.
+                let is_known = existing.as_ref().map_or(false, |a| {
+                    a.state == AddonState::Updatable
+                        && a.relevant_release_package().map(|p| &p.version) == Some(&version)
+                });
+
+                // Show the new version, unless the addon is being updated right now.
+                if let Some(existing) = existing {
+                    if matches!(existing.state, AddonState::Ajour(_) | AddonState::Updatable) {
+                        *existing = addon.clone();
+                    }
+                }
+
+                let key = (flavor, addon.primary_folder_id.clone(), version);
+                if !is_known && ajour.notification_state.notified.insert(key) {
+                    new_updates.push((flavor, addon));
+                }
+            }
+
+            if !new_updates.is_empty() {
+                let webhook = ajour.config.webhook.clone();
+                let webhook_addons = new_updates
+                    .iter()
+                    .map(|(flavor, addon)| WebhookAddon::new(addon, *flavor))
+                    .collect::<Vec<_>>();
+
+                return Ok(Command::perform(
+                    async move {
+                        notify_updates(&new_updates, false);
+
+                        if let Err(e) =
+                            send_webhook(&webhook, WebhookEvent::UpdatesFound, &webhook_addons)
+                                .await
+                        {
+                            log::error!("failed to post to the webhook: {}", e);
+                        }
+                    },
+                    Message::None,
+                ));
+            }
+        }
+        Message::CheckedForUpdates(Err(error)) => {
+            log::error!("{}", error);
+
+            ajour.notification_state.checking = false;
+        }
+        Message::Interaction(Interaction::ToggleUpdateNotifications(is_checked)) => {
+            log::debug!("Interaction::ToggleUpdateNotifications({})", is_checked);
+
+            ajour.config.notifications.enabled = is_checked;
+
+            // Persist the newly updated config.
+            let _ = ajour.config.save();
+        }
+        Message::Interaction(Interaction::ToggleOffline(is_checked)) => {
+            log::debug!("Interaction::ToggleOffline({})", is_checked);
+
+            ajour.config.offline = is_checked;
+            network::set_offline(is_checked);
+
+            // Persist the newly updated config.
+            let _ = ajour.config.save();
+
+            // Shows the addons from the cache, or brings them up to date again.
+            return handle_message(ajour, Message::Interaction(Interaction::Refresh));
+        }
+        Message::Interaction(Interaction::ToggleRunAfterGameExits(is_checked)) => {
+            log::debug!("Interaction::ToggleRunAfterGameExits({})", is_checked);
+
+            ajour.config.run_after_game_exits = is_checked;
+
+            // Persist the newly updated config.
+            let _ = ajour.config.save();
+        }
+        Message::Interaction(Interaction::ToggleOpenLinks(is_checked)) => {
+            log::debug!("Interaction::ToggleOpenLinks({})", is_checked);
+
+            let result = if is_checked {
+                register_link_handler(&[AJOUR_SCHEME])
+            } else {
+                unregister_link_handler(&[AJOUR_SCHEME])
+            };
+
+            match result {
+                Ok(_) => {
+                    ajour.config.open_links = is_checked;
+
+                    // Persist the newly updated config.
+                    let _ = ajour.config.save();
+                }
+                Err(e) => ajour.state = AjourState::Error(e),
+            }
+        }
+        Message::Interaction(Interaction::ToggleTrayIcon(is_checked)) => {
+            log::debug!("Interaction::ToggleTrayIcon({})", is_checked);
+
+            ajour.config.tray_icon = is_checked;
+            update_tray(ajour);
+
+            // Persist the newly updated config.
+            let _ = ajour.config.save();
+        }
+        Message::Interaction(Interaction::ToggleCloseToTray(is_checked)) => {
+            log::debug!("Interaction::ToggleCloseToTray({})", is_checked);
+
+            ajour.config.close_to_tray = is_checked;
+            update_tray(ajour);
+
+            // Persist the newly updated config.
+            let _ = ajour.config.save();
+        }
+        Message::TrayAction(action) => {
+            log::debug!("Message::TrayAction({:?})", action);
+
+            match action {
+                tray::Action::Show => instance::focus_running_window(),
+                tray::Action::CheckForUpdates => {
+                    if matches!(ajour.state, AjourState::Idle) {
+                        return handle_message(ajour, Message::Interaction(Interaction::Refresh));
+                    }
+                }
+                // Same as `ajour update` while Ajour is running.
+                tray::Action::UpdateAll => {
+                    return handle_message(ajour, Message::InstanceRequest(Request::UpdateAll));
+                }
+                tray::Action::Quit => tray::quit(),
+            }
+        }
+        Message::Interaction(Interaction::InstallFromLink) => {
+            log::debug!("Interaction::InstallFromLink");
+
+            if let Some(link) = ajour.install_link_state.link.take() {
+                // Links made for a flavor we don't have are installed to the selected one.
+                let flavor = link
+                    .flavor
+                    .filter(|f| ajour.valid_flavors.contains(f))
+                    .unwrap_or(ajour.config.wow.flavor);
+
+                return handle_message(
+                    ajour,
+                    Message::Interaction(Interaction::CatalogInstall(link.source, flavor, link.id)),
+                );
+            }
+        }
+        Message::Interaction(Interaction::DismissInstallLink) => {
+            log::debug!("Interaction::DismissInstallLink");
+
+            ajour.install_link_state.link = None;
+        }
+        Message::Interaction(Interaction::RunDeferred) => {
+            log::debug!("Interaction::RunDeferred");
+
+            let flavor = ajour.config.wow.flavor;
+            return run_deferred(ajour, |a| a.flavor == flavor);
+        }
+        Message::Interaction(Interaction::CancelDeferred) => {
+            log::debug!("Interaction::CancelDeferred");
+
+            let flavor = ajour.config.wow.flavor;
+            ajour.game_state.deferred.retain(|a| a.flavor != flavor);
+        }
+        Message::CheckThemes(_) => {
+            if user_themes_modified() != ajour.theme_state.user_themes_modified {
+                log::debug!("Message::CheckThemes - themes changed");
+
+                return Ok(Command::perform(load_user_themes(), Message::ThemesLoaded));
+            }
+        }
+        Message::CheckGame(_) => {
+            return Ok(Command::perform(
+                async { game::running_flavors() },
+                Message::RunningFlavors,
+            ));
+        }
+        Message::RunningFlavors(running) => {
+            ajour.game_state.running = running;
+
+            if ajour.config.run_after_game_exits {
+                let flavor = ajour.config.wow.flavor;
+                let running = ajour.game_state.running.clone();
+
+                // Actions of other flavors wait until their flavor is selected again.
+                return run_deferred(ajour, |a| a.flavor == flavor && !a.is_blocked(&running));
+            }
+        }
+        Message::Interaction(Interaction::DismissUpdateReport) => {
+            log::debug!("Interaction::DismissUpdateReport");
+
+            ajour.update_report = None;
+        }
+        Message::RowDensitySelected(density) => {
+            log::debug!("Message::RowDensitySelected({})", density);
 
-            let flavor = ajour.config.wow.flavor;
-            let mut addons = ajour.addons.entry(flavor).or_default();
+            ajour.config.row_density = density;
 
-            sort_addons(&mut addons, sort_direction, column_key);
+            // Persist the newly updated config.
+            let _ = ajour.config.save();
+        }
+        Message::CheckIntervalSelected(interval) => {
+            log::debug!("Message::CheckIntervalSelected({})", interval);
 
-            ajour.header_state.previous_sort_direction = Some(sort_direction);
-            ajour.header_state.previous_column_key = Some(column_key);
+            ajour.config.notifications.interval = interval;
+
+            // Persist the newly updated config.
+            let _ = ajour.config.save();
         }
-        Message::Interaction(Interaction::SortCatalogColumn(column_key)) => {
-            // Close settings if shown.
-            ajour.is_showing_settings = false;
+        Message::Interaction(Interaction::ToggleInstallFromUrl) => {
+            log::debug!("Interaction::ToggleInstallFromUrl");
 
-            // First time clicking a column should sort it in Ascending order, otherwise
-            // flip the sort direction.
-            let mut sort_direction = SortDirection::Asc;
+            let state = &mut ajour.install_from_url_state;
+            state.is_shown = !state.is_shown;
+            state.status = None;
+        }
+        Message::Interaction(Interaction::InstallFromUrlInput(url)) => {
+            ajour.install_from_url_state.url = url;
+        }
+        Message::Interaction(Interaction::BrowseArchive) => {
+            log::debug!("Interaction::BrowseArchive");
 
-            if let Some(previous_column_key) = ajour.catalog_header_state.previous_column_key {
-                if column_key == previous_column_key {
-                    if let Some(previous_sort_direction) =
-                        ajour.catalog_header_state.previous_sort_direction
-                    {
-                        sort_direction = previous_sort_direction.toggle()
-                    }
-                }
-            }
+            return Ok(Command::perform(open_archive(), Message::ArchiveChosen));
+        }
+        Message::ArchiveChosen(path) => {
+            log::debug!("Message::ArchiveChosen({:?})", &path);
 
-            // Exception would be first time ever sorting and sorting by title.
-            // Since its already sorting in Asc by default, we should sort Desc.
-            if ajour.catalog_header_state.previous_column_key.is_none()
-                && column_key == CatalogColumnKey::Title
-            {
-                sort_direction = SortDirection::Desc;
+            if let Some(path) = path {
+                ajour.install_from_url_state.url = path.display().to_string();
             }
+        }
+        Message::Interaction(Interaction::InstallFromUrl) => {
+            let url = ajour.install_from_url_state.url.trim().to_string();
+            log::debug!("Interaction::InstallFromUrl({})", &url);
 
-            log::debug!(
-                "Interaction::SortCatalogColumn({:?}, {:?})",
-                column_key,
-                sort_direction
-            );
-
-            ajour.catalog_header_state.previous_sort_direction = Some(sort_direction);
-            ajour.catalog_header_state.previous_column_key = Some(column_key);
+            let flavor = ajour.config.wow.flavor;
 
-            query_and_sort_catalog(ajour);
+            if let (Some(addon_directory), Some(temp_directory)) = (
+                ajour.config.get_addon_directory_for_flavor(&flavor),
+                ajour.config.get_download_directory_for_flavor(flavor),
+            ) {
+                ajour.install_from_url_state.installing = true;
+                ajour.install_from_url_state.status = None;
+
+                return Ok(Command::perform(
+                    perform_install_from_url(
+                        ajour.shared_client.clone(),
+                        ajour.fingerprint_collection.clone(),
+                        flavor,
+                        url,
+                        temp_directory,
+                        addon_directory,
+                    ),
+                    Message::InstalledFromUrl,
+                ));
+            }
         }
-        Message::ReleaseChannelSelected(release_channel) => {
-            log::debug!("Message::ReleaseChannelSelected({:?})", release_channel);
+        Message::InstalledFromUrl(result) => {
+            let state = &mut ajour.install_from_url_state;
+            state.installing = false;
 
-            if let ExpandType::Details(expanded_addon) = &ajour.expanded_type {
-                let flavor = ajour.config.wow.flavor;
-                let addons = ajour.addons.entry(flavor).or_default();
-                if let Some(addon) = addons
-                    .iter_mut()
-                    .find(|a| a.primary_folder_id == expanded_addon.primary_folder_id)
-                {
-                    addon.release_channel = release_channel;
+            match result {
+                Ok(folders) => {
+                    let folder_ids = folders.iter().map(|f| f.id.as_str()).collect::<Vec<_>>();
+                    log::debug!("Message::InstalledFromUrl({:?})", &folder_ids);
 
-                    // Check if addon is updatable.
-                    if let Some(package) = addon.relevant_release_package() {
-                        if addon.is_updatable(package) {
-                            addon.state = AddonState::Updatable;
-                        } else {
-                            addon.state = AddonState::Ajour(None);
-                        }
-                    }
+                    state.status = Some(localized_string_with(
+                        "installed-addon",
+                        &[("title", &folder_ids.join(", "))],
+                    ));
+                    state.url.clear();
 
-                    // Update config with the newly changed release channel.
-                    ajour
-                        .config
-                        .addons
-                        .release_channels
-                        .entry(flavor)
-                        .or_default()
-                        .insert(addon.primary_folder_id.clone(), release_channel);
+                    // Reparse the addons to show the newly installed addon.
+                    return handle_message(ajour, Message::Interaction(Interaction::Refresh));
+                }
+                Err(error) => {
+                    log::error!("{}", error);
 
-                    // Persist the newly updated config.
-                    let _ = &ajour.config.save();
+                    state.status = Some(error.user_message());
                 }
             }
         }
-        Message::ThemeSelected(theme_name) => {
-            log::debug!("Message::ThemeSelected({:?})", &theme_name);
+        Message::Interaction(Interaction::ToggleUpdateAllFlavors(is_checked)) => {
+            log::debug!("Interaction::ToggleUpdateAllFlavors({})", is_checked);
 
-            ajour.theme_state.current_theme_name = theme_name.clone();
+            ajour.config.update_all_flavors = is_checked;
 
-            ajour.config.theme = Some(theme_name);
-            let _ = ajour.config.save();
+            // Persist the newly updated config.
+            let _ = &ajour.config.save();
         }
-        Message::ThemesLoaded(mut themes) => {
-            log::debug!("Message::ThemesLoaded({} themes)", themes.len());
+        Message::Interaction(Interaction::AddonsQuery(query)) => {
+            log::debug!("Interaction::AddonsQuery({})", &query);
 
-            themes.sort();
+            // An empty query shows all addons.
+            ajour.addons_filter_state.query = if query.is_empty() { None } else { Some(query) };
+        }
+        Message::Interaction(Interaction::AddonStatusFilterSelected(status)) => {
+            log::debug!("Interaction::AddonStatusFilterSelected({})", status);
 
-            for theme in themes {
-                ajour.theme_state.themes.push((theme.name.clone(), theme));
+            ajour.addons_filter_state.status = status;
+        }
+        Message::Interaction(Interaction::ToggleWhatsNew) => {
+            log::debug!("Interaction::ToggleWhatsNew");
+
+            // Close settings, clean up and the update preview if shown.
+            ajour.is_showing_settings = false;
+            ajour.cleanup_state.is_shown = false;
+            ajour.update_preview_state.is_shown = false;
+
+            let state = &mut ajour.whats_new_state;
+            state.is_shown = !state.is_shown;
+
+            // The updates have been seen once closed.
+            if !state.is_shown {
+                state.history.updates.clear();
+                let _ = state.history.save();
             }
         }
-        Message::Interaction(Interaction::ResizeColumn(column_type, event)) => match event {
-            ResizeEvent::ResizeColumn {
-                left_name,
-                left_width,
-                right_name,
-                right_width,
-            } => match column_type {
-                AjourMode::MyAddons => {
-                    let left_key = ColumnKey::from(left_name.as_str());
-                    let right_key = ColumnKey::from(right_name.as_str());
+        Message::Interaction(Interaction::ToggleUpdatePreview) => {
+            log::debug!("Interaction::ToggleUpdatePreview");
 
-                    if let Some(column) = ajour
-                        .header_state
-                        .columns
-                        .iter_mut()
-                        .find(|c| c.key == left_key && left_key != ColumnKey::Title)
-                    {
-                        column.width = Length::Units(left_width);
-                    }
+            // Close settings, clean up and what's new if shown.
+            ajour.is_showing_settings = false;
+            ajour.cleanup_state.is_shown = false;
+            ajour.whats_new_state.is_shown = false;
 
-                    if let Some(column) = ajour
-                        .header_state
-                        .columns
-                        .iter_mut()
-                        .find(|c| c.key == right_key && right_key != ColumnKey::Title)
-                    {
-                        column.width = Length::Units(right_width);
-                    }
-                }
-                AjourMode::Catalog => {
-                    let left_key = CatalogColumnKey::from(left_name.as_str());
-                    let right_key = CatalogColumnKey::from(right_name.as_str());
+            let state = &mut ajour.update_preview_state;
+            state.is_shown = !state.is_shown;
+        }
+        Message::Interaction(Interaction::ToggleShortcuts) => {
+            log::debug!("Interaction::ToggleShortcuts");
 
-                    if let Some(column) =
-                        ajour.catalog_header_state.columns.iter_mut().find(|c| {
-                            c.key == left_key && left_key != CatalogColumnKey::Description
-                        })
-                    {
-                        column.width = Length::Units(left_width);
-                    }
+            let state = &mut ajour.keyboard_state;
+            state.is_showing_shortcuts = !state.is_showing_shortcuts;
+        }
+        Message::Interaction(Interaction::ToggleCleanup) => {
+            log::debug!("Interaction::ToggleCleanup");
 
-                    if let Some(column) =
-                        ajour.catalog_header_state.columns.iter_mut().find(|c| {
-                            c.key == right_key && right_key != CatalogColumnKey::Description
-                        })
-                    {
-                        column.width = Length::Units(right_width);
-                    }
+            // Close settings, what's new and the update preview if shown.
+            ajour.is_showing_settings = false;
+            ajour.whats_new_state.is_shown = false;
+            ajour.update_preview_state.is_shown = false;
+
+            let state = &mut ajour.cleanup_state;
+            state.is_shown = !state.is_shown;
+            state.status = None;
+            state.leftovers.clear();
+
+            let flavor = ajour.config.wow.flavor;
+
+            if ajour.cleanup_state.is_shown {
+                if let Some(addon_directory) = ajour.config.get_addon_directory_for_flavor(&flavor)
+                {
+                    ajour.cleanup_state.scanning = true;
+
+                    return Ok(Command::perform(
+                        perform_find_leftovers(
+                            addon_directory,
+                            ajour.config.get_wtf_directory_for_flavor(&flavor),
+                            ajour.addons.get(&flavor).cloned().unwrap_or_default(),
+                        ),
+                        Message::LeftoversFound,
+                    ));
                 }
-            },
-            ResizeEvent::Finished => {
-                // Persist changes to config
-                save_column_configs(ajour);
             }
-        },
-        Message::Interaction(Interaction::ScaleUp) => {
-            let prev_scale = ajour.scale_state.scale;
+        }
+        Message::LeftoversFound(result) => {
+            let state = &mut ajour.cleanup_state;
+            state.scanning = false;
 
-            ajour.scale_state.scale = (prev_scale + 0.1).min(2.0);
+            match result {
+                Ok(leftovers) => {
+                    log::debug!("Message::LeftoversFound({} leftovers)", leftovers.len());
 
-            ajour.config.scale = Some(ajour.scale_state.scale);
-            let _ = ajour.config.save();
+                    state.leftovers = leftovers.into_iter().map(|l| (l, false)).collect();
+                }
+                Err(error) => {
+                    log::error!("{}", error);
 
-            log::debug!(
-                "Interaction::ScaleUp({} -> {})",
-                prev_scale,
-                ajour.scale_state.scale
-            );
+                    state.status = Some(error.user_message());
+                }
+            }
         }
-        Message::Interaction(Interaction::ScaleDown) => {
-            let prev_scale = ajour.scale_state.scale;
+        Message::Interaction(Interaction::ToggleLeftover(idx, is_checked)) => {
+            if let Some((_, is_selected)) = ajour.cleanup_state.leftovers.get_mut(idx) {
+                *is_selected = is_checked;
+            }
 
-            ajour.scale_state.scale = (prev_scale - 0.1).max(0.5);
+            ajour.cleanup_state.status = None;
+        }
+        Message::Interaction(Interaction::DeleteLeftovers) => {
+            let state = &mut ajour.cleanup_state;
 
-            ajour.config.scale = Some(ajour.scale_state.scale);
-            let _ = ajour.config.save();
+            let (selected, remaining): (Vec<_>, Vec<_>) =
+                state.leftovers.drain(..).partition(|(_, s)| *s);
+            let selected = selected.into_iter().map(|(l, _)| l).collect::<Vec<_>>();
 
-            log::debug!(
-                "Interaction::ScaleDown({} -> {})",
-                prev_scale,
-                ajour.scale_state.scale
-            );
-        }
-        Message::UpdateBackupDirectory(path) => {
-            log::debug!("Message::UpdateBackupDirectory({:?})", &path);
+            log::debug!("Interaction::DeleteLeftovers({} leftovers)", selected.len());
 
-            if let Some(path) = path {
-                // Update the backup directory path.
-                ajour.config.backup_directory = Some(path.clone());
-                // Persist the newly updated config.
-                let _ = &ajour.config.save();
+            state.leftovers = remaining;
 
-                // Check if a latest backup exists in path
-                return Ok(Command::perform(latest_backup(path), Message::LatestBackup));
+            match delete_leftovers(&selected) {
+                Ok(()) => {
+                    state.status = Some(localized_string_with(
+                        "deleted-leftovers",
+                        &[("count", &selected.len())],
+                    ))
+                }
+                Err(error) => {
+                    log::error!("{}", error);
+
+                    // Keep what's still on disk in the list.
+                    state.leftovers.extend(
+                        selected
+                            .into_iter()
+                            .filter(|l| l.path.exists())
+                            .map(|l| (l, true)),
+                    );
+                    state.status = Some(error.user_message());
+                }
             }
         }
+        Message::Interaction(Interaction::WagoAccountSelected(account)) => {
+            log::debug!("Interaction::WagoAccountSelected({})", account);
 
-        Message::Interaction(Interaction::Backup) => {
-            log::debug!("Interaction::Backup");
+            let flavor = ajour.config.wow.flavor;
+            ajour.config.wago.accounts.insert(flavor, account);
 
-            // This will disable our backup button and show a message that the
-            // app is processing the backup. We will unflag this on completion.
-            ajour.backup_state.backing_up = true;
+            // Persist the newly updated config.
+            let _ = &ajour.config.save();
 
-            let mut src_folders = vec![];
+            ajour.wago_state.auras.clear();
+
+            return Ok(check_wago(ajour));
+        }
+        Message::Interaction(Interaction::CheckWago) => {
+            log::debug!("Interaction::CheckWago");
 
-            // Shouldn't panic since button is only clickable if wow directory is chosen
-            let wow_dir = ajour.config.wow.directory.as_ref().unwrap();
+            return Ok(check_wago(ajour));
+        }
+        Message::WagoChecked((flavor, result)) => {
+            // The auras of a previously selected flavor are of no use.
+            if flavor != ajour.config.wow.flavor {
+                return Ok(Command::none());
+            }
 
-            // Shouldn't panic since button is only shown if backup directory is chosen
-            let dest = ajour.config.backup_directory.as_ref().unwrap();
+            let state = &mut ajour.wago_state;
+            state.checking = false;
 
-            // Backup WTF & AddOn directories for both flavors if they exist
-            for flavor in Flavor::ALL.iter() {
-                let addon_dir = ajour.config.get_addon_directory_for_flavor(flavor).unwrap();
-                let wtf_dir = ajour.config.get_wtf_directory_for_flavor(flavor).unwrap();
+            match result {
+                Ok(auras) => {
+                    log::debug!("Message::WagoChecked({} auras)", auras.len());
 
-                if addon_dir.exists() {
-                    src_folders.push(BackupFolder::new(&addon_dir, wow_dir));
+                    state.auras = auras;
                 }
+                Err(error) => {
+                    log::error!("{}", error);
 
-                if wtf_dir.exists() {
-                    src_folders.push(BackupFolder::new(&wtf_dir, wow_dir));
+                    state.status = Some(error.user_message());
                 }
             }
-
-            return Ok(Command::perform(
-                backup_folders(src_folders, dest.to_owned()),
-                Message::BackupFinished,
-            ));
         }
-        Message::LatestBackup(as_of) => {
-            log::debug!("Message::LatestBackup({:?})", &as_of);
+        Message::Interaction(Interaction::UpdateWago) => {
+            log::debug!("Interaction::UpdateWago");
 
-            ajour.backup_state.last_backup = as_of;
-        }
-        Message::BackupFinished(Ok(as_of)) => {
-            log::debug!("Message::BackupFinished({})", as_of.format("%H:%M:%S"));
+            let flavor = ajour.config.wow.flavor;
 
-            ajour.backup_state.backing_up = false;
-            ajour.backup_state.last_backup = Some(as_of);
+            if let Some(addon_directory) = ajour.config.get_addon_directory_for_flavor(&flavor) {
+                let state = &mut ajour.wago_state;
+                state.updating = true;
+                state.status = None;
+
+                return Ok(Command::perform(
+                    update_companion(addon_directory, flavor, state.auras.clone()),
+                    Message::CompanionWritten,
+                ));
+            }
         }
-        Message::BackupFinished(Err(error)) => {
-            log::error!("{}", error);
+        Message::CompanionWritten(result) => {
+            let state = &mut ajour.wago_state;
+            state.updating = false;
 
-            ajour.backup_state.backing_up = false;
+            match result {
+                Ok(update) => {
+                    log::debug!(
+                        "Message::CompanionWritten({} updates, {} failed)",
+                        update.written,
+                        update.failed.len()
+                    );
 
-            ajour.state = AjourState::Error(error);
+                    let mut status =
+                        localized_string_with("wago-updates-ready", &[("count", &update.written)]);
+                    if !update.failed.is_empty() {
+                        status.push_str(". ");
+                        status.push_str(&localized_string_with(
+                            "wago-updates-failed",
+                            &[("auras", &update.failed.join(", "))],
+                        ));
+                    }
+
+                    state.status = Some(status);
+                }
+                Err(error) => {
+                    log::error!("{}", error);
+
+                    state.status = Some(error.user_message());
+                }
+            }
         }
         Message::Interaction(Interaction::ToggleColumn(is_checked, key)) => {
             // We can't untoggle the addon title column
@@ -1271,6 +3397,7 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
 
             // Catalog search query
             ajour.catalog_search_state.query = Some(query);
+            ajour.catalog_search_state.page = 0;
 
             query_and_sort_catalog(ajour);
         }
@@ -1308,6 +3435,7 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
 
             // Select category
             ajour.catalog_search_state.category = category;
+            ajour.catalog_search_state.page = 0;
 
             query_and_sort_catalog(ajour);
         }
@@ -1318,6 +3446,7 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
             ajour.is_showing_settings = false;
             // Catalog result size
             ajour.catalog_search_state.result_size = size;
+            ajour.catalog_search_state.page = 0;
 
             query_and_sort_catalog(ajour);
         }
@@ -1328,8 +3457,41 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
             ajour.is_showing_settings = false;
             // Catalog source
             ajour.catalog_search_state.source = source;
+            ajour.catalog_search_state.page = 0;
+
+            query_and_sort_catalog(ajour);
+        }
+        Message::CatalogAddonSourceSelected((source, id, selected_source)) => {
+            log::debug!(
+                "Message::CatalogAddonSourceSelected({}, {}, {})",
+                source,
+                id,
+                selected_source
+            );
+
+            if let Some(row) = ajour
+                .catalog_search_state
+                .catalog_rows
+                .iter_mut()
+                .find(|r| r.addon.source == source && r.addon.id == id)
+            {
+                if let Some(addon) = row.addon.with_source(selected_source) {
+                    row.addon = addon;
+                }
+            }
+        }
+        Message::Interaction(Interaction::CatalogPageSelected(page)) => {
+            log::debug!("Interaction::CatalogPageSelected({})", page);
+
+            // Close settings if shown.
+            ajour.is_showing_settings = false;
+            // Catalog page
+            ajour.catalog_search_state.page = page;
 
             query_and_sort_catalog(ajour);
+
+            // Start from the top of the new page.
+            ajour.catalog_search_state.scrollable_state = Default::default();
         }
         Message::CatalogInstallAddonFetched((flavor, id, result)) => match result {
             Ok(mut addon) => {
@@ -1340,6 +3502,9 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
                 );
 
                 if let Some(addons) = ajour.addons.get_mut(&flavor) {
+                    // Required addons which aren't installed yet are installed along with it.
+                    let missing_dependencies = addon.missing_dependencies(addons);
+
                     // Add the addon to our collection
                     addon.state = AddonState::Downloading;
                     addons.push(addon.clone());
@@ -1349,16 +3514,50 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
                         .get_download_directory_for_flavor(flavor)
                         .expect("Expected a valid path");
 
-                    return Ok(Command::perform(
+                    let mut commands = vec![Command::perform(
                         perform_download_addon(
                             DownloadReason::Install,
                             ajour.shared_client.clone(),
+                            ajour
+                                .download_manager
+                                .queue((flavor, addon.primary_folder_id.clone())),
                             flavor,
                             addon,
                             to_directory,
                         ),
                         Message::DownloadedAddon,
-                    ));
+                    )];
+
+                    for dependency in missing_dependencies {
+                        // Skip dependencies which are already being installed.
+                        let is_installing =
+                            ajour.catalog_install_statuses.iter().any(|(f, i, s)| {
+                                *f == flavor
+                                    && *i == dependency
+                                    && *s == CatalogInstallStatus::Downloading
+                            });
+                        if is_installing {
+                            continue;
+                        }
+
+                        log::debug!("installing {} which is required by {}", dependency, id);
+
+                        ajour
+                            .catalog_install_statuses
+                            .retain(|(f, i, _)| !(*f == flavor && *i == dependency));
+                        ajour.catalog_install_statuses.push((
+                            flavor,
+                            dependency,
+                            CatalogInstallStatus::Downloading,
+                        ));
+
+                        commands.push(Command::perform(
+                            perform_fetch_latest_addon(catalog::Source::Curse, dependency, flavor),
+                            Message::CatalogInstallAddonFetched,
+                        ));
+                    }
+
+                    return Ok(Command::batch(commands));
                 }
             }
             Err(error) => {
@@ -1389,6 +3588,42 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
                 }
             }
         }
+        Message::FetchedAddonDetails((repository, id, result)) => {
+            log::debug!(
+                "Message::FetchedAddonDetails(({}, {}, error: {}))",
+                repository,
+                &id,
+                result.is_err()
+            );
+
+            match result {
+                Ok(details) => {
+                    ajour.addon_details.insert((repository, id), details);
+                }
+                Err(error) => {
+                    log::error!("Message::FetchedAddonDetails(error: {})", &error);
+                }
+            }
+        }
+        Message::FetchedUpdateChangelog((flavor, id, result)) => {
+            log::debug!(
+                "Message::FetchedUpdateChangelog(({}, error: {}))",
+                &id,
+                result.is_err()
+            );
+
+            match result {
+                Ok(Some((changelog, _))) => {
+                    let history = &mut ajour.whats_new_state.history;
+                    history.set_changelog(flavor, &id, changelog);
+                    let _ = history.save();
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    log::error!("Message::FetchedUpdateChangelog(error: {})", &error);
+                }
+            }
+        }
         Message::FetchedCurseChangelog((addon, key, result)) => {
             log::debug!(
                 "Message::FetchedCurseChangelog(error: {})",
@@ -1410,9 +3645,13 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
         Message::Error(error)
         | Message::Parse(Err(error))
         | Message::NeedsUpdate(Err(error))
-        | Message::CatalogDownloaded(Err(error)) => {
+        | Message::CatalogDownloaded(Err(error))
+        | Message::LogsRead(Err(error))
+        | Message::DiagnosticsGenerated(Err(error)) => {
             log::error!("{}", error);
 
+            ajour.toast_state.error(error.user_message(), None);
+
             ajour.state = AjourState::Error(error);
         }
         Message::RuntimeEvent(iced_native::Event::Window(
@@ -1424,6 +3663,21 @@ pub fn handle_message(ajour: &mut Ajour, message: Message) -> Result<Command<Mes
             ajour.config.window_size = Some((width, height));
             let _ = ajour.config.save();
         }
+        Message::RuntimeEvent(iced_native::Event::Keyboard(
+            iced_native::keyboard::Event::ModifiersChanged(modifiers),
+        )) => {
+            // Held while clicking addons to select them, command is used on macOS.
+            ajour.selection_state.is_control_pressed = modifiers.control || modifiers.logo;
+            ajour.selection_state.is_shift_pressed = modifiers.shift;
+        }
+        Message::RuntimeEvent(iced_native::Event::Keyboard(
+            iced_native::keyboard::Event::KeyPressed {
+                key_code,
+                modifiers,
+            },
+        )) => {
+            return handle_key_pressed(ajour, key_code, modifiers.control || modifiers.logo);
+        }
         Message::RuntimeEvent(_) => {}
         Message::None(_) => {}
     }
@@ -1440,14 +3694,81 @@ async fn open_directory() -> Option<PathBuf> {
     None
 }
 
+async fn open_archive() -> Option<PathBuf> {
+    let dialog = OpenSingleFile {
+        dir: None,
+        filter: Some(&["zip"]),
+    };
+    if let Ok(show) = dialog.show() {
+        return show;
+    }
+
+    None
+}
+
+/// Installs the zip at `url` and fingerprints its folders, so they are
+/// recognized when the addons are parsed again.
+async fn perform_install_from_url(
+    shared_client: Arc<HttpClient>,
+    fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
+    flavor: Flavor,
+    url: String,
+    temp_directory: PathBuf,
+    addon_directory: PathBuf,
+) -> Result<Vec<AddonFolder>> {
+    let folders = install_from_url(&shared_client, &url, &temp_directory, &addon_directory).await?;
+
+    for folder in folders.iter() {
+        update_addon_fingerprint(
+            fingerprint_collection.clone(),
+            flavor,
+            &addon_directory,
+            folder.id.clone(),
+        )
+        .await?;
+    }
+
+    Ok(folders)
+}
+
+async fn perform_undo_last_update(
+    fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
+    flavor: Flavor,
+    addon_directory: PathBuf,
+) -> (Flavor, Result<Vec<Snapshot>>) {
+    (
+        flavor,
+        snapshot::undo_last_update(fingerprint_collection, flavor, &addon_directory).await,
+    )
+}
+
+async fn perform_verify_addons(
+    flavor: Flavor,
+    addons: Vec<Addon>,
+    addon_directory: PathBuf,
+) -> (Flavor, Vec<(String, Verification)>) {
+    (
+        flavor,
+        verify_addons(&addons, flavor, &addon_directory).await,
+    )
+}
+
+async fn perform_check_for_updates(
+    fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
+    config: Config,
+) -> Result<Vec<(Flavor, Addon)>> {
+    check_for_updates(fingerprint_collection, &config, &config.managed_flavors()).await
+}
+
 async fn perform_read_addon_directory(
     fingerprint_collection: Arc<Mutex<Option<FingerprintCollection>>>,
     root_dir: PathBuf,
     flavor: Flavor,
+    source_priority: SourcePriority,
 ) -> (Flavor, Result<Vec<Addon>>) {
     (
         flavor,
-        read_addon_directory(fingerprint_collection, root_dir, flavor).await,
+        read_addon_directory(fingerprint_collection, root_dir, flavor, source_priority).await,
     )
 }
 
@@ -1464,6 +3785,51 @@ async fn perform_fetch_tukui_changelog(
     )
 }
 
+async fn perform_fetch_addon_details(
+    repository: Repository,
+    id: String,
+    flavor: Flavor,
+) -> (Repository, String, Result<AddonDetails>) {
+    let result = match repository {
+        Repository::Curse => match id.parse::<u32>() {
+            Ok(curse_id) => curse_api::fetch_addon_details(curse_id).await,
+            Err(_) => Err(ClientError::Custom(localized_string_with(
+                "invalid-curse-id",
+                &[("id", &id)],
+            ))),
+        },
+        Repository::Tukui => tukui_api::fetch_addon_details(&id, &flavor).await,
+        Repository::WowI => Err(ClientError::Custom(localized_string(
+            "wowinterface-details-unsupported",
+        ))),
+    };
+
+    (repository, id, result)
+}
+
+/// Fetches the changelog of the version the addon was updated to.
+async fn perform_fetch_update_changelog(
+    flavor: Flavor,
+    addon: Addon,
+) -> (Flavor, String, Result<Option<(String, String)>>) {
+    let file_id = addon
+        .relevant_release_package()
+        .and_then(|package| package.file_id);
+
+    let result = match (addon.active_repository, addon.repository_id()) {
+        (Some(Repository::Curse), Some(id)) => match (id.parse::<u32>(), file_id) {
+            (Ok(id), Some(file_id)) => curse_api::fetch_changelog(id, file_id).await.map(Some),
+            _ => Ok(None),
+        },
+        (Some(Repository::Tukui), Some(id)) => {
+            tukui_api::fetch_changelog(&id, &flavor).await.map(Some)
+        }
+        _ => Ok(None),
+    };
+
+    (flavor, addon.primary_folder_id, result)
+}
+
 async fn perform_fetch_curse_changelog(
     addon: Addon,
     key: AddonVersionKey,
@@ -1473,11 +3839,11 @@ async fn perform_fetch_curse_changelog(
     (addon, key, curse_api::fetch_changelog(id, file_id).await)
 }
 
-/// Downloads the newest version of the addon.
-/// This is for now only downloading from warcraftinterface.
+/// Downloads the newest version of the addon once `download` is at the front of the queue.
 async fn perform_download_addon(
     reason: DownloadReason,
     shared_client: Arc<HttpClient>,
+    mut download: Download,
     flavor: Flavor,
     addon: Addon,
     to_directory: PathBuf,
@@ -1486,7 +3852,7 @@ async fn perform_download_addon(
         reason,
         flavor,
         addon.primary_folder_id.clone(),
-        download_addon(&shared_client, &addon, &to_directory).await,
+        download_addon(&shared_client, &addon, &to_directory, Some(&mut download)).await,
     )
 }
 
@@ -1518,7 +3884,20 @@ async fn perform_unpack_addon(
         reason,
         flavor,
         addon.primary_folder_id.clone(),
-        install_addon(&addon, &from_directory, &to_directory).await,
+        install_addon(&addon, flavor, &from_directory, &to_directory).await,
+    )
+}
+
+/// Reinstalls the previous version of `Addon` from the archive cache into `to_directory`.
+async fn perform_rollback_addon(
+    flavor: Flavor,
+    addon: Addon,
+    to_directory: PathBuf,
+) -> (Flavor, String, Result<(CachedArchive, Vec<AddonFolder>)>) {
+    (
+        flavor,
+        addon.primary_folder_id.clone(),
+        rollback_addon(&addon, flavor, &to_directory).await,
     )
 }
 
@@ -1536,6 +3915,517 @@ async fn perform_fetch_latest_addon(
     (flavor, source_id, result)
 }
 
+async fn perform_find_leftovers(
+    addon_directory: PathBuf,
+    wtf_directory: Option<PathBuf>,
+    addons: Vec<Addon>,
+) -> Result<Vec<Leftover>> {
+    find_leftovers(&addon_directory, wtf_directory.as_deref(), &addons)
+}
+
+async fn perform_check_wago(
+    flavor: Flavor,
+    wtf_directory: PathBuf,
+    account: String,
+) -> (Flavor, Result<Vec<Aura>>) {
+    (
+        flavor,
+        wago::check_for_updates(wtf_directory, account).await,
+    )
+}
+
+/// Lists the accounts with WeakAuras or Plater installed for the current flavor, and
+/// checks the auras of the chosen account for updates.
+///
+/// The first account is chosen if none was chosen before.
+fn check_wago(ajour: &mut Ajour) -> Command<Message> {
+    let flavor = ajour.config.wow.flavor;
+    let state = &mut ajour.wago_state;
+    state.status = None;
+
+    let wtf_directory = match ajour.config.get_wtf_directory_for_flavor(&flavor) {
+        Some(wtf_directory) => wtf_directory,
+        None => return Command::none(),
+    };
+
+    state.accounts = wago::accounts(&wtf_directory);
+
+    let chosen_account = ajour
+        .config
+        .wago
+        .accounts
+        .get(&flavor)
+        .filter(|a| state.accounts.contains(a))
+        .cloned();
+
+    let account = match chosen_account.or_else(|| state.accounts.first().cloned()) {
+        Some(account) => account,
+        None => {
+            state.auras.clear();
+            return Command::none();
+        }
+    };
+
+    if ajour.config.wago.accounts.get(&flavor) != Some(&account) {
+        ajour.config.wago.accounts.insert(flavor, account.clone());
+
+        // Persist the newly updated config.
+        let _ = &ajour.config.save();
+    }
+
+    state.checking = true;
+
+    Command::perform(
+        perform_check_wago(flavor, wtf_directory, account),
+        Message::WagoChecked,
+    )
+}
+
+/// Switches to the World of Warcraft installation in `directory` and parses
+/// its addons.
+fn switch_installation(ajour: &mut Ajour, directory: PathBuf) -> Command<Message> {
+    ajour.config.switch_installation(directory);
+
+    reload_config(ajour)
+}
+
+/// Saves the config after switching installation or profile, and parses the
+/// addons again.
+fn reload_config(ajour: &mut Ajour) -> Command<Message> {
+    // Persist the newly updated config.
+    let _ = &ajour.config.save();
+
+    // Forget everything about the previous installation.
+    ajour.addons = HashMap::new();
+    ajour.valid_flavors.clear();
+    ajour.expanded_type = ExpandType::None;
+    ajour.cleanup_state.is_shown = false;
+    ajour.wago_state = Default::default();
+
+    // Set loading state.
+    ajour.state = AjourState::Loading;
+    // Reload config.
+    Command::perform(load_config(), Message::Parse)
+}
+
+/// Shows or removes the tray icon to match the settings.
+fn update_tray(ajour: &mut Ajour) {
+    if !ajour.config.tray_icon {
+        ajour.tray = None;
+    } else if ajour.tray.is_none() {
+        ajour.tray = tray::start();
+    }
+
+    tray::set_close_to_tray(ajour.config.close_to_tray && ajour.tray.is_some());
+}
+
+/// Starts backing up the WTF & AddOn directories for all flavors.
+fn start_backup(ajour: &mut Ajour) -> Command<Message> {
+    // This will disable our backup button and show a message that the
+    // app is processing the backup. We will unflag this on completion.
+    ajour.backup_state.backing_up = true;
+
+    // Shouldn't panic since backups are only started if backup directory is chosen
+    let dest = ajour.config.backup_directory.as_ref().unwrap();
+
+    // Backup WTF & AddOn directories for all flavors if they exist
+    let src_folders =
+        flavor_backup_folders(&ajour.config, &Flavor::ALL[..], &BackupContent::DEFAULT);
+
+    Command::perform(
+        backup_folders(src_folders, dest.to_owned()),
+        Message::BackupFinished,
+    )
+}
+
+/// Lists the archives in the backup directory, clearing the selected archive
+/// if it no longer exists.
+fn refresh_backup_archives(ajour: &mut Ajour) {
+    let backup_state = &mut ajour.backup_state;
+
+    backup_state.archives = ajour
+        .config
+        .backup_directory
+        .as_ref()
+        .map(|dir| list_backups(dir))
+        .unwrap_or_default();
+
+    if let Some(archive) = backup_state.restore_archive.as_ref() {
+        if !backup_state.archives.contains(archive) {
+            backup_state.restore_archive = None;
+            backup_state.confirming_restore = false;
+        }
+    }
+}
+
+/// Starts updating all updatable addons of the current flavor.
+fn update_all_addons(ajour: &mut Ajour) -> Command<Message> {
+    // Updates the addons of every flavor if chosen, otherwise of the current one.
+    let flavors = if ajour.config.update_all_flavors {
+        ajour.valid_flavors.clone()
+    } else {
+        vec![ajour.config.wow.flavor]
+    };
+
+    start_update_batch(ajour);
+
+    let mut report = UpdateReport::default();
+    let mut commands = vec![];
+    let mut updating = vec![];
+    for flavor in flavors {
+        // Update all updatable addons, expect ignored.
+        let ignored_ids = ajour.config.addons.ignored.entry(flavor).or_default();
+        let mut addons: Vec<_> = ajour
+            .addons
+            .entry(flavor)
+            .or_default()
+            .iter_mut()
+            .filter(|a| !ignored_ids.iter().any(|i| i == &a.primary_folder_id))
+            .collect();
+
+        for addon in addons.iter_mut() {
+            if addon.state == AddonState::Updatable {
+                if let Some(to_directory) = ajour.config.get_download_directory_for_flavor(flavor) {
+                    report.start(flavor, &addon.primary_folder_id);
+                    updating.push(json!({
+                        "id": addon.primary_folder_id,
+                        "title": addon.title(),
+                        "flavor": flavor,
+                        "old_version": addon.version(),
+                        "new_version": addon.relevant_release_package().map(|p| &p.version),
+                    }));
+
+                    addon.state = AddonState::Downloading;
+                    let download = ajour
+                        .download_manager
+                        .queue((flavor, addon.primary_folder_id.clone()));
+                    let addon = addon.clone();
+                    commands.push(Command::perform(
+                        perform_download_addon(
+                            DownloadReason::Update,
+                            ajour.shared_client.clone(),
+                            download,
+                            flavor,
+                            addon,
+                            to_directory,
+                        ),
+                        Message::DownloadedAddon,
+                    ))
+                }
+            }
+        }
+
+        for addon in ajour.addons.get(&flavor).into_iter().flatten() {
+            if let Some(reason) = skip_reason(addon, &ajour.config.addons, flavor) {
+                report.skip(addon.title(), reason);
+            }
+        }
+    }
+
+    if !updating.is_empty() {
+        spawn_hook(
+            &ajour.config.hooks,
+            HookEvent::UpdateAllStarted,
+            json!({ "count": updating.len(), "addons": updating }),
+        );
+    }
+
+    // The report is shown once every addon has either been updated or failed.
+    ajour.update_report = Some(report);
+
+    Command::batch(commands)
+}
+
+/// Starts a new batch of snapshots, unless other updates are still running, so
+/// updates started together are undone together.
+fn start_update_batch(ajour: &Ajour) {
+    let is_updating = ajour.addons.values().flatten().any(|a| {
+        matches!(
+            a.state,
+            AddonState::Downloading | AddonState::Unpacking | AddonState::Fingerprint
+        )
+    });
+
+    if !is_updating {
+        snapshot::start_batch();
+    }
+}
+
+/// Records the outcome of `addon` in the report of "Update All", if it's part of it.
+///
+/// Runs the hook of the report's end once it's finished, and posts the updated
+/// addons to the webhook.
+fn finish_update_report(
+    report: Option<&mut UpdateReport>,
+    config: &Config,
+    flavor: Flavor,
+    addon: &Addon,
+    error: Option<&ClientError>,
+) {
+    if let Some(report) = report {
+        let was_finished = report.is_finished();
+        report.finish(flavor, &addon.primary_folder_id, addon.title(), error);
+
+        if !was_finished && report.is_finished() {
+            let webhook = config.webhook.clone();
+            let updated = report
+                .updated
+                .iter()
+                .map(WebhookAddon::titled)
+                .collect::<Vec<_>>();
+            async_std::task::spawn(async move {
+                if let Err(e) = send_webhook(&webhook, WebhookEvent::UpdatesApplied, &updated).await
+                {
+                    log::error!("failed to post to the webhook: {}", e);
+                }
+            });
+
+            spawn_hook(
+                &config.hooks,
+                HookEvent::UpdateAllFinished,
+                json!({
+                    "updated": report.updated.len(),
+                    "failed": report.failed.len(),
+                    "skipped": report.skipped.len(),
+                }),
+            );
+        }
+    }
+}
+
+/// Runs the hook of `event` on its own thread, so Ajour doesn't wait for it.
+fn spawn_hook(hooks: &Hooks, event: HookEvent, payload: serde_json::Value) {
+    if hooks == &Hooks::default() {
+        return;
+    }
+
+    let hooks = hooks.clone();
+    std::thread::spawn(move || run_hook(&hooks, event, payload));
+}
+
+/// Deletes addon folders from disk, along with their SavedVariables if the user
+/// chose to.
+fn delete_folders(ajour: &Ajour, flavor: Flavor, folders: &[AddonFolder]) {
+    let _ = delete_addons(folders);
+
+    if ajour.delete_saved_variables {
+        if let Some(wtf_directory) = ajour.config.get_wtf_directory_for_flavor(&flavor) {
+            if let Err(e) = delete_saved_variables(&wtf_directory, folders) {
+                log::error!("failed to delete SavedVariables: {}", e);
+            }
+        }
+    }
+}
+
+/// Returns the ids of the selected addons of the current flavor which match `filter`.
+fn selected_addon_ids(ajour: &Ajour, filter: impl Fn(&Addon) -> bool) -> Vec<String> {
+    let flavor = ajour.config.wow.flavor;
+
+    ajour
+        .addons
+        .get(&flavor)
+        .map(|addons| {
+            addons
+                .iter()
+                .filter(|a| ajour.selection_state.is_selected(&a.primary_folder_id) && filter(a))
+                .map(|a| a.primary_folder_id.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the flavors among `flavors` which the game is running for, unless the
+/// actions held back before are being run.
+fn running_game_flavors(ajour: &mut Ajour, flavors: &[Flavor]) -> Vec<Flavor> {
+    if ajour.game_state.is_running_deferred {
+        return vec![];
+    }
+
+    ajour.game_state.running = game::running_flavors();
+
+    flavors
+        .iter()
+        .filter(|f| ajour.game_state.running.contains(f))
+        .copied()
+        .collect()
+}
+
+/// Holds back `interaction` if the game is running for any of `flavors`, since
+/// addons changed while the game is open end up half loaded. Returns `true` if
+/// it was held back.
+fn defer_while_game_running(
+    ajour: &mut Ajour,
+    flavors: &[Flavor],
+    interaction: Interaction,
+) -> bool {
+    let blocked_by = running_game_flavors(ajour, flavors);
+    if blocked_by.is_empty() {
+        return false;
+    }
+
+    log::info!(
+        "World of Warcraft is running, holding back {:?}",
+        &interaction
+    );
+
+    ajour.game_state.deferred.push(DeferredAction {
+        flavor: ajour.config.wow.flavor,
+        blocked_by,
+        interaction,
+    });
+
+    true
+}
+
+/// Runs the held back actions matching `predicate`, without checking for the game
+/// again.
+fn run_deferred(
+    ajour: &mut Ajour,
+    predicate: impl Fn(&DeferredAction) -> bool,
+) -> Result<Command<Message>> {
+    let (deferred, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut ajour.game_state.deferred)
+        .into_iter()
+        .partition(predicate);
+    ajour.game_state.deferred = kept;
+    ajour.game_state.is_running_deferred = true;
+
+    let mut commands = vec![];
+    let mut result = Ok(());
+
+    for action in deferred {
+        // Deletions were confirmed before they were held back.
+        if let Interaction::Delete(id) = &action.interaction {
+            ajour.delete_confirmation = Some(id.clone());
+        }
+
+        match handle_message(ajour, Message::Interaction(action.interaction)) {
+            Ok(command) => commands.push(command),
+            Err(e) => {
+                result = Err(e);
+                break;
+            }
+        }
+    }
+
+    ajour.game_state.is_running_deferred = false;
+
+    result.map(|_| Command::batch(commands))
+}
+
+/// Keyboard shortcuts, `is_command` is whether ctrl, or command on macOS, is held.
+fn handle_key_pressed(
+    ajour: &mut Ajour,
+    key_code: KeyCode,
+    is_command: bool,
+) -> Result<Command<Message>> {
+    let is_typing = ajour.addons_filter_state.query_state.is_focused()
+        || ajour.catalog_search_state.query_state.is_focused()
+        || ajour.logs_state.module_input_state.is_focused()
+        || ajour.profile_state.name_input_state.is_focused()
+        || ajour
+            .advanced_settings_state
+            .inputs
+            .iter()
+            .any(|(_, _, state)| state.is_focused());
+
+    match (key_code, is_command) {
+        (KeyCode::F1, _) => {
+            return handle_message(ajour, Message::Interaction(Interaction::ToggleShortcuts));
+        }
+        (KeyCode::Escape, _) => {
+            // Closes the panel on top first.
+            if ajour.keyboard_state.is_showing_shortcuts {
+                ajour.keyboard_state.is_showing_shortcuts = false;
+            } else if ajour.release_notes_state.is_shown {
+                ajour.release_notes_state.is_shown = false;
+            } else if ajour.logs_state.is_shown {
+                ajour.logs_state.is_shown = false;
+            } else if is_typing {
+                ajour.addons_filter_state.query_state = Default::default();
+                ajour.catalog_search_state.query_state = Default::default();
+            } else if ajour.is_showing_settings {
+                ajour.is_showing_settings = false;
+            } else if !matches!(ajour.expanded_type, ExpandType::None) {
+                ajour.expanded_type = ExpandType::None;
+            } else if ajour.whats_new_state.is_shown {
+                return handle_message(ajour, Message::Interaction(Interaction::ToggleWhatsNew));
+            } else if ajour.update_preview_state.is_shown {
+                ajour.update_preview_state.is_shown = false;
+            } else {
+                ajour.cleanup_state.is_shown = false;
+                ajour.keyboard_state.focused_id = None;
+            }
+        }
+        (KeyCode::F, true) => match ajour.mode {
+            AjourMode::MyAddons => {
+                ajour.addons_filter_state.query_state = text_input::State::focused();
+            }
+            AjourMode::Catalog => {
+                ajour.catalog_search_state.query_state = text_input::State::focused();
+            }
+            AjourMode::Wago => {}
+        },
+        (KeyCode::U, true) if matches!(ajour.state, AjourState::Idle) => {
+            return handle_message(ajour, Message::Interaction(Interaction::UpdateAll));
+        }
+        (KeyCode::Up, false) | (KeyCode::Down, false) if ajour.mode == AjourMode::MyAddons => {
+            let flavor = ajour.config.wow.flavor;
+            let filter_state = &ajour.addons_filter_state;
+            let client_build = ajour.client_builds.get(&flavor);
+            let shown_ids: Vec<&str> = ajour
+                .addons
+                .get(&flavor)
+                .map(|addons| {
+                    addons
+                        .iter()
+                        .filter(|a| {
+                            filter_state.matches(
+                                a,
+                                client_build,
+                                ajour.config.addons.abandoned_after_months(),
+                            )
+                        })
+                        .map(|a| a.primary_folder_id.as_str())
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            let focused = ajour
+                .keyboard_state
+                .focused_id
+                .as_deref()
+                .and_then(|id| shown_ids.iter().position(|i| *i == id));
+
+            // Starts from the first or last addon when none is focused.
+            let index = match (focused, key_code) {
+                (Some(index), KeyCode::Up) => index.saturating_sub(1),
+                (Some(index), _) => (index + 1).min(shown_ids.len().saturating_sub(1)),
+                (None, KeyCode::Up) => shown_ids.len().saturating_sub(1),
+                (None, _) => 0,
+            };
+
+            ajour.keyboard_state.focused_id = shown_ids.get(index).map(|id| id.to_string());
+        }
+        (KeyCode::Enter, false) if ajour.mode == AjourMode::MyAddons && !is_typing => {
+            let flavor = ajour.config.wow.flavor;
+            let focused_id = ajour.keyboard_state.focused_id.clone();
+            let is_updatable = ajour.addons.get(&flavor).map_or(false, |addons| {
+                addons.iter().any(|a| {
+                    Some(&a.primary_folder_id) == focused_id.as_ref()
+                        && a.state == AddonState::Updatable
+                })
+            });
+
+            if let (Some(id), true) = (focused_id, is_updatable) {
+                return handle_message(ajour, Message::Interaction(Interaction::Update(id)));
+            }
+        }
+        _ => {}
+    }
+
+    Ok(Command::none())
+}
+
 fn sort_addons(addons: &mut [Addon], sort_direction: SortDirection, column_key: ColumnKey) {
     match (column_key, sort_direction) {
         (ColumnKey::Title, SortDirection::Asc) => {
@@ -1623,6 +4513,60 @@ fn sort_addons(addons: &mut [Addon], sort_direction: SortDirection, column_key:
                     .reverse()
             });
         }
+        (ColumnKey::Source, SortDirection::Asc) => addons.sort_by(|a, b| {
+            a.active_repository
+                .map(|r| r.to_string())
+                .cmp(&b.active_repository.map(|r| r.to_string()))
+                .then_with(|| a.cmp(&b))
+        }),
+        (ColumnKey::Source, SortDirection::Desc) => addons.sort_by(|a, b| {
+            a.active_repository
+                .map(|r| r.to_string())
+                .cmp(&b.active_repository.map(|r| r.to_string()))
+                .reverse()
+                .then_with(|| a.cmp(&b))
+        }),
+        (ColumnKey::Size, SortDirection::Asc) => {
+            addons.sort_by(|a, b| a.size().cmp(&b.size()).then_with(|| a.cmp(&b)))
+        }
+        (ColumnKey::Size, SortDirection::Desc) => {
+            addons.sort_by(|a, b| a.size().cmp(&b.size()).reverse().then_with(|| a.cmp(&b)))
+        }
+        (ColumnKey::InstalledAt, SortDirection::Asc) => addons.sort_by(|a, b| {
+            a.install_dates
+                .installed_at
+                .cmp(&b.install_dates.installed_at)
+                .then_with(|| a.cmp(&b))
+        }),
+        (ColumnKey::InstalledAt, SortDirection::Desc) => addons.sort_by(|a, b| {
+            a.install_dates
+                .installed_at
+                .cmp(&b.install_dates.installed_at)
+                .reverse()
+                .then_with(|| a.cmp(&b))
+        }),
+        (ColumnKey::UpdatedAt, SortDirection::Asc) => addons.sort_by(|a, b| {
+            a.install_dates
+                .updated_at
+                .cmp(&b.install_dates.updated_at)
+                .then_with(|| a.cmp(&b))
+        }),
+        (ColumnKey::UpdatedAt, SortDirection::Desc) => addons.sort_by(|a, b| {
+            a.install_dates
+                .updated_at
+                .cmp(&b.install_dates.updated_at)
+                .reverse()
+                .then_with(|| a.cmp(&b))
+        }),
+        (ColumnKey::Interface, SortDirection::Asc) => {
+            addons.sort_by(|a, b| a.interface().cmp(&b.interface()).then_with(|| a.cmp(&b)))
+        }
+        (ColumnKey::Interface, SortDirection::Desc) => addons.sort_by(|a, b| {
+            a.interface()
+                .cmp(&b.interface())
+                .reverse()
+                .then_with(|| a.cmp(&b))
+        }),
     }
 }
 
@@ -1691,6 +4635,18 @@ fn query_and_sort_catalog(ajour: &mut Ajour) {
         let mut catalog_rows: Vec<_> = catalog
             .addons
             .iter()
+            // Shows the addon as it is on the chosen source, if it's on several.
+            .filter_map(|a| {
+                let entry = a.entries().find(|e| {
+                    e.flavors.iter().any(|f| *f == flavor.catalog_flavor())
+                        && match source {
+                            CatalogSource::All => true,
+                            CatalogSource::Choice(source) => e.source == *source,
+                        }
+                })?;
+
+                a.with_source(entry.source)
+            })
             .filter(|a| {
                 let cleaned_text =
                     format!("{} {}", a.name.to_lowercase(), a.summary.to_lowercase());
@@ -1701,16 +4657,10 @@ fn query_and_sort_catalog(ajour: &mut Ajour) {
                     true
                 }
             })
-            .filter(|a| a.flavors.iter().any(|f| *f == flavor.base_flavor()))
-            .filter(|a| match source {
-                CatalogSource::All => true,
-                CatalogSource::Choice(source) => a.source == *source,
-            })
             .filter(|a| match category {
                 CatalogCategory::All => true,
                 CatalogCategory::Choice(name) => a.categories.iter().any(|c| c == name),
             })
-            .cloned()
             .map(CatalogRow::from)
             .collect();
 
@@ -1725,10 +4675,21 @@ fn query_and_sort_catalog(ajour: &mut Ajour) {
 
         sort_catalog_addons(&mut catalog_rows, sort_direction, column_key);
 
+        ajour.catalog_search_state.total_results = catalog_rows.len();
+
+        // Make sure the page is still within bounds, since the amount of results
+        // can change when the flavor or catalog changes.
+        let last_page = ajour.catalog_search_state.total_pages() - 1;
+        if ajour.catalog_search_state.page > last_page {
+            ajour.catalog_search_state.page = last_page;
+        }
+
+        let page = ajour.catalog_search_state.page;
+
         catalog_rows = catalog_rows
             .into_iter()
-            .enumerate()
-            .filter_map(|(idx, row)| if idx < result_size { Some(row) } else { None })
+            .skip(page * result_size)
+            .take(result_size)
             .collect();
 
         ajour.catalog_search_state.catalog_rows = catalog_rows;
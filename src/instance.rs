@@ -0,0 +1,194 @@
+use ajour_core::fs::config_dir;
+
+use async_std::io::BufReader;
+use async_std::net::{TcpListener, TcpStream};
+use async_std::prelude::*;
+use async_std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, Write};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+/// Reply of a running instance to a request, which tells it apart from anything
+/// else listening on the port.
+const ACK: &str = "ajour";
+
+/// How long to wait on a running instance before assuming there is none.
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Requests an instance of Ajour launched later hands over to the running one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Request {
+    /// Bring the window to the foreground.
+    Focus,
+    /// Update all addons, handed over by the `update` command.
+    UpdateAll,
+    /// Open an install link, handed over by the `open` command.
+    Open(String),
+}
+
+/// The running instance of the GUI, which listens for requests of instances
+/// launched after it.
+#[derive(Debug, Clone)]
+pub struct Instance(Arc<TcpListener>);
+
+/// Outcome of claiming the single instance.
+pub enum Claim {
+    Claimed(Instance),
+    /// Another instance is running, and was asked to come to the foreground.
+    Running,
+    /// Something other than Ajour uses the port, so instances can't be kept single.
+    Unavailable,
+}
+
+/// Claims the single instance of the GUI for the config directory in use.
+///
+/// The instance is claimed by listening on a local port derived from the config
+/// directory, which the OS releases again when Ajour exits or crashes.
+pub fn claim() -> Claim {
+    match std::net::TcpListener::bind(address()) {
+        Ok(listener) => Claim::Claimed(Instance(Arc::new(TcpListener::from(listener)))),
+        Err(_) if send(Request::Focus) => Claim::Running,
+        Err(e) => {
+            log::warn!(
+                "failed to claim the single instance, as {} is in use: {}",
+                address(),
+                e
+            );
+
+            Claim::Unavailable
+        }
+    }
+}
+
+/// Hands `request` over to the running instance of the GUI. Returns `true` if it
+/// was received.
+pub fn send(request: Request) -> bool {
+    let result = (|| -> std::io::Result<bool> {
+        let mut stream = std::net::TcpStream::connect_timeout(&address(), TIMEOUT)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+
+        let mut line = serde_json::to_string(&request)?;
+        line.push('\n');
+        stream.write_all(line.as_bytes())?;
+
+        let mut reply = String::new();
+        std::io::BufReader::new(stream).read_line(&mut reply)?;
+
+        Ok(reply.trim() == ACK)
+    })();
+
+    match result {
+        Ok(received) => {
+            if received {
+                log::debug!("handed {:?} over to the running instance", request);
+            }
+
+            received
+        }
+        Err(_) => false,
+    }
+}
+
+/// Brings the window of the running instance to the foreground. Only Windows lets
+/// other processes do this, elsewhere launching Ajour again focuses it already,
+/// eg. the app on macOS, or it's left to the window manager.
+pub fn focus_running_window() {
+    #[cfg(windows)]
+    unsafe {
+        use std::os::windows::ffi::OsStrExt;
+        use winapi::um::winuser::{FindWindowW, SetForegroundWindow, ShowWindow, SW_RESTORE};
+
+        let title: Vec<u16> = std::ffi::OsStr::new("Ajour")
+            .encode_wide()
+            .chain(Some(0))
+            .collect();
+        let window = FindWindowW(std::ptr::null(), title.as_ptr());
+
+        if !window.is_null() {
+            ShowWindow(window, SW_RESTORE);
+            SetForegroundWindow(window);
+        }
+    }
+}
+
+impl Instance {
+    /// Returns the requests of instances launched after this one.
+    pub fn requests(&self) -> iced::Subscription<Request> {
+        iced::Subscription::from_recipe(Requests(self.0.clone()))
+    }
+}
+
+/// Port the instance with the config directory in use listens on. Each config
+/// directory, eg. one set with `--data-directory`, has an instance of its own.
+fn address() -> SocketAddr {
+    // FNV-1a, as the port has to be the same for every build of Ajour.
+    let mut hash: u32 = 0x811c_9dc5;
+    for byte in config_dir().to_string_lossy().bytes() {
+        hash ^= byte as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+
+    // Somewhere in the dynamic port range.
+    let port = 49152 + (hash % 16000) as u16;
+
+    SocketAddr::from((Ipv4Addr::LOCALHOST, port))
+}
+
+struct Requests(Arc<TcpListener>);
+
+impl<H, I> iced_futures::subscription::Recipe<H, I> for Requests
+where
+    H: Hasher,
+{
+    type Output = Request;
+
+    fn hash(&self, state: &mut H) {
+        std::any::TypeId::of::<Self>().hash(state);
+    }
+
+    fn stream(
+        self: Box<Self>,
+        _input: futures::stream::BoxStream<'static, I>,
+    ) -> futures::stream::BoxStream<'static, Self::Output> {
+        Box::pin(futures::stream::unfold(self.0, |listener| async move {
+            loop {
+                let stream = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        log::error!("failed to accept a request of another instance: {}", e);
+                        continue;
+                    }
+                };
+
+                if let Some(request) = receive(stream).await {
+                    return Some((request, listener));
+                }
+            }
+        }))
+    }
+}
+
+/// Reads a request from another instance, and acknowledges it.
+async fn receive(mut stream: TcpStream) -> Option<Request> {
+    let mut line = String::new();
+    {
+        let mut reader = BufReader::new(&stream);
+
+        // Don't hang on a connection which never sends a whole line.
+        async_std::future::timeout(TIMEOUT, reader.read_line(&mut line))
+            .await
+            .ok()?
+            .ok()?;
+    }
+
+    let request = serde_json::from_str(line.trim()).ok()?;
+    stream
+        .write_all(format!("{}\n", ACK).as_bytes())
+        .await
+        .ok()?;
+
+    Some(request)
+}
@@ -4,16 +4,27 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod cli;
+mod command;
 mod gui;
-mod update;
+mod instance;
+mod notification;
+mod tray;
 
+use ajour_core::config::{set_profile, Config, LogLevel, Logging};
 use ajour_core::error::ClientError;
-use ajour_core::fs::CONFIG_DIR;
+use ajour_core::fs::{has_portable_marker, portable_dir, PersistentData, CONFIG_DIR};
+use ajour_core::localization::{self, localized_string_with, Language};
+use ajour_core::logs::{log_file, RotatingLog};
+use ajour_core::self_update;
 use ajour_core::Result;
 
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub fn main() {
+    // The config isn't found until the arguments are parsed, so their help is in
+    // the language of the system.
+    localization::set_language(Language::system());
+
     let opts_result = cli::get_opts();
 
     #[cfg(debug_assertions)]
@@ -25,11 +36,13 @@ pub fn main() {
     // an error message anyway and this value won't matter. If it's not an error,
     // the underlying `command` will drive this variable. If a `command` is passed
     // on the command line, Ajour functions as a CLI instead of launching the GUI.
+    // Links are opened in the GUI.
     let is_cli = opts_result
         .as_ref()
         .map(|o| &o.command)
         .unwrap_or(&None)
-        .is_some();
+        .as_ref()
+        .map_or(false, |c| !matches!(c, cli::Command::Open { .. }));
 
     // This function validates whether or not we need to exit and print any message
     // due to arguments passed on the command line. If not, it will return a
@@ -37,41 +50,252 @@ pub fn main() {
     // fix that allows us to print to the console when not using the GUI.
     let opts = cli::validate_opts_or_exit(opts_result, is_cli, is_debug);
 
-    setup_logger(is_cli, is_debug).expect("setup logging");
+    // Results are printed to stdout in json, so logging has to go elsewhere.
+    let is_json = is_cli && opts.output == cli::OutputFormat::Json;
+
+    // The terminal UI takes the terminal, so it logs to the log file like the GUI.
+    let is_tui = matches!(opts.command, Some(cli::Command::Tui));
+
+    // Completions and the man page are printed to stdout as well.
+    let log_to_stderr = is_json
+        || matches!(
+            opts.command,
+            Some(cli::Command::Completions { .. }) | Some(cli::Command::Manpage)
+        );
+
+    // The log is written to the data directory, so it's set first.
+    let portable_dir = if opts.portable || has_portable_marker() {
+        portable_dir()
+    } else {
+        None
+    };
+
+    if let Some(data_dir) = opts.data_directory.clone().or(portable_dir) {
+        if !data_dir.exists() {
+            std::fs::create_dir_all(&data_dir).expect("could not create data directory");
+        }
 
-    if let Some(data_dir) = &opts.data_directory {
         let mut config_dir = CONFIG_DIR.lock().unwrap();
 
-        *config_dir = data_dir.clone();
+        *config_dir = data_dir;
     }
 
+    let (logging, language) = <Config as PersistentData>::load()
+        .map(|config| (config.logging, config.language))
+        .unwrap_or_default();
+
+    if let Some(language) = language {
+        localization::set_language(language);
+    }
+
+    // Only errors are logged in quiet mode. Progress isn't drawn either, but the
+    // results a command was asked for are always printed.
+    let log_level = if opts.quiet {
+        Some(LogLevel::Error)
+    } else {
+        opts.log_level
+    };
+
+    setup_logger(
+        is_cli && !is_tui,
+        is_debug,
+        log_to_stderr,
+        log_level,
+        &logging,
+    )
+    .expect("setup logging");
+
     log_panics::init();
 
-    log::info!("Ajour {} has started.", VERSION);
+    set_profile(opts.profile.clone());
+
+    log::info!(
+        "{}",
+        localized_string_with("ajour-started", &[("version", &VERSION)])
+    );
+
+    for path in self_update::remove_self_update_leftovers() {
+        log::info!(
+            "{}",
+            localized_string_with("removed-update-leftover", &[("path", &path.display())])
+        );
+    }
 
     match opts.command {
+        Some(cli::Command::Open { ref link }) => {
+            let link = link.clone();
+
+            // Start the GUI, or hand the link over to the one running
+            gui::run(opts, Some(link));
+        }
         Some(command) => {
+            let output = opts.output;
+
             // Process the command and exit
             if let Err(e) = match command {
-                cli::Command::Update => update::update_all_addons(),
+                cli::Command::Backup {
+                    action:
+                        cli::BackupAction::Restore {
+                            backup,
+                            flavor,
+                            include,
+                            yes,
+                        },
+                } => command::restore_backup(backup, flavor, include, yes, output),
+                cli::Command::Backup {
+                    action: cli::BackupAction::List,
+                } => command::list_backups(output),
+                cli::Command::Backup {
+                    action:
+                        cli::BackupAction::Create {
+                            destination,
+                            flavors,
+                            include,
+                        },
+                } => command::backup(destination, flavors, include, output),
+                cli::Command::Check { notify } => command::check(notify, output),
+                cli::Command::Update {
+                    addon: Some(addon),
+                    flavor,
+                    wait,
+                    dry_run,
+                    ..
+                } => command::update_addon_by_name(addon, flavor, wait, dry_run, output),
+                cli::Command::Update {
+                    concurrency,
+                    wait,
+                    dry_run,
+                    ..
+                } => command::update_all_addons(concurrency, wait, dry_run, output),
+                cli::Command::Daemon {
+                    interval,
+                    concurrency,
+                    notify,
+                } => command::daemon(interval, concurrency, notify, output),
+                cli::Command::Doctor {
+                    bundle,
+                    destination,
+                } => command::doctor(bundle, destination, output),
+                cli::Command::Export { file } => command::export_addons(file, output),
+                cli::Command::Import {
+                    file, wowup: true, ..
+                } => command::import_wowup(file, output),
+                cli::Command::Import {
+                    file,
+                    curseforge: true,
+                    ..
+                } => {
+                    // Clap makes sure `file` is passed if `wowup` isn't
+                    command::import_curseforge(file.unwrap_or_default(), output)
+                }
+                cli::Command::Import { file, .. } => {
+                    // Clap makes sure `file` is passed if `wowup` isn't
+                    command::import_addons(file.unwrap_or_default(), output)
+                }
+                cli::Command::Install {
+                    url: Some(url),
+                    flavor,
+                    ..
+                } => command::install_from_url(url, flavor, output),
+                cli::Command::Install {
+                    addon,
+                    flavor,
+                    no_deps,
+                    ..
+                } => {
+                    // Clap makes sure `addon` is passed if `url` isn't
+                    command::install_from_catalog(
+                        addon.unwrap_or_default(),
+                        flavor,
+                        no_deps,
+                        output,
+                    )
+                }
+                cli::Command::List {
+                    flavors,
+                    outdated,
+                    incompatible,
+                    abandoned,
+                } => command::list_addons(flavors, outdated, incompatible, abandoned, output),
+                cli::Command::Pin { addon, flavor } => command::pin(addon, flavor, output),
+                cli::Command::Unpin { addon, flavor } => command::unpin(addon, flavor, output),
+                cli::Command::Rollback { addon, flavor } => {
+                    command::rollback(addon, flavor, output)
+                }
+                cli::Command::Verify { flavors, repair } => {
+                    command::verify(flavors, repair, output)
+                }
+                cli::Command::Links {
+                    action: cli::LinksAction::Register { curseforge },
+                } => command::register_links(curseforge, output),
+                cli::Command::Links {
+                    action: cli::LinksAction::Unregister,
+                } => command::unregister_links(output),
+                cli::Command::Sync { action } => command::sync(action, output),
+                cli::Command::Tui => command::tui(),
+                cli::Command::Completions { shell } => command::completions(shell),
+                cli::Command::Manpage => command::manpage(),
+                cli::Command::Config {
+                    action: cli::ConfigAction::Get { key },
+                } => command::get_config_option(key, output),
+                cli::Command::Config {
+                    action: cli::ConfigAction::Set { key, value },
+                } => command::set_config_option(key, value, output),
+                cli::Command::Config {
+                    action: cli::ConfigAction::List,
+                } => command::list_config_options(output),
+                cli::Command::Path {
+                    action: cli::PathAction::Get,
+                } => command::get_path(output),
+                cli::Command::Path {
+                    action: cli::PathAction::Set { directory },
+                } => command::set_path(directory, output),
+                cli::Command::Path {
+                    action: cli::PathAction::Detect { set },
+                } => command::detect_path(set, output),
+                cli::Command::Open { .. } => unreachable!("links are opened in the GUI"),
             } {
                 log_error(&e);
+
+                if is_json {
+                    let _ =
+                        command::print_json(output, &serde_json::json!({ "error": e.to_string() }));
+                }
+
+                command::set_exit_code(command::ExitCode::from_error(&e));
+            }
+
+            let code = command::exit_code();
+            if code != 0 {
+                std::process::exit(code);
             }
         }
         None => {
             // Start the GUI
-            gui::run(opts);
+            gui::run(opts, None);
         }
     }
 }
 
 /// Log any errors
 pub fn log_error(e: &ClientError) {
-    log::error!("{}", e);
+    log::error!("{}", e.user_message());
 }
 
+/// Sets up logging to the terminal for commands and while debugging, and to a
+/// rotated log in the data directory otherwise.
+///
+/// `level` is taken over the one of the config, and applies to both Ajour and
+/// its core. Without either, everything Ajour logs is kept, and everything the
+/// core logs unless running a command.
 #[allow(clippy::unnecessary_operation)]
-fn setup_logger(is_cli: bool, is_debug: bool) -> Result<()> {
+fn setup_logger(
+    is_cli: bool,
+    is_debug: bool,
+    log_to_stderr: bool,
+    level: Option<LogLevel>,
+    logging: &Logging,
+) -> Result<()> {
     let mut logger = fern::Dispatch::new()
         .format(|out, message, record| {
             out.finish(format_args!(
@@ -83,30 +307,50 @@ fn setup_logger(is_cli: bool, is_debug: bool) -> Result<()> {
             ))
         })
         .level(log::LevelFilter::Off)
-        .level_for("panic", log::LevelFilter::Error)
-        .level_for("ajour", log::LevelFilter::Trace);
+        .level_for("panic", log::LevelFilter::Error);
+
+    // The level chosen in the settings is the one of the log file, the output of
+    // commands only follows the level passed to them.
+    let level = if is_cli {
+        level
+    } else {
+        level.or(logging.level)
+    };
+
+    match level {
+        Some(level) => {
+            logger = logger
+                .level_for("ajour", level.level_filter())
+                .level_for("ajour_core", level.level_filter());
+        }
+        None => {
+            logger = logger.level_for("ajour", log::LevelFilter::Trace);
 
-    if !is_cli {
-        logger = logger.level_for("ajour_core", log::LevelFilter::Trace);
+            if !is_cli {
+                logger = logger.level_for("ajour_core", log::LevelFilter::Trace);
+            }
+        }
     }
 
-    if is_cli || is_debug {
+    if log_to_stderr {
+        logger = logger.chain(std::io::stderr());
+    } else if is_cli {
+        // Lines are printed above the progress of updates, if it's drawn.
+        logger = logger.chain(fern::Output::call(|record| {
+            command::print_line(&record.args().to_string())
+        }));
+    } else if is_debug {
         logger = logger.chain(std::io::stdout());
     }
 
     if !is_cli && !is_debug {
-        use std::fs::OpenOptions;
-
-        let config_dir = ajour_core::fs::config_dir();
-
-        let log_file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .append(false)
-            .truncate(true)
-            .open(config_dir.join("ajour.log"))?;
+        let log_file = RotatingLog::open(
+            log_file(),
+            logging.keep_count.unwrap_or(Logging::DEFAULT_KEEP_COUNT),
+            logging.max_size_mb.unwrap_or(Logging::DEFAULT_MAX_SIZE_MB) * 1024 * 1024,
+        )?;
 
-        logger = logger.chain(log_file);
+        logger = logger.chain(Box::new(log_file) as Box<dyn std::io::Write + Send>);
     };
 
     logger.apply()?;
@@ -0,0 +1,124 @@
+use ajour_core::addon::Addon;
+use ajour_core::config::Flavor;
+use ajour_core::localization::{localized_string, localized_string_with};
+use ajour_core::update::UpdateReport;
+
+/// Amount of addons listed in the notification body.
+const MAX_LISTED_ADDONS: usize = 5;
+
+/// Shows a desktop notification about addons with a new version available.
+///
+/// If `open_on_click` is set, clicking the notification launches Ajour and this blocks
+/// until the notification is closed. Click actions are only supported on Linux and BSD.
+pub fn notify_updates(updates: &[(Flavor, Addon)], open_on_click: bool) {
+    if updates.is_empty() {
+        return;
+    }
+
+    let summary = if updates.len() == 1 {
+        localized_string("addon-update-available")
+    } else {
+        localized_string_with("addon-updates-available", &[("count", &updates.len())])
+    };
+
+    let mut lines = updates
+        .iter()
+        .take(MAX_LISTED_ADDONS)
+        .map(|(flavor, addon)| {
+            let version = addon
+                .relevant_release_package()
+                .map(|p| p.version.clone())
+                .unwrap_or_default();
+
+            format!("{} {} ({})", addon.title(), version, flavor)
+        })
+        .collect::<Vec<_>>();
+
+    if updates.len() > MAX_LISTED_ADDONS {
+        lines.push(localized_string_with(
+            "and-more",
+            &[("count", &(updates.len() - MAX_LISTED_ADDONS))],
+        ));
+    }
+
+    let mut notification = notify_rust::Notification::new();
+    notification
+        .appname("Ajour")
+        .summary(&summary)
+        .body(&lines.join("\n"));
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        if open_on_click {
+            notification.action("default", &localized_string("open-ajour"));
+        }
+
+        match notification.show() {
+            Ok(handle) if open_on_click => handle.wait_for_action(|action| {
+                if action == "default" {
+                    open_ajour();
+                }
+            }),
+            Ok(_) => {}
+            Err(e) => log::error!("failed to show notification: {}", e),
+        }
+    }
+
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    {
+        let _ = open_on_click;
+
+        if let Err(e) = notification.show() {
+            log::error!("failed to show notification: {}", e);
+        }
+    }
+}
+
+/// Shows a desktop notification about the addons updated in the background, and
+/// the ones which failed.
+pub fn notify_update_report(report: &UpdateReport) {
+    if report.updated.is_empty() && report.failed.is_empty() {
+        return;
+    }
+
+    let mut lines = report
+        .updated
+        .iter()
+        .take(MAX_LISTED_ADDONS)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if report.updated.len() > MAX_LISTED_ADDONS {
+        lines.push(localized_string_with(
+            "and-more",
+            &[("count", &(report.updated.len() - MAX_LISTED_ADDONS))],
+        ));
+    }
+
+    for (title, _) in report.failed.iter().take(MAX_LISTED_ADDONS) {
+        lines.push(localized_string_with(
+            "addon-failed-short",
+            &[("title", title)],
+        ));
+    }
+
+    let result = notify_rust::Notification::new()
+        .appname("Ajour")
+        .summary(&report.summary())
+        .body(&lines.join("\n"))
+        .show();
+
+    if let Err(e) = result {
+        log::error!("failed to show notification: {}", e);
+    }
+}
+
+/// Launches the Ajour GUI as a new process.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn open_ajour() {
+    let result = std::env::current_exe().and_then(|exe| std::process::Command::new(exe).spawn());
+
+    if let Err(e) = result {
+        log::error!("failed to open Ajour: {}", e);
+    }
+}
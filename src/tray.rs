@@ -0,0 +1,234 @@
+use ajour_core::localization::localized_string;
+
+use futures::channel::mpsc::UnboundedReceiver;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+/// Actions in the menu of the tray icon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Bring the window back, after it was closed to the tray.
+    Show,
+    CheckForUpdates,
+    UpdateAll,
+    Quit,
+}
+
+impl Action {
+    /// Actions in the order they're listed in the menu. The window can only be
+    /// hidden to the tray on Windows, so it's only shown again there.
+    #[cfg_attr(not(feature = "tray"), allow(dead_code))]
+    fn menu() -> Vec<Action> {
+        let mut actions = vec![Action::CheckForUpdates, Action::UpdateAll, Action::Quit];
+
+        if cfg!(windows) {
+            actions.insert(0, Action::Show);
+        }
+
+        actions
+    }
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Action::Show => localized_string("tray-show"),
+            Action::CheckForUpdates => localized_string("tray-check-for-updates"),
+            Action::UpdateAll => localized_string("tray-update-all"),
+            Action::Quit => localized_string("tray-quit"),
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// The icon of Ajour in the system tray, which is removed once this is dropped.
+///
+/// The icon lives on a thread of its own, and hands the actions chosen in its
+/// menu over to the GUI.
+#[derive(Debug, Clone)]
+pub struct Tray(Arc<Mutex<Option<UnboundedReceiver<Action>>>>);
+
+/// Whether this build of Ajour can show a tray icon.
+pub fn is_supported() -> bool {
+    cfg!(all(feature = "tray", any(windows, target_os = "linux")))
+}
+
+/// Shows the tray icon, returning `None` if it isn't supported or failed to show.
+#[cfg(all(feature = "tray", any(windows, target_os = "linux")))]
+pub fn start() -> Option<Tray> {
+    use futures::channel::mpsc::{unbounded, UnboundedSender};
+    use std::time::Duration;
+    use tray_item::{IconSource, TrayItem};
+
+    fn build(sender: UnboundedSender<Action>) -> Result<TrayItem, tray_item::TIError> {
+        let mut tray = TrayItem::new("Ajour", IconSource::Resource("ajour"))?;
+
+        for action in Action::menu() {
+            // Menu callbacks can be called from any thread.
+            let sender = Mutex::new(sender.clone());
+            tray.add_menu_item(&action.to_string(), move || {
+                let _ = sender.lock().unwrap().unbounded_send(action);
+            })?;
+        }
+
+        Ok(tray)
+    }
+
+    let (sender, receiver) = unbounded();
+    let (started_sender, started_receiver) = std::sync::mpsc::channel();
+
+    let spawned = std::thread::Builder::new()
+        .name("tray".to_string())
+        .spawn(move || {
+            let tray = match build(sender.clone()) {
+                Ok(tray) => tray,
+                Err(e) => {
+                    log::error!("failed to show the tray icon: {:?}", e);
+                    let _ = started_sender.send(false);
+                    return;
+                }
+            };
+            let _ = started_sender.send(true);
+
+            // Keeps the icon until the GUI drops its end of the channel.
+            while !sender.is_closed() {
+                std::thread::sleep(Duration::from_millis(500));
+            }
+
+            drop(tray);
+        });
+
+    if let Err(e) = spawned {
+        log::error!("failed to start the tray thread: {}", e);
+        return None;
+    }
+
+    match started_receiver.recv() {
+        Ok(true) => Some(Tray(Arc::new(Mutex::new(Some(receiver))))),
+        _ => None,
+    }
+}
+
+/// Shows the tray icon, returning `None` if it isn't supported or failed to show.
+#[cfg(not(all(feature = "tray", any(windows, target_os = "linux"))))]
+pub fn start() -> Option<Tray> {
+    None
+}
+
+impl Tray {
+    /// Returns the actions chosen in the menu of the icon.
+    pub fn actions(&self) -> iced::Subscription<Action> {
+        iced::Subscription::from_recipe(Actions(self.0.clone()))
+    }
+}
+
+struct Actions(Arc<Mutex<Option<UnboundedReceiver<Action>>>>);
+
+impl<H, I> iced_futures::subscription::Recipe<H, I> for Actions
+where
+    H: Hasher,
+{
+    type Output = Action;
+
+    fn hash(&self, state: &mut H) {
+        std::any::TypeId::of::<Self>().hash(state);
+    }
+
+    fn stream(
+        self: Box<Self>,
+        _input: futures::stream::BoxStream<'static, I>,
+    ) -> futures::stream::BoxStream<'static, Self::Output> {
+        // The actions are only received once, by the first subscription.
+        match self.0.lock().unwrap().take() {
+            Some(receiver) => Box::pin(receiver),
+            None => Box::pin(futures::stream::empty()),
+        }
+    }
+}
+
+/// Sets whether closing the window hides it to the tray, instead of quitting.
+/// Only supported on Windows, and called from the thread of the window.
+pub fn set_close_to_tray(enabled: bool) {
+    #[cfg(windows)]
+    window::set_close_to_tray(enabled);
+
+    #[cfg(not(windows))]
+    let _ = enabled;
+}
+
+/// Quits Ajour, even if closing the window hides it to the tray.
+pub fn quit() {
+    #[cfg(windows)]
+    {
+        if window::close() {
+            return;
+        }
+    }
+
+    std::process::exit(0);
+}
+
+#[cfg(windows)]
+mod window {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use winapi::shared::basetsd::{DWORD_PTR, UINT_PTR};
+    use winapi::shared::minwindef::{LPARAM, LRESULT, UINT, WPARAM};
+    use winapi::shared::windef::HWND;
+    use winapi::um::commctrl::{DefSubclassProc, SetWindowSubclass};
+    use winapi::um::winuser::{FindWindowW, PostMessageW, ShowWindow, SW_HIDE, WM_CLOSE};
+
+    static CLOSE_TO_TRAY: AtomicBool = AtomicBool::new(false);
+    static SUBCLASSED: AtomicBool = AtomicBool::new(false);
+
+    fn find() -> HWND {
+        use std::os::windows::ffi::OsStrExt;
+
+        let title: Vec<u16> = std::ffi::OsStr::new("Ajour")
+            .encode_wide()
+            .chain(Some(0))
+            .collect();
+
+        unsafe { FindWindowW(std::ptr::null(), title.as_ptr()) }
+    }
+
+    /// The window is subclassed the first time this is enabled, so its close
+    /// requests reach `close_to_tray` before the GUI, which would quit.
+    pub fn set_close_to_tray(enabled: bool) {
+        CLOSE_TO_TRAY.store(enabled, Ordering::SeqCst);
+
+        if enabled && !SUBCLASSED.swap(true, Ordering::SeqCst) {
+            let window = find();
+
+            if window.is_null()
+                || unsafe { SetWindowSubclass(window, Some(close_to_tray), 1, 0) } == 0
+            {
+                log::error!("failed to let the window close to the tray");
+                SUBCLASSED.store(false, Ordering::SeqCst);
+            }
+        }
+    }
+
+    unsafe extern "system" fn close_to_tray(
+        window: HWND,
+        message: UINT,
+        wparam: WPARAM,
+        lparam: LPARAM,
+        _id: UINT_PTR,
+        _data: DWORD_PTR,
+    ) -> LRESULT {
+        if message == WM_CLOSE && CLOSE_TO_TRAY.load(Ordering::SeqCst) {
+            ShowWindow(window, SW_HIDE);
+            return 0;
+        }
+
+        DefSubclassProc(window, message, wparam, lparam)
+    }
+
+    /// Closes the window, which quits the GUI. Returns `false` if it wasn't found.
+    pub fn close() -> bool {
+        CLOSE_TO_TRAY.store(false, Ordering::SeqCst);
+
+        let window = find();
+        !window.is_null() && unsafe { PostMessageW(window, WM_CLOSE, 0, 0) } != 0
+    }
+}